@@ -99,7 +99,11 @@ impl AiService {
                  // For now, let's just use the ID and time.
                  #[allow(clippy::cast_precision_loss)]
                  let hours = stat.total_seconds as f32 / 3600.0;
-                 let _ = writeln!(context, "{}. {} took {:.1} hours", i + 1, stat.issue_id, hours);
+                 let _ = write!(context, "{}. {} took {:.1} hours", i + 1, stat.issue_id, hours);
+                 if stat.days_worked > 1 {
+                     let _ = write!(context, " over {} days", stat.days_worked);
+                 }
+                 context.push('\n');
             }
             context.push('\n');
         }
@@ -64,6 +64,137 @@ pub(crate) fn truncate(s: &str, max: usize) -> String {
     }
 }
 
+/// Default similarity a fuzzy name match must exceed to be suggested
+pub const DEFAULT_NAME_MATCH_THRESHOLD: f32 = 0.6;
+
+/// Algorithm used to compare local and PM project names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NameMatchAlgorithm {
+    /// Jaccard similarity of the character sets (ignores character order)
+    #[default]
+    Jaccard,
+    /// Levenshtein edit distance normalized by the longer name
+    Levenshtein,
+    /// Jaccard similarity of word tokens split on non-alphanumerics
+    TokenSet,
+}
+
+impl NameMatchAlgorithm {
+    /// Similarity of two names in `0.0..=1.0`
+    #[must_use]
+    pub fn similarity(self, a: &str, b: &str) -> f32 {
+        match self {
+            Self::Jaccard => AutoLinker::calculate_name_similarity(a, b),
+            Self::Levenshtein => levenshtein_ratio(a, b),
+            Self::TokenSet => token_set_similarity(a, b),
+        }
+    }
+}
+
+impl std::str::FromStr for NameMatchAlgorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "jaccard" => Ok(Self::Jaccard),
+            "levenshtein" => Ok(Self::Levenshtein),
+            "token-set" | "token_set" | "tokenset" => Ok(Self::TokenSet),
+            _ => anyhow::bail!(
+                "Unknown name match algorithm: {s}. Use 'jaccard', 'levenshtein', or 'token-set'"
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for NameMatchAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Jaccard => write!(f, "jaccard"),
+            Self::Levenshtein => write!(f, "levenshtein"),
+            Self::TokenSet => write!(f, "token-set"),
+        }
+    }
+}
+
+/// Fuzzy project-name matching settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NameMatching {
+    pub algorithm: NameMatchAlgorithm,
+    /// Similarity a match must exceed to be suggested
+    pub threshold: f32,
+}
+
+impl Default for NameMatching {
+    fn default() -> Self {
+        Self::new(NameMatchAlgorithm::default(), DEFAULT_NAME_MATCH_THRESHOLD)
+    }
+}
+
+impl NameMatching {
+    #[must_use]
+    pub fn new(algorithm: NameMatchAlgorithm, threshold: f32) -> Self {
+        Self {
+            algorithm,
+            threshold,
+        }
+    }
+
+    /// Similarity of two names if it exceeds the threshold
+    #[must_use]
+    pub fn score(&self, a: &str, b: &str) -> Option<f32> {
+        let similarity = self.algorithm.similarity(a, b);
+        (similarity > self.threshold).then_some(similarity)
+    }
+}
+
+/// Levenshtein distance normalized to a similarity ratio
+fn levenshtein_ratio(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    // Project names are short, so precision loss is not a practical concern
+    #[allow(clippy::cast_precision_loss)]
+    let ratio = 1.0 - previous[b.len()] as f32 / longest as f32;
+    ratio
+}
+
+/// Jaccard similarity over alphanumeric word tokens
+fn token_set_similarity(a: &str, b: &str) -> f32 {
+    let tokens = |s: &str| -> std::collections::HashSet<String> {
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|t| !t.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let a_tokens = tokens(a);
+    let b_tokens = tokens(b);
+
+    let union = a_tokens.union(&b_tokens).count();
+    if union == 0 {
+        return if a == b { 1.0 } else { 0.0 };
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let result = a_tokens.intersection(&b_tokens).count() as f32 / union as f32;
+    result
+}
+
 /// Service for automatically linking projects to PM systems
 pub struct AutoLinker {
     database: Arc<Database>,
@@ -71,6 +202,8 @@ pub struct AutoLinker {
     plane_url_pattern: Regex,
     /// Regex to extract issue ID from URLs
     issue_id_pattern: Regex,
+    /// Algorithm and threshold for fuzzy project name matching
+    name_matching: NameMatching,
 }
 
 impl AutoLinker {
@@ -90,9 +223,17 @@ impl AutoLinker {
             ).unwrap(),
             // Matches: PROJ-123 style issue IDs
             issue_id_pattern: Regex::new(r"([A-Z]{2,10})-(\d+)").unwrap(),
+            name_matching: NameMatching::default(),
         }
     }
 
+    /// Set the algorithm and threshold used for fuzzy name matching
+    #[must_use]
+    pub fn with_name_matching(mut self, name_matching: NameMatching) -> Self {
+        self.name_matching = name_matching;
+        self
+    }
+
     /// Analyze browser URLs to suggest project links
     ///
     /// When a user visits a Plane.so issue page, we can infer which PM project
@@ -225,9 +366,9 @@ impl AutoLinker {
                 let pm_name_lower = pm.name.to_lowercase();
                 let pm_id_lower = pm.identifier.to_lowercase();
 
-                let similarity = Self::calculate_name_similarity(&local_name_lower, &pm_name_lower);
-
-                if similarity > 0.6 {
+                if let Some(similarity) =
+                    self.name_matching.score(&local_name_lower, &pm_name_lower)
+                {
                     suggestions.push(LinkSuggestion {
                         local_project_id: local.id,
                         local_project_name: local.name.clone(),
@@ -388,26 +529,7 @@ impl AutoLinker {
 
     /// Calculate similarity between two project names
     pub(crate) fn calculate_name_similarity(a: &str, b: &str) -> f32 {
-        if a == b {
-            return 1.0;
-        }
-
-        // Simple Jaccard similarity on character n-grams
-        let a_grams: std::collections::HashSet<_> = a.chars().collect();
-        let b_grams: std::collections::HashSet<_> = b.chars().collect();
-
-        let intersection = a_grams.intersection(&b_grams).count();
-        let union = a_grams.union(&b_grams).count();
-
-        if union == 0 {
-            0.0
-        } else {
-            // For project names (typically < 100 chars), these counts are small enough
-            // that precision loss is not a practical concern
-            #[allow(clippy::cast_precision_loss)]
-            let result = intersection as f32 / union as f32;
-            result
-        }
+        toki_storage::db::name_similarity(a, b)
     }
 
     /// Extract remote URL from git config content
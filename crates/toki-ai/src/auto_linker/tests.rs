@@ -448,3 +448,50 @@ fn test_config_to_project_name() {
     let project_name = AutoLinker::extract_project_from_git_url(&remote_url).unwrap();
     assert_eq!(project_name, "awesome-app");
 }
+
+// ============================================================================
+// NameMatching tests
+// ============================================================================
+
+#[test]
+fn test_levenshtein_distinguishes_anagrams_where_jaccard_cannot() {
+    let jaccard = NameMatchAlgorithm::Jaccard.similarity("api", "pia");
+    let levenshtein = NameMatchAlgorithm::Levenshtein.similarity("api", "pia");
+    assert!((jaccard - 1.0).abs() < f32::EPSILON);
+    assert!(levenshtein < 0.5);
+}
+
+#[test]
+fn test_levenshtein_ratio_one_edit() {
+    let result = NameMatchAlgorithm::Levenshtein.similarity("hygieia", "hygeia");
+    assert!((result - 6.0 / 7.0).abs() < 0.01);
+}
+
+#[test]
+fn test_token_set_ignores_word_order() {
+    let result = NameMatchAlgorithm::TokenSet.similarity("billing-api", "api_billing");
+    assert!((result - 1.0).abs() < f32::EPSILON);
+    assert!(NameMatchAlgorithm::TokenSet.similarity("billing-api", "billing-web") < 0.5);
+}
+
+#[test]
+fn test_name_matching_threshold_gates_suggestions() {
+    let lenient = NameMatching::new(NameMatchAlgorithm::Levenshtein, 0.5);
+    let strict = NameMatching::new(NameMatchAlgorithm::Levenshtein, 0.9);
+    assert!(lenient.score("hygieia", "hygeia").is_some());
+    assert!(strict.score("hygieia", "hygeia").is_none());
+}
+
+#[test]
+fn test_name_match_algorithm_from_str() {
+    assert_eq!(
+        "levenshtein".parse::<NameMatchAlgorithm>().unwrap(),
+        NameMatchAlgorithm::Levenshtein
+    );
+    assert_eq!(
+        "Token-Set".parse::<NameMatchAlgorithm>().unwrap(),
+        NameMatchAlgorithm::TokenSet
+    );
+    assert!("soundex".parse::<NameMatchAlgorithm>().is_err());
+    assert_eq!(NameMatching::default().algorithm, NameMatchAlgorithm::Jaccard);
+}
@@ -1,6 +1,44 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
 use anyhow::{Result, Context};
 use fastembed::{TextEmbedding, InitOptions, EmbeddingModel};
 
+/// Texts embedded per model call by [`EmbeddingService::generate_embeddings`]
+pub const EMBEDDING_BATCH_SIZE: usize = 32;
+
+/// The process-wide service handed out by [`EmbeddingService::shared`]
+static SHARED_SERVICE: SharedSlot<EmbeddingService> = SharedSlot::new();
+
+/// Lazily created value shared by every caller in the process
+///
+/// Unlike `OnceLock`, a failed initialization is not remembered, so a later
+/// caller can try again (e.g. once the model download succeeds).
+struct SharedSlot<T> {
+    value: Mutex<Option<Arc<Mutex<T>>>>,
+}
+
+impl<T> SharedSlot<T> {
+    const fn new() -> Self {
+        Self {
+            value: Mutex::new(None),
+        }
+    }
+
+    /// Return the shared value, creating it with `init` on first use
+    ///
+    /// The slot stays locked while `init` runs, so concurrent callers wait for
+    /// the first one instead of creating a second value.
+    fn get_or_try_init(&self, init: impl FnOnce() -> Result<T>) -> Result<Arc<Mutex<T>>> {
+        let mut value = self.value.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(shared) = value.as_ref() {
+            return Ok(Arc::clone(shared));
+        }
+        let shared = Arc::new(Mutex::new(init()?));
+        *value = Some(Arc::clone(&shared));
+        Ok(shared)
+    }
+}
+
 /// Service for generating text embeddings and calculating similarity
 pub struct EmbeddingService {
     model: TextEmbedding,
@@ -23,6 +61,21 @@ impl EmbeddingService {
         Ok(Self { model })
     }
 
+    /// The embedding service shared across the process, loading the model on first use
+    ///
+    /// Matchers, issue sync, and gravity all use this instance, so the model is
+    /// only held in memory once. `enabled` is the `ai.embeddings` setting.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if embeddings are disabled or the model fails to load.
+    pub fn shared(enabled: bool) -> Result<Arc<Mutex<Self>>> {
+        if !enabled {
+            anyhow::bail!("Embeddings are disabled (ai.embeddings = false)");
+        }
+        SHARED_SERVICE.get_or_try_init(Self::new)
+    }
+
     /// Generate embedding vector for a given text
     /// 
     /// # Errors
@@ -38,6 +91,26 @@ impl EmbeddingService {
             .context("Failed to generate embedding")
     }
 
+    /// Generate embeddings for several texts in batches
+    ///
+    /// The result has one vector per text, in the same order as `texts`.
+    ///
+    /// # Errors
+    /// Returns error if model inference fails
+    pub fn generate_embeddings(&mut self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let embeddings = self.model.embed(texts, Some(EMBEDDING_BATCH_SIZE))?;
+        anyhow::ensure!(
+            embeddings.len() == texts.len(),
+            "Expected {} embeddings, got {}",
+            texts.len(),
+            embeddings.len()
+        );
+        Ok(embeddings)
+    }
+
     /// Calculate Cosine Similarity between two vectors
     /// Returns a score between -1.0 and 1.0 (usually 0.0-1.0 for text)
     #[must_use] pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
@@ -70,4 +143,63 @@ mod tests {
         assert!((EmbeddingService::cosine_similarity(&v1, &v2) - 1.0).abs() < f32::EPSILON);
         assert!((EmbeddingService::cosine_similarity(&v1, &v3)).abs() < f32::EPSILON);
     }
+
+    #[test]
+    fn test_shared_slot_returns_same_instance() {
+        let slot: SharedSlot<Vec<f32>> = SharedSlot::new();
+        let mut inits = 0;
+
+        let first = slot
+            .get_or_try_init(|| {
+                inits += 1;
+                Ok(vec![1.0])
+            })
+            .unwrap();
+        let second = slot
+            .get_or_try_init(|| {
+                inits += 1;
+                Ok(vec![2.0])
+            })
+            .unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(inits, 1);
+        assert_eq!(*second.lock().unwrap(), vec![1.0]);
+    }
+
+    #[test]
+    fn test_shared_slot_retries_after_failed_init() {
+        let slot: SharedSlot<u32> = SharedSlot::new();
+
+        let failed = slot.get_or_try_init(|| anyhow::bail!("download failed"));
+        assert!(failed.is_err());
+        let value = slot.get_or_try_init(|| Ok(7)).unwrap();
+        assert_eq!(*value.lock().unwrap(), 7);
+    }
+
+    #[test]
+    #[ignore = "downloads the embedding model"]
+    fn test_batch_embeddings_match_single_embeddings() {
+        let mut service = EmbeddingService::new().unwrap();
+        let texts: Vec<String> = (0..EMBEDDING_BATCH_SIZE + 3)
+            .map(|i| format!("PROJ-{i} Fix login redirect loop on page {i}"))
+            .collect();
+
+        let batch = service.generate_embeddings(&texts).unwrap();
+        assert_eq!(batch.len(), texts.len());
+        for (text, embedding) in texts.iter().zip(&batch) {
+            let single = service.generate_embedding(text).unwrap();
+            assert_eq!(single.len(), embedding.len());
+            for (a, b) in single.iter().zip(embedding) {
+                assert!((a - b).abs() < 1e-4, "{text}: {a} != {b}");
+            }
+        }
+        assert!(service.generate_embeddings(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_shared_respects_disabled_embeddings() {
+        let error = EmbeddingService::shared(false).err().unwrap();
+        assert!(error.to_string().contains("disabled"));
+    }
 }
@@ -3,13 +3,27 @@ mod tests;
 
 use std::sync::{Arc, Mutex};
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use toki_storage::Database;
 use uuid::Uuid;
 use crate::embedding::EmbeddingService;
+use crate::standup::is_in_progress;
+
+/// Share of issue gravity carried by each signal (sums to 1.0)
+const RECENCY_WEIGHT: f32 = 0.4;
+const FREQUENCY_WEIGHT: f32 = 0.3;
+const ASSIGNMENT_WEIGHT: f32 = 0.15;
+const STATUS_WEIGHT: f32 = 0.15;
+
+/// Hours after which the recency signal has halved
+const RECENCY_HALF_LIFE_HOURS: f32 = 24.0;
+
+/// Sessions at which the frequency signal reaches half its weight
+const FREQUENCY_HALF_SESSIONS: f32 = 3.0;
 
 /// Calculates semantic gravity (relevance) between activities and project context
 pub struct GravityCalculator {
-    embedding_service: Mutex<EmbeddingService>,
+    embedding_service: Arc<Mutex<EmbeddingService>>,
     database: Arc<Database>,
 }
 
@@ -37,15 +51,90 @@ impl RelevanceStatus {
     }
 }
 
+/// Activity signals for a single issue
+#[derive(Debug, Clone, Default)]
+pub struct IssueSignals {
+    /// Most recent session that touched the issue
+    pub last_touched: Option<DateTime<Utc>>,
+    /// Sessions that touched the issue in the period
+    pub session_count: u32,
+    /// Whether the issue has an assignee
+    pub assigned: bool,
+    /// Status in the PM system (e.g. "In Progress")
+    pub status: String,
+}
+
+/// Contribution of each signal to an issue's gravity
+///
+/// Components are already weighted, so they add up to [`Self::gravity`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GravityBreakdown {
+    pub recency: f32,
+    pub frequency: f32,
+    pub assignment: f32,
+    pub status: f32,
+}
+
+impl GravityBreakdown {
+    /// Weigh an issue's signals as of `now`
+    #[must_use]
+    pub fn from_signals(signals: &IssueSignals, now: DateTime<Utc>) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let recency = signals.last_touched.map_or(0.0, |touched| {
+            let hours = (now - touched).num_minutes().max(0) as f32 / 60.0;
+            0.5_f32.powf(hours / RECENCY_HALF_LIFE_HOURS)
+        });
+
+        #[allow(clippy::cast_precision_loss)]
+        let sessions = signals.session_count as f32;
+        let frequency = sessions / (sessions + FREQUENCY_HALF_SESSIONS);
+
+        let status = signals.status.to_lowercase();
+        let status_factor = if is_in_progress(&status) {
+            1.0
+        } else if matches!(
+            status.as_str(),
+            "done" | "closed" | "completed" | "cancelled" | "canceled"
+        ) {
+            0.0
+        } else {
+            0.5
+        };
+
+        Self {
+            recency: recency * RECENCY_WEIGHT,
+            frequency: frequency * FREQUENCY_WEIGHT,
+            assignment: if signals.assigned {
+                ASSIGNMENT_WEIGHT
+            } else {
+                0.0
+            },
+            status: status_factor * STATUS_WEIGHT,
+        }
+    }
+
+    /// Overall gravity between 0.0 and 1.0
+    #[must_use]
+    pub fn gravity(&self) -> f32 {
+        self.recency + self.frequency + self.assignment + self.status
+    }
+
+    /// Headline status for the overall gravity
+    #[must_use]
+    pub fn relevance(&self) -> RelevanceStatus {
+        RelevanceStatus::from_score(self.gravity())
+    }
+}
+
 impl GravityCalculator {
     /// Create a new gravity calculator
     ///
     /// # Errors
     ///
-    /// Returns an error if the embedding service fails to initialize
-    pub fn new(database: Arc<Database>) -> Result<Self> {
+    /// Returns an error if the shared embedding service is disabled or fails to initialize
+    pub fn new(database: Arc<Database>, embeddings_enabled: bool) -> Result<Self> {
         Ok(Self {
-            embedding_service: Mutex::new(EmbeddingService::new()?),
+            embedding_service: EmbeddingService::shared(embeddings_enabled)?,
             database,
         })
     }
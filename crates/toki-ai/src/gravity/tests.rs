@@ -236,3 +236,53 @@ fn test_relevance_status_all_variants() {
         }
     }
 }
+
+// ============================================================================
+// GravityBreakdown tests
+// ============================================================================
+
+#[test]
+fn test_breakdown_components_sum_to_gravity() {
+    let now = chrono::Utc::now();
+    let signals = IssueSignals {
+        last_touched: Some(now - chrono::Duration::hours(30)),
+        session_count: 4,
+        assigned: true,
+        status: "Todo".to_string(),
+    };
+
+    let breakdown = GravityBreakdown::from_signals(&signals, now);
+    let sum = breakdown.recency + breakdown.frequency + breakdown.assignment + breakdown.status;
+
+    assert!((breakdown.gravity() - sum).abs() < f32::EPSILON);
+    assert!(breakdown.gravity() > 0.0 && breakdown.gravity() <= 1.0);
+    assert_eq!(breakdown.relevance(), RelevanceStatus::from_score(sum));
+}
+
+#[test]
+fn test_recency_dominates_for_just_touched_issue() {
+    let now = chrono::Utc::now();
+    let signals = IssueSignals {
+        last_touched: Some(now),
+        session_count: 1,
+        assigned: true,
+        status: "in_progress".to_string(),
+    };
+
+    let breakdown = GravityBreakdown::from_signals(&signals, now);
+
+    assert!(breakdown.recency > breakdown.frequency);
+    assert!(breakdown.recency > breakdown.assignment);
+    assert!(breakdown.recency > breakdown.status);
+    assert_eq!(breakdown.relevance(), RelevanceStatus::Focus);
+
+    // A done, untouched issue carries no gravity
+    let stale = GravityBreakdown::from_signals(
+        &IssueSignals {
+            status: "Done".to_string(),
+            ..IssueSignals::default()
+        },
+        now,
+    );
+    assert!(stale.gravity().abs() < f32::EPSILON);
+}
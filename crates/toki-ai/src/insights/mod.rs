@@ -1,8 +1,112 @@
 #[cfg(test)]
 mod tests;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
 use toki_storage::{Activity, ActivitySpan};
+use uuid::Uuid;
+
+/// A change after a longer gap is a return from a break, not a switch
+pub const SWITCH_MAX_GAP_SECONDS: i64 = 10 * 60;
+
+/// Days in the fragmentation moving average
+pub const FRAGMENTATION_WINDOW_DAYS: usize = 3;
+
+/// Relative rise of the moving average that flags fragmentation as rising
+pub const FRAGMENTATION_RISE_RATIO: f64 = 0.2;
+
+/// Smallest absolute rise (weighted switches per hour) that counts as rising
+pub const FRAGMENTATION_MIN_RISE: f64 = 1.0;
+
+/// Standard deviations from the baseline that make a metric an anomaly
+pub const ANOMALY_STD_DEVS: f64 = 2.0;
+
+/// Smallest spread assumed for a category's daily time
+///
+/// A baseline with the same time every day would otherwise flag any change
+/// at all, and one without variance could not be divided by.
+pub const ANOMALY_MIN_SPREAD_SECONDS: f64 = 15.0 * 60.0;
+
+/// Smallest spread assumed for the daily switch rate, in switches per hour
+pub const ANOMALY_MIN_SPREAD_SWITCH_RATE: f64 = 1.0;
+
+/// How disruptive a switch between two consecutive spans is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SwitchSeverity {
+    /// Another app for the same kind of work on the same project
+    App,
+    /// Another kind of work on the same project
+    Category,
+    /// Another project
+    Project,
+}
+
+impl SwitchSeverity {
+    /// Weight of the switch in the fragmentation index
+    #[must_use]
+    pub const fn weight(self) -> f64 {
+        match self {
+            Self::App => 0.25,
+            Self::Category => 0.5,
+            Self::Project => 1.0,
+        }
+    }
+}
+
+/// Context switching on one day
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyFragmentation {
+    pub date: NaiveDate,
+    pub active_seconds: u32,
+    pub switches: u32,
+    /// Severity-weighted switches per active hour
+    pub index: f64,
+    /// Mean index over this day and the days before it in the window
+    pub moving_average: f64,
+}
+
+/// Daily fragmentation over a period, oldest day first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FragmentationTrend {
+    pub days: Vec<DailyFragmentation>,
+    /// Whether the moving average went up by more than the rise thresholds
+    pub rising: bool,
+}
+
+/// What an anomaly was measured on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AnomalyMetric {
+    /// Seconds per tracked day in a category
+    Category(String),
+    /// Switches per active hour
+    SwitchRate,
+}
+
+/// A metric of the current period far from its baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomaly {
+    pub metric: AnomalyMetric,
+    /// Value over the current period
+    pub value: f64,
+    /// Mean of the baseline's daily values
+    pub baseline_mean: f64,
+    /// Standard deviation of the baseline's daily values
+    pub baseline_std_dev: f64,
+    /// Signed distance from the baseline mean, in spreads
+    ///
+    /// The spread is the standard deviation, raised to the metric's minimum
+    /// spread when the baseline barely varies.
+    pub deviations: f64,
+}
+
+impl Anomaly {
+    /// Whether the value is above the baseline
+    #[must_use]
+    pub fn is_above(&self) -> bool {
+        self.deviations > 0.0
+    }
+}
 
 /// Generate insights from activity data
 pub struct InsightsGenerator;
@@ -33,6 +137,22 @@ impl InsightsGenerator {
         category_time
     }
 
+    /// Calculate time spent per (category, project) pair from `ActivitySpan` records
+    #[must_use]
+    pub fn time_per_category_and_project_from_spans(
+        spans: &[ActivitySpan],
+    ) -> HashMap<(String, Option<Uuid>), u32> {
+        let mut time: HashMap<(String, Option<Uuid>), u32> = HashMap::new();
+
+        for span in spans {
+            *time
+                .entry((span.category.clone(), span.project_id))
+                .or_insert(0) += span.duration_seconds;
+        }
+
+        time
+    }
+
     /// Calculate total active time
     #[must_use]
     pub fn total_active_time(activities: &[Activity]) -> u32 {
@@ -49,6 +169,82 @@ impl InsightsGenerator {
         spans.iter().map(|s| s.duration_seconds).sum()
     }
 
+    /// Calculate time spent inside remote-desktop sessions
+    #[must_use]
+    pub fn remote_time_from_spans(spans: &[ActivitySpan]) -> u32 {
+        spans
+            .iter()
+            .filter(|s| s.is_remote())
+            .map(|s| s.duration_seconds)
+            .sum()
+    }
+
+    /// Severity of switching from one span to the next, if it is a switch
+    ///
+    /// Spans on the same app, category and project are not a switch, and
+    /// neither is anything after a gap longer than [`SWITCH_MAX_GAP_SECONDS`].
+    #[must_use]
+    pub fn switch_severity(from: &ActivitySpan, to: &ActivitySpan) -> Option<SwitchSeverity> {
+        let from_end = from.end_time.unwrap_or_else(|| {
+            from.start_time + chrono::Duration::seconds(i64::from(from.duration_seconds))
+        });
+        if (to.start_time - from_end).num_seconds() > SWITCH_MAX_GAP_SECONDS {
+            return None;
+        }
+        if from.project_id != to.project_id {
+            Some(SwitchSeverity::Project)
+        } else if from.category != to.category {
+            Some(SwitchSeverity::Category)
+        } else if from.app_bundle_id != to.app_bundle_id {
+            Some(SwitchSeverity::App)
+        } else {
+            None
+        }
+    }
+
+    /// Daily fragmentation index, trended with a moving average
+    ///
+    /// The index of a day is its severity-weighted switches per active hour,
+    /// so a single-focus day stays near zero. Days start at UTC midnight and
+    /// only days with tracked time are included.
+    #[must_use]
+    pub fn fragmentation_trend(spans: &[ActivitySpan]) -> FragmentationTrend {
+        let mut ordered: Vec<&ActivitySpan> = spans.iter().collect();
+        ordered.sort_by_key(|s| s.start_time);
+        SpanAggregator::from_spans(ordered).fragmentation_trend()
+    }
+
+    /// Categories and switch rate of `current` that stand out from `baseline`
+    ///
+    /// Uses [`ANOMALY_STD_DEVS`], see
+    /// [`InsightsGenerator::detect_anomalies_with_threshold`].
+    #[must_use]
+    pub fn detect_anomalies(current: &[ActivitySpan], baseline: &[ActivitySpan]) -> Vec<Anomaly> {
+        Self::detect_anomalies_with_threshold(current, baseline, ANOMALY_STD_DEVS)
+    }
+
+    /// Categories and switch rate of `current` more than `std_devs` standard
+    /// deviations from `baseline`
+    ///
+    /// Each tracked day of the baseline is one sample: seconds per category,
+    /// and switches per active hour. The current period is compared by its
+    /// average per tracked day, so periods of different lengths compare
+    /// fairly. Nothing is flagged when either period has no tracked time.
+    /// Largest deviation first.
+    #[must_use]
+    pub fn detect_anomalies_with_threshold(
+        current: &[ActivitySpan],
+        baseline: &[ActivitySpan],
+        std_devs: f64,
+    ) -> Vec<Anomaly> {
+        let aggregate = |spans: &[ActivitySpan]| {
+            let mut ordered: Vec<&ActivitySpan> = spans.iter().collect();
+            ordered.sort_by_key(|s| s.start_time);
+            SpanAggregator::from_spans(ordered)
+        };
+        aggregate(current).anomalies(&aggregate(baseline), std_devs)
+    }
+
     /// Find most used applications
     #[must_use]
     pub fn top_applications(activities: &[Activity], limit: usize) -> Vec<(String, u32)> {
@@ -68,15 +264,232 @@ impl InsightsGenerator {
     /// Find most used applications from spans
     #[must_use]
     pub fn top_applications_from_spans(spans: &[ActivitySpan], limit: usize) -> Vec<(String, u32)> {
-        let mut app_time: HashMap<String, u32> = HashMap::new();
+        SpanAggregator::from_spans(spans).top_applications(limit)
+    }
+}
+
+/// Switches and active time of one day, before the index is computed
+#[derive(Debug, Default)]
+struct DayTally {
+    active_seconds: u32,
+    switches: u32,
+    weighted_switches: f64,
+    category_seconds: HashMap<String, u32>,
+}
 
+/// Span totals built up one span at a time
+///
+/// Only running totals are kept, so spans can be fed straight from
+/// `Database::for_each_activity_span` without loading a whole period. Spans
+/// must arrive in start order for the fragmentation trend to be right.
+#[derive(Debug, Default)]
+pub struct SpanAggregator {
+    total_seconds: u32,
+    remote_seconds: u32,
+    category_time: HashMap<String, u32>,
+    app_time: HashMap<String, u32>,
+    days: BTreeMap<NaiveDate, DayTally>,
+    previous: Option<ActivitySpan>,
+}
+
+impl SpanAggregator {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Aggregate spans from any iterator, in start order
+    #[must_use]
+    pub fn from_spans<'a>(spans: impl IntoIterator<Item = &'a ActivitySpan>) -> Self {
+        let mut aggregator = Self::new();
         for span in spans {
-            *app_time.entry(span.app_bundle_id.clone()).or_insert(0) += span.duration_seconds;
+            aggregator.add(span);
         }
+        aggregator
+    }
 
-        let mut sorted: Vec<_> = app_time.into_iter().collect();
+    /// Add the next span
+    pub fn add(&mut self, span: &ActivitySpan) {
+        self.total_seconds += span.duration_seconds;
+        if span.is_remote() {
+            self.remote_seconds += span.duration_seconds;
+        }
+        *self.category_time.entry(span.category.clone()).or_insert(0) += span.duration_seconds;
+        *self.app_time.entry(span.app_bundle_id.clone()).or_insert(0) += span.duration_seconds;
+
+        let date = span.start_time.date_naive();
+        let day = self.days.entry(date).or_default();
+        day.active_seconds += span.duration_seconds;
+        *day.category_seconds
+            .entry(span.category.clone())
+            .or_insert(0) += span.duration_seconds;
+        // Switches are only counted within a day
+        let severity = self
+            .previous
+            .as_ref()
+            .filter(|previous| previous.start_time.date_naive() == date)
+            .and_then(|previous| InsightsGenerator::switch_severity(previous, span));
+        if let Some(severity) = severity {
+            day.switches += 1;
+            day.weighted_switches += severity.weight();
+        }
+        self.previous = Some(span.clone());
+    }
+
+    /// Total tracked seconds
+    #[must_use]
+    pub fn total_seconds(&self) -> u32 {
+        self.total_seconds
+    }
+
+    /// Seconds spent inside remote-desktop sessions
+    #[must_use]
+    pub fn remote_seconds(&self) -> u32 {
+        self.remote_seconds
+    }
+
+    /// Seconds per category
+    #[must_use]
+    pub fn time_per_category(&self) -> &HashMap<String, u32> {
+        &self.category_time
+    }
+
+    /// Most used applications, most time first
+    #[must_use]
+    pub fn top_applications(&self, limit: usize) -> Vec<(String, u32)> {
+        let mut sorted: Vec<_> = self
+            .app_time
+            .iter()
+            .map(|(app, seconds)| (app.clone(), *seconds))
+            .collect();
         sorted.sort_by(|a, b| b.1.cmp(&a.1));
         sorted.truncate(limit);
         sorted
     }
+
+    /// Daily fragmentation index, see [`InsightsGenerator::fragmentation_trend`]
+    #[must_use]
+    pub fn fragmentation_trend(&self) -> FragmentationTrend {
+        let mut days: Vec<DailyFragmentation> = Vec::with_capacity(self.days.len());
+        for (date, tally) in &self.days {
+            let index = if tally.active_seconds > 0 {
+                tally.weighted_switches / (f64::from(tally.active_seconds) / 3600.0)
+            } else {
+                0.0
+            };
+
+            let window_start = days.len().saturating_sub(FRAGMENTATION_WINDOW_DAYS - 1);
+            let window = &days[window_start..];
+            let window_sum: f64 = window.iter().map(|d| d.index).sum::<f64>() + index;
+            #[allow(clippy::cast_precision_loss)] // The window is a few days long
+            let moving_average = window_sum / (window.len() + 1) as f64;
+
+            days.push(DailyFragmentation {
+                date: *date,
+                active_seconds: tally.active_seconds,
+                switches: tally.switches,
+                index,
+                moving_average,
+            });
+        }
+
+        let rising = match (days.first(), days.last()) {
+            (Some(first), Some(last)) if days.len() > FRAGMENTATION_WINDOW_DAYS => {
+                let rise = last.moving_average - first.moving_average;
+                rise >= FRAGMENTATION_MIN_RISE
+                    && last.moving_average > first.moving_average * (1.0 + FRAGMENTATION_RISE_RATIO)
+            }
+            _ => false,
+        };
+        FragmentationTrend { days, rising }
+    }
+
+    /// Categories and switch rate that stand out from `baseline`, see
+    /// [`InsightsGenerator::detect_anomalies_with_threshold`]
+    #[must_use]
+    pub fn anomalies(&self, baseline: &Self, std_devs: f64) -> Vec<Anomaly> {
+        if self.days.is_empty() || baseline.days.is_empty() {
+            return Vec::new();
+        }
+        #[allow(clippy::cast_precision_loss)] // Days in a period
+        let current_days = self.days.len() as f64;
+
+        let mut categories: Vec<&String> = self
+            .category_time
+            .keys()
+            .chain(baseline.category_time.keys())
+            .collect();
+        categories.sort();
+        categories.dedup();
+
+        let mut anomalies = Vec::new();
+        for category in categories {
+            let samples: Vec<f64> = baseline
+                .days
+                .values()
+                .map(|day| f64::from(day.category_seconds.get(category).copied().unwrap_or(0)))
+                .collect();
+            let value =
+                f64::from(self.category_time.get(category).copied().unwrap_or(0)) / current_days;
+            anomalies.extend(outlier(
+                AnomalyMetric::Category(category.clone()),
+                value,
+                &samples,
+                ANOMALY_MIN_SPREAD_SECONDS,
+                std_devs,
+            ));
+        }
+
+        let samples: Vec<f64> = baseline
+            .days
+            .values()
+            .filter(|day| day.active_seconds > 0)
+            .map(|day| f64::from(day.switches) / (f64::from(day.active_seconds) / 3600.0))
+            .collect();
+        let (switches, active_seconds) = self.days.values().fold((0, 0), |(s, a), day| {
+            (s + day.switches, a + day.active_seconds)
+        });
+        if active_seconds > 0 {
+            let value = f64::from(switches) / (f64::from(active_seconds) / 3600.0);
+            anomalies.extend(outlier(
+                AnomalyMetric::SwitchRate,
+                value,
+                &samples,
+                ANOMALY_MIN_SPREAD_SWITCH_RATE,
+                std_devs,
+            ));
+        }
+
+        anomalies.sort_by(|a, b| b.deviations.abs().total_cmp(&a.deviations.abs()));
+        anomalies
+    }
+}
+
+/// An anomaly if `value` is more than `std_devs` spreads from the samples' mean
+///
+/// The spread is the samples' standard deviation, but never below
+/// `min_spread`. Without samples there is nothing to compare against.
+fn outlier(
+    metric: AnomalyMetric,
+    value: f64,
+    samples: &[f64],
+    min_spread: f64,
+    std_devs: f64,
+) -> Option<Anomaly> {
+    if samples.is_empty() {
+        return None;
+    }
+    #[allow(clippy::cast_precision_loss)] // Days in a period
+    let count = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / count;
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count;
+    let std_dev = variance.sqrt();
+    let deviations = (value - mean) / std_dev.max(min_spread);
+    (deviations.abs() > std_devs).then_some(Anomaly {
+        metric,
+        value,
+        baseline_mean: mean,
+        baseline_std_dev: std_dev,
+        deviations,
+    })
 }
@@ -120,6 +120,26 @@ fn test_time_per_category_from_spans_aggregates() {
     assert_eq!(result.get("Browser"), Some(&600));
 }
 
+#[test]
+fn test_time_per_category_and_project_from_spans() {
+    let project = Uuid::new_v4();
+    let mut with_project = create_span("com.app.vscode", "Development", 1800);
+    with_project.project_id = Some(project);
+    let spans = vec![
+        with_project,
+        create_span("com.app.vscode", "Development", 600),
+        create_span("com.app.chrome", "Browser", 300),
+    ];
+    let result = InsightsGenerator::time_per_category_and_project_from_spans(&spans);
+    assert_eq!(result.len(), 3);
+    assert_eq!(
+        result.get(&("Development".to_string(), Some(project))),
+        Some(&1800)
+    );
+    assert_eq!(result.get(&("Development".to_string(), None)), Some(&600));
+    assert_eq!(result.get(&("Browser".to_string(), None)), Some(&300));
+}
+
 #[test]
 fn test_time_per_category_from_spans_many_categories() {
     let spans = vec![
@@ -462,3 +482,263 @@ fn test_unicode_app_bundle_ids() {
     let result = InsightsGenerator::top_applications_from_spans(&spans, 5);
     assert_eq!(result.len(), 2);
 }
+
+// ============================================================================
+// remote_time_from_spans tests
+// ============================================================================
+
+#[test]
+fn test_remote_time_from_spans_counts_only_remote() {
+    let mut remote = create_span("com.microsoft.rdc.macos", "Uncategorized", 1800);
+    remote.mark_remote();
+    let spans = vec![remote, create_span("com.app.vscode", "Development", 600)];
+
+    assert_eq!(InsightsGenerator::remote_time_from_spans(&spans), 1800);
+    assert_eq!(InsightsGenerator::total_time_from_spans(&spans), 2400);
+}
+
+// ============================================================================
+// fragmentation_trend tests
+// ============================================================================
+
+/// Back-to-back spans of `minutes` each, starting at `start`
+fn span_run(
+    start: chrono::DateTime<Utc>,
+    minutes: i64,
+    spans: &[(&str, &str, Option<Uuid>)],
+) -> Vec<ActivitySpan> {
+    spans
+        .iter()
+        .enumerate()
+        .map(|(i, (app, category, project_id))| {
+            let mut span = create_span(app, category, u32::try_from(minutes * 60).unwrap());
+            span.start_time =
+                start + chrono::Duration::minutes(minutes * i64::try_from(i).unwrap());
+            span.end_time = Some(span.start_time + chrono::Duration::minutes(minutes));
+            span.project_id = *project_id;
+            span
+        })
+        .collect()
+}
+
+#[test]
+fn test_fragmentation_index_high_for_rapid_switching_and_zero_for_focus() {
+    use chrono::TimeZone;
+
+    let api = Some(Uuid::new_v4());
+    let web = Some(Uuid::new_v4());
+    let focus_day = Utc.with_ymd_and_hms(2025, 3, 3, 9, 0, 0).unwrap();
+    let busy_day = Utc.with_ymd_and_hms(2025, 3, 4, 9, 0, 0).unwrap();
+
+    let mut spans = span_run(focus_day, 60, &[("code", "Development", api); 4]);
+    let switching: Vec<_> = (0..24)
+        .map(|i| match i % 3 {
+            0 => ("code", "Development", api),
+            1 => ("slack", "Communication", api),
+            _ => ("code", "Development", web),
+        })
+        .collect();
+    spans.extend(span_run(busy_day, 5, &switching));
+
+    let trend = InsightsGenerator::fragmentation_trend(&spans);
+    assert_eq!(trend.days.len(), 2);
+    let (focus, busy) = (&trend.days[0], &trend.days[1]);
+    assert_eq!((focus.switches, focus.index), (0, 0.0));
+    assert_eq!(busy.switches, 23);
+    assert!(busy.index > 5.0, "index was {}", busy.index);
+    assert!((busy.moving_average - busy.index / 2.0).abs() < 1e-9);
+    // Too few days to call a trend
+    assert!(!trend.rising);
+}
+
+#[test]
+fn test_fragmentation_trend_rising_and_breaks_are_not_switches() {
+    use chrono::TimeZone;
+
+    let api = Some(Uuid::new_v4());
+    let web = Some(Uuid::new_v4());
+    let day = |d: u32| Utc.with_ymd_and_hms(2025, 3, d, 9, 0, 0).unwrap();
+
+    let mut spans = Vec::new();
+    for d in 3..=5 {
+        spans.extend(span_run(day(d), 60, &[("code", "Development", api); 3]));
+    }
+    for d in 6..=8 {
+        spans.extend(span_run(
+            day(d),
+            10,
+            &[
+                ("code", "Development", api),
+                ("code", "Development", web),
+                ("code", "Review", web),
+                ("browser", "Review", web),
+            ],
+        ));
+    }
+    let trend = InsightsGenerator::fragmentation_trend(&spans);
+    assert_eq!(trend.days.len(), 6);
+    assert!(trend.rising);
+
+    // The same switches spread out by long breaks do not count
+    let mut apart = span_run(day(9), 10, &[("code", "Development", api)]);
+    apart.extend(span_run(
+        day(9) + chrono::Duration::hours(1),
+        10,
+        &[("code", "Development", web)],
+    ));
+    let trend = InsightsGenerator::fragmentation_trend(&apart);
+    assert_eq!(trend.days[0].switches, 0);
+    assert_eq!(
+        InsightsGenerator::switch_severity(&spans[spans.len() - 2], &spans[spans.len() - 1]),
+        Some(SwitchSeverity::App)
+    );
+}
+
+// ============================================================================
+// SpanAggregator tests
+// ============================================================================
+
+#[test]
+fn test_span_aggregator_matches_slice_helpers() {
+    use chrono::TimeZone;
+
+    let api = Some(Uuid::new_v4());
+    let web = Some(Uuid::new_v4());
+    let day = |d: u32| Utc.with_ymd_and_hms(2025, 3, d, 9, 0, 0).unwrap();
+    let mut spans = span_run(
+        day(3),
+        20,
+        &[
+            ("code", "Development", api),
+            ("slack", "Communication", api),
+            ("code", "Development", web),
+        ],
+    );
+    spans.extend(span_run(day(4), 30, &[("code", "Review", web); 2]));
+
+    let mut aggregator = SpanAggregator::new();
+    for span in &spans {
+        aggregator.add(span);
+    }
+
+    assert_eq!(
+        aggregator.total_seconds(),
+        InsightsGenerator::total_time_from_spans(&spans)
+    );
+    assert_eq!(
+        aggregator.remote_seconds(),
+        InsightsGenerator::remote_time_from_spans(&spans)
+    );
+    assert_eq!(
+        aggregator.time_per_category(),
+        &InsightsGenerator::time_per_category_from_spans(&spans)
+    );
+    assert_eq!(
+        aggregator.top_applications(1),
+        vec![("code".to_string(), 6000)]
+    );
+    let trend = aggregator.fragmentation_trend();
+    assert_eq!(trend, InsightsGenerator::fragmentation_trend(&spans));
+    // The day change between the third and fourth span is not a switch
+    assert_eq!(
+        trend.days.iter().map(|d| d.switches).collect::<Vec<_>>(),
+        vec![2, 0]
+    );
+}
+
+// ============================================================================
+// detect_anomalies tests
+// ============================================================================
+
+#[test]
+fn test_detect_anomalies_flags_category_far_from_baseline() {
+    use chrono::TimeZone;
+
+    let api = Some(Uuid::new_v4());
+    let day = |d: u32| Utc.with_ymd_and_hms(2025, 3, d, 9, 0, 0).unwrap();
+    let mut baseline = Vec::new();
+    for (d, meeting_minutes) in [(3, 50), (4, 60), (5, 70), (6, 60), (7, 60)] {
+        baseline.extend(span_run(day(d), 240, &[("code", "Development", api)]));
+        baseline.extend(span_run(
+            day(d) + chrono::Duration::hours(5),
+            meeting_minutes,
+            &[("zoom", "Meetings", api)],
+        ));
+    }
+    let mut current = Vec::new();
+    for d in [10, 11] {
+        current.extend(span_run(day(d), 240, &[("code", "Development", api)]));
+        current.extend(span_run(
+            day(d) + chrono::Duration::hours(5),
+            240,
+            &[("zoom", "Meetings", api)],
+        ));
+    }
+
+    let anomalies = InsightsGenerator::detect_anomalies(&current, &baseline);
+    assert_eq!(anomalies.len(), 1, "{anomalies:?}");
+    let meetings = &anomalies[0];
+    assert_eq!(
+        meetings.metric,
+        AnomalyMetric::Category("Meetings".to_string())
+    );
+    assert!(meetings.is_above());
+    assert!((meetings.value - 4.0 * 3600.0).abs() < 1e-9);
+    assert!((meetings.baseline_mean - 3600.0).abs() < 1e-9);
+    assert!(meetings.deviations > ANOMALY_STD_DEVS);
+}
+
+#[test]
+fn test_detect_anomalies_flags_switch_rate() {
+    use chrono::TimeZone;
+
+    let api = Some(Uuid::new_v4());
+    let web = Some(Uuid::new_v4());
+    let day = |d: u32| Utc.with_ymd_and_hms(2025, 3, d, 9, 0, 0).unwrap();
+    let mut baseline = Vec::new();
+    for d in 3..8 {
+        baseline.extend(span_run(day(d), 30, &[("code", "Development", api); 4]));
+    }
+    let switching: Vec<_> = (0..24)
+        .map(|i| {
+            if i % 2 == 0 {
+                ("code", "Development", api)
+            } else {
+                ("code", "Development", web)
+            }
+        })
+        .collect();
+    let current = span_run(day(10), 5, &switching);
+
+    let anomalies = InsightsGenerator::detect_anomalies(&current, &baseline);
+    let rate = anomalies
+        .iter()
+        .find(|a| a.metric == AnomalyMetric::SwitchRate)
+        .expect("switch rate anomaly");
+    assert!((rate.value - 23.0 / 2.0).abs() < 1e-9);
+    assert!(rate.baseline_mean.abs() < f64::EPSILON);
+    assert!(rate.baseline_std_dev.abs() < f64::EPSILON);
+    assert!(rate.deviations.is_finite());
+}
+
+#[test]
+fn test_detect_anomalies_without_baseline_or_variance() {
+    use chrono::TimeZone;
+
+    let day = |d: u32| Utc.with_ymd_and_hms(2025, 3, d, 9, 0, 0).unwrap();
+    let current = span_run(day(10), 250, &[("code", "Development", None)]);
+    assert!(InsightsGenerator::detect_anomalies(&current, &[]).is_empty());
+    assert!(InsightsGenerator::detect_anomalies(&[], &current).is_empty());
+
+    // The same four hours every day: a 10 minute change stays within the
+    // minimum spread, a threshold of zero flags it
+    let mut baseline = Vec::new();
+    for d in 3..8 {
+        baseline.extend(span_run(day(d), 240, &[("code", "Development", None)]));
+    }
+    assert!(InsightsGenerator::detect_anomalies(&current, &baseline).is_empty());
+    let strict = InsightsGenerator::detect_anomalies_with_threshold(&current, &baseline, 0.0);
+    assert_eq!(strict.len(), 1);
+    assert!(strict[0].baseline_std_dev.abs() < f64::EPSILON);
+    assert!((strict[0].deviations - 600.0 / ANOMALY_MIN_SPREAD_SECONDS).abs() < 1e-9);
+}
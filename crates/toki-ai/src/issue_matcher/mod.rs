@@ -10,12 +10,15 @@
 
 #[cfg(test)]
 mod tests;
+mod warmup;
+
+pub use warmup::{rank_candidates, CandidateCache};
 
 use regex::Regex;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use uuid::Uuid;
 
 use crate::embedding::EmbeddingService;
@@ -37,6 +40,7 @@ pub enum MatchReason {
     BranchName,              // Found in git branch
     BrowserUrl(String),      // Visited issue page
     FilePathPattern(String), // File path contains issue ID
+    TerminalCommand(String), // Shell command mentions issue ID
     SemanticSimilarity(f32), // AI semantic match score
     RecentlyViewed,          // Recently viewed in PM system
     Assigned,                // User is assigned to this issue
@@ -49,9 +53,155 @@ pub struct ActivitySignals {
     pub edited_files: Vec<String>,
     pub browser_urls: Vec<String>,
     pub window_titles: Vec<String>,
+    pub terminal_commands: Vec<String>,
     pub git_branch: Option<String>,
 }
 
+impl ActivitySignals {
+    /// Whether no context was collected at all
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.git_branch.is_none()
+            && self.recent_commits.is_empty()
+            && self.edited_files.is_empty()
+            && self.browser_urls.is_empty()
+            && self.window_titles.is_empty()
+            && self.terminal_commands.is_empty()
+    }
+}
+
+/// Minimum cosine similarity for a semantic match to contribute to the score
+pub const SEMANTIC_SIMILARITY_FLOOR: f32 = 0.3;
+
+/// Candidates nearest the activity context that get a semantic score
+///
+/// The rest can still match on an explicit issue ID.
+const SEMANTIC_NEIGHBORS: usize = 20;
+
+/// Most likely reason a suggestion run produced no match
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoMatchCause {
+    /// No active issues synced for the project
+    NoCandidates,
+    /// No branch, commits, URLs, files, or window titles were collected
+    NoSignals,
+    /// The embedding model could not be loaded
+    EmbeddingsUnavailable,
+    /// Candidates exist but none has an embedding yet
+    NoEmbeddedCandidates,
+    /// Everything is in place; scores were just too low
+    BelowThreshold,
+}
+
+impl NoMatchCause {
+    /// Actionable hint for the user
+    #[must_use]
+    pub const fn hint(self) -> &'static str {
+        match self {
+            Self::NoCandidates => "No active issues for this project. Run 'toki issue-sync'.",
+            Self::NoSignals => {
+                "No context collected. Commit, check out an issue branch, or edit files first."
+            }
+            Self::EmbeddingsUnavailable => {
+                "Embedding model unavailable; only explicit issue IDs can match."
+            }
+            Self::NoEmbeddedCandidates => {
+                "Issues have no embeddings yet. Run 'toki issue-sync --force'."
+            }
+            Self::BelowThreshold => {
+                "Context is too different from every issue. Put the issue ID in the branch name."
+            }
+        }
+    }
+}
+
+/// Explanation of a suggestion run, for `toki suggest-issue --diagnose`
+#[derive(Debug, Clone)]
+pub struct MatchDiagnosis {
+    pub active_candidates: usize,
+    pub embedded_candidates: usize,
+    pub has_branch: bool,
+    pub commit_count: usize,
+    pub url_count: usize,
+    pub file_count: usize,
+    pub window_title_count: usize,
+    pub command_count: usize,
+    pub embeddings_available: bool,
+    /// Raw semantic similarity per embedded candidate, best first, regardless of the floor
+    pub top_similarities: Vec<(String, f32)>,
+}
+
+impl MatchDiagnosis {
+    /// Build a diagnosis from the candidates and signals a suggestion run would use
+    ///
+    /// `context_embedding` is `None` when the embedding model is unavailable.
+    #[must_use]
+    pub fn new(
+        candidates: &[IssueCandidate],
+        signals: &ActivitySignals,
+        context_embedding: Option<&[f32]>,
+        top_n: usize,
+    ) -> Self {
+        let mut top_similarities: Vec<(String, f32)> = context_embedding
+            .map(|context| {
+                candidates
+                    .iter()
+                    .filter_map(|c| {
+                        c.embedding.as_ref().map(|e| {
+                            (
+                                c.external_id.clone(),
+                                EmbeddingService::cosine_similarity(context, e),
+                            )
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        top_similarities.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        top_similarities.truncate(top_n);
+
+        Self {
+            active_candidates: candidates.len(),
+            embedded_candidates: candidates.iter().filter(|c| c.embedding.is_some()).count(),
+            has_branch: signals.git_branch.is_some(),
+            commit_count: signals.recent_commits.len(),
+            url_count: signals.browser_urls.len(),
+            file_count: signals.edited_files.len(),
+            window_title_count: signals.window_titles.len(),
+            command_count: signals.terminal_commands.len(),
+            embeddings_available: context_embedding.is_some(),
+            top_similarities,
+        }
+    }
+
+    /// Whether any context signal was collected
+    #[must_use]
+    pub const fn has_signals(&self) -> bool {
+        self.has_branch
+            || self.commit_count > 0
+            || self.url_count > 0
+            || self.file_count > 0
+            || self.window_title_count > 0
+            || self.command_count > 0
+    }
+
+    /// The first blocking problem, in the order a user should fix them
+    #[must_use]
+    pub const fn cause(&self) -> NoMatchCause {
+        if self.active_candidates == 0 {
+            NoMatchCause::NoCandidates
+        } else if !self.has_signals() {
+            NoMatchCause::NoSignals
+        } else if !self.embeddings_available {
+            NoMatchCause::EmbeddingsUnavailable
+        } else if self.embedded_candidates == 0 {
+            NoMatchCause::NoEmbeddedCandidates
+        } else {
+            NoMatchCause::BelowThreshold
+        }
+    }
+}
+
 /// Issue from PM system for matching
 #[derive(Debug, Clone)]
 pub struct CandidateIssue {
@@ -63,6 +213,39 @@ pub struct CandidateIssue {
     pub is_assigned_to_user: bool,
 }
 
+/// Matches common issue ID patterns: PROJ-123, ABC-1, etc.
+pub const DEFAULT_ISSUE_ID_PATTERN: &str = r"(?i)([A-Z]{2,10}-\d+)";
+
+/// Compile a user-supplied issue ID pattern, or the default when none is given
+///
+/// # Errors
+///
+/// Returns an error if the pattern is not a valid regex
+pub fn compile_issue_id_pattern(pattern: Option<&str>) -> Result<Regex> {
+    let pattern = pattern.unwrap_or(DEFAULT_ISSUE_ID_PATTERN);
+    Regex::new(pattern).with_context(|| format!("Invalid issue ID pattern: {pattern}"))
+}
+
+/// Issue IDs matched by `pattern` in `text`
+///
+/// When the pattern has a capture group, the ID is what group 1 matched, so
+/// `#(\d+)` yields `1234` for `#1234`. IDs with letters are uppercased so
+/// `proj-1` matches `PROJ-1`; numeric IDs are kept as written.
+fn find_issue_ids(pattern: &Regex, text: &str) -> Vec<String> {
+    pattern
+        .captures_iter(text)
+        .filter_map(|caps| caps.get(1).or_else(|| caps.get(0)))
+        .map(|m| {
+            let id = m.as_str();
+            if id.chars().any(char::is_alphabetic) {
+                id.to_uppercase()
+            } else {
+                id.to_string()
+            }
+        })
+        .collect()
+}
+
 /// Smart issue matcher
 pub struct IssueMatcher {
     issue_id_pattern: Regex,
@@ -77,11 +260,21 @@ impl IssueMatcher {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            // Matches common issue ID patterns: PROJ-123, ABC-1, etc.
-            issue_id_pattern: Regex::new(r"(?i)([A-Z]{2,10}-\d+)").unwrap(),
+            issue_id_pattern: Regex::new(DEFAULT_ISSUE_ID_PATTERN).unwrap(),
         }
     }
 
+    /// Create an issue matcher that recognizes IDs with a custom regex (e.g. `#\d+`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern is not a valid regex
+    pub fn with_pattern(pattern: &str) -> Result<Self> {
+        Ok(Self {
+            issue_id_pattern: compile_issue_id_pattern(Some(pattern))?,
+        })
+    }
+
     /// Find the best matching issue from candidates based on activity signals
     #[must_use]
     pub fn find_best_match(
@@ -111,46 +304,31 @@ impl IssueMatcher {
         }
 
         // 2. Check browser URLs (visited issue page = high confidence)
-        for url in &signals.browser_urls {
-            for id in self.extract_issue_ids(url) {
-                if let Some((score, reasons)) = scores.get_mut(&id) {
-                    *score += 0.8;
-                    reasons.push(MatchReason::BrowserUrl(url.clone()));
-                }
-            }
-        }
+        self.score_mentions(&mut scores, &signals.browser_urls, 0.8, |url| {
+            MatchReason::BrowserUrl(url.clone())
+        });
 
         // 3. Check commit messages
-        for commit in &signals.recent_commits {
-            for id in self.extract_issue_ids(commit) {
-                if let Some((score, reasons)) = scores.get_mut(&id) {
-                    *score += 0.7;
-                    reasons.push(MatchReason::CommitMessage(commit.clone()));
-                }
-            }
-        }
-
-        // 4. Check file paths
-        for file in &signals.edited_files {
-            for id in self.extract_issue_ids(file) {
-                if let Some((score, reasons)) = scores.get_mut(&id) {
-                    *score += 0.5;
-                    reasons.push(MatchReason::FilePathPattern(file.clone()));
-                }
-            }
-        }
-
-        // 5. Check window titles
-        for title in &signals.window_titles {
-            for id in self.extract_issue_ids(title) {
-                if let Some((score, reasons)) = scores.get_mut(&id) {
-                    *score += 0.4;
-                    reasons.push(MatchReason::RecentlyViewed);
-                }
-            }
-        }
-
-        // 6. Boost assigned issues
+        self.score_mentions(&mut scores, &signals.recent_commits, 0.7, |commit| {
+            MatchReason::CommitMessage(commit.clone())
+        });
+
+        // 4. Check terminal commands (e.g. `git checkout PROJ-12`)
+        self.score_mentions(&mut scores, &signals.terminal_commands, 0.6, |command| {
+            MatchReason::TerminalCommand(command.clone())
+        });
+
+        // 5. Check file paths
+        self.score_mentions(&mut scores, &signals.edited_files, 0.5, |file| {
+            MatchReason::FilePathPattern(file.clone())
+        });
+
+        // 6. Check window titles
+        self.score_mentions(&mut scores, &signals.window_titles, 0.4, |_| {
+            MatchReason::RecentlyViewed
+        });
+
+        // 7. Boost assigned issues
         for candidate in candidates {
             if candidate.is_assigned_to_user {
                 if let Some((score, reasons)) = scores.get_mut(&candidate.external_id) {
@@ -160,7 +338,7 @@ impl IssueMatcher {
             }
         }
 
-        // 7. Semantic similarity (keyword matching as fallback)
+        // 8. Semantic similarity (keyword matching as fallback)
         for candidate in candidates {
             let similarity = Self::calculate_semantic_similarity(signals, candidate);
             if similarity > 0.3 {
@@ -188,12 +366,27 @@ impl IssueMatcher {
         })
     }
 
+    /// Add `weight` to each candidate for every text in `texts` that mentions its issue ID
+    fn score_mentions(
+        &self,
+        scores: &mut HashMap<String, (f32, Vec<MatchReason>)>,
+        texts: &[String],
+        weight: f32,
+        reason: impl Fn(&String) -> MatchReason,
+    ) {
+        for text in texts {
+            for id in self.extract_issue_ids(text) {
+                if let Some((score, reasons)) = scores.get_mut(&id) {
+                    *score += weight;
+                    reasons.push(reason(text));
+                }
+            }
+        }
+    }
+
     /// Extract issue IDs from text
     pub(crate) fn extract_issue_ids(&self, text: &str) -> Vec<String> {
-        self.issue_id_pattern
-            .find_iter(text)
-            .map(|m| m.as_str().to_uppercase())
-            .collect()
+        find_issue_ids(&self.issue_id_pattern, text)
     }
 
     /// Calculate semantic similarity between signals and candidate issue
@@ -221,10 +414,11 @@ impl IssueMatcher {
 
         // Check if keywords appear in signals
         let signal_text = format!(
-            "{} {} {}",
+            "{} {} {} {}",
             signals.edited_files.join(" "),
             signals.recent_commits.join(" "),
-            signals.window_titles.join(" ")
+            signals.window_titles.join(" "),
+            signals.terminal_commands.join(" ")
         )
         .to_lowercase();
 
@@ -292,6 +486,16 @@ impl IssueMatcher {
                 }
             }
 
+            for command in &signals.terminal_commands {
+                if self
+                    .extract_issue_ids(command)
+                    .contains(&candidate.external_id.to_uppercase())
+                {
+                    score += 0.6;
+                    reasons.push(MatchReason::TerminalCommand(command.clone()));
+                }
+            }
+
             if candidate.is_assigned_to_user && candidate.status != "done" {
                 score += 0.3;
                 reasons.push(MatchReason::Assigned);
@@ -330,6 +534,17 @@ impl Default for IssueMatcher {
     }
 }
 
+/// Score boost for an issue's workflow status
+///
+/// Actively worked issues get the larger boost, planned work a small one.
+pub(crate) fn status_boost(status: &str) -> f32 {
+    match status.to_lowercase().as_str() {
+        "in_progress" | "in progress" | "started" => 0.15,
+        "todo" | "backlog" => 0.05,
+        _ => 0.0,
+    }
+}
+
 // ============================================================================
 // Smart Issue Matcher - Embedding-based semantic matching
 // ============================================================================
@@ -343,6 +558,7 @@ pub struct SmartIssueMatcher {
     embedding_service: Arc<Mutex<EmbeddingService>>,
     database: Arc<Database>,
     issue_id_pattern: Regex,
+    candidate_cache: Option<Arc<CandidateCache>>,
 }
 
 impl SmartIssueMatcher {
@@ -350,18 +566,16 @@ impl SmartIssueMatcher {
     ///
     /// # Errors
     ///
-    /// Returns an error if the embedding service fails to initialize
+    /// Returns an error if the shared embedding service is disabled or fails to initialize
     ///
     /// # Panics
     ///
     /// Panics if the issue ID regex pattern fails to compile (should never happen with valid pattern)
-    pub fn new(database: Arc<Database>) -> Result<Self> {
-        let embedding_service = EmbeddingService::new()?;
-        Ok(Self {
-            embedding_service: Arc::new(Mutex::new(embedding_service)),
+    pub fn new(database: Arc<Database>, embeddings_enabled: bool) -> Result<Self> {
+        Ok(Self::with_embedding_service(
             database,
-            issue_id_pattern: Regex::new(r"(?i)([A-Z]{2,10}-\d+)").unwrap(),
-        })
+            EmbeddingService::shared(embeddings_enabled)?,
+        ))
     }
 
     /// Create with an existing embedding service (for sharing)
@@ -377,8 +591,52 @@ impl SmartIssueMatcher {
         Self {
             embedding_service,
             database,
-            issue_id_pattern: Regex::new(r"(?i)([A-Z]{2,10}-\d+)").unwrap(),
+            issue_id_pattern: Regex::new(DEFAULT_ISSUE_ID_PATTERN).unwrap(),
+            candidate_cache: None,
+        }
+    }
+
+    /// Create a smart issue matcher with a custom issue ID regex
+    ///
+    /// Falls back to [`DEFAULT_ISSUE_ID_PATTERN`] when `pattern` is `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern is invalid, or the shared embedding
+    /// service is disabled or fails to initialize
+    pub fn with_pattern(
+        database: Arc<Database>,
+        pattern: Option<&str>,
+        embeddings_enabled: bool,
+    ) -> Result<Self> {
+        let issue_id_pattern = compile_issue_id_pattern(pattern)?;
+        Ok(Self {
+            issue_id_pattern,
+            ..Self::new(database, embeddings_enabled)?
+        })
+    }
+
+    /// Read candidates from a warmed cache instead of the database
+    ///
+    /// Projects missing from the cache still fall back to a database read.
+    #[must_use]
+    pub fn with_candidate_cache(mut self, cache: Arc<CandidateCache>) -> Self {
+        self.candidate_cache = Some(cache);
+        self
+    }
+
+    /// Active candidates for a project, from the warmed cache when possible
+    fn active_candidates(&self, project_id: Uuid) -> Result<Arc<Vec<IssueCandidate>>> {
+        if let Some(cached) = self
+            .candidate_cache
+            .as_ref()
+            .and_then(|cache| cache.get(project_id))
+        {
+            return Ok(cached);
         }
+        Ok(Arc::new(
+            self.database.get_active_issue_candidates(project_id)?,
+        ))
     }
 
     /// Find best matching issues using hybrid scoring (rules + semantics)
@@ -387,7 +645,8 @@ impl SmartIssueMatcher {
     /// - Git branch with issue ID: 0.95 (near-certain)
     /// - Commit message with issue ID: 0.85
     /// - Browser URL with issue ID: 0.80
-    /// - Semantic similarity > 0.5: 0.60 * similarity
+    /// - Semantic similarity > 0.5: 0.60 * similarity, for the issues nearest
+    ///   the activity context only
     /// - Assigned to user: +0.20 boost
     /// - Status = `in_progress`: +0.10 boost
     ///
@@ -400,20 +659,30 @@ impl SmartIssueMatcher {
         project_id: Uuid,
         max_results: usize,
     ) -> Result<Vec<IssueMatch>> {
-        // Get active issue candidates from database
-        let candidates = self.database.get_active_issue_candidates(project_id)?;
+        // Get active issue candidates from the warmed cache or database
+        let candidates = self.active_candidates(project_id)?;
 
         if candidates.is_empty() {
             log::debug!("No issue candidates found for project {project_id}");
             return Ok(Vec::new());
         }
 
-        // Generate context embedding from signals
+        // Generate context embedding from signals, and find the closest issues by it
         let context_embedding = self.generate_context_embedding(signals)?;
+        let similarities: HashMap<Uuid, f32> = self
+            .database
+            .nearest_issue_candidates(
+                project_id,
+                &context_embedding,
+                SEMANTIC_NEIGHBORS.max(max_results),
+            )?
+            .into_iter()
+            .map(|(candidate, similarity)| (candidate.id, similarity))
+            .collect();
 
-        let mut scores: Vec<(IssueCandidate, f32, Vec<MatchReason>)> = Vec::new();
+        let mut scores: Vec<(&IssueCandidate, f32, Vec<MatchReason>)> = Vec::new();
 
-        for candidate in candidates {
+        for candidate in candidates.iter() {
             let mut score = 0.0f32;
             let mut reasons = Vec::new();
 
@@ -425,31 +694,28 @@ impl SmartIssueMatcher {
                 }
             }
 
-            // 2. Commit message matching
-            for commit in &signals.recent_commits {
-                if self.extract_issue_ids(commit).contains(&candidate.external_id.to_uppercase()) {
-                    score += 0.85;
-                    reasons.push(MatchReason::CommitMessage(commit.clone()));
-                    break; // Only count once
-                }
+            // 2. Commit message matching (only counted once)
+            if let Some(commit) = self.first_mentioning(&signals.recent_commits, candidate) {
+                score += 0.85;
+                reasons.push(MatchReason::CommitMessage(commit.clone()));
             }
 
             // 3. Browser URL matching
-            for url in &signals.browser_urls {
-                if self.extract_issue_ids(url).contains(&candidate.external_id.to_uppercase()) {
-                    score += 0.80;
-                    reasons.push(MatchReason::BrowserUrl(url.clone()));
-                    break;
-                }
+            if let Some(url) = self.first_mentioning(&signals.browser_urls, candidate) {
+                score += 0.80;
+                reasons.push(MatchReason::BrowserUrl(url.clone()));
             }
 
-            // 4. Semantic similarity (only if we have embeddings)
-            if let Some(ref issue_embedding) = candidate.embedding {
-                let similarity =
-                    EmbeddingService::cosine_similarity(&context_embedding, issue_embedding);
+            // 4. Terminal command matching (e.g. `git checkout PROJ-12`)
+            if let Some(command) = self.first_mentioning(&signals.terminal_commands, candidate) {
+                score += 0.60;
+                reasons.push(MatchReason::TerminalCommand(command.clone()));
+            }
 
-                // Lower threshold to 0.3 for weak context, stronger weight for higher similarity
-                if similarity > 0.3 {
+            // 5. Semantic similarity (only for the issues nearest the context)
+            if let Some(&similarity) = similarities.get(&candidate.id) {
+                // Low floor for weak context, stronger weight for higher similarity
+                if similarity > SEMANTIC_SIMILARITY_FLOOR {
                     // Scale: 0.3-0.5 = low, 0.5-0.7 = medium, 0.7+ = high
                     let semantic_score = if similarity > 0.7 {
                         0.70 // High semantic match
@@ -463,13 +729,8 @@ impl SmartIssueMatcher {
                 }
             }
 
-            // 5. Contextual boosts based on issue status
-            let status_lower = candidate.status.to_lowercase();
-            if status_lower == "in_progress" || status_lower == "in progress" || status_lower == "started" {
-                score += 0.15; // Higher boost for actively worked issues
-            } else if status_lower == "todo" || status_lower == "backlog" {
-                score += 0.05; // Small boost for planned work
-            }
+            // 6. Contextual boosts based on issue status
+            score += status_boost(&candidate.status);
 
             // Only include if we have some signal (lower threshold for weak context)
             if score > 0.0 {
@@ -485,13 +746,52 @@ impl SmartIssueMatcher {
             .into_iter()
             .take(max_results)
             .map(|(candidate, score, reasons)| IssueMatch {
-                issue_id: candidate.external_id,
+                issue_id: candidate.external_id.clone(),
                 confidence: score.min(1.0),
                 match_reasons: reasons,
             })
             .collect())
     }
 
+    /// First of `texts` that mentions the candidate's issue ID
+    fn first_mentioning<'a>(
+        &self,
+        texts: &'a [String],
+        candidate: &IssueCandidate,
+    ) -> Option<&'a String> {
+        let id = candidate.external_id.to_uppercase();
+        texts
+            .iter()
+            .find(|text| self.extract_issue_ids(text).contains(&id))
+    }
+
+    /// Explain what a suggestion run for this project would see
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the candidate query fails
+    pub fn diagnose(
+        &self,
+        signals: &ActivitySignals,
+        project_id: Uuid,
+        top_n: usize,
+    ) -> Result<MatchDiagnosis> {
+        let candidates = self.active_candidates(project_id)?;
+        let context_embedding = match self.generate_context_embedding(signals) {
+            Ok(embedding) => Some(embedding),
+            Err(e) => {
+                log::warn!("Failed to embed activity context: {e}");
+                None
+            }
+        };
+        Ok(MatchDiagnosis::new(
+            &candidates,
+            signals,
+            context_embedding.as_deref(),
+            top_n,
+        ))
+    }
+
     /// Generate embedding for activity context
     fn generate_context_embedding(&self, signals: &ActivitySignals) -> Result<Vec<f32>> {
         let context_text = Self::generate_context_text(signals);
@@ -518,6 +818,11 @@ impl SmartIssueMatcher {
             parts.push(format!("Commit: {commit}"));
         }
 
+        // Latest terminal commands (say what kind of work it is)
+        for command in signals.terminal_commands.iter().rev().take(3) {
+            parts.push(format!("Command: {command}"));
+        }
+
         // Edited files (extract meaningful names)
         for file in signals.edited_files.iter().take(5) {
             if let Some(name) = std::path::Path::new(file).file_name() {
@@ -554,10 +859,7 @@ impl SmartIssueMatcher {
 
     /// Extract issue IDs from text
     pub(crate) fn extract_issue_ids(&self, text: &str) -> Vec<String> {
-        self.issue_id_pattern
-            .find_iter(text)
-            .map(|m| m.as_str().to_uppercase())
-            .collect()
+        find_issue_ids(&self.issue_id_pattern, text)
     }
 
     /// Format match reasons for display
@@ -570,6 +872,7 @@ impl SmartIssueMatcher {
                 MatchReason::CommitMessage(_) => "Commit message".to_string(),
                 MatchReason::BrowserUrl(_) => "Browser URL".to_string(),
                 MatchReason::FilePathPattern(_) => "File path".to_string(),
+                MatchReason::TerminalCommand(_) => "Terminal command".to_string(),
                 MatchReason::SemanticSimilarity(s) => format!("Semantic ({:.0}%)", s * 100.0),
                 MatchReason::RecentlyViewed => "Recently viewed".to_string(),
                 MatchReason::Assigned => "Assigned".to_string(),
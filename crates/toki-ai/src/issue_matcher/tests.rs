@@ -122,6 +122,7 @@ fn test_activity_signals_clone() {
         edited_files: vec!["file.rs".to_string()],
         browser_urls: vec!["https://example.com".to_string()],
         window_titles: vec!["VS Code".to_string()],
+        terminal_commands: vec!["cargo test".to_string()],
         git_branch: Some("feature/test".to_string()),
     };
     let cloned = signals.clone();
@@ -231,6 +232,52 @@ fn test_extract_issue_ids_lowercase() {
     );
 }
 
+#[test]
+fn test_custom_pattern_github_style() {
+    let matcher = IssueMatcher::with_pattern(r"#\d+").unwrap();
+    assert_eq!(
+        matcher.extract_issue_ids("Fix login redirect (#1234, #56)"),
+        vec!["#1234", "#56"]
+    );
+}
+
+#[test]
+fn test_custom_pattern_capture_group_is_the_id() {
+    let matcher = IssueMatcher::with_pattern(r"#(\d+)").unwrap();
+    assert_eq!(
+        matcher.extract_issue_ids("Fix login redirect (#1234, #56)"),
+        vec!["1234", "56"]
+    );
+}
+
+#[test]
+fn test_custom_pattern_jira_style() {
+    let matcher = IssueMatcher::with_pattern(r"(?i)\b[A-Z][A-Z0-9]+-\d+\b").unwrap();
+    assert_eq!(
+        matcher.extract_issue_ids("feature/web2-17-checkout"),
+        vec!["WEB2-17"]
+    );
+}
+
+#[test]
+fn test_custom_pattern_numeric_only() {
+    let matcher = IssueMatcher::with_pattern(r"\b\d{4,}\b").unwrap();
+    assert_eq!(
+        matcher.extract_issue_ids("bugfix/48213-timeout"),
+        vec!["48213"]
+    );
+    assert!(matcher.extract_issue_ids("PROJ-12").is_empty());
+}
+
+#[test]
+fn test_invalid_custom_pattern_rejected() {
+    assert!(IssueMatcher::with_pattern("(unclosed").is_err());
+    assert_eq!(
+        compile_issue_id_pattern(None).unwrap().as_str(),
+        DEFAULT_ISSUE_ID_PATTERN
+    );
+}
+
 #[test]
 fn test_extract_issue_ids_no_match() {
     let matcher = IssueMatcher::new();
@@ -450,6 +497,30 @@ fn test_find_best_match_commit_message() {
     assert_eq!(result.unwrap().issue_id, "TASK-100");
 }
 
+#[test]
+fn test_find_best_match_terminal_command() {
+    let matcher = IssueMatcher::new();
+    let signals = ActivitySignals {
+        terminal_commands: vec!["git checkout -b OPS-17-rollout".to_string()],
+        ..Default::default()
+    };
+    assert!(!signals.is_empty());
+    let candidates = vec![CandidateIssue {
+        external_id: "OPS-17".to_string(),
+        title: "Roll out the new cluster".to_string(),
+        description: None,
+        status: "open".to_string(),
+        labels: vec![],
+        is_assigned_to_user: false,
+    }];
+    let result = matcher.find_best_match(&signals, &candidates).unwrap();
+    assert_eq!(result.issue_id, "OPS-17");
+    assert!(matches!(
+        result.match_reasons[0],
+        MatchReason::TerminalCommand(_)
+    ));
+}
+
 #[test]
 fn test_find_best_match_file_path() {
     let matcher = IssueMatcher::new();
@@ -553,6 +624,7 @@ fn test_find_best_match_confidence_capped() {
         browser_urls: vec!["https://example.com/TEST-1".to_string()],
         edited_files: vec!["TEST-1/file.rs".to_string()],
         window_titles: vec!["TEST-1 - Window".to_string()],
+        terminal_commands: vec!["git checkout TEST-1".to_string()],
     };
     let candidates = vec![CandidateIssue {
         external_id: "TEST-1".to_string(),
@@ -813,9 +885,11 @@ fn test_generate_context_text_combined() {
         edited_files: vec!["/src/main.rs".to_string()],
         browser_urls: vec!["https://docs.rs/something".to_string()],
         window_titles: vec!["Terminal".to_string()],
+        terminal_commands: vec!["cargo test".to_string()],
     };
     let text = SmartIssueMatcher::generate_context_text(&signals);
     assert!(text.contains("Branch: main"));
+    assert!(text.contains("Command: cargo test"));
     assert!(text.contains("Commit: initial commit"));
     assert!(text.contains("File: main.rs"));
     assert!(text.contains("Activity: Terminal"));
@@ -912,3 +986,180 @@ fn test_format_reasons_all_types() {
     assert!(formatted.contains("Recently viewed"));
     assert!(formatted.contains("Assigned"));
 }
+
+// ============================================================================
+// MatchDiagnosis tests
+// ============================================================================
+
+fn embedded_candidate(external_id: &str, embedding: Vec<f32>) -> IssueCandidate {
+    let mut candidate = IssueCandidate::new(
+        Uuid::new_v4(),
+        external_id.to_string(),
+        "plane".to_string(),
+        "Fix login".to_string(),
+    );
+    candidate.embedding = Some(embedding);
+    candidate
+}
+
+fn branch_signals() -> ActivitySignals {
+    ActivitySignals {
+        git_branch: Some("feature/login".to_string()),
+        recent_commits: vec!["fix: login redirect".to_string()],
+        ..ActivitySignals::default()
+    }
+}
+
+#[test]
+fn test_diagnosis_reports_zero_candidates() {
+    let diagnosis = MatchDiagnosis::new(&[], &branch_signals(), Some(&[1.0, 0.0]), 5);
+    assert_eq!(diagnosis.active_candidates, 0);
+    assert!(diagnosis.has_signals());
+    assert_eq!(diagnosis.cause(), NoMatchCause::NoCandidates);
+}
+
+#[test]
+fn test_diagnosis_reports_zero_signals() {
+    let candidates = vec![embedded_candidate("PROJ-1", vec![1.0, 0.0])];
+    let signals = ActivitySignals::default();
+    assert!(signals.is_empty());
+
+    let diagnosis = MatchDiagnosis::new(&candidates, &signals, Some(&[1.0, 0.0]), 5);
+    assert_eq!(diagnosis.active_candidates, 1);
+    assert!(!diagnosis.has_signals());
+    assert_eq!(diagnosis.cause(), NoMatchCause::NoSignals);
+}
+
+#[test]
+fn test_diagnosis_lists_raw_scores_below_floor() {
+    let candidates = vec![
+        embedded_candidate("PROJ-1", vec![0.0, 1.0]),
+        embedded_candidate("PROJ-2", vec![0.2, 1.0]),
+    ];
+    let diagnosis = MatchDiagnosis::new(&candidates, &branch_signals(), Some(&[1.0, 0.0]), 5);
+
+    assert_eq!(diagnosis.cause(), NoMatchCause::BelowThreshold);
+    assert_eq!(diagnosis.top_similarities.len(), 2);
+    assert_eq!(diagnosis.top_similarities[0].0, "PROJ-2");
+    assert!(diagnosis.top_similarities[0].1 < SEMANTIC_SIMILARITY_FLOOR);
+}
+
+#[test]
+fn test_diagnosis_without_embedding_model() {
+    let candidates = vec![embedded_candidate("PROJ-1", vec![1.0, 0.0])];
+    let diagnosis = MatchDiagnosis::new(&candidates, &branch_signals(), None, 5);
+    assert!(!diagnosis.embeddings_available);
+    assert!(diagnosis.top_similarities.is_empty());
+    assert_eq!(diagnosis.cause(), NoMatchCause::EmbeddingsUnavailable);
+}
+
+// ============================================================================
+// Candidate warmup tests
+// ============================================================================
+
+fn linked_project(db: &Database, name: &str) -> Uuid {
+    let project = db
+        .get_or_create_project(name, &format!("/src/{name}"))
+        .unwrap();
+    db.link_project_to_pm(project.id, "plane", name, None)
+        .unwrap();
+    project.id
+}
+
+fn stored_candidate(db: &Database, project_id: Uuid, external_id: &str, status: &str) {
+    let mut candidate = IssueCandidate::new(
+        project_id,
+        external_id.to_string(),
+        "plane".to_string(),
+        format!("Issue {external_id}"),
+    );
+    candidate.status = status.to_string();
+    db.upsert_issue_candidate(&candidate).unwrap();
+}
+
+#[test]
+fn test_warmup_ranks_every_linked_project() {
+    let db = Database::new(Some(std::path::PathBuf::from(":memory:"))).unwrap();
+    let projects: Vec<Uuid> = (0..5)
+        .map(|i| linked_project(&db, &format!("project-{i}")))
+        .collect();
+    for (i, project_id) in projects.iter().enumerate() {
+        stored_candidate(&db, *project_id, &format!("P{i}-1"), "backlog");
+        stored_candidate(&db, *project_id, &format!("P{i}-2"), "in_progress");
+        stored_candidate(&db, *project_id, &format!("P{i}-3"), "done");
+    }
+    let unlinked = db.get_or_create_project("scratch", "/src/scratch").unwrap();
+    stored_candidate(&db, unlinked.id, "S-1", "in_progress");
+
+    let cache = CandidateCache::new();
+    assert_eq!(cache.warm(&db, 2).unwrap(), projects.len());
+    assert_eq!(cache.len(), projects.len());
+    assert!(cache.get(unlinked.id).is_none());
+
+    for (i, project_id) in projects.iter().enumerate() {
+        let ranked = cache.get(*project_id).unwrap();
+        let ids: Vec<&str> = ranked.iter().map(|c| c.external_id.as_str()).collect();
+        assert_eq!(ids, [format!("P{i}-2"), format!("P{i}-1")]);
+    }
+}
+
+#[test]
+fn test_warmup_caches_project_without_candidates() {
+    let db = Database::new(Some(std::path::PathBuf::from(":memory:"))).unwrap();
+    let empty = linked_project(&db, "empty");
+    let busy = linked_project(&db, "busy");
+    stored_candidate(&db, busy, "B-1", "todo");
+
+    let cache = CandidateCache::new();
+    assert_eq!(cache.warm(&db, 8).unwrap(), 2);
+    assert!(cache.get(empty).unwrap().is_empty());
+    assert_eq!(cache.get(busy).unwrap().len(), 1);
+
+    // No linked projects leaves an empty cache rather than failing
+    let fresh = Database::new(Some(std::path::PathBuf::from(":memory:"))).unwrap();
+    assert_eq!(cache.warm(&fresh, 0).unwrap(), 0);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_cache_rewarmed_after_sync() {
+    let db = Database::new(Some(std::path::PathBuf::from(":memory:"))).unwrap();
+    let project_id = linked_project(&db, "api");
+    stored_candidate(&db, project_id, "API-1", "todo");
+
+    let cache = CandidateCache::new();
+    assert!(cache.refresh(&db, 2).unwrap());
+    assert!(!cache.refresh(&db, 2).unwrap());
+
+    // A later sync changes the candidates, so the next refresh re-warms
+    stored_candidate(&db, project_id, "API-2", "in_progress");
+    assert_eq!(cache.get(project_id).unwrap().len(), 1);
+    assert!(cache.refresh(&db, 2).unwrap());
+    let ranked = cache.get(project_id).unwrap();
+    let ids: Vec<&str> = ranked.iter().map(|c| c.external_id.as_str()).collect();
+    assert_eq!(ids, ["API-2", "API-1"]);
+}
+
+#[test]
+fn test_rank_candidates_prefers_active_and_embedded_issues() {
+    let project_id = Uuid::new_v4();
+    let candidate = |id: &str, status: &str, embedding: Option<Vec<f32>>| {
+        let mut candidate = IssueCandidate::new(
+            project_id,
+            id.to_string(),
+            "plane".to_string(),
+            id.to_string(),
+        );
+        candidate.status = status.to_string();
+        candidate.embedding = embedding;
+        candidate
+    };
+    let ranked = rank_candidates(vec![
+        candidate("A", "review", None),
+        candidate("B", "backlog", None),
+        candidate("C", "backlog", Some(vec![1.0])),
+        candidate("D", "In Progress", None),
+    ]);
+    let ids: Vec<&str> = ranked.iter().map(|c| c.external_id.as_str()).collect();
+    assert_eq!(ids, ["D", "C", "B", "A"]);
+}
@@ -0,0 +1,155 @@
+//! Issue candidate warmup
+//!
+//! Ranks the active issue candidates of every PM-linked project up front, so
+//! a long-running process (the MCP server) does not read them again for every
+//! match. The database is only read, and only from the calling thread; the
+//! bounded worker pool ranks candidates that are already in memory. Candidates
+//! keep the embeddings stored by issue sync; none are computed here.
+//!
+//! The cache remembers [`Database::issue_candidates_version`] at warmup, and
+//! [`CandidateCache::refresh`] warms it again once any process has synced.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+use anyhow::Result;
+use uuid::Uuid;
+
+use toki_storage::db::Database;
+use toki_storage::models::IssueCandidate;
+
+use super::status_boost;
+
+/// Ranked issue candidates per linked project
+#[derive(Debug, Default)]
+pub struct CandidateCache {
+    ranked: RwLock<HashMap<Uuid, Arc<Vec<IssueCandidate>>>>,
+    /// Candidates version the cache was warmed at
+    version: RwLock<Option<String>>,
+}
+
+impl CandidateCache {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ranked candidates for a project, if it was warmed
+    #[must_use]
+    pub fn get(&self, project_id: Uuid) -> Option<Arc<Vec<IssueCandidate>>> {
+        self.ranked.read().ok()?.get(&project_id).cloned()
+    }
+
+    /// Number of warmed projects
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ranked.read().map_or(0, |ranked| ranked.len())
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Read and rank the candidates of every PM-linked project
+    ///
+    /// Replaces whatever was cached before. Returns the number of projects
+    /// warmed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the linked projects or their candidates cannot be read
+    pub fn warm(&self, database: &Database, threads: usize) -> Result<usize> {
+        let version = database.issue_candidates_version()?;
+        let warmed = self.rank_all(read_linked_candidates(database)?, threads)?;
+        *self
+            .version
+            .write()
+            .map_err(|e| anyhow::anyhow!("Failed to lock candidate cache: {e}"))? = Some(version);
+        Ok(warmed)
+    }
+
+    /// Warm the cache again if candidates changed since the last warmup
+    ///
+    /// Returns whether the cache was rebuilt.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the candidates cannot be read
+    pub fn refresh(&self, database: &Database, threads: usize) -> Result<bool> {
+        let current = database.issue_candidates_version()?;
+        let warmed_at = self.version.read().ok().and_then(|version| version.clone());
+        if warmed_at.as_deref() == Some(current.as_str()) {
+            return Ok(false);
+        }
+        self.warm(database, threads)?;
+        Ok(true)
+    }
+
+    /// Rank already-read candidates, `threads` projects at a time
+    ///
+    /// Replaces whatever was cached before. A project without active
+    /// candidates is cached with an empty list. Returns the number of
+    /// projects warmed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a ranking worker panicked
+    fn rank_all(
+        &self,
+        pending: Vec<(Uuid, Vec<IssueCandidate>)>,
+        threads: usize,
+    ) -> Result<usize> {
+        let pending: Vec<_> = pending.into_iter().map(Mutex::new).collect();
+        let next = AtomicUsize::new(0);
+        let ranked = Mutex::new(HashMap::with_capacity(pending.len()));
+        std::thread::scope(|scope| {
+            for _ in 0..threads.clamp(1, pending.len().max(1)) {
+                scope.spawn(|| {
+                    while let Some(slot) = pending.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let Ok(mut slot) = slot.lock() else { continue };
+                        let (project_id, candidates) = std::mem::take(&mut *slot);
+                        let candidates = Arc::new(rank_candidates(candidates));
+                        if let Ok(mut ranked) = ranked.lock() {
+                            ranked.insert(project_id, candidates);
+                        }
+                    }
+                });
+            }
+        });
+
+        let ranked = ranked
+            .into_inner()
+            .map_err(|e| anyhow::anyhow!("Candidate warmup worker panicked: {e}"))?;
+        let warmed = ranked.len();
+        *self
+            .ranked
+            .write()
+            .map_err(|e| anyhow::anyhow!("Failed to lock candidate cache: {e}"))? = ranked;
+        Ok(warmed)
+    }
+}
+
+/// Active candidates of every PM-linked project
+fn read_linked_candidates(database: &Database) -> Result<Vec<(Uuid, Vec<IssueCandidate>)>> {
+    database
+        .get_projects_with_pm_link()?
+        .into_iter()
+        .map(|project| Ok((project.id, database.get_active_issue_candidates(project.id)?)))
+        .collect()
+}
+
+/// Order candidates by how likely they are to be worked on
+///
+/// In-progress issues come first, then planned ones, then the rest; issues
+/// with an embedding go ahead of those without. Ties keep their sync order.
+#[must_use]
+pub fn rank_candidates(mut candidates: Vec<IssueCandidate>) -> Vec<IssueCandidate> {
+    candidates.sort_by(|a, b| {
+        status_boost(&b.status)
+            .total_cmp(&status_boost(&a.status))
+            .then_with(|| b.embedding.is_some().cmp(&a.embedding.is_some()))
+    });
+    candidates
+}
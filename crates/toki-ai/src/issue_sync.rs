@@ -1,18 +1,24 @@
 //! Issue synchronization service for AI matching
 //!
-//! Syncs issues from PM systems (Plane.so, Notion) to local database
+//! Syncs issues from PM systems (Plane.so, Notion, Trello) to local database
 //! and computes embeddings for semantic matching.
 
 use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use std::fmt::Write;
+use std::future::Future;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
-use toki_integrations::plane::PlaneClient;
-use toki_integrations::notion::{NotionClient, PropertyMappingConfig};
+use toki_integrations::plane::{PlaneClient, PlaneState, PlaneWorkItem};
+use toki_integrations::notion::{
+    NotionClient, PageQuery, PagedResults, PaginationMode, PropertyMappingConfig,
+};
+use toki_integrations::trello::TrelloClient;
 use toki_storage::db::Database;
 use toki_storage::models::{IssueCandidate, Project};
 
-use crate::embedding::EmbeddingService;
+use crate::embedding::{EmbeddingService, EMBEDDING_BATCH_SIZE};
 
 /// Statistics from issue sync operation
 #[derive(Debug, Default)]
@@ -37,24 +43,280 @@ impl std::fmt::Display for SyncStats {
     }
 }
 
+/// Cosine similarity above which two issues are reported as possible duplicates
+pub const DUPLICATE_SIMILARITY_THRESHOLD: f32 = 0.95;
+
+/// A pair of issue candidates whose embeddings are nearly identical
+#[derive(Debug, Clone)]
+pub struct DuplicatePair {
+    pub first: IssueCandidate,
+    pub second: IssueCandidate,
+    pub similarity: f32,
+}
+
+/// Find pairs of candidates whose embeddings exceed the similarity threshold
+///
+/// Candidates without embeddings are skipped. Pairs are ranked by similarity,
+/// most similar first.
+#[must_use]
+pub fn find_duplicate_pairs(candidates: &[IssueCandidate], threshold: f32) -> Vec<DuplicatePair> {
+    let embedded: Vec<(&IssueCandidate, &Vec<f32>)> = candidates
+        .iter()
+        .filter_map(|c| c.embedding.as_ref().map(|e| (c, e)))
+        .collect();
+
+    let mut pairs = Vec::new();
+    for (i, (first, first_embedding)) in embedded.iter().enumerate() {
+        for (second, second_embedding) in &embedded[i + 1..] {
+            let similarity = EmbeddingService::cosine_similarity(first_embedding, second_embedding);
+            if similarity >= threshold {
+                pairs.push(DuplicatePair {
+                    first: (*first).clone(),
+                    second: (*second).clone(),
+                    similarity,
+                });
+            }
+        }
+    }
+
+    pairs.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    pairs
+}
+
+/// Find possible duplicate issues within each linked project
+///
+/// Only active issues are compared; nothing is merged, pairs are just reported.
+///
+/// # Errors
+///
+/// Returns an error if database queries fail
+pub fn find_duplicate_issues(database: &Database, threshold: f32) -> Result<Vec<DuplicatePair>> {
+    let mut pairs = Vec::new();
+    for project in database.get_projects_with_pm_link()? {
+        let candidates = database.get_active_issue_candidates(project.id)?;
+        pairs.extend(find_duplicate_pairs(&candidates, threshold));
+    }
+
+    pairs.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    Ok(pairs)
+}
+
+/// Keep the checkpoint of `database_id` in step with a fetch's outcome
+///
+/// A fetch that stopped early saves the cursor it stopped at, so the next sync
+/// continues there rather than at page one; a complete fetch clears it.
+fn record_checkpoint<T>(
+    database: &Database,
+    database_id: &str,
+    fetched: &PagedResults<T>,
+) -> Result<()> {
+    match &fetched.resume_cursor {
+        Some(cursor) => database.save_sync_checkpoint(database_id, cursor),
+        None => database.clear_sync_checkpoint(database_id),
+    }
+}
+
+/// Run a paginated Notion fetch from the checkpoint saved under `key`
+///
+/// The checkpoint is updated from the outcome as in [`record_checkpoint`]. A
+/// saved cursor that Notion rejects is dropped and the fetch starts over.
+///
+/// # Errors
+///
+/// Returns an error if the checkpoint cannot be read or written, or the fetch
+/// fails before its first page
+pub(crate) async fn fetch_from_checkpoint<T, F, Fut>(
+    database: &Database,
+    key: &str,
+    fetch: F,
+) -> Result<PagedResults<T>>
+where
+    F: Fn(Option<String>) -> Fut,
+    Fut: Future<Output = Result<PagedResults<T>>>,
+{
+    let fetched = match database.get_sync_checkpoint(key)? {
+        Some(cursor) => {
+            log::info!("Resuming Notion fetch for {key} from saved cursor");
+            match fetch(Some(cursor)).await {
+                Ok(fetched) => fetched,
+                Err(e) => {
+                    log::warn!("Saved Notion cursor was rejected ({e:#}); starting over");
+                    fetch(None).await?
+                }
+            }
+        }
+        None => fetch(None).await?,
+    };
+    record_checkpoint(database, key, &fetched)?;
+    Ok(fetched)
+}
+
+/// Cosine similarity every pair in a reported cluster must reach
+pub const CLUSTER_SIMILARITY_THRESHOLD: f32 = 0.85;
+
+/// Most clusters shown after a sync
+pub const MAX_REPORTED_CLUSTERS: usize = 3;
+
+/// A group of issues whose embeddings are all close to each other
+#[derive(Debug, Clone)]
+pub struct IssueCluster {
+    pub members: Vec<IssueCandidate>,
+    /// Lowest pairwise similarity within the cluster
+    pub min_similarity: f32,
+}
+
+/// Group candidates into tight clusters of similar embeddings
+///
+/// An issue joins a cluster only if it is within `threshold` of every member, so
+/// a chain of loosely related issues never collapses into one cluster. Each issue
+/// belongs to at most one cluster; singletons are dropped. Clusters are ranked by
+/// size, then by tightness.
+#[must_use]
+pub fn find_similarity_clusters(
+    candidates: &[IssueCandidate],
+    threshold: f32,
+) -> Vec<IssueCluster> {
+    let embedded: Vec<(&IssueCandidate, &Vec<f32>)> = candidates
+        .iter()
+        .filter_map(|c| c.embedding.as_ref().map(|e| (c, e)))
+        .collect();
+
+    let mut assigned = vec![false; embedded.len()];
+    let mut clusters = Vec::new();
+    for seed in 0..embedded.len() {
+        if assigned[seed] {
+            continue;
+        }
+        let mut members = vec![seed];
+        let mut min_similarity = 1.0_f32;
+        for other in seed + 1..embedded.len() {
+            if assigned[other] {
+                continue;
+            }
+            let similarities: Vec<f32> = members
+                .iter()
+                .map(|&m| EmbeddingService::cosine_similarity(embedded[m].1, embedded[other].1))
+                .collect();
+            if similarities.iter().all(|&s| s >= threshold) {
+                members.push(other);
+                min_similarity = similarities.into_iter().fold(min_similarity, f32::min);
+            }
+        }
+
+        if members.len() > 1 {
+            for &m in &members {
+                assigned[m] = true;
+            }
+            clusters.push(IssueCluster {
+                members: members.iter().map(|&m| embedded[m].0.clone()).collect(),
+                min_similarity,
+            });
+        }
+    }
+
+    clusters.sort_by(|a, b| {
+        b.members
+            .len()
+            .cmp(&a.members.len())
+            .then(b.min_similarity.total_cmp(&a.min_similarity))
+    });
+    clusters
+}
+
+/// Find the tightest clusters of similar active issues within each linked project
+///
+/// Meant as a quick look at whether embeddings separate the user's issues in a
+/// meaningful way. Returns at most `limit` clusters.
+///
+/// # Errors
+///
+/// Returns an error if database queries fail
+pub fn find_issue_clusters(
+    database: &Database,
+    threshold: f32,
+    limit: usize,
+) -> Result<Vec<IssueCluster>> {
+    let mut clusters = Vec::new();
+    for project in database.get_projects_with_pm_link()? {
+        let candidates = database.get_active_issue_candidates(project.id)?;
+        clusters.extend(find_similarity_clusters(&candidates, threshold));
+    }
+
+    clusters.sort_by(|a, b| {
+        b.members
+            .len()
+            .cmp(&a.members.len())
+            .then(b.min_similarity.total_cmp(&a.min_similarity))
+    });
+    clusters.truncate(limit);
+    Ok(clusters)
+}
+
+/// Hash of the exact text fed to the embedding model
+#[must_use]
+pub fn embedding_input_hash(text: &str) -> String {
+    hmac_sha256::Hash::hash(text.as_bytes())
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, b| {
+            let _ = write!(hex, "{b:02x}");
+            hex
+        })
+}
+
+/// Whether a candidate's stored embedding no longer matches its embedding input
+///
+/// Only fields that feed the embedding text count; a status-only change keeps
+/// the hash and skips recomputation.
+fn embedding_is_stale(
+    stored_hash: Option<&str>,
+    candidate: &IssueCandidate,
+    include_labels: bool,
+) -> bool {
+    let input_hash = embedding_input_hash(&candidate.embedding_input(include_labels));
+    stored_hash != Some(input_hash.as_str())
+}
+
+/// Whether an automatic embedding build is due
+///
+/// A build within the last `refresh_hours` is fresh enough; 0 always rebuilds.
+#[must_use]
+pub fn embedding_build_due(
+    last_build: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    refresh_hours: u32,
+) -> bool {
+    last_build.is_none_or(|built_at| now - built_at >= Duration::hours(refresh_hours.into()))
+}
+
 /// Service for syncing issues from PM systems and computing embeddings
 pub struct IssueSyncService {
-    embedding_service: Arc<Mutex<EmbeddingService>>,
+    /// `None` when embeddings are disabled (`ai.embeddings = false`)
+    embedding_service: Option<Arc<Mutex<EmbeddingService>>>,
     database: Arc<Database>,
+    embed_labels: bool,
 }
 
 impl IssueSyncService {
     /// Create a new issue sync service
     ///
+    /// With `embeddings_enabled` off (`ai.embeddings = false`), issues are synced
+    /// without computing embeddings.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the embedding service fails to initialize
-    pub fn new(database: Arc<Database>) -> Result<Self> {
-        let embedding_service = EmbeddingService::new()?;
-        Ok(Self {
-            embedding_service: Arc::new(Mutex::new(embedding_service)),
+    /// Returns an error if the shared embedding service fails to initialize
+    pub fn new(database: Arc<Database>, embeddings_enabled: bool) -> Result<Self> {
+        if !embeddings_enabled {
+            return Ok(Self {
+                embedding_service: None,
+                database,
+                embed_labels: true,
+            });
+        }
+        Ok(Self::with_embedding_service(
             database,
-        })
+            EmbeddingService::shared(embeddings_enabled)?,
+        ))
     }
 
     /// Create with an existing embedding service (for sharing across components)
@@ -64,11 +326,36 @@ impl IssueSyncService {
         embedding_service: Arc<Mutex<EmbeddingService>>,
     ) -> Self {
         Self {
-            embedding_service,
+            embedding_service: Some(embedding_service),
             database,
+            embed_labels: true,
         }
     }
 
+    /// Choose whether issue labels are part of the embedding input (default: true)
+    #[must_use]
+    pub const fn with_label_embedding(mut self, include_labels: bool) -> Self {
+        self.embed_labels = include_labels;
+        self
+    }
+
+    /// Check whether a synced candidate needs its embedding (re)computed
+    fn needs_embedding(
+        &self,
+        existing: Option<&IssueCandidate>,
+        candidate: &IssueCandidate,
+    ) -> Result<bool> {
+        let stored_hash = match existing {
+            Some(e) if e.embedding.is_some() => self.database.get_issue_embedding_hash(e.id)?,
+            _ => None,
+        };
+        Ok(embedding_is_stale(
+            stored_hash.as_deref(),
+            candidate,
+            self.embed_labels,
+        ))
+    }
+
     /// Sync issues from Plane.so for a specific project
     ///
     /// # Errors
@@ -114,8 +401,8 @@ impl IssueSyncService {
         // Fetch states to build state_id -> state_name mapping
         let work_states = plane_client.list_states(&project_uuid).await?;
         let state_map: std::collections::HashMap<Uuid, String> = work_states
-            .into_iter()
-            .map(|s| (s.id, s.name))
+            .iter()
+            .map(|s| (s.id, s.name.clone()))
             .collect();
 
         log::debug!("Loaded {} states for project", state_map.len());
@@ -141,6 +428,7 @@ impl IssueSyncService {
         log::info!("Fetched {} work items from Plane", all_items.len());
 
         // Process each work item
+        let mut to_embed = Vec::new();
         for item in &all_items {
             let candidate_data =
                 PlaneClient::work_item_to_issue_candidate(item, Some(project_identifier), Some(&state_map));
@@ -150,10 +438,6 @@ impl IssueSyncService {
                 .database
                 .get_issue_candidate(&candidate_data.external_id, "plane")?;
 
-            let needs_embedding = existing
-                .as_ref()
-                .is_none_or(|e| e.embedding.is_none());
-
             // Create IssueCandidate
             let mut candidate = IssueCandidate::new(
                 local_project.id,
@@ -174,27 +458,61 @@ impl IssueSyncService {
                 stats.issues_synced += 1;
             }
 
+            let needs_embedding = self.needs_embedding(existing.as_ref(), &candidate)?;
+
             // Upsert to database
             self.database.upsert_issue_candidate(&candidate)?;
 
-            // Compute embedding if needed
-            if needs_embedding {
-                match self.compute_and_store_embedding(&candidate) {
-                    Ok(()) => stats.embeddings_computed += 1,
-                    Err(e) => {
-                        stats.errors.push(format!(
-                            "Failed to compute embedding for {}: {e}",
-                            candidate.external_id
-                        ));
-                    }
+            // Record when the issue was completed (only looked up once)
+            if existing.as_ref().is_none_or(|e| e.done_at.is_none()) {
+                if let Err(e) = self
+                    .record_plane_completion(plane_client, &project_uuid, item, &work_states, &candidate)
+                    .await
+                {
+                    stats.errors.push(format!(
+                        "Failed to fetch activity for {}: {e}",
+                        candidate.external_id
+                    ));
                 }
             }
+
+            if needs_embedding {
+                to_embed.push(candidate);
+            }
         }
 
+        // Compute embeddings in batches rather than one model call per issue
+        self.compute_and_store_embeddings(&to_embed, &mut stats);
+
         log::info!("Issue sync complete: {stats}");
         Ok(stats)
     }
 
+    /// Store the done timestamp for a completed Plane work item from its activity log
+    async fn record_plane_completion(
+        &self,
+        plane_client: &PlaneClient,
+        project_uuid: &Uuid,
+        item: &PlaneWorkItem,
+        states: &[PlaneState],
+        candidate: &IssueCandidate,
+    ) -> Result<()> {
+        let is_completed = item.state.is_some_and(|state_id| {
+            states
+                .iter()
+                .any(|s| s.id == state_id && s.group.as_deref() == Some("completed"))
+        });
+        if !is_completed {
+            return Ok(());
+        }
+
+        let activities = plane_client.get_issue_activity(project_uuid, &item.id).await?;
+        if let Some(done_at) = PlaneClient::completed_at(&activities, states) {
+            self.database.update_issue_done_at(candidate.id, done_at)?;
+        }
+        Ok(())
+    }
+
     /// Sync issues from Notion for a specific project
     ///
     /// # Arguments
@@ -232,22 +550,39 @@ impl IssueSyncService {
             local_project.name
         );
 
-        // Fetch all pages as issue candidates
-        let candidates = notion_client
-            .fetch_database_as_issues(&database_id, config, fetch_blocks)
-            .await?;
+        // Fetch all pages as issue candidates; if a later page fails, keep what was fetched
+        // and resume from there on the next sync
+        let (database, workspace) = (database_id.as_str(), local_project.pm_workspace.as_deref());
+        let fetch = move |start_cursor: Option<String>| async move {
+            let query = PageQuery {
+                mode: PaginationMode::Lenient,
+                start_cursor: start_cursor.as_deref(),
+                edited_since: None,
+            };
+            notion_client
+                .fetch_database_as_issues(database, workspace, config, fetch_blocks, query)
+                .await
+        };
+        let fetched = fetch_from_checkpoint(&self.database, &database_id, fetch).await?;
+        if let Some(error) = &fetched.error {
+            stats.errors.push(format!(
+                "Notion fetch for '{}' stopped early at cursor {}: {error}",
+                local_project.name,
+                fetched.resume_cursor.as_deref().unwrap_or("(start)")
+            ));
+        }
+        let candidates = fetched.items;
 
         log::info!("Fetched {} pages from Notion", candidates.len());
 
         // Process each candidate
+        let mut to_embed = Vec::new();
         for candidate_data in &candidates {
             // Check if we need to update or insert
             let existing = self
                 .database
                 .get_issue_candidate(&candidate_data.external_id, "notion")?;
 
-            let needs_embedding = existing.as_ref().is_none_or(|e| e.embedding.is_none());
-
             // Create IssueCandidate
             let mut candidate = IssueCandidate::new(
                 local_project.id,
@@ -269,32 +604,95 @@ impl IssueSyncService {
                 stats.issues_synced += 1;
             }
 
+            let needs_embedding = self.needs_embedding(existing.as_ref(), &candidate)?;
+
             // Upsert to database
             self.database.upsert_issue_candidate(&candidate)?;
 
-            // Compute embedding if needed
             if needs_embedding {
-                match self.compute_and_store_embedding(&candidate) {
-                    Ok(()) => stats.embeddings_computed += 1,
-                    Err(e) => {
-                        stats.errors.push(format!(
-                            "Failed to compute embedding for {}: {e}",
-                            candidate.external_id
-                        ));
-                    }
-                }
+                to_embed.push(candidate);
             }
         }
 
+        self.compute_and_store_embeddings(&to_embed, &mut stats);
+
         log::info!("Notion issue sync complete: {stats}");
         Ok(stats)
     }
 
-    /// Sync all projects that have PM links (Plane or Notion)
+    /// Sync open cards from a Trello board for a specific project
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if API calls fail or database operations fail
+    pub async fn sync_trello_project_issues(
+        &self,
+        trello_client: &TrelloClient,
+        local_project: &Project,
+    ) -> Result<SyncStats> {
+        let mut stats = SyncStats::default();
+
+        let Some(board_id) = local_project.pm_project_id.clone() else {
+            stats.errors.push(format!(
+                "Project '{}' has no Trello board linked",
+                local_project.name
+            ));
+            return Ok(stats);
+        };
+
+        log::info!(
+            "Syncing cards from Trello board {} for local project '{}'",
+            board_id,
+            local_project.name
+        );
+
+        let cards = trello_client.list_cards(&board_id).await?;
+        log::info!("Fetched {} cards from Trello", cards.len());
+
+        let mut to_embed = Vec::new();
+        for card in cards {
+            let existing = self
+                .database
+                .get_issue_candidate(&card.external_id, "trello")?;
+
+            let mut candidate = IssueCandidate::new(
+                local_project.id,
+                card.external_id,
+                card.external_system,
+                card.title,
+            );
+            candidate.pm_project_id = Some(board_id.clone());
+            candidate.description = card.description;
+            candidate.status = card.status;
+            candidate.labels = card.labels;
+
+            // Preserve existing ID if updating
+            if let Some(existing_candidate) = &existing {
+                candidate.id = existing_candidate.id;
+                stats.issues_updated += 1;
+            } else {
+                stats.issues_synced += 1;
+            }
+
+            let needs_embedding = self.needs_embedding(existing.as_ref(), &candidate)?;
+            self.database.upsert_issue_candidate(&candidate)?;
+            if needs_embedding {
+                to_embed.push(candidate);
+            }
+        }
+
+        self.compute_and_store_embeddings(&to_embed, &mut stats);
+
+        log::info!("Trello card sync complete: {stats}");
+        Ok(stats)
+    }
+
+    /// Sync all projects that have PM links (Plane, Notion or Trello)
     ///
     /// # Arguments
     /// * `plane_client` - Optional Plane.so client
     /// * `notion_client` - Optional Notion client
+    /// * `trello_client` - Optional Trello client
     ///
     /// # Errors
     ///
@@ -303,19 +701,39 @@ impl IssueSyncService {
         &self,
         plane_client: Option<&PlaneClient>,
         notion_client: Option<&NotionClient>,
+        trello_client: Option<&TrelloClient>,
     ) -> Result<SyncStats> {
-        let mut total_stats = SyncStats::default();
-
         let linked_projects = self.database.get_projects_with_pm_link()?;
 
         if linked_projects.is_empty() {
             log::info!("No projects with PM links found");
-            return Ok(total_stats);
+            return Ok(SyncStats::default());
         }
 
+        self.sync_linked_projects(&linked_projects, plane_client, notion_client, trello_client)
+            .await
+    }
+
+    /// Sync the given PM-linked projects (Plane, Notion or Trello)
+    ///
+    /// Projects whose PM system has no client are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database operations fail; per-project sync failures
+    /// are collected in [`SyncStats::errors`]
+    pub async fn sync_linked_projects(
+        &self,
+        linked_projects: &[Project],
+        plane_client: Option<&PlaneClient>,
+        notion_client: Option<&NotionClient>,
+        trello_client: Option<&TrelloClient>,
+    ) -> Result<SyncStats> {
+        let mut total_stats = SyncStats::default();
+
         log::info!("Syncing {} linked projects", linked_projects.len());
 
-        for project in &linked_projects {
+        for project in linked_projects {
             let result = match project.pm_system.as_deref() {
                 Some("plane") => {
                     if let Some(client) = plane_client {
@@ -340,6 +758,17 @@ impl IssueSyncService {
                         continue;
                     }
                 }
+                Some("trello") => {
+                    if let Some(client) = trello_client {
+                        self.sync_trello_project_issues(client, project).await
+                    } else {
+                        log::debug!(
+                            "Skipping Trello project '{}': no Trello client provided",
+                            project.name
+                        );
+                        continue;
+                    }
+                }
                 other => {
                     log::debug!(
                         "Skipping project '{}': unsupported PM system {:?}",
@@ -378,32 +807,57 @@ impl IssueSyncService {
         &self,
         plane_client: &PlaneClient,
     ) -> Result<SyncStats> {
-        self.sync_all_linked_projects_multi(Some(plane_client), None)
+        self.sync_all_linked_projects_multi(Some(plane_client), None, None)
             .await
     }
 
-    /// Compute embedding for an issue and store it
-    fn compute_and_store_embedding(&self, candidate: &IssueCandidate) -> Result<()> {
-        let text = candidate.embedding_text();
-
-        let embedding = {
-            let mut service = self
-                .embedding_service
-                .lock()
-                .map_err(|e| anyhow::anyhow!("Failed to lock embedding service: {e}"))?;
-            service.generate_embedding(&text)?
+    /// Compute embeddings for issues in batches and store them
+    ///
+    /// The embedding service is locked once per batch, not per issue.
+    /// Failures are recorded per issue in `stats.errors`. Does nothing when
+    /// embeddings are disabled.
+    fn compute_and_store_embeddings(&self, candidates: &[IssueCandidate], stats: &mut SyncStats) {
+        let Some(embedding_service) = &self.embedding_service else {
+            return;
         };
+        for batch in candidates.chunks(EMBEDDING_BATCH_SIZE) {
+            let texts: Vec<String> = batch
+                .iter()
+                .map(|candidate| candidate.embedding_input(self.embed_labels))
+                .collect();
 
-        self.database
-            .update_issue_embedding(candidate.id, &embedding)?;
-
-        log::debug!(
-            "Computed embedding for {} ({} dims)",
-            candidate.external_id,
-            embedding.len()
-        );
+            let embeddings = embedding_service
+                .lock()
+                .map_err(|e| anyhow::anyhow!("Failed to lock embedding service: {e}"))
+                .and_then(|mut service| service.generate_embeddings(&texts));
+            let embeddings = match embeddings {
+                Ok(embeddings) => embeddings,
+                Err(e) => {
+                    stats.errors.extend(batch.iter().map(|candidate| {
+                        format!(
+                            "Failed to compute embedding for {}: {e}",
+                            candidate.external_id
+                        )
+                    }));
+                    continue;
+                }
+            };
 
-        Ok(())
+            for ((candidate, text), embedding) in batch.iter().zip(&texts).zip(&embeddings) {
+                let input_hash = embedding_input_hash(text);
+                match self
+                    .database
+                    .update_issue_embedding(candidate.id, embedding, &input_hash)
+                {
+                    Ok(()) => stats.embeddings_computed += 1,
+                    Err(e) => stats.errors.push(format!(
+                        "Failed to store embedding for {}: {e}",
+                        candidate.external_id
+                    )),
+                }
+            }
+            log::debug!("Computed {} embeddings", embeddings.len());
+        }
     }
 
     /// Recompute embeddings for all issues without embeddings
@@ -412,33 +866,56 @@ impl IssueSyncService {
     ///
     /// Returns an error if database operations fail
     pub fn recompute_missing_embeddings(&self) -> Result<usize> {
-        let linked_projects = self.database.get_projects_with_pm_link()?;
-        let mut computed = 0;
+        let mut missing = Vec::new();
+        for project in &self.database.get_projects_with_pm_link()? {
+            missing.extend(
+                self.database
+                    .get_issue_candidates_for_project(project.id)?
+                    .into_iter()
+                    .filter(|candidate| candidate.embedding.is_none()),
+            );
+        }
 
-        for project in &linked_projects {
-            let candidates = self.database.get_issue_candidates_for_project(project.id)?;
+        let mut stats = SyncStats::default();
+        self.compute_and_store_embeddings(&missing, &mut stats);
+        for error in &stats.errors {
+            log::warn!("{error}");
+        }
+        Ok(stats.embeddings_computed)
+    }
 
-            for candidate in candidates {
-                if candidate.embedding.is_none() {
-                    if let Err(e) = self.compute_and_store_embedding(&candidate) {
-                        log::warn!(
-                            "Failed to compute embedding for {}: {e}",
-                            candidate.external_id
-                        );
-                    } else {
-                        computed += 1;
-                    }
-                }
-            }
+    /// Recompute missing embeddings unless the last build is still fresh
+    ///
+    /// Returns `None` when the build was skipped, always the case with embeddings
+    /// disabled. `force` ignores the freshness window.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database operations fail
+    pub fn rebuild_embeddings_if_stale(
+        &self,
+        refresh_hours: u32,
+        force: bool,
+    ) -> Result<Option<usize>> {
+        if self.embedding_service.is_none() {
+            return Ok(None);
+        }
+        let last_build = self.database.get_last_embedding_build()?;
+        if !force && !embedding_build_due(last_build, Utc::now(), refresh_hours) {
+            log::debug!("Skipping embedding build; last build at {last_build:?}");
+            return Ok(None);
         }
 
-        Ok(computed)
+        let computed = self.recompute_missing_embeddings()?;
+        self.database.record_embedding_build(Utc::now(), computed)?;
+        Ok(Some(computed))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use toki_integrations::notion::test_util::mock_pages;
 
     #[test]
     fn test_sync_stats_display() {
@@ -455,4 +932,192 @@ mod tests {
         assert!(display.contains("Embeddings: 8"));
         assert!(display.contains("Errors: 1"));
     }
+
+    #[tokio::test]
+    async fn test_interrupted_fetch_resumes_from_saved_cursor() {
+        let db = Database::new(Some(std::path::PathBuf::from(":memory:"))).unwrap();
+        let database_id = "notion-db";
+
+        let interrupted =
+            fetch_from_checkpoint(&db, database_id, |cursor| mock_pages(cursor, Some(4)))
+                .await
+                .unwrap();
+        assert_eq!(interrupted.items, vec![1, 2, 3]);
+        assert_eq!(
+            db.get_sync_checkpoint(database_id).unwrap().as_deref(),
+            Some("cursor-4")
+        );
+
+        // The next sync continues at the failed page, not page one
+        let resumed = fetch_from_checkpoint(&db, database_id, |cursor| mock_pages(cursor, None))
+            .await
+            .unwrap();
+        assert_eq!(resumed.items, vec![4, 5]);
+        assert_eq!(db.get_sync_checkpoint(database_id).unwrap(), None);
+        assert_eq!(db.get_sync_checkpoint("other-db").unwrap(), None);
+    }
+
+    #[test]
+    fn test_sync_service_without_embeddings_when_disabled() {
+        let db = Arc::new(Database::new(Some(std::path::PathBuf::from(":memory:"))).unwrap());
+        let project = db.get_or_create_project("toki", "/code/toki").unwrap();
+        db.link_project_to_pm(project.id, "plane", "proj-1", None).unwrap();
+        let candidate =
+            IssueCandidate::new(project.id, "PROJ-1".into(), "plane".into(), "Fix".into());
+        db.upsert_issue_candidate(&candidate).unwrap();
+
+        let service = IssueSyncService::new(db.clone(), false).unwrap();
+        assert!(service.embedding_service.is_none());
+        assert_eq!(service.recompute_missing_embeddings().unwrap(), 0);
+        assert_eq!(service.rebuild_embeddings_if_stale(24, true).unwrap(), None);
+        let stored = db.get_issue_candidate_by_id(candidate.id).unwrap().unwrap();
+        assert!(stored.embedding.is_none());
+    }
+
+    fn candidate_with_embedding(external_id: &str, title: &str, embedding: Vec<f32>) -> IssueCandidate {
+        let mut candidate = IssueCandidate::new(
+            Uuid::new_v4(),
+            external_id.to_string(),
+            "plane".to_string(),
+            title.to_string(),
+        );
+        candidate.embedding = Some(embedding);
+        candidate
+    }
+
+    #[test]
+    fn test_near_identical_embeddings_reported_as_duplicates() {
+        let candidates = vec![
+            candidate_with_embedding("PROJ-1", "Login button broken", vec![0.9, 0.1, 0.0]),
+            candidate_with_embedding("PROJ-2", "Login button not working", vec![0.89, 0.11, 0.0]),
+            candidate_with_embedding("PROJ-3", "Add dark mode", vec![0.0, 0.2, 0.9]),
+        ];
+
+        let pairs = find_duplicate_pairs(&candidates, DUPLICATE_SIMILARITY_THRESHOLD);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].first.external_id, "PROJ-1");
+        assert_eq!(pairs[0].second.external_id, "PROJ-2");
+        assert!(pairs[0].similarity > 0.99);
+    }
+
+    #[test]
+    fn test_dissimilar_embeddings_not_reported() {
+        let candidates = vec![
+            candidate_with_embedding("PROJ-1", "Login button broken", vec![1.0, 0.0, 0.0]),
+            candidate_with_embedding("PROJ-3", "Add dark mode", vec![0.0, 1.0, 0.0]),
+        ];
+
+        assert!(find_duplicate_pairs(&candidates, DUPLICATE_SIMILARITY_THRESHOLD).is_empty());
+    }
+
+    #[test]
+    fn test_candidates_without_embeddings_skipped() {
+        let mut unembedded = candidate_with_embedding("PROJ-2", "Login button broken", vec![]);
+        unembedded.embedding = None;
+        let candidates = vec![
+            candidate_with_embedding("PROJ-1", "Login button broken", vec![1.0, 0.0, 0.0]),
+            unembedded,
+        ];
+
+        assert!(find_duplicate_pairs(&candidates, DUPLICATE_SIMILARITY_THRESHOLD).is_empty());
+    }
+
+    #[test]
+    fn test_clusters_group_near_identical_embeddings() {
+        let candidates = vec![
+            candidate_with_embedding("PROJ-1", "Login button broken", vec![0.9, 0.1, 0.0]),
+            candidate_with_embedding("PROJ-2", "Dark mode colors", vec![0.0, 0.1, 0.9]),
+            candidate_with_embedding("PROJ-3", "Login button not working", vec![0.88, 0.12, 0.0]),
+            candidate_with_embedding("PROJ-4", "Login page crash", vec![0.85, 0.2, 0.05]),
+            candidate_with_embedding("PROJ-5", "Dark mode toggle", vec![0.05, 0.1, 0.88]),
+            candidate_with_embedding("PROJ-6", "Export to CSV", vec![0.0, 1.0, 0.0]),
+        ];
+
+        let clusters = find_similarity_clusters(&candidates, CLUSTER_SIMILARITY_THRESHOLD);
+        let ids: Vec<Vec<&str>> = clusters
+            .iter()
+            .map(|c| c.members.iter().map(|m| m.external_id.as_str()).collect())
+            .collect();
+        assert_eq!(
+            ids,
+            [vec!["PROJ-1", "PROJ-3", "PROJ-4"], vec!["PROJ-2", "PROJ-5"]]
+        );
+        assert!(clusters
+            .iter()
+            .all(|c| c.min_similarity >= CLUSTER_SIMILARITY_THRESHOLD));
+    }
+
+    #[test]
+    fn test_clusters_separate_dissimilar_embeddings() {
+        // B is close to A and to C, but A and C are far apart: no chaining
+        let candidates = vec![
+            candidate_with_embedding("A", "a", vec![1.0, 0.0, 0.0]),
+            candidate_with_embedding("B", "b", vec![0.866, 0.5, 0.0]),
+            candidate_with_embedding("C", "c", vec![0.5, 0.866, 0.0]),
+            candidate_with_embedding("D", "d", vec![0.0, 0.0, 1.0]),
+        ];
+
+        let clusters = find_similarity_clusters(&candidates, CLUSTER_SIMILARITY_THRESHOLD);
+        assert_eq!(clusters.len(), 1);
+        let ids: Vec<&str> = clusters[0]
+            .members
+            .iter()
+            .map(|m| m.external_id.as_str())
+            .collect();
+        assert_eq!(ids, ["A", "B"]);
+
+        let orthogonal = vec![
+            candidate_with_embedding("X", "x", vec![1.0, 0.0]),
+            candidate_with_embedding("Y", "y", vec![0.0, 1.0]),
+        ];
+        assert!(find_similarity_clusters(&orthogonal, CLUSTER_SIMILARITY_THRESHOLD).is_empty());
+    }
+
+    fn synced_hash(candidate: &IssueCandidate, include_labels: bool) -> String {
+        embedding_input_hash(&candidate.embedding_input(include_labels))
+    }
+
+    #[test]
+    fn test_status_only_change_skips_embedding() {
+        let mut candidate = candidate_with_embedding("PROJ-1", "Login button broken", vec![]);
+        candidate.labels = vec!["bug".to_string()];
+        let stored = synced_hash(&candidate, true);
+
+        candidate.status = "done".to_string();
+        assert!(!embedding_is_stale(Some(&stored), &candidate, true));
+    }
+
+    #[test]
+    fn test_label_change_triggers_embedding_only_when_labels_embedded() {
+        let mut candidate = candidate_with_embedding("PROJ-1", "Login button broken", vec![]);
+        candidate.labels = vec!["bug".to_string()];
+        let with_labels = synced_hash(&candidate, true);
+        let without_labels = synced_hash(&candidate, false);
+
+        candidate.labels.push("auth".to_string());
+        assert!(embedding_is_stale(Some(&with_labels), &candidate, true));
+        assert!(!embedding_is_stale(
+            Some(&without_labels),
+            &candidate,
+            false
+        ));
+    }
+
+    #[test]
+    fn test_embedding_build_skipped_while_fresh() {
+        let now = Utc::now();
+        let recent = Some(now - Duration::hours(2));
+        let old = Some(now - Duration::hours(30));
+
+        assert!(!embedding_build_due(recent, now, 24));
+        assert!(embedding_build_due(old, now, 24));
+        assert!(embedding_build_due(None, now, 24));
+        assert!(embedding_build_due(recent, now, 0));
+    }
+
+    #[test]
+    fn test_missing_hash_triggers_embedding() {
+        let candidate = candidate_with_embedding("PROJ-1", "Login button broken", vec![]);
+        assert!(embedding_is_stale(None, &candidate, true));
+    }
 }
@@ -15,22 +15,38 @@ pub mod time_analyzer;
 pub mod time_estimator;
 pub mod work_summary;
 
-pub use auto_linker::{AutoLinker, LinkReason, LinkSuggestion};
+pub use auto_linker::{
+    AutoLinker, LinkReason, LinkSuggestion, NameMatchAlgorithm, NameMatching,
+    DEFAULT_NAME_MATCH_THRESHOLD,
+};
 pub use embedding::EmbeddingService;
-pub use gravity::{GravityCalculator, RelevanceStatus};
-pub use insights::InsightsGenerator;
+pub use gravity::{GravityBreakdown, GravityCalculator, IssueSignals, RelevanceStatus};
+pub use insights::{
+    Anomaly, AnomalyMetric, DailyFragmentation, FragmentationTrend, InsightsGenerator,
+    SpanAggregator, SwitchSeverity, ANOMALY_STD_DEVS,
+};
 pub use issue_matcher::{
-    ActivitySignals, CandidateIssue, IssueMatch, IssueMatcher, MatchReason, SmartIssueMatcher,
+    compile_issue_id_pattern, rank_candidates, ActivitySignals,
+    CandidateCache, CandidateIssue, IssueMatch, IssueMatcher, MatchDiagnosis, MatchReason,
+    NoMatchCause, SmartIssueMatcher, DEFAULT_ISSUE_ID_PATTERN, SEMANTIC_SIMILARITY_FLOOR,
+};
+pub use issue_sync::{
+    embedding_build_due, embedding_input_hash, find_duplicate_issues, find_duplicate_pairs,
+    find_issue_clusters, find_similarity_clusters, DuplicatePair, IssueCluster, IssueSyncService,
+    SyncStats, CLUSTER_SIMILARITY_THRESHOLD, DUPLICATE_SIMILARITY_THRESHOLD, MAX_REPORTED_CLUSTERS,
+};
+pub use notion_issue_sync::{
+    preview_pages, NotionIssueSyncService, SyncOptions, SyncOutcome, SyncResult, SyncTarget,
 };
-pub use issue_sync::{IssueSyncService, SyncStats};
-pub use notion_issue_sync::{NotionIssueSyncService, SyncOptions, SyncOutcome, SyncResult, SyncTarget};
 pub use notion_mapper::{IssueMappingConfig, NotionIssueMapper};
-pub use rules::RuleEngine;
+pub use rules::{RuleEngine, DEFAULT_FALLBACK_CATEGORY};
 pub use time_analyzer::{
-    ActivitySegment, DailySummaryReport, SuggestedIssue, SuggestedTimeBlock, TimeAnalyzer,
-    WorkPattern,
+    format_weekly_report, label_focus_blocks, ActivitySegment, BlockFocus, DailySummaryReport,
+    SuggestedIssue, SuggestedTimeBlock, TimeAnalyzer, WorkPattern,
+};
+pub use standup::{
+    PlannedIssue, ProjectStandupItem, StandupFormat, StandupGenerator, StandupReport,
 };
-pub use standup::{ProjectStandupItem, StandupFormat, StandupGenerator, StandupReport};
 pub use time_estimator::{
     EstimationMethod, SimilarIssue, TimeBreakdown, TimeEstimate, TimeEstimator,
 };
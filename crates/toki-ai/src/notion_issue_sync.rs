@@ -6,13 +6,37 @@
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
-use toki_integrations::notion::{NotionClient, NotionIssueCandidateData, PropertyMappingConfig};
-use toki_integrations::traits::{CreatedIssue, IssueManagement, IssueSyncReport};
+use chrono::{DateTime, Utc};
+use toki_integrations::notion::{
+    NotionClient, NotionIssueCandidateData, PageQuery, PaginationMode, PropertyMappingConfig,
+};
+use toki_integrations::traits::{
+    CreatedIssue, IssueManagement, IssueSyncReport, UpdateIssueRequest,
+};
 use toki_storage::db::Database;
 use toki_storage::models::SyncedIssue;
 
+use crate::issue_sync::fetch_from_checkpoint;
 use crate::notion_mapper::{IssueMappingConfig, NotionIssueMapper};
 
+/// Checkpoint key of a sync into one target project
+///
+/// Kept apart from the issue candidate sync of the same database, which saves
+/// its checkpoint under the bare database ID.
+fn checkpoint_key(database_id: &str, target_system: &str, target_project: &str) -> String {
+    format!("{database_id}->{target_system}:{target_project}")
+}
+
+/// Latest `last_edited_time` among fetched pages
+fn latest_edit(candidates: &[NotionIssueCandidateData]) -> Option<DateTime<Utc>> {
+    candidates
+        .iter()
+        .filter_map(|c| c.last_edited_time.as_deref())
+        .filter_map(|time| DateTime::parse_from_rfc3339(time).ok())
+        .map(|time| time.with_timezone(&Utc))
+        .max()
+}
+
 /// Target system for issue sync
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SyncTarget {
@@ -35,9 +59,10 @@ impl SyncTarget {
 #[derive(Debug, Clone)]
 #[derive(Default)]
 pub struct SyncOptions {
-    /// Dry run - don't actually create issues
+    /// Dry run - only preview which issues would be created or updated
     pub dry_run: bool,
-    /// Force sync even if already synced
+    /// Force sync even if already synced (updates the existing issue), and fetch
+    /// every page rather than only those edited since the last sync
     pub force: bool,
     /// Maximum number of issues to create in one run
     pub limit: Option<usize>,
@@ -76,12 +101,68 @@ pub enum SyncOutcome {
         issue_number: u64,
         issue_url: String,
     },
+    /// Existing issue was updated (forced re-sync)
+    Updated {
+        issue_number: u64,
+        issue_url: String,
+    },
     /// Issue was skipped (already synced)
     Skipped { reason: String },
     /// Issue creation failed
     Failed { error: String },
     /// Would be created (dry run)
     WouldCreate,
+    /// Existing issue would be updated (dry run)
+    WouldUpdate {
+        issue_number: u64,
+        issue_url: String,
+    },
+}
+
+/// What a sync does with one page, given its existing sync record
+#[derive(Debug)]
+enum PagePlan {
+    Skip(String),
+    Create,
+    Update(SyncedIssue),
+}
+
+impl PagePlan {
+    fn new(existing: Option<SyncedIssue>, options: &SyncOptions) -> Self {
+        match existing {
+            None => Self::Create,
+            Some(_) if !options.force => Self::Skip("Already synced".to_string()),
+            Some(synced) => Self::Update(synced),
+        }
+    }
+}
+
+/// Preview what a sync would do with each page, without writing anywhere
+///
+/// `synced_issue` looks up a page's existing sync record for the target.
+pub fn preview_pages<'a>(
+    candidates: impl IntoIterator<Item = &'a NotionIssueCandidateData>,
+    options: &SyncOptions,
+    synced_issue: impl Fn(&str) -> Option<SyncedIssue>,
+) -> Vec<SyncResult> {
+    candidates
+        .into_iter()
+        .map(|candidate| {
+            let outcome = match PagePlan::new(synced_issue(&candidate.page_id), options) {
+                PagePlan::Skip(reason) => SyncOutcome::Skipped { reason },
+                PagePlan::Create => SyncOutcome::WouldCreate,
+                PagePlan::Update(synced) => SyncOutcome::WouldUpdate {
+                    issue_number: synced.target_issue_number,
+                    issue_url: synced.target_issue_url,
+                },
+            };
+            SyncResult {
+                page_id: candidate.page_id.clone(),
+                title: candidate.title.clone(),
+                outcome,
+            }
+        })
+        .collect()
 }
 
 /// Service for syncing Notion pages to GitHub/GitLab issues
@@ -142,12 +223,49 @@ impl NotionIssueSyncService {
             "Starting sync from Notion database {database_id} to {target_system} project {target_project}"
         );
 
-        // Fetch all pages from Notion database as issue candidates
-        let candidates = self
-            .notion_client
-            .fetch_database_as_issues(database_id, property_config, false)
-            .await
-            .context("Failed to fetch Notion pages")?;
+        // Only fetch pages edited since the last complete sync, unless forced
+        let key = checkpoint_key(database_id, target_system, target_project);
+        let edited_since = if options.force {
+            None
+        } else {
+            self.db.get_sync_watermark(&key)?
+        };
+        if let Some(since) = edited_since {
+            log::info!("Fetching Notion pages edited since {since}");
+        }
+
+        // Fetch pages from the Notion database as issue candidates; if a later page fails,
+        // sync what was fetched and continue from that page next time (dry runs and
+        // forced full syncs start over, since a saved cursor belongs to a filtered query)
+        // Use the workspace the database was linked with, so ids match `toki issue-sync`
+        let workspace = self.db.get_notion_workspace(database_id)?;
+        let workspace = workspace.as_deref();
+        let notion_client = &self.notion_client;
+        let fetch = move |start_cursor: Option<String>| async move {
+            let query = PageQuery {
+                mode: PaginationMode::Lenient,
+                start_cursor: start_cursor.as_deref(),
+                edited_since,
+            };
+            notion_client
+                .fetch_database_as_issues(database_id, workspace, property_config, false, query)
+                .await
+        };
+        let fetched = if options.dry_run || options.force {
+            fetch(None).await
+        } else {
+            fetch_from_checkpoint(&self.db, &key, fetch).await
+        }
+        .context("Failed to fetch Notion pages")?;
+
+        let mut report = IssueSyncReport::new();
+        if let Some(error) = &fetched.error {
+            report.errors.push(format!(
+                "Notion fetch stopped early, the next sync resumes there: {error}"
+            ));
+        }
+        let fetched_all = fetched.is_complete();
+        let candidates = fetched.items;
 
         log::info!("Fetched {} pages from Notion database", candidates.len());
 
@@ -170,39 +288,67 @@ impl NotionIssueSyncService {
         );
 
         // Apply limit
+        let limited = options.limit.is_some_and(|limit| filtered.len() > limit);
         let to_process: Vec<_> = if let Some(limit) = options.limit {
             filtered.into_iter().take(limit).collect()
         } else {
             filtered
         };
 
-        let mut report = IssueSyncReport::new();
-        let mut results = Vec::new();
-
-        for candidate in to_process {
-            let result = self
-                .sync_single_page(
-                    candidate,
-                    database_id,
-                    target_client,
-                    target_project,
-                    target_system,
-                    options,
-                )
-                .await;
+        let results = if options.dry_run {
+            let results = preview_pages(to_process, options, |page_id| {
+                self.synced_issue(page_id, target_system, target_project)
+            });
+            for result in &results {
+                log::info!("[DRY RUN] {:?}: {}", result.outcome, result.title);
+            }
+            results
+        } else {
+            let mut results = Vec::new();
+            for candidate in to_process {
+                let existing = self.synced_issue(&candidate.page_id, target_system, target_project);
+                let plan = PagePlan::new(existing, options);
+                results.push(
+                    self.sync_single_page(
+                        candidate,
+                        plan,
+                        database_id,
+                        target_client,
+                        target_project,
+                        target_system,
+                    )
+                    .await,
+                );
+            }
+            results
+        };
 
+        for result in &results {
             match &result.outcome {
                 SyncOutcome::Created { .. } => report.record_created(),
-                SyncOutcome::Skipped { .. } | SyncOutcome::WouldCreate => report.record_skipped(),
+                SyncOutcome::Updated { .. } => report.record_updated(),
+                SyncOutcome::Skipped { .. }
+                | SyncOutcome::WouldCreate
+                | SyncOutcome::WouldUpdate { .. } => report.record_skipped(),
                 SyncOutcome::Failed { error } => report.record_failure(error.clone()),
             }
+        }
 
-            results.push(result);
+        // Move the watermark only once every fetched page was handled, so pages
+        // left out by the limit or that failed are fetched again next time
+        if !options.dry_run && fetched_all && !limited && report.failed == 0 {
+            if let Some(latest) = latest_edit(&candidates) {
+                self.db.save_sync_watermark(&key, latest)?;
+                if options.force {
+                    self.db.clear_sync_checkpoint(&key)?;
+                }
+            }
         }
 
         log::info!(
-            "Sync complete: {} created, {} skipped, {} failed",
+            "Sync complete: {} created, {} updated, {} skipped, {} failed",
             report.created,
+            report.updated,
             report.skipped,
             report.failed
         );
@@ -210,92 +356,102 @@ impl NotionIssueSyncService {
         Ok((report, results))
     }
 
+    /// Existing sync record for a page, if any
+    fn synced_issue(
+        &self,
+        page_id: &str,
+        target_system: &str,
+        target_project: &str,
+    ) -> Option<SyncedIssue> {
+        self.db
+            .get_synced_issue(page_id, target_system, target_project)
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to check sync status for {page_id}: {e}");
+                None
+            })
+    }
+
     /// Sync a single Notion page to an issue
     async fn sync_single_page<T: IssueManagement>(
         &self,
         candidate: &NotionIssueCandidateData,
+        plan: PagePlan,
         database_id: &str,
         target_client: &T,
         target_project: &str,
         target_system: &str,
-        options: &SyncOptions,
     ) -> SyncResult {
         let page_id = &candidate.page_id;
         let title = candidate.title.clone();
 
-        // Check if already synced (unless force)
-        if !options.force {
-            match self.db.is_page_synced(page_id, target_system, target_project) {
-                Ok(true) => {
-                    log::debug!("Page {page_id} already synced, skipping");
-                    return SyncResult {
-                        page_id: page_id.clone(),
-                        title,
-                        outcome: SyncOutcome::Skipped {
-                            reason: "Already synced".to_string(),
-                        },
-                    };
-                }
-                Ok(false) => {}
-                Err(e) => {
-                    log::warn!("Failed to check sync status for {page_id}: {e}");
-                }
-            }
-        }
-
-        // Dry run - just report what would happen
-        if options.dry_run {
-            log::info!("[DRY RUN] Would create issue: {title}");
-            return SyncResult {
-                page_id: page_id.clone(),
-                title,
-                outcome: SyncOutcome::WouldCreate,
-            };
-        }
-
         // Map to issue request
         let issue_request = self.mapper.map_to_issue_request(candidate);
 
-        // Create the issue
-        match target_client.create_issue(&issue_request).await {
-            Ok(created) => {
-                log::info!(
-                    "Created issue #{} in {}: {}",
-                    created.number,
-                    target_system,
-                    created.title
-                );
-
-                // Record the sync
-                if let Err(e) = self.record_sync(
-                    page_id,
-                    database_id,
-                    target_system,
-                    target_project,
-                    &created,
-                ) {
-                    log::error!("Failed to record sync: {e}");
-                }
-
-                SyncResult {
-                    page_id: page_id.clone(),
-                    title,
-                    outcome: SyncOutcome::Created {
+        let outcome = match plan {
+            PagePlan::Skip(reason) => {
+                log::debug!("Page {page_id} skipped: {reason}");
+                SyncOutcome::Skipped { reason }
+            }
+            PagePlan::Create => match target_client.create_issue(&issue_request).await {
+                Ok(created) => {
+                    log::info!(
+                        "Created issue #{} in {}: {}",
+                        created.number,
+                        target_system,
+                        created.title
+                    );
+                    self.record_sync(
+                        page_id,
+                        database_id,
+                        target_system,
+                        target_project,
+                        &created,
+                    );
+                    SyncOutcome::Created {
                         issue_number: created.number,
                         issue_url: created.url,
-                    },
+                    }
                 }
-            }
-            Err(e) => {
-                log::error!("Failed to create issue for {title}: {e}");
-                SyncResult {
-                    page_id: page_id.clone(),
-                    title,
-                    outcome: SyncOutcome::Failed {
+                Err(e) => {
+                    log::error!("Failed to create issue for {title}: {e}");
+                    SyncOutcome::Failed {
                         error: e.to_string(),
-                    },
+                    }
+                }
+            },
+            PagePlan::Update(mut synced) => {
+                // Only title and labels are refreshed, so edits made in the target survive
+                let update = UpdateIssueRequest {
+                    title: Some(issue_request.title.clone()),
+                    labels: Some(issue_request.labels.clone()),
+                    ..UpdateIssueRequest::default()
+                };
+                let issue_number = synced.target_issue_number.to_string();
+                match target_client.update_issue(&issue_number, &update).await {
+                    Ok(()) => {
+                        log::info!("Updated issue #{issue_number} in {target_system}: {title}");
+                        synced.title = issue_request.title;
+                        synced.updated_at = chrono::Utc::now();
+                        self.save_sync_record(&synced);
+                        SyncOutcome::Updated {
+                            issue_number: synced.target_issue_number,
+                            issue_url: synced.target_issue_url,
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("Failed to update issue #{issue_number} for {title}: {e}");
+                        SyncOutcome::Failed {
+                            error: e.to_string(),
+                        }
+                    }
                 }
             }
+        };
+
+        SyncResult {
+            page_id: page_id.clone(),
+            title,
+            outcome,
         }
     }
 
@@ -307,7 +463,7 @@ impl NotionIssueSyncService {
         target_system: &str,
         target_project: &str,
         created: &CreatedIssue,
-    ) -> Result<()> {
+    ) {
         let synced = SyncedIssue::new(
             page_id.to_string(),
             database_id.to_string(),
@@ -319,11 +475,13 @@ impl NotionIssueSyncService {
             created.title.clone(),
         );
 
-        self.db
-            .upsert_synced_issue(&synced)
-            .context("Failed to save sync record")?;
+        self.save_sync_record(&synced);
+    }
 
-        Ok(())
+    fn save_sync_record(&self, synced: &SyncedIssue) {
+        if let Err(e) = self.db.upsert_synced_issue(synced) {
+            log::error!("Failed to record sync: {e}");
+        }
     }
 
     /// Get sync history for a database
@@ -356,6 +514,44 @@ impl NotionIssueSyncService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+    use toki_integrations::notion::test_util::mock_pages;
+
+    #[tokio::test]
+    async fn test_interrupted_sync_resumes_from_checkpoint() {
+        let db = Database::new(Some(std::path::PathBuf::from(":memory:"))).unwrap();
+        let key = checkpoint_key("notion-db", "github", "acme/api");
+
+        // The laptop sleeps while page 4 is fetched
+        let sleeps_at_page_4 = |cursor| mock_pages(cursor, Some(4));
+        let interrupted = fetch_from_checkpoint(&db, &key, sleeps_at_page_4)
+            .await
+            .unwrap();
+        assert_eq!(interrupted.items, vec![1, 2, 3]);
+        assert_eq!(
+            db.get_sync_checkpoint(&key).unwrap().as_deref(),
+            Some("cursor-4")
+        );
+        assert_eq!(db.get_sync_checkpoint("notion-db").unwrap(), None);
+
+        let resumed = fetch_from_checkpoint(&db, &key, |cursor| mock_pages(cursor, None))
+            .await
+            .unwrap();
+        assert_eq!(resumed.items, vec![4, 5]);
+        assert_eq!(db.get_sync_checkpoint(&key).unwrap(), None);
+
+        // A cursor Notion no longer accepts starts the fetch over
+        db.save_sync_checkpoint(&key, "cursor-expired").unwrap();
+        let restarted = fetch_from_checkpoint(&db, &key, |cursor| async move {
+            if cursor.as_deref() == Some("cursor-expired") {
+                anyhow::bail!("Notion API error (400): invalid start_cursor");
+            }
+            mock_pages(cursor, None).await
+        })
+        .await
+        .unwrap();
+        assert_eq!(restarted.items, vec![1, 2, 3, 4, 5]);
+    }
 
     #[test]
     fn test_sync_target_as_str() {
@@ -378,4 +574,104 @@ mod tests {
         assert!(opts.dry_run);
         assert!(!opts.force);
     }
+
+    fn page(page_id: &str, title: &str) -> NotionIssueCandidateData {
+        NotionIssueCandidateData {
+            external_id: page_id.to_string(),
+            external_system: "notion".to_string(),
+            title: title.to_string(),
+            description: None,
+            status: "Ready".to_string(),
+            database_id: "db-1".to_string(),
+            page_id: page_id.to_string(),
+            labels: Vec::new(),
+            last_edited_time: None,
+        }
+    }
+
+    #[test]
+    fn test_latest_edit_picks_newest_page() {
+        let edited = |page_id: &str, time: Option<&str>| NotionIssueCandidateData {
+            last_edited_time: time.map(String::from),
+            ..page(page_id, page_id)
+        };
+        let candidates = [
+            edited("a", Some("2025-03-14T09:30:00.000Z")),
+            edited("b", Some("2025-03-14T11:05:00.000Z")),
+            edited("c", None),
+            edited("d", Some("not a timestamp")),
+        ];
+
+        assert_eq!(
+            latest_edit(&candidates).map(|t| t.to_rfc3339()),
+            Some("2025-03-14T11:05:00+00:00".to_string())
+        );
+        assert_eq!(latest_edit(&[]), None);
+    }
+
+    #[test]
+    fn test_sync_watermark_round_trips() {
+        let db = Database::new(Some(std::path::PathBuf::from(":memory:"))).unwrap();
+        let key = checkpoint_key("notion-db", "github", "owner/repo");
+        assert_eq!(db.get_sync_watermark(&key).unwrap(), None);
+
+        let latest = DateTime::parse_from_rfc3339("2025-03-14T11:05:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        db.save_sync_watermark(&key, latest).unwrap();
+        assert_eq!(db.get_sync_watermark(&key).unwrap(), Some(latest));
+    }
+
+    fn synced_state() -> HashMap<String, SyncedIssue> {
+        let synced = SyncedIssue::new(
+            "page-synced".to_string(),
+            "db-1".to_string(),
+            "github".to_string(),
+            "owner/repo".to_string(),
+            "9001".to_string(),
+            12,
+            "https://github.com/owner/repo/issues/12".to_string(),
+            "Old title".to_string(),
+        );
+        HashMap::from([(synced.source_page_id.clone(), synced)])
+    }
+
+    #[test]
+    fn test_preview_reports_update_for_synced_and_create_for_new_pages() {
+        let state = synced_state();
+        let pages = [
+            page("page-synced", "Fix login"),
+            page("page-new", "Add export"),
+        ];
+        let options = SyncOptions {
+            force: true,
+            ..SyncOptions::dry_run()
+        };
+
+        let preview = preview_pages(&pages, &options, |id| state.get(id).cloned());
+
+        assert_eq!(preview.len(), 2);
+        assert_eq!(preview[0].title, "Fix login");
+        assert!(matches!(
+            &preview[0].outcome,
+            SyncOutcome::WouldUpdate { issue_number: 12, issue_url }
+                if issue_url == "https://github.com/owner/repo/issues/12"
+        ));
+        assert_eq!(preview[1].title, "Add export");
+        assert!(matches!(preview[1].outcome, SyncOutcome::WouldCreate));
+    }
+
+    #[test]
+    fn test_preview_skips_synced_pages_without_force() {
+        let state = synced_state();
+        let pages = [
+            page("page-synced", "Fix login"),
+            page("page-new", "Add export"),
+        ];
+
+        let preview = preview_pages(&pages, &SyncOptions::dry_run(), |id| state.get(id).cloned());
+
+        assert!(matches!(preview[0].outcome, SyncOutcome::Skipped { .. }));
+        assert!(matches!(preview[1].outcome, SyncOutcome::WouldCreate));
+    }
 }
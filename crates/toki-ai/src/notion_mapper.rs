@@ -161,15 +161,19 @@ impl NotionIssueMapper {
     }
 
     /// Convert a Notion page directly to issue request using property mapping
+    ///
+    /// `workspace` must be the one the database was linked with, so ids match synced issues.
     #[must_use]
     pub fn map_page_to_issue_request(
         &self,
         page: &NotionPage,
+        workspace: Option<&str>,
         database_id: &str,
         mapping: &PropertyMapping,
     ) -> CreateIssueRequest {
         // Use NotionClient's helper to convert to candidate first
-        let candidate = NotionClient::page_to_issue_candidate(page, database_id, mapping, None);
+        let candidate =
+            NotionClient::page_to_issue_candidate(page, workspace, database_id, mapping, None);
         self.map_to_issue_request(&candidate)
     }
 
@@ -197,6 +201,7 @@ mod tests {
             database_id: "db-123".to_string(),
             page_id: "page-456".to_string(),
             labels: vec!["bug".to_string(), "urgent".to_string()],
+            last_edited_time: None,
         }
     }
 
@@ -279,6 +284,7 @@ mod tests {
                 database_id: "db".to_string(),
                 page_id: "p1".to_string(),
                 labels: vec![],
+                last_edited_time: None,
             },
             NotionIssueCandidateData {
                 external_id: "2".to_string(),
@@ -289,6 +295,7 @@ mod tests {
                 database_id: "db".to_string(),
                 page_id: "p2".to_string(),
                 labels: vec![],
+                last_edited_time: None,
             },
             NotionIssueCandidateData {
                 external_id: "3".to_string(),
@@ -299,6 +306,7 @@ mod tests {
                 database_id: "db".to_string(),
                 page_id: "p3".to_string(),
                 labels: vec![],
+                last_edited_time: None,
             },
         ];
 
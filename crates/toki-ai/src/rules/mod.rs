@@ -6,6 +6,9 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Category returned when no rule matches, unless configured otherwise
+pub const DEFAULT_FALLBACK_CATEGORY: &str = "Uncategorized";
+
 /// Rule for categorizing applications
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Rule {
@@ -18,6 +21,7 @@ pub struct Rule {
 pub struct RuleEngine {
     rules: Vec<Rule>,
     compiled_patterns: HashMap<String, Regex>,
+    fallback_category: Option<String>, // Returned when nothing matches (None = no category)
 }
 
 impl RuleEngine {
@@ -27,9 +31,17 @@ impl RuleEngine {
         Self {
             rules: Vec::new(),
             compiled_patterns: HashMap::new(),
+            fallback_category: Some(DEFAULT_FALLBACK_CATEGORY.to_string()),
         }
     }
 
+    /// Set the category returned when no rule matches (None leaves it unclassified)
+    #[must_use]
+    pub fn with_fallback_category(mut self, category: Option<String>) -> Self {
+        self.fallback_category = category;
+        self
+    }
+
     /// Add a rule to the engine
     ///
     /// # Errors
@@ -77,14 +89,54 @@ impl RuleEngine {
     /// Classify an application
     #[must_use]
     pub fn classify(&self, app_id: &str) -> Option<(String, f32)> {
-        for rule in &self.rules {
-            if let Some(regex) = self.compiled_patterns.get(&rule.pattern) {
-                if regex.is_match(app_id) {
-                    return Some((rule.category.clone(), rule.confidence));
-                }
-            }
-        }
-        None
+        self.matching_rules(app_id)
+            .next()
+            .map(|rule| (rule.category.clone(), rule.confidence))
+    }
+
+    /// Classify with the first rule of at least `min_confidence`
+    ///
+    /// When no rule qualifies, returns the fallback category with zero
+    /// confidence so aggregation always has a bucket.
+    #[must_use]
+    pub fn classify_with_threshold(
+        &self,
+        app_id: &str,
+        min_confidence: f32,
+    ) -> Option<(String, f32)> {
+        self.matching_rules(app_id)
+            .find(|rule| rule.confidence >= min_confidence)
+            .map(|rule| (rule.category.clone(), rule.confidence))
+            .or_else(|| self.fallback())
+    }
+
+    /// Classify with the highest-confidence matching rule
+    ///
+    /// Ties go to the rule added first. Falls back like
+    /// [`Self::classify_with_threshold`] when nothing matches.
+    #[must_use]
+    pub fn classify_best(&self, app_id: &str) -> Option<(String, f32)> {
+        self.matching_rules(app_id)
+            .fold(None, |best: Option<&Rule>, rule| match best {
+                Some(best) if best.confidence >= rule.confidence => Some(best),
+                _ => Some(rule),
+            })
+            .map(|rule| (rule.category.clone(), rule.confidence))
+            .or_else(|| self.fallback())
+    }
+
+    fn matching_rules<'a>(&'a self, app_id: &'a str) -> impl Iterator<Item = &'a Rule> {
+        self.rules.iter().filter(move |rule| {
+            self.compiled_patterns
+                .get(&rule.pattern)
+                .is_some_and(|regex| regex.is_match(app_id))
+        })
+    }
+
+    fn fallback(&self) -> Option<(String, f32)> {
+        self.fallback_category
+            .as_ref()
+            .map(|category| (category.clone(), 0.0))
     }
 }
 
@@ -405,6 +405,76 @@ fn test_classify_unicode() {
     assert_eq!(category, "Japanese");
 }
 
+// ============================================================================
+// RuleEngine::classify_with_threshold / classify_best tests
+// ============================================================================
+
+fn engine_with_overlapping_rules() -> RuleEngine {
+    let mut engine = RuleEngine::new();
+    engine.add_rule(Rule {
+        pattern: "(?i)notes".to_string(),
+        category: "Writing".to_string(),
+        confidence: 0.6,
+    }).unwrap();
+    engine.add_rule(Rule {
+        pattern: "(?i)obsidian".to_string(),
+        category: "Research".to_string(),
+        confidence: 0.9,
+    }).unwrap();
+    engine
+}
+
+#[test]
+fn test_classify_best_prefers_highest_confidence() {
+    let engine = engine_with_overlapping_rules();
+
+    // Both rules match; first match wins for classify, best for classify_best
+    assert_eq!(engine.classify("ObsidianNotes").unwrap().0, "Writing");
+    let (category, confidence) = engine.classify_best("ObsidianNotes").unwrap();
+    assert_eq!(category, "Research");
+    assert!((confidence - 0.9).abs() < f32::EPSILON);
+}
+
+#[test]
+fn test_classify_best_tie_keeps_first_rule() {
+    let mut engine = RuleEngine::new();
+    for category in ["First", "Second"] {
+        engine.add_rule(Rule {
+            pattern: "test".to_string(),
+            category: category.to_string(),
+            confidence: 0.8,
+        }).unwrap();
+    }
+
+    assert_eq!(engine.classify_best("test").unwrap().0, "First");
+}
+
+#[test]
+fn test_classify_with_threshold_skips_low_confidence_rules() {
+    let engine = engine_with_overlapping_rules();
+
+    let (category, _) = engine.classify_with_threshold("ObsidianNotes", 0.8).unwrap();
+    assert_eq!(category, "Research");
+
+    // The only matching rule is below the threshold
+    let (category, confidence) = engine.classify_with_threshold("Notes", 0.8).unwrap();
+    assert_eq!(category, DEFAULT_FALLBACK_CATEGORY);
+    assert!(confidence.abs() < f32::EPSILON);
+}
+
+#[test]
+fn test_fallback_category_configurable() {
+    let engine = engine_with_overlapping_rules().with_fallback_category(Some("Other".to_string()));
+    assert_eq!(engine.classify_best("unknown").unwrap().0, "Other");
+    assert_eq!(engine.classify_with_threshold("unknown", 0.0).unwrap().0, "Other");
+
+    // Plain classify never falls back
+    assert!(engine.classify("unknown").is_none());
+
+    let engine = engine_with_overlapping_rules().with_fallback_category(None);
+    assert!(engine.classify_best("unknown").is_none());
+}
+
 // ============================================================================
 // Edge cases and integration tests
 // ============================================================================
@@ -8,10 +8,10 @@
 //! - **Blockers:** Detected issues or None
 
 use chrono::{DateTime, Duration, NaiveDate, Utc};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use toki_storage::{ClaudeSession, Database, Project};
+use toki_storage::{ClaudeSession, Database, DurationFormat, IssueCandidate, Project};
 
 #[cfg(test)]
 mod tests;
@@ -59,6 +59,19 @@ pub struct ProjectStandupItem {
     pub description: Option<String>,
 }
 
+/// In-progress issue planned for today, with no time logged yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedIssue {
+    pub external_id: String,
+    pub title: String,
+}
+
+impl PlannedIssue {
+    fn label(&self) -> String {
+        format!("{} {} (planned)", self.external_id, self.title)
+    }
+}
+
 /// Standup report data
 #[derive(Debug)]
 pub struct StandupReport {
@@ -70,31 +83,20 @@ pub struct StandupReport {
     pub today_work: Vec<ProjectStandupItem>,
     /// Total time today
     pub today_total_seconds: u32,
+    /// In-progress issues listed under today (only with `--include-planned`)
+    pub planned_issues: Vec<PlannedIssue>,
     /// Detected blockers
     pub blockers: Vec<String>,
     /// Date of the standup
     pub date: NaiveDate,
+    /// How durations are rendered (`display.duration_format`)
+    pub duration_format: DurationFormat,
 }
 
 impl StandupReport {
-    /// Format duration in human-readable form
-    pub(crate) fn format_duration(seconds: u32) -> String {
-        let hours = seconds / 3600;
-        let minutes = (seconds % 3600) / 60;
-
-        if hours > 0 {
-            if minutes > 0 {
-                format!("{hours}h {minutes}m")
-            } else {
-                format!("{hours}h")
-            }
-        } else if minutes > 0 {
-            format!("{minutes}m")
-        } else if seconds > 0 {
-            format!("{seconds}s")
-        } else {
-            "0m".to_string()
-        }
+    /// Format duration using the configured display format
+    pub(crate) fn format_duration(&self, seconds: u32) -> String {
+        self.duration_format.format(seconds)
     }
 
     /// Generate output in the specified format
@@ -124,7 +126,7 @@ impl StandupReport {
                 .yesterday_work
                 .iter()
                 .map(|item| {
-                    let time = Self::format_duration(item.total_seconds);
+                    let time = self.format_duration(item.total_seconds);
                     if let Some(desc) = &item.description {
                         format!("{} on {} ({})", desc, item.project.name, time)
                     } else {
@@ -138,7 +140,7 @@ impl StandupReport {
 
         // Today
         output.push_str("Today: ");
-        if self.today_work.is_empty() {
+        if self.today_work.is_empty() && self.planned_issues.is_empty() {
             output.push_str("Will continue previous work\n");
         } else {
             let items: Vec<String> = self
@@ -151,6 +153,7 @@ impl StandupReport {
                         format!("Working on {}", item.project.name)
                     }
                 })
+                .chain(self.planned_issues.iter().map(PlannedIssue::label))
                 .collect();
             output.push_str(&items.join(", "));
             output.push('\n');
@@ -182,7 +185,7 @@ impl StandupReport {
                 .yesterday_work
                 .iter()
                 .map(|item| {
-                    let time = Self::format_duration(item.total_seconds);
+                    let time = self.format_duration(item.total_seconds);
                     if let Some(desc) = &item.description {
                         format!("{} on {} ({})", desc, item.project.name, time)
                     } else {
@@ -196,7 +199,7 @@ impl StandupReport {
 
         // Today
         output.push_str("**Today:** ");
-        if self.today_work.is_empty() {
+        if self.today_work.is_empty() && self.planned_issues.is_empty() {
             output.push_str("Will continue previous work\n");
         } else {
             let items: Vec<String> = self
@@ -209,6 +212,7 @@ impl StandupReport {
                         format!("Working on {}", item.project.name)
                     }
                 })
+                .chain(self.planned_issues.iter().map(PlannedIssue::label))
                 .collect();
             output.push_str(&items.join(", "));
             output.push('\n');
@@ -240,7 +244,7 @@ impl StandupReport {
                 .yesterday_work
                 .iter()
                 .map(|item| {
-                    let time = Self::format_duration(item.total_seconds);
+                    let time = self.format_duration(item.total_seconds);
                     if let Some(desc) = &item.description {
                         format!("{} on `{}` ({})", desc, item.project.name, time)
                     } else {
@@ -254,7 +258,7 @@ impl StandupReport {
 
         // Today
         output.push_str("*Today:* ");
-        if self.today_work.is_empty() {
+        if self.today_work.is_empty() && self.planned_issues.is_empty() {
             output.push_str("Will continue previous work\n");
         } else {
             let items: Vec<String> = self
@@ -267,6 +271,7 @@ impl StandupReport {
                         format!("Working on `{}`", item.project.name)
                     }
                 })
+                .chain(self.planned_issues.iter().map(PlannedIssue::label))
                 .collect();
             output.push_str(&items.join(", "));
             output.push('\n');
@@ -298,7 +303,7 @@ impl StandupReport {
                 .yesterday_work
                 .iter()
                 .map(|item| {
-                    let time = Self::format_duration(item.total_seconds);
+                    let time = self.format_duration(item.total_seconds);
                     if let Some(desc) = &item.description {
                         format!("{} on `{}` ({})", desc, item.project.name, time)
                     } else {
@@ -312,7 +317,7 @@ impl StandupReport {
 
         // Today
         output.push_str("**Today:** ");
-        if self.today_work.is_empty() {
+        if self.today_work.is_empty() && self.planned_issues.is_empty() {
             output.push_str("Will continue previous work\n");
         } else {
             let items: Vec<String> = self
@@ -325,6 +330,7 @@ impl StandupReport {
                         format!("Working on `{}`", item.project.name)
                     }
                 })
+                .chain(self.planned_issues.iter().map(PlannedIssue::label))
                 .collect();
             output.push_str(&items.join(", "));
             output.push('\n');
@@ -356,13 +362,13 @@ impl StandupReport {
             "date": self.date.to_string(),
             "yesterday": {
                 "total_seconds": self.yesterday_total_seconds,
-                "total_time": Self::format_duration(self.yesterday_total_seconds),
+                "total_time": self.format_duration(self.yesterday_total_seconds),
                 "items": self.yesterday_work.iter().map(|item| {
                     serde_json::json!({
                         "project": item.project.name,
                         "path": item.project.path,
                         "seconds": item.total_seconds,
-                        "time": Self::format_duration(item.total_seconds),
+                        "time": self.format_duration(item.total_seconds),
                         "sessions": item.session_count,
                         "tool_calls": item.tool_calls,
                         "prompts": item.prompt_count,
@@ -372,18 +378,24 @@ impl StandupReport {
             },
             "today": {
                 "total_seconds": self.today_total_seconds,
-                "total_time": Self::format_duration(self.today_total_seconds),
+                "total_time": self.format_duration(self.today_total_seconds),
                 "items": self.today_work.iter().map(|item| {
                     serde_json::json!({
                         "project": item.project.name,
                         "path": item.project.path,
                         "seconds": item.total_seconds,
-                        "time": Self::format_duration(item.total_seconds),
+                        "time": self.format_duration(item.total_seconds),
                         "sessions": item.session_count,
                         "tool_calls": item.tool_calls,
                         "prompts": item.prompt_count,
                         "description": item.description
                     })
+                }).collect::<Vec<_>>(),
+                "planned": self.planned_issues.iter().map(|issue| {
+                    serde_json::json!({
+                        "issue_id": issue.external_id,
+                        "title": issue.title
+                    })
                 }).collect::<Vec<_>>()
             },
             "blockers": self.blockers
@@ -393,16 +405,63 @@ impl StandupReport {
     }
 }
 
+/// Whether an issue status means work has started
+pub(crate) fn is_in_progress(status: &str) -> bool {
+    matches!(
+        status.to_lowercase().replace(['_', '-'], " ").as_str(),
+        "in progress" | "started" | "doing"
+    )
+}
+
+/// In-progress issues assigned to the user that have no logged time
+///
+/// Issues without a recorded assignee count as the user's own, since not every
+/// integration syncs assignees. Issues in `logged` are already covered by tracked work.
+pub(crate) fn planned_issues(
+    candidates: &[IssueCandidate],
+    assignee: Option<&str>,
+    logged: &HashSet<String>,
+) -> Vec<PlannedIssue> {
+    candidates
+        .iter()
+        .filter(|c| is_in_progress(&c.status))
+        .filter(|c| match (&c.assignee, assignee) {
+            (None, _) => true,
+            (Some(owner), Some(me)) => owner.eq_ignore_ascii_case(me),
+            (Some(_), None) => false,
+        })
+        .filter(|c| !logged.contains(&c.external_id))
+        .map(|c| PlannedIssue {
+            external_id: c.external_id.clone(),
+            title: c.title.clone(),
+        })
+        .collect()
+}
+
 /// Standup report generator
 pub struct StandupGenerator {
     db: Arc<Database>,
+    include_planned: bool,
+    assignee: Option<String>,
 }
 
 impl StandupGenerator {
     /// Create a new standup generator
     #[must_use]
     pub fn new(db: Arc<Database>) -> Self {
-        Self { db }
+        Self {
+            db,
+            include_planned: false,
+            assignee: None,
+        }
+    }
+
+    /// List in-progress issues assigned to `assignee` under today, even without logged time
+    #[must_use]
+    pub fn with_planned_issues(mut self, assignee: Option<String>) -> Self {
+        self.include_planned = true;
+        self.assignee = assignee;
+        self
     }
 
     /// Generate a standup report for the given date
@@ -446,6 +505,12 @@ impl StandupGenerator {
         // Aggregate today's work
         let (today_work, today_total) = self.aggregate_sessions(&today_sessions);
 
+        let planned = if self.include_planned {
+            self.planned_issues(&today_sessions, today_start, today_end)?
+        } else {
+            Vec::new()
+        };
+
         // Detect blockers
         let blockers = self.detect_blockers(&yesterday_sessions, &today_sessions);
 
@@ -454,11 +519,47 @@ impl StandupGenerator {
             yesterday_total_seconds: yesterday_total,
             today_work,
             today_total_seconds: today_total,
+            planned_issues: planned,
             blockers,
             date: today,
+            duration_format: self.db.get_settings()?.duration_format,
         })
     }
 
+    /// Active in-progress issues across all projects, minus those with time logged today
+    fn planned_issues(
+        &self,
+        today_sessions: &[ClaudeSession],
+        today_start: DateTime<Utc>,
+        today_end: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<PlannedIssue>> {
+        let mut logged: HashSet<String> = HashSet::new();
+        for session in today_sessions {
+            for issue in self.db.get_session_issues(session.id)? {
+                logged.insert(issue.issue_id);
+            }
+        }
+        for block in self.db.get_confirmed_time_blocks()? {
+            if block.start_time <= today_end && block.end_time >= today_start {
+                for id in block.work_item_ids {
+                    if let Some(issue) = self.db.get_issue_candidate_by_id(id)? {
+                        logged.insert(issue.external_id);
+                    }
+                }
+            }
+        }
+
+        let mut candidates = Vec::new();
+        for project in self.db.get_all_projects()? {
+            candidates.extend(self.db.get_active_issue_candidates(project.id)?);
+        }
+        Ok(planned_issues(
+            &candidates,
+            self.assignee.as_deref(),
+            &logged,
+        ))
+    }
+
     /// Aggregate sessions by project
     fn aggregate_sessions(
         &self,
@@ -55,32 +55,37 @@ fn test_standup_format_equality() {
 
 #[test]
 fn test_format_duration_zero() {
-    assert_eq!(StandupReport::format_duration(0), "0m");
+    let report = create_empty_report();
+    assert_eq!(report.format_duration(0), "0m");
 }
 
 #[test]
 fn test_format_duration_seconds_only() {
-    assert_eq!(StandupReport::format_duration(30), "30s");
-    assert_eq!(StandupReport::format_duration(59), "59s");
+    let report = create_empty_report();
+    assert_eq!(report.format_duration(30), "30s");
+    assert_eq!(report.format_duration(59), "59s");
 }
 
 #[test]
 fn test_format_duration_minutes_only() {
-    assert_eq!(StandupReport::format_duration(60), "1m");
-    assert_eq!(StandupReport::format_duration(120), "2m");
-    assert_eq!(StandupReport::format_duration(45 * 60), "45m");
+    let report = create_empty_report();
+    assert_eq!(report.format_duration(60), "1m");
+    assert_eq!(report.format_duration(120), "2m");
+    assert_eq!(report.format_duration(45 * 60), "45m");
 }
 
 #[test]
 fn test_format_duration_hours_only() {
-    assert_eq!(StandupReport::format_duration(3600), "1h");
-    assert_eq!(StandupReport::format_duration(2 * 3600), "2h");
+    let report = create_empty_report();
+    assert_eq!(report.format_duration(3600), "1h");
+    assert_eq!(report.format_duration(2 * 3600), "2h");
 }
 
 #[test]
 fn test_format_duration_hours_and_minutes() {
-    assert_eq!(StandupReport::format_duration(3600 + 30 * 60), "1h 30m");
-    assert_eq!(StandupReport::format_duration(2 * 3600 + 15 * 60), "2h 15m");
+    let report = create_empty_report();
+    assert_eq!(report.format_duration(3600 + 30 * 60), "1h 30m");
+    assert_eq!(report.format_duration(2 * 3600 + 15 * 60), "2h 15m");
 }
 
 // ==================== Helper functions for tests ====================
@@ -116,8 +121,10 @@ fn create_empty_report() -> StandupReport {
         yesterday_total_seconds: 0,
         today_work: vec![],
         today_total_seconds: 0,
+        planned_issues: vec![],
         blockers: vec![],
         date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+        duration_format: DurationFormat::default(),
     }
 }
 
@@ -132,8 +139,10 @@ fn create_sample_report() -> StandupReport {
             create_test_standup_item("toki", 1800, Some("Adding tests")),
         ],
         today_total_seconds: 1800,
+        planned_issues: vec![],
         blockers: vec!["API rate limit issue".to_string()],
         date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+        duration_format: DurationFormat::default(),
     }
 }
 
@@ -169,8 +178,10 @@ fn test_format_text_no_description() {
         yesterday_total_seconds: 3600,
         today_work: vec![],
         today_total_seconds: 0,
+        planned_issues: vec![],
         blockers: vec![],
         date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+        duration_format: DurationFormat::default(),
     };
     let output = report.format_text();
 
@@ -305,8 +316,10 @@ fn test_format_json_item_fields() {
         yesterday_total_seconds: 5400,
         today_work: vec![],
         today_total_seconds: 0,
+        planned_issues: vec![],
         blockers: vec![],
         date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+        duration_format: DurationFormat::default(),
     };
     let output = report.format_json();
 
@@ -400,12 +413,14 @@ fn test_format_multiple_blockers() {
         yesterday_total_seconds: 0,
         today_work: vec![],
         today_total_seconds: 0,
+        planned_issues: vec![],
         blockers: vec![
             "API issue".to_string(),
             "Build failing".to_string(),
             "Waiting for review".to_string(),
         ],
         date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+        duration_format: DurationFormat::default(),
     };
     let output = report.format_text();
 
@@ -425,8 +440,10 @@ fn test_format_multiple_projects_yesterday() {
         yesterday_total_seconds: 6300,
         today_work: vec![],
         today_total_seconds: 0,
+        planned_issues: vec![],
         blockers: vec![],
         date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+        duration_format: DurationFormat::default(),
     };
     let output = report.format_text();
 
@@ -434,3 +451,63 @@ fn test_format_multiple_projects_yesterday() {
     assert!(output.contains("Worked on project-b (30m)"));
     assert!(output.contains("Worked on project-c (15m)"));
 }
+
+// ==================== Planned issues tests ====================
+
+fn candidate(external_id: &str, status: &str, assignee: Option<&str>) -> IssueCandidate {
+    let mut issue = IssueCandidate::new(
+        Uuid::new_v4(),
+        external_id.to_string(),
+        "plane".to_string(),
+        format!("Title of {external_id}"),
+    );
+    issue.status = status.to_string();
+    issue.assignee = assignee.map(String::from);
+    issue
+}
+
+#[test]
+fn test_planned_issues_in_progress_assigned_and_unlogged() {
+    let candidates = vec![
+        candidate("PROJ-1", "In Progress", Some("alice")),
+        candidate("PROJ-2", "in_progress", None),
+        candidate("PROJ-3", "backlog", Some("alice")),
+        candidate("PROJ-4", "started", Some("bob")),
+        candidate("PROJ-5", "doing", Some("Alice")),
+    ];
+    let logged: HashSet<String> = ["PROJ-5".to_string()].into_iter().collect();
+
+    let ids = |planned: Vec<PlannedIssue>| -> Vec<String> {
+        planned.into_iter().map(|p| p.external_id).collect()
+    };
+    assert_eq!(
+        ids(planned_issues(&candidates, Some("alice"), &logged)),
+        vec!["PROJ-1", "PROJ-2"]
+    );
+    // Without a known user, only unassigned issues count as the user's
+    assert_eq!(
+        ids(planned_issues(&candidates, None, &logged)),
+        vec!["PROJ-2"]
+    );
+}
+
+#[test]
+fn test_planned_issues_listed_under_today() {
+    let mut report = create_empty_report();
+    report.planned_issues = vec![PlannedIssue {
+        external_id: "PROJ-7".to_string(),
+        title: "Wire up login".to_string(),
+    }];
+
+    assert!(report
+        .format_text()
+        .contains("Today: PROJ-7 Wire up login (planned)\n"));
+
+    report.today_work = vec![create_test_standup_item("toki", 1800, Some("Adding tests"))];
+    assert!(report
+        .format_slack()
+        .contains("*Today:* Adding tests on `toki`, PROJ-7 Wire up login (planned)\n"));
+
+    let json: serde_json::Value = serde_json::from_str(&report.format_json()).unwrap();
+    assert_eq!(json["today"]["planned"][0]["issue_id"], "PROJ-7");
+}
@@ -10,6 +10,11 @@
 use chrono::{DateTime, Duration, Utc};
 use std::collections::HashMap;
 use std::fmt::Write;
+use toki_storage::models::{
+    DEFAULT_ANALYZER_MERGE_GAP_MINUTES, DEFAULT_ANALYZER_MIN_BLOCK_MINUTES,
+    DEFAULT_FOCUS_MIN_SECONDS,
+};
+use toki_storage::{ActivitySpan, TrackingTimezone};
 
 #[cfg(test)]
 mod tests;
@@ -55,6 +60,31 @@ pub struct ActivitySegment {
     pub git_commits: Vec<String>,
     pub git_branch: Option<String>,
     pub browser_urls: Vec<String>,
+    pub terminal_commands: Vec<String>,
+}
+
+impl ActivitySegment {
+    /// Build a segment from a finished span and its recorded context
+    ///
+    /// Returns `None` for spans that are still open.
+    #[must_use]
+    pub fn from_span(span: &ActivitySpan) -> Option<Self> {
+        let end_time = span.end_time?;
+        let context = span.context.as_ref();
+        Some(Self {
+            start_time: span.start_time,
+            end_time,
+            project_name: None, // Would need to look up project
+            category: span.category.clone(),
+            edited_files: context.map(|c| c.edited_files.clone()).unwrap_or_default(),
+            git_commits: context.map(|c| c.git_commits.clone()).unwrap_or_default(),
+            git_branch: context.and_then(|c| c.git_branch.clone()),
+            browser_urls: context.map(|c| c.browser_urls.clone()).unwrap_or_default(),
+            terminal_commands: context
+                .map(|c| c.terminal_commands.clone())
+                .unwrap_or_default(),
+        })
+    }
 }
 
 /// Suggested time block
@@ -67,6 +97,45 @@ pub struct SuggestedTimeBlock {
     pub suggested_issues: Vec<SuggestedIssue>,
     pub confidence: f32,
     pub reasoning: Vec<String>, // Explanation for the suggestion
+    pub focus: BlockFocus,
+}
+
+/// Whether a time block was long enough to count as focused work
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockFocus {
+    /// At least the configured focus minimum
+    Focus,
+    /// Shorter than the focus minimum
+    #[default]
+    Fragmented,
+}
+
+impl BlockFocus {
+    /// Label a block of `duration_seconds` against a focus minimum
+    #[must_use]
+    pub fn classify(duration_seconds: u32, focus_min_seconds: u32) -> Self {
+        if duration_seconds >= focus_min_seconds {
+            Self::Focus
+        } else {
+            Self::Fragmented
+        }
+    }
+}
+
+impl std::fmt::Display for BlockFocus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Focus => write!(f, "Focus"),
+            Self::Fragmented => write!(f, "Fragmented"),
+        }
+    }
+}
+
+/// Label each block as Focus or Fragmented once merging is done
+pub fn label_focus_blocks(blocks: &mut [SuggestedTimeBlock], focus_min_seconds: u32) {
+    for block in blocks {
+        block.focus = BlockFocus::classify(block.duration_seconds, focus_min_seconds);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -90,19 +159,78 @@ pub enum WorkPattern {
     Unknown,
 }
 
+impl WorkPattern {
+    /// Name stored on activity spans and shown in insights
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SingleFocus => "SingleFocus",
+            Self::MultiTasking => "MultiTasking",
+            Self::Exploration => "Exploration",
+            Self::Maintenance => "Maintenance",
+            Self::CodeReview => "CodeReview",
+            Self::Debugging => "Debugging",
+            Self::Meeting => "Meeting",
+            Self::Documentation => "Documentation",
+            Self::Unknown => "Unknown",
+        }
+    }
+}
+
+impl std::fmt::Display for WorkPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Time analyzer
 pub struct TimeAnalyzer {
     min_block_duration: Duration, // Minimum time block duration
+    merge_gap: Duration,          // Similar segments closer than this share a block
+    focus_min_seconds: u32,       // Shorter blocks are labelled fragmented
+    day_boundary: Option<(TrackingTimezone, u32)>, // Work day split for daily summaries
 }
 
 impl TimeAnalyzer {
     #[must_use]
     pub fn new() -> Self {
+        Self::with_config(
+            Duration::minutes(i64::from(DEFAULT_ANALYZER_MIN_BLOCK_MINUTES)),
+            Duration::minutes(i64::from(DEFAULT_ANALYZER_MERGE_GAP_MINUTES)),
+        )
+    }
+
+    /// Analyzer with its own block thresholds
+    ///
+    /// Blocks shorter than `min_block` are not suggested, and segments with
+    /// the same pattern are only joined when less than `merge_gap` apart.
+    #[must_use]
+    pub fn with_config(min_block: Duration, merge_gap: Duration) -> Self {
         Self {
-            min_block_duration: Duration::minutes(5),
+            min_block_duration: min_block,
+            merge_gap,
+            focus_min_seconds: DEFAULT_FOCUS_MIN_SECONDS,
+            day_boundary: None,
         }
     }
 
+    /// Use a different minimum for blocks labelled Focus
+    #[must_use]
+    pub fn with_focus_min_seconds(mut self, focus_min_seconds: u32) -> Self {
+        self.focus_min_seconds = focus_min_seconds;
+        self
+    }
+
+    /// Split daily summaries into work days starting at `boundary_hour` in `timezone`
+    ///
+    /// Segments are counted for the work day they start in, so with a 04:00
+    /// boundary, activity at 01:00 belongs to the previous day's summary.
+    #[must_use]
+    pub fn with_day_boundary(mut self, timezone: TrackingTimezone, boundary_hour: u32) -> Self {
+        self.day_boundary = Some((timezone, boundary_hour));
+        self
+    }
+
     /// Analyze activity segments and suggest time block classifications
     #[must_use]
     pub fn analyze_and_suggest(&self, segments: &[ActivitySegment]) -> Vec<SuggestedTimeBlock> {
@@ -117,7 +245,7 @@ impl TimeAnalyzer {
         for segment in segments {
             let pattern = Self::detect_pattern(segment);
             let should_merge = current_pattern.as_ref() == Some(&pattern)
-                && Self::should_merge_segments(
+                && self.should_merge_segments(
                     current_block.as_ref().map(|b| b.end_time),
                     segment.start_time,
                 );
@@ -156,6 +284,12 @@ impl TimeAnalyzer {
         suggestions
     }
 
+    /// Detect the work pattern of a finished span, from its category and context
+    #[must_use]
+    pub fn detect_span_pattern(span: &ActivitySpan) -> Option<WorkPattern> {
+        ActivitySegment::from_span(span).map(|segment| Self::detect_pattern(&segment))
+    }
+
     /// Detect work pattern
     pub(crate) fn detect_pattern(segment: &ActivitySegment) -> WorkPattern {
         // Infer from file types
@@ -195,6 +329,20 @@ impl TimeAnalyzer {
             }
         }
 
+        // Infer from terminal commands
+        for command in &segment.terminal_commands {
+            let lower = command.to_lowercase();
+            if lower.starts_with("gh pr") || lower.starts_with("git diff") {
+                return WorkPattern::CodeReview;
+            }
+            if lower
+                .split_whitespace()
+                .any(|word| matches!(word, "test" | "pytest" | "jest" | "vitest" | "gdb" | "lldb"))
+            {
+                return WorkPattern::Debugging;
+            }
+        }
+
         // Infer from URLs
         for url in &segment.browser_urls {
             let lower = url.to_lowercase();
@@ -220,7 +368,8 @@ impl TimeAnalyzer {
                     return WorkPattern::Exploration;
                 }
             }
-            "Communication" => return WorkPattern::Meeting,
+            // Older data has calls and chat lumped together as "Communication"
+            "Meeting" | "Communication" => return WorkPattern::Meeting,
             _ => {}
         }
 
@@ -228,13 +377,17 @@ impl TimeAnalyzer {
     }
 
     /// Determine if two time segments should be merged
-    pub(crate) fn should_merge_segments(prev_end: Option<DateTime<Utc>>, next_start: DateTime<Utc>) -> bool {
+    pub(crate) fn should_merge_segments(
+        &self,
+        prev_end: Option<DateTime<Utc>>,
+        next_start: DateTime<Utc>,
+    ) -> bool {
         let Some(end) = prev_end else {
             return false;
         };
         let gap = next_start - end;
-        // If gap is less than 10 minutes, treat as same block
-        gap < Duration::minutes(10)
+        // If gap is less than the merge gap, treat as same block
+        gap < self.merge_gap
     }
 
     /// Create new time block
@@ -254,6 +407,7 @@ impl TimeAnalyzer {
             suggested_issues,
             confidence,
             reasoning,
+            focus: BlockFocus::default(),
         }
     }
 
@@ -325,6 +479,20 @@ impl TimeAnalyzer {
             }
         }
 
+        // From terminal commands (e.g. `git checkout PROJ-12`)
+        for command in &segment.terminal_commands {
+            for cap in issue_pattern.captures_iter(command) {
+                let id = cap[1].to_uppercase();
+                if !issues.iter().any(|i| i.issue_id == id) {
+                    issues.push(SuggestedIssue {
+                        issue_id: id,
+                        confidence: 0.75,
+                        reason: format!("From command: {command}"),
+                    });
+                }
+            }
+        }
+
         // From URLs
         for url in &segment.browser_urls {
             for cap in issue_pattern.captures_iter(url) {
@@ -391,13 +559,30 @@ impl TimeAnalyzer {
     }
 
     /// Generate daily summary
+    ///
+    /// With a day boundary set, segments that start in another work day are
+    /// left out; otherwise every segment is counted for `date`.
     #[must_use]
     pub fn generate_daily_summary(
         &self,
         date: chrono::NaiveDate,
         segments: &[ActivitySegment],
     ) -> DailySummaryReport {
-        let suggestions = self.analyze_and_suggest(segments);
+        let day_segments: Vec<ActivitySegment>;
+        let segments = match self.day_boundary {
+            Some((timezone, boundary_hour)) => {
+                day_segments = segments
+                    .iter()
+                    .filter(|s| timezone.work_date(s.start_time, boundary_hour) == date)
+                    .cloned()
+                    .collect();
+                &day_segments
+            }
+            None => segments,
+        };
+
+        let mut suggestions = self.analyze_and_suggest(segments);
+        label_focus_blocks(&mut suggestions, self.focus_min_seconds);
 
         let total_seconds: u32 = segments
             .iter()
@@ -452,6 +637,69 @@ pub struct DailySummaryReport {
 }
 
 impl DailySummaryReport {
+    /// Report for a day without any activity
+    #[must_use]
+    pub fn empty(date: chrono::NaiveDate) -> Self {
+        Self {
+            date,
+            total_active_seconds: 0,
+            classified_seconds: 0,
+            unclassified_seconds: 0,
+            project_breakdown: HashMap::new(),
+            suggested_blocks: Vec::new(),
+        }
+    }
+
+    /// Add another report's time and blocks to this one
+    ///
+    /// Times are summed per project, keeping projects that only one of the
+    /// reports has, and the other report's blocks are appended. `date` is
+    /// left alone, so merge into the first day of a range.
+    pub fn merge(&mut self, other: &DailySummaryReport) {
+        self.total_active_seconds = self
+            .total_active_seconds
+            .saturating_add(other.total_active_seconds);
+        self.classified_seconds = self
+            .classified_seconds
+            .saturating_add(other.classified_seconds);
+        self.unclassified_seconds = self
+            .unclassified_seconds
+            .saturating_add(other.unclassified_seconds);
+        for (project, seconds) in &other.project_breakdown {
+            let total = self.project_breakdown.entry(project.clone()).or_insert(0);
+            *total = total.saturating_add(*seconds);
+        }
+        self.suggested_blocks
+            .extend(other.suggested_blocks.iter().cloned());
+    }
+
+    /// Time in blocks labelled Focus
+    #[must_use]
+    pub fn focus_seconds(&self) -> u32 {
+        self.blocks_seconds(BlockFocus::Focus)
+    }
+
+    /// Time in blocks labelled Fragmented
+    #[must_use]
+    pub fn fragmented_seconds(&self) -> u32 {
+        self.blocks_seconds(BlockFocus::Fragmented)
+    }
+
+    /// Share of block time spent in focus blocks, or `None` without blocks
+    #[must_use]
+    pub fn focus_ratio(&self) -> Option<f64> {
+        let total = self.focus_seconds() + self.fragmented_seconds();
+        (total > 0).then(|| f64::from(self.focus_seconds()) / f64::from(total))
+    }
+
+    fn blocks_seconds(&self, focus: BlockFocus) -> u32 {
+        self.suggested_blocks
+            .iter()
+            .filter(|b| b.focus == focus)
+            .map(|b| b.duration_seconds)
+            .sum()
+    }
+
     /// Format as readable report
     #[must_use]
     pub fn format_report(&self) -> String {
@@ -476,9 +724,19 @@ impl DailySummaryReport {
         );
         let _ = writeln!(
             report,
-            "Unclassified time: {}\n",
+            "Unclassified time: {}",
             format_duration(self.unclassified_seconds)
         );
+        if let Some(ratio) = self.focus_ratio() {
+            let _ = writeln!(
+                report,
+                "Focus: {} ({:.0}%), Fragmented: {}",
+                format_duration(self.focus_seconds()),
+                ratio * 100.0,
+                format_duration(self.fragmented_seconds())
+            );
+        }
+        report.push('\n');
 
         report.push_str("Project breakdown:\n");
         let mut projects: Vec<_> = self.project_breakdown.iter().collect();
@@ -499,11 +757,12 @@ impl DailySummaryReport {
                 let end = block.end_time.format("%H:%M");
                 let _ = writeln!(
                     report,
-                    "   {}. {} - {} ({})",
+                    "   {}. {} - {} ({}) [{}]",
                     i + 1,
                     start,
                     end,
-                    format_duration(block.duration_seconds)
+                    format_duration(block.duration_seconds),
+                    block.focus
                 );
                 let _ = writeln!(report, "      {}", block.suggested_description);
                 if !block.suggested_issues.is_empty() {
@@ -525,3 +784,101 @@ impl DailySummaryReport {
         report
     }
 }
+
+/// Format several days as a weekly report: a subtotal per day, then the
+/// totals across all of them
+///
+/// Reports for the same date are combined into one day. Days without a
+/// report are left out, and each project shows on how many days it was
+/// worked on.
+#[must_use]
+pub fn format_weekly_report(reports: &[DailySummaryReport]) -> String {
+    let mut days: Vec<DailySummaryReport> = Vec::new();
+    let mut sorted: Vec<&DailySummaryReport> = reports.iter().collect();
+    sorted.sort_by_key(|r| r.date);
+    for report in sorted {
+        match days.last_mut() {
+            Some(day) if day.date == report.date => day.merge(report),
+            _ => {
+                let mut day = DailySummaryReport::empty(report.date);
+                day.merge(report);
+                days.push(day);
+            }
+        }
+    }
+
+    let (Some(first), Some(last)) = (days.first(), days.last()) else {
+        return "=== Weekly Summary ===\nNo activity recorded\n".to_string();
+    };
+
+    let mut report = String::new();
+    let _ = writeln!(
+        report,
+        "=== Weekly Summary {} - {} ===",
+        first.date, last.date
+    );
+    report.push_str("Daily totals:\n");
+    let mut total = DailySummaryReport::empty(first.date);
+    let mut project_days: HashMap<&str, usize> = HashMap::new();
+    for day in &days {
+        let _ = writeln!(
+            report,
+            "   {} {}: {} (classified {})",
+            day.date.format("%a"),
+            day.date,
+            format_duration(day.total_active_seconds),
+            format_duration(day.classified_seconds)
+        );
+        for (project, seconds) in &day.project_breakdown {
+            if *seconds > 0 {
+                *project_days.entry(project.as_str()).or_insert(0) += 1;
+            }
+        }
+        total.merge(day);
+    }
+
+    report.push_str("\nProject breakdown:\n");
+    let mut projects: Vec<_> = total.project_breakdown.iter().collect();
+    projects.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    for (project, seconds) in projects {
+        let worked_days = project_days.get(project.as_str()).copied().unwrap_or(0);
+        let _ = writeln!(
+            report,
+            "   \u{2022} {project}: {} ({worked_days} of {} days)",
+            format_duration(*seconds),
+            days.len()
+        );
+    }
+
+    let percentage = if total.total_active_seconds > 0 {
+        f64::from(total.classified_seconds) / f64::from(total.total_active_seconds) * 100.0
+    } else {
+        0.0
+    };
+    let _ = writeln!(
+        report,
+        "\nTotal active time: {}",
+        format_duration(total.total_active_seconds)
+    );
+    let _ = writeln!(
+        report,
+        "Classified time: {} ({percentage:.0}%)",
+        format_duration(total.classified_seconds)
+    );
+    let _ = writeln!(
+        report,
+        "Unclassified time: {}",
+        format_duration(total.unclassified_seconds)
+    );
+    if let Some(ratio) = total.focus_ratio() {
+        let _ = writeln!(
+            report,
+            "Focus: {} ({:.0}%), Fragmented: {}",
+            format_duration(total.focus_seconds()),
+            ratio * 100.0,
+            format_duration(total.fragmented_seconds())
+        );
+    }
+
+    report
+}
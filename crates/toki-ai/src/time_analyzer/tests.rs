@@ -19,6 +19,7 @@ fn create_test_segment(
         git_commits: vec![],
         git_branch: None,
         browser_urls: vec![],
+        terminal_commands: vec![],
     }
 }
 
@@ -33,6 +34,7 @@ fn create_segment_with_files(files: Vec<&str>) -> ActivitySegment {
         git_commits: vec![],
         git_branch: None,
         browser_urls: vec![],
+        terminal_commands: vec![],
     }
 }
 
@@ -47,6 +49,7 @@ fn create_segment_with_commits(commits: Vec<&str>) -> ActivitySegment {
         git_commits: commits.into_iter().map(String::from).collect(),
         git_branch: None,
         browser_urls: vec![],
+        terminal_commands: vec![],
     }
 }
 
@@ -61,6 +64,7 @@ fn create_segment_with_urls(urls: Vec<&str>) -> ActivitySegment {
         git_commits: vec![],
         git_branch: None,
         browser_urls: urls.into_iter().map(String::from).collect(),
+        terminal_commands: vec![],
     }
 }
 
@@ -75,6 +79,7 @@ fn create_segment_with_branch(branch: &str) -> ActivitySegment {
         git_commits: vec![],
         git_branch: Some(branch.to_string()),
         browser_urls: vec![],
+        terminal_commands: vec![],
     }
 }
 
@@ -239,6 +244,27 @@ fn test_detect_pattern_from_docs_commit() {
     assert_eq!(TimeAnalyzer::detect_pattern(&segment), WorkPattern::Documentation);
 }
 
+#[test]
+fn test_detect_span_pattern_uses_span_context() {
+    let start = Utc::now() - Duration::minutes(30);
+    let mut span = ActivitySpan::new(
+        "com.microsoft.VSCode".to_string(),
+        "Coding".to_string(),
+        start,
+        None,
+        None,
+        None,
+    );
+    span.add_edited_file("docs/README.md".to_string());
+    // Open spans have no pattern yet
+    assert_eq!(TimeAnalyzer::detect_span_pattern(&span), None);
+
+    span.end_time = Some(Utc::now());
+    let pattern = TimeAnalyzer::detect_span_pattern(&span).unwrap();
+    assert_eq!(pattern, WorkPattern::Documentation);
+    assert_eq!(pattern.to_string(), "Documentation");
+}
+
 #[test]
 fn test_detect_pattern_from_review_commit() {
     let segment = create_segment_with_commits(vec!["review: address PR comments"]);
@@ -309,21 +335,21 @@ fn test_detect_pattern_default_single_focus() {
 #[test]
 fn test_should_merge_segments_no_prev_end() {
     let next_start = Utc::now();
-    assert!(!TimeAnalyzer::should_merge_segments(None, next_start));
+    assert!(!TimeAnalyzer::new().should_merge_segments(None, next_start));
 }
 
 #[test]
 fn test_should_merge_segments_small_gap() {
     let now = Utc::now();
     let prev_end = now - Duration::minutes(5);
-    assert!(TimeAnalyzer::should_merge_segments(Some(prev_end), now));
+    assert!(TimeAnalyzer::new().should_merge_segments(Some(prev_end), now));
 }
 
 #[test]
 fn test_should_merge_segments_large_gap() {
     let now = Utc::now();
     let prev_end = now - Duration::minutes(15);
-    assert!(!TimeAnalyzer::should_merge_segments(Some(prev_end), now));
+    assert!(!TimeAnalyzer::new().should_merge_segments(Some(prev_end), now));
 }
 
 #[test]
@@ -331,14 +357,49 @@ fn test_should_merge_segments_exact_boundary() {
     let now = Utc::now();
     let prev_end = now - Duration::minutes(10);
     // Gap is exactly 10 minutes, should NOT merge (< 10, not <=)
-    assert!(!TimeAnalyzer::should_merge_segments(Some(prev_end), now));
+    assert!(!TimeAnalyzer::new().should_merge_segments(Some(prev_end), now));
 }
 
 #[test]
 fn test_should_merge_segments_just_under_boundary() {
     let now = Utc::now();
     let prev_end = now - Duration::minutes(9) - Duration::seconds(59);
-    assert!(TimeAnalyzer::should_merge_segments(Some(prev_end), now));
+    assert!(TimeAnalyzer::new().should_merge_segments(Some(prev_end), now));
+}
+
+#[test]
+fn test_should_merge_segments_with_configured_gap() {
+    let now = Utc::now();
+    let prev_end = now - Duration::minutes(7);
+    let analyzer = TimeAnalyzer::with_config(Duration::minutes(5), Duration::minutes(5));
+
+    assert!(TimeAnalyzer::new().should_merge_segments(Some(prev_end), now));
+    assert!(!analyzer.should_merge_segments(Some(prev_end), now));
+}
+
+#[test]
+fn test_configured_merge_gap_splits_blocks() {
+    // Two 20-minute segments of the same pattern, 7 minutes apart
+    let segments = vec![
+        create_test_segment(Some("project"), "Coding", 47, 27),
+        create_test_segment(Some("project"), "Coding", 20, 0),
+    ];
+
+    let merged = TimeAnalyzer::new().analyze_and_suggest(&segments);
+    assert_eq!(merged.len(), 1);
+
+    let split = TimeAnalyzer::with_config(Duration::minutes(5), Duration::minutes(5))
+        .analyze_and_suggest(&segments);
+    assert_eq!(split.len(), 2);
+}
+
+#[test]
+fn test_configured_min_block_drops_short_blocks() {
+    let segments = vec![create_test_segment(Some("project"), "Coding", 8, 0)];
+
+    assert_eq!(TimeAnalyzer::new().analyze_and_suggest(&segments).len(), 1);
+    let analyzer = TimeAnalyzer::with_config(Duration::minutes(10), Duration::minutes(10));
+    assert!(analyzer.analyze_and_suggest(&segments).is_empty());
 }
 
 // ==================== extract_issues tests ====================
@@ -374,6 +435,31 @@ fn test_extract_issues_from_url() {
     assert!((issues[0].confidence - 0.7).abs() < 0.01);
 }
 
+#[test]
+fn test_extract_issues_and_pattern_from_terminal_commands() {
+    let mut segment = create_test_segment(Some("api"), "Terminal", 30, 0);
+    segment.terminal_commands = vec![
+        "git checkout -b ops-17-rollout".to_string(),
+        "cargo test -p api".to_string(),
+    ];
+
+    let issues = TimeAnalyzer::extract_issues(&segment);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].issue_id, "OPS-17");
+    assert!(issues[0].reason.contains("git checkout"));
+    assert_eq!(
+        TimeAnalyzer::detect_pattern(&segment),
+        WorkPattern::Debugging
+    );
+
+    // `latest` is not a test run
+    segment.terminal_commands = vec!["docker pull api:latest".to_string()];
+    assert_eq!(
+        TimeAnalyzer::detect_pattern(&segment),
+        WorkPattern::SingleFocus
+    );
+}
+
 #[test]
 fn test_extract_issues_multiple_sources() {
     let now = Utc::now();
@@ -386,6 +472,7 @@ fn test_extract_issues_multiple_sources() {
         git_commits: vec!["PROJ-123: implement feature".to_string()],
         git_branch: Some("feature/PROJ-123-feature".to_string()),
         browser_urls: vec!["https://jira.com/PROJ-456".to_string()],
+        terminal_commands: vec![],
     };
 
     let issues = TimeAnalyzer::extract_issues(&segment);
@@ -601,6 +688,7 @@ fn test_analyze_and_suggest_single_segment() {
         git_commits: vec!["PROJ-123: Add feature".to_string()],
         git_branch: None,
         browser_urls: vec![],
+        terminal_commands: vec![],
     };
 
     let suggestions = analyzer.analyze_and_suggest(&[segment]);
@@ -622,6 +710,7 @@ fn test_analyze_and_suggest_filters_short_blocks() {
         git_commits: vec![],
         git_branch: None,
         browser_urls: vec![],
+        terminal_commands: vec![],
     };
 
     let suggestions = analyzer.analyze_and_suggest(&[segment]);
@@ -646,6 +735,7 @@ fn test_analyze_and_suggest_merges_similar_patterns() {
             git_commits: vec![],
             git_branch: None,
             browser_urls: vec![],
+            terminal_commands: vec![],
         },
         ActivitySegment {
             start_time: now - Duration::minutes(10), // 5 min gap (< 10 min)
@@ -656,6 +746,7 @@ fn test_analyze_and_suggest_merges_similar_patterns() {
             git_commits: vec![],
             git_branch: None,
             browser_urls: vec![],
+            terminal_commands: vec![],
         },
     ];
 
@@ -680,6 +771,7 @@ fn test_analyze_and_suggest_separates_different_patterns() {
             git_commits: vec![],
             git_branch: None,
             browser_urls: vec![],
+            terminal_commands: vec![],
         },
         ActivitySegment {
             start_time: now - Duration::minutes(30),
@@ -690,6 +782,7 @@ fn test_analyze_and_suggest_separates_different_patterns() {
             git_commits: vec![],
             git_branch: None,
             browser_urls: vec![],
+            terminal_commands: vec![],
         },
     ];
 
@@ -732,6 +825,7 @@ fn test_generate_daily_summary_with_data() {
             git_commits: vec!["PROJ-1: feature".to_string()],
             git_branch: None,
             browser_urls: vec![],
+            terminal_commands: vec![],
         },
         ActivitySegment {
             start_time: now - Duration::hours(1),
@@ -742,6 +836,7 @@ fn test_generate_daily_summary_with_data() {
             git_commits: vec![],
             git_branch: None,
             browser_urls: vec![],
+            terminal_commands: vec![],
         },
     ];
 
@@ -770,6 +865,7 @@ fn test_generate_daily_summary_project_times() {
             git_commits: vec![],
             git_branch: None,
             browser_urls: vec![],
+            terminal_commands: vec![],
         },
         ActivitySegment {
             start_time: now - Duration::minutes(30),
@@ -780,6 +876,7 @@ fn test_generate_daily_summary_project_times() {
             git_commits: vec![],
             git_branch: None,
             browser_urls: vec![],
+            terminal_commands: vec![],
         },
     ];
 
@@ -790,6 +887,47 @@ fn test_generate_daily_summary_project_times() {
     assert!(*project_a_time >= 5300 && *project_a_time <= 5500);
 }
 
+#[test]
+fn test_generate_daily_summary_counts_early_hours_for_previous_work_day() {
+    // Work days start at 04:00 at +08:00, i.e. 20:00 UTC the day before
+    let analyzer = TimeAnalyzer::new().with_day_boundary("+08:00".parse().unwrap(), 4);
+    let at = |rfc3339: &str| {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    };
+    let segment = |project: &str, start: &str, end: &str| ActivitySegment {
+        start_time: at(start),
+        end_time: at(end),
+        project_name: Some(project.to_string()),
+        category: "Coding".to_string(),
+        edited_files: vec![],
+        git_commits: vec![],
+        git_branch: None,
+        browser_urls: vec![],
+        terminal_commands: vec![],
+    };
+    let segments = vec![
+        // 22:00-23:00 local on the 14th
+        segment("evening", "2025-03-14T14:00:00Z", "2025-03-14T15:00:00Z"),
+        // 01:00-02:00 local on the 15th, before the boundary
+        segment("late-night", "2025-03-14T17:00:00Z", "2025-03-14T18:00:00Z"),
+        // 09:00-10:00 local on the 15th
+        segment("morning", "2025-03-15T01:00:00Z", "2025-03-15T02:00:00Z"),
+    ];
+
+    let march_14 =
+        analyzer.generate_daily_summary(NaiveDate::from_ymd_opt(2025, 3, 14).unwrap(), &segments);
+    assert_eq!(march_14.total_active_seconds, 7200);
+    assert!(march_14.project_breakdown.contains_key("evening"));
+    assert!(march_14.project_breakdown.contains_key("late-night"));
+
+    let march_15 =
+        analyzer.generate_daily_summary(NaiveDate::from_ymd_opt(2025, 3, 15).unwrap(), &segments);
+    assert_eq!(march_15.total_active_seconds, 3600);
+    assert!(march_15.project_breakdown.contains_key("morning"));
+}
+
 // ==================== DailySummaryReport::format_report tests ====================
 
 #[test]
@@ -848,6 +986,7 @@ fn test_format_report_with_blocks() {
             ],
             confidence: 0.9,
             reasoning: vec![],
+            focus: BlockFocus::Focus,
         },
     ];
 
@@ -868,6 +1007,135 @@ fn test_format_report_with_blocks() {
     assert!(output.contains("Confidence: 90%"));
 }
 
+// ==================== Weekly rollup tests ====================
+
+fn day_report(day: u32, projects: &[(&str, u32)], unclassified: u32) -> DailySummaryReport {
+    let project_breakdown: HashMap<String, u32> = projects
+        .iter()
+        .map(|(name, seconds)| ((*name).to_string(), *seconds))
+        .collect();
+    let classified: u32 = project_breakdown.values().sum();
+    DailySummaryReport {
+        date: NaiveDate::from_ymd_opt(2025, 3, day).unwrap(),
+        total_active_seconds: classified + unclassified,
+        classified_seconds: classified,
+        unclassified_seconds: unclassified,
+        project_breakdown,
+        suggested_blocks: vec![],
+    }
+}
+
+#[test]
+fn test_merge_sums_time_and_keeps_projects_from_either_day() {
+    let mut monday = day_report(10, &[("toki", 3600), ("api", 1800)], 600);
+    monday.suggested_blocks.push(block_of_minutes(30));
+    let mut tuesday = day_report(11, &[("toki", 1800), ("web", 900)], 300);
+    tuesday.suggested_blocks.push(block_of_minutes(45));
+
+    monday.merge(&tuesday);
+
+    assert_eq!(monday.date, NaiveDate::from_ymd_opt(2025, 3, 10).unwrap());
+    assert_eq!(monday.total_active_seconds, 9000);
+    assert_eq!(monday.classified_seconds, 8100);
+    assert_eq!(monday.unclassified_seconds, 900);
+    assert_eq!(monday.project_breakdown.get("toki"), Some(&5400));
+    assert_eq!(monday.project_breakdown.get("api"), Some(&1800));
+    assert_eq!(monday.project_breakdown.get("web"), Some(&900));
+    assert_eq!(monday.suggested_blocks.len(), 2);
+}
+
+#[test]
+fn test_weekly_report_has_day_subtotals_and_grand_total() {
+    let reports = vec![
+        day_report(12, &[("web", 1800)], 0),
+        day_report(10, &[("toki", 3600)], 1200),
+        // A second report for Monday is folded into the same day
+        day_report(10, &[("toki", 600)], 0),
+    ];
+
+    let output = format_weekly_report(&reports);
+
+    assert!(output.contains("=== Weekly Summary 2025-03-10 - 2025-03-12 ==="));
+    assert!(output.contains("Mon 2025-03-10: 1h 30m (classified 1h 10m)"));
+    assert!(output.contains("Wed 2025-03-12: 30m (classified 30m)"));
+    assert!(!output.contains("2025-03-11:"));
+    assert!(output.contains("toki: 1h 10m (1 of 2 days)"));
+    assert!(output.contains("web: 30m (1 of 2 days)"));
+    assert!(output.contains("Total active time: 2h 0m"));
+    assert!(output.contains("Classified time: 1h 40m (83%)"));
+    assert!(output.contains("Unclassified time: 20m"));
+    assert!(output.find("Mon 2025-03-10").unwrap() < output.find("Wed 2025-03-12").unwrap());
+}
+
+#[test]
+fn test_weekly_report_without_days() {
+    let output = format_weekly_report(&[]);
+    assert!(output.contains("No activity recorded"));
+}
+
+// ==================== Focus labelling tests ====================
+
+fn block_of_minutes(minutes: i64) -> SuggestedTimeBlock {
+    let now = Utc::now();
+    SuggestedTimeBlock {
+        start_time: now - Duration::minutes(minutes),
+        end_time: now,
+        duration_seconds: duration_seconds(now - Duration::minutes(minutes), now),
+        suggested_description: "Work".to_string(),
+        suggested_issues: vec![],
+        confidence: 0.5,
+        reasoning: vec![],
+        focus: BlockFocus::default(),
+    }
+}
+
+#[test]
+fn test_focus_blocks_labelled_at_pomodoro_threshold() {
+    let mut blocks = vec![block_of_minutes(30), block_of_minutes(5)];
+    label_focus_blocks(&mut blocks, 25 * 60);
+
+    assert_eq!(blocks[0].focus, BlockFocus::Focus);
+    assert_eq!(blocks[1].focus, BlockFocus::Fragmented);
+    assert_eq!(BlockFocus::classify(25 * 60, 25 * 60), BlockFocus::Focus);
+}
+
+#[test]
+fn test_summary_reports_focus_ratio() {
+    let mut suggested_blocks = vec![block_of_minutes(30), block_of_minutes(10)];
+    label_focus_blocks(&mut suggested_blocks, 25 * 60);
+    let report = DailySummaryReport {
+        date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+        total_active_seconds: 2400,
+        classified_seconds: 0,
+        unclassified_seconds: 2400,
+        project_breakdown: HashMap::new(),
+        suggested_blocks,
+    };
+
+    assert_eq!(report.focus_seconds(), 1800);
+    assert_eq!(report.fragmented_seconds(), 600);
+    assert!((report.focus_ratio().unwrap() - 0.75).abs() < f64::EPSILON);
+
+    let output = report.format_report();
+    assert!(output.contains("Focus: 30m (75%), Fragmented: 10m"));
+    assert!(output.contains("(30m) [Focus]"));
+    assert!(output.contains("(10m) [Fragmented]"));
+}
+
+#[test]
+fn test_analyzer_uses_configured_focus_minimum() {
+    let analyzer = TimeAnalyzer::new().with_focus_min_seconds(10 * 60);
+    let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+    let segments = vec![create_test_segment(Some("toki"), "Coding", 15, 0)];
+
+    let report = analyzer.generate_daily_summary(date, &segments);
+    assert!(!report.suggested_blocks.is_empty());
+    assert!(report
+        .suggested_blocks
+        .iter()
+        .all(|b| b.focus == BlockFocus::Focus));
+}
+
 // ==================== SuggestedIssue tests ====================
 
 #[test]
@@ -909,6 +1177,7 @@ fn test_suggested_time_block_clone() {
         suggested_issues: vec![],
         confidence: 0.8,
         reasoning: vec!["reason1".to_string()],
+        focus: BlockFocus::Focus,
     };
 
     let cloned = block.clone();
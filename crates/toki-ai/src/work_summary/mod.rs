@@ -7,11 +7,11 @@
 //! - Issue associations
 
 use chrono::{DateTime, Duration, NaiveDate, Utc};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write;
 use std::sync::Arc;
 
-use toki_storage::{ClaudeSession, Database, Project};
+use toki_storage::{ClaudeSession, Database, DurationFormat, IssueCandidate, Project};
 
 #[cfg(test)]
 mod tests;
@@ -119,25 +119,16 @@ pub struct WorkSummary {
     pub sessions: Vec<ClaudeSession>,
     pub insights: Vec<String>,
     pub suggestions: Vec<String>,
+    /// Issues that moved to done during the period
+    pub closed_issues: Vec<IssueCandidate>,
+    /// How durations are rendered (`display.duration_format`)
+    pub duration_format: DurationFormat,
 }
 
 impl WorkSummary {
-    /// Format duration in human-readable form
-    pub(crate) fn format_duration(seconds: u32) -> String {
-        let hours = seconds / 3600;
-        let minutes = (seconds % 3600) / 60;
-
-        if hours > 0 {
-            if minutes > 0 {
-                format!("{hours}h {minutes}m")
-            } else {
-                format!("{hours}h")
-            }
-        } else if minutes > 0 {
-            format!("{minutes}m")
-        } else {
-            format!("{seconds}s")
-        }
+    /// Format duration using the configured display format
+    pub(crate) fn format_duration(&self, seconds: u32) -> String {
+        self.duration_format.format(seconds)
     }
 
     /// Generate a natural language summary
@@ -153,7 +144,7 @@ impl WorkSummary {
         let _ = writeln!(
             output,
             "- **Total Time**: {}",
-            Self::format_duration(self.total_seconds)
+            self.format_duration(self.total_seconds)
         );
         let _ = writeln!(output, "- **Sessions**: {}", self.session_count);
         let _ = writeln!(output, "- **Tool Calls**: {}", self.total_tool_calls);
@@ -180,7 +171,7 @@ impl WorkSummary {
                 let _ = writeln!(
                     output,
                     "- Time: {}",
-                    Self::format_duration(project.total_seconds)
+                    self.format_duration(project.total_seconds)
                 );
                 let _ = writeln!(output, "- Sessions: {}", project.session_count);
                 let _ = writeln!(output, "- Tool Calls: {}", project.tool_calls);
@@ -188,6 +179,22 @@ impl WorkSummary {
             }
         }
 
+        // Closed issues, grouped by label once any of them is labeled
+        if !self.closed_issues.is_empty() {
+            output.push_str("## Closed in Period\n\n");
+            if self.closed_issues.iter().all(|i| i.labels.is_empty()) {
+                let issues: Vec<&IssueCandidate> = self.closed_issues.iter().collect();
+                Self::write_closed_issues(&mut output, &issues);
+                output.push('\n');
+            } else {
+                for (label, issues) in self.closed_issues_by_label() {
+                    let _ = writeln!(output, "### {label}\n");
+                    Self::write_closed_issues(&mut output, &issues);
+                    output.push('\n');
+                }
+            }
+        }
+
         // Insights
         if !self.insights.is_empty() {
             output.push_str("## Insights\n\n");
@@ -209,6 +216,37 @@ impl WorkSummary {
         output
     }
 
+    /// Closed issues grouped by their first label, alphabetically
+    ///
+    /// Issues without labels come last under "Unlabeled".
+    #[must_use]
+    pub fn closed_issues_by_label(&self) -> Vec<(&str, Vec<&IssueCandidate>)> {
+        let mut groups: BTreeMap<&str, Vec<&IssueCandidate>> = BTreeMap::new();
+        let mut unlabeled = Vec::new();
+        for issue in &self.closed_issues {
+            match issue.labels.first() {
+                Some(label) => groups.entry(label.as_str()).or_default().push(issue),
+                None => unlabeled.push(issue),
+            }
+        }
+
+        let mut grouped: Vec<_> = groups.into_iter().collect();
+        if !unlabeled.is_empty() {
+            grouped.push(("Unlabeled", unlabeled));
+        }
+        grouped
+    }
+
+    fn write_closed_issues(output: &mut String, issues: &[&IssueCandidate]) {
+        for issue in issues {
+            let done_date = issue
+                .done_at
+                .map(|t| format!(" ({})", t.format("%Y-%m-%d")))
+                .unwrap_or_default();
+            let _ = writeln!(output, "- {} - {}{done_date}", issue.external_id, issue.title);
+        }
+    }
+
     /// Generate a concise one-paragraph summary
     ///
     /// # Panics
@@ -217,7 +255,7 @@ impl WorkSummary {
     /// (should never happen as the length check ensures at least 2 elements)
     #[must_use]
     pub fn generate_brief(&self) -> String {
-        let time_str = Self::format_duration(self.total_seconds);
+        let time_str = self.format_duration(self.total_seconds);
 
         if self.session_count == 0 {
             return format!(
@@ -260,7 +298,7 @@ impl WorkSummary {
         serde_json::json!({
             "period": self.period.display_name(),
             "total_seconds": self.total_seconds,
-            "total_time_formatted": Self::format_duration(self.total_seconds),
+            "total_time_formatted": self.format_duration(self.total_seconds),
             "session_count": self.session_count,
             "tool_calls": self.total_tool_calls,
             "prompts": self.total_prompts,
@@ -269,12 +307,21 @@ impl WorkSummary {
                     "name": p.project.name,
                     "path": p.project.path,
                     "seconds": p.total_seconds,
-                    "time_formatted": Self::format_duration(p.total_seconds),
+                    "time_formatted": self.format_duration(p.total_seconds),
                     "sessions": p.session_count,
                     "tool_calls": p.tool_calls,
                     "prompts": p.prompt_count
                 })
             }).collect::<Vec<_>>(),
+            "closed_issues": self.closed_issues.iter().map(|i| {
+                serde_json::json!({
+                    "id": i.external_id,
+                    "system": i.external_system,
+                    "title": i.title,
+                    "labels": i.labels,
+                    "done_at": i.done_at.map(|t| t.to_rfc3339())
+                })
+            }).collect::<Vec<_>>(),
             "insights": self.insights,
             "suggestions": self.suggestions
         })
@@ -340,11 +387,15 @@ impl WorkSummaryGenerator {
         projects.sort_by(|a, b| b.total_seconds.cmp(&a.total_seconds));
 
         // Generate insights
-        let insights = Self::generate_insights(&sessions, &projects, total_seconds);
+        let duration_format = self.db.get_settings()?.duration_format;
+        let insights =
+            Self::generate_insights(&sessions, &projects, total_seconds, duration_format);
 
         // Generate suggestions
         let suggestions = Self::generate_suggestions(&sessions, &projects);
 
+        let closed_issues = self.db.get_issues_done_between(start, end)?;
+
         Ok(WorkSummary {
             period,
             total_seconds,
@@ -355,6 +406,8 @@ impl WorkSummaryGenerator {
             sessions,
             insights,
             suggestions,
+            closed_issues,
+            duration_format,
         })
     }
 
@@ -402,9 +455,22 @@ impl WorkSummaryGenerator {
             prompt_count: total_prompts,
         };
 
-        let insights = Self::generate_insights(&sessions, &[project_summary.clone()], total_seconds);
+        let duration_format = self.db.get_settings()?.duration_format;
+        let insights = Self::generate_insights(
+            &sessions,
+            &[project_summary.clone()],
+            total_seconds,
+            duration_format,
+        );
         let suggestions = Self::generate_suggestions(&sessions, &[project_summary.clone()]);
 
+        let closed_issues = self
+            .db
+            .get_issues_done_between(start, end)?
+            .into_iter()
+            .filter(|i| i.project_id == project.id)
+            .collect();
+
         Ok(WorkSummary {
             period,
             total_seconds,
@@ -415,6 +481,8 @@ impl WorkSummaryGenerator {
             sessions,
             insights,
             suggestions,
+            closed_issues,
+            duration_format,
         })
     }
 
@@ -423,6 +491,7 @@ impl WorkSummaryGenerator {
         sessions: &[ClaudeSession],
         projects: &[ProjectWorkSummary],
         total_seconds: u32,
+        duration_format: DurationFormat,
     ) -> Vec<String> {
         let mut insights = Vec::new();
 
@@ -436,7 +505,7 @@ impl WorkSummaryGenerator {
         if avg_duration > 0 {
             insights.push(format!(
                 "Average session duration: {}",
-                WorkSummary::format_duration(avg_duration)
+                duration_format.format(avg_duration)
             ));
         }
 
@@ -1,5 +1,5 @@
 use super::*;
-use chrono::{NaiveDate, Timelike};
+use chrono::{NaiveDate, TimeZone, Timelike};
 use uuid::Uuid;
 
 // ==================== Helper functions ====================
@@ -59,6 +59,8 @@ fn create_empty_work_summary() -> WorkSummary {
         sessions: vec![],
         insights: vec![],
         suggestions: vec![],
+        closed_issues: vec![],
+        duration_format: DurationFormat::default(),
     }
 }
 
@@ -76,6 +78,8 @@ fn create_sample_work_summary() -> WorkSummary {
         sessions: vec![],
         insights: vec!["Test insight".to_string()],
         suggestions: vec!["Test suggestion".to_string()],
+        closed_issues: vec![],
+        duration_format: DurationFormat::default(),
     }
 }
 
@@ -175,40 +179,53 @@ fn test_summary_period_date_range_custom() {
 
 #[test]
 fn test_format_duration_seconds_only() {
-    assert_eq!(WorkSummary::format_duration(30), "30s");
-    assert_eq!(WorkSummary::format_duration(59), "59s");
+    let summary = create_empty_work_summary();
+    assert_eq!(summary.format_duration(30), "30s");
+    assert_eq!(summary.format_duration(59), "59s");
 }
 
 #[test]
 fn test_format_duration_zero() {
-    assert_eq!(WorkSummary::format_duration(0), "0s");
+    let summary = create_empty_work_summary();
+    assert_eq!(summary.format_duration(0), "0m");
 }
 
 #[test]
 fn test_format_duration_minutes_only() {
-    assert_eq!(WorkSummary::format_duration(60), "1m");
-    assert_eq!(WorkSummary::format_duration(120), "2m");
-    assert_eq!(WorkSummary::format_duration(45 * 60), "45m");
+    let summary = create_empty_work_summary();
+    assert_eq!(summary.format_duration(60), "1m");
+    assert_eq!(summary.format_duration(120), "2m");
+    assert_eq!(summary.format_duration(45 * 60), "45m");
 }
 
 #[test]
 fn test_format_duration_hours_only() {
-    assert_eq!(WorkSummary::format_duration(3600), "1h");
-    assert_eq!(WorkSummary::format_duration(2 * 3600), "2h");
+    let summary = create_empty_work_summary();
+    assert_eq!(summary.format_duration(3600), "1h");
+    assert_eq!(summary.format_duration(2 * 3600), "2h");
 }
 
 #[test]
 fn test_format_duration_hours_and_minutes() {
-    assert_eq!(WorkSummary::format_duration(3600 + 30 * 60), "1h 30m");
-    assert_eq!(WorkSummary::format_duration(2 * 3600 + 15 * 60), "2h 15m");
+    let summary = create_empty_work_summary();
+    assert_eq!(summary.format_duration(3600 + 30 * 60), "1h 30m");
+    assert_eq!(summary.format_duration(2 * 3600 + 15 * 60), "2h 15m");
 }
 
 #[test]
 fn test_format_duration_ignores_leftover_seconds() {
+    let summary = create_empty_work_summary();
     // Hours and minutes present, seconds ignored
-    assert_eq!(WorkSummary::format_duration(3600 + 30 * 60 + 45), "1h 30m");
+    assert_eq!(summary.format_duration(3600 + 30 * 60 + 45), "1h 30m");
     // Minutes present, seconds ignored
-    assert_eq!(WorkSummary::format_duration(5 * 60 + 30), "5m");
+    assert_eq!(summary.format_duration(5 * 60 + 30), "5m");
+}
+
+#[test]
+fn test_format_duration_uses_summary_format() {
+    let mut summary = create_empty_work_summary();
+    summary.duration_format = DurationFormat::DecimalHours;
+    assert_eq!(summary.format_duration(5430), "1.5h");
 }
 
 // ==================== WorkSummary::generate_text tests ====================
@@ -290,6 +307,8 @@ fn test_generate_brief_with_single_project() {
         sessions: vec![],
         insights: vec![],
         suggestions: vec![],
+        closed_issues: vec![],
+        duration_format: DurationFormat::default(),
     };
     let output = summary.generate_brief();
 
@@ -316,6 +335,8 @@ fn test_generate_brief_with_multiple_projects() {
         sessions: vec![],
         insights: vec![],
         suggestions: vec![],
+        closed_issues: vec![],
+        duration_format: DurationFormat::default(),
     };
     let output = summary.generate_brief();
 
@@ -339,6 +360,8 @@ fn test_generate_brief_with_three_projects() {
         sessions: vec![],
         insights: vec![],
         suggestions: vec![],
+        closed_issues: vec![],
+        duration_format: DurationFormat::default(),
     };
     let output = summary.generate_brief();
 
@@ -358,6 +381,8 @@ fn test_generate_brief_no_projects() {
         sessions: vec![],
         insights: vec![],
         suggestions: vec![],
+        closed_issues: vec![],
+        duration_format: DurationFormat::default(),
     };
     let output = summary.generate_brief();
 
@@ -427,7 +452,12 @@ fn test_generate_insights_empty_sessions() {
     let sessions: Vec<ClaudeSession> = vec![];
     let projects: Vec<ProjectWorkSummary> = vec![];
 
-    let insights = WorkSummaryGenerator::generate_insights(&sessions, &projects, 0);
+    let insights = WorkSummaryGenerator::generate_insights(
+        &sessions,
+        &projects,
+        0,
+        DurationFormat::default(),
+    );
 
     assert!(insights.is_empty());
 }
@@ -444,7 +474,12 @@ fn test_generate_insights_avg_duration() {
     ];
     let projects: Vec<ProjectWorkSummary> = vec![];
 
-    let insights = WorkSummaryGenerator::generate_insights(&sessions, &projects, 7200);
+    let insights = WorkSummaryGenerator::generate_insights(
+        &sessions,
+        &projects,
+        7200,
+        DurationFormat::default(),
+    );
 
     // Average duration: 7200/2 = 3600s = 1h
     assert!(insights.iter().any(|i| i.contains("Average session duration: 1h")));
@@ -460,7 +495,12 @@ fn test_generate_insights_tool_usage_rate() {
     ];
     let projects: Vec<ProjectWorkSummary> = vec![];
 
-    let insights = WorkSummaryGenerator::generate_insights(&sessions, &projects, 3600);
+    let insights = WorkSummaryGenerator::generate_insights(
+        &sessions,
+        &projects,
+        3600,
+        DurationFormat::default(),
+    );
 
     // 120 calls / 1 hour = 120 calls/hour
     assert!(insights.iter().any(|i| i.contains("Tool usage rate: 120.0 calls/hour")));
@@ -477,7 +517,12 @@ fn test_generate_insights_highly_focused() {
     ];
 
     // 100% of time on one project
-    let insights = WorkSummaryGenerator::generate_insights(&sessions, &projects, 3600);
+    let insights = WorkSummaryGenerator::generate_insights(
+        &sessions,
+        &projects,
+        3600,
+        DurationFormat::default(),
+    );
 
     assert!(insights.iter().any(|i| i.contains("Highly focused: 100% of time on focused-project")));
 }
@@ -495,7 +540,12 @@ fn test_generate_insights_context_switching() {
     ];
 
     // 33% each, more than 2 projects
-    let insights = WorkSummaryGenerator::generate_insights(&sessions, &projects, 3600);
+    let insights = WorkSummaryGenerator::generate_insights(
+        &sessions,
+        &projects,
+        3600,
+        DurationFormat::default(),
+    );
 
     assert!(insights.iter().any(|i| i.contains("Context switching: work spread across 3 projects")));
 }
@@ -508,7 +558,12 @@ fn test_generate_insights_active_sessions() {
     ];
     let projects: Vec<ProjectWorkSummary> = vec![];
 
-    let insights = WorkSummaryGenerator::generate_insights(&sessions, &projects, 3600);
+    let insights = WorkSummaryGenerator::generate_insights(
+        &sessions,
+        &projects,
+        3600,
+        DurationFormat::default(),
+    );
 
     assert!(insights.iter().any(|i| i.contains("1 session(s) currently active")));
 }
@@ -628,6 +683,8 @@ fn test_generate_text_zero_total_seconds() {
         sessions: vec![],
         insights: vec![],
         suggestions: vec![],
+        closed_issues: vec![],
+        duration_format: DurationFormat::default(),
     };
     let output = summary.generate_text();
 
@@ -657,9 +714,72 @@ fn test_generate_brief_yesterday_period() {
         sessions: vec![],
         insights: vec![],
         suggestions: vec![],
+        closed_issues: vec![],
+        duration_format: DurationFormat::default(),
     };
     let output = summary.generate_brief();
 
     assert!(output.starts_with("Yesterday:"));
     assert!(output.contains("30m of AI-assisted development"));
 }
+
+#[test]
+fn test_generate_text_lists_closed_issues() {
+    let mut summary = create_sample_work_summary();
+    let mut issue = IssueCandidate::new(
+        Uuid::new_v4(),
+        "PROJ-42".to_string(),
+        "plane".to_string(),
+        "Fix login redirect".to_string(),
+    );
+    issue.done_at = Some(Utc.with_ymd_and_hms(2025, 1, 12, 15, 30, 0).unwrap());
+    summary.closed_issues.push(issue);
+
+    let text = summary.generate_text();
+    assert!(text.contains("## Closed in Period"));
+    assert!(text.contains("- PROJ-42 - Fix login redirect (2025-01-12)"));
+    assert_eq!(summary.to_json()["closed_issues"][0]["id"], "PROJ-42");
+}
+
+#[test]
+fn test_generate_text_groups_closed_issues_by_label() {
+    let mut summary = create_sample_work_summary();
+    for (id, labels) in [
+        ("PROJ-1", vec!["frontend", "bug"]),
+        ("PROJ-2", vec![]),
+        ("PROJ-3", vec!["bug"]),
+        ("PROJ-4", vec!["frontend"]),
+    ] {
+        let mut issue = IssueCandidate::new(
+            Uuid::new_v4(),
+            id.to_string(),
+            "github".to_string(),
+            format!("Issue {id}"),
+        );
+        issue.labels = labels.into_iter().map(String::from).collect();
+        summary.closed_issues.push(issue);
+    }
+
+    let groups: Vec<(&str, Vec<&str>)> = summary
+        .closed_issues_by_label()
+        .into_iter()
+        .map(|(label, issues)| {
+            (
+                label,
+                issues.iter().map(|i| i.external_id.as_str()).collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        groups,
+        [
+            ("bug", vec!["PROJ-3"]),
+            ("frontend", vec!["PROJ-1", "PROJ-4"]),
+            ("Unlabeled", vec!["PROJ-2"]),
+        ]
+    );
+
+    let text = summary.generate_text();
+    assert!(text.contains("### frontend\n\n- PROJ-1 - Issue PROJ-1\n- PROJ-4 - Issue PROJ-4\n"));
+    assert_eq!(summary.to_json()["closed_issues"][0]["labels"][1], "bug");
+}
@@ -0,0 +1,220 @@
+//! Time block editing commands
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveTime, Utc};
+use clap::Subcommand;
+use toki_storage::{Database, TimeBlock};
+use uuid::Uuid;
+
+use super::issue::resolve_issue;
+
+#[derive(Subcommand, Debug)]
+pub enum BlockAction {
+    /// Split a block in two, attributing each part to a different issue
+    Split {
+        /// Time block ID
+        id: String,
+        /// Split point: HH:MM (UTC, on the block's day) or an RFC 3339 timestamp
+        #[arg(long)]
+        at: String,
+        /// Issue for the part before the split point (e.g., 42, #42, PROJ-42)
+        #[arg(long)]
+        issue_a: String,
+        /// Issue for the part after the split point
+        #[arg(long)]
+        issue_b: String,
+        /// Issue tracking system, when an ID exists in several (plane, notion)
+        #[arg(long)]
+        system: Option<String>,
+    },
+    /// Change a block's description or issue
+    Edit {
+        /// Time block ID
+        id: String,
+        /// New description
+        #[arg(long)]
+        description: Option<String>,
+        /// Attribute the whole block to this issue (e.g., 42, #42, PROJ-42)
+        #[arg(long)]
+        issue: Option<String>,
+        /// Issue tracking system, when an ID exists in several (plane, notion)
+        #[arg(long)]
+        system: Option<String>,
+    },
+    /// Merge a second block into the first
+    Merge {
+        /// Time block ID to keep
+        id: String,
+        /// Time block ID to merge into it (deleted afterwards)
+        other: String,
+    },
+    /// Show the recorded edits, merges, splits, and confirmations of a block
+    History {
+        /// Time block ID
+        id: String,
+    },
+}
+
+/// Handle time block commands
+pub fn handle_block_command(action: BlockAction) -> Result<()> {
+    match action {
+        BlockAction::Split {
+            id,
+            at,
+            issue_a,
+            issue_b,
+            system,
+        } => split_block(&id, &at, &issue_a, &issue_b, system.as_deref()),
+        BlockAction::Edit {
+            id,
+            description,
+            issue,
+            system,
+        } => edit_block(&id, description, issue.as_deref(), system.as_deref()),
+        BlockAction::Merge { id, other } => merge_blocks(&id, &other),
+        BlockAction::History { id } => show_history(&id),
+    }
+}
+
+fn split_block(
+    id: &str,
+    at: &str,
+    issue_a: &str,
+    issue_b: &str,
+    system: Option<&str>,
+) -> Result<()> {
+    let db = Database::new(None).context("Failed to open database")?;
+
+    let block_id = parse_block_id(id)?;
+    let block = db
+        .get_time_block(block_id)?
+        .with_context(|| format!("Time block {block_id} not found"))?;
+    let at = parse_split_time(at, &block)?;
+    let first_issue = resolve_issue(&db, issue_a, system)?;
+    let second_issue = resolve_issue(&db, issue_b, system)?;
+
+    let (first, second) = db.split_time_block(block_id, at, first_issue.id, second_issue.id)?;
+
+    let format = db.get_settings()?.duration_format;
+    println!("Split block {block_id}:");
+    for (part, issue) in [(&first, &first_issue), (&second, &second_issue)] {
+        println!(
+            "  {} - {}  {:>8}  #{} {}",
+            part.start_time.format("%H:%M"),
+            part.end_time.format("%H:%M"),
+            format.format(block_seconds(part)),
+            issue.external_id,
+            issue.title
+        );
+    }
+    Ok(())
+}
+
+fn edit_block(
+    id: &str,
+    description: Option<String>,
+    issue: Option<&str>,
+    system: Option<&str>,
+) -> Result<()> {
+    if description.is_none() && issue.is_none() {
+        anyhow::bail!("Nothing to change. Pass --description and/or --issue");
+    }
+    let db = Database::new(None).context("Failed to open database")?;
+
+    let block_id = parse_block_id(id)?;
+    let mut block = db
+        .get_time_block(block_id)?
+        .with_context(|| format!("Time block {block_id} not found"))?;
+    if let Some(description) = description {
+        block.description = description;
+    }
+    if let Some(issue) = issue {
+        block.work_item_ids = vec![resolve_issue(&db, issue, system)?.id];
+    }
+
+    db.edit_time_block(&block)?;
+    println!("Updated block {block_id}: {}", block.summary());
+    Ok(())
+}
+
+fn merge_blocks(id: &str, other: &str) -> Result<()> {
+    let db = Database::new(None).context("Failed to open database")?;
+
+    let block_id = parse_block_id(id)?;
+    let other_id = parse_block_id(other)?;
+    let merged = db.merge_time_blocks(block_id, other_id)?;
+    println!(
+        "Merged block {other_id} into {block_id}: {}",
+        merged.summary()
+    );
+    Ok(())
+}
+
+fn show_history(id: &str) -> Result<()> {
+    let db = Database::new(None).context("Failed to open database")?;
+
+    let block_id = parse_block_id(id)?;
+    let history = db.get_block_history(block_id)?;
+    if history.is_empty() {
+        println!("No recorded changes for block {block_id}.");
+        return Ok(());
+    }
+
+    println!("History of block {block_id}:");
+    for entry in history {
+        println!(
+            "  {}  {}",
+            entry.created_at.format("%Y-%m-%d %H:%M:%S"),
+            entry.action
+        );
+        if let Some(before) = entry.before {
+            println!("    before: {before}");
+        }
+        if let Some(after) = entry.after {
+            println!("    after:  {after}");
+        }
+    }
+    Ok(())
+}
+
+fn parse_block_id(id: &str) -> Result<Uuid> {
+    Uuid::parse_str(id.trim()).context("Invalid time block ID")
+}
+
+/// Parse `--at` as an RFC 3339 timestamp or a UTC time of day on the block's start date
+fn parse_split_time(at: &str, block: &TimeBlock) -> Result<DateTime<Utc>> {
+    let at = at.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(at) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    let time = NaiveTime::parse_from_str(at, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(at, "%H:%M:%S"))
+        .with_context(|| format!("Invalid split time '{at}'. Use HH:MM or RFC 3339"))?;
+    Ok(block.start_time.date_naive().and_time(time).and_utc())
+}
+
+fn block_seconds(block: &TimeBlock) -> u32 {
+    u32::try_from((block.end_time - block.start_time).num_seconds()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_split_time_on_block_day() {
+        let start = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        let block = TimeBlock::manual(start, start + chrono::Duration::hours(2), String::new());
+
+        assert_eq!(
+            parse_split_time("10:15", &block).unwrap(),
+            Utc.with_ymd_and_hms(2025, 3, 10, 10, 15, 0).unwrap()
+        );
+        assert_eq!(
+            parse_split_time("2025-03-10T19:30:00+09:00", &block).unwrap(),
+            Utc.with_ymd_and_hms(2025, 3, 10, 10, 30, 0).unwrap()
+        );
+        assert!(parse_split_time("quarter past", &block).is_err());
+    }
+}
@@ -0,0 +1,34 @@
+/// Native messaging host for the browser extension
+///
+/// The protocol and host manifest are described in `toki_core::native_messaging`.
+use anyhow::Result;
+use serde_json::json;
+use std::{io, path::Path};
+use toki_core::{
+    ipc::{IpcClient, IpcRequest, IpcResponse},
+    native_messaging::{read_message, write_message, BrowserTab},
+};
+
+/// Forward reported tabs to the daemon until the browser closes the pipe
+///
+/// Every message is acknowledged on stdout, which carries nothing else; logs
+/// go to stderr, where the browser shows them in its console.
+///
+/// # Errors
+///
+/// Returns an error if a message cannot be read or the reply cannot be written
+pub async fn run_browser_host(data_dir: &Path) -> Result<()> {
+    let client = IpcClient::new(&data_dir.join("toki.sock"));
+    let mut stdin = io::stdin();
+    let mut stdout = io::stdout();
+
+    while let Some(tab) = read_message::<BrowserTab>(&mut stdin)? {
+        let reply = match client.send_command(IpcRequest::BrowserTab(tab)).await {
+            Ok(IpcResponse::TabRecorded) => json!({ "ok": true }),
+            Ok(other) => json!({ "ok": false, "error": format!("Unexpected response: {other:?}") }),
+            Err(e) => json!({ "ok": false, "error": format!("Daemon not reachable: {e}") }),
+        };
+        write_message(&mut stdout, &reply)?;
+    }
+    Ok(())
+}
@@ -1,10 +1,27 @@
 /// Configuration management command handlers
-use anyhow::Result;
-use toki_storage::{Database, IntegrationConfig};
+use anyhow::{Context, Result};
+use toki_ai::NameMatchAlgorithm;
+use toki_core::AppDisplayNames;
+use toki_storage::{Database, IntegrationConfig, Settings};
 
 pub fn handle_config_get(key: &str) -> Result<()> {
     let db = Database::new(None)?;
-    let value = get_config_value(&db, key)?;
+    // Bundle ids and project names contain dots, so these keys are split on the first dot only
+    let value = if let Some(bundle_id) = key.strip_prefix("app_names.") {
+        AppDisplayNames::load(&db)?.get(bundle_id).map(String::from)
+    } else if let Some(project) = key.strip_prefix("billable.") {
+        let project_id = config_project_id(&db, "billable", project)?;
+        db.get_project_billable_categories()?
+            .remove(&project_id)
+            .map(|categories| categories.join(", "))
+    } else if let Some(project) = key.strip_prefix("work_hours.") {
+        let project_id = config_project_id(&db, "work_hours", project)?;
+        db.get_project_work_hours()?
+            .remove(&project_id)
+            .map(|hours| hours.to_string())
+    } else {
+        get_config_value(&db, key)?
+    };
     match value {
         Some(v) => println!("{key} = {v}"),
         None => println!("{key} is not set"),
@@ -14,17 +31,315 @@ pub fn handle_config_get(key: &str) -> Result<()> {
 
 pub fn handle_config_set(key: &str, value: &str) -> Result<()> {
     let db = Database::new(None)?;
-    set_config_value(&db, key, value)?;
+    apply_config_value(&db, key, value)?;
     println!("Set {key} = {value}");
     Ok(())
 }
 
+/// Set any configuration key, including the per-app and per-project sections
+fn apply_config_value(db: &Database, key: &str, value: &str) -> Result<()> {
+    // Bundle ids and project names contain dots, so these keys are split on the first dot only
+    if let Some(bundle_id) = key.strip_prefix("app_names.") {
+        set_app_name(db, bundle_id, value)
+    } else if let Some(project) = key.strip_prefix("billable.") {
+        set_project_billable(db, project, value)
+    } else if let Some(project) = key.strip_prefix("work_hours.") {
+        set_project_work_hours(db, project, value)
+    } else {
+        set_config_value(db, key, value)
+    }
+}
+
+/// Keys written by `toki config export`, by section
+const EXPORT_KEYS: &[(&str, &[&str])] = &[
+    ("plane", &["api_url", "api_key", "workspace", "project"]),
+    ("github", &["token", "project", "estimate_field"]),
+    ("gitlab", &["api_url", "token", "project"]),
+    ("jira", &["api_url", "email", "api_key", "project"]),
+    ("notion", &["api_key", "database_id", "time_property"]),
+    ("trello", &["api_key", "token"]),
+    (
+        "settings",
+        &[
+            "idle_threshold_seconds",
+            "work_item_tracking",
+            "capture_window_title",
+            "capture_browser_url",
+            "url_whitelist",
+            "span_context_cap",
+            "name_match_algorithm",
+            "name_match_threshold",
+            "max_session_seconds",
+            "billable_categories",
+            "work_hours",
+            "min_activity_seconds",
+            "timezone",
+            "day_boundary_hour",
+            "require_focused_display",
+            "self_tracking",
+            "commit_grace_seconds",
+            "embedding_refresh_hours",
+            "issue_id_pattern",
+            "candidate_warmup_threads",
+            "capture_terminal_commands",
+            "terminal_history_path",
+        ],
+    ),
+    ("display", &["duration_format"]),
+    (
+        "notify",
+        &[
+            "enabled",
+            "desktop",
+            "webhook_url",
+            "focus_hours",
+            "idle_minutes",
+            "review_time",
+            "cooldown_minutes",
+        ],
+    ),
+    ("slack", &["webhook_url"]),
+    ("integrations", &["debug_log"]),
+    (
+        "ai",
+        &[
+            "provider",
+            "model",
+            "api_key",
+            "base_url",
+            "enabled",
+            "auto_attach_min_confidence",
+            "backfill_min_confidence",
+            "embeddings",
+        ],
+    ),
+    ("calendar", &["overlap_policy"]),
+    ("timeline", &["focus_min_seconds"]),
+    ("sync", &["min_entry_seconds"]),
+    ("next", &["cache_size", "cache_ttl_seconds", "energy_lookback_weeks"]),
+    ("analyzer", &["min_block_minutes", "merge_gap_minutes"]),
+];
+
+/// Sections keyed by bundle id or project name rather than a fixed field list
+const MAPPED_SECTIONS: &[&str] = &["app_names", "billable", "work_hours"];
+
+/// Whether a key holds a credential, which exports leave out unless asked
+fn is_secret_key(section: &str, field: &str) -> bool {
+    // Chat webhook URLs embed their own access token
+    matches!(field, "api_key" | "token")
+        || (matches!(section, "notify" | "slack") && field == "webhook_url")
+}
+
+pub fn handle_config_export(path: &str, include_secrets: bool) -> Result<()> {
+    let db = Database::new(None)?;
+    let exported = export_config(&db, include_secrets)?;
+
+    let mut contents = String::from("# toki configuration, apply with `toki config import`\n");
+    if include_secrets {
+        contents.push_str("# Contains API keys and tokens. Keep this file private.\n");
+        eprintln!("Warning: {path} contains API keys and tokens in plain text. Keep it private.");
+    }
+    contents.push_str(&toml::to_string(&exported)?);
+    std::fs::write(path, contents).with_context(|| format!("Failed to write {path}"))?;
+
+    println!("Exported configuration to {path}");
+    if !include_secrets {
+        println!("API keys and tokens were left out; pass --include-secrets to export them.");
+    }
+    Ok(())
+}
+
+pub fn handle_config_import(path: &str) -> Result<()> {
+    let db = Database::new(None)?;
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {path}"))?;
+    let table: toml::Table =
+        toml::from_str(&contents).with_context(|| format!("Failed to parse {path}"))?;
+
+    let applied = import_config(&db, &table)?;
+    println!("Imported {applied} configuration values from {path}");
+    Ok(())
+}
+
+/// Collect every set configuration value as TOML, one table per section
+fn export_config(db: &Database, include_secrets: bool) -> Result<toml::Table> {
+    let mut exported = toml::Table::new();
+    for (section, fields) in EXPORT_KEYS {
+        let mut table = toml::Table::new();
+        for field in *fields {
+            if !include_secrets && is_secret_key(section, field) {
+                continue;
+            }
+            if let Some(value) = get_config_value(db, &format!("{section}.{field}"))? {
+                table.insert((*field).to_string(), toml::Value::String(value));
+            }
+        }
+        if !table.is_empty() {
+            exported.insert((*section).to_string(), toml::Value::Table(table));
+        }
+    }
+
+    let mut app_names = toml::Table::new();
+    for (bundle_id, name) in db.get_app_display_names()? {
+        app_names.insert(bundle_id, toml::Value::String(name));
+    }
+    let mut billable = toml::Table::new();
+    for (project_id, categories) in db.get_project_billable_categories()? {
+        if let Some(project) = db.get_project(project_id)? {
+            billable.insert(project.name, toml::Value::String(categories.join(", ")));
+        }
+    }
+    let mut work_hours = toml::Table::new();
+    for (project_id, hours) in db.get_project_work_hours()? {
+        if let Some(project) = db.get_project(project_id)? {
+            work_hours.insert(project.name, toml::Value::String(hours.to_string()));
+        }
+    }
+    for (section, table) in [
+        ("app_names", app_names),
+        ("billable", billable),
+        ("work_hours", work_hours),
+    ] {
+        if !table.is_empty() {
+            exported.insert(section.to_string(), toml::Value::Table(table));
+        }
+    }
+
+    Ok(exported)
+}
+
+/// Apply an exported configuration, returning how many values were set
+///
+/// Every key is checked before anything is written, so a file with an
+/// unknown key changes nothing.
+fn import_config(db: &Database, table: &toml::Table) -> Result<usize> {
+    let mut values = Vec::new();
+    let mut invalid = Vec::new();
+    for (section, fields) in table {
+        let known = EXPORT_KEYS
+            .iter()
+            .find(|(name, _)| name == section)
+            .map(|(_, fields)| *fields);
+        let Some(fields_table) = fields.as_table() else {
+            invalid.push(section.clone());
+            continue;
+        };
+        for (field, value) in fields_table {
+            let key = format!("{section}.{field}");
+            let field_known = known.map_or(MAPPED_SECTIONS.contains(&section.as_str()), |known| {
+                known.contains(&field.as_str())
+            });
+            let value = match value {
+                toml::Value::String(s) => Some(s.clone()),
+                toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => {
+                    Some(value.to_string())
+                }
+                _ => None,
+            };
+            match value {
+                Some(value) if field_known => values.push((key, value)),
+                _ => invalid.push(key),
+            }
+        }
+    }
+    if !invalid.is_empty() {
+        anyhow::bail!(
+            "Invalid configuration keys (nothing was imported): {}",
+            invalid.join(", ")
+        );
+    }
+
+    for (key, value) in &values {
+        apply_config_value(db, key, value).with_context(|| format!("Failed to set {key}"))?;
+    }
+    Ok(values.len())
+}
+
 pub fn handle_config_list() -> Result<()> {
     let db = Database::new(None)?;
 
     println!("Configuration:");
     println!("\u{2550}\u{2550}\u{2550}\u{2550}\u{2550}\u{2550}\u{2550}\u{2550}\u{2550}\u{2550}\u{2550}\u{2550}\u{2550}\u{2550}");
 
+    list_integrations(&db);
+
+    let settings = db.get_settings()?;
+    list_settings(&settings);
+
+    println!("\n[display]");
+    println!("  duration_format = {}", settings.duration_format);
+
+    let notify = &settings.notifications;
+    println!("\n[notify]");
+    println!("  enabled = {}", notify.enabled);
+    println!("  desktop = {}", notify.desktop);
+    if let Some(url) = &notify.webhook_url {
+        println!("  webhook_url = {url}");
+    }
+    println!("  focus_hours = {}", notify.focus_hours);
+    println!("  idle_minutes = {}", notify.idle_minutes);
+    println!(
+        "  review_time = {}",
+        notify.review_time.as_deref().unwrap_or("off")
+    );
+    println!("  cooldown_minutes = {}", notify.cooldown_minutes);
+
+    if let Some(url) = &settings.slack_webhook_url {
+        println!("\n[slack]");
+        println!("  webhook_url = {url}");
+    }
+
+    println!("\n[integrations]");
+    println!("  debug_log = {}", settings.integration_debug_log);
+
+    println!("\n[ai]");
+    println!(
+        "  auto_attach_min_confidence = {}",
+        settings.auto_attach_min_confidence
+    );
+    println!(
+        "  backfill_min_confidence = {}",
+        settings.backfill_min_confidence
+    );
+    println!("  embeddings = {}", settings.embeddings_enabled);
+
+    println!("\n[calendar]");
+    println!("  overlap_policy = {}", settings.calendar_overlap_policy);
+
+    println!("\n[timeline]");
+    println!("  focus_min_seconds = {}", settings.focus_min_seconds);
+
+    println!("\n[sync]");
+    println!("  min_entry_seconds = {}", settings.sync_min_entry_seconds);
+
+    println!("\n[next]");
+    println!("  cache_size = {}", settings.suggestion_cache_size);
+    println!(
+        "  cache_ttl_seconds = {}",
+        settings.suggestion_cache_ttl_seconds
+    );
+    println!(
+        "  energy_lookback_weeks = {}",
+        settings.energy_lookback_weeks
+    );
+
+    println!("\n[analyzer]");
+    println!(
+        "  min_block_minutes = {}",
+        settings.analyzer_min_block_minutes
+    );
+    println!(
+        "  merge_gap_minutes = {}",
+        settings.analyzer_merge_gap_minutes
+    );
+
+    list_app_names(&db)?;
+    list_billable_overrides(&db)?;
+    list_work_hours_overrides(&db)
+}
+
+/// List integration configs, with keys masked
+fn list_integrations(db: &Database) {
     // List Plane integration config
     if let Ok(Some(config)) = db.get_integration_config("plane") {
         println!("\n[plane]");
@@ -65,6 +380,12 @@ pub fn handle_config_list() -> Result<()> {
                 &config.api_key.chars().take(8).collect::<String>()
             );
         }
+        if let Some(project) = &config.project_id {
+            println!("  project = {project}");
+        }
+        if let Some(field) = &config.workspace_slug {
+            println!("  estimate_field = {field}");
+        }
     }
 
     // List GitLab integration config
@@ -84,8 +405,43 @@ pub fn handle_config_list() -> Result<()> {
         }
     }
 
-    // List settings
-    let settings = db.get_settings()?;
+    // List Jira integration config
+    if let Ok(Some(config)) = db.get_integration_config("jira") {
+        println!("\n[jira]");
+        println!("  api_url = {}", config.api_url);
+        // email is stored in workspace_slug for Jira
+        if let Some(email) = &config.workspace_slug {
+            println!("  email = {email}");
+        }
+        if !config.api_key.is_empty() {
+            println!(
+                "  api_key = {}***",
+                &config.api_key.chars().take(8).collect::<String>()
+            );
+        }
+    }
+
+    // List Trello integration config
+    if let Ok(Some(config)) = db.get_integration_config("trello") {
+        println!("\n[trello]");
+        if !config.api_key.is_empty() {
+            println!(
+                "  api_key = {}***",
+                &config.api_key.chars().take(8).collect::<String>()
+            );
+        }
+        // token is stored in workspace_slug for Trello
+        if let Some(token) = &config.workspace_slug {
+            println!(
+                "  token = {}***",
+                &token.chars().take(8).collect::<String>()
+            );
+        }
+    }
+}
+
+/// Print the `settings` section
+fn list_settings(settings: &Settings) {
     println!("\n[settings]");
     println!(
         "  idle_threshold_seconds = {}",
@@ -96,6 +452,96 @@ pub fn handle_config_list() -> Result<()> {
         settings.enable_work_item_tracking
     );
     println!("  capture_window_title = {}", settings.capture_window_title);
+    println!("  capture_browser_url = {}", settings.capture_browser_url);
+    println!("  url_whitelist = {}", settings.url_whitelist.join(", "));
+    println!("  span_context_cap = {}", settings.span_context_cap);
+    println!("  name_match_algorithm = {}", settings.name_match_algorithm);
+    println!("  name_match_threshold = {}", settings.name_match_threshold);
+    println!("  max_session_seconds = {}", settings.max_session_seconds);
+    println!(
+        "  billable_categories = {}",
+        settings.billable_categories.join(", ")
+    );
+    println!("  work_hours = {}", settings.work_hours);
+    println!("  min_activity_seconds = {}", settings.min_activity_seconds);
+    println!("  timezone = {}", settings.timezone);
+    println!("  day_boundary_hour = {}", settings.day_boundary_hour);
+    println!(
+        "  require_focused_display = {}",
+        settings.require_focused_display
+    );
+    println!("  self_tracking = {}", settings.self_tracking);
+    println!("  commit_grace_seconds = {}", settings.commit_grace_seconds);
+    println!(
+        "  embedding_refresh_hours = {}",
+        settings.embedding_refresh_hours
+    );
+    println!(
+        "  issue_id_pattern = {}",
+        settings.issue_id_pattern.as_deref().unwrap_or("default")
+    );
+    println!(
+        "  candidate_warmup_threads = {}",
+        settings.candidate_warmup_threads
+    );
+    println!(
+        "  capture_terminal_commands = {}",
+        settings.capture_terminal_commands
+    );
+    println!(
+        "  terminal_history_path = {}",
+        settings.terminal_history_path.as_deref().unwrap_or("auto")
+    );
+}
+
+/// List user-defined app display name overrides
+fn list_app_names(db: &Database) -> Result<()> {
+    let mut app_names: Vec<_> = db.get_app_display_names()?.into_iter().collect();
+    if !app_names.is_empty() {
+        app_names.sort();
+        println!("\n[app_names]");
+        for (bundle_id, name) in app_names {
+            println!("  {bundle_id} = {name}");
+        }
+    }
+
+    Ok(())
+}
+
+/// List per-project billable category overrides
+fn list_billable_overrides(db: &Database) -> Result<()> {
+    let mut overrides = Vec::new();
+    for (project_id, categories) in db.get_project_billable_categories()? {
+        if let Some(project) = db.get_project(project_id)? {
+            overrides.push((project.name, categories.join(", ")));
+        }
+    }
+    if !overrides.is_empty() {
+        overrides.sort();
+        println!("\n[billable]");
+        for (project, categories) in overrides {
+            println!("  {project} = {categories}");
+        }
+    }
+
+    Ok(())
+}
+
+/// List per-project working hours overrides
+fn list_work_hours_overrides(db: &Database) -> Result<()> {
+    let mut overrides = Vec::new();
+    for (project_id, hours) in db.get_project_work_hours()? {
+        if let Some(project) = db.get_project(project_id)? {
+            overrides.push((project.name, hours));
+        }
+    }
+    if !overrides.is_empty() {
+        overrides.sort_by(|a, b| a.0.cmp(&b.0));
+        println!("\n[work_hours]");
+        for (project, hours) in overrides {
+            println!("  {project} = {hours}");
+        }
+    }
 
     Ok(())
 }
@@ -111,15 +557,21 @@ fn get_config_value(db: &Database, key: &str) -> Result<Option<String>> {
     let field = parts[1];
 
     match section {
-        "plane" | "github" | "gitlab" | "jira" | "notion" => {
+        "plane" | "github" | "gitlab" | "jira" | "notion" | "trello" => {
             if let Some(config) = db.get_integration_config(section)? {
                 let value = match field {
+                    // For Trello, the user token is stored in workspace_slug field
+                    "token" if section == "trello" => config.workspace_slug.clone(),
                     "api_url" => Some(config.api_url),
                     "api_key" | "token" => Some(config.api_key),
                     "workspace" | "workspace_slug" => config.workspace_slug.clone(),
                     "project" | "project_id" | "database_id" => config.project_id.clone(),
                     // For Notion, time_property is stored in workspace_slug field
                     "time_property" if section == "notion" => config.workspace_slug.clone(),
+                    // For Jira, the account email is stored in workspace_slug field
+                    "email" if section == "jira" => config.workspace_slug.clone(),
+                    // For GitHub, the estimate field is stored in workspace_slug field
+                    "estimate_field" if section == "github" => config.workspace_slug.clone(),
                     _ => None,
                 };
                 Ok(value)
@@ -127,26 +579,142 @@ fn get_config_value(db: &Database, key: &str) -> Result<Option<String>> {
                 Ok(None)
             }
         }
-        "settings" => {
+        "settings" => Ok(settings_value(db.get_settings()?, field)),
+        "display" => {
             let settings = db.get_settings()?;
             let value = match field {
-                "idle_threshold" | "idle_threshold_seconds" => {
-                    Some(settings.idle_threshold_seconds.to_string())
-                }
-                "work_item_tracking" | "enable_work_item_tracking" => {
-                    Some(settings.enable_work_item_tracking.to_string())
-                }
-                "capture_window_title" => Some(settings.capture_window_title.to_string()),
+                "duration_format" => Some(settings.duration_format.to_string()),
+                _ => None,
+            };
+            Ok(value)
+        }
+        "notify" => {
+            let notify = db.get_settings()?.notifications;
+            let value = match field {
+                "enabled" => Some(notify.enabled.to_string()),
+                "desktop" => Some(notify.desktop.to_string()),
+                "webhook_url" => notify.webhook_url,
+                "focus_hours" => Some(notify.focus_hours.to_string()),
+                "idle_minutes" => Some(notify.idle_minutes.to_string()),
+                "review_time" => notify.review_time,
+                "cooldown_minutes" => Some(notify.cooldown_minutes.to_string()),
+                _ => None,
+            };
+            Ok(value)
+        }
+        "slack" => Ok(match field {
+            "webhook_url" => db.get_settings()?.slack_webhook_url,
+            _ => None,
+        }),
+        "integrations" => {
+            let settings = db.get_settings()?;
+            let value = match field {
+                "debug_log" => Some(settings.integration_debug_log.to_string()),
+                _ => None,
+            };
+            Ok(value)
+        }
+        "calendar" => {
+            let settings = db.get_settings()?;
+            let value = match field {
+                "overlap_policy" => Some(settings.calendar_overlap_policy.to_string()),
+                _ => None,
+            };
+            Ok(value)
+        }
+        "timeline" => {
+            let settings = db.get_settings()?;
+            let value = match field {
+                "focus_min_seconds" => Some(settings.focus_min_seconds.to_string()),
+                _ => None,
+            };
+            Ok(value)
+        }
+        "sync" => {
+            let settings = db.get_settings()?;
+            let value = match field {
+                "min_entry_seconds" => Some(settings.sync_min_entry_seconds.to_string()),
                 _ => None,
             };
             Ok(value)
         }
+        "next" => Ok(next_value(&db.get_settings()?, field)),
+        "analyzer" => {
+            let settings = db.get_settings()?;
+            let value = match field {
+                "min_block_minutes" => Some(settings.analyzer_min_block_minutes.to_string()),
+                "merge_gap_minutes" => Some(settings.analyzer_merge_gap_minutes.to_string()),
+                _ => None,
+            };
+            Ok(value)
+        }
+        "ai" => ai_value(db, field),
         _ => anyhow::bail!(
-            "Unknown section: {section}. Valid sections: plane, github, gitlab, jira, notion, settings"
+            "Unknown section: {section}. Valid sections: plane, github, gitlab, jira, notion, trello, settings, display, notify, slack, integrations, ai, calendar, timeline, sync, next, analyzer, app_names, billable, work_hours"
         ),
     }
 }
 
+/// Value of a field in the `ai` section
+fn ai_value(db: &Database, field: &str) -> Result<Option<String>> {
+    let settings = db.get_settings()?;
+    let ai = db.get_ai_config()?;
+    Ok(match field {
+        "provider" => Some(ai.provider.to_string()),
+        "model" => ai.model,
+        "api_key" => ai.api_key,
+        "base_url" => ai.base_url,
+        "enabled" => Some(ai.enabled.to_string()),
+        "auto_attach_min_confidence" => Some(settings.auto_attach_min_confidence.to_string()),
+        "backfill_min_confidence" => Some(settings.backfill_min_confidence.to_string()),
+        "embeddings" => Some(settings.embeddings_enabled.to_string()),
+        _ => None,
+    })
+}
+
+/// Value of a field in the `next` section
+fn next_value(settings: &Settings, field: &str) -> Option<String> {
+    match field {
+        "cache_size" => Some(settings.suggestion_cache_size.to_string()),
+        "cache_ttl_seconds" => Some(settings.suggestion_cache_ttl_seconds.to_string()),
+        "energy_lookback_weeks" => Some(settings.energy_lookback_weeks.to_string()),
+        _ => None,
+    }
+}
+
+/// Value of a field in the `settings` section
+fn settings_value(settings: Settings, field: &str) -> Option<String> {
+    match field {
+        "idle_threshold" | "idle_threshold_seconds" => {
+            Some(settings.idle_threshold_seconds.to_string())
+        }
+        "work_item_tracking" | "enable_work_item_tracking" => {
+            Some(settings.enable_work_item_tracking.to_string())
+        }
+        "capture_window_title" => Some(settings.capture_window_title.to_string()),
+        "capture_browser_url" => Some(settings.capture_browser_url.to_string()),
+        "url_whitelist" => Some(settings.url_whitelist.join(", ")),
+        "span_context_cap" => Some(settings.span_context_cap.to_string()),
+        "name_match_algorithm" => Some(settings.name_match_algorithm),
+        "name_match_threshold" => Some(settings.name_match_threshold.to_string()),
+        "max_session_seconds" => Some(settings.max_session_seconds.to_string()),
+        "billable_categories" => Some(settings.billable_categories.join(", ")),
+        "work_hours" => Some(settings.work_hours.to_string()),
+        "min_activity_seconds" => Some(settings.min_activity_seconds.to_string()),
+        "timezone" => Some(settings.timezone.to_string()),
+        "day_boundary_hour" => Some(settings.day_boundary_hour.to_string()),
+        "require_focused_display" => Some(settings.require_focused_display.to_string()),
+        "self_tracking" => Some(settings.self_tracking.to_string()),
+        "commit_grace_seconds" => Some(settings.commit_grace_seconds.to_string()),
+        "embedding_refresh_hours" => Some(settings.embedding_refresh_hours.to_string()),
+        "issue_id_pattern" => settings.issue_id_pattern,
+        "candidate_warmup_threads" => Some(settings.candidate_warmup_threads.to_string()),
+        "capture_terminal_commands" => Some(settings.capture_terminal_commands.to_string()),
+        "terminal_history_path" => settings.terminal_history_path,
+        _ => None,
+    }
+}
+
 fn set_config_value(db: &Database, key: &str, value: &str) -> Result<()> {
     let parts: Vec<&str> = key.split('.').collect();
 
@@ -167,7 +735,12 @@ fn set_config_value(db: &Database, key: &str, value: &str) -> Result<()> {
                 "api_url" => config.api_url = value.to_string(),
                 "api_key" | "token" => config.api_key = value.to_string(),
                 "workspace" | "workspace_slug" => config.workspace_slug = Some(value.to_string()),
+                // Jira authenticates with the account email; it is stored in workspace_slug
+                "email" if section == "jira" => config.workspace_slug = Some(value.to_string()),
                 "project" | "project_id" => config.project_id = Some(value.to_string()),
+                _ if section == "jira" => anyhow::bail!(
+                    "Unknown field: {field}. Valid fields: api_url, api_key, email, project"
+                ),
                 _ => anyhow::bail!(
                     "Unknown field: {field}. Valid fields: api_url, api_key, workspace, project"
                 ),
@@ -183,8 +756,11 @@ fn set_config_value(db: &Database, key: &str, value: &str) -> Result<()> {
 
             match field {
                 "token" | "api_key" => config.api_key = value.to_string(),
+                // Projects (v2) node id and the single-select field estimates go to
+                "project" | "project_id" => config.project_id = Some(value.to_string()),
+                "estimate_field" => config.workspace_slug = Some(value.to_string()),
                 _ => anyhow::bail!(
-                    "Unknown field: {field}. Valid fields: token"
+                    "Unknown field: {field}. Valid fields: token, project, estimate_field"
                 ),
             }
 
@@ -226,40 +802,523 @@ fn set_config_value(db: &Database, key: &str, value: &str) -> Result<()> {
             config.updated_at = chrono::Utc::now();
             db.upsert_integration_config(&config)?;
         }
-        "settings" => {
+        "trello" => set_trello_setting(db, field, value)?,
+        "settings" => set_setting(db, field, value)?,
+        "display" => {
             let mut settings = db.get_settings()?;
-
             match field {
-                "idle_threshold" | "idle_threshold_seconds" => {
-                    settings.idle_threshold_seconds = value
-                        .parse()
-                        .map_err(|_| anyhow::anyhow!("Invalid number"))?;
-                }
-                "work_item_tracking" | "enable_work_item_tracking" => {
-                    settings.enable_work_item_tracking = value == "true" || value == "1";
-                }
-                "capture_window_title" => {
-                    settings.capture_window_title = value == "true" || value == "1";
+                "duration_format" => {
+                    settings.duration_format = value.parse().map_err(anyhow::Error::msg)?;
                 }
-                _ => anyhow::bail!("Unknown field: {field}"),
+                _ => anyhow::bail!("Unknown field: {field}. Valid fields: duration_format"),
             }
-
             db.update_settings(&settings)?;
         }
-        "ai" => {
-            match field {
-                "provider" => db.update_ai_config_field("provider", Some(value))?,
-                "model" => db.update_ai_config_field("model", Some(value))?,
-                "api_key" => db.update_ai_config_field("api_key", Some(value))?,
-                "base_url" => db.update_ai_config_field("base_url", Some(value))?,
-                "enabled" => db.update_ai_config_field("enabled", Some(value))?,
-                _ => anyhow::bail!("Unknown field: {field}. Valid fields: provider, model, api_key, base_url, enabled"),
+        "notify" => set_notify_setting(db, field, value)?,
+        "slack" => set_slack_setting(db, field, value)?,
+        "integrations" => set_integrations_setting(db, field, value)?,
+        "ai" => set_ai_setting(db, field, value)?,
+        "calendar" => set_calendar_setting(db, field, value)?,
+        "timeline" => set_timeline_setting(db, field, value)?,
+        "sync" => set_sync_setting(db, field, value)?,
+        "next" => set_next_setting(db, field, value)?,
+        "analyzer" => set_analyzer_setting(db, field, value)?,
+        _ => anyhow::bail!(
+            "Unknown section: {section}. Valid sections: plane, github, gitlab, jira, notion, trello, settings, display, notify, slack, integrations, ai, calendar, timeline, sync, next, analyzer, app_names, billable, work_hours"
+        ),
+    }
+
+    Ok(())
+}
+
+/// Set a field in the `trello` section
+///
+/// Trello needs an API key and a user token; the token is stored in `workspace_slug`.
+fn set_trello_setting(db: &Database, field: &str, value: &str) -> Result<()> {
+    let mut config = db.get_integration_config("trello")?.unwrap_or_else(|| {
+        IntegrationConfig::new("trello".to_string(), String::new(), String::new())
+    });
+
+    match field {
+        "api_key" => config.api_key = value.to_string(),
+        "token" => config.workspace_slug = Some(value.to_string()),
+        _ => anyhow::bail!("Unknown field: {field}. Valid fields: api_key, token"),
+    }
+
+    config.updated_at = chrono::Utc::now();
+    db.upsert_integration_config(&config)
+}
+
+/// Set a field in the `notify` section
+///
+/// Numeric thresholds accept 0 to turn that event off; `review_time` accepts "off".
+fn set_notify_setting(db: &Database, field: &str, value: &str) -> Result<()> {
+    let mut settings = db.get_settings()?;
+    let notify = &mut settings.notifications;
+    let parse_number = |value: &str| -> Result<u32> {
+        value.parse().map_err(|_| anyhow::anyhow!("Invalid number"))
+    };
+
+    match field {
+        "enabled" => notify.enabled = value == "true" || value == "1",
+        "desktop" => notify.desktop = value == "true" || value == "1",
+        "webhook_url" => notify.webhook_url = parse_webhook_url(value)?,
+        "focus_hours" => notify.focus_hours = parse_number(value)?,
+        "idle_minutes" => notify.idle_minutes = parse_number(value)?,
+        "cooldown_minutes" => notify.cooldown_minutes = parse_number(value)?,
+        "review_time" => {
+            notify.review_time = if value == "off" {
+                None
+            } else {
+                chrono::NaiveTime::parse_from_str(value, "%H:%M")
+                    .map_err(|_| anyhow::anyhow!("Invalid time. Use HH:MM (e.g., 17:30) or 'off'"))?;
+                Some(value.to_string())
+            };
+        }
+        _ => anyhow::bail!(
+            "Unknown field: {field}. Valid fields: enabled, desktop, webhook_url, focus_hours, idle_minutes, review_time, cooldown_minutes"
+        ),
+    }
+
+    db.update_settings(&settings)?;
+    Ok(())
+}
+
+/// Set a field in the `slack` section
+///
+/// `webhook_url` is the incoming webhook `toki standup --post` sends to.
+fn set_slack_setting(db: &Database, field: &str, value: &str) -> Result<()> {
+    let mut settings = db.get_settings()?;
+    match field {
+        "webhook_url" => settings.slack_webhook_url = parse_webhook_url(value)?,
+        _ => anyhow::bail!("Unknown field: {field}. Valid fields: webhook_url"),
+    }
+    db.update_settings(&settings)?;
+    Ok(())
+}
+
+/// Parse a webhook URL setting, where "" or "off" clears it
+fn parse_webhook_url(value: &str) -> Result<Option<String>> {
+    match value {
+        "" | "off" => Ok(None),
+        url if url.starts_with("http://") || url.starts_with("https://") => {
+            Ok(Some(url.to_string()))
+        }
+        _ => anyhow::bail!("Webhook URL must start with http:// or https://"),
+    }
+}
+
+/// Set a field in the `integrations` section
+///
+/// `debug_log` (off, requests, bodies) writes API requests to `<data dir>/integrations.log`.
+fn set_integrations_setting(db: &Database, field: &str, value: &str) -> Result<()> {
+    let mut settings = db.get_settings()?;
+    match field {
+        "debug_log" => {
+            settings.integration_debug_log = value.parse().map_err(anyhow::Error::msg)?;
+        }
+        _ => anyhow::bail!("Unknown field: {field}. Valid fields: debug_log"),
+    }
+    db.update_settings(&settings)?;
+    Ok(())
+}
+
+/// Set a field in the `calendar` section
+fn set_calendar_setting(db: &Database, field: &str, value: &str) -> Result<()> {
+    let mut settings = db.get_settings()?;
+    match field {
+        "overlap_policy" => {
+            settings.calendar_overlap_policy = value.parse().map_err(anyhow::Error::msg)?;
+        }
+        _ => anyhow::bail!("Unknown field: {field}. Valid fields: overlap_policy"),
+    }
+    db.update_settings(&settings)?;
+    Ok(())
+}
+
+/// Set a field in the `timeline` section
+///
+/// `focus_min_seconds` is the shortest block labelled Focus; shorter ones are Fragmented.
+fn set_timeline_setting(db: &Database, field: &str, value: &str) -> Result<()> {
+    let mut settings = db.get_settings()?;
+    match field {
+        "focus_min_seconds" => {
+            settings.focus_min_seconds = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid number"))?;
+        }
+        _ => anyhow::bail!("Unknown field: {field}. Valid fields: focus_min_seconds"),
+    }
+    db.update_settings(&settings)?;
+    Ok(())
+}
+
+/// Set a field in the `sync` section
+///
+/// `min_entry_seconds` skips issues whose unsynced time stays below it (0 turns it off).
+fn set_sync_setting(db: &Database, field: &str, value: &str) -> Result<()> {
+    let mut settings = db.get_settings()?;
+    match field {
+        "min_entry_seconds" => {
+            settings.sync_min_entry_seconds = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid number"))?;
+        }
+        _ => anyhow::bail!("Unknown field: {field}. Valid fields: min_entry_seconds"),
+    }
+    db.update_settings(&settings)?;
+    Ok(())
+}
+
+/// Set a field in the `next` section
+///
+/// `cache_size` is how many ranked issues are kept per project; `cache_ttl_seconds`
+/// is how long a ranking is reused (0 turns the cache off). `energy_lookback_weeks`
+/// is how much past activity tells high- from low-energy hours (0 turns it off).
+fn set_next_setting(db: &Database, field: &str, value: &str) -> Result<()> {
+    let mut settings = db.get_settings()?;
+    let number = value
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid number"))?;
+    match field {
+        "cache_size" => settings.suggestion_cache_size = number,
+        "cache_ttl_seconds" => settings.suggestion_cache_ttl_seconds = number,
+        "energy_lookback_weeks" => settings.energy_lookback_weeks = number,
+        _ => anyhow::bail!(
+            "Unknown field: {field}. Valid fields: cache_size, cache_ttl_seconds, \
+             energy_lookback_weeks"
+        ),
+    }
+    db.update_settings(&settings)?;
+    Ok(())
+}
+
+/// Set a field in the `analyzer` section
+///
+/// `toki review` drops suggested blocks shorter than `min_block_minutes` and joins
+/// similar work less than `merge_gap_minutes` apart.
+fn set_analyzer_setting(db: &Database, field: &str, value: &str) -> Result<()> {
+    let mut settings = db.get_settings()?;
+    let number = value
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid number"))?;
+    match field {
+        "min_block_minutes" => settings.analyzer_min_block_minutes = number,
+        "merge_gap_minutes" => settings.analyzer_merge_gap_minutes = number,
+        _ => anyhow::bail!(
+            "Unknown field: {field}. Valid fields: min_block_minutes, merge_gap_minutes"
+        ),
+    }
+    db.update_settings(&settings)?;
+    Ok(())
+}
+
+/// Set a field in the `ai` section
+///
+/// The confidence floors and `embeddings` live in settings; the rest is the AI provider config.
+fn set_ai_setting(db: &Database, field: &str, value: &str) -> Result<()> {
+    match field {
+        "provider" | "model" | "api_key" | "base_url" | "enabled" => {
+            db.update_ai_config_field(field, Some(value))?;
+        }
+        "auto_attach_min_confidence" | "backfill_min_confidence" => {
+            let threshold = value
+                .parse()
+                .ok()
+                .filter(|t| (0.0..=1.0).contains(t))
+                .ok_or_else(|| anyhow::anyhow!("Invalid threshold (must be 0.0-1.0)"))?;
+            let mut settings = db.get_settings()?;
+            if field == "backfill_min_confidence" {
+                settings.backfill_min_confidence = threshold;
+            } else {
+                settings.auto_attach_min_confidence = threshold;
             }
+            db.update_settings(&settings)?;
+        }
+        "embeddings" => {
+            let mut settings = db.get_settings()?;
+            settings.embeddings_enabled = value == "true" || value == "1";
+            db.update_settings(&settings)?;
         }
         _ => anyhow::bail!(
-            "Unknown section: {section}. Valid sections: plane, github, gitlab, jira, notion, settings, ai"
+            "Unknown field: {field}. Valid fields: provider, model, api_key, base_url, enabled, auto_attach_min_confidence, backfill_min_confidence, embeddings"
         ),
     }
+    Ok(())
+}
+
+/// Set a field in the `settings` section
+fn set_setting(db: &Database, field: &str, value: &str) -> Result<()> {
+    let mut settings = db.get_settings()?;
+
+    match field {
+        "idle_threshold" | "idle_threshold_seconds" => {
+            settings.idle_threshold_seconds = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid number"))?;
+        }
+        "work_item_tracking" | "enable_work_item_tracking" => {
+            settings.enable_work_item_tracking = value == "true" || value == "1";
+        }
+        "capture_window_title" => {
+            settings.capture_window_title = value == "true" || value == "1";
+        }
+        "capture_browser_url" => {
+            settings.capture_browser_url = value == "true" || value == "1";
+        }
+        "url_whitelist" => settings.url_whitelist = parse_category_list(value),
+        "require_focused_display" => {
+            settings.require_focused_display = value == "true" || value == "1";
+        }
+        "span_context_cap" => {
+            settings.span_context_cap = value
+                .parse()
+                .ok()
+                .filter(|cap| *cap > 0)
+                .ok_or_else(|| anyhow::anyhow!("Invalid number (must be >= 1)"))?;
+        }
+        "name_match_algorithm" => {
+            let algorithm: NameMatchAlgorithm = value.parse()?;
+            settings.name_match_algorithm = algorithm.to_string();
+        }
+        "name_match_threshold" => {
+            settings.name_match_threshold = value
+                .parse()
+                .ok()
+                .filter(|t| (0.0..=1.0).contains(t))
+                .ok_or_else(|| anyhow::anyhow!("Invalid threshold (must be 0.0-1.0)"))?;
+        }
+        "max_session_seconds" => {
+            // 0 disables the limit
+            settings.max_session_seconds = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid number"))?;
+        }
+        "billable_categories" => settings.billable_categories = parse_category_list(value),
+        "work_hours" => settings.work_hours = value.parse().map_err(anyhow::Error::msg)?,
+        "timezone" => settings.timezone = value.parse().map_err(anyhow::Error::msg)?,
+        "day_boundary_hour" => {
+            settings.day_boundary_hour = value
+                .parse()
+                .ok()
+                .filter(|hour| *hour < 24)
+                .ok_or_else(|| anyhow::anyhow!("Invalid hour (must be 0-23)"))?;
+        }
+        "self_tracking" => settings.self_tracking = value.parse().map_err(anyhow::Error::msg)?,
+        "commit_grace_seconds" => {
+            // 0 stops carrying a commit's issue back to earlier spans
+            settings.commit_grace_seconds = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid number"))?;
+        }
+        "candidate_warmup_threads" => {
+            // 0 turns the MCP server's candidate cache off
+            settings.candidate_warmup_threads = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid number"))?;
+        }
+        "issue_id_pattern" => {
+            settings.issue_id_pattern = match value {
+                "" | "default" => None,
+                pattern => {
+                    toki_ai::compile_issue_id_pattern(Some(pattern))?;
+                    Some(pattern.to_string())
+                }
+            };
+        }
+        "capture_terminal_commands" => {
+            settings.capture_terminal_commands = value == "true" || value == "1";
+        }
+        "terminal_history_path" => {
+            settings.terminal_history_path = match value {
+                "" | "auto" => None,
+                path => Some(path.to_string()),
+            };
+        }
+        "embedding_refresh_hours" => {
+            // 0 rebuilds on every sync
+            settings.embedding_refresh_hours = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid number"))?;
+        }
+        "min_activity_seconds" => {
+            // 0 keeps every activity
+            settings.min_activity_seconds = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid number"))?;
+        }
+        _ => anyhow::bail!("Unknown field: {field}"),
+    }
+
+    db.update_settings(&settings)?;
+    Ok(())
+}
+
+/// Parse a comma-separated category list, dropping empty entries
+fn parse_category_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Resolve the project named in a per-project key such as `billable.<project>`
+fn config_project_id(db: &Database, section: &str, project: &str) -> Result<uuid::Uuid> {
+    if project.is_empty() {
+        anyhow::bail!("Missing project name. Use: {section}.<project> (e.g., {section}.toki)");
+    }
+    db.get_project_by_name(project)?
+        .map(|p| p.id)
+        .ok_or_else(|| anyhow::anyhow!("Project not found: {project}"))
+}
+
+/// Set (or, with an empty value, remove) a project's billable categories
+fn set_project_billable(db: &Database, project: &str, value: &str) -> Result<()> {
+    let project_id = config_project_id(db, "billable", project)?;
+    let categories = parse_category_list(value);
+    if categories.is_empty() {
+        db.remove_project_billable_categories(project_id)?;
+    } else {
+        db.set_project_billable_categories(project_id, &categories)?;
+    }
+
+    Ok(())
+}
+
+/// Set (or, with an empty value, remove) a project's working hours
+fn set_project_work_hours(db: &Database, project: &str, value: &str) -> Result<()> {
+    let project_id = config_project_id(db, "work_hours", project)?;
+    if value.trim().is_empty() {
+        db.remove_project_work_hours(project_id)?;
+    } else {
+        db.set_project_work_hours(project_id, value.parse().map_err(anyhow::Error::msg)?)?;
+    }
 
     Ok(())
 }
+
+/// Set (or, with an empty value, remove) an app display name override
+fn set_app_name(db: &Database, bundle_id: &str, name: &str) -> Result<()> {
+    if bundle_id.is_empty() {
+        anyhow::bail!(
+            "Missing bundle id. Use: app_names.<bundle_id> (e.g., app_names.com.microsoft.VSCode)"
+        );
+    }
+
+    if name.is_empty() {
+        db.remove_app_display_name(bundle_id)?;
+    } else {
+        db.set_app_display_name(bundle_id, name)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn configured_db() -> Database {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        set_config_value(&db, "plane.api_url", "https://plane.example.com").unwrap();
+        set_config_value(&db, "plane.api_key", "plane-secret").unwrap();
+        set_config_value(&db, "github.token", "ghp_secret").unwrap();
+        set_config_value(&db, "trello.api_key", "trello-key-secret").unwrap();
+        set_config_value(&db, "trello.token", "trello-token-secret").unwrap();
+        set_config_value(&db, "notify.webhook_url", "https://hooks.example.com/x").unwrap();
+        set_config_value(
+            &db,
+            "slack.webhook_url",
+            "https://hooks.slack.com/services/T/B/x",
+        )
+        .unwrap();
+        set_config_value(&db, "settings.commit_grace_seconds", "120").unwrap();
+        set_app_name(&db, "com.microsoft.VSCode", "Code").unwrap();
+        db
+    }
+
+    #[test]
+    fn test_export_omits_secrets_by_default() {
+        let db = configured_db();
+
+        let exported = export_config(&db, false).unwrap();
+        assert_eq!(
+            exported["plane"]["api_url"].as_str(),
+            Some("https://plane.example.com")
+        );
+        assert!(exported["plane"].get("api_key").is_none());
+        assert!(exported.get("github").is_none());
+        assert!(exported.get("trello").is_none());
+        assert!(exported["notify"].get("webhook_url").is_none());
+        assert!(exported.get("slack").is_none());
+        assert_eq!(
+            exported["settings"]["commit_grace_seconds"].as_str(),
+            Some("120")
+        );
+        assert_eq!(
+            exported["app_names"]["com.microsoft.VSCode"].as_str(),
+            Some("Code")
+        );
+        assert!(!toml::to_string(&exported).unwrap().contains("secret"));
+
+        let with_secrets = export_config(&db, true).unwrap();
+        assert_eq!(
+            with_secrets["plane"]["api_key"].as_str(),
+            Some("plane-secret")
+        );
+        assert_eq!(with_secrets["github"]["token"].as_str(), Some("ghp_secret"));
+        assert_eq!(
+            with_secrets["trello"]["token"].as_str(),
+            Some("trello-token-secret")
+        );
+        assert_eq!(
+            with_secrets["slack"]["webhook_url"].as_str(),
+            Some("https://hooks.slack.com/services/T/B/x")
+        );
+    }
+
+    #[test]
+    fn test_import_applies_exported_values() {
+        let exported = export_config(&configured_db(), true).unwrap();
+        let contents = toml::to_string(&exported).unwrap();
+
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let table: toml::Table = toml::from_str(&contents).unwrap();
+        assert!(import_config(&db, &table).unwrap() > 0);
+        assert_eq!(
+            get_config_value(&db, "plane.api_key").unwrap().as_deref(),
+            Some("plane-secret")
+        );
+        assert_eq!(db.get_settings().unwrap().commit_grace_seconds, 120);
+        assert_eq!(
+            db.get_app_display_names().unwrap()["com.microsoft.VSCode"],
+            "Code"
+        );
+
+        // Plain TOML numbers and booleans are accepted too
+        let table: toml::Table =
+            toml::from_str("[settings]\nidle_threshold_seconds = 90\ncapture_window_title = false")
+                .unwrap();
+        assert_eq!(import_config(&db, &table).unwrap(), 2);
+        let settings = db.get_settings().unwrap();
+        assert_eq!(settings.idle_threshold_seconds, 90);
+        assert!(!settings.capture_window_title);
+    }
+
+    #[test]
+    fn test_import_rejects_unknown_keys_before_applying() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let table: toml::Table = toml::from_str(
+            "[settings]\ncommit_grace_seconds = 60\nidle_treshold = 5\n\n[wiki]\nurl = \"x\"",
+        )
+        .unwrap();
+
+        let error = import_config(&db, &table).unwrap_err().to_string();
+        assert!(error.contains("settings.idle_treshold"), "{error}");
+        assert!(error.contains("wiki"), "{error}");
+        assert_ne!(db.get_settings().unwrap().commit_grace_seconds, 60);
+    }
+}
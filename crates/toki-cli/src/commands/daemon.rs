@@ -5,29 +5,18 @@ use sysinfo::{Pid, System};
 use toki_core::{
     config::get_data_dir,
     ipc::{IpcClient, IpcRequest, IpcResponse},
-    Daemon,
+    AppDisplayNames, Daemon,
 };
 use toki_storage::Database;
 
-pub fn start_daemon(data_dir: &Path) -> Result<()> {
+pub fn start_daemon(data_dir: &Path, force: bool) -> Result<()> {
     let pid_file_path = data_dir.join("toki.pid");
     let sock_path = data_dir.join("toki.sock");
 
-    // 1. Check if daemon is already running
-    if pid_file_path.exists() {
-        if let Ok(pid_str) = fs::read_to_string(&pid_file_path) {
-            if let Ok(pid) = pid_str.trim().parse::<usize>() {
-                let mut sys = System::new();
-                if sys.refresh_process(Pid::from(pid)) {
-                    log::info!("Daemon is already running (PID: {pid}).");
-                    return Ok(());
-                }
-            }
-        }
-        // If pid file is stale, remove it
-        log::warn!("Removing stale PID file.");
-        let _ = fs::remove_file(&pid_file_path);
-    }
+    // 1. Refuse to start next to a running daemon, which would double-count time
+    acquire_pid_lock(&pid_file_path, force, |pid| {
+        System::new().refresh_process(Pid::from(pid))
+    })?;
 
     // 2. Clean up old socket if it exists
     if sock_path.exists() {
@@ -52,6 +41,38 @@ pub fn start_daemon(data_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Clear the way for a new daemon, given the PID file a previous one left behind
+///
+/// A PID file whose process is gone is a stale lock and is removed. If the process is
+/// still alive, starting is refused unless `force` is set, in which case the lock is
+/// taken over (e.g. when the PID has been reused by an unrelated process).
+fn acquire_pid_lock(
+    pid_file_path: &Path,
+    force: bool,
+    is_alive: impl Fn(usize) -> bool,
+) -> Result<()> {
+    if !pid_file_path.exists() {
+        return Ok(());
+    }
+
+    let pid = fs::read_to_string(pid_file_path)
+        .ok()
+        .and_then(|pid_str| pid_str.trim().parse::<usize>().ok());
+
+    match pid {
+        Some(pid) if is_alive(pid) && !force => anyhow::bail!(
+            "Toki daemon is already running (PID: {pid}). \
+             Run `toki stop` first, or `toki start --force` if that process is not toki."
+        ),
+        Some(pid) if is_alive(pid) => {
+            log::warn!("Taking over PID file held by running process {pid} (--force).");
+        }
+        _ => log::warn!("Removing stale PID file."),
+    }
+    fs::remove_file(pid_file_path)?;
+    Ok(())
+}
+
 pub async fn run_daemon_process() -> Result<()> {
     // This is the detached daemon process
     // We must set up logging here, as this is a new process.
@@ -154,10 +175,15 @@ pub async fn show_status(data_dir: &Path) -> Result<()> {
                 "Daemon Status: {}",
                 if running { "Running" } else { "Stopped" }
             );
+            let app_names = Database::new(None)
+                .and_then(|db| AppDisplayNames::load(&db))
+                .unwrap_or_default();
             println!("\nCurrent Activity:");
             println!(
                 "  Window: {}",
-                current_window.unwrap_or_else(|| "None".to_string())
+                current_window
+                    .as_deref()
+                    .map_or("None", |app| app_names.display_name(app))
             );
             println!(
                 "  Issue: {}",
@@ -178,6 +204,25 @@ pub async fn show_status(data_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+pub async fn reload_daemon(data_dir: &Path) -> Result<()> {
+    let sock_path = data_dir.join("toki.sock");
+
+    if !sock_path.exists() {
+        println!("Daemon is not running. Settings will apply on the next `toki start`.");
+        return Ok(());
+    }
+
+    let client = IpcClient::new(&sock_path);
+    match client.send_command(IpcRequest::Reload).await? {
+        IpcResponse::Reloaded => {
+            println!("Daemon reloaded settings. The current session continues.");
+            Ok(())
+        }
+        IpcResponse::ReloadFailed { error } => anyhow::bail!("Daemon reload failed: {error}"),
+        _ => anyhow::bail!("Unexpected response from daemon"),
+    }
+}
+
 fn setup_daemon_logging() -> Result<()> {
     use std::fs::{create_dir_all, OpenOptions};
 
@@ -199,3 +244,38 @@ fn setup_daemon_logging() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_running_daemon_blocks_second_start() {
+        let dir = tempfile::tempdir().unwrap();
+        let pid_file = dir.path().join("toki.pid");
+        fs::write(&pid_file, "4242").unwrap();
+
+        let err = acquire_pid_lock(&pid_file, false, |pid| pid == 4242).unwrap_err();
+        assert!(err.to_string().contains("already running (PID: 4242)"));
+        assert!(pid_file.exists());
+
+        acquire_pid_lock(&pid_file, true, |pid| pid == 4242).unwrap();
+        assert!(!pid_file.exists());
+    }
+
+    #[test]
+    fn test_stale_pid_file_taken_over() {
+        let dir = tempfile::tempdir().unwrap();
+        let pid_file = dir.path().join("toki.pid");
+
+        fs::write(&pid_file, "4242\n").unwrap();
+        acquire_pid_lock(&pid_file, false, |_| false).unwrap();
+        assert!(!pid_file.exists());
+
+        fs::write(&pid_file, "not a pid").unwrap();
+        acquire_pid_lock(&pid_file, false, |_| true).unwrap();
+        assert!(!pid_file.exists());
+
+        acquire_pid_lock(&pid_file, false, |_| true).unwrap();
+    }
+}
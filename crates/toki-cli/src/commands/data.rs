@@ -1,24 +1,234 @@
-/// Data management command handlers (export, delete)
-use anyhow::Result;
-use chrono::{Duration, Utc};
+/// Data management command handlers (export, import, stats, delete, optimize)
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
 use std::fmt::Write;
-use toki_storage::Database;
+use std::io::{BufRead, BufReader, BufWriter};
+use toki_storage::{Activity, ActivitySpan, Database, Project, Session, TimeBlock};
 
-use super::helpers::escape_csv;
+use super::helpers::{escape_csv, parse_csv, parse_date_range};
+use super::ical::{block_uid, write_calendar, CalendarEvent};
 
-pub fn handle_data_export(format: &str, output: Option<String>) -> Result<()> {
+/// One NDJSON line, tagged with the kind of row it holds
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "entity", rename_all = "snake_case")]
+enum NdjsonRecord {
+    Activity(Activity),
+    Span(ActivitySpan),
+    Session(Session),
+    Block(TimeBlock),
+}
+
+/// Rows written or imported, per entity
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NdjsonCounts {
+    pub activities: usize,
+    pub spans: usize,
+    pub sessions: usize,
+    pub blocks: usize,
+}
+
+impl std::fmt::Display for NdjsonCounts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} activities, {} spans, {} sessions, {} blocks",
+            self.activities, self.spans, self.sessions, self.blocks
+        )
+    }
+}
+
+/// Layout version of `toki data export json`, checked on import
+const JSON_EXPORT_VERSION: u64 = 1;
+
+/// Document written by `toki data export json`
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonExport {
+    version: u64,
+    exported_at: DateTime<Utc>,
+    projects: Vec<Project>,
+    sessions: Vec<Session>,
+    activities: Vec<Activity>,
+    spans: Vec<ActivitySpan>,
+    blocks: Vec<TimeBlock>,
+}
+
+impl JsonExport {
+    fn counts(&self) -> NdjsonCounts {
+        NdjsonCounts {
+            activities: self.activities.len(),
+            spans: self.spans.len(),
+            sessions: self.sessions.len(),
+            blocks: self.blocks.len(),
+        }
+    }
+}
+
+fn write_record(out: &mut impl std::io::Write, record: &NdjsonRecord) -> Result<()> {
+    serde_json::to_writer(&mut *out, record)?;
+    out.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Write every row in the range as NDJSON, one row at a time
+pub fn write_ndjson(
+    db: &Database,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    out: &mut impl std::io::Write,
+) -> Result<NdjsonCounts> {
+    let counts = NdjsonCounts {
+        // Sessions first, so spans can link to them on import
+        sessions: db.for_each_session(start, end, |session| {
+            write_record(out, &NdjsonRecord::Session(session))
+        })?,
+        activities: db.for_each_activity(start, end, |activity| {
+            write_record(out, &NdjsonRecord::Activity(activity))
+        })?,
+        spans: db.for_each_activity_span(start, end, |span| {
+            write_record(out, &NdjsonRecord::Span(span))
+        })?,
+        blocks: db.for_each_time_block(start, end, |block| {
+            write_record(out, &NdjsonRecord::Block(block))
+        })?,
+    };
+    out.flush()?;
+    Ok(counts)
+}
+
+/// Import NDJSON produced by [`write_ndjson`]
+///
+/// Rows whose id already exists are skipped, so only newly inserted rows are counted.
+pub fn import_ndjson(db: &Database, reader: impl BufRead) -> Result<NdjsonCounts> {
+    let mut counts = NdjsonCounts::default();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: NdjsonRecord = serde_json::from_str(&line)
+            .with_context(|| format!("Invalid record on line {}", index + 1))?;
+        match record {
+            NdjsonRecord::Activity(activity) => {
+                counts.activities += usize::from(db.import_activity(&activity)?);
+            }
+            NdjsonRecord::Span(span) => {
+                counts.spans += usize::from(db.import_activity_span(&span)?);
+            }
+            NdjsonRecord::Session(session) => {
+                counts.sessions += usize::from(db.import_session(&session)?);
+            }
+            NdjsonRecord::Block(block) => {
+                counts.blocks += usize::from(db.import_time_block(&block)?);
+            }
+        }
+    }
+    Ok(counts)
+}
+
+/// Collect every row in the range, and all projects, for a JSON export
+fn json_export(db: &Database, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<JsonExport> {
+    let mut export = JsonExport {
+        version: JSON_EXPORT_VERSION,
+        exported_at: Utc::now(),
+        projects: db.get_all_projects()?,
+        sessions: Vec::new(),
+        activities: Vec::new(),
+        spans: Vec::new(),
+        blocks: Vec::new(),
+    };
+    db.for_each_session(start, end, |session| {
+        export.sessions.push(session);
+        Ok(())
+    })?;
+    db.for_each_activity(start, end, |activity| {
+        export.activities.push(activity);
+        Ok(())
+    })?;
+    db.for_each_activity_span(start, end, |span| {
+        export.spans.push(span);
+        Ok(())
+    })?;
+    db.for_each_time_block(start, end, |block| {
+        export.blocks.push(block);
+        Ok(())
+    })?;
+    Ok(export)
+}
+
+/// Import a JSON export, all or nothing
+///
+/// The export is parsed in full before anything is written, and every row is
+/// inserted in one transaction. Rows whose id already exists are skipped; the
+/// number of new projects is returned next to the other counts.
+pub fn import_json(db: &Database, text: &str) -> Result<(usize, NdjsonCounts)> {
+    let value: serde_json::Value = serde_json::from_str(text).context("Invalid JSON")?;
+    match value.get("version").and_then(serde_json::Value::as_u64) {
+        Some(JSON_EXPORT_VERSION) => {}
+        Some(version) => anyhow::bail!(
+            "Unsupported export version {version}; this toki reads version {JSON_EXPORT_VERSION}"
+        ),
+        None => {
+            anyhow::bail!("Not a versioned toki export; re-export it with `toki data export json`")
+        }
+    }
+    let export: JsonExport = serde_json::from_value(value).context("Invalid export record")?;
+
+    db.import_in_transaction(|db| {
+        let mut projects = 0;
+        for project in &export.projects {
+            projects += usize::from(db.import_project(project)?);
+        }
+        let mut counts = NdjsonCounts::default();
+        // Sessions before spans, so spans keep their session link
+        for session in &export.sessions {
+            counts.sessions += usize::from(db.import_session(session)?);
+        }
+        for activity in &export.activities {
+            counts.activities += usize::from(db.import_activity(activity)?);
+        }
+        for span in &export.spans {
+            counts.spans += usize::from(db.import_activity_span(span)?);
+        }
+        for block in &export.blocks {
+            counts.blocks += usize::from(db.import_time_block(block)?);
+        }
+        Ok((projects, counts))
+    })
+}
+
+pub fn handle_data_export(format: &str, output: Option<String>, range: Option<&str>) -> Result<()> {
     let db = Database::new(None)?;
-    let end = Utc::now();
-    let start = end - Duration::days(365);
+    let (start, end) = if let Some(range) = range {
+        parse_date_range(range)?
+    } else {
+        let end = Utc::now();
+        (end - Duration::days(365), end)
+    };
 
-    let output_path = output.unwrap_or_else(|| format!("toki_export.{format}"));
+    let extension = if format == "ical" { "ics" } else { format };
+    let output_path = output.unwrap_or_else(|| format!("toki_export.{extension}"));
 
     match format {
+        "ndjson" => {
+            // Progress goes to stderr so `-o -` can be piped
+            let counts = if output_path == "-" {
+                write_ndjson(&db, start, end, &mut std::io::stdout().lock())?
+            } else {
+                let file = std::fs::File::create(&output_path)
+                    .with_context(|| format!("Failed to create {output_path}"))?;
+                write_ndjson(&db, start, end, &mut BufWriter::new(file))?
+            };
+            eprintln!("Exported {counts} to {output_path}");
+        }
         "json" => {
-            let activities = db.get_activities(start, end)?;
-            let json = serde_json::to_string_pretty(&activities)?;
-            std::fs::write(&output_path, json)?;
-            println!("Exported {} activities to {output_path}", activities.len());
+            let export = json_export(&db, start, end)?;
+            std::fs::write(&output_path, serde_json::to_string_pretty(&export)?)?;
+            println!(
+                "Exported {} projects, {} to {output_path}",
+                export.projects.len(),
+                export.counts()
+            );
         }
         "csv" => {
             // Export activity spans (more detailed data)
@@ -47,14 +257,168 @@ pub fn handle_data_export(format: &str, output: Option<String>) -> Result<()> {
             std::fs::write(&output_path, csv_content)?;
             println!("Exported {} activity spans to {output_path}", spans.len());
         }
+        "ical" => {
+            let mut blocks = Vec::new();
+            db.for_each_time_block(start, end, |block| {
+                if block.confirmed {
+                    blocks.push(block);
+                }
+                Ok(())
+            })?;
+            std::fs::write(
+                &output_path,
+                write_calendar(&time_block_events(&blocks), Utc::now()),
+            )?;
+            println!(
+                "Exported {} confirmed time blocks to {output_path}",
+                blocks.len()
+            );
+        }
         _ => {
-            println!("Unknown format: {format}. Use 'json' or 'csv'");
+            println!("Unknown format: {format}. Use 'json', 'csv', 'ndjson' or 'ical'");
         }
     }
 
     Ok(())
 }
 
+/// Time blocks as calendar events, with tags as categories
+fn time_block_events(blocks: &[TimeBlock]) -> Vec<CalendarEvent> {
+    blocks
+        .iter()
+        .map(|block| {
+            let description = block.description.trim();
+            CalendarEvent {
+                uid: block_uid(block.id),
+                start: block.start_time,
+                end: block.end_time,
+                summary: if description.is_empty() {
+                    "Work".to_string()
+                } else {
+                    description.to_string()
+                },
+                description: String::new(),
+                categories: block.tags.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Parse time blocks from CSV in the `toki worklog --format csv` layout
+///
+/// Columns are found by header name: `start_time` and `end_time` (RFC 3339) are
+/// required; `description`, `project` (by name) and `tags` (`;`-separated) are
+/// optional. Every row is validated before anything is written.
+fn parse_csv_blocks(db: &Database, text: &str) -> Result<Vec<TimeBlock>> {
+    let mut records = parse_csv(text)?.into_iter();
+    let Some(header) = records.next() else {
+        anyhow::bail!("CSV file is empty");
+    };
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+    let (Some(start_col), Some(end_col)) = (column("start_time"), column("end_time")) else {
+        anyhow::bail!("CSV header must contain start_time and end_time columns");
+    };
+    let (description_col, project_col, tags_col) =
+        (column("description"), column("project"), column("tags"));
+
+    let mut blocks = Vec::new();
+    for (index, record) in records.enumerate() {
+        let row = index + 2;
+        let field = |col: Option<usize>| {
+            col.and_then(|c| record.get(c))
+                .map_or("", |value| value.trim())
+        };
+        let parse_time = |col: usize, name: &str| {
+            DateTime::parse_from_rfc3339(field(Some(col)))
+                .map(|t| t.with_timezone(&Utc))
+                .with_context(|| format!("Invalid {name} in row {row}"))
+        };
+
+        let start = parse_time(start_col, "start_time")?;
+        let end = parse_time(end_col, "end_time")?;
+        if end <= start {
+            anyhow::bail!("end_time is not after start_time in row {row}");
+        }
+
+        let mut block = TimeBlock::manual(start, end, field(description_col).to_string());
+        let project = field(project_col);
+        if !project.is_empty() {
+            let found = db
+                .get_project_by_name(project)?
+                .with_context(|| format!("Project not found in row {row}: {project}"))?;
+            block.project_id = Some(found.id);
+        }
+        block.tags = field(tags_col)
+            .split(';')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect();
+        blocks.push(block);
+    }
+    Ok(blocks)
+}
+
+pub fn handle_data_import(path: &str) -> Result<()> {
+    let db = Database::new(None)?;
+    if std::path::Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+    {
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("Failed to read {path}"))?;
+        let blocks = parse_csv_blocks(&db, &text)?;
+        for block in &blocks {
+            db.save_time_block(block)?;
+        }
+        println!("Imported {} time blocks from {path}", blocks.len());
+        return Ok(());
+    }
+
+    if std::path::Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    {
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("Failed to read {path}"))?;
+        let (projects, counts) = import_json(&db, &text)?;
+        println!("Imported {projects} projects, {counts} from {path}");
+        return Ok(());
+    }
+
+    let file = std::fs::File::open(path).with_context(|| format!("Failed to open {path}"))?;
+    let counts = import_ndjson(&db, BufReader::new(file))?;
+    println!("Imported {counts} from {path}");
+    Ok(())
+}
+
+pub fn handle_data_stats() -> Result<()> {
+    let db = Database::new(None)?;
+    let stats = db.get_embedding_stats()?;
+    let refresh_hours = db.get_settings()?.embedding_refresh_hours;
+
+    println!("Issue embeddings:");
+    println!(
+        "  Issues: {} ({} with embeddings)",
+        stats.issues, stats.embedded_issues
+    );
+    match stats.last_build {
+        Some(built_at) => println!(
+            "  Last build: {}",
+            built_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+        ),
+        None => println!("  Last build: never"),
+    }
+    println!("  Automatic rebuild after: {refresh_hours}h");
+    Ok(())
+}
+
 pub fn handle_data_delete(period: &str) -> Result<()> {
     let db = Database::new(None)?;
 
@@ -89,3 +453,238 @@ pub fn handle_data_delete(period: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Vacuum the database and report how much the file shrank
+pub fn handle_data_optimize() -> Result<()> {
+    let path = Database::default_db_path();
+    let db = Database::new(Some(path.clone()))?;
+    let before = std::fs::metadata(&path)?.len();
+
+    println!("Optimizing {}...", path.display());
+    db.vacuum()?;
+
+    let after = std::fs::metadata(&path)?.len();
+    println!("  Before: {}", format_file_size(before));
+    println!("  After:  {}", format_file_size(after));
+    println!(
+        "  Reclaimed: {}",
+        format_file_size(before.saturating_sub(after))
+    );
+    Ok(())
+}
+
+/// File size in the largest unit that keeps it at or above 1 (e.g. "4.2 MB")
+fn format_file_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    #[allow(clippy::cast_precision_loss)] // Shown to one decimal
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn populate(db: &Database, now: DateTime<Utc>) {
+        for minutes in [10, 20, 30] {
+            let mut activity =
+                Activity::new("com.apple.Terminal".to_string(), "Terminal".to_string(), 60);
+            activity.timestamp = now - Duration::minutes(minutes);
+            db.insert_activity(&activity).unwrap();
+        }
+
+        let session_id = db.create_session(now - Duration::hours(1)).unwrap();
+        db.create_session(now - Duration::minutes(5)).unwrap();
+
+        for minutes in [40, 50] {
+            let span = ActivitySpan::new(
+                "com.microsoft.VSCode".to_string(),
+                "Coding".to_string(),
+                now - Duration::minutes(minutes),
+                None,
+                None,
+                Some(session_id),
+            );
+            db.create_activity_span(&span).unwrap();
+        }
+
+        let block = TimeBlock::manual(
+            now - Duration::hours(1),
+            now - Duration::minutes(30),
+            "Review, \"quoted\" notes".to_string(),
+        );
+        db.save_time_block(&block).unwrap();
+    }
+
+    #[test]
+    fn test_ndjson_round_trip_preserves_row_counts() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = Database::new(Some(temp_dir.path().join("source.db"))).unwrap();
+        let now = Utc::now();
+        populate(&source, now);
+
+        let (start, end) = (now - Duration::days(1), now + Duration::minutes(1));
+        let mut buffer = Vec::new();
+        let exported = write_ndjson(&source, start, end, &mut buffer).unwrap();
+        assert_eq!(
+            exported,
+            NdjsonCounts {
+                activities: 3,
+                spans: 2,
+                sessions: 2,
+                blocks: 1
+            }
+        );
+        let text = String::from_utf8(buffer.clone()).unwrap();
+        assert_eq!(text.lines().count(), 8);
+        assert!(text.lines().all(|line| line.contains("\"entity\":")));
+
+        let target = Database::new(Some(temp_dir.path().join("target.db"))).unwrap();
+        let imported = import_ndjson(&target, buffer.as_slice()).unwrap();
+        assert_eq!(imported, exported);
+
+        let mut reexported = Vec::new();
+        assert_eq!(
+            write_ndjson(&target, start, end, &mut reexported).unwrap(),
+            exported
+        );
+
+        // Importing the same export again inserts nothing
+        assert_eq!(
+            import_ndjson(&target, buffer.as_slice()).unwrap(),
+            NdjsonCounts::default()
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip_skips_existing_rows() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = Database::new(Some(temp_dir.path().join("source.db"))).unwrap();
+        let now = Utc::now();
+        populate(&source, now);
+        source.get_or_create_project("toki", "/work/toki").unwrap();
+
+        let (start, end) = (now - Duration::days(1), now + Duration::minutes(1));
+        let export = json_export(&source, start, end).unwrap();
+        let text = serde_json::to_string_pretty(&export).unwrap();
+
+        let target = Database::new(Some(temp_dir.path().join("target.db"))).unwrap();
+        let (projects, counts) = import_json(&target, &text).unwrap();
+        assert_eq!(projects, 1);
+        assert_eq!(counts, export.counts());
+        assert_eq!(json_export(&target, start, end).unwrap().counts(), counts);
+
+        assert_eq!(
+            import_json(&target, &text).unwrap(),
+            (0, NdjsonCounts::default())
+        );
+    }
+
+    #[test]
+    fn test_json_import_rejects_other_versions_and_rolls_back() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("toki.db"))).unwrap();
+
+        let error = import_json(&db, "[]").unwrap_err();
+        assert!(error.to_string().contains("Not a versioned"), "{error}");
+        let newer = r#"{"version": 2, "projects": []}"#;
+        let error = import_json(&db, newer).unwrap_err();
+        assert!(error.to_string().contains("version 2"), "{error}");
+
+        // One malformed session fails the whole import, valid rows included
+        let now = Utc::now();
+        let session = Session::new();
+        let mut export = serde_json::json!({
+            "version": JSON_EXPORT_VERSION,
+            "exported_at": now,
+            "projects": [],
+            "sessions": [session, {"id": "not-a-session"}],
+            "activities": [],
+            "spans": [],
+            "blocks": [],
+        });
+        assert!(import_json(&db, &export.to_string()).is_err());
+
+        export["sessions"] = serde_json::json!([session]);
+        let (_, counts) = import_json(&db, &export.to_string()).unwrap();
+        assert_eq!(counts.sessions, 1);
+    }
+
+    #[test]
+    fn test_csv_import_handles_quotes_and_bom() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("toki.db"))).unwrap();
+        db.get_or_create_project("toki", "/work/toki").unwrap();
+        let text = "\u{feff}start_time,end_time,project,description,tags\r\n\
+                    2025-03-14T09:00:00Z,2025-03-14T10:30:00Z,toki,\"Review, fix and ship\",review;release\r\n\
+                    2025-03-14T11:00:00Z,2025-03-14T11:15:00Z,,\"Answer \"\"urgent\"\" email\",\r\n";
+
+        let blocks = parse_csv_blocks(&db, text).unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].description, "Review, fix and ship");
+        assert_eq!(blocks[0].tags, ["review", "release"]);
+        assert!(blocks[0].project_id.is_some());
+        assert_eq!(
+            (blocks[0].end_time - blocks[0].start_time).num_minutes(),
+            90
+        );
+        assert_eq!(blocks[1].description, "Answer \"urgent\" email");
+        assert!(blocks[1].project_id.is_none());
+        assert!(blocks[1].tags.is_empty());
+
+        let error = parse_csv_blocks(&db, "start_time,end_time\n2025-03-14,x\n").unwrap_err();
+        assert!(error.to_string().contains("row 2"), "{error}");
+    }
+
+    #[test]
+    fn test_ndjson_import_reports_bad_line() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("toki.db"))).unwrap();
+        let input = "\n{\"entity\":\"unknown\"}\n";
+
+        let error = import_ndjson(&db, input.as_bytes()).unwrap_err();
+        assert!(error.to_string().contains("line 2"), "{error}");
+    }
+
+    #[test]
+    fn test_ical_export_of_time_blocks() {
+        let stamp = Utc::now();
+        let empty = write_calendar(&time_block_events(&[]), stamp);
+        assert!(empty.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(empty.ends_with("END:VCALENDAR\r\n"));
+        assert!(!empty.contains("BEGIN:VEVENT"));
+
+        let start = DateTime::parse_from_rfc3339("2025-03-14T09:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut block = TimeBlock::manual(
+            start,
+            start + Duration::minutes(90),
+            "Review; fix, ship".to_string(),
+        );
+        block.tags = vec!["client".to_string(), "release".to_string()];
+
+        let ics = write_calendar(&time_block_events(std::slice::from_ref(&block)), stamp);
+        assert!(ics.contains(&format!("UID:{}@toki\r\n", block.id)));
+        assert!(ics.contains("DTSTART:20250314T090000Z\r\n"));
+        assert!(ics.contains("DTEND:20250314T103000Z\r\n"));
+        assert!(ics.contains("SUMMARY:Review\\; fix\\, ship\r\n"));
+        assert!(ics.contains("CATEGORIES:client,release\r\n"));
+    }
+
+    #[test]
+    fn test_format_file_size() {
+        assert_eq!(format_file_size(512), "512 B");
+        assert_eq!(format_file_size(1536), "1.5 KB");
+        assert_eq!(format_file_size(48 * 1024 * 1024), "48.0 MB");
+    }
+}
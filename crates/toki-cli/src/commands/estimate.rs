@@ -10,14 +10,23 @@ use std::sync::Arc;
 use anyhow::{Context, Result};
 use toki_ai::AiService;
 use toki_ai::time_estimator::{TimeBreakdown, TimeEstimate, TimeEstimator};
-use toki_storage::models::Complexity;
+use toki_integrations::{GitHubClient, GitLabClient};
+use toki_storage::models::{Complexity, IssueCandidate};
 use toki_storage::Database;
 
+/// Shortest estimate worth pushing; GitLab durations have minute resolution
+const MIN_PUSHED_ESTIMATE_SECONDS: u32 = 60;
+
+/// How to point estimates at a GitHub project field
+const GITHUB_FIELD_SETUP: &str = "toki config set github.project <project id> \
+&& toki config set github.estimate_field <single-select field id>";
+
 /// Estimate complexity and time for an issue
 pub async fn handle_estimate_command(
     issue_id: &str,
     set_complexity: Option<&str>,
     system: &str,
+    push: bool,
 ) -> Result<()> {
     let db = Arc::new(Database::new(None).context("Failed to open database")?);
 
@@ -30,6 +39,14 @@ pub async fn handle_estimate_command(
                 "Issue {issue_id} not found. Try running 'toki issue-sync' first."
             )
         })?;
+    // The lookup falls back to any system, so decide from the issue itself
+    let is_github = issue.external_system == "github";
+    if push && !is_github && issue.external_system != "gitlab" {
+        anyhow::bail!(
+            "Pushing estimates is only supported for GitLab and GitHub issues, not {}",
+            issue.external_system
+        );
+    }
 
     println!("Issue #{}: {}", issue.external_id, issue.title);
     println!("System: {}", issue.external_system);
@@ -57,6 +74,11 @@ pub async fn handle_estimate_command(
 
         println!("Set complexity: {complexity}");
         println!("Reason: {reason}");
+
+        if is_github && !push_github_complexity(&db, &issue, complexity).await? {
+            println!();
+            println!("Stored locally. To also set it on a GitHub project: {GITHUB_FIELD_SETUP}");
+        }
         return Ok(());
     }
 
@@ -103,6 +125,14 @@ pub async fn handle_estimate_command(
 
     print_time_estimate(&time_estimate, complexity);
 
+    if push && is_github {
+        if !push_github_complexity(&db, &issue, complexity).await? {
+            anyhow::bail!("GitHub project field not configured. Run: {GITHUB_FIELD_SETUP}");
+        }
+    } else if push {
+        push_gitlab_estimate(&db, &issue, &time_estimate).await?;
+    }
+
     // Show complexity scale reference
     println!();
     println!("Complexity Scale:");
@@ -120,6 +150,92 @@ pub async fn handle_estimate_command(
     Ok(())
 }
 
+/// Post the estimate to the GitLab issue as an `/estimate` quick action
+async fn push_gitlab_estimate(
+    db: &Database,
+    issue: &IssueCandidate,
+    estimate: &TimeEstimate,
+) -> Result<()> {
+    let seconds = pushable_estimate_seconds(estimate)?;
+
+    let config = db.get_integration_config("gitlab")?.ok_or_else(|| {
+        anyhow::anyhow!("GitLab not configured. Run: toki config set gitlab.api_key <token>")
+    })?;
+    let project = issue
+        .pm_project_id
+        .clone()
+        .or(config.project_id)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "GitLab project not configured. Run: toki config set gitlab.project <project>"
+            )
+        })?;
+    let client = if config.api_url.is_empty() {
+        GitLabClient::new(&config.api_key, &project)?
+    } else {
+        GitLabClient::with_base_url(&config.api_key, &project, &config.api_url)?
+    };
+
+    client
+        .push_estimate_note(&issue.external_id, seconds)
+        .await?;
+
+    println!();
+    println!(
+        "Pushed to GitLab: {}",
+        GitLabClient::estimate_note_body(seconds)
+    );
+    Ok(())
+}
+
+/// Select the complexity in the configured GitHub project's single-select field
+///
+/// The field's options are matched by name to the complexity labels
+/// (trivial, simple, moderate, complex, epic), ignoring case. Returns `false`
+/// without pushing when no project field is configured.
+async fn push_github_complexity(
+    db: &Database,
+    issue: &IssueCandidate,
+    complexity: Complexity,
+) -> Result<bool> {
+    let Some(config) = db.get_integration_config("github")? else {
+        return Ok(false);
+    };
+    // The estimate field id is stored in workspace_slug
+    let (Some(project), Some(field)) = (&config.project_id, &config.workspace_slug) else {
+        return Ok(false);
+    };
+    let repo = issue.pm_project_id.clone().ok_or_else(|| {
+        anyhow::anyhow!(
+            "Repository of issue #{} is unknown. Try running 'toki issue-sync' first.",
+            issue.external_id
+        )
+    })?;
+
+    GitHubClient::new(&config.api_key, repo)?
+        .set_issue_project_option(
+            project,
+            issue.external_id.trim_start_matches('#'),
+            field,
+            complexity.label(),
+        )
+        .await?;
+
+    println!();
+    println!("Pushed to GitHub project: {}", complexity.label());
+    Ok(true)
+}
+
+/// Estimate to push, refusing one too small to express in GitLab's format
+fn pushable_estimate_seconds(estimate: &TimeEstimate) -> Result<u32> {
+    if estimate.estimated_seconds < MIN_PUSHED_ESTIMATE_SECONDS {
+        anyhow::bail!(
+            "No estimate available to push. Set a complexity first: toki estimate <issue> --set <complexity>"
+        );
+    }
+    Ok(estimate.estimated_seconds)
+}
+
 /// Print time estimate details
 fn print_time_estimate(estimate: &TimeEstimate, _complexity: Complexity) {
     println!();
@@ -288,3 +404,33 @@ fn estimate_complexity(title: &str, description: Option<&str>, labels: &[String]
 
     (complexity, reason)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toki_ai::time_estimator::EstimationMethod;
+
+    fn estimate_of(seconds: u32) -> TimeEstimate {
+        TimeEstimate {
+            estimated_seconds: seconds,
+            low_seconds: seconds,
+            high_seconds: seconds,
+            confidence: 0.5,
+            similar_issues: Vec::new(),
+            method: EstimationMethod::ComplexityBased,
+            breakdown: None,
+        }
+    }
+
+    #[test]
+    fn test_pushable_estimate_becomes_gitlab_note() {
+        let seconds = pushable_estimate_seconds(&estimate_of(2 * 3600)).unwrap();
+        assert_eq!(GitLabClient::estimate_note_body(seconds), "/estimate 2h");
+    }
+
+    #[test]
+    fn test_refuses_to_push_without_estimate() {
+        assert!(pushable_estimate_seconds(&estimate_of(0)).is_err());
+        assert!(pushable_estimate_seconds(&estimate_of(45)).is_err());
+    }
+}
@@ -0,0 +1,308 @@
+//! ASCII gantt chart of a day's activity
+//!
+//! The day is cut into equal columns, at least 15 minutes wide. Each column
+//! goes to the category with the most time in it and stays blank when nothing
+//! was recorded, so idle time shows up as gaps.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use chrono::{DateTime, Duration, Timelike, Utc};
+use toki_storage::{ActivitySpan, DurationFormat, TrackingTimezone, WorkHours};
+
+/// Columns are never narrower than this
+const MIN_BUCKET_MINUTES: i64 = 15;
+
+/// Chart width when neither `--width` nor `$COLUMNS` is set
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// Width of the category label in front of each bar
+const LABEL_WIDTH: usize = 14;
+
+/// Room kept after each bar for the category total
+const TOTAL_WIDTH: usize = 9;
+
+/// Fewest columns drawn, however narrow the terminal
+const MIN_COLUMNS: usize = 12;
+
+/// ANSI foreground colors, picked per category
+const PALETTE: &[u8] = &[32, 34, 33, 35, 36, 31, 92, 94, 93, 95, 96, 91];
+
+/// Terminal color code for a category, the same on every run
+#[must_use]
+pub fn category_color(category: &str) -> u8 {
+    let hash = category.bytes().fold(0usize, |hash, byte| {
+        hash.wrapping_mul(31).wrapping_add(usize::from(byte))
+    });
+    PALETTE[hash % PALETTE.len()]
+}
+
+/// When a span ended; an open span runs until now
+fn span_end(span: &ActivitySpan) -> DateTime<Utc> {
+    span.end_time.unwrap_or_else(Utc::now).max(span.start_time)
+}
+
+/// `a / b` rounded up, for positive numbers
+fn div_ceil(a: i64, b: i64) -> i64 {
+    (a + b - 1) / b
+}
+
+fn floor_hour(time: DateTime<Utc>) -> DateTime<Utc> {
+    time - Duration::seconds(i64::from(time.minute() * 60 + time.second()))
+        - Duration::nanoseconds(i64::from(time.nanosecond()))
+}
+
+/// Time covered by the chart: the working hours of the day, widened to whole
+/// hours around any activity outside them
+#[must_use]
+pub fn chart_window(
+    day_start: DateTime<Utc>,
+    work_hours: WorkHours,
+    spans: &[ActivitySpan],
+) -> (DateTime<Utc>, DateTime<Utc>) {
+    let mut from = day_start + Duration::hours(i64::from(work_hours.start_hour));
+    let mut to = day_start + Duration::hours(i64::from(work_hours.end_hour));
+    if to <= from {
+        to += Duration::days(1);
+    }
+
+    for span in spans {
+        from = from.min(floor_hour(span.start_time));
+        let end = span_end(span);
+        let end_hour = floor_hour(end);
+        to = to.max(if end_hour == end {
+            end
+        } else {
+            end_hour + Duration::hours(1)
+        });
+    }
+    (from, to)
+}
+
+/// One category's bar
+#[derive(Debug)]
+struct GanttRow {
+    category: String,
+    seconds: u32,
+    columns: Vec<bool>,
+}
+
+/// Day of activity laid out in columns
+#[derive(Debug)]
+pub struct Gantt {
+    start: DateTime<Utc>,
+    bucket: Duration,
+    columns: usize,
+    rows: Vec<GanttRow>,
+}
+
+impl Gantt {
+    /// Lay out spans between `from` and `to` in a chart at most `width` characters wide
+    #[must_use]
+    pub fn build(
+        spans: &[ActivitySpan],
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        width: usize,
+    ) -> Self {
+        let available = width
+            .saturating_sub(LABEL_WIDTH + TOTAL_WIDTH)
+            .max(MIN_COLUMNS);
+        let window_minutes = (to - from).num_minutes().max(1);
+        let quarters = div_ceil(window_minutes, MIN_BUCKET_MINUTES);
+        let quarters_per_column = div_ceil(quarters, i64::try_from(available).unwrap_or(i64::MAX));
+        let bucket = Duration::minutes(MIN_BUCKET_MINUTES * quarters_per_column.max(1));
+        let columns = usize::try_from(div_ceil(window_minutes, bucket.num_minutes())).unwrap_or(0);
+
+        // Seconds per category in each column
+        let mut coverage: Vec<HashMap<&str, i64>> = vec![HashMap::new(); columns];
+        let mut totals: HashMap<&str, u32> = HashMap::new();
+        for span in spans {
+            let (start, end) = (span.start_time.max(from), span_end(span).min(to));
+            if end <= start {
+                continue;
+            }
+            *totals.entry(span.category.as_str()).or_default() +=
+                u32::try_from((end - start).num_seconds()).unwrap_or(0);
+
+            let first =
+                usize::try_from((start - from).num_seconds() / bucket.num_seconds()).unwrap_or(0);
+            for (index, column) in coverage.iter_mut().enumerate().skip(first) {
+                let column_start = from + bucket * i32::try_from(index).unwrap_or(i32::MAX);
+                if column_start >= end {
+                    break;
+                }
+                let overlap = end.min(column_start + bucket) - start.max(column_start);
+                *column.entry(span.category.as_str()).or_default() += overlap.num_seconds();
+            }
+        }
+
+        let owners: Vec<Option<&str>> = coverage
+            .iter()
+            .map(|column| {
+                column
+                    .iter()
+                    .filter(|(_, seconds)| **seconds > 0)
+                    .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+                    .map(|(category, _)| *category)
+            })
+            .collect();
+
+        let mut rows: Vec<GanttRow> = totals
+            .into_iter()
+            .map(|(category, seconds)| GanttRow {
+                category: category.to_string(),
+                seconds,
+                columns: owners
+                    .iter()
+                    .map(|owner| *owner == Some(category))
+                    .collect(),
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            b.seconds
+                .cmp(&a.seconds)
+                .then_with(|| a.category.cmp(&b.category))
+        });
+
+        Self {
+            start: from,
+            bucket,
+            columns,
+            rows,
+        }
+    }
+
+    /// Minutes each column stands for
+    #[must_use]
+    pub fn bucket_minutes(&self) -> i64 {
+        self.bucket.num_minutes()
+    }
+
+    /// Hour labels above the columns that start on the hour
+    fn axis(&self, timezone: TrackingTimezone) -> String {
+        let offset = Duration::minutes(i64::from(timezone.offset_minutes));
+        let mut axis = vec![' '; self.columns + 1];
+        let mut next_free = 0;
+        for index in 0..self.columns {
+            let local =
+                self.start + self.bucket * i32::try_from(index).unwrap_or(i32::MAX) + offset;
+            if index >= next_free && local.minute() == 0 {
+                let label = format!("{:02}", local.hour());
+                for (slot, c) in axis[index..].iter_mut().zip(label.chars()) {
+                    *slot = c;
+                }
+                next_free = index + label.len() + 1;
+            }
+        }
+        axis.into_iter().collect::<String>().trim_end().to_string()
+    }
+
+    /// Draw the chart, one bar per category with its total time
+    #[must_use]
+    pub fn render(
+        &self,
+        timezone: TrackingTimezone,
+        durations: DurationFormat,
+        color: bool,
+    ) -> String {
+        let mut out = format!("{:LABEL_WIDTH$}{}\n", "", self.axis(timezone));
+        for row in &self.rows {
+            let label: String = row.category.chars().take(LABEL_WIDTH - 1).collect();
+            let bar: String = row
+                .columns
+                .iter()
+                .map(|filled| if *filled { '#' } else { ' ' })
+                .collect();
+            let bar = if color {
+                format!("\x1b[{}m{bar}\x1b[0m", category_color(&row.category))
+            } else {
+                bar
+            };
+            let _ = writeln!(
+                out,
+                "{label:LABEL_WIDTH$}{bar} {}",
+                durations.format(row.seconds)
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span_at(category: &str, start: &str, minutes: i64) -> ActivitySpan {
+        let start = DateTime::parse_from_rfc3339(start)
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut span = ActivitySpan::new(
+            "com.example.app".to_string(),
+            category.to_string(),
+            start,
+            None,
+            None,
+            None,
+        );
+        span.end_time = Some(start + Duration::minutes(minutes));
+        span
+    }
+
+    fn time(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_columns_follow_dominant_category_and_gaps_stay_blank() {
+        let spans = vec![
+            span_at("Coding", "2025-03-14T09:00:00Z", 30),
+            // Idle from 09:30 to 10:00
+            span_at("Meetings", "2025-03-14T10:00:00Z", 20),
+            span_at("Coding", "2025-03-14T10:20:00Z", 10),
+        ];
+        let gantt = Gantt::build(
+            &spans,
+            time("2025-03-14T09:00:00Z"),
+            time("2025-03-14T11:00:00Z"),
+            DEFAULT_WIDTH,
+        );
+
+        assert_eq!(gantt.bucket_minutes(), 15);
+        let chart = gantt.render(
+            TrackingTimezone::default(),
+            DurationFormat::default(),
+            false,
+        );
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines[0].trim(), "09  10");
+        assert_eq!(lines[1], format!("{:14}##   #   40m", "Coding"));
+        assert_eq!(lines[2], format!("{:14}    #    20m", "Meetings"));
+    }
+
+    #[test]
+    fn test_narrow_width_widens_columns() {
+        let spans = vec![span_at("Coding", "2025-03-14T08:00:00Z", 60)];
+        let gantt = Gantt::build(
+            &spans,
+            time("2025-03-14T00:00:00Z"),
+            time("2025-03-15T00:00:00Z"),
+            30,
+        );
+        // 96 quarter hours squeezed into the minimum of 12 columns
+        assert_eq!(gantt.bucket_minutes(), 120);
+        assert_eq!(gantt.columns, 12);
+    }
+
+    #[test]
+    fn test_window_covers_work_hours_and_activity_outside_them() {
+        let day_start = time("2025-03-14T00:00:00Z");
+        let late = span_at("Coding", "2025-03-14T19:10:00Z", 30);
+
+        let (from, to) = chart_window(day_start, WorkHours::default(), &[late]);
+        assert_eq!(from, time("2025-03-14T09:00:00Z"));
+        assert_eq!(to, time("2025-03-14T20:00:00Z"));
+    }
+}
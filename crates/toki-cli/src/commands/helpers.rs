@@ -1,5 +1,15 @@
 //! Helper utility functions for CLI commands
 
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use toki_integrations::TrelloClient;
+use toki_storage::{Database, IntegrationConfig, IntegrationDebugLog};
+
+/// Integration request log file, relative to the data directory
+pub const INTEGRATION_LOG_FILE: &str = "integrations.log";
+
 /// Safely truncate a string to a maximum number of characters (not bytes).
 /// This avoids panics when slicing multi-byte UTF-8 characters.
 pub fn truncate_str(s: &str, max_chars: usize) -> String {
@@ -21,10 +31,136 @@ pub fn escape_csv(s: &str) -> String {
     }
 }
 
+/// Parse CSV text into records (RFC 4180)
+///
+/// Handles quoted fields containing commas, line breaks and doubled quotes,
+/// CRLF line endings and the UTF-8 BOM Excel puts in front of the header.
+/// Blank lines are skipped.
+pub fn parse_csv(text: &str) -> Result<Vec<Vec<String>>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                if record.len() == 1 && record[0].is_empty() {
+                    record.clear();
+                } else {
+                    records.push(std::mem::take(&mut record));
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        anyhow::bail!(
+            "Unterminated quoted field in CSV record {}",
+            records.len() + 1
+        );
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Parse a `YYYY-MM-DD:YYYY-MM-DD` range covering both days in full
+pub fn parse_date_range(range: &str) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+    let mut parts = range.split(':');
+    let (Some(start), Some(end), None) = (parts.next(), parts.next(), parts.next()) else {
+        anyhow::bail!("Invalid date range format. Use YYYY-MM-DD:YYYY-MM-DD");
+    };
+    let start = NaiveDate::parse_from_str(start, "%Y-%m-%d")
+        .context("Invalid start date")?
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc();
+    let end = NaiveDate::parse_from_str(end, "%Y-%m-%d")
+        .context("Invalid end date")?
+        .and_hms_opt(23, 59, 59)
+        .unwrap()
+        .and_utc();
+    Ok((start, end))
+}
+
+/// Apply `integrations.debug_log` to the integration clients of this process
+pub fn apply_integration_debug_log(db: &Database, data_dir: &Path) -> Result<()> {
+    match db.get_settings()?.integration_debug_log {
+        IntegrationDebugLog::Off => toki_integrations::disable_request_log(),
+        level => toki_integrations::enable_request_log(
+            data_dir.join(INTEGRATION_LOG_FILE),
+            level == IntegrationDebugLog::Bodies,
+        ),
+    }
+    Ok(())
+}
+
+/// Trello client from the `trello` integration config
+///
+/// The API key is stored in `api_key` and the user token in `workspace_slug`.
+pub fn trello_client(config: &IntegrationConfig) -> Result<TrelloClient> {
+    let token = config.workspace_slug.as_deref().unwrap_or_default();
+    if config.api_key.is_empty() || token.is_empty() {
+        anyhow::bail!(
+            "Trello credentials not configured. Run: toki config set trello.api_key <key> and toki config set trello.token <token>"
+        );
+    }
+    TrelloClient::new(&config.api_key, token)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_csv_quoted_fields_and_bom() {
+        let text = "\u{feff}start_time,description,tags\r\n\
+                    2025-03-14T09:00:00Z,\"Review, then fix\",a;b\r\n\
+                    2025-03-14T10:00:00Z,\"Say \"\"hi\"\"\",\r\n\
+                    \r\n\
+                    2025-03-14T11:00:00Z,\"two\nlines\",x";
+
+        let records = parse_csv(text).unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0], ["start_time", "description", "tags"]);
+        assert_eq!(
+            records[1],
+            ["2025-03-14T09:00:00Z", "Review, then fix", "a;b"]
+        );
+        assert_eq!(records[2], ["2025-03-14T10:00:00Z", "Say \"hi\"", ""]);
+        assert_eq!(records[3], ["2025-03-14T11:00:00Z", "two\nlines", "x"]);
+    }
+
+    #[test]
+    fn test_parse_csv_round_trips_escape_csv() {
+        let values = ["plain", "a,b", "say \"hi\"", "multi\nline"];
+        let line = values.map(escape_csv).join(",");
+
+        assert_eq!(parse_csv(&line).unwrap(), [values]);
+        assert!(parse_csv("a,\"unterminated").is_err());
+    }
+
     #[test]
     fn test_truncate_str_short() {
         assert_eq!(truncate_str("hello", 10), "hello");
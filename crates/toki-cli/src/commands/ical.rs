@@ -0,0 +1,219 @@
+//! iCalendar (RFC 5545) output
+//!
+//! Minimal VCALENDAR writer used to export time blocks as calendar events.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Maximum line length in octets, excluding the CRLF
+const MAX_LINE_OCTETS: usize = 75;
+
+/// One VEVENT
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub summary: String,
+    pub description: String,
+    pub categories: Vec<String>,
+}
+
+/// Event UID for a time block, shared by every export so calendars dedupe re-imports
+#[must_use]
+pub fn block_uid(block_id: Uuid) -> String {
+    format!("{block_id}@toki")
+}
+
+/// Render events as an iCalendar document with CRLF line endings
+#[must_use]
+pub fn write_calendar(events: &[CalendarEvent], stamp: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//RikaiDev//toki//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", escape_text(&event.uid)));
+        lines.push(format!("DTSTAMP:{}", format_datetime(stamp)));
+        lines.push(format!("DTSTART:{}", format_datetime(event.start)));
+        lines.push(format!("DTEND:{}", format_datetime(event.end)));
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if !event.description.is_empty() {
+            lines.push(format!("DESCRIPTION:{}", escape_text(&event.description)));
+        }
+        if !event.categories.is_empty() {
+            let categories: Vec<String> = event.categories.iter().map(|c| escape_text(c)).collect();
+            lines.push(format!("CATEGORIES:{}", categories.join(",")));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line) + "\r\n").collect()
+}
+
+/// UTC date-time in the RFC 5545 basic format (e.g., `20250101T093000Z`)
+fn format_datetime(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape a TEXT property value
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Fold a content line at 75 octets without splitting UTF-8 characters
+///
+/// Continuation lines start with a single space, which counts toward their length.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + line.len() / MAX_LINE_OCTETS * 3);
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// Unfold and parse VEVENTs into (name, unescaped value) property lists
+    fn parse_events(ics: &str) -> Vec<Vec<(String, String)>> {
+        let unfolded = ics.replace("\r\n ", "");
+        let mut events = Vec::new();
+        let mut current: Option<Vec<(String, String)>> = None;
+        for line in unfolded.split("\r\n").filter(|l| !l.is_empty()) {
+            match line {
+                "BEGIN:VEVENT" => current = Some(Vec::new()),
+                "END:VEVENT" => events.extend(current.take()),
+                _ => {
+                    if let (Some(props), Some((name, value))) = (&mut current, line.split_once(':'))
+                    {
+                        props.push((name.to_string(), unescape(value)));
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    fn unescape(value: &str) -> String {
+        let mut out = String::new();
+        let mut chars = value.chars();
+        while let Some(c) = chars.next() {
+            if c == '\\' {
+                match chars.next() {
+                    Some('n' | 'N') => out.push('\n'),
+                    Some(other) => out.push(other),
+                    None => {}
+                }
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    fn prop<'a>(event: &'a [(String, String)], name: &str) -> Option<&'a str> {
+        event
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn test_events_round_trip_through_parser() {
+        let start = Utc.with_ymd_and_hms(2025, 3, 14, 9, 0, 0).unwrap();
+        let long_summary = format!("PROJ-42: {}", "refactor session manager ".repeat(6));
+        let events = vec![
+            CalendarEvent {
+                uid: "block-1@toki".to_string(),
+                start,
+                end: start + chrono::Duration::minutes(90),
+                summary: long_summary.clone(),
+                description: "Category: Development".to_string(),
+                categories: Vec::new(),
+            },
+            CalendarEvent {
+                uid: "block-2@toki".to_string(),
+                start: start + chrono::Duration::hours(2),
+                end: start + chrono::Duration::hours(3),
+                summary: "Code review \u{2014} \u{5be9}\u{67fb}".repeat(5),
+                description: String::new(),
+                categories: Vec::new(),
+            },
+        ];
+
+        let ics = write_calendar(&events, start);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.split("\r\n").all(|line| line.len() <= MAX_LINE_OCTETS));
+
+        let parsed = parse_events(&ics);
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(prop(&parsed[0], "UID"), Some("block-1@toki"));
+        assert_eq!(prop(&parsed[0], "DTSTART"), Some("20250314T090000Z"));
+        assert_eq!(prop(&parsed[0], "DTEND"), Some("20250314T103000Z"));
+        assert_eq!(prop(&parsed[0], "SUMMARY"), Some(long_summary.as_str()));
+        assert_eq!(
+            prop(&parsed[0], "DESCRIPTION"),
+            Some("Category: Development")
+        );
+        assert_eq!(
+            prop(&parsed[1], "SUMMARY"),
+            Some(events[1].summary.as_str())
+        );
+        assert_eq!(prop(&parsed[1], "DESCRIPTION"), None);
+    }
+
+    #[test]
+    fn test_special_characters_escaped() {
+        assert_eq!(
+            escape_text("a,b;c\\d\r\ne"),
+            "a\\,b\\;c\\\\d\\ne".to_string()
+        );
+
+        let start = Utc.with_ymd_and_hms(2025, 3, 14, 9, 0, 0).unwrap();
+        let event = CalendarEvent {
+            uid: "x".to_string(),
+            start,
+            end: start,
+            summary: "Fix login, signup; docs".to_string(),
+            description: "Category: Development\nIssues: #1".to_string(),
+            categories: vec!["client, inc".to_string(), "review".to_string()],
+        };
+        let ics = write_calendar(std::slice::from_ref(&event), start);
+        assert!(ics.contains("SUMMARY:Fix login\\, signup\\; docs\r\n"));
+        assert!(ics.contains("DESCRIPTION:Category: Development\\nIssues: #1\r\n"));
+        assert!(ics.contains("CATEGORIES:client\\, inc,review\r\n"));
+
+        let parsed = parse_events(&ics);
+        assert_eq!(prop(&parsed[0], "SUMMARY"), Some(event.summary.as_str()));
+        assert_eq!(
+            prop(&parsed[0], "DESCRIPTION"),
+            Some(event.description.as_str())
+        );
+    }
+}
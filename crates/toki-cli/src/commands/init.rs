@@ -1,24 +1,70 @@
 //! Initialize toki with complete system setup
 //!
-//! Handles database initialization, permission guidance, and auto-start configuration
+//! Handles database initialization, permission guidance, and auto-start configuration.
+//! Re-running init keeps an existing database unless `--force` is given.
 
 use anyhow::{Context, Result};
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use toki_storage::{default_key_path, generate_key, save_key_to_file, Database};
 
+/// Integration sections reported in the summary of an existing install
+const INTEGRATION_SYSTEMS: &[&str] = &["plane", "notion", "github", "gitlab", "jira", "trello"];
+
+/// What an existing installation already holds
+#[derive(Debug)]
+struct InstallSummary {
+    projects: usize,
+    categories: usize,
+    rules: usize,
+    integrations: Vec<&'static str>,
+}
+
+impl InstallSummary {
+    fn load(db: &Database) -> Result<Self> {
+        let mut integrations = Vec::new();
+        for system in INTEGRATION_SYSTEMS {
+            if db.get_integration_config(system)?.is_some() {
+                integrations.push(*system);
+            }
+        }
+
+        Ok(Self {
+            projects: db.get_all_projects()?.len(),
+            categories: db.get_categories()?.len(),
+            rules: db.get_classification_rules()?.len(),
+            integrations,
+        })
+    }
+
+    fn print(&self) {
+        println!("  Projects:             {}", self.projects);
+        println!("  Categories:           {}", self.categories);
+        println!("  Classification rules: {}", self.rules);
+        if self.integrations.is_empty() {
+            println!("  Integrations:         none");
+        } else {
+            println!("  Integrations:         {}", self.integrations.join(", "));
+        }
+    }
+}
+
 /// Initialize toki with complete setup
 ///
+/// An existing database is summarized and kept as-is; `force` moves it aside
+/// and creates a fresh one with default settings.
+///
 /// # Errors
 ///
 /// Returns an error if key generation, file operations, or database initialization fails
-pub fn init_command(enable_encryption: bool) -> Result<()> {
+pub fn init_command(enable_encryption: bool, force: bool) -> Result<()> {
     println!("Initializing Toki Time Tracking...\n");
 
     // Step 1: Database setup
     println!("Step 1/3: Database Setup");
     println!("{}", "-".repeat(40));
-    setup_database(enable_encryption)?;
+    setup_database(&Database::default_db_path(), enable_encryption, force)?;
 
     // Step 2: Platform-specific permissions
     println!("\nStep 2/3: System Permissions");
@@ -41,7 +87,23 @@ pub fn init_command(enable_encryption: bool) -> Result<()> {
     Ok(())
 }
 
-fn setup_database(enable_encryption: bool) -> Result<()> {
+fn setup_database(db_path: &Path, enable_encryption: bool, force: bool) -> Result<()> {
+    if db_path.exists() {
+        // Opening also applies pending migrations to older installs
+        let summary = InstallSummary::load(&Database::new(Some(db_path.to_path_buf()))?)?;
+        println!("Existing installation found at: {}", db_path.display());
+        summary.print();
+
+        if !force {
+            println!("\nKeeping existing settings, categories and rules.");
+            println!("Run 'toki init --force' to recreate the database from scratch.");
+            return Ok(());
+        }
+
+        let backup = backup_database(db_path)?;
+        println!("\nMoved existing database to: {}", backup.display());
+    }
+
     if enable_encryption {
         println!("Setting up encrypted database...");
 
@@ -52,7 +114,7 @@ fn setup_database(enable_encryption: bool) -> Result<()> {
             println!("Using existing key.");
 
             // Just verify database works
-            let _ = Database::new(None)?;
+            let _ = Database::new(Some(db_path.to_path_buf()))?;
         } else {
             let key = generate_key();
             save_key_to_file(&key, &key_path)?;
@@ -60,7 +122,7 @@ fn setup_database(enable_encryption: bool) -> Result<()> {
             println!("Encryption key saved to: {}", key_path.display());
             println!("Keep this file safe!");
 
-            let db = Database::new_with_encryption(None, Some(key))?;
+            let db = Database::new_with_encryption(Some(db_path.to_path_buf()), Some(key))?;
             drop(db);
         }
 
@@ -68,7 +130,7 @@ fn setup_database(enable_encryption: bool) -> Result<()> {
     } else {
         println!("Setting up database (unencrypted)...");
 
-        let db = Database::new(None)?;
+        let db = Database::new(Some(db_path.to_path_buf()))?;
         drop(db);
 
         println!("Database ready.");
@@ -78,6 +140,14 @@ fn setup_database(enable_encryption: bool) -> Result<()> {
     Ok(())
 }
 
+/// Move the database aside (replacing any previous backup) so it can be recreated
+fn backup_database(db_path: &Path) -> Result<PathBuf> {
+    let backup = db_path.with_extension("db.bak");
+    fs::rename(db_path, &backup)
+        .with_context(|| format!("Failed to move {} aside", db_path.display()))?;
+    Ok(backup)
+}
+
 fn show_permission_guidance() {
     #[cfg(target_os = "macos")]
     {
@@ -241,3 +311,79 @@ fn setup_autostart() -> Result<()> {
     println!("Please start toki manually with: toki start");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toki_storage::{Category, ClassificationRule, PatternType};
+
+    fn customize(db_path: &Path) {
+        let db = Database::new(Some(db_path.to_path_buf())).unwrap();
+        let mut settings = db.get_settings().unwrap();
+        settings.idle_threshold_seconds = 900;
+        db.update_settings(&settings).unwrap();
+        db.get_or_create_project("toki", "/code/toki").unwrap();
+        db.upsert_category(&Category::new(
+            "Client Work".to_string(),
+            "acme".to_string(),
+            None,
+        ))
+        .unwrap();
+        db.save_classification_rule(&ClassificationRule::from_correction(
+            "instagram.com".to_string(),
+            PatternType::Domain,
+            "Break".to_string(),
+        ))
+        .unwrap();
+    }
+
+    /// Whether the customizations made by [`customize`] are present
+    fn customized_category_and_rule(db: &Database) -> (bool, bool) {
+        let category = db
+            .get_categories()
+            .unwrap()
+            .iter()
+            .any(|c| c.name == "Client Work");
+        let rule = db
+            .get_classification_rules()
+            .unwrap()
+            .iter()
+            .any(|r| r.pattern == "instagram.com");
+        (category, rule)
+    }
+
+    #[test]
+    fn test_reinit_without_force_preserves_settings() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("toki.db");
+        setup_database(&db_path, false, false).unwrap();
+        customize(&db_path);
+
+        setup_database(&db_path, false, false).unwrap();
+
+        let db = Database::new(Some(db_path)).unwrap();
+        assert_eq!(db.get_settings().unwrap().idle_threshold_seconds, 900);
+        assert_eq!(db.get_all_projects().unwrap().len(), 1);
+        assert_eq!(customized_category_and_rule(&db), (true, true));
+    }
+
+    #[test]
+    fn test_reinit_with_force_resets_to_defaults() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("toki.db");
+        setup_database(&db_path, false, false).unwrap();
+        customize(&db_path);
+
+        setup_database(&db_path, false, true).unwrap();
+
+        let db = Database::new(Some(db_path.clone())).unwrap();
+        let defaults = toki_storage::Settings::default();
+        assert_eq!(
+            db.get_settings().unwrap().idle_threshold_seconds,
+            defaults.idle_threshold_seconds
+        );
+        assert!(db.get_all_projects().unwrap().is_empty());
+        assert_eq!(customized_category_and_rule(&db), (false, false));
+        assert!(db_path.with_extension("db.bak").exists());
+    }
+}
@@ -2,11 +2,30 @@
 //!
 //! Analyzes work patterns over time to provide insights and detect anomalies.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Datelike, Duration, NaiveDate, Timelike, Utc};
-use toki_storage::Database;
+use toki_ai::{
+    AnomalyMetric, FragmentationTrend, GravityBreakdown, InsightsGenerator, IssueSignals,
+    SpanAggregator, ANOMALY_STD_DEVS,
+};
+use toki_core::{AppDisplayNames, WorkSchedule};
+use toki_storage::{ActivitySpan, Database, DurationFormat};
+
+use super::helpers::parse_date_range;
+
+/// Issues listed by `--focus gravity`
+const MAX_GRAVITY_ISSUES: usize = 10;
+
+/// Apps listed by `--focus apps`
+const MAX_TOP_APPS: usize = 10;
+
+/// Apps and categories listed by `--focus unmatched`
+const MAX_UNMATCHED_ITEMS: usize = 5;
+
+/// Share of activity outside working hours reported as an anomaly
+const OFF_HOURS_SHARE_WARNING: f64 = 0.25;
 
 /// Productivity metrics for a time period
 #[derive(Debug, Default)]
@@ -33,6 +52,48 @@ struct ProductivityMetrics {
     longest_session: u32,
     /// Sessions per day
     sessions_per_day: HashMap<NaiveDate, u32>,
+    /// Tracked activity time in seconds
+    activity_seconds: u32,
+    /// Tracked activity outside the working hours of its project
+    off_hours_seconds: u32,
+    /// Daily context-switching index, from tracked activity
+    fragmentation: FragmentationTrend,
+    /// Tracked activity totals, for comparing against another period
+    activity: SpanAggregator,
+}
+
+/// One app's usage in the current period next to the previous one
+#[derive(Debug, Clone, PartialEq)]
+struct AppUsageTrend {
+    bundle_id: String,
+    seconds: u32,
+    previous_seconds: u32,
+    /// Share of the current period's active time (0.0-1.0)
+    share: f64,
+}
+
+impl AppUsageTrend {
+    /// Change in seconds since the previous period
+    fn delta_seconds(&self) -> i64 {
+        i64::from(self.seconds) - i64::from(self.previous_seconds)
+    }
+}
+
+/// Tracked time and how much of it has no issue attributed
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct AttributionGap {
+    total_seconds: u32,
+    unmatched_seconds: u32,
+}
+
+impl AttributionGap {
+    /// Share of tracked time without an issue (0.0-1.0)
+    fn unmatched_fraction(self) -> f64 {
+        if self.total_seconds == 0 {
+            return 0.0;
+        }
+        f64::from(self.unmatched_seconds) / f64::from(self.total_seconds)
+    }
 }
 
 /// Anomaly detected in the data
@@ -85,24 +146,7 @@ fn parse_period(period: &str) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
             let start = today.and_hms_opt(0, 0, 0).unwrap().and_utc();
             Ok((start, now))
         }
-        _ if period.contains(':') => {
-            // Custom range: YYYY-MM-DD:YYYY-MM-DD
-            let parts: Vec<&str> = period.split(':').collect();
-            if parts.len() != 2 {
-                anyhow::bail!("Invalid date range format. Use YYYY-MM-DD:YYYY-MM-DD");
-            }
-            let start = NaiveDate::parse_from_str(parts[0], "%Y-%m-%d")
-                .context("Invalid start date")?
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let end = NaiveDate::parse_from_str(parts[1], "%Y-%m-%d")
-                .context("Invalid end date")?
-                .and_hms_opt(23, 59, 59)
-                .unwrap()
-                .and_utc();
-            Ok((start, end))
-        }
+        _ if period.contains(':') => parse_date_range(period),
         _ => {
             anyhow::bail!("Unknown period: {period}. Use 'week', 'month', 'today', or YYYY-MM-DD:YYYY-MM-DD");
         }
@@ -157,6 +201,20 @@ fn collect_metrics(db: &Database, start: DateTime<Utc>, end: DateTime<Utc>) -> R
 
     metrics.project_count = u32::try_from(projects_seen.len()).unwrap_or(u32::MAX);
 
+    // Streamed, so a month of spans is never loaded at once
+    let schedule = WorkSchedule::load(db)?;
+    let mut activity = SpanAggregator::new();
+    db.for_each_activity_span(start, end, |span| {
+        if schedule.is_off_hours(&span) {
+            metrics.off_hours_seconds += span.duration_seconds;
+        }
+        activity.add(&span);
+        Ok(())
+    })?;
+    metrics.activity_seconds = activity.total_seconds();
+    metrics.fragmentation = activity.fragmentation_trend();
+    metrics.activity = activity;
+
     if metrics.session_count > 0 {
         metrics.avg_session_seconds = metrics.total_seconds / metrics.session_count;
     }
@@ -164,8 +222,133 @@ fn collect_metrics(db: &Database, start: DateTime<Utc>, end: DateTime<Utc>) -> R
     Ok(metrics)
 }
 
+/// Share of tracked activity outside working hours (0.0-1.0)
+fn off_hours_share(metrics: &ProductivityMetrics) -> f64 {
+    if metrics.activity_seconds == 0 {
+        return 0.0;
+    }
+    f64::from(metrics.off_hours_seconds) / f64::from(metrics.activity_seconds)
+}
+
+/// Per-app usage with the change since the previous period
+///
+/// Takes per-app totals for both periods, most used first. The top `limit` apps of
+/// the current period come first, followed by apps from the previous period's top
+/// `limit` that were not used at all this period.
+fn app_usage_trends(
+    current: &[(String, u32)],
+    previous: &[(String, u32)],
+    limit: usize,
+) -> Vec<AppUsageTrend> {
+    let total: u32 = current.iter().map(|(_, seconds)| seconds).sum();
+    let previous_seconds: HashMap<&str, u32> = previous
+        .iter()
+        .map(|(app, seconds)| (app.as_str(), *seconds))
+        .collect();
+    let current_apps: HashSet<&str> = current.iter().map(|(app, _)| app.as_str()).collect();
+
+    let mut trends: Vec<AppUsageTrend> = current
+        .iter()
+        .take(limit)
+        .map(|(app, seconds)| AppUsageTrend {
+            bundle_id: app.clone(),
+            seconds: *seconds,
+            previous_seconds: previous_seconds.get(app.as_str()).copied().unwrap_or(0),
+            share: if total > 0 {
+                f64::from(*seconds) / f64::from(total)
+            } else {
+                0.0
+            },
+        })
+        .collect();
+
+    trends.extend(
+        previous
+            .iter()
+            .take(limit)
+            .filter(|(app, _)| !current_apps.contains(app.as_str()))
+            .map(|(app, seconds)| AppUsageTrend {
+                bundle_id: app.clone(),
+                seconds: 0,
+                previous_seconds: *seconds,
+                share: 0.0,
+            }),
+    );
+    trends
+}
+
+/// Unattributed time overall and per project, most unattributed project first
+///
+/// `spans` are all spans of the period and `unmatched` the ones without a work item.
+fn attribution_gaps(
+    spans: &[ActivitySpan],
+    unmatched: &[ActivitySpan],
+) -> (AttributionGap, Vec<(Option<uuid::Uuid>, AttributionGap)>) {
+    let mut overall = AttributionGap::default();
+    let mut by_project: HashMap<Option<uuid::Uuid>, AttributionGap> = HashMap::new();
+    for span in spans {
+        overall.total_seconds += span.duration_seconds;
+        by_project.entry(span.project_id).or_default().total_seconds += span.duration_seconds;
+    }
+    for span in unmatched {
+        overall.unmatched_seconds += span.duration_seconds;
+        let gap = by_project.entry(span.project_id).or_default();
+        gap.unmatched_seconds += span.duration_seconds;
+    }
+
+    let mut projects: Vec<_> = by_project.into_iter().collect();
+    projects.sort_by_key(|(_, gap)| std::cmp::Reverse(gap.unmatched_seconds));
+    (overall, projects)
+}
+
+/// Unattributed time per category, most first
+fn unmatched_by_category(unmatched: &[ActivitySpan], limit: usize) -> Vec<(String, u32)> {
+    let mut totals: HashMap<&str, u32> = HashMap::new();
+    for span in unmatched {
+        *totals.entry(span.category.as_str()).or_insert(0) += span.duration_seconds;
+    }
+    let mut categories: Vec<(String, u32)> = totals
+        .into_iter()
+        .map(|(category, seconds)| (category.to_string(), seconds))
+        .collect();
+    categories.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    categories.truncate(limit);
+    categories
+}
+
+/// Time per stored work pattern, most time first
+fn work_pattern_totals(spans: &[(ActivitySpan, String)]) -> Vec<(String, u32)> {
+    let mut totals: HashMap<&str, u32> = HashMap::new();
+    for (span, pattern) in spans {
+        *totals.entry(pattern.as_str()).or_insert(0) += span.duration_seconds;
+    }
+    let mut patterns: Vec<(String, u32)> = totals
+        .into_iter()
+        .map(|(pattern, seconds)| (pattern.to_string(), seconds))
+        .collect();
+    patterns.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    patterns
+}
+
+/// Time per stored work pattern for each day a span started on
+fn work_pattern_days(spans: &[(ActivitySpan, String)]) -> BTreeMap<NaiveDate, Vec<(String, u32)>> {
+    let mut days: BTreeMap<NaiveDate, Vec<(ActivitySpan, String)>> = BTreeMap::new();
+    for (span, pattern) in spans {
+        days.entry(span.start_time.date_naive())
+            .or_default()
+            .push((span.clone(), pattern.clone()));
+    }
+    days.into_iter()
+        .map(|(day, spans)| (day, work_pattern_totals(&spans)))
+        .collect()
+}
+
 /// Detect anomalies by comparing current period with previous
-fn detect_anomalies(current: &ProductivityMetrics, previous: Option<&ProductivityMetrics>) -> Vec<Anomaly> {
+fn detect_anomalies(
+    current: &ProductivityMetrics,
+    previous: Option<&ProductivityMetrics>,
+    durations: DurationFormat,
+) -> Vec<Anomaly> {
     let mut anomalies = Vec::new();
 
     // Check for unusual patterns in current period
@@ -175,7 +358,7 @@ fn detect_anomalies(current: &ProductivityMetrics, previous: Option<&Productivit
         anomalies.push(Anomaly {
             description: "Unusually long session detected".to_string(),
             severity: AnomalySeverity::Info,
-            value: format_duration(current.longest_session),
+            value: durations.format(current.longest_session),
             expected: "< 3h".to_string(),
         });
     }
@@ -190,6 +373,30 @@ fn detect_anomalies(current: &ProductivityMetrics, previous: Option<&Productivit
         });
     }
 
+    // Focus getting more fragmented over the period
+    if let (true, Some(first), Some(last)) = (
+        current.fragmentation.rising,
+        current.fragmentation.days.first(),
+        current.fragmentation.days.last(),
+    ) {
+        anomalies.push(Anomaly {
+            description: "Context switching is rising".to_string(),
+            severity: AnomalySeverity::Warning,
+            value: format!("{:.1} switches/h", last.moving_average),
+            expected: format!("~{:.1} switches/h", first.moving_average),
+        });
+    }
+
+    // Much of the tracked activity outside working hours
+    if off_hours_share(current) > OFF_HOURS_SHARE_WARNING {
+        anomalies.push(Anomaly {
+            description: "Much of the work happened outside working hours".to_string(),
+            severity: AnomalySeverity::Warning,
+            value: format!("{:.0}%", off_hours_share(current) * 100.0),
+            expected: format!("< {:.0}%", OFF_HOURS_SHARE_WARNING * 100.0),
+        });
+    }
+
     // Compare with previous period if available
     if let Some(prev) = previous {
         // Significant change in total time (> 50% increase or decrease)
@@ -240,11 +447,58 @@ fn detect_anomalies(current: &ProductivityMetrics, previous: Option<&Productivit
                 });
             }
         }
+
+        // Categories and switch rate far from the previous period's days
+        anomalies.extend(
+            current
+                .activity
+                .anomalies(&prev.activity, ANOMALY_STD_DEVS)
+                .iter()
+                .map(|anomaly| baseline_anomaly(anomaly, durations)),
+        );
     }
 
     anomalies
 }
 
+/// Describe an anomaly against the previous period's daily values
+fn baseline_anomaly(anomaly: &toki_ai::Anomaly, durations: DurationFormat) -> Anomaly {
+    let direction = if anomaly.is_above() { "above" } else { "below" };
+    let severity = if anomaly.deviations.abs() > 2.0 * ANOMALY_STD_DEVS {
+        AnomalySeverity::Alert
+    } else {
+        AnomalySeverity::Warning
+    };
+    match &anomaly.metric {
+        AnomalyMetric::Category(category) => {
+            let per_day = |seconds: f64| {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let seconds = seconds.round() as u32;
+                format!("{}/day", durations.format(seconds))
+            };
+            Anomaly {
+                description: format!("{category} time is far {direction} usual"),
+                severity,
+                value: per_day(anomaly.value),
+                expected: format!(
+                    "{} \u{b1} {}",
+                    per_day(anomaly.baseline_mean),
+                    per_day(anomaly.baseline_std_dev)
+                ),
+            }
+        }
+        AnomalyMetric::SwitchRate => Anomaly {
+            description: format!("Context switch rate is far {direction} usual"),
+            severity,
+            value: format!("{:.1} switches/h", anomaly.value),
+            expected: format!(
+                "{:.1} \u{b1} {:.1} switches/h",
+                anomaly.baseline_mean, anomaly.baseline_std_dev
+            ),
+        },
+    }
+}
+
 /// Find peak productivity hours
 fn find_peak_hours(metrics: &ProductivityMetrics) -> Vec<(usize, u32)> {
     let mut hours: Vec<(usize, u32)> = metrics.hourly_distribution
@@ -303,16 +557,23 @@ fn generate_suggestions(metrics: &ProductivityMetrics, anomalies: &[Anomaly]) ->
 }
 
 /// Print the summary section
-fn print_summary(metrics: &ProductivityMetrics) {
+fn print_summary(metrics: &ProductivityMetrics, durations: DurationFormat) {
     println!("Summary");
     println!("{}", "\u{2500}".repeat(40));
-    println!("Total time:      {}", format_duration(metrics.total_seconds));
+    println!("Total time:      {}", durations.format(metrics.total_seconds));
     println!("Sessions:        {}", metrics.session_count);
-    println!("Avg session:     {}", format_duration(metrics.avg_session_seconds));
+    println!("Avg session:     {}", durations.format(metrics.avg_session_seconds));
     println!("Projects:        {}", metrics.project_count);
     println!("Tool calls:      {}", metrics.total_tool_calls);
     println!("Prompts:         {}", metrics.total_prompts);
     println!("Context switches:{}", metrics.context_switches);
+    if metrics.activity_seconds > 0 {
+        println!(
+            "Off-hours:       {} ({:.0}%)",
+            durations.format(metrics.off_hours_seconds),
+            off_hours_share(metrics) * 100.0
+        );
+    }
 }
 
 /// Print the anomalies section
@@ -335,7 +596,7 @@ fn print_anomalies(anomalies: &[Anomaly]) {
 }
 
 /// Print the patterns section
-fn print_patterns(metrics: &ProductivityMetrics) {
+fn print_patterns(metrics: &ProductivityMetrics, durations: DurationFormat) {
     println!();
     println!("Patterns");
     println!("{}", "\u{2500}".repeat(40));
@@ -366,11 +627,11 @@ fn print_patterns(metrics: &ProductivityMetrics) {
         println!(
             "Most productive day: {} ({})",
             days[best_day],
-            format_duration(best_day_time)
+            durations.format(best_day_time)
         );
     }
 
-    println!("Longest session: {}", format_duration(metrics.longest_session));
+    println!("Longest session: {}", durations.format(metrics.longest_session));
 }
 
 /// Print the suggestions section
@@ -391,8 +652,10 @@ pub fn handle_insights_command(
     period: &str,
     compare: bool,
     focus: Option<&str>,
+    explain: bool,
 ) -> Result<()> {
     let db = Database::new(None).context("Failed to open database")?;
+    let durations = db.get_settings()?.duration_format;
 
     // Parse current period
     let (start, end) = parse_period(period)?;
@@ -407,6 +670,17 @@ pub fn handle_insights_command(
     );
     println!();
 
+    // App usage comes from tracked activity, so it does not need any sessions
+    if focus == Some("apps") {
+        return print_app_analysis(&db, start, end, end - start, durations);
+    }
+    if focus == Some("unmatched") {
+        return print_unmatched_analysis(&db, start, end, durations);
+    }
+    if focus == Some("patterns") {
+        return print_pattern_analysis(&db, start, end, durations);
+    }
+
     // Collect current metrics
     let current_metrics = collect_metrics(&db, start, end)?;
 
@@ -428,38 +702,43 @@ pub fn handle_insights_command(
     // Handle focus mode
     match focus {
         Some("hours") => {
-            print_hourly_analysis(&current_metrics);
+            print_hourly_analysis(&current_metrics, durations);
             return Ok(());
         }
         Some("sessions") => {
-            print_session_analysis(&current_metrics);
+            print_session_analysis(&current_metrics, durations);
             return Ok(());
         }
         Some("context-switches") => {
-            print_context_switch_analysis(&current_metrics);
+            print_context_switch_analysis(&current_metrics, durations);
             return Ok(());
         }
+        Some("gravity") => {
+            return print_gravity_analysis(&db, start, end, explain);
+        }
         Some(f) => {
-            println!("Unknown focus: {f}. Use: hours, sessions, context-switches");
+            println!(
+                "Unknown focus: {f}. Use: hours, sessions, context-switches, gravity, apps, unmatched, patterns"
+            );
             return Ok(());
         }
         None => {}
     }
 
-    print_summary(&current_metrics);
+    print_summary(&current_metrics, durations);
 
     if compare {
         if let Some(ref prev) = previous_metrics {
             println!();
             println!("vs Previous Period");
             println!("{}", "\u{2500}".repeat(40));
-            print_comparison(&current_metrics, prev);
+            print_comparison(&current_metrics, prev, durations);
         }
     }
 
-    let anomalies = detect_anomalies(&current_metrics, previous_metrics.as_ref());
+    let anomalies = detect_anomalies(&current_metrics, previous_metrics.as_ref(), durations);
     print_anomalies(&anomalies);
-    print_patterns(&current_metrics);
+    print_patterns(&current_metrics, durations);
 
     let suggestions = generate_suggestions(&current_metrics, &anomalies);
     print_suggestions_section(&suggestions);
@@ -468,7 +747,7 @@ pub fn handle_insights_command(
 }
 
 /// Print hourly analysis
-fn print_hourly_analysis(metrics: &ProductivityMetrics) {
+fn print_hourly_analysis(metrics: &ProductivityMetrics, durations: DurationFormat) {
     println!("Hourly Distribution");
     println!("{}", "\u{2500}".repeat(40));
 
@@ -485,18 +764,18 @@ fn print_hourly_analysis(metrics: &ProductivityMetrics) {
                 0
             };
             let bar = "\u{2588}".repeat(bar_len);
-            println!("{:02}:00 {} {}", hour, bar, format_duration(time));
+            println!("{:02}:00 {} {}", hour, bar, durations.format(time));
         }
     }
 }
 
 /// Print session analysis
-fn print_session_analysis(metrics: &ProductivityMetrics) {
+fn print_session_analysis(metrics: &ProductivityMetrics, durations: DurationFormat) {
     println!("Session Analysis");
     println!("{}", "\u{2500}".repeat(40));
     println!("Total sessions:  {}", metrics.session_count);
-    println!("Average length:  {}", format_duration(metrics.avg_session_seconds));
-    println!("Longest session: {}", format_duration(metrics.longest_session));
+    println!("Average length:  {}", durations.format(metrics.avg_session_seconds));
+    println!("Longest session: {}", durations.format(metrics.longest_session));
     println!();
     println!("Sessions per day:");
 
@@ -509,7 +788,7 @@ fn print_session_analysis(metrics: &ProductivityMetrics) {
 }
 
 /// Print context switch analysis
-fn print_context_switch_analysis(metrics: &ProductivityMetrics) {
+fn print_context_switch_analysis(metrics: &ProductivityMetrics, durations: DurationFormat) {
     println!("Context Switch Analysis");
     println!("{}", "\u{2500}".repeat(40));
     println!("Total switches: {}", metrics.context_switches);
@@ -525,10 +804,241 @@ fn print_context_switch_analysis(metrics: &ProductivityMetrics) {
         println!("High context switching can reduce productivity.");
         println!("Consider batching work by project.");
     }
+
+    let trend = &metrics.fragmentation;
+    if !trend.days.is_empty() {
+        println!();
+        println!("Focus health (weighted switches per active hour):");
+        for day in &trend.days {
+            println!(
+                "  {}  {:>5.1}  avg {:>5.1}  {} switches in {}",
+                day.date,
+                day.index,
+                day.moving_average,
+                day.switches,
+                durations.format(day.active_seconds)
+            );
+        }
+        if trend.rising {
+            println!("Fragmentation is rising; protect some blocks of focus time.");
+        }
+    }
+}
+
+/// Print issues ranked by gravity, with each signal's contribution if `explain` is set
+fn print_gravity_analysis(
+    db: &Database,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    explain: bool,
+) -> Result<()> {
+    let mut signals: HashMap<(String, String), IssueSignals> = HashMap::new();
+    for session in db.get_claude_sessions(start, end)? {
+        let touched = session.ended_at.unwrap_or(session.started_at);
+        // A session may link the same issue under several relationships
+        let issues: HashSet<(String, String)> = db
+            .get_session_issues(session.id)?
+            .into_iter()
+            .map(|link| (link.issue_system, link.issue_id))
+            .collect();
+        for issue in issues {
+            let entry = signals.entry(issue).or_default();
+            entry.session_count += 1;
+            entry.last_touched = entry.last_touched.max(Some(touched));
+        }
+    }
+
+    let now = Utc::now();
+    let mut ranked = Vec::new();
+    for ((system, issue_id), mut issue_signals) in signals {
+        let candidate = db.get_issue_candidate(&issue_id, &system)?;
+        if let Some(candidate) = &candidate {
+            issue_signals.assigned = candidate.assignee.is_some();
+            issue_signals.status.clone_from(&candidate.status);
+        }
+        let title = candidate.map(|c| c.title).unwrap_or_default();
+        let breakdown = GravityBreakdown::from_signals(&issue_signals, now);
+        ranked.push((format!("{system}#{issue_id}"), title, breakdown));
+    }
+    ranked.sort_by(|a, b| b.2.gravity().total_cmp(&a.2.gravity()));
+
+    println!("Issue Gravity");
+    println!("{}", "\u{2500}".repeat(40));
+    if ranked.is_empty() {
+        println!("No issues linked to sessions in this period.");
+        return Ok(());
+    }
+
+    for (id, title, breakdown) in ranked.iter().take(MAX_GRAVITY_ISSUES) {
+        println!(
+            "{:.2} {:<6} {id} {title}",
+            breakdown.gravity(),
+            format!("{:?}", breakdown.relevance())
+        );
+        if explain {
+            println!(
+                "     recency {:.2} + frequency {:.2} + assignment {:.2} + status {:.2}",
+                breakdown.recency, breakdown.frequency, breakdown.assignment, breakdown.status
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Print top apps with their share of active time and change since the previous period
+fn print_app_analysis(
+    db: &Database,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    period: Duration,
+    durations: DurationFormat,
+) -> Result<()> {
+    let app_totals = |start, end| -> Result<Vec<(String, u32)>> {
+        let mut activity = SpanAggregator::new();
+        db.for_each_activity_span(start, end, |span| {
+            activity.add(&span);
+            Ok(())
+        })?;
+        Ok(activity.top_applications(usize::MAX))
+    };
+    let current = app_totals(start, end)?;
+    let previous = app_totals(start - period, start)?;
+
+    println!("App Usage");
+    println!("{}", "\u{2500}".repeat(40));
+    if current.is_empty() && previous.is_empty() {
+        println!("No tracked activity in this period.");
+        return Ok(());
+    }
+
+    let app_names = AppDisplayNames::load(db)?;
+    for trend in app_usage_trends(&current, &previous, MAX_TOP_APPS) {
+        let change = match (trend.previous_seconds, trend.seconds) {
+            (0, _) => "new".to_string(),
+            (_, 0) => "not used".to_string(),
+            _ => {
+                let sign = if trend.delta_seconds() < 0 { '-' } else { '+' };
+                let delta = u32::try_from(trend.delta_seconds().unsigned_abs()).unwrap_or(u32::MAX);
+                format!("{sign}{}", durations.format(delta))
+            }
+        };
+        println!(
+            "{:<24} {:>8} {:>4.0}%  {change}",
+            app_names.display_name(&trend.bundle_id),
+            durations.format(trend.seconds),
+            trend.share * 100.0
+        );
+    }
+
+    Ok(())
+}
+
+/// Print how much tracked time has no issue, per project, app, and category
+fn print_unmatched_analysis(
+    db: &Database,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    durations: DurationFormat,
+) -> Result<()> {
+    let spans = db.get_activity_spans(start, end)?;
+    let unmatched = db.get_unmatched_spans(start, end)?;
+
+    println!("Unattributed Time");
+    println!("{}", "\u{2500}".repeat(40));
+    let (overall, projects) = attribution_gaps(&spans, &unmatched);
+    if overall.total_seconds == 0 {
+        println!("No tracked activity in this period.");
+        return Ok(());
+    }
+    println!(
+        "No issue: {} of {} ({:.0}%)",
+        durations.format(overall.unmatched_seconds),
+        durations.format(overall.total_seconds),
+        overall.unmatched_fraction() * 100.0
+    );
+    if overall.unmatched_seconds == 0 {
+        return Ok(());
+    }
+
+    println!("\nBy project:");
+    for (project_id, gap) in projects.iter().filter(|(_, gap)| gap.unmatched_seconds > 0) {
+        let name = match project_id {
+            Some(id) => db
+                .get_project(*id)?
+                .map_or_else(|| "(unknown project)".to_string(), |p| p.name),
+            None => "(no project)".to_string(),
+        };
+        println!(
+            "  {:<22} {:>8} of {:>8} {:>4.0}%",
+            name,
+            durations.format(gap.unmatched_seconds),
+            durations.format(gap.total_seconds),
+            gap.unmatched_fraction() * 100.0
+        );
+    }
+
+    let app_names = AppDisplayNames::load(db)?;
+    println!("\nTop apps without an issue:");
+    let top_apps = InsightsGenerator::top_applications_from_spans(&unmatched, MAX_UNMATCHED_ITEMS);
+    for (app, seconds) in top_apps {
+        let name = app_names.display_name(&app);
+        println!("  {name:<22} {:>8}", durations.format(seconds));
+    }
+
+    println!("\nTop categories without an issue:");
+    for (category, seconds) in unmatched_by_category(&unmatched, MAX_UNMATCHED_ITEMS) {
+        println!("  {category:<22} {:>8}", durations.format(seconds));
+    }
+
+    Ok(())
+}
+
+/// Print time per work pattern (Debugging, Documentation, Meeting, ...), overall and per day
+fn print_pattern_analysis(
+    db: &Database,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    durations: DurationFormat,
+) -> Result<()> {
+    let spans = db.get_work_pattern_spans(start, end)?;
+
+    println!("Work Patterns");
+    println!("{}", "\u{2500}".repeat(40));
+    if spans.is_empty() {
+        println!("No work patterns recorded in this period.");
+        println!("Patterns are stored as spans finish, or for past days by `toki review`.");
+        return Ok(());
+    }
+
+    let totals = work_pattern_totals(&spans);
+    let total_seconds: u32 = totals.iter().map(|(_, seconds)| seconds).sum();
+    for (pattern, seconds) in &totals {
+        println!(
+            "{pattern:<16} {:>8} {:>4.0}%",
+            durations.format(*seconds),
+            f64::from(*seconds) / f64::from(total_seconds.max(1)) * 100.0
+        );
+    }
+
+    println!("\nBy day:");
+    for (day, patterns) in work_pattern_days(&spans) {
+        let breakdown: Vec<String> = patterns
+            .iter()
+            .map(|(pattern, seconds)| format!("{pattern} {}", durations.format(*seconds)))
+            .collect();
+        println!("  {day}  {}", breakdown.join(", "));
+    }
+
+    Ok(())
 }
 
 /// Print comparison between current and previous period
-fn print_comparison(current: &ProductivityMetrics, previous: &ProductivityMetrics) {
+fn print_comparison(
+    current: &ProductivityMetrics,
+    previous: &ProductivityMetrics,
+    durations: DurationFormat,
+) {
     let time_change = if previous.total_seconds > 0 {
         ((f64::from(current.total_seconds) - f64::from(previous.total_seconds))
             / f64::from(previous.total_seconds))
@@ -548,8 +1058,8 @@ fn print_comparison(current: &ProductivityMetrics, previous: &ProductivityMetric
     println!(
         "Time:     {:+.0}% ({} vs {})",
         time_change,
-        format_duration(current.total_seconds),
-        format_duration(previous.total_seconds)
+        durations.format(current.total_seconds),
+        durations.format(previous.total_seconds)
     );
     println!(
         "Sessions: {:+.0}% ({} vs {})",
@@ -559,22 +1069,190 @@ fn print_comparison(current: &ProductivityMetrics, previous: &ProductivityMetric
     );
 }
 
-/// Format duration in human-readable form
-fn format_duration(seconds: u32) -> String {
-    let hours = seconds / 3600;
-    let minutes = (seconds % 3600) / 60;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(apps: &[(&str, u32)]) -> Vec<(String, u32)> {
+        apps.iter()
+            .map(|(app, seconds)| ((*app).to_string(), *seconds))
+            .collect()
+    }
 
-    if hours > 0 {
-        if minutes > 0 {
-            format!("{hours}h {minutes}m")
-        } else {
-            format!("{hours}h")
+    #[test]
+    fn test_app_trends_compute_share_and_delta() {
+        let current = usage(&[("com.microsoft.VSCode", 3600), ("com.apple.Safari", 1200)]);
+        let previous = usage(&[("com.apple.Safari", 2400), ("com.microsoft.VSCode", 1800)]);
+
+        let trends = app_usage_trends(&current, &previous, 10);
+
+        assert_eq!(trends.len(), 2);
+        assert_eq!(trends[0].bundle_id, "com.microsoft.VSCode");
+        assert!((trends[0].share - 0.75).abs() < f64::EPSILON);
+        assert_eq!(trends[0].delta_seconds(), 1800);
+        assert_eq!(trends[1].bundle_id, "com.apple.Safari");
+        assert_eq!(trends[1].delta_seconds(), -1200);
+    }
+
+    fn span(
+        app: &str,
+        category: &str,
+        project_id: Option<uuid::Uuid>,
+        work_item_id: Option<uuid::Uuid>,
+        seconds: u32,
+    ) -> ActivitySpan {
+        let start = Utc::now() - Duration::hours(2);
+        let mut span = ActivitySpan::new(
+            app.to_string(),
+            category.to_string(),
+            start,
+            project_id,
+            work_item_id,
+            None,
+        );
+        span.end_time = Some(start + Duration::seconds(i64::from(seconds)));
+        span.duration_seconds = seconds;
+        span
+    }
+
+    #[test]
+    fn test_unmatched_fraction_is_null_work_item_time_over_total() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+        let toki = db.get_or_create_project("toki", "/code/toki").unwrap().id;
+        let other = db.get_or_create_project("other", "/code/other").unwrap().id;
+        let work_item = toki_storage::WorkItem::new("42".to_string(), "github".to_string());
+        db.upsert_work_item(&work_item).unwrap();
+        let issue = Some(work_item.id);
+        let spans = [
+            span("com.microsoft.VSCode", "Coding", Some(toki), issue, 3600),
+            span("com.microsoft.VSCode", "Coding", Some(toki), None, 1200),
+            span("com.google.Chrome", "Browser", Some(other), None, 600),
+            span("com.google.Chrome", "Browser", None, issue, 600),
+        ];
+        for span in &spans {
+            db.create_activity_span(span).unwrap();
         }
-    } else if minutes > 0 {
-        format!("{minutes}m")
-    } else if seconds > 0 {
-        format!("{seconds}s")
-    } else {
-        "0m".to_string()
+
+        let (start, end) = (Utc::now() - Duration::days(1), Utc::now());
+        let all = db.get_activity_spans(start, end).unwrap();
+        let unmatched = db.get_unmatched_spans(start, end).unwrap();
+        assert_eq!(unmatched.len(), 2);
+        assert!(unmatched.iter().all(|s| s.work_item_id.is_none()));
+
+        let (overall, projects) = attribution_gaps(&all, &unmatched);
+        let null_seconds: u32 = spans
+            .iter()
+            .filter(|s| s.work_item_id.is_none())
+            .map(|s| s.duration_seconds)
+            .sum();
+        assert_eq!(overall.total_seconds, 6000);
+        assert_eq!(overall.unmatched_seconds, null_seconds);
+        assert!((overall.unmatched_fraction() - 1800.0 / 6000.0).abs() < f64::EPSILON);
+
+        assert_eq!(projects[0].0, Some(toki));
+        assert!((projects[0].1.unmatched_fraction() - 1200.0 / 4800.0).abs() < f64::EPSILON);
+        assert_eq!(
+            unmatched_by_category(&unmatched, 5),
+            vec![("Coding".to_string(), 1200), ("Browser".to_string(), 600)]
+        );
+    }
+
+    #[test]
+    fn test_pattern_totals_match_stored_span_patterns() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+        let coding = |seconds| span("com.microsoft.VSCode", "Coding", None, None, seconds);
+        let stored = [
+            (coding(3600), "Debugging"),
+            (span("us.zoom.xos", "Meeting", None, None, 1800), "Meeting"),
+            (coding(900), "Debugging"),
+            (coding(600), "Documentation"),
+        ];
+        for (span, pattern) in &stored {
+            db.create_activity_span(span).unwrap();
+            db.set_activity_span_work_pattern(span.id, pattern).unwrap();
+        }
+        // Spans without a detected pattern are left out
+        db.create_activity_span(&span("com.apple.Safari", "Browser", None, None, 300))
+            .unwrap();
+
+        let (start, end) = (Utc::now() - Duration::days(1), Utc::now());
+        let spans = db.get_work_pattern_spans(start, end).unwrap();
+        assert_eq!(spans.len(), stored.len());
+        for (span, pattern) in &spans {
+            let (_, expected) = stored.iter().find(|(s, _)| s.id == span.id).unwrap();
+            assert_eq!(pattern, expected);
+        }
+
+        let totals = work_pattern_totals(&spans);
+        assert_eq!(
+            totals,
+            vec![
+                ("Debugging".to_string(), 4500),
+                ("Meeting".to_string(), 1800),
+                ("Documentation".to_string(), 600),
+            ]
+        );
+        let days = work_pattern_days(&spans);
+        let day_sum: u32 = days.values().flatten().map(|(_, seconds)| seconds).sum();
+        let total: u32 = totals.iter().map(|(_, seconds)| seconds).sum();
+        assert_eq!(day_sum, total);
+    }
+
+    #[test]
+    fn test_app_trends_include_new_and_dropped_apps() {
+        let current = usage(&[("com.microsoft.VSCode", 3000), ("com.figma.Desktop", 1000)]);
+        let previous = usage(&[
+            ("com.microsoft.VSCode", 3000),
+            ("com.tinyspeck.slackmacgap", 900),
+        ]);
+
+        let trends = app_usage_trends(&current, &previous, 10);
+
+        let figma = trends
+            .iter()
+            .find(|t| t.bundle_id == "com.figma.Desktop")
+            .unwrap();
+        assert_eq!(figma.previous_seconds, 0);
+        assert_eq!(figma.delta_seconds(), 1000);
+
+        let slack = trends.last().unwrap();
+        assert_eq!(slack.bundle_id, "com.tinyspeck.slackmacgap");
+        assert_eq!(slack.seconds, 0);
+        assert_eq!(slack.delta_seconds(), -900);
+        assert!(slack.share.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_baseline_anomaly_shows_daily_values_and_direction() {
+        let anomaly = baseline_anomaly(
+            &toki_ai::Anomaly {
+                metric: AnomalyMetric::Category("Meetings".to_string()),
+                value: 4.0 * 3600.0,
+                baseline_mean: 3600.0,
+                baseline_std_dev: 600.0,
+                deviations: 12.0,
+            },
+            DurationFormat::HoursMinutes,
+        );
+        assert_eq!(anomaly.description, "Meetings time is far above usual");
+        assert!(matches!(anomaly.severity, AnomalySeverity::Alert));
+        assert_eq!(anomaly.value, "4h/day");
+        assert_eq!(anomaly.expected, "1h/day \u{b1} 10m/day");
+
+        let rate = baseline_anomaly(
+            &toki_ai::Anomaly {
+                metric: AnomalyMetric::SwitchRate,
+                value: 1.0,
+                baseline_mean: 6.0,
+                baseline_std_dev: 2.0,
+                deviations: -2.5,
+            },
+            DurationFormat::HoursMinutes,
+        );
+        assert_eq!(rate.description, "Context switch rate is far below usual");
+        assert!(matches!(rate.severity, AnomalySeverity::Warning));
+        assert_eq!(rate.value, "1.0 switches/h");
     }
 }
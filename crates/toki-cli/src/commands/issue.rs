@@ -0,0 +1,403 @@
+//! Issue inspection commands
+//!
+//! Works on issue candidates synced via `toki issue-sync`.
+
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::Subcommand;
+use toki_ai::{find_duplicate_issues, DUPLICATE_SIMILARITY_THRESHOLD};
+use toki_storage::{ActivitySpan, Database, DurationFormat, IssueCandidate, TimeBlock};
+use uuid::Uuid;
+
+use super::helpers::truncate_str;
+
+#[derive(Subcommand, Debug)]
+pub enum IssueAction {
+    /// List possible duplicate issues (near-identical embeddings)
+    Duplicates {
+        /// Minimum cosine similarity (0.0-1.0) to report a pair
+        #[arg(long, default_value_t = DUPLICATE_SIMILARITY_THRESHOLD)]
+        threshold: f32,
+    },
+    /// Show issue details with logged time
+    Show {
+        /// Issue ID (e.g., 42, #42, PROJ-42)
+        id: String,
+        /// Issue tracking system, when the ID exists in several (plane, notion)
+        #[arg(long)]
+        system: Option<String>,
+    },
+}
+
+/// Handle issue commands
+pub fn handle_issue_command(action: IssueAction) -> Result<()> {
+    match action {
+        IssueAction::Duplicates { threshold } => show_duplicates(threshold),
+        IssueAction::Show { id, system } => show_issue(&id, system.as_deref()),
+    }
+}
+
+/// Report ranked pairs of possible duplicate issues
+fn show_duplicates(threshold: f32) -> Result<()> {
+    if !(0.0..=1.0).contains(&threshold) {
+        anyhow::bail!("Threshold must be between 0.0 and 1.0");
+    }
+
+    let db = Database::new(None).context("Failed to open database")?;
+    let pairs = find_duplicate_issues(&db, threshold)?;
+
+    if pairs.is_empty() {
+        println!("No possible duplicates found (similarity >= {threshold:.2}).");
+        return Ok(());
+    }
+
+    println!("Possible duplicate issues ({}):\n", pairs.len());
+    for pair in &pairs {
+        println!("  {:.1}% similar", pair.similarity * 100.0);
+        println!(
+            "    #{} - {}",
+            pair.first.external_id,
+            truncate_str(&pair.first.title, 60)
+        );
+        println!(
+            "    #{} - {}",
+            pair.second.external_id,
+            truncate_str(&pair.second.title, 60)
+        );
+        println!();
+    }
+
+    println!("Duplicates are not merged automatically; close or merge them in your PM system.");
+    Ok(())
+}
+
+/// Time attributed to a single issue
+#[derive(Debug, Default, PartialEq, Eq)]
+struct IssueTime {
+    span_seconds: u32,
+    span_count: usize,
+    block_seconds: u32,
+    block_count: usize,
+    last_activity: Option<DateTime<Utc>>,
+    first_activity: Option<DateTime<Utc>>,
+    days_worked: u32,
+}
+
+impl IssueTime {
+    /// Confirmed time if any blocks were reviewed, otherwise tracked span time
+    fn logged_seconds(&self) -> u32 {
+        if self.block_count > 0 {
+            self.block_seconds
+        } else {
+            self.span_seconds
+        }
+    }
+}
+
+/// Show details and logged time for one issue
+fn show_issue(id: &str, system: Option<&str>) -> Result<()> {
+    let db = Database::new(None).context("Failed to open database")?;
+
+    let issue = resolve_issue(&db, id, system)?;
+    let spans = db.get_activity_spans_by_work_item(issue.id)?;
+    let blocks = db.get_confirmed_time_blocks()?;
+    let mut time = aggregate_issue_time(issue.id, &spans, &blocks);
+    let stats = db.issue_time_stats(&issue.external_id, DateTime::<Utc>::MIN_UTC, Utc::now())?;
+    time.first_activity = stats.first_activity;
+    time.days_worked = stats.days_worked;
+
+    let url = issue_url(&db, &issue);
+    let format = db.get_settings()?.duration_format;
+    print!(
+        "{}",
+        format_issue_details(&issue, &time, url.as_deref(), format)
+    );
+    Ok(())
+}
+
+/// Resolve a user-supplied issue ID to a synced candidate
+///
+/// Accepts a leading '#', any letter case, and a bare number for prefixed IDs.
+pub(crate) fn resolve_issue(
+    db: &Database,
+    id: &str,
+    system: Option<&str>,
+) -> Result<IssueCandidate> {
+    let id = id.trim().trim_start_matches('#');
+    if id.is_empty() {
+        anyhow::bail!("Issue ID must not be empty");
+    }
+
+    let mut matches = db.find_issue_candidates_by_external_id(id)?;
+    if let Some(system) = system {
+        matches.retain(|c| c.external_system.eq_ignore_ascii_case(system));
+    }
+
+    // An exact (case-insensitive) match wins over suffix matches
+    if matches.len() > 1 {
+        let exact: Vec<_> = matches
+            .iter()
+            .filter(|c| c.external_id.eq_ignore_ascii_case(id))
+            .cloned()
+            .collect();
+        if !exact.is_empty() {
+            matches = exact;
+        }
+    }
+
+    match matches.len() {
+        0 => anyhow::bail!("Issue not found: {id}. Run 'toki issue-sync' to sync issues first"),
+        1 => Ok(matches.remove(0)),
+        _ => {
+            let options: Vec<String> = matches
+                .iter()
+                .map(|c| format!("{} ({})", c.external_id, c.external_system))
+                .collect();
+            anyhow::bail!(
+                "Issue ID '{id}' is ambiguous: {}. Use --system to pick one",
+                options.join(", ")
+            )
+        }
+    }
+}
+
+/// Sum the span and confirmed block time attributed to an issue
+fn aggregate_issue_time(issue_id: Uuid, spans: &[ActivitySpan], blocks: &[TimeBlock]) -> IssueTime {
+    let mut time = IssueTime::default();
+
+    for span in spans
+        .iter()
+        .filter(|s| s.all_work_item_ids().contains(&issue_id))
+    {
+        time.span_seconds += span.duration_seconds;
+        time.span_count += 1;
+        let last = span.end_time.unwrap_or(span.start_time);
+        time.last_activity = time.last_activity.max(Some(last));
+    }
+
+    for block in blocks
+        .iter()
+        .filter(|b| b.confirmed && b.work_item_ids.contains(&issue_id))
+    {
+        let seconds = (block.end_time - block.start_time).num_seconds().max(0);
+        time.block_seconds += u32::try_from(seconds).unwrap_or(u32::MAX);
+        time.block_count += 1;
+        time.last_activity = time.last_activity.max(Some(block.end_time));
+    }
+
+    time
+}
+
+/// Browser URL for an issue, when it can be derived from synced data
+pub(crate) fn issue_url(db: &Database, issue: &IssueCandidate) -> Option<String> {
+    match issue.external_system.as_str() {
+        "notion" => issue
+            .source_page_id
+            .as_ref()
+            .map(|page_id| format!("https://notion.so/{}", page_id.replace('-', ""))),
+        "plane" => {
+            let config = db.get_integration_config("plane").ok().flatten()?;
+            let workspace = config.workspace_slug?;
+            // Plane Cloud serves the API and the web app from different hosts
+            let base = config
+                .api_url
+                .trim_end_matches('/')
+                .replace("://api.plane.so", "://app.plane.so");
+            Some(format!("{base}/{workspace}/browse/{}/", issue.external_id))
+        }
+        _ => None,
+    }
+}
+
+fn format_issue_details(
+    issue: &IssueCandidate,
+    time: &IssueTime,
+    url: Option<&str>,
+    format: DurationFormat,
+) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "#{} - {}", issue.external_id, issue.title);
+    let _ = writeln!(out, "  System:        {}", issue.external_system);
+    let _ = writeln!(out, "  Status:        {}", issue.status);
+    if !issue.labels.is_empty() {
+        let _ = writeln!(out, "  Labels:        {}", issue.labels.join(", "));
+    }
+    if let Some(assignee) = &issue.assignee {
+        let _ = writeln!(out, "  Assignee:      {assignee}");
+    }
+    if let Some(url) = url {
+        let _ = writeln!(out, "  URL:           {url}");
+    }
+
+    let _ = writeln!(out, "\nLogged time:");
+    let _ = writeln!(
+        out,
+        "  Tracked:       {} ({} spans)",
+        format.format(time.span_seconds),
+        time.span_count
+    );
+    let _ = writeln!(
+        out,
+        "  Confirmed:     {} ({} blocks)",
+        format.format(time.block_seconds),
+        time.block_count
+    );
+
+    if let Some(estimate) = issue.estimated_seconds {
+        let actual = time.logged_seconds();
+        let source = issue.estimate_source.as_deref().unwrap_or("unknown");
+        let _ = writeln!(
+            out,
+            "  Estimate:      {} ({source})",
+            format.format(estimate)
+        );
+        if estimate > 0 {
+            let _ = writeln!(
+                out,
+                "  Actual:        {} ({:.0}% of estimate)",
+                format.format(actual),
+                f64::from(actual) / f64::from(estimate) * 100.0
+            );
+        }
+    }
+
+    if let Some(first) = time.first_activity {
+        let _ = writeln!(
+            out,
+            "  Worked on:     {} days since {}",
+            time.days_worked,
+            first.format("%Y-%m-%d")
+        );
+    }
+    match time.last_activity {
+        Some(last) => {
+            let _ = writeln!(out, "  Last activity: {}", last.format("%Y-%m-%d %H:%M"));
+        }
+        None => {
+            let _ = writeln!(out, "  Last activity: none recorded");
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn test_db() -> (tempfile::TempDir, Database) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+        (temp_dir, db)
+    }
+
+    fn span_for(work_item_id: Option<Uuid>, start: DateTime<Utc>, seconds: u32) -> ActivitySpan {
+        let mut span = ActivitySpan::new(
+            "com.microsoft.VSCode".to_string(),
+            "Coding".to_string(),
+            start,
+            None,
+            work_item_id,
+            None,
+        );
+        span.end_time = Some(start + Duration::seconds(i64::from(seconds)));
+        span.duration_seconds = seconds;
+        span
+    }
+
+    #[test]
+    fn test_logged_time_matches_attributed_spans() {
+        let issue_id = Uuid::new_v4();
+        let start = Utc::now() - Duration::hours(5);
+
+        let mut secondary = span_for(None, start + Duration::hours(2), 600);
+        secondary.add_work_item(issue_id);
+        let spans = vec![
+            span_for(Some(issue_id), start, 1800),
+            span_for(Some(Uuid::new_v4()), start + Duration::hours(1), 900),
+            secondary,
+        ];
+
+        let mut block = TimeBlock::manual(start, start + Duration::hours(1), "API".to_string());
+        block.work_item_ids.push(issue_id);
+        let mut unconfirmed = block.clone();
+        unconfirmed.confirmed = false;
+
+        let time = aggregate_issue_time(issue_id, &spans, &[block, unconfirmed]);
+        assert_eq!(time.span_seconds, 2400);
+        assert_eq!(time.span_count, 2);
+        assert_eq!(time.block_seconds, 3600);
+        assert_eq!(time.block_count, 1);
+        assert_eq!(time.logged_seconds(), 3600);
+        assert_eq!(
+            time.last_activity,
+            Some(start + Duration::hours(2) + Duration::seconds(600))
+        );
+    }
+
+    #[test]
+    fn test_status_and_labels_displayed() {
+        let mut issue = IssueCandidate::new(
+            Uuid::new_v4(),
+            "PROJ-42".to_string(),
+            "plane".to_string(),
+            "Fix login".to_string(),
+        );
+        issue.status = "in_progress".to_string();
+        issue.labels = vec!["bug".to_string(), "auth".to_string()];
+        issue.estimated_seconds = Some(7200);
+
+        let time = IssueTime {
+            span_seconds: 3600,
+            span_count: 2,
+            ..IssueTime::default()
+        };
+        let details = format_issue_details(
+            &issue,
+            &time,
+            Some("https://example.com/PROJ-42"),
+            DurationFormat::HoursMinutes,
+        );
+        assert!(details.contains("#PROJ-42 - Fix login"));
+        assert!(details.contains("Status:        in_progress"));
+        assert!(details.contains("Labels:        bug, auth"));
+        assert!(details.contains("URL:           https://example.com/PROJ-42"));
+        assert!(details.contains("(50% of estimate)"));
+        assert!(details.contains("Last activity: none recorded"));
+    }
+
+    #[test]
+    fn test_issue_id_resolved_tolerantly() {
+        let (_dir, db) = test_db();
+        let project_id = db.get_or_create_project("toki", "/tmp/toki").unwrap().id;
+        let plane = IssueCandidate::new(
+            project_id,
+            "PROJ-42".to_string(),
+            "plane".to_string(),
+            "Fix login".to_string(),
+        );
+        let notion = IssueCandidate::new(
+            project_id,
+            "42".to_string(),
+            "notion".to_string(),
+            "Write docs".to_string(),
+        );
+        db.upsert_issue_candidate(&plane).unwrap();
+        db.upsert_issue_candidate(&notion).unwrap();
+
+        assert_eq!(resolve_issue(&db, "proj-42", None).unwrap().id, plane.id);
+        assert_eq!(resolve_issue(&db, "#42", None).unwrap().id, notion.id);
+        assert_eq!(
+            resolve_issue(&db, "42", Some("plane")).unwrap().id,
+            plane.id
+        );
+        assert!(resolve_issue(&db, "43", None).is_err());
+        // LIKE wildcards in the ID are matched literally
+        assert!(resolve_issue(&db, "_2", None).is_err());
+        assert!(resolve_issue(&db, "%", None).is_err());
+    }
+}
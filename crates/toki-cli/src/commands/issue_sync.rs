@@ -1,39 +1,93 @@
 /// Issue sync command handler - sync issues from PM systems for AI matching
 use anyhow::Result;
 use std::sync::Arc;
-use toki_ai::IssueSyncService;
-use toki_integrations::{NotionClient, PlaneClient};
-use toki_storage::Database;
+use toki_ai::{
+    find_duplicate_issues, find_issue_clusters, IssueCluster, IssueSyncService, SyncStats,
+    CLUSTER_SIMILARITY_THRESHOLD, DUPLICATE_SIMILARITY_THRESHOLD, MAX_REPORTED_CLUSTERS,
+};
+use toki_integrations::{NotionClient, PlaneClient, TrelloClient};
+use toki_storage::{Database, Project};
 
-pub async fn handle_issue_sync_command(force: bool) -> Result<()> {
+/// Notion database ids are accepted with or without dashes
+fn normalize_database_id(id: &str) -> String {
+    id.trim().replace('-', "").to_lowercase()
+}
+
+/// Projects linked to the Notion database named by `database`
+///
+/// `database` is a database id or the name of the project linked to it.
+fn select_database_projects(linked_projects: &[Project], database: &str) -> Result<Vec<Project>> {
+    let wanted = normalize_database_id(database);
+    let notion_projects = || {
+        linked_projects
+            .iter()
+            .filter(|p| p.pm_system.as_deref() == Some("notion"))
+    };
+
+    let selected: Vec<Project> = notion_projects()
+        .filter(|p| {
+            p.name.eq_ignore_ascii_case(database.trim())
+                || p.pm_project_id
+                    .as_deref()
+                    .is_some_and(|id| normalize_database_id(id) == wanted)
+        })
+        .cloned()
+        .collect();
+
+    if selected.is_empty() {
+        let configured: Vec<String> = notion_projects()
+            .map(|p| format!("{} ({})", p.name, p.pm_project_id.as_deref().unwrap_or("?")))
+            .collect();
+        if configured.is_empty() {
+            anyhow::bail!(
+                "Notion database not configured: {database}. No project is linked to a Notion database."
+            );
+        }
+        anyhow::bail!(
+            "Notion database not configured: {database}. Linked databases: {}",
+            configured.join(", ")
+        );
+    }
+    Ok(selected)
+}
+
+pub async fn handle_issue_sync_command(
+    force: bool,
+    duplicates: bool,
+    database: Option<String>,
+    show_clusters: bool,
+    verbose: bool,
+) -> Result<()> {
     let db = Arc::new(Database::new(None)?);
 
     // Check if we have any linked projects
-    let linked_projects = db.get_projects_with_pm_link()?;
+    let mut linked_projects = db.get_projects_with_pm_link()?;
     if linked_projects.is_empty() {
         println!("No projects linked to any PM system.");
         println!("\nLink a project first:");
         println!("  toki project link --project <name> --plane-project <IDENTIFIER>");
         println!("  toki project link --project <name> --notion-database <ID>");
+        println!("  toki project link --project <name> --trello-board <ID>");
         return Ok(());
     }
 
+    // Scope the sync to one Notion database; every other project is skipped
+    if let Some(database) = &database {
+        linked_projects = select_database_projects(&linked_projects, database)?;
+    }
+
     // Count projects by PM system
-    let plane_count = linked_projects
-        .iter()
-        .filter(|p| p.pm_system.as_deref() == Some("plane"))
-        .count();
-    let notion_count = linked_projects
-        .iter()
-        .filter(|p| p.pm_system.as_deref() == Some("notion"))
-        .count();
+    let plane_count = count_linked(&linked_projects, "plane");
+    let notion_count = count_linked(&linked_projects, "notion");
+    let trello_count = count_linked(&linked_projects, "trello");
 
     println!("Syncing issues from PM systems...");
     println!(
-        "  Linked projects: {} (Plane: {}, Notion: {})",
+        "  Linked projects: {} (Plane: {}, Notion: {}, Trello: {})",
         linked_projects.len(),
         plane_count,
-        notion_count
+        notion_count,
+        trello_count
     );
 
     // Initialize clients based on what's configured and needed
@@ -65,7 +119,12 @@ pub async fn handle_issue_sync_command(force: bool) -> Result<()> {
                 None
             } else {
                 println!("  Notion: configured");
-                Some(NotionClient::new(config.api_key.clone())?)
+                let client = NotionClient::new(config.api_key.clone())?;
+                client.preload_page_ids(db.get_notion_page_id_map()?).await;
+                if database.is_some() {
+                    verify_databases_accessible(&client, &linked_projects).await?;
+                }
+                Some(client)
             }
         } else {
             println!("  Warning: Notion not configured");
@@ -75,14 +134,125 @@ pub async fn handle_issue_sync_command(force: bool) -> Result<()> {
         None
     };
 
+    let trello_client = if trello_count > 0 {
+        trello_client(&db)?
+    } else {
+        None
+    };
+
     // Create sync service
-    let sync_service = IssueSyncService::new(db.clone())?;
+    let sync_service = IssueSyncService::new(db.clone(), db.get_settings()?.embeddings_enabled)?;
 
-    // Sync all linked projects (both Plane and Notion)
+    // Sync the selected linked projects (Plane, Notion and Trello)
     let stats = sync_service
-        .sync_all_linked_projects_multi(plane_client.as_ref(), notion_client.as_ref())
+        .sync_linked_projects(
+            &linked_projects,
+            plane_client.as_ref(),
+            notion_client.as_ref(),
+            trello_client.as_ref(),
+        )
         .await?;
 
+    print_sync_stats(&stats);
+
+    let rebuild =
+        |refresh_hours, force| sync_service.rebuild_embeddings_if_stale(refresh_hours, force);
+    if let Some(message) = rebuild_embeddings(&db, rebuild, force, verbose)? {
+        println!("\n{message}");
+    }
+
+    // Optional post-sync pass: flag near-identical issues (never merged automatically)
+    if duplicates {
+        let pairs = find_duplicate_issues(&db, DUPLICATE_SIMILARITY_THRESHOLD)?;
+        if pairs.is_empty() {
+            println!("\nNo possible duplicate issues found.");
+        } else {
+            println!("\nPossible duplicate issues: {}", pairs.len());
+            println!("  Run 'toki issue duplicates' to review them.");
+        }
+    }
+
+    // Compares every pair of issues, so only on request
+    if show_clusters {
+        let clusters =
+            find_issue_clusters(&db, CLUSTER_SIMILARITY_THRESHOLD, MAX_REPORTED_CLUSTERS)?;
+        print_clusters(&clusters);
+    }
+
+    Ok(())
+}
+
+/// Number of projects linked to a PM system
+fn count_linked(projects: &[Project], system: &str) -> usize {
+    projects
+        .iter()
+        .filter(|p| p.pm_system.as_deref() == Some(system))
+        .count()
+}
+
+/// Trello client for the sync, or `None` with a warning when Trello is not set up
+fn trello_client(db: &Database) -> Result<Option<TrelloClient>> {
+    let Some(config) = db.get_integration_config("trello")? else {
+        println!("  Warning: Trello not configured");
+        return Ok(None);
+    };
+    match super::helpers::trello_client(&config) {
+        Ok(client) => {
+            println!("  Trello: configured");
+            Ok(Some(client))
+        }
+        Err(e) => {
+            println!("  Warning: {e}");
+            Ok(None)
+        }
+    }
+}
+
+/// Recompute missing embeddings, unless a recent build makes it redundant
+///
+/// `rebuild` is called with the configured refresh interval and `force`, and returns
+/// `None` when it skipped the build. Returns the line to report, if any; a skipped
+/// rebuild is only reported when `verbose`.
+fn rebuild_embeddings(
+    db: &Database,
+    rebuild: impl FnOnce(u32, bool) -> Result<Option<usize>>,
+    force: bool,
+    verbose: bool,
+) -> Result<Option<String>> {
+    let refresh_hours = db.get_settings()?.embedding_refresh_hours;
+    Ok(match rebuild(refresh_hours, force)? {
+        Some(computed) => Some(format!("Recomputed missing embeddings: {computed}")),
+        None if verbose => Some(format!(
+            "Embeddings built within the last {refresh_hours}h; skipping rebuild (use --force)"
+        )),
+        None => None,
+    })
+}
+
+fn print_clusters(clusters: &[IssueCluster]) {
+    if clusters.is_empty() {
+        println!("\nNo clusters of similar issues found.");
+        println!(
+            "  Embeddings keep every issue apart at {CLUSTER_SIMILARITY_THRESHOLD:.2} similarity."
+        );
+        return;
+    }
+
+    println!("\nSimilar issue clusters:");
+    for (i, cluster) in clusters.iter().enumerate() {
+        println!(
+            "  {}. {} issues (similarity >= {:.2})",
+            i + 1,
+            cluster.members.len(),
+            cluster.min_similarity
+        );
+        for issue in &cluster.members {
+            println!("     {} {}", issue.external_id, issue.title);
+        }
+    }
+}
+
+fn print_sync_stats(stats: &SyncStats) {
     println!("\nSync complete:");
     println!("  Issues synced: {}", stats.issues_synced);
     println!("  Issues updated: {}", stats.issues_updated);
@@ -94,13 +264,108 @@ pub async fn handle_issue_sync_command(force: bool) -> Result<()> {
             println!("  - {err}");
         }
     }
+}
 
-    // If force, recompute missing embeddings
-    if force {
-        println!("\nRecomputing missing embeddings...");
-        let computed = sync_service.recompute_missing_embeddings()?;
-        println!("  Computed: {computed} embeddings");
+/// Fail early if a selected Notion database can't be read with the configured key
+async fn verify_databases_accessible(client: &NotionClient, projects: &[Project]) -> Result<()> {
+    for database_id in projects.iter().filter_map(|p| p.pm_project_id.as_deref()) {
+        client.get_database(database_id).await.map_err(|e| {
+            anyhow::anyhow!(
+                "Notion database {database_id} is not accessible: {e}\n\
+                 Make sure your integration has access to the database \
+                 (Open database -> ... menu -> Add connections)"
+            )
+        })?;
     }
-
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use toki_ai::embedding_build_due;
+
+    fn linked(name: &str, system: &str, pm_project_id: &str) -> Project {
+        let mut project = Project::new(name.to_string(), format!("/work/{name}"));
+        project.pm_system = Some(system.to_string());
+        project.pm_project_id = Some(pm_project_id.to_string());
+        project
+    }
+
+    fn projects() -> Vec<Project> {
+        vec![
+            linked("client-a", "notion", "1a2b3c4d5e6f47a8b9c0d1e2f3a4b5c6"),
+            linked("client-b", "notion", "ffffeeee-dddd-cccc-bbbb-aaaa99998888"),
+            linked("toki", "plane", "TOKI"),
+        ]
+    }
+
+    fn names(projects: &[Project]) -> Vec<&str> {
+        projects.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    /// Stand-in for `IssueSyncService::rebuild_embeddings_if_stale` that embeds nothing
+    fn rebuild_if_due(db: &Database, refresh_hours: u32, force: bool) -> Result<Option<usize>> {
+        let last_build = db.get_last_embedding_build()?;
+        if !force && !embedding_build_due(last_build, Utc::now(), refresh_hours) {
+            return Ok(None);
+        }
+        db.record_embedding_build(Utc::now(), 3)?;
+        Ok(Some(3))
+    }
+
+    #[test]
+    fn test_skipped_rebuild_reported_only_when_verbose() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+        let rebuild = |hours, force| rebuild_if_due(&db, hours, force);
+
+        // Never built: due, so the rebuild runs and is always reported
+        let message = rebuild_embeddings(&db, rebuild, false, false).unwrap();
+        assert_eq!(message.as_deref(), Some("Recomputed missing embeddings: 3"));
+
+        // Just built: not due, so the skip stays quiet unless verbose
+        assert_eq!(
+            rebuild_embeddings(&db, rebuild, false, false).unwrap(),
+            None
+        );
+        let message = rebuild_embeddings(&db, rebuild, false, true)
+            .unwrap()
+            .unwrap();
+        assert!(message.contains("skipping rebuild"), "{message}");
+
+        // --force rebuilds even when not due
+        let message = rebuild_embeddings(&db, rebuild, true, false).unwrap();
+        assert_eq!(message.as_deref(), Some("Recomputed missing embeddings: 3"));
+    }
+
+    #[test]
+    fn test_database_option_selects_only_that_database() {
+        let linked_projects = projects();
+
+        let by_dashed_id =
+            select_database_projects(&linked_projects, "1a2b3c4d-5e6f-47a8-b9c0-d1e2f3a4b5c6")
+                .unwrap();
+        assert_eq!(names(&by_dashed_id), ["client-a"]);
+
+        let by_plain_id =
+            select_database_projects(&linked_projects, "FFFFEEEEDDDDCCCCBBBBAAAA99998888").unwrap();
+        assert_eq!(names(&by_plain_id), ["client-b"]);
+
+        let by_alias = select_database_projects(&linked_projects, "Client-B").unwrap();
+        assert_eq!(names(&by_alias), ["client-b"]);
+    }
+
+    #[test]
+    fn test_unknown_or_non_notion_database_rejected() {
+        let linked_projects = projects();
+
+        let error = select_database_projects(&linked_projects, "0000").unwrap_err();
+        assert!(error.to_string().contains("client-a"), "{error}");
+
+        // A Plane project is not a Notion database, even by name
+        assert!(select_database_projects(&linked_projects, "toki").is_err());
+        assert!(select_database_projects(&linked_projects, "TOKI").is_err());
+    }
+}
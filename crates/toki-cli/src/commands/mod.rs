@@ -1,10 +1,15 @@
+pub mod block;
+pub mod browser_host;
 pub mod config;
 pub mod daemon;
 pub mod data;
 pub mod estimate;
+pub mod gantt;
 pub mod helpers;
+pub mod ical;
 pub mod init;
 pub mod insights;
+pub mod issue;
 pub mod issue_sync;
 pub mod learn;
 pub mod next;
@@ -20,4 +25,6 @@ pub mod standup;
 pub mod suggest;
 pub mod summary;
 pub mod sync;
+pub mod timeline;
 pub mod update;
+pub mod worklog;
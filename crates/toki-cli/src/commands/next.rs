@@ -6,14 +6,43 @@
 //! - Time/energy constraints
 //! - Issue complexity and priority
 
+use std::fmt::Write;
+use std::future::Future;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Timelike, Utc};
+use serde::Serialize;
 use toki_ai::AiService;
 use toki_ai::time_estimator::TimeEstimator;
 use toki_storage::models::Complexity;
-use toki_storage::{Database, IssueCandidate};
+use toki_storage::{ActivitySpan, Database, IssueCandidate, RankedSuggestion, TrackingTimezone};
+use uuid::Uuid;
+
+use super::helpers::escape_csv;
+use super::issue::issue_url;
+
+/// Output format for suggestions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NextFormat {
+    /// Human-readable list
+    Text,
+    /// Array of suggestion objects, for IDE integrations
+    Json,
+    /// One row per suggestion
+    Csv,
+}
+
+impl NextFormat {
+    fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => anyhow::bail!("Unknown format: {s}. Use 'text', 'json', or 'csv'"),
+        }
+    }
+}
 
 /// Focus level for task selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -45,6 +74,96 @@ impl FocusLevel {
     }
 }
 
+/// Score added to deep-focus issues at high-energy hours and trivial ones at low-energy hours
+const ENERGY_BONUS: f32 = 15.0;
+
+/// An hour this far above the typical active hour counts as high energy
+const HIGH_ENERGY_RATIO: f64 = 1.25;
+
+/// An hour this far below the typical active hour counts as low energy
+const LOW_ENERGY_RATIO: f64 = 0.5;
+
+/// How productive the user usually is at an hour, relative to their other active hours
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EnergyLevel {
+    High,
+    Normal,
+    Low,
+}
+
+/// Active minutes per local hour of the day, averaged over the lookback days
+struct EnergyProfile {
+    minutes_by_hour: [f64; 24],
+}
+
+impl EnergyProfile {
+    /// Spread past spans over the hours of the day they ran in `timezone`
+    fn from_spans(spans: &[ActivitySpan], timezone: TrackingTimezone, lookback_days: u32) -> Self {
+        let offset = Duration::minutes(i64::from(timezone.offset_minutes)).num_seconds();
+        let mut seconds_by_hour = [0u32; 24];
+        for span in spans {
+            let end = span
+                .end_time
+                .unwrap_or(span.start_time + Duration::seconds(i64::from(span.duration_seconds)));
+            let (mut time, end) = (span.start_time.timestamp() + offset, end.timestamp() + offset);
+            while time < end {
+                let hour = time.div_euclid(3600);
+                let chunk_end = ((hour + 1) * 3600).min(end);
+                let slot = usize::try_from(hour.rem_euclid(24)).unwrap_or_default();
+                seconds_by_hour[slot] = seconds_by_hour[slot]
+                    .saturating_add(u32::try_from(chunk_end - time).unwrap_or_default());
+                time = chunk_end;
+            }
+        }
+
+        let days = f64::from(lookback_days.max(1));
+        Self {
+            minutes_by_hour: seconds_by_hour.map(|seconds| f64::from(seconds) / 60.0 / days),
+        }
+    }
+
+    /// Energy score of a local hour: active minutes per day spent in it
+    fn score(&self, hour: u32) -> f64 {
+        self.minutes_by_hour[hour as usize % 24]
+    }
+
+    /// Energy of a local hour compared with the average hour the user is active in
+    fn level(&self, hour: u32) -> EnergyLevel {
+        let mut active_hours = 0u32;
+        let mut active_minutes = 0.0;
+        for minutes in self.minutes_by_hour.iter().filter(|m| **m > 0.0) {
+            active_hours += 1;
+            active_minutes += minutes;
+        }
+        if active_hours == 0 {
+            return EnergyLevel::Normal;
+        }
+
+        let ratio = self.score(hour) / (active_minutes / f64::from(active_hours));
+        if ratio >= HIGH_ENERGY_RATIO {
+            EnergyLevel::High
+        } else if ratio <= LOW_ENERGY_RATIO {
+            EnergyLevel::Low
+        } else {
+            EnergyLevel::Normal
+        }
+    }
+}
+
+/// Energy of the current hour, from `settings.energy_lookback_weeks` of past activity
+fn current_energy(db: &Database, now: DateTime<Utc>) -> Result<EnergyLevel> {
+    let settings = db.get_settings()?;
+    if settings.energy_lookback_weeks == 0 {
+        return Ok(EnergyLevel::Normal);
+    }
+
+    let lookback_days = settings.energy_lookback_weeks.saturating_mul(7);
+    let spans = db.get_activity_spans(now - Duration::days(i64::from(lookback_days)), now)?;
+    let profile = EnergyProfile::from_spans(&spans, settings.timezone, lookback_days);
+    let offset = Duration::minutes(i64::from(settings.timezone.offset_minutes));
+    Ok(profile.level((now + offset).hour()))
+}
+
 /// Parse time string (e.g., "30m", "2h", "1h30m") to seconds
 fn parse_time_to_seconds(s: &str) -> Option<u32> {
     let s = s.to_lowercase();
@@ -87,6 +206,7 @@ struct TaskSuggestion {
 struct ScoringContext<'a> {
     max_time_seconds: Option<u32>,
     focus_level: FocusLevel,
+    energy: EnergyLevel,
     recent_issue_ids: &'a [String],
     db: &'a Database,
 }
@@ -156,6 +276,21 @@ fn check_focus_constraint(complexity: Complexity, focus_level: FocusLevel) -> Op
     }
 }
 
+/// Favor deep-focus issues when energy is high and trivial ones when it is low
+fn check_energy_fit(complexity: Complexity, energy: EnergyLevel) -> (f32, Option<String>) {
+    match energy {
+        EnergyLevel::High if complexity.points() >= 5 => (
+            ENERGY_BONUS,
+            Some("you usually focus well at this hour".to_string()),
+        ),
+        EnergyLevel::Low if complexity.points() <= 1 => (
+            ENERGY_BONUS,
+            Some("light task for a low-energy hour".to_string()),
+        ),
+        _ => (0.0, None),
+    }
+}
+
 /// Calculate embedding similarity bonus
 fn calculate_embedding_bonus(
     issue: &IssueCandidate,
@@ -234,6 +369,11 @@ fn score_issue(
     score += focus_bonus;
     reasons.extend(focus_reason);
 
+    // Time-of-day energy
+    let (energy_bonus, energy_reason) = check_energy_fit(complexity, ctx.energy);
+    score += energy_bonus;
+    reasons.extend(energy_reason);
+
     // Context continuity
     if ctx.recent_issue_ids.contains(&issue.external_id) {
         score += 25.0;
@@ -298,12 +438,179 @@ fn display_suggestions(suggestions: &[TaskSuggestion], max_time_seconds: Option<
     }
 }
 
+/// Structured form of a suggestion
+#[derive(Debug, Serialize)]
+struct SuggestionRow {
+    issue_id: String,
+    system: String,
+    title: String,
+    score: f32,
+    rationale: Vec<String>,
+    estimated_seconds: u32,
+    /// Share of the `--time` window the estimate takes (`None` without `--time`)
+    time_fit: Option<f64>,
+    url: Option<String>,
+}
+
+fn suggestion_rows(
+    db: &Database,
+    suggestions: &[TaskSuggestion],
+    max_time_seconds: Option<u32>,
+) -> Vec<SuggestionRow> {
+    suggestions
+        .iter()
+        .map(|s| SuggestionRow {
+            issue_id: s.issue.external_id.clone(),
+            system: s.issue.external_system.clone(),
+            title: s.issue.title.clone(),
+            score: s.score,
+            rationale: s.reasons.clone(),
+            estimated_seconds: s.estimated_seconds,
+            time_fit: max_time_seconds
+                .filter(|max| *max > 0)
+                .map(|max| f64::from(s.estimated_seconds) / f64::from(max)),
+            url: issue_url(db, &s.issue),
+        })
+        .collect()
+}
+
+fn format_csv(rows: &[SuggestionRow]) -> String {
+    let mut csv =
+        String::from("issue_id,system,title,score,rationale,estimated_seconds,time_fit,url\n");
+    for row in rows {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{}",
+            escape_csv(&row.issue_id),
+            escape_csv(&row.system),
+            escape_csv(&row.title),
+            row.score,
+            escape_csv(&row.rationale.join(";")),
+            row.estimated_seconds,
+            row.time_fit
+                .map(|fit| format!("{fit:.2}"))
+                .unwrap_or_default(),
+            escape_csv(row.url.as_deref().unwrap_or_default()),
+        );
+    }
+    csv
+}
+
+/// Print suggestions as JSON or CSV
+fn print_structured(rows: &[SuggestionRow], format: NextFormat) -> Result<()> {
+    if format == NextFormat::Csv {
+        print!("{}", format_csv(rows));
+    } else {
+        println!("{}", serde_json::to_string_pretty(rows)?);
+    }
+    Ok(())
+}
+
+/// Explain why nothing is suggested; structured formats get an empty list instead
+fn print_no_suggestions(format: NextFormat, message: &str) -> Result<()> {
+    if format == NextFormat::Text {
+        println!("{message}");
+        Ok(())
+    } else {
+        print_structured(&[], format)
+    }
+}
+
+/// Reuse of ranked issue lists across `toki next` runs
+#[derive(Debug, Clone, Copy)]
+struct RankingCache {
+    /// Ranked issues kept per project
+    size: usize,
+    /// How long a ranking is reused (0 = never)
+    ttl_seconds: u32,
+}
+
+impl RankingCache {
+    fn from_settings(db: &Database) -> Result<Self> {
+        let settings = db.get_settings()?;
+        Ok(Self {
+            size: usize::try_from(settings.suggestion_cache_size).unwrap_or(usize::MAX),
+            ttl_seconds: settings.suggestion_cache_ttl_seconds,
+        })
+    }
+
+    /// A cached ranking only holds `size` issues, so larger requests re-score
+    fn serves(self, count: usize) -> bool {
+        self.ttl_seconds > 0 && count <= self.size
+    }
+}
+
+/// Get a project's ranking from the cache, or score its issues with `rank` and cache that
+///
+/// The returned ranking is best first. It is cut to the cache size only when
+/// the cache can serve `count`; otherwise the full ranking is returned.
+async fn project_ranking<F, Fut>(
+    db: &Database,
+    project_id: Uuid,
+    constraints: &str,
+    cache: RankingCache,
+    count: usize,
+    rank: F,
+) -> Result<Vec<RankedSuggestion>>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Vec<RankedSuggestion>>,
+{
+    if cache.serves(count) {
+        if let Some(ranking) =
+            db.get_suggestion_ranking(project_id, constraints, cache.ttl_seconds)?
+        {
+            log::debug!("Reusing cached ranking for project {project_id}");
+            return Ok(ranking);
+        }
+    }
+
+    let mut ranking = rank().await;
+    ranking.sort_by(|a, b| b.score.total_cmp(&a.score));
+    if cache.ttl_seconds > 0 {
+        let cached = &ranking[..ranking.len().min(cache.size)];
+        db.save_suggestion_ranking(project_id, constraints, cached)?;
+    }
+    if cache.serves(count) {
+        ranking.truncate(cache.size);
+    }
+    Ok(ranking)
+}
+
+/// Score issues that pass the constraints
+async fn score_issues(
+    issues: Vec<IssueCandidate>,
+    estimator: &TimeEstimator,
+    ctx: &ScoringContext<'_>,
+) -> Vec<RankedSuggestion> {
+    let mut ranking = Vec::new();
+    for issue in issues {
+        let estimated_seconds = estimator
+            .estimate(&issue)
+            .await
+            .ok()
+            .map_or(7200, |e| e.estimated_seconds);
+
+        if let Some((score, reasons)) = score_issue(&issue, estimated_seconds, ctx) {
+            ranking.push(RankedSuggestion {
+                issue_candidate_id: issue.id,
+                score,
+                reasons,
+                estimated_seconds,
+            });
+        }
+    }
+    ranking
+}
+
 /// Handle the next task suggestion command
 pub async fn handle_next_command(
     time: Option<&str>,
     focus: Option<&str>,
     count: usize,
+    format: &str,
 ) -> Result<()> {
+    let format = NextFormat::parse(format)?;
     let db = Arc::new(Database::new(None).context("Failed to open database")?);
 
     // Parse constraints
@@ -313,20 +620,24 @@ pub async fn handle_next_command(
     // Get all projects
     let projects = db.get_all_projects()?;
     if projects.is_empty() {
-        println!("No projects found. Run 'toki project auto-link' first.");
-        return Ok(());
+        return print_no_suggestions(
+            format,
+            "No projects found. Run 'toki project auto-link' first.",
+        );
     }
 
-    // Collect all active issues across projects
-    let all_issues: Vec<IssueCandidate> = projects
+    // Collect active issues per project
+    let project_issues: Vec<(Uuid, Vec<IssueCandidate>)> = projects
         .iter()
-        .filter_map(|p| db.get_active_issue_candidates(p.id).ok())
-        .flatten()
+        .filter_map(|p| Some((p.id, db.get_active_issue_candidates(p.id).ok()?)))
+        .filter(|(_, issues)| !issues.is_empty())
         .collect();
 
-    if all_issues.is_empty() {
-        println!("No open issues found. Run 'toki issue-sync' to sync issues from your PM system.");
-        return Ok(());
+    if project_issues.is_empty() {
+        return print_no_suggestions(
+            format,
+            "No open issues found. Run 'toki issue-sync' to sync issues from your PM system.",
+        );
     }
 
     // Get recent issue IDs for context
@@ -337,46 +648,75 @@ pub async fn handle_next_command(
     let estimator = TimeEstimator::new(db.clone(), ai_service);
 
     // Create scoring context
+    let energy = current_energy(&db, Utc::now())?;
     let ctx = ScoringContext {
         max_time_seconds,
         focus_level,
+        energy,
         recent_issue_ids: &recent_issue_ids,
         db: &db,
     };
 
-    // Score and rank issues
-    let mut suggestions: Vec<TaskSuggestion> = Vec::new();
-    for issue in all_issues {
-        let estimated_seconds = estimator
-            .estimate(&issue)
-            .await
-            .ok()
-            .map_or(7200, |e| e.estimated_seconds);
-
-        if let Some((score, reasons)) = score_issue(&issue, estimated_seconds, &ctx) {
-            suggestions.push(TaskSuggestion {
-                issue,
-                score,
-                reasons,
-                estimated_seconds,
-            });
-        }
+    // Rank each project's issues, reusing recent rankings for the same constraints
+    let cache = RankingCache::from_settings(&db)?;
+    let constraints = format!(
+        "time={}:focus={focus_level:?}:energy={energy:?}",
+        max_time_seconds.unwrap_or(0)
+    );
+    let mut ranking = Vec::new();
+    for (project_id, issues) in project_issues {
+        let scored = project_ranking(&db, project_id, &constraints, cache, count, || {
+            score_issues(issues, &estimator, &ctx)
+        })
+        .await?;
+        ranking.extend(scored);
     }
 
-    // Sort by score (highest first) and take top N
-    suggestions.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
-    suggestions.truncate(count);
+    let suggestions = top_suggestions(&db, ranking, count)?;
 
     if suggestions.is_empty() {
-        println!("No tasks match your constraints.");
+        let mut message = "No tasks match your constraints.".to_string();
         if max_time_seconds.is_some() {
-            println!("Try increasing the --time limit or removing constraints.");
+            message.push_str("\nTry increasing the --time limit or removing constraints.");
         }
+        return print_no_suggestions(format, &message);
+    }
+
+    if format == NextFormat::Text {
+        display_suggestions(&suggestions, max_time_seconds, focus_level, focus.is_some());
         return Ok(());
     }
+    print_structured(
+        &suggestion_rows(&db, &suggestions, max_time_seconds),
+        format,
+    )
+}
 
-    display_suggestions(&suggestions, max_time_seconds, focus_level, focus.is_some());
-    Ok(())
+/// Sort by score (highest first), take the top `count` and load their issues
+fn top_suggestions(
+    db: &Database,
+    mut ranking: Vec<RankedSuggestion>,
+    count: usize,
+) -> Result<Vec<TaskSuggestion>> {
+    ranking.sort_by(|a, b| b.score.total_cmp(&a.score));
+    ranking.truncate(count);
+    load_suggestions(db, ranking)
+}
+
+/// Attach issue details to ranked entries, dropping issues that no longer exist
+fn load_suggestions(db: &Database, ranking: Vec<RankedSuggestion>) -> Result<Vec<TaskSuggestion>> {
+    let mut suggestions = Vec::new();
+    for ranked in ranking {
+        if let Some(issue) = db.get_issue_candidate_by_id(ranked.issue_candidate_id)? {
+            suggestions.push(TaskSuggestion {
+                issue,
+                score: ranked.score,
+                reasons: ranked.reasons,
+                estimated_seconds: ranked.estimated_seconds,
+            });
+        }
+    }
+    Ok(suggestions)
 }
 
 /// Format duration in human-readable form
@@ -413,3 +753,223 @@ fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
 
     dot / (norm_a * norm_b)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn ranked(issue_candidate_id: Uuid, score: f32) -> RankedSuggestion {
+        RankedSuggestion {
+            issue_candidate_id,
+            score,
+            reasons: vec!["continues recent work".to_string()],
+            estimated_seconds: 3600,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ranking_reused_within_ttl_until_new_activity() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+        let project = db.get_or_create_project("toki", "/code/toki").unwrap();
+        let low = IssueCandidate::new(project.id, "1".into(), "github".into(), "Low".into());
+        let high = IssueCandidate::new(project.id, "2".into(), "github".into(), "High".into());
+        for issue in [&low, &high] {
+            db.upsert_issue_candidate(issue).unwrap();
+        }
+
+        let cache = RankingCache {
+            size: 20,
+            ttl_seconds: 600,
+        };
+        let scored = AtomicU32::new(0);
+        let rank = || async {
+            scored.fetch_add(1, Ordering::SeqCst);
+            vec![ranked(low.id, 40.0), ranked(high.id, 90.0)]
+        };
+
+        let first = project_ranking(&db, project.id, "focus=Normal", cache, 3, rank)
+            .await
+            .unwrap();
+        assert_eq!(first[0].issue_candidate_id, high.id);
+
+        // `toki next --count 10` right after reuses the ranking without scoring
+        let second = project_ranking(&db, project.id, "focus=Normal", cache, 10, rank)
+            .await
+            .unwrap();
+        assert_eq!(second, first);
+        assert_eq!(scored.load(Ordering::SeqCst), 1);
+
+        // Other constraints and new activity both need a fresh ranking
+        project_ranking(&db, project.id, "focus=Deep", cache, 3, rank)
+            .await
+            .unwrap();
+        assert_eq!(scored.load(Ordering::SeqCst), 2);
+
+        let span = ActivitySpan::new(
+            "com.microsoft.VSCode".to_string(),
+            "Coding".to_string(),
+            Utc::now(),
+            Some(project.id),
+            None,
+            None,
+        );
+        db.create_activity_span(&span).unwrap();
+        project_ranking(&db, project.id, "focus=Normal", cache, 3, rank)
+            .await
+            .unwrap();
+        assert_eq!(scored.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_ranking_rescored_beyond_cache_size_or_after_issue_sync() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+        let project = db.get_or_create_project("toki", "/code/toki").unwrap();
+        let mut issue = IssueCandidate::new(project.id, "7".into(), "plane".into(), "Fix".into());
+        db.upsert_issue_candidate(&issue).unwrap();
+
+        let cache = RankingCache {
+            size: 1,
+            ttl_seconds: 600,
+        };
+        let issue_id = issue.id;
+        let scored = AtomicU32::new(0);
+        let rank = || async {
+            scored.fetch_add(1, Ordering::SeqCst);
+            vec![ranked(issue_id, 50.0)]
+        };
+
+        project_ranking(&db, project.id, "", cache, 1, rank)
+            .await
+            .unwrap();
+        project_ranking(&db, project.id, "", cache, 2, rank)
+            .await
+            .unwrap();
+        assert_eq!(scored.load(Ordering::SeqCst), 2);
+
+        issue.last_synced = Utc::now() + Duration::seconds(1);
+        db.upsert_issue_candidate(&issue).unwrap();
+        project_ranking(&db, project.id, "", cache, 1, rank)
+            .await
+            .unwrap();
+        assert_eq!(scored.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_json_suggestions_ordered_by_score_with_rationale() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+        let project = db.get_or_create_project("toki", "/code/toki").unwrap();
+        let mut issues = Vec::new();
+        for (id, title) in [("1", "Low"), ("2", "High"), ("3", "Middle")] {
+            let mut issue =
+                IssueCandidate::new(project.id, id.into(), "notion".into(), title.into());
+            issue.source_page_id = Some(format!("page-{id}"));
+            db.upsert_issue_candidate(&issue).unwrap();
+            issues.push(issue);
+        }
+        let ranking = vec![
+            ranked(issues[0].id, 40.0),
+            ranked(issues[1].id, 90.0),
+            ranked(issues[2].id, 65.0),
+        ];
+
+        let suggestions = top_suggestions(&db, ranking, 3).unwrap();
+        let rows = suggestion_rows(&db, &suggestions, Some(7200));
+        let json = serde_json::to_string_pretty(&rows).unwrap();
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+
+        let titles: Vec<&str> = parsed
+            .iter()
+            .map(|s| s["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(titles, ["High", "Middle", "Low"]);
+        let scores: Vec<f64> = parsed
+            .iter()
+            .map(|s| s["score"].as_f64().unwrap())
+            .collect();
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        let top = &parsed[0];
+        assert_eq!(top["issue_id"], "2");
+        assert_eq!(top["rationale"][0], "continues recent work");
+        assert_eq!(top["estimated_seconds"], 3600);
+        assert_eq!(top["time_fit"], 0.5);
+        assert_eq!(top["url"], "https://notion.so/page2");
+    }
+
+    /// Four weeks of focused mornings (09:00-11:00) and short afternoons (15:00-15:15)
+    fn morning_person_spans(now: DateTime<Utc>) -> Vec<ActivitySpan> {
+        let today = now.date_naive();
+        let mut spans = Vec::new();
+        for days_ago in 1..=28 {
+            let date = today - Duration::days(days_ago);
+            for (hour, minutes) in [(9, 120), (15, 15)] {
+                let start = date.and_hms_opt(hour, 0, 0).unwrap().and_utc();
+                let mut span = ActivitySpan::new(
+                    "com.microsoft.VSCode".to_string(),
+                    "Coding".to_string(),
+                    start,
+                    None,
+                    None,
+                    None,
+                );
+                span.end_time = Some(start + Duration::minutes(minutes));
+                spans.push(span);
+            }
+        }
+        spans
+    }
+
+    #[test]
+    fn test_energy_profile_from_active_minutes_per_hour() {
+        let spans = morning_person_spans(Utc::now());
+        let profile = EnergyProfile::from_spans(&spans, TrackingTimezone::default(), 28);
+
+        assert!((profile.score(9) - 60.0).abs() < 1e-9);
+        assert!((profile.score(10) - 60.0).abs() < 1e-9);
+        assert!((profile.score(15) - 15.0).abs() < 1e-9);
+        assert!(profile.score(3).abs() < 1e-9);
+
+        assert_eq!(profile.level(10), EnergyLevel::High);
+        assert_eq!(profile.level(15), EnergyLevel::Low);
+        // No history says nothing about energy
+        let empty = EnergyProfile::from_spans(&[], TrackingTimezone::default(), 28);
+        assert_eq!(empty.level(10), EnergyLevel::Normal);
+    }
+
+    #[test]
+    fn test_deep_work_ranked_first_in_the_morning_and_trivial_in_the_afternoon() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+        let project = db.get_or_create_project("toki", "/code/toki").unwrap();
+        let mut deep = IssueCandidate::new(project.id, "1".into(), "github".into(), "Deep".into());
+        deep.complexity = Some(Complexity::Complex);
+        let mut trivial =
+            IssueCandidate::new(project.id, "2".into(), "github".into(), "Typo".into());
+        trivial.complexity = Some(Complexity::Trivial);
+
+        let spans = morning_person_spans(Utc::now());
+        let profile = EnergyProfile::from_spans(&spans, TrackingTimezone::default(), 28);
+        let top_at = |hour: u32| {
+            let ctx = ScoringContext {
+                max_time_seconds: None,
+                focus_level: FocusLevel::Normal,
+                energy: profile.level(hour),
+                recent_issue_ids: &[],
+                db: &db,
+            };
+            let score = |issue: &IssueCandidate| score_issue(issue, 3600, &ctx).unwrap().0;
+            if score(&deep) > score(&trivial) {
+                "Deep"
+            } else {
+                "Typo"
+            }
+        };
+
+        assert_eq!(top_at(10), "Deep");
+        assert_eq!(top_at(15), "Typo");
+    }
+}
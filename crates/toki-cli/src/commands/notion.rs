@@ -35,7 +35,7 @@ pub enum NotionAction {
         /// Dry run - show what would be synced without creating issues
         #[arg(short = 'n', long)]
         dry_run: bool,
-        /// Force sync even for already-synced pages
+        /// Force sync even for already-synced pages, fetching every page again
         #[arg(short, long)]
         force: bool,
         /// Maximum number of issues to create
@@ -56,7 +56,7 @@ pub enum NotionAction {
         /// Dry run - show what would be synced without creating issues
         #[arg(short = 'n', long)]
         dry_run: bool,
-        /// Force sync even for already-synced pages
+        /// Force sync even for already-synced pages, fetching every page again
         #[arg(short, long)]
         force: bool,
         /// Maximum number of issues to create
@@ -96,14 +96,21 @@ fn print_sync_results(results: &[SyncResult], report: &IssueSyncReport) {
             SyncOutcome::Failed { error } => {
                 println!("  [FAILED] {} - {error}", result.title);
             }
+            SyncOutcome::Updated { issue_number, issue_url } => {
+                println!("  [UPDATED] #{issue_number} {} -> {issue_url}", result.title);
+            }
             SyncOutcome::WouldCreate => {
                 println!("  [WOULD CREATE] {}", result.title);
             }
+            SyncOutcome::WouldUpdate { issue_number, .. } => {
+                println!("  [WOULD UPDATE] #{issue_number} {}", result.title);
+            }
         }
     }
 
     println!("\nSummary:");
     println!("  Created: {}", report.created);
+    println!("  Updated: {}", report.updated);
     println!("  Skipped: {}", report.skipped);
     println!("  Failed:  {}", report.failed);
 }
@@ -194,7 +201,7 @@ async fn handle_pages_schema(client: &NotionClient, database: &str) -> Result<()
     Ok(())
 }
 
-async fn handle_pages_list(client: &NotionClient, database: &str) -> Result<()> {
+async fn handle_pages_list(client: &NotionClient, db: &Database, database: &str) -> Result<()> {
     println!("Fetching pages from database...\n");
     let pages = client.query_database_all(database).await?;
 
@@ -205,12 +212,13 @@ async fn handle_pages_list(client: &NotionClient, database: &str) -> Result<()>
 
     let db_info = client.get_database(database).await?;
     let mapping = db_info.detect_property_mapping(None);
+    let workspace = db.get_notion_workspace(database)?;
 
     println!("{:<15} {:<50} STATUS", "ID", "TITLE");
     println!("{}", "-".repeat(80));
 
     for page in &pages {
-        let external_id = NotionClient::generate_external_id(database, &page.id);
+        let external_id = NotionClient::generate_external_id(workspace.as_deref(), database, &page.id);
         let title = mapping.title.as_ref()
             .and_then(|prop_name| page.properties.get(prop_name))
             .and_then(toki_integrations::NotionPropertyValue::as_plain_text)
@@ -374,7 +382,7 @@ pub async fn handle_notion_command(action: NotionAction) -> Result<()> {
             if schema {
                 handle_pages_schema(&client, &database).await?;
             } else {
-                handle_pages_list(&client, &database).await?;
+                handle_pages_list(&client, &db, &database).await?;
             }
         }
         NotionAction::SyncToGithub { database, repo, dry_run, force, limit } => {
@@ -2,16 +2,19 @@
 use anyhow::Result;
 use clap::Subcommand;
 use std::sync::Arc;
+use toki_core::config::get_data_dir;
+use toki_core::ipc::{IpcClient, IpcRequest, IpcResponse};
 use toki_integrations::plane::PlaneClient;
-use toki_storage::Database;
+use toki_integrations::{ApiError, NotionClient};
+use toki_storage::{Database, Project, ProjectMatch};
 
-use super::helpers::truncate_str;
+use super::helpers::{trello_client, truncate_str};
 
 #[derive(Subcommand, Debug)]
 pub enum ProjectAction {
     /// List all tracked projects
     List,
-    /// Link a local project to a PM system (Plane or Notion)
+    /// Link a local project to a PM system (Plane, Notion or Trello)
     Link {
         /// Local project name
         #[arg(short, long)]
@@ -22,6 +25,12 @@ pub enum ProjectAction {
         /// Notion database ID
         #[arg(long)]
         notion_database: Option<String>,
+        /// Notion workspace name, to keep issue ids distinct across workspaces
+        #[arg(long, requires = "notion_database")]
+        notion_workspace: Option<String>,
+        /// Trello board ID (the part after /b/ in the board URL)
+        #[arg(long)]
+        trello_board: Option<String>,
     },
     /// Unlink a project from PM system
     Unlink {
@@ -37,6 +46,17 @@ pub enum ProjectAction {
         #[arg(long)]
         apply: bool,
     },
+    /// Find tracked projects by partial name
+    Find {
+        /// Part of the project name (e.g., "api")
+        query: String,
+    },
+    /// Check that linked PM projects still exist upstream
+    Validate {
+        /// Unlink projects whose PM project no longer exists
+        #[arg(long)]
+        unlink: bool,
+    },
 }
 
 #[allow(clippy::too_many_lines)]
@@ -84,6 +104,8 @@ pub async fn handle_project_command(action: ProjectAction) -> Result<()> {
             project,
             plane_project,
             notion_database,
+            notion_workspace,
+            trello_board,
         } => {
             // Find local project by name
             let Some(local_project) = db.get_project_by_name(&project)? else {
@@ -93,8 +115,8 @@ pub async fn handle_project_command(action: ProjectAction) -> Result<()> {
             };
 
             // Determine which PM system to link
-            match (plane_project, notion_database) {
-                (Some(plane_id), None) => {
+            match (plane_project, notion_database, trello_board) {
+                (Some(plane_id), None, None) => {
                     // Link to Plane.so
                     let Some(config) = db.get_integration_config("plane")? else {
                         println!("Plane.so is not configured.");
@@ -140,10 +162,27 @@ pub async fn handle_project_command(action: ProjectAction) -> Result<()> {
                     );
                     println!("\nNow run 'toki issue-sync' to fetch issues for AI matching.");
                 }
-                (None, Some(db_id)) => {
-                    // Link to Notion database
-                    use toki_integrations::NotionClient;
+                (None, Some(db_id), None) => {
+                    // The workspace is part of each synced issue's external id, so changing it
+                    // on an already-synced database would orphan the existing candidates
+                    let same_database = local_project.pm_system.as_deref() == Some("notion")
+                        && local_project
+                            .pm_project_id
+                            .as_deref()
+                            .is_some_and(|id| same_notion_id(id, &db_id));
+                    if same_database
+                        && local_project.pm_workspace != notion_workspace
+                        && !db.get_issue_candidates_for_project(local_project.id)?.is_empty()
+                    {
+                        println!(
+                            "'{project}' already has issues synced from this database under workspace '{}'.",
+                            local_project.pm_workspace.as_deref().unwrap_or("(none)")
+                        );
+                        println!("Changing the workspace would orphan them; keep the current workspace.");
+                        return Ok(());
+                    }
 
+                    // Link to Notion database
                     let Some(config) = db.get_integration_config("notion")? else {
                         println!("Notion is not configured.");
                         println!(
@@ -173,20 +212,49 @@ pub async fn handle_project_command(action: ProjectAction) -> Result<()> {
                         .map_or("Untitled", |t| t.plain_text.as_str());
 
                     // Link the project
-                    db.link_project_to_pm(local_project.id, "notion", &db_id, None)?;
+                    db.link_project_to_pm(
+                        local_project.id,
+                        "notion",
+                        &db_id,
+                        notion_workspace.as_deref(),
+                    )?;
 
                     println!("Linked '{project}' -> Notion database '{db_title}'");
                     println!("\nNow run 'toki issue-sync' to fetch issues for AI matching.");
                 }
-                (Some(_), Some(_)) => {
-                    println!("Error: Cannot specify both --plane-project and --notion-database.");
-                    println!("Choose one PM system to link.");
+                (None, None, Some(board_id)) => {
+                    // Link to Trello board
+                    let Some(config) = db.get_integration_config("trello")? else {
+                        println!("Trello is not configured.");
+                        println!("Run 'toki config set trello.api_key <key>' and 'toki config set trello.token <token>' first.");
+                        return Ok(());
+                    };
+
+                    // Verify board exists and is accessible
+                    let trello_client = trello_client(&config)?;
+                    let board_name = match trello_client.board_name(&board_id).await {
+                        Ok(name) => name,
+                        Err(e) => {
+                            println!("Failed to access Trello board: {e}");
+                            return Ok(());
+                        }
+                    };
+
+                    db.link_project_to_pm(local_project.id, "trello", &board_id, None)?;
+
+                    println!("Linked '{project}' -> Trello board '{board_name}'");
+                    println!("\nNow run 'toki issue-sync' to fetch cards for AI matching.");
                 }
-                (None, None) => {
-                    println!("Error: Please specify either --plane-project or --notion-database.");
+                (None, None, None) => {
+                    println!("Error: Please specify one of --plane-project, --notion-database or --trello-board.");
                     println!("\nExamples:");
                     println!("  toki project link --project myapp --plane-project PROJ");
                     println!("  toki project link --project myapp --notion-database abc123...");
+                    println!("  toki project link --project myapp --trello-board aB3dE5fG");
+                }
+                _ => {
+                    println!("Error: Cannot link to more than one PM system at once.");
+                    println!("Choose one PM system to link.");
                 }
             }
         }
@@ -205,7 +273,7 @@ pub async fn handle_project_command(action: ProjectAction) -> Result<()> {
             min_confidence,
             apply,
         } => {
-            use toki_ai::AutoLinker;
+            use toki_ai::{AutoLinker, NameMatching};
 
             // Get Plane configuration
             let Some(config) = db.get_integration_config("plane")? else {
@@ -225,8 +293,14 @@ pub async fn handle_project_command(action: ProjectAction) -> Result<()> {
                 Some(config.api_url.clone()),
             )?;
 
+            let settings = db.get_settings()?;
+            let name_matching = NameMatching::new(
+                settings.name_match_algorithm.parse().unwrap_or_default(),
+                settings.name_match_threshold,
+            );
+
             let db_arc = Arc::new(Database::new(None)?);
-            let auto_linker = AutoLinker::new(db_arc);
+            let auto_linker = AutoLinker::new(db_arc).with_name_matching(name_matching);
 
             println!("Analyzing projects for auto-linking...\n");
 
@@ -303,7 +377,248 @@ pub async fn handle_project_command(action: ProjectAction) -> Result<()> {
                 println!("  toki project auto-link --apply");
             }
         }
+
+        ProjectAction::Find { query } => {
+            let matches = find_projects(&db, &query).await?;
+            if matches.is_empty() {
+                println!("No project matches '{query}'.");
+                return Ok(());
+            }
+
+            println!("{:<20} {:>6}  PATH", "NAME", "SCORE");
+            for candidate in &matches {
+                println!(
+                    "{:<20} {:>6.2}  {}",
+                    truncate_str(&candidate.name, 18),
+                    candidate.score,
+                    truncate_str(&candidate.path, 40)
+                );
+            }
+        }
+
+        ProjectAction::Validate { unlink } => validate_links(&db, unlink).await?,
     }
 
     Ok(())
 }
+
+/// Rank projects by name, through the daemon when it is running
+async fn find_projects(db: &Database, query: &str) -> Result<Vec<ProjectMatch>> {
+    let sock_path = get_data_dir()?.join("toki.sock");
+    if !sock_path.exists() {
+        return db.search_projects(query);
+    }
+
+    let request = IpcRequest::ResolveProject {
+        query: query.to_string(),
+    };
+    match IpcClient::new(&sock_path).send_command(request).await {
+        Ok(IpcResponse::ProjectMatches { matches }) => Ok(matches),
+        Ok(IpcResponse::ResolveFailed { error }) => anyhow::bail!("Project search failed: {error}"),
+        Ok(_) => anyhow::bail!("Unexpected response from daemon"),
+        // A stale socket from a daemon that did not shut down cleanly
+        Err(e) => {
+            log::debug!("Daemon did not answer the project search: {e}");
+            db.search_projects(query)
+        }
+    }
+}
+
+/// Result of checking one project's PM link
+#[derive(Debug, PartialEq, Eq)]
+enum LinkStatus {
+    /// The PM project still resolves
+    Alive,
+    /// The PM project is gone (deleted upstream or access revoked)
+    Dead(String),
+    /// The check failed for another reason; the link may still be fine
+    Unverified(String),
+}
+
+/// Whether two Notion database ids refer to the same database (with or without dashes)
+fn same_notion_id(a: &str, b: &str) -> bool {
+    let clean = |id: &str| id.replace('-', "").to_lowercase();
+    clean(a) == clean(b)
+}
+
+/// Map a PM lookup result to a link status
+///
+/// Only a 404 from the API counts as a dead link; auth, rate-limit, and network
+/// errors leave the link unverified so a flaky connection never unlinks anything.
+fn classify_link_check<T>(result: &Result<T>) -> LinkStatus {
+    match result {
+        Ok(_) => LinkStatus::Alive,
+        Err(e) if ApiError::is_not_found_error(e) => {
+            LinkStatus::Dead("PM project not found".to_string())
+        }
+        Err(e) => LinkStatus::Unverified(e.to_string()),
+    }
+}
+
+/// Check a single project's link against its PM system
+async fn check_project_link(
+    db: &Database,
+    project: &Project,
+    pm_system: &str,
+    pm_id: &str,
+) -> LinkStatus {
+    match pm_system {
+        "plane" => {
+            let Ok(project_id) = uuid::Uuid::parse_str(pm_id) else {
+                return LinkStatus::Dead(format!("invalid Plane project id: {pm_id}"));
+            };
+            let config = match db.get_integration_config("plane") {
+                Ok(Some(config)) => config,
+                Ok(None) => {
+                    return LinkStatus::Unverified("Plane.so is not configured".to_string())
+                }
+                Err(e) => return LinkStatus::Unverified(e.to_string()),
+            };
+            let workspace = config
+                .workspace_slug
+                .clone()
+                .or_else(|| project.pm_workspace.clone())
+                .unwrap_or_default();
+            let client = match PlaneClient::new(config.api_key, workspace, Some(config.api_url)) {
+                Ok(client) => client,
+                Err(e) => return LinkStatus::Unverified(e.to_string()),
+            };
+            classify_link_check(&client.get_project(&project_id).await)
+        }
+        "notion" => {
+            let config = match db.get_integration_config("notion") {
+                Ok(Some(config)) => config,
+                Ok(None) => return LinkStatus::Unverified("Notion is not configured".to_string()),
+                Err(e) => return LinkStatus::Unverified(e.to_string()),
+            };
+            let client = match NotionClient::new(config.api_key) {
+                Ok(client) => client,
+                Err(e) => return LinkStatus::Unverified(e.to_string()),
+            };
+            classify_link_check(&client.get_database(pm_id).await)
+        }
+        "trello" => {
+            let config = match db.get_integration_config("trello") {
+                Ok(Some(config)) => config,
+                Ok(None) => return LinkStatus::Unverified("Trello is not configured".to_string()),
+                Err(e) => return LinkStatus::Unverified(e.to_string()),
+            };
+            let client = match trello_client(&config) {
+                Ok(client) => client,
+                Err(e) => return LinkStatus::Unverified(e.to_string()),
+            };
+            classify_link_check(&client.board_name(pm_id).await)
+        }
+        other => LinkStatus::Unverified(format!("validation not supported for {other}")),
+    }
+}
+
+/// Report linked projects whose PM project no longer exists
+async fn validate_links(db: &Database, unlink: bool) -> Result<()> {
+    let linked: Vec<_> = db
+        .get_all_projects()?
+        .into_iter()
+        .filter_map(|p| {
+            let system = p.pm_system.clone().filter(|s| !s.is_empty())?;
+            let pm_id = p.pm_project_id.clone().filter(|s| !s.is_empty())?;
+            Some((p, system, pm_id))
+        })
+        .collect();
+
+    if linked.is_empty() {
+        println!("No linked projects to validate.");
+        return Ok(());
+    }
+
+    println!("Validating {} linked project(s)...\n", linked.len());
+
+    let mut dead = Vec::new();
+    let mut unverified = 0;
+    for (project, system, pm_id) in &linked {
+        match check_project_link(db, project, system, pm_id).await {
+            LinkStatus::Alive => println!("  ok          {} -> {system} {pm_id}", project.name),
+            LinkStatus::Dead(reason) => {
+                println!(
+                    "  dead        {} -> {system} {pm_id} ({reason})",
+                    project.name
+                );
+                dead.push(project);
+            }
+            LinkStatus::Unverified(reason) => {
+                println!(
+                    "  unverified  {} -> {system} {pm_id} ({reason})",
+                    project.name
+                );
+                unverified += 1;
+            }
+        }
+    }
+
+    println!(
+        "\n{} ok, {} dead, {unverified} could not be verified",
+        linked.len() - dead.len() - unverified,
+        dead.len()
+    );
+
+    if dead.is_empty() {
+        return Ok(());
+    }
+
+    if unlink {
+        for project in &dead {
+            db.link_project_to_pm(project.id, "", "", None)?;
+            println!("Unlinked '{}'", project.name);
+        }
+    } else {
+        println!("To remove dead links, run: toki project validate --unlink");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_error(status: u16) -> anyhow::Error {
+        ApiError {
+            api_name: "Plane".to_string(),
+            status,
+            body: String::new(),
+            retry_after: None,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_notion_ids_compared_without_dashes() {
+        assert!(same_notion_id(
+            "abcd1234-5678-9abc-def0-123456789abc",
+            "ABCD123456789ABCDEF0123456789ABC"
+        ));
+        assert!(!same_notion_id("abcd1234", "abcd1235"));
+    }
+
+    #[test]
+    fn test_not_found_reported_as_dead_link() {
+        let result: Result<()> = Err(api_error(404).context("Failed to fetch project"));
+        assert!(matches!(classify_link_check(&result), LinkStatus::Dead(_)));
+    }
+
+    #[test]
+    fn test_transient_error_reported_as_unverified() {
+        let result: Result<()> = Err(api_error(503));
+        assert!(matches!(
+            classify_link_check(&result),
+            LinkStatus::Unverified(_)
+        ));
+
+        let result: Result<()> = Err(anyhow::anyhow!("Failed to send request to Plane API"));
+        assert!(matches!(
+            classify_link_check(&result),
+            LinkStatus::Unverified(_)
+        ));
+
+        assert_eq!(classify_link_check(&Ok(())), LinkStatus::Alive);
+    }
+}
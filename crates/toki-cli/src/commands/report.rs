@@ -1,21 +1,48 @@
 /// Report and categories command handlers
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt::Write;
+
 use anyhow::Result;
 use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use tabled::builder::Builder;
 use tabled::{Table, Tabled};
 use toki_ai::InsightsGenerator;
+use toki_core::{AppDisplayNames, BillableCategories};
 use toki_storage::models::OutcomeSummary;
-use toki_storage::Database;
+use toki_storage::{
+    ActivitySpan, Database, DurationFormat, IssueTimeStats, TimeBlock, TrackingTimezone,
+};
+
+use super::helpers::escape_csv;
+use super::ical::{block_uid, write_calendar, CalendarEvent};
+use super::issue::resolve_issue;
+use uuid::Uuid;
+
+/// Max project/category columns in a split report; the rest fold into "Other"
+const MAX_MATRIX_COLUMNS: usize = 6;
 
 #[derive(Tabled)]
 struct CategoryStats {
     #[tabled(rename = "Category")]
     category: String,
-    #[tabled(rename = "Time (minutes)")]
-    time_minutes: u32,
+    #[tabled(rename = "Time")]
+    time: String,
+    #[tabled(skip)]
+    seconds: u32,
     #[tabled(rename = "Percentage")]
     percentage: String,
 }
 
+#[derive(Tabled)]
+struct AppStats {
+    #[tabled(rename = "Application")]
+    app: String,
+    #[tabled(rename = "Time")]
+    time: String,
+}
+
 #[derive(Tabled)]
 struct SessionOutcomeRow {
     #[tabled(rename = "Session")]
@@ -28,39 +55,196 @@ struct SessionOutcomeRow {
     outcomes: String,
 }
 
-pub fn handle_report_command(period: &str, by_outcome: bool) -> Result<()> {
+pub fn handle_report_command(
+    period: &str,
+    by_outcome: bool,
+    split_by: Option<&str>,
+    format: &str,
+    timezone_from_db: bool,
+    work_item: Option<&str>,
+) -> Result<()> {
+    let split_by = split_by.map(SplitBy::parse).transpose()?;
+    if !matches!(format, "text" | "json" | "csv" | "ical") {
+        anyhow::bail!("Unknown format: {format}. Use 'text', 'json', 'csv', or 'ical'");
+    }
+    if work_item.is_some() && format != "text" {
+        anyhow::bail!("--work-item reports are text only; drop --format {format}");
+    }
+    // Keep stdout parseable for the machine-readable formats
+    let machine_readable = matches!(format, "json" | "csv");
+    let notice = |message: &str| {
+        if machine_readable {
+            eprintln!("{message}");
+        } else {
+            println!("{message}");
+        }
+    };
     let db = Database::new(None)?;
 
-    let (start, end) = match period {
-        "today" => {
-            let start = Utc::now()
-                .date_naive()
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let end = Utc::now();
-            (start, end)
-        }
-        "week" => {
-            let end = Utc::now();
-            let start = end - Duration::days(7);
-            (start, end)
-        }
-        "month" => {
-            let end = Utc::now();
-            let start = end - Duration::days(30);
-            (start, end)
+    let settings = db.get_settings()?;
+    let (configured, boundary_hour) = (settings.timezone, settings.day_boundary_hour);
+    let durations = settings.duration_format;
+    let Some((mut start, mut end)) = period_range(period, configured, boundary_hour) else {
+        println!("Unknown period: {period}. Use 'today', 'week', or 'month'");
+        return Ok(());
+    };
+
+    // Day boundaries follow settings.timezone; warn if the data was captured in another one
+    if let Some(captured) = timezone_mismatch(configured, &db.get_session_timezones(start, end)?) {
+        if timezone_from_db {
+            notice(&format!(
+                "Reporting in capture timezone {captured} (settings.timezone is {configured})\n"
+            ));
+            (start, end) = period_range(period, captured, boundary_hour).unwrap_or((start, end));
+        } else {
+            notice(&format!(
+                "Warning: sessions in this period were captured in timezone {captured}, \
+                 but settings.timezone is {configured}; day boundaries may have shifted."
+            ));
+            notice("Run with --user-timezone-from-db to report in the capture timezone.\n");
         }
-        _ => {
-            println!("Unknown period: {period}. Use 'today', 'week', or 'month'");
-            return Ok(());
+    }
+
+    if let Some(work_item) = work_item {
+        handle_work_item_report(&db, period, start, end, work_item, durations)
+    } else if format == "ical" {
+        handle_ical_report(&db, start, end)
+    } else if format == "json" {
+        let summary = build_report_summary(&db, period, start, end)?;
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        Ok(())
+    } else if format == "csv" {
+        let summary = build_report_summary(&db, period, start, end)?;
+        print!("{}", summary.to_csv());
+        Ok(())
+    } else if by_outcome {
+        handle_outcome_report(&db, period, start, end, durations)
+    } else if let Some(split_by) = split_by {
+        handle_split_report(&db, period, start, end, split_by, durations)
+    } else {
+        handle_time_report(&db, period, start, end, durations)
+    }
+}
+
+/// Report range for a period, with days starting at `boundary_hour` in `timezone`
+///
+/// "today" is the work day in progress, so with a 04:00 boundary it still
+/// covers the previous evening until 04:00.
+fn period_range(
+    period: &str,
+    timezone: TrackingTimezone,
+    boundary_hour: u32,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let end = Utc::now();
+    let start = match period {
+        "today" => {
+            timezone.start_of_work_day(timezone.work_date(end, boundary_hour), boundary_hour)
         }
+        "week" => end - Duration::days(7),
+        "month" => end - Duration::days(30),
+        _ => return None,
     };
+    Some((start, end))
+}
 
-    if by_outcome {
-        handle_outcome_report(&db, period, start, end)
+/// Capture timezone that differs from the configured one, if any
+///
+/// `captured` lists the timezones sessions were recorded in, most used first.
+fn timezone_mismatch(
+    configured: TrackingTimezone,
+    captured: &[(TrackingTimezone, usize)],
+) -> Option<TrackingTimezone> {
+    captured
+        .iter()
+        .map(|(timezone, _)| *timezone)
+        .find(|timezone| *timezone != configured)
+}
+
+/// Print confirmed time blocks in the period as iCalendar events
+fn handle_ical_report(db: &Database, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<()> {
+    let blocks: Vec<TimeBlock> = db
+        .get_confirmed_time_blocks()?
+        .into_iter()
+        .filter(|b| b.start_time < end && b.end_time > start)
+        .collect();
+    let spans = db.get_activity_spans(start, end)?;
+
+    let events: Vec<CalendarEvent> = blocks
+        .iter()
+        .map(|block| {
+            let issues: Vec<String> = block
+                .work_item_ids
+                .iter()
+                .filter_map(|id| db.get_issue_candidate_by_id(*id).ok().flatten())
+                .map(|issue| format!("#{} {}", issue.external_id, issue.title))
+                .collect();
+            let category = dominant_category(block, &spans);
+            block_event(block, category.as_deref(), &issues)
+        })
+        .collect();
+
+    print!("{}", write_calendar(&events, Utc::now()));
+    Ok(())
+}
+
+/// Category with the most tracked time inside a block
+pub(crate) fn dominant_category(block: &TimeBlock, spans: &[ActivitySpan]) -> Option<String> {
+    let mut by_category: HashMap<&str, i64> = HashMap::new();
+    for span in spans {
+        let span_end = span
+            .end_time
+            .unwrap_or(span.start_time + Duration::seconds(i64::from(span.duration_seconds)));
+        let overlap =
+            (span_end.min(block.end_time) - span.start_time.max(block.start_time)).num_seconds();
+        if overlap > 0 {
+            *by_category.entry(span.category.as_str()).or_insert(0) += overlap;
+        }
+    }
+
+    by_category
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(category, _)| category.to_string())
+}
+
+/// Whether a block is billable, judged by its dominant category
+///
+/// Blocks without tracked activity have no category and are not billable.
+pub(crate) fn is_block_billable(
+    block: &TimeBlock,
+    spans: &[ActivitySpan],
+    billable: &BillableCategories,
+) -> bool {
+    dominant_category(block, spans)
+        .is_some_and(|category| billable.is_billable(&category, block.project_id))
+}
+
+/// Calendar event for a block; the summary falls back to its issues
+fn block_event(block: &TimeBlock, category: Option<&str>, issues: &[String]) -> CalendarEvent {
+    let description = block.description.trim();
+    let summary = if !description.is_empty() {
+        description.to_string()
+    } else if issues.is_empty() {
+        "Work".to_string()
     } else {
-        handle_time_report(&db, period, start, end)
+        issues.join(", ")
+    };
+
+    let mut details = vec![format!("Category: {}", category.unwrap_or("Uncategorized"))];
+    if !issues.is_empty() {
+        details.push(format!("Issues: {}", issues.join(", ")));
+    }
+    if !block.tags.is_empty() {
+        details.push(format!("Tags: {}", block.tags.join(", ")));
+    }
+
+    CalendarEvent {
+        uid: block_uid(block.id),
+        start: block.start_time,
+        end: block.end_time,
+        summary,
+        description: details.join("\n"),
+        categories: block.tags.clone(),
     }
 }
 
@@ -70,6 +254,7 @@ fn handle_time_report(
     period: &str,
     start: DateTime<Utc>,
     end: DateTime<Utc>,
+    durations: DurationFormat,
 ) -> Result<()> {
     // Use activity_spans for more accurate data
     let spans = db.get_activity_spans(start, end)?;
@@ -88,7 +273,6 @@ fn handle_time_report(
     let mut stats: Vec<CategoryStats> = category_time
         .into_iter()
         .map(|(category, seconds)| {
-            let minutes = seconds / 60;
             let percentage = if total_time > 0 {
                 format!(
                     "{:.1}%",
@@ -99,18 +283,451 @@ fn handle_time_report(
             };
             CategoryStats {
                 category,
-                time_minutes: minutes,
+                time: durations.format(seconds),
+                seconds,
                 percentage,
             }
         })
         .collect();
 
-    stats.sort_by(|a, b| b.time_minutes.cmp(&a.time_minutes));
+    stats.sort_by(|a, b| b.seconds.cmp(&a.seconds));
 
     let table = Table::new(stats).to_string();
     println!("\n{table}");
 
-    println!("\nTotal tracked time: {} minutes", total_time / 60);
+    let app_names = AppDisplayNames::load(db)?;
+    let app_stats: Vec<AppStats> = InsightsGenerator::top_applications_from_spans(&spans, 5)
+        .into_iter()
+        .map(|(bundle_id, seconds)| AppStats {
+            app: app_names.display_name(&bundle_id).to_string(),
+            time: durations.format(seconds),
+        })
+        .collect();
+
+    println!("\nTop Applications:");
+    println!("{}", Table::new(app_stats));
+
+    println!("\nTotal tracked time: {}", durations.format(total_time));
+
+    let billable = BillableCategories::load(db)?;
+    if billable.is_configured() {
+        let totals = billable.totals(&spans);
+        println!(
+            "  Billable: {} / Non-billable: {}",
+            durations.format(totals.billable_seconds),
+            durations.format(totals.non_billable_seconds)
+        );
+    }
+
+    // Remote-desktop time is reported separately since the local app is only the client
+    let remote_time = InsightsGenerator::remote_time_from_spans(&spans);
+    if remote_time > 0 {
+        println!(
+            "  of which remote sessions: {}",
+            durations.format(remote_time)
+        );
+    }
+
+    Ok(())
+}
+
+/// Machine-readable time report, for `--format json` and `--format csv`
+#[derive(Debug, Serialize)]
+struct ReportSummary {
+    period: String,
+    /// Resolved range the report covers
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    total_seconds: u32,
+    categories: Vec<ReportTotal>,
+    projects: Vec<ReportTotal>,
+    work_items: Vec<WorkItemTotal>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReportTotal {
+    name: String,
+    seconds: u32,
+}
+
+/// Time on one work item in the report range, from [`IssueTimeStats`]
+#[derive(Debug, Serialize)]
+struct WorkItemTotal {
+    id: String,
+    system: String,
+    seconds: u32,
+    sessions: u32,
+    days_worked: u32,
+    first_activity: Option<DateTime<Utc>>,
+    last_activity: Option<DateTime<Utc>>,
+}
+
+impl From<IssueTimeStats> for WorkItemTotal {
+    fn from(stats: IssueTimeStats) -> Self {
+        Self {
+            id: stats.issue_id,
+            system: stats.issue_system,
+            seconds: stats.total_seconds,
+            sessions: stats.session_count,
+            days_worked: stats.days_worked,
+            first_activity: stats.first_activity,
+            last_activity: stats.last_activity,
+        }
+    }
+}
+
+impl ReportSummary {
+    fn to_csv(&self) -> String {
+        let mut csv = String::from("section,name,seconds\n");
+        let _ = writeln!(csv, "total,,{}", self.total_seconds);
+        for (section, totals) in [("category", &self.categories), ("project", &self.projects)] {
+            for total in totals {
+                let name = escape_csv(&total.name);
+                let _ = writeln!(csv, "{section},{name},{}", total.seconds);
+            }
+        }
+        for item in &self.work_items {
+            let _ = writeln!(csv, "work_item,{},{}", escape_csv(&item.id), item.seconds);
+        }
+        csv
+    }
+}
+
+/// Totals sorted by descending time, then by name for stable output
+fn sorted_totals(totals: impl IntoIterator<Item = (String, u32)>) -> Vec<ReportTotal> {
+    let mut totals: Vec<ReportTotal> = totals
+        .into_iter()
+        .map(|(name, seconds)| ReportTotal { name, seconds })
+        .collect();
+    totals.sort_by(|a, b| b.seconds.cmp(&a.seconds).then_with(|| a.name.cmp(&b.name)));
+    totals
+}
+
+/// Aggregate the period into a [`ReportSummary`]
+///
+/// Work item totals count the spans whose primary work item it is, whether
+/// that is a detected work item or an issue candidate attached in review.
+fn build_report_summary(
+    db: &Database,
+    period: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<ReportSummary> {
+    let spans = db.get_activity_spans(start, end)?;
+    let category_time = InsightsGenerator::time_per_category_from_spans(&spans);
+
+    let names = project_names(db, spans.iter())?;
+    let mut project_time: HashMap<String, u32> = HashMap::new();
+    for span in &spans {
+        let name = span
+            .project_id
+            .and_then(|id| names.get(&id).cloned())
+            .unwrap_or_else(|| "(no project)".to_string());
+        *project_time.entry(name).or_insert(0) += span.duration_seconds;
+    }
+
+    let mut external_ids = Vec::new();
+    for work_item_id in spans.iter().filter_map(|s| s.work_item_id) {
+        let external_id = match db.get_work_item_by_id(work_item_id)? {
+            Some(item) => Some(item.external_id),
+            None => db
+                .get_issue_candidate_by_id(work_item_id)?
+                .map(|issue| issue.external_id),
+        };
+        if let Some(external_id) = external_id {
+            if !external_ids.contains(&external_id) {
+                external_ids.push(external_id);
+            }
+        }
+    }
+    let mut work_items = Vec::with_capacity(external_ids.len());
+    for external_id in &external_ids {
+        let stats = db.issue_time_stats(external_id, start, end)?;
+        work_items.push(WorkItemTotal::from(stats));
+    }
+    work_items.sort_by(|a, b| b.seconds.cmp(&a.seconds).then_with(|| a.id.cmp(&b.id)));
+
+    Ok(ReportSummary {
+        period: period.to_string(),
+        start,
+        end,
+        total_seconds: category_time.values().sum(),
+        categories: sorted_totals(category_time),
+        projects: sorted_totals(project_time),
+        work_items,
+    })
+}
+
+/// Dimension used for the columns of a split report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SplitBy {
+    /// Rows are categories, columns are projects
+    Project,
+    /// Rows are projects, columns are categories
+    Category,
+}
+
+impl SplitBy {
+    fn parse(value: &str) -> Result<Self> {
+        match value {
+            "project" => Ok(Self::Project),
+            "category" => Ok(Self::Category),
+            _ => anyhow::bail!("Unknown split: {value}. Use 'project' or 'category'"),
+        }
+    }
+}
+
+/// Time matrix with one cell per (row, column), in seconds
+#[derive(Debug)]
+struct TimeMatrix {
+    rows: Vec<String>,
+    columns: Vec<String>,
+    cells: Vec<Vec<u32>>,
+}
+
+impl TimeMatrix {
+    fn row_total(&self, row: usize) -> u32 {
+        self.cells[row].iter().sum()
+    }
+
+    fn column_total(&self, column: usize) -> u32 {
+        self.cells.iter().map(|cells| cells[column]).sum()
+    }
+
+    fn grand_total(&self) -> u32 {
+        self.cells.iter().flatten().sum()
+    }
+}
+
+/// Build a category x project matrix from spans
+///
+/// Columns are ordered by total time; beyond `max_columns` the smallest
+/// columns are folded into a single "Other" column so totals still reconcile.
+fn build_time_matrix(
+    spans: &[ActivitySpan],
+    split_by: SplitBy,
+    project_names: &HashMap<Uuid, String>,
+    max_columns: usize,
+) -> TimeMatrix {
+    let project_label = |id: Option<Uuid>| {
+        id.and_then(|id| project_names.get(&id).cloned())
+            .unwrap_or_else(|| "(no project)".to_string())
+    };
+
+    let mut cells: HashMap<(String, String), u32> = HashMap::new();
+    for ((category, project_id), seconds) in
+        InsightsGenerator::time_per_category_and_project_from_spans(spans)
+    {
+        let key = match split_by {
+            SplitBy::Project => (category, project_label(project_id)),
+            SplitBy::Category => (project_label(project_id), category),
+        };
+        *cells.entry(key).or_insert(0) += seconds;
+    }
+
+    let mut row_totals: HashMap<String, u32> = HashMap::new();
+    let mut column_totals: HashMap<String, u32> = HashMap::new();
+    for ((row, column), seconds) in &cells {
+        *row_totals.entry(row.clone()).or_insert(0) += seconds;
+        *column_totals.entry(column.clone()).or_insert(0) += seconds;
+    }
+
+    let rows = sorted_by_total(row_totals);
+    let mut columns = sorted_by_total(column_totals);
+    let folded = if columns.len() > max_columns {
+        columns.split_off(max_columns.saturating_sub(1))
+    } else {
+        Vec::new()
+    };
+    if !folded.is_empty() {
+        columns.push("Other".to_string());
+    }
+
+    let matrix_cells = rows
+        .iter()
+        .map(|row| {
+            let mut values: Vec<u32> = columns
+                .iter()
+                .map(|column| {
+                    cells
+                        .get(&(row.clone(), column.clone()))
+                        .copied()
+                        .unwrap_or(0)
+                })
+                .collect();
+            if !folded.is_empty() {
+                let other: u32 = folded
+                    .iter()
+                    .filter_map(|column| cells.get(&(row.clone(), column.clone())))
+                    .sum();
+                if let Some(last) = values.last_mut() {
+                    *last = other;
+                }
+            }
+            values
+        })
+        .collect();
+
+    TimeMatrix {
+        rows,
+        columns,
+        cells: matrix_cells,
+    }
+}
+
+/// Sort keys by descending total, then by name for stable output
+fn sorted_by_total(totals: HashMap<String, u32>) -> Vec<String> {
+    let mut sorted: Vec<_> = totals.into_iter().collect();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    sorted.into_iter().map(|(key, _)| key).collect()
+}
+
+/// Generate a category x project matrix report
+fn handle_split_report(
+    db: &Database,
+    period: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    split_by: SplitBy,
+    durations: DurationFormat,
+) -> Result<()> {
+    let spans = db.get_activity_spans(start, end)?;
+
+    if spans.is_empty() {
+        println!("No activities recorded for period: {period}");
+        return Ok(());
+    }
+
+    let project_names = project_names(db, spans.iter())?;
+    let matrix = build_time_matrix(&spans, split_by, &project_names, MAX_MATRIX_COLUMNS);
+
+    let corner = match split_by {
+        SplitBy::Project => "Category \\ Project",
+        SplitBy::Category => "Project \\ Category",
+    };
+    let mut builder = Builder::default();
+    let mut header = vec![corner.to_string()];
+    header.extend(matrix.columns.iter().cloned());
+    header.push("Total".to_string());
+    builder.push_record(header);
+
+    for (i, row) in matrix.rows.iter().enumerate() {
+        let mut record = vec![row.clone()];
+        record.extend(matrix.cells[i].iter().map(|s| durations.format(*s)));
+        record.push(durations.format(matrix.row_total(i)));
+        builder.push_record(record);
+    }
+
+    let mut totals = vec!["Total".to_string()];
+    totals.extend((0..matrix.columns.len()).map(|c| durations.format(matrix.column_total(c))));
+    totals.push(durations.format(matrix.grand_total()));
+    builder.push_record(totals);
+
+    println!("\nTime Tracking Report: {period}");
+    println!("{}", "\u{2550}".repeat(28));
+    println!("\n{}", builder.build());
+
+    Ok(())
+}
+
+/// Names of the projects the spans belong to
+fn project_names<'a>(
+    db: &Database,
+    spans: impl Iterator<Item = &'a ActivitySpan>,
+) -> Result<HashMap<Uuid, String>> {
+    let mut names = HashMap::new();
+    for project_id in spans.filter_map(|s| s.project_id) {
+        if let Entry::Vacant(entry) = names.entry(project_id) {
+            if let Some(project) = db.get_project(project_id)? {
+                entry.insert(project.name);
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Spans attributed to an issue (primary or secondary), oldest first, with their total
+fn issue_spans(issue_id: Uuid, spans: &[ActivitySpan]) -> (Vec<&ActivitySpan>, u32) {
+    let mut attributed: Vec<&ActivitySpan> = spans
+        .iter()
+        .filter(|s| s.all_work_item_ids().contains(&issue_id))
+        .collect();
+    attributed.sort_by_key(|s| s.start_time);
+    let total = attributed.iter().map(|s| s.duration_seconds).sum();
+    (attributed, total)
+}
+
+/// Drill down into the time logged against one issue in the period
+fn handle_work_item_report(
+    db: &Database,
+    period: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    work_item: &str,
+    durations: DurationFormat,
+) -> Result<()> {
+    let issue = resolve_issue(db, work_item, None)?;
+    let all_spans = db.get_activity_spans(start, end)?;
+    let (spans, total) = issue_spans(issue.id, &all_spans);
+
+    println!(
+        "\nTime on #{} - {} ({}): {period}",
+        issue.external_id, issue.title, issue.external_system
+    );
+    println!("{}", "\u{2550}".repeat(28));
+    println!(
+        "Total: {} across {} spans",
+        durations.format(total),
+        spans.len()
+    );
+
+    if !spans.is_empty() {
+        let app_names = AppDisplayNames::load(db)?;
+        let project_names = project_names(db, spans.iter().copied())?;
+
+        let mut builder = Builder::default();
+        builder.push_record(["Date", "Duration", "Project", "Description"]);
+        for span in &spans {
+            let project = span.project_id.and_then(|id| project_names.get(&id));
+            builder.push_record([
+                span.start_time.format("%Y-%m-%d %H:%M").to_string(),
+                durations.format(span.duration_seconds),
+                project.map_or_else(|| "-".to_string(), Clone::clone),
+                format!(
+                    "{} in {}",
+                    span.category,
+                    app_names.display_name(&span.app_bundle_id)
+                ),
+            ]);
+        }
+        println!("\n{}", builder.build());
+    }
+
+    let mut blocks = Vec::new();
+    db.for_each_time_block(start, end, |block| {
+        if block.confirmed && block.work_item_ids.contains(&issue.id) {
+            blocks.push(block);
+        }
+        Ok(())
+    })?;
+    if !blocks.is_empty() {
+        println!("\nConfirmed blocks:");
+        for block in &blocks {
+            let seconds = (block.end_time - block.start_time).num_seconds().max(0);
+            let status = if db.is_time_block_synced(block.id)? {
+                "synced"
+            } else {
+                "not synced"
+            };
+            println!(
+                "  {} - {}  {:>8}  {status:<10}  {}",
+                block.start_time.format("%Y-%m-%d %H:%M"),
+                block.end_time.format("%H:%M"),
+                durations.format(u32::try_from(seconds).unwrap_or(u32::MAX)),
+                block.description
+            );
+        }
+    }
 
     Ok(())
 }
@@ -121,6 +738,7 @@ fn handle_outcome_report(
     period: &str,
     start: DateTime<Utc>,
     end: DateTime<Utc>,
+    durations: DurationFormat,
 ) -> Result<()> {
     // Get all sessions in the period
     let sessions = db.get_claude_sessions(start, end)?;
@@ -154,7 +772,7 @@ fn handle_outcome_report(
             session_rows.push(SessionOutcomeRow {
                 session: truncate_id(&session.session_id),
                 project: project_name,
-                duration: format_duration(session.duration_seconds()),
+                duration: durations.format(session.duration_seconds()),
                 outcomes: summary.to_string(),
             });
         }
@@ -185,18 +803,6 @@ fn handle_outcome_report(
     Ok(())
 }
 
-/// Format duration in human-readable form
-fn format_duration(seconds: u32) -> String {
-    let hours = seconds / 3600;
-    let minutes = (seconds % 3600) / 60;
-
-    if hours > 0 {
-        format!("{hours}h {minutes}m")
-    } else {
-        format!("{minutes}m")
-    }
-}
-
 /// Truncate session ID for display
 fn truncate_id(id: &str) -> String {
     if id.len() > 12 {
@@ -222,3 +828,267 @@ pub fn handle_categories_command() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(category: &str, project_id: Option<Uuid>, seconds: u32) -> ActivitySpan {
+        let mut span = ActivitySpan::new(
+            "com.microsoft.VSCode".to_string(),
+            category.to_string(),
+            Utc::now(),
+            project_id,
+            None,
+            None,
+        );
+        span.duration_seconds = seconds;
+        span
+    }
+
+    fn assert_reconciles(matrix: &TimeMatrix, expected_total: u32) {
+        let row_sum: u32 = (0..matrix.rows.len()).map(|r| matrix.row_total(r)).sum();
+        let column_sum: u32 = (0..matrix.columns.len())
+            .map(|c| matrix.column_total(c))
+            .sum();
+        assert_eq!(row_sum, expected_total);
+        assert_eq!(column_sum, expected_total);
+        assert_eq!(matrix.grand_total(), expected_total);
+    }
+
+    #[test]
+    fn test_timezone_change_detected_from_stored_sessions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("toki.db"))).unwrap();
+        let set_timezone = |value: &str| {
+            let mut settings = db.get_settings().unwrap();
+            settings.timezone = value.parse().unwrap();
+            db.update_settings(&settings).unwrap();
+            settings.timezone
+        };
+        let (start, end) = (Utc::now() - Duration::days(7), Utc::now());
+
+        let tokyo = set_timezone("+09:00");
+        db.create_session(end - Duration::days(2)).unwrap();
+        db.create_session(end - Duration::days(1)).unwrap();
+        let captured = db.get_session_timezones(start, end).unwrap();
+        assert_eq!(timezone_mismatch(tokyo, &captured), None);
+
+        let taipei = set_timezone("+08:00");
+        let captured = db.get_session_timezones(start, end).unwrap();
+        assert_eq!(captured, [(tokyo, 2)]);
+        assert_eq!(timezone_mismatch(taipei, &captured), Some(tokyo));
+
+        // Once new sessions use the new zone, the old one is still reported
+        db.create_session(end - Duration::hours(1)).unwrap();
+        let captured = db.get_session_timezones(start, end).unwrap();
+        assert_eq!(timezone_mismatch(taipei, &captured), Some(tokyo));
+    }
+
+    #[test]
+    fn test_split_by_project_matrix_cells_and_totals() {
+        let api = Uuid::new_v4();
+        let web = Uuid::new_v4();
+        let names = HashMap::from([(api, "api".to_string()), (web, "web".to_string())]);
+        let spans = vec![
+            span("Development", Some(api), 3600),
+            span("Development", Some(web), 1200),
+            span("Communication", Some(api), 600),
+            span("Development", None, 300),
+        ];
+
+        let matrix = build_time_matrix(&spans, SplitBy::Project, &names, MAX_MATRIX_COLUMNS);
+
+        assert_eq!(matrix.rows, vec!["Development", "Communication"]);
+        assert_eq!(matrix.columns, vec!["api", "web", "(no project)"]);
+        assert_eq!(matrix.cells[0], vec![3600, 1200, 300]);
+        assert_eq!(matrix.cells[1], vec![600, 0, 0]);
+        assert_eq!(matrix.row_total(0), 5100);
+        assert_eq!(matrix.column_total(0), 4200);
+        assert_reconciles(&matrix, 5700);
+
+        let transposed = build_time_matrix(&spans, SplitBy::Category, &names, MAX_MATRIX_COLUMNS);
+        assert_eq!(transposed.columns, vec!["Development", "Communication"]);
+        assert_reconciles(&transposed, 5700);
+    }
+
+    #[test]
+    fn test_work_item_spans_total_and_order() {
+        let issue = Uuid::new_v4();
+        let now = Utc::now();
+        let at = |minutes_ago: i64, work_item: Option<Uuid>, seconds: u32| {
+            let mut span = span("Development", None, seconds);
+            span.start_time = now - Duration::minutes(minutes_ago);
+            span.work_item_id = work_item;
+            span
+        };
+        let mut secondary = at(90, None, 300);
+        secondary.add_work_item(issue);
+        let spans = vec![
+            at(30, Some(issue), 1200),
+            at(60, Some(Uuid::new_v4()), 900),
+            secondary,
+            at(120, Some(issue), 600),
+            at(10, None, 400),
+        ];
+
+        let (attributed, total) = issue_spans(issue, &spans);
+
+        assert_eq!(total, 1200 + 300 + 600);
+        assert_eq!(
+            total,
+            attributed.iter().map(|s| s.duration_seconds).sum::<u32>()
+        );
+        let durations: Vec<u32> = attributed.iter().map(|s| s.duration_seconds).collect();
+        assert_eq!(durations, vec![600, 300, 1200]);
+        assert!(attributed
+            .windows(2)
+            .all(|pair| pair[0].start_time <= pair[1].start_time));
+    }
+
+    #[test]
+    fn test_split_matrix_folds_extra_columns_into_other() {
+        let names = HashMap::new();
+        let spans: Vec<ActivitySpan> = (1..=5u32)
+            .map(|i| span(&format!("Category {i}"), None, i * 60))
+            .collect();
+
+        let matrix = build_time_matrix(&spans, SplitBy::Category, &names, 3);
+
+        assert_eq!(matrix.columns, vec!["Category 5", "Category 4", "Other"]);
+        assert_eq!(matrix.cells[0], vec![300, 240, 360]);
+        assert_reconciles(&matrix, 900);
+    }
+
+    #[test]
+    fn test_block_event_uses_dominant_category_and_issue_fallback() {
+        let end = Utc::now();
+        let mut block = TimeBlock::manual(end - Duration::hours(1), end, "  ".to_string());
+        block.tags = vec!["backend".to_string()];
+
+        let mut coding = span("Development", None, 2400);
+        coding.start_time = end - Duration::minutes(50);
+        let mut chat = span("Communication", None, 600);
+        chat.start_time = end - Duration::minutes(10);
+        let mut outside = span("Communication", None, 7200);
+        outside.start_time = end - Duration::hours(4);
+
+        let category = dominant_category(&block, &[coding, chat, outside]);
+        assert_eq!(category.as_deref(), Some("Development"));
+
+        let issues = vec!["#PROJ-42 Fix login".to_string()];
+        let event = block_event(&block, category.as_deref(), &issues);
+        assert_eq!(event.summary, "#PROJ-42 Fix login");
+        assert_eq!(event.uid, format!("{}@toki", block.id));
+        assert_eq!(event.start, block.start_time);
+        assert_eq!(
+            event.description,
+            "Category: Development\nIssues: #PROJ-42 Fix login\nTags: backend"
+        );
+    }
+
+    #[test]
+    fn test_report_summary_totals_and_csv() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("toki.db"))).unwrap();
+        let project = db.get_or_create_project("toki", "/src/toki").unwrap();
+        let issue = toki_storage::WorkItem::new("PROJ-9".to_string(), "GitBranch".to_string());
+        db.upsert_work_item(&issue).unwrap();
+
+        let end = Utc::now();
+        let start = end - Duration::hours(6);
+        let record = |category: &str, project_id, work_item_id, hours_ago: i64, minutes: i64| {
+            let from = end - Duration::hours(hours_ago);
+            let span = ActivitySpan::new(
+                "com.microsoft.VSCode".to_string(),
+                category.to_string(),
+                from,
+                project_id,
+                work_item_id,
+                None,
+            );
+            db.create_activity_span(&span).unwrap();
+            db.finalize_activity_span(span.id, from + Duration::minutes(minutes))
+                .unwrap();
+        };
+        record("Development", Some(project.id), Some(issue.id), 5, 60);
+        record("Development", Some(project.id), None, 3, 30);
+        record("Communication", None, None, 2, 15);
+
+        let summary = build_report_summary(&db, "today", start, end).unwrap();
+        assert_eq!((summary.start, summary.end), (start, end));
+        assert_eq!(summary.total_seconds, 105 * 60);
+        let totals = |totals: &[ReportTotal]| {
+            totals
+                .iter()
+                .map(|t| (t.name.clone(), t.seconds))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            totals(&summary.categories),
+            [
+                ("Development".to_string(), 5400),
+                ("Communication".to_string(), 900)
+            ]
+        );
+        assert_eq!(
+            totals(&summary.projects),
+            [
+                ("toki".to_string(), 5400),
+                ("(no project)".to_string(), 900)
+            ]
+        );
+        assert_eq!(summary.work_items.len(), 1);
+        assert_eq!(summary.work_items[0].id, "PROJ-9");
+        assert_eq!(summary.work_items[0].seconds, 3600);
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["total_seconds"], 6300);
+        assert_eq!(json["start"], serde_json::to_value(start).unwrap());
+
+        assert_eq!(
+            summary.to_csv(),
+            "section,name,seconds\n\
+             total,,6300\n\
+             category,Development,5400\n\
+             category,Communication,900\n\
+             project,toki,5400\n\
+             project,(no project),900\n\
+             work_item,PROJ-9,3600\n"
+        );
+    }
+
+    #[test]
+    fn test_report_summary_counts_candidate_linked_spans() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("toki.db"))).unwrap();
+        let project = db.get_or_create_project("toki", "/src/toki").unwrap();
+        let issue = toki_storage::IssueCandidate::new(
+            project.id,
+            "PROJ-12".to_string(),
+            "plane".to_string(),
+            "Fix login".to_string(),
+        );
+        db.upsert_issue_candidate(&issue).unwrap();
+
+        let end = Utc::now();
+        let from = end - Duration::hours(2);
+        let span = ActivitySpan::new(
+            "com.microsoft.VSCode".to_string(),
+            "Development".to_string(),
+            from,
+            Some(project.id),
+            Some(issue.id),
+            None,
+        );
+        db.create_activity_span(&span).unwrap();
+        db.finalize_activity_span(span.id, from + Duration::minutes(40))
+            .unwrap();
+
+        let summary = build_report_summary(&db, "today", end - Duration::hours(6), end).unwrap();
+        assert_eq!(summary.work_items.len(), 1);
+        assert_eq!(summary.work_items[0].id, "PROJ-12");
+        assert_eq!(summary.work_items[0].system, "plane");
+        assert_eq!(summary.work_items[0].seconds, 40 * 60);
+    }
+}
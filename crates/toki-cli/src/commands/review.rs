@@ -1,9 +1,10 @@
 /// Review command handler - AI-powered daily activity review
 use anyhow::Result;
-use chrono::{NaiveDate, TimeZone, Utc};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use std::sync::Arc;
 use toki_ai::{ActivitySegment, ActivitySignals, SmartIssueMatcher, SuggestedIssue, TimeAnalyzer};
-use toki_storage::{Database, TimeBlock};
+use toki_core::classifier::UNCERTAIN_CATEGORY_CONFIDENCE;
+use toki_storage::{ActivitySpan, ClassificationRule, Database, PatternType, TimeBlock};
 
 /// Handle the review command - show daily activity summary with AI suggestions
 #[allow(clippy::cognitive_complexity)]
@@ -12,27 +13,28 @@ pub fn handle_review_command(
     date: Option<String>,
     verbose: bool,
     confirm_all: bool,
+    uncertain: bool,
+    correct: Option<&str>,
 ) -> Result<()> {
     let db = Arc::new(Database::new(None)?);
 
-    // Parse date or use today
+    let settings = db.get_settings()?;
+
+    // Parse date or use the current work day
     let target_date = if let Some(date_str) = date {
         NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
             .map_err(|_| anyhow::anyhow!("Invalid date format. Use YYYY-MM-DD"))?
     } else {
-        Utc::now().date_naive()
+        settings.work_date(Utc::now())
     };
 
-    // Get time range for the date
-    let start = target_date
-        .and_hms_opt(0, 0, 0)
-        .ok_or_else(|| anyhow::anyhow!("Invalid time"))?;
-    let end = target_date
-        .and_hms_opt(23, 59, 59)
-        .ok_or_else(|| anyhow::anyhow!("Invalid time"))?;
+    // Work day range, up to the last second before the next day starts
+    let (start_utc, next_day_start) = settings.work_day_range(target_date);
+    let end_utc = next_day_start - Duration::seconds(1);
 
-    let start_utc = Utc.from_utc_datetime(&start);
-    let end_utc = Utc.from_utc_datetime(&end);
+    if uncertain {
+        return handle_uncertain_review(&db, target_date, start_utc, end_utc, correct);
+    }
 
     // Fetch activity spans for the day
     let spans = db.get_activity_spans(start_utc, end_utc)?;
@@ -45,40 +47,24 @@ pub fn handle_review_command(
     // Convert ActivitySpan to ActivitySegment for AI analysis
     let segments: Vec<ActivitySegment> = spans
         .iter()
-        .filter_map(|span| {
-            let end_time = span.end_time?;
-            Some(ActivitySegment {
-                start_time: span.start_time,
-                end_time,
-                project_name: None, // Would need to look up project
-                category: span.category.clone(),
-                edited_files: span
-                    .context
-                    .as_ref()
-                    .map(|c| c.edited_files.clone())
-                    .unwrap_or_default(),
-                git_commits: span
-                    .context
-                    .as_ref()
-                    .map(|c| c.git_commits.clone())
-                    .unwrap_or_default(),
-                git_branch: span.context.as_ref().and_then(|c| c.git_branch.clone()),
-                browser_urls: span
-                    .context
-                    .as_ref()
-                    .map(|c| c.browser_urls.clone())
-                    .unwrap_or_default(),
-            })
-        })
+        .filter_map(ActivitySegment::from_span)
         .collect();
+    record_work_patterns(&db, &spans)?;
 
     // Analyze with AI
-    let analyzer = TimeAnalyzer::new();
+    let analyzer = TimeAnalyzer::with_config(
+        Duration::minutes(i64::from(settings.analyzer_min_block_minutes)),
+        Duration::minutes(i64::from(settings.analyzer_merge_gap_minutes)),
+    )
+    .with_focus_min_seconds(settings.focus_min_seconds)
+    .with_day_boundary(settings.timezone, settings.day_boundary_hour);
     let mut summary = analyzer.generate_daily_summary(target_date, &segments);
 
     // Compute Gravity/Relevance for unclassified or generic activities
     // This is the "Quiet Tech" magic: infer relevance without rules
-    if let Ok(gravity_calc) = toki_ai::GravityCalculator::new(db.clone()) {
+    if let Ok(gravity_calc) =
+        toki_ai::GravityCalculator::new(db.clone(), settings.embeddings_enabled)
+    {
         // We need to compute gravity for each segment against its likely project
         // For simplicity in this phase, we'll just check against the most active project of the day
         let top_project_id = if let Ok(projects) = db
@@ -141,6 +127,14 @@ pub fn handle_review_command(
         classified_pct,
         summary.unclassified_seconds / 60
     );
+    if let Some(ratio) = summary.focus_ratio() {
+        println!(
+            "Focus: {}m ({:.0}%), Fragmented: {}m",
+            summary.focus_seconds() / 60,
+            ratio * 100.0,
+            summary.fragmented_seconds() / 60
+        );
+    }
 
     // Project breakdown from project_time table (accurate multi-window tracking)
     let date_str = target_date.format("%Y-%m-%d").to_string();
@@ -181,7 +175,12 @@ pub fn handle_review_command(
         println!("{}", "-".repeat(60));
 
         // Try to initialize SmartIssueMatcher for AI-based issue matching
-        let smart_matcher = SmartIssueMatcher::new(db.clone()).ok();
+        let smart_matcher = SmartIssueMatcher::with_pattern(
+            db.clone(),
+            settings.issue_id_pattern.as_deref(),
+            settings.embeddings_enabled,
+        )
+        .ok();
 
         // Get the top project for smart matching
         let date_str_for_matching = target_date.format("%Y-%m-%d").to_string();
@@ -198,11 +197,12 @@ pub fn handle_review_command(
             let confidence_pct = (block.confidence.clamp(0.0, 1.0) * 100.0) as u32;
 
             println!(
-                "\n{}. {} - {} ({}m) - {}% confidence",
+                "\n{}. {} - {} ({}m) [{}] - {}% confidence",
                 i + 1,
                 start,
                 end,
                 duration_mins,
+                block.focus,
                 confidence_pct
             );
             println!("   {}", block.suggested_description);
@@ -246,6 +246,11 @@ pub fn handle_review_command(
                                     .collect(),
                                 // Use block description as window title context for semantic matching
                                 window_titles: vec![block.suggested_description.clone()],
+                                terminal_commands: block_segments
+                                    .iter()
+                                    .flat_map(|s| s.terminal_commands.clone())
+                                    .take(5)
+                                    .collect(),
                             };
 
                             // Find matches using AI
@@ -310,20 +315,21 @@ pub fn handle_review_command(
         println!("\n{}", "-".repeat(60));
         println!("Saving {} time blocks...", summary.suggested_blocks.len());
 
+        // Confirming everything at once is a bulk operation: only attach confident matches
+        let min_confidence = db.get_settings()?.bulk_attach_min_confidence();
         let mut saved_count = 0;
         for suggested in &summary.suggested_blocks {
             // Convert suggested issues to issue candidate UUIDs
-            let work_item_ids: Vec<_> = suggested
-                .suggested_issues
-                .iter()
-                .filter_map(|si| {
-                    // Try to find the issue candidate by external ID (supports all systems)
-                    db.get_issue_candidate_by_external_id(&si.issue_id)
-                        .ok()
-                        .flatten()
-                        .map(|ic| ic.id)
-                })
-                .collect();
+            let work_item_ids: Vec<_> =
+                attachable_issues(&suggested.suggested_issues, min_confidence)
+                    .filter_map(|si| {
+                        // Try to find the issue candidate by external ID (supports all systems)
+                        db.get_issue_candidate_by_external_id(&si.issue_id)
+                            .ok()
+                            .flatten()
+                            .map(|ic| ic.id)
+                    })
+                    .collect();
 
             // Use the ai_suggested constructor, then mark as confirmed
             let mut time_block = TimeBlock::ai_suggested(
@@ -356,3 +362,251 @@ pub fn handle_review_command(
 
     Ok(())
 }
+
+/// Store the detected work pattern on each finished span
+///
+/// Spans tracked before patterns were recorded get one here, and spans whose
+/// context was enriched since they finished are re-detected.
+fn record_work_patterns(db: &Database, spans: &[ActivitySpan]) -> Result<()> {
+    for span in spans {
+        if let Some(pattern) = TimeAnalyzer::detect_span_pattern(span) {
+            db.set_activity_span_work_pattern(span.id, pattern.as_str())?;
+        }
+    }
+    Ok(())
+}
+
+/// Time one app spent in low-confidence categories
+#[derive(Debug, Clone, PartialEq)]
+struct UncertainApp {
+    app_bundle_id: String,
+    category: String,
+    seconds: u32,
+    spans: usize,
+    min_confidence: f32,
+}
+
+/// Low-confidence spans in a range, grouped by app, most time first
+fn uncertain_apps(
+    db: &Database,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<UncertainApp>> {
+    let mut apps: Vec<UncertainApp> = Vec::new();
+    for (span, confidence) in
+        db.get_uncertain_activity_spans(start, end, UNCERTAIN_CATEGORY_CONFIDENCE)?
+    {
+        if let Some(app) = apps
+            .iter_mut()
+            .find(|a| a.app_bundle_id == span.app_bundle_id)
+        {
+            app.seconds += span.duration_seconds;
+            app.spans += 1;
+            app.min_confidence = app.min_confidence.min(confidence);
+        } else {
+            apps.push(UncertainApp {
+                app_bundle_id: span.app_bundle_id,
+                category: span.category,
+                seconds: span.duration_seconds,
+                spans: 1,
+                min_confidence: confidence,
+            });
+        }
+    }
+    apps.sort_by_key(|a| std::cmp::Reverse(a.seconds));
+    Ok(apps)
+}
+
+/// Teach the classifier an app's category and fix its uncertain spans in the range
+///
+/// Saves (or updates) a bundle-ID rule, so future spans are classified the same
+/// way. Returns the number of spans corrected.
+fn correct_app_category(
+    db: &Database,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    app_bundle_id: &str,
+    category: &str,
+) -> Result<usize> {
+    let rule = match db.find_rule_by_pattern(app_bundle_id, &PatternType::BundleId)? {
+        Some(mut existing) => {
+            existing.category = category.to_string();
+            existing
+        }
+        None => ClassificationRule::from_correction(
+            app_bundle_id.to_string(),
+            PatternType::BundleId,
+            category.to_string(),
+        ),
+    };
+    db.save_classification_rule(&rule)?;
+
+    let mut corrected = 0;
+    for (span, _) in db.get_uncertain_activity_spans(start, end, UNCERTAIN_CATEGORY_CONFIDENCE)? {
+        if span.app_bundle_id == app_bundle_id {
+            db.update_activity_span_category(span.id, category)?;
+            db.set_activity_span_category_confidence(span.id, 1.0)?;
+            corrected += 1;
+        }
+    }
+    Ok(corrected)
+}
+
+/// `toki review --uncertain`: list low-confidence spans, or apply a correction
+fn handle_uncertain_review(
+    db: &Database,
+    date: NaiveDate,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    correct: Option<&str>,
+) -> Result<()> {
+    if let Some(correction) = correct {
+        let (app, category) = correction
+            .split_once('=')
+            .map(|(app, category)| (app.trim(), category.trim()))
+            .filter(|(app, category)| !app.is_empty() && !category.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Invalid correction: {correction}. Use APP=CATEGORY"))?;
+        let corrected = correct_app_category(db, start, end, app, category)?;
+        println!("Learned: '{app}' -> '{category}' ({corrected} span(s) on {date} corrected)");
+        println!("Restart the daemon to apply the rule to new activity: toki stop && toki start");
+        return Ok(());
+    }
+
+    let apps = uncertain_apps(db, start, end)?;
+    if apps.is_empty() {
+        println!("No uncertain classifications on {date}.");
+        return Ok(());
+    }
+
+    let format = db.get_settings()?.duration_format;
+    println!("Uncertain classifications on {date}:\n");
+    println!(
+        "{:<40} {:<16} {:>10} {:>6} CONF",
+        "APP", "CATEGORY", "TIME", "SPANS"
+    );
+    for app in &apps {
+        println!(
+            "{:<40} {:<16} {:>10} {:>6} {:.2}",
+            app.app_bundle_id,
+            app.category,
+            format.format(app.seconds),
+            app.spans,
+            app.min_confidence
+        );
+    }
+    println!("\nCorrect one with: toki review --uncertain --correct <APP>=<CATEGORY>");
+    Ok(())
+}
+
+/// Suggested issues confident enough to attach without a human looking at each one
+fn attachable_issues(
+    issues: &[SuggestedIssue],
+    min_confidence: f32,
+) -> impl Iterator<Item = &SuggestedIssue> {
+    issues
+        .iter()
+        .filter(move |si| si.confidence >= min_confidence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use toki_storage::Settings;
+
+    #[test]
+    fn test_confirm_all_uses_backfill_threshold_over_live() {
+        let settings = Settings {
+            auto_attach_min_confidence: 0.5,
+            backfill_min_confidence: 0.85,
+            ..Settings::default_settings()
+        };
+        let issues: Vec<SuggestedIssue> = [("PROJ-1", 0.9), ("PROJ-2", 0.7), ("PROJ-3", 0.55)]
+            .into_iter()
+            .map(|(id, confidence)| SuggestedIssue {
+                issue_id: id.to_string(),
+                confidence,
+                reason: String::new(),
+            })
+            .collect();
+
+        let attached: Vec<&str> = attachable_issues(&issues, settings.bulk_attach_min_confidence())
+            .map(|si| si.issue_id.as_str())
+            .collect();
+
+        // PROJ-2 and PROJ-3 pass the live threshold but not the bulk one
+        assert_eq!(attached, vec!["PROJ-1"]);
+    }
+
+    fn finished_span(db: &Database, app: &str, category: &str, confidence: f32) -> uuid::Uuid {
+        let start = Utc.with_ymd_and_hms(2025, 3, 14, 9, 0, 0).unwrap();
+        let span = toki_storage::ActivitySpan::new(
+            app.to_string(),
+            category.to_string(),
+            start,
+            None,
+            None,
+            None,
+        );
+        db.create_activity_span(&span).unwrap();
+        db.finalize_activity_span(span.id, start + chrono::Duration::minutes(30))
+            .unwrap();
+        db.set_activity_span_category_confidence(span.id, confidence)
+            .unwrap();
+        span.id
+    }
+
+    fn day() -> (DateTime<Utc>, DateTime<Utc>) {
+        (
+            Utc.with_ymd_and_hms(2025, 3, 14, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 3, 14, 23, 59, 59).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_low_confidence_spans_surfaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(dir.path().join("toki.db"))).unwrap();
+        finished_span(&db, "com.example.Unknown", "Uncategorized", 0.1);
+        finished_span(&db, "com.example.Unknown", "Uncategorized", 0.3);
+        finished_span(&db, "com.microsoft.VSCode", "Coding", 0.8);
+        let (start, end) = day();
+
+        let apps = uncertain_apps(&db, start, end).unwrap();
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps[0].app_bundle_id, "com.example.Unknown");
+        assert_eq!(apps[0].spans, 2);
+        assert_eq!(apps[0].seconds, 3600);
+        assert!((apps[0].min_confidence - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_correction_creates_rule_and_fixes_spans() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(dir.path().join("toki.db"))).unwrap();
+        let span_id = finished_span(&db, "com.example.Figma", "Uncategorized", 0.1);
+        let (start, end) = day();
+
+        let corrected =
+            correct_app_category(&db, start, end, "com.example.Figma", "Design").unwrap();
+        assert_eq!(corrected, 1);
+
+        let rule = db
+            .find_rule_by_pattern("com.example.Figma", &PatternType::BundleId)
+            .unwrap()
+            .unwrap();
+        assert_eq!(rule.category, "Design");
+        assert_eq!(
+            db.get_activity_span(span_id).unwrap().unwrap().category,
+            "Design"
+        );
+        assert!(uncertain_apps(&db, start, end).unwrap().is_empty());
+
+        // Correcting again updates the same rule instead of adding another
+        finished_span(&db, "com.example.Figma", "Uncategorized", 0.1);
+        correct_app_category(&db, start, end, "com.example.Figma", "Research").unwrap();
+        let rules = db.get_classification_rules().unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].category, "Research");
+    }
+}
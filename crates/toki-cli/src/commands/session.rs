@@ -7,7 +7,7 @@ use anyhow::{Context, Result};
 use chrono::{Duration, Utc};
 use clap::Subcommand;
 use toki_storage::models::{IssueRelationship, OutcomeSummary, OutcomeType, SessionIssue, SessionOutcome};
-use toki_storage::Database;
+use toki_storage::{Database, Session};
 
 #[derive(Subcommand, Debug)]
 pub enum SessionAction {
@@ -75,6 +75,14 @@ pub enum SessionAction {
         #[arg(long, default_value = "worked_on")]
         relationship: String,
     },
+    /// Discard the current tracking session (e.g. a demo on someone else's machine)
+    ///
+    /// The session is closed and excluded from reports and insights.
+    Abort {
+        /// Also delete the session's activity spans
+        #[arg(long)]
+        delete_spans: bool,
+    },
 }
 
 /// Handle session commands
@@ -97,9 +105,53 @@ pub fn handle_session_command(action: SessionAction) -> Result<()> {
             system,
             relationship,
         } => link_issue(&id, &issue, &system, &relationship),
+        SessionAction::Abort { delete_spans } => abort_session(delete_spans),
     }
 }
 
+/// Discard the current tracking session
+fn abort_session(delete_spans: bool) -> Result<()> {
+    let db = Database::new(None).context("Failed to open database")?;
+
+    let Some((session, removed_spans)) = discard_current_session(&db, delete_spans)? else {
+        println!("No active tracking session to abort.");
+        return Ok(());
+    };
+
+    println!(
+        "Discarded session started at {}",
+        session
+            .start_time
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M")
+    );
+    if delete_spans {
+        println!("Deleted {removed_spans} activity spans.");
+    } else {
+        println!("Its activity spans are kept but excluded from reports and insights.");
+    }
+
+    Ok(())
+}
+
+/// Discard the current session, optionally deleting its spans
+///
+/// Returns the discarded session and the number of deleted spans.
+fn discard_current_session(db: &Database, delete_spans: bool) -> Result<Option<(Session, usize)>> {
+    let Some(session) = db.get_current_session()? else {
+        return Ok(None);
+    };
+
+    db.discard_session(session.id, Utc::now())?;
+    let removed = if delete_spans {
+        db.delete_activity_spans_by_session(session.id)?
+    } else {
+        0
+    };
+
+    Ok(Some((session, removed)))
+}
+
 /// Start a new Claude Code session
 fn start_session(session_id: &str, project_path: Option<&str>) -> Result<()> {
     let db = Database::new(None).context("Failed to open database")?;
@@ -447,3 +499,93 @@ fn truncate_id(id: &str) -> String {
         id.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toki_storage::ActivitySpan;
+
+    fn test_db() -> (tempfile::TempDir, Database) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+        (temp_dir, db)
+    }
+
+    fn session_with_span(db: &Database, start: chrono::DateTime<Utc>) -> uuid::Uuid {
+        let session_id = db.create_session(start).unwrap();
+        let mut span = ActivitySpan::new(
+            "com.apple.Safari".to_string(),
+            "Browser".to_string(),
+            start,
+            None,
+            None,
+            Some(session_id),
+        );
+        span.duration_seconds = 600;
+        db.create_activity_span(&span).unwrap();
+        session_id
+    }
+
+    #[test]
+    fn test_discarded_session_excluded_from_aggregations() {
+        let (_dir, db) = test_db();
+        let now = Utc::now();
+        let kept = session_with_span(&db, now - Duration::hours(3));
+        db.finalize_session(kept, now - Duration::hours(2)).unwrap();
+        let aborted = session_with_span(&db, now - Duration::hours(1));
+
+        let (session, deleted) = discard_current_session(&db, false).unwrap().unwrap();
+        assert_eq!(session.id, aborted);
+        assert_eq!(deleted, 0);
+
+        let range = (now - Duration::days(1), now);
+        let sessions = db.get_sessions(range.0, range.1, true).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, kept);
+        assert!(db.get_current_session().unwrap().is_none());
+
+        let spans = db.get_activity_spans(range.0, range.1).unwrap();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].session_id, Some(kept));
+    }
+
+    #[test]
+    fn test_empty_sessions_excluded_unless_requested() {
+        let (_dir, db) = test_db();
+        let now = Utc::now();
+        let active = db.create_session(now - Duration::hours(2)).unwrap();
+        db.update_session_stats(active, 1800, 0, 0, &[], &[])
+            .unwrap();
+        db.finalize_session(active, now - Duration::hours(1))
+            .unwrap();
+        let empty = db.create_session(now - Duration::minutes(30)).unwrap();
+        db.finalize_session(empty, now - Duration::minutes(30))
+            .unwrap();
+
+        let range = (now - Duration::days(1), now);
+        let sessions = db.get_sessions(range.0, range.1, false).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, active);
+
+        let all = db.get_sessions(range.0, range.1, true).unwrap();
+        assert_eq!(all.len(), 2);
+    }
+
+    #[test]
+    fn test_abort_with_delete_spans_removes_session_spans() {
+        let (_dir, db) = test_db();
+        let session_id = session_with_span(&db, Utc::now() - Duration::hours(1));
+
+        let (_, deleted) = discard_current_session(&db, true).unwrap().unwrap();
+        assert_eq!(deleted, 1);
+        let remaining = db.get_activity_spans_by_session(session_id).unwrap();
+        assert!(remaining.is_empty());
+        assert!(db.is_session_discarded(session_id).unwrap());
+    }
+
+    #[test]
+    fn test_abort_without_active_session() {
+        let (_dir, db) = test_db();
+        assert!(discard_current_session(&db, false).unwrap().is_none());
+    }
+}
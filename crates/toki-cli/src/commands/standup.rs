@@ -12,23 +12,41 @@
 //! toki standup --format discord
 //! toki standup --format markdown
 //! toki standup --format json
+//!
+//! # Also list in-progress issues without logged time under "today"
+//! toki standup --include-planned
+//! toki standup --include-planned --assignee alice
+//!
+//! # Send to Slack instead of printing (after `toki config set slack.webhook_url <url>`)
+//! toki standup --format slack --post
 //! ```
 
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use chrono::NaiveDate;
-use toki_ai::{StandupFormat, StandupGenerator};
+use toki_ai::{StandupFormat, StandupGenerator, StandupReport};
+use toki_integrations::post_to_slack;
 use toki_storage::Database;
 
 /// Generate and output a standup report
 ///
+/// With `include_planned`, in-progress issues assigned to `assignee` are listed under
+/// "today" even if no time was logged on them yet. With `post`, the Slack
+/// formatted report is sent to the configured Slack webhook instead of printed.
+///
 /// # Errors
 ///
-/// Returns an error if database access or report generation fails
-pub fn handle_standup_command(format: &str, date: Option<&str>) -> Result<()> {
+/// Returns an error if database access or report generation fails, or the
+/// report cannot be posted
+pub async fn handle_standup_command(
+    format: &str,
+    date: Option<&str>,
+    include_planned: bool,
+    assignee: Option<String>,
+    post: bool,
+) -> Result<()> {
     let db = Arc::new(Database::new(None).context("Failed to open database")?);
-    let generator = StandupGenerator::new(db);
 
     // Parse optional date
     let parsed_date = if let Some(date_str) = date {
@@ -40,9 +58,96 @@ pub fn handle_standup_command(format: &str, date: Option<&str>) -> Result<()> {
         None
     };
 
-    let report = generator.generate(parsed_date)?;
     let standup_format = StandupFormat::parse(format);
+    let webhook_url = if post {
+        if standup_format != StandupFormat::Slack {
+            anyhow::bail!("--post sends to Slack, use it with --format slack");
+        }
+        Some(db.get_settings()?.slack_webhook_url.context(
+            "No Slack webhook configured. Set one with: toki config set slack.webhook_url <url>",
+        )?)
+    } else {
+        None
+    };
 
-    println!("{}", report.format(standup_format));
+    let report = generate_report(db, parsed_date, include_planned, assignee)?;
+    let Some(webhook_url) = webhook_url else {
+        println!("{}", report.format(standup_format));
+        return Ok(());
+    };
+    post_to_slack(&webhook_url, &report.format(standup_format)).await?;
+    println!("Posted standup to Slack.");
     Ok(())
 }
+
+fn generate_report(
+    db: Arc<Database>,
+    date: Option<NaiveDate>,
+    include_planned: bool,
+    assignee: Option<String>,
+) -> Result<StandupReport> {
+    let mut generator = StandupGenerator::new(db);
+    if include_planned {
+        generator = generator.with_planned_issues(assignee);
+    }
+    generator.generate(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use toki_storage::{IssueCandidate, TimeBlock};
+
+    #[test]
+    fn test_planned_issue_listed_only_with_flag() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::new(Some(temp_dir.path().join("test.db"))).unwrap());
+        let project = db.get_or_create_project("toki", "/home/user/toki").unwrap();
+
+        let mut planned = IssueCandidate::new(
+            project.id,
+            "PROJ-7".to_string(),
+            "plane".to_string(),
+            "Wire up login".to_string(),
+        );
+        planned.status = "In Progress".to_string();
+        planned.assignee = Some("alice".to_string());
+        db.upsert_issue_candidate(&planned).unwrap();
+
+        // Already has logged time today, so it is not repeated as planned
+        let mut logged = IssueCandidate::new(
+            project.id,
+            "PROJ-8".to_string(),
+            "plane".to_string(),
+            "Fix signup".to_string(),
+        );
+        logged.status = "in_progress".to_string();
+        db.upsert_issue_candidate(&logged).unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 3, 14, 10, 0, 0).unwrap();
+        let mut block = TimeBlock::manual(
+            start,
+            start + chrono::Duration::hours(1),
+            "Signup".to_string(),
+        );
+        block.work_item_ids = vec![logged.id];
+        db.save_time_block(&block).unwrap();
+
+        let date = Some(start.date_naive());
+        let without = generate_report(db.clone(), date, false, None).unwrap();
+        assert!(without.planned_issues.is_empty());
+        assert!(without
+            .format_text()
+            .contains("Today: Will continue previous work"));
+
+        let with = generate_report(db.clone(), date, true, Some("Alice".to_string())).unwrap();
+        assert_eq!(with.planned_issues.len(), 1);
+        assert!(with
+            .format_text()
+            .contains("Today: PROJ-7 Wire up login (planned)"));
+
+        // Assigned to someone else
+        let other = generate_report(db, date, true, Some("bob".to_string())).unwrap();
+        assert!(other.planned_issues.is_empty());
+    }
+}
@@ -7,7 +7,9 @@ use std::sync::Arc;
 
 use anyhow::{Context, Result};
 
-use toki_ai::issue_matcher::{ActivitySignals, SmartIssueMatcher};
+use toki_ai::issue_matcher::{
+    ActivitySignals, MatchDiagnosis, SmartIssueMatcher, SEMANTIC_SIMILARITY_FLOOR,
+};
 use toki_detector::git::GitDetector;
 use toki_storage::Database;
 
@@ -16,6 +18,7 @@ pub fn run(
     path: Option<PathBuf>,
     max_suggestions: usize,
     apply: bool,
+    diagnose: bool,
 ) -> Result<()> {
     let working_dir = path.unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
@@ -60,13 +63,38 @@ pub fn run(
         })?;
 
     // Create matcher and find suggestions
-    let matcher = SmartIssueMatcher::new(db.clone())
-        .context("Failed to initialize issue matcher")?;
+    let settings = db.get_settings()?;
+    let matcher = match SmartIssueMatcher::with_pattern(
+        db.clone(),
+        settings.issue_id_pattern.as_deref(),
+        settings.embeddings_enabled,
+    ) {
+        Ok(matcher) => matcher,
+        Err(e) if diagnose => {
+            log::warn!("Failed to initialize issue matcher: {e}");
+            let candidates = db.get_active_issue_candidates(project.id)?;
+            print_diagnosis(&MatchDiagnosis::new(
+                &candidates,
+                &signals,
+                None,
+                max_suggestions,
+            ));
+            return Ok(());
+        }
+        Err(e) => return Err(e).context("Failed to initialize issue matcher"),
+    };
 
     let suggestions = matcher.find_best_matches(&signals, project.id, max_suggestions)?;
 
+    if diagnose {
+        print_diagnosis(&matcher.diagnose(&signals, project.id, max_suggestions)?);
+    }
+
     if suggestions.is_empty() {
         println!("No matching issues found.");
+        if diagnose {
+            return Ok(());
+        }
         println!("\nPossible reasons:");
         println!("  - No issues synced for this project (run 'toki issue-sync')");
         println!("  - Branch/commits don't match any issue patterns");
@@ -96,6 +124,14 @@ pub fn run(
 
     if apply && !suggestions.is_empty() {
         let best = &suggestions[0];
+        let min_confidence = db.get_settings()?.auto_attach_min_confidence;
+        if best.confidence < min_confidence {
+            println!(
+                "Best match is below ai.auto_attach_min_confidence ({:.0}%); not applying.",
+                min_confidence * 100.0
+            );
+            return Ok(());
+        }
         println!("Applying best match: {}", best.issue_id);
         // TODO: Link current work to this issue
         println!("(Auto-linking not yet implemented)");
@@ -106,6 +142,41 @@ pub fn run(
     Ok(())
 }
 
+/// Print what the matcher saw, so an empty result can be acted on
+fn print_diagnosis(diagnosis: &MatchDiagnosis) {
+    println!("Diagnosis:");
+    println!(
+        "  Active candidates: {} ({} with embeddings)",
+        diagnosis.active_candidates, diagnosis.embedded_candidates
+    );
+    println!(
+        "  Signals: branch {}, {} commits, {} URLs, {} files",
+        if diagnosis.has_branch { "yes" } else { "no" },
+        diagnosis.commit_count,
+        diagnosis.url_count,
+        diagnosis.file_count
+    );
+    println!(
+        "  Embeddings: {}",
+        if diagnosis.embeddings_available {
+            "available"
+        } else {
+            "unavailable"
+        }
+    );
+    if !diagnosis.top_similarities.is_empty() {
+        println!(
+            "  Top raw similarity (semantic floor {:.0}%):",
+            SEMANTIC_SIMILARITY_FLOOR * 100.0
+        );
+        for (issue_id, similarity) in &diagnosis.top_similarities {
+            println!("    {issue_id}: {:.0}%", similarity * 100.0);
+        }
+    }
+    println!("  Likely cause: {}", diagnosis.cause().hint());
+    println!();
+}
+
 /// Collect activity signals from git repository
 fn collect_git_signals(detector: &GitDetector, repo_path: &std::path::Path) -> Result<ActivitySignals> {
     let branch = detector.get_branch_name(repo_path)?;
@@ -118,6 +189,7 @@ fn collect_git_signals(detector: &GitDetector, repo_path: &std::path::Path) -> R
         edited_files: files,
         browser_urls: Vec::new(),
         window_titles: Vec::new(),
+        terminal_commands: Vec::new(),
     })
 }
 
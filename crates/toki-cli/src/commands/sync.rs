@@ -1,12 +1,27 @@
 /// Time sync command handler
 use anyhow::Result;
-use toki_integrations::{GitLabClient, PlaneClient, ProjectManagementSystem, TimeEntry};
-use toki_storage::Database;
+use chrono::Duration;
+use toki_core::BillableCategories;
+use toki_integrations::{
+    GitLabClient, JiraClient, PlaneClient, ProjectManagementSystem, TimeEntry,
+};
+use toki_storage::{Database, DurationFormat, TimeBlock};
+use uuid::Uuid;
 
 #[allow(clippy::cognitive_complexity)]
 #[allow(clippy::too_many_lines)]
-pub async fn handle_sync_command(system: String, dry_run: bool, reviewed: bool) -> Result<()> {
+pub async fn handle_sync_command(
+    system: String,
+    dry_run: bool,
+    reviewed: bool,
+    project: Option<String>,
+    billable_only: bool,
+) -> Result<()> {
     let db = Database::new(None)?;
+    let project_id = resolve_project_id(&db, project.as_deref())?;
+    let settings = db.get_settings()?;
+    let min_entry_seconds = settings.sync_min_entry_seconds;
+    let durations = settings.duration_format;
 
     let config = db
         .get_integration_config(&system)?
@@ -20,6 +35,12 @@ pub async fn handle_sync_command(system: String, dry_run: bool, reviewed: bool)
     if reviewed {
         println!("  (Syncing only reviewed/confirmed time blocks)");
     }
+    if let Some(name) = &project {
+        println!("  (Limited to project: {name})");
+    }
+    if billable_only {
+        println!("  (Syncing only billable time blocks)");
+    }
 
     let sync_result = match config.system_type.as_str() {
         "plane" => {
@@ -39,7 +60,7 @@ pub async fn handle_sync_command(system: String, dry_run: bool, reviewed: bool)
 
             if reviewed {
                 // Only sync confirmed time blocks
-                let time_blocks = db.get_confirmed_time_blocks()?;
+                let time_blocks = confirmed_blocks(&db, project_id, billable_only)?;
 
                 for block in time_blocks {
                     // Get the first associated issue candidate
@@ -89,6 +110,8 @@ pub async fn handle_sync_command(system: String, dry_run: bool, reviewed: bool)
                 }
             }
 
+            let time_entries = skip_below_floor(time_entries, min_entry_seconds, durations);
+
             if time_entries.is_empty() {
                 if reviewed {
                     println!("No confirmed time blocks to sync.");
@@ -119,17 +142,14 @@ pub async fn handle_sync_command(system: String, dry_run: bool, reviewed: bool)
             }
 
             // Populate page ID cache from database
-            let page_id_map = db.get_notion_page_id_map()?;
-            for (external_id, page_id) in &page_id_map {
-                client.cache_page_id(external_id, page_id).await;
-            }
-            log::debug!("Loaded {} page ID mappings from database", page_id_map.len());
+            let loaded = client.preload_page_ids(db.get_notion_page_id_map()?).await;
+            log::debug!("Loaded {loaded} page ID mappings from database");
 
             let mut time_entries = Vec::new();
 
             if reviewed {
                 // Only sync confirmed time blocks
-                let time_blocks = db.get_confirmed_time_blocks()?;
+                let time_blocks = confirmed_blocks(&db, project_id, billable_only)?;
 
                 for block in time_blocks {
                     // Get the first associated issue candidate
@@ -184,6 +204,8 @@ pub async fn handle_sync_command(system: String, dry_run: bool, reviewed: bool)
                 }
             }
 
+            let time_entries = skip_below_floor(time_entries, min_entry_seconds, durations);
+
             if time_entries.is_empty() {
                 if reviewed {
                     println!("No confirmed time blocks to sync.");
@@ -224,7 +246,7 @@ pub async fn handle_sync_command(system: String, dry_run: bool, reviewed: bool)
 
             if reviewed {
                 // Only sync confirmed time blocks
-                let time_blocks = db.get_confirmed_time_blocks()?;
+                let time_blocks = confirmed_blocks(&db, project_id, billable_only)?;
 
                 for block in time_blocks {
                     // Get the first associated issue candidate
@@ -279,6 +301,8 @@ pub async fn handle_sync_command(system: String, dry_run: bool, reviewed: bool)
                 }
             }
 
+            let time_entries = skip_below_floor(time_entries, min_entry_seconds, durations);
+
             if time_entries.is_empty() {
                 if reviewed {
                     println!("No confirmed time blocks to sync to GitLab.");
@@ -293,7 +317,7 @@ pub async fn handle_sync_command(system: String, dry_run: bool, reviewed: bool)
 
             if dry_run {
                 for entry in &time_entries {
-                    let duration = format_duration(entry.duration_seconds);
+                    let duration = durations.format(entry.duration_seconds);
                     println!(
                         "  Issue #{} - {} - {}",
                         entry.work_item_id, duration, entry.description
@@ -304,6 +328,88 @@ pub async fn handle_sync_command(system: String, dry_run: bool, reviewed: bool)
                 client.batch_sync(time_entries).await
             }
         }
+        "jira" => {
+            use toki_integrations::SyncReport;
+
+            // The account email is stored in workspace_slug for Jira
+            let email = config.workspace_slug.as_deref().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Jira email not configured. Run: toki config set jira.email <email>"
+                )
+            })?;
+            if config.api_url.is_empty() {
+                anyhow::bail!(
+                    "Jira site not configured. Run: toki config set jira.api_url https://<site>.atlassian.net"
+                );
+            }
+            let client = JiraClient::new(&config.api_url, email, &config.api_key)?;
+
+            let time_entries =
+                system_time_entries(&db, "jira", reviewed, project_id, billable_only)?;
+            let time_entries = skip_below_floor(time_entries, min_entry_seconds, durations);
+
+            if time_entries.is_empty() {
+                if reviewed {
+                    println!("No confirmed time blocks to sync to Jira.");
+                    println!("Run 'toki review' to review and confirm time blocks first.");
+                } else {
+                    println!("No Jira time entries to sync.");
+                }
+                return Ok(());
+            }
+
+            println!("Found {} time entries to sync to Jira", time_entries.len());
+
+            if dry_run {
+                for entry in &time_entries {
+                    let duration = durations.format(entry.duration_seconds);
+                    println!(
+                        "  {} - {} - {}",
+                        entry.work_item_id, duration, entry.description
+                    );
+                }
+                Ok(SyncReport::new(0))
+            } else {
+                client.batch_sync(time_entries).await
+            }
+        }
+        "trello" => {
+            use toki_integrations::SyncReport;
+
+            let client = super::helpers::trello_client(&config)?;
+
+            let time_entries =
+                system_time_entries(&db, "trello", reviewed, project_id, billable_only)?;
+            let time_entries = skip_below_floor(time_entries, min_entry_seconds, durations);
+
+            if time_entries.is_empty() {
+                if reviewed {
+                    println!("No confirmed time blocks to sync to Trello.");
+                    println!("Run 'toki review' to review and confirm time blocks first.");
+                } else {
+                    println!("No Trello time entries to sync.");
+                }
+                return Ok(());
+            }
+
+            println!(
+                "Found {} time entries to sync to Trello",
+                time_entries.len()
+            );
+
+            if dry_run {
+                for entry in &time_entries {
+                    let duration = durations.format(entry.duration_seconds);
+                    println!(
+                        "  Card {} - {} - {}",
+                        entry.work_item_id, duration, entry.description
+                    );
+                }
+                Ok(SyncReport::new(0))
+            } else {
+                client.batch_sync(time_entries).await
+            }
+        }
         _ => {
             anyhow::bail!("Unsupported PM system: {}", config.system_type);
         }
@@ -324,14 +430,314 @@ pub async fn handle_sync_command(system: String, dry_run: bool, reviewed: bool)
     Ok(())
 }
 
-/// Format duration in human-readable form (e.g., "1h 30m")
-fn format_duration(seconds: u32) -> String {
-    let hours = seconds / 3600;
-    let minutes = (seconds % 3600) / 60;
-    match (hours, minutes) {
-        (0, 0) => "0m".to_string(),
-        (0, m) => format!("{m}m"),
-        (h, 0) => format!("{h}h"),
-        (h, m) => format!("{h}h {m}m"),
+/// Collect time entries for issues from one PM system
+///
+/// With `reviewed`, confirmed time blocks are used; otherwise every tracked
+/// activity on the system's work items.
+fn system_time_entries(
+    db: &Database,
+    system: &str,
+    reviewed: bool,
+    project_id: Option<Uuid>,
+    billable_only: bool,
+) -> Result<Vec<TimeEntry>> {
+    let mut time_entries = Vec::new();
+
+    if reviewed {
+        for block in confirmed_blocks(db, project_id, billable_only)? {
+            // Get the first associated issue candidate
+            let Some(issue_id) = block.work_item_ids.first() else {
+                continue;
+            };
+            let Some(issue) = db.get_issue_candidate_by_id(*issue_id)? else {
+                continue;
+            };
+            if issue.external_system != system {
+                continue;
+            }
+            let duration = u32::try_from((block.end_time - block.start_time).num_seconds().max(0))
+                .unwrap_or(u32::MAX);
+            time_entries.push(TimeEntry {
+                work_item_id: issue.external_id,
+                start_time: block.start_time,
+                duration_seconds: duration,
+                description: block.description.clone(),
+                category: block
+                    .tags
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| "Development".to_string()),
+            });
+        }
+    } else {
+        for work_item in db.get_all_work_items()? {
+            if work_item.external_system != system {
+                continue;
+            }
+            for activity in db.get_activities_by_work_item(work_item.id)? {
+                time_entries.push(TimeEntry {
+                    work_item_id: work_item.external_id.clone(),
+                    start_time: activity.timestamp,
+                    duration_seconds: activity.duration_seconds,
+                    description: format!("Auto-tracked by Toki: {}", activity.category),
+                    category: activity.category,
+                });
+            }
+        }
+    }
+
+    Ok(time_entries)
+}
+
+/// Resolve a project name to its ID, erroring if no such project exists
+fn resolve_project_id(db: &Database, name: Option<&str>) -> Result<Option<Uuid>> {
+    let Some(name) = name else {
+        return Ok(None);
+    };
+
+    let project = db
+        .get_project_by_name(name)?
+        .ok_or_else(|| anyhow::anyhow!("Project not found: {name}"))?;
+    Ok(Some(project.id))
+}
+
+/// Get confirmed time blocks, optionally limited to a single project
+///
+/// With `billable_only`, blocks whose dominant category is not billable are dropped.
+fn confirmed_blocks(
+    db: &Database,
+    project_id: Option<Uuid>,
+    billable_only: bool,
+) -> Result<Vec<TimeBlock>> {
+    let blocks = match project_id {
+        Some(id) => db.get_confirmed_time_blocks_for_project(id),
+        None => db.get_confirmed_time_blocks(),
+    }?;
+    if !billable_only || blocks.is_empty() {
+        return Ok(blocks);
+    }
+
+    let billable = BillableCategories::load(db)?;
+    if !billable.is_configured() {
+        anyhow::bail!(
+            "No billable categories configured. Run: toki config set settings.billable_categories <categories>"
+        );
+    }
+
+    // Spans may start before the block they overlap
+    let start = blocks.iter().map(|b| b.start_time).min().unwrap_or_default() - Duration::days(1);
+    let end = blocks.iter().map(|b| b.end_time).max().unwrap_or_default();
+    let spans = db.get_activity_spans(start, end)?;
+
+    Ok(blocks
+        .into_iter()
+        .filter(|block| super::report::is_block_billable(block, &spans, &billable))
+        .collect())
+}
+
+/// Drop entries below `sync.min_entry_seconds`, reporting what was skipped
+fn skip_below_floor(
+    entries: Vec<TimeEntry>,
+    min_seconds: u32,
+    durations: DurationFormat,
+) -> Vec<TimeEntry> {
+    let (kept, skipped) = apply_entry_floor(entries, min_seconds);
+    if !skipped.is_empty() {
+        let total: u32 = skipped.iter().map(|e| e.duration_seconds).sum();
+        println!(
+            "Skipped {} entries ({}) below the {} sync floor:",
+            skipped.len(),
+            durations.format(total),
+            durations.format(min_seconds)
+        );
+        for entry in &skipped {
+            println!(
+                "  {} - {} - {}",
+                entry.work_item_id,
+                durations.format(entry.duration_seconds),
+                entry.description
+            );
+        }
+    }
+    kept
+}
+
+/// Split entries into those to sync and those below the `min_seconds` floor
+///
+/// Entries at or above the floor sync as they are. Shorter entries on the same
+/// issue are combined into one entry, which syncs if together they reach the
+/// floor; otherwise they are all skipped. A floor of 0 keeps everything.
+fn apply_entry_floor(
+    entries: Vec<TimeEntry>,
+    min_seconds: u32,
+) -> (Vec<TimeEntry>, Vec<TimeEntry>) {
+    if min_seconds == 0 {
+        return (entries, Vec::new());
+    }
+
+    let mut kept = Vec::new();
+    let mut short_by_issue: Vec<(String, Vec<TimeEntry>)> = Vec::new();
+    for entry in entries {
+        if entry.duration_seconds >= min_seconds {
+            kept.push(entry);
+        } else if let Some((_, group)) = short_by_issue
+            .iter_mut()
+            .find(|(issue, _)| *issue == entry.work_item_id)
+        {
+            group.push(entry);
+        } else {
+            short_by_issue.push((entry.work_item_id.clone(), vec![entry]));
+        }
+    }
+
+    let mut skipped = Vec::new();
+    for (_, group) in short_by_issue {
+        let total: u32 = group.iter().map(|e| e.duration_seconds).sum();
+        if total < min_seconds {
+            skipped.extend(group);
+            continue;
+        }
+        let mut descriptions: Vec<&str> = Vec::new();
+        for description in group.iter().map(|e| e.description.as_str()) {
+            if !description.is_empty() && !descriptions.contains(&description) {
+                descriptions.push(description);
+            }
+        }
+        let first = &group[0];
+        let start_time = group.iter().map(|e| e.start_time).min();
+        kept.push(TimeEntry {
+            work_item_id: first.work_item_id.clone(),
+            start_time: start_time.unwrap_or(first.start_time),
+            duration_seconds: total,
+            description: descriptions.join("; "),
+            category: first.category.clone(),
+        });
+    }
+
+    kept.sort_by_key(|e| e.start_time);
+    (kept, skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use toki_storage::ActivitySpan;
+
+    fn confirmed_block(project_id: Option<Uuid>) -> TimeBlock {
+        let end = Utc::now();
+        let mut block = TimeBlock::manual(end - Duration::hours(1), end, "work".to_string());
+        block.project_id = project_id;
+        block
+    }
+
+    #[test]
+    fn test_project_filter_includes_only_named_project() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+        let toki = db.get_or_create_project("toki", "/code/toki").unwrap();
+        let other = db.get_or_create_project("other", "/code/other").unwrap();
+
+        let block = confirmed_block(Some(toki.id));
+        db.save_time_block(&block).unwrap();
+        db.save_time_block(&confirmed_block(Some(other.id))).unwrap();
+        db.save_time_block(&confirmed_block(None)).unwrap();
+
+        let project_id = resolve_project_id(&db, Some("toki")).unwrap();
+        let blocks = confirmed_blocks(&db, project_id, false).unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].id, block.id);
+
+        assert_eq!(confirmed_blocks(&db, None, false).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_billable_only_excludes_non_billable_blocks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+        let mut settings = db.get_settings().unwrap();
+        settings.billable_categories = vec!["Coding".to_string()];
+        db.update_settings(&settings).unwrap();
+
+        let end = Utc::now();
+        let coding = TimeBlock::manual(
+            end - Duration::hours(3),
+            end - Duration::hours(2),
+            "API work".to_string(),
+        );
+        let meeting = TimeBlock::manual(end - Duration::hours(1), end, "standup".to_string());
+        db.save_time_block(&coding).unwrap();
+        db.save_time_block(&meeting).unwrap();
+        for (block, category) in [(&coding, "Coding"), (&meeting, "Communication")] {
+            let mut span = ActivitySpan::new(
+                "app".to_string(),
+                category.to_string(),
+                block.start_time,
+                None,
+                None,
+                None,
+            );
+            span.end_time = Some(block.end_time);
+            span.duration_seconds = 3600;
+            db.create_activity_span(&span).unwrap();
+        }
+
+        let blocks = confirmed_blocks(&db, None, true).unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].id, coding.id);
+        assert_eq!(confirmed_blocks(&db, None, false).unwrap().len(), 2);
+    }
+
+    fn entry(issue: &str, minutes_ago: i64, minutes: u32) -> TimeEntry {
+        TimeEntry {
+            work_item_id: issue.to_string(),
+            start_time: Utc::now() - Duration::minutes(minutes_ago),
+            duration_seconds: minutes * 60,
+            description: format!("work on {issue}"),
+            category: "Development".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_lone_short_entry_skipped_below_floor() {
+        let (kept, skipped) = apply_entry_floor(vec![entry("42", 10, 2)], 5 * 60);
+        assert!(kept.is_empty());
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].work_item_id, "42");
+    }
+
+    #[test]
+    fn test_short_entries_on_one_issue_aggregate_past_floor() {
+        let entries = vec![
+            entry("42", 60, 2),
+            entry("7", 50, 2),
+            entry("42", 40, 2),
+            entry("42", 20, 2),
+            entry("9", 10, 30),
+        ];
+        let first_start = entries[0].start_time;
+
+        let (kept, skipped) = apply_entry_floor(entries, 5 * 60);
+
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept[0].work_item_id, "42");
+        assert_eq!(kept[0].duration_seconds, 6 * 60);
+        assert_eq!(kept[0].start_time, first_start);
+        assert_eq!(kept[0].description, "work on 42");
+        assert_eq!(kept[1].work_item_id, "9");
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].work_item_id, "7");
+
+        let (all, none) = apply_entry_floor(vec![entry("7", 5, 1)], 0);
+        assert_eq!((all.len(), none.len()), (1, 0));
+    }
+
+    #[test]
+    fn test_unknown_project_errors() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+
+        let err = resolve_project_id(&db, Some("missing")).unwrap_err();
+        assert!(err.to_string().contains("Project not found: missing"));
     }
 }
@@ -0,0 +1,59 @@
+//! Day timeline command
+//!
+//! Shows when each category of work happened over a day, as an ASCII gantt.
+
+use std::io::IsTerminal;
+
+use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use toki_storage::Database;
+
+use super::gantt::{chart_window, Gantt, DEFAULT_WIDTH};
+
+/// Print the activity of a day (today by default) as one bar per category
+pub fn handle_timeline_command(date: Option<&str>, width: Option<usize>) -> Result<()> {
+    let db = Database::new(None)?;
+    let settings = db.get_settings()?;
+    let timezone = settings.timezone;
+    let offset = Duration::minutes(i64::from(timezone.offset_minutes));
+
+    // Days start at midnight in settings.timezone, like reports
+    let day_start = if let Some(date) = date {
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .context("Invalid date format. Use YYYY-MM-DD")?;
+        Utc.from_utc_datetime(&date.and_time(NaiveTime::MIN)) - offset
+    } else {
+        timezone.start_of_day(Utc::now())
+    };
+    let day = (day_start + offset).date_naive();
+
+    let spans = db.get_activity_spans(
+        day_start,
+        day_start + Duration::days(1) - Duration::seconds(1),
+    )?;
+    if spans.is_empty() {
+        println!("No activity recorded for {day}");
+        return Ok(());
+    }
+
+    let width = width.or_else(terminal_width).unwrap_or(DEFAULT_WIDTH);
+    let (from, to) = chart_window(day_start, settings.work_hours, &spans);
+    let gantt = Gantt::build(&spans, from, to, width);
+
+    println!(
+        "Timeline for {day} ({}m per column, {timezone})\n",
+        gantt.bucket_minutes()
+    );
+    print!("{}", gantt.render(timezone, settings.duration_format, use_color()));
+    Ok(())
+}
+
+/// Terminal width from `$COLUMNS`, if the shell exports it
+fn terminal_width() -> Option<usize> {
+    std::env::var("COLUMNS").ok()?.trim().parse().ok()
+}
+
+/// Color bars only on a terminal, and never when `NO_COLOR` is set
+fn use_color() -> bool {
+    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+}
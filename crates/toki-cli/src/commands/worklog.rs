@@ -0,0 +1,279 @@
+//! Local worklog export
+//!
+//! Exports confirmed time blocks as a durable worklog for users without
+//! any PM integration. Exported blocks are marked as logged so the next
+//! run only picks up new work.
+//!
+//! Usage:
+//! ```bash
+//! toki worklog week --format markdown --output worklog.md
+//! ```
+
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use toki_core::BillableCategories;
+use toki_storage::{ActivitySpan, Database, TimeBlock};
+
+use super::helpers::escape_csv;
+use super::report::is_block_billable;
+
+/// One line of the worklog
+#[derive(Debug, Serialize)]
+struct WorklogEntry {
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    duration_seconds: i64,
+    project: Option<String>,
+    issues: Vec<String>,
+    description: String,
+    tags: Vec<String>,
+    /// `None` when no billable categories are configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    billable: Option<bool>,
+}
+
+/// Export confirmed, not-yet-logged time blocks as a worklog
+///
+/// # Errors
+///
+/// Returns an error if the period or format is unknown, or database/file access fails
+pub fn handle_worklog_command(period: &str, format: &str, output: Option<&str>) -> Result<()> {
+    let (start, end) = parse_period(period)?;
+    if !matches!(format, "json" | "csv" | "markdown" | "md") {
+        anyhow::bail!("Unknown format: {format}. Use 'json', 'csv', or 'markdown'");
+    }
+
+    let db = Database::new(None).context("Failed to open database")?;
+    if export_worklog(&db, start, end, format, output)? == 0 {
+        println!("No new confirmed time blocks for period: {period}");
+        println!("Run 'toki review' to review and confirm time blocks first.");
+    }
+
+    Ok(())
+}
+
+/// Write unlogged blocks in `start..end` and mark them logged once the output succeeded
+///
+/// Returns the number of exported blocks
+fn export_worklog(
+    db: &Database,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    format: &str,
+    output: Option<&str>,
+) -> Result<usize> {
+    let blocks = db.get_unlogged_time_blocks(start, end)?;
+    if blocks.is_empty() {
+        return Ok(0);
+    }
+
+    let billable = BillableCategories::load(db)?;
+    let spans = if billable.is_configured() {
+        db.get_activity_spans(start, end)?
+    } else {
+        Vec::new()
+    };
+    let entries: Vec<WorklogEntry> = blocks
+        .iter()
+        .map(|b| to_entry(db, b, &spans, &billable))
+        .collect();
+    let content = match format {
+        "json" => serde_json::to_string_pretty(&entries)?,
+        "csv" => format_csv(&entries),
+        _ => format_markdown(&entries),
+    };
+
+    if let Some(path) = output {
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write worklog to {path}"))?;
+        println!("Wrote {} worklog entries to {path}", entries.len());
+    } else {
+        println!("{content}");
+    }
+
+    let ids: Vec<_> = blocks.iter().map(|b| b.id).collect();
+    db.mark_time_blocks_logged(&ids)?;
+    Ok(blocks.len())
+}
+
+fn parse_period(period: &str) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+    let end = Utc::now();
+    let start = match period {
+        "today" => end
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .unwrap_or_default()
+            .and_utc(),
+        "week" => end - Duration::days(7),
+        "month" => end - Duration::days(30),
+        "all" => end - Duration::days(3650),
+        _ => anyhow::bail!("Unknown period: {period}. Use 'today', 'week', 'month', or 'all'"),
+    };
+    Ok((start, end))
+}
+
+fn to_entry(
+    db: &Database,
+    block: &TimeBlock,
+    spans: &[ActivitySpan],
+    billable: &BillableCategories,
+) -> WorklogEntry {
+    let project = block
+        .project_id
+        .and_then(|pid| db.get_project(pid).ok().flatten())
+        .map(|p| p.name);
+    let issues = block
+        .work_item_ids
+        .iter()
+        .filter_map(|id| db.get_issue_candidate_by_id(*id).ok().flatten())
+        .map(|issue| issue.external_id)
+        .collect();
+
+    WorklogEntry {
+        start_time: block.start_time,
+        end_time: block.end_time,
+        duration_seconds: (block.end_time - block.start_time).num_seconds().max(0),
+        project,
+        issues,
+        description: block.description.clone(),
+        tags: block.tags.clone(),
+        billable: billable
+            .is_configured()
+            .then(|| is_block_billable(block, spans, billable)),
+    }
+}
+
+fn format_csv(entries: &[WorklogEntry]) -> String {
+    let mut csv = String::from(
+        "start_time,end_time,duration_seconds,project,issues,description,tags,billable\n",
+    );
+    for entry in entries {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{}",
+            entry.start_time.to_rfc3339(),
+            entry.end_time.to_rfc3339(),
+            entry.duration_seconds,
+            escape_csv(entry.project.as_deref().unwrap_or_default()),
+            escape_csv(&entry.issues.join(";")),
+            escape_csv(&entry.description),
+            escape_csv(&entry.tags.join(";")),
+            entry.billable.map(|b| b.to_string()).unwrap_or_default(),
+        );
+    }
+    csv
+}
+
+fn format_markdown(entries: &[WorklogEntry]) -> String {
+    let mut md = String::from("# Worklog\n\n| Date | Time | Duration | Project | Issues | Description |\n|---|---|---|---|---|---|\n");
+    for entry in entries {
+        let _ = writeln!(
+            md,
+            "| {} | {}-{} | {} | {} | {} | {} |",
+            entry.start_time.format("%Y-%m-%d"),
+            entry.start_time.format("%H:%M"),
+            entry.end_time.format("%H:%M"),
+            format_duration(entry.duration_seconds),
+            entry.project.as_deref().unwrap_or("-"),
+            entry.issues.join(", "),
+            entry.description.replace('|', "\\|"),
+        );
+    }
+
+    let total: i64 = entries.iter().map(|e| e.duration_seconds).sum();
+    let _ = writeln!(md, "\n**Total:** {}", format_duration(total));
+    if entries.iter().any(|e| e.billable.is_some()) {
+        let billable: i64 = entries
+            .iter()
+            .filter(|e| e.billable == Some(true))
+            .map(|e| e.duration_seconds)
+            .sum();
+        let _ = writeln!(
+            md,
+            "**Billable:** {} / **Non-billable:** {}",
+            format_duration(billable),
+            format_duration(total - billable)
+        );
+    }
+    md
+}
+
+/// Format duration in human-readable form (e.g., "1h 30m")
+fn format_duration(seconds: i64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    match (hours, minutes) {
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h {m}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_db() -> (tempfile::TempDir, Database) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+        (temp_dir, db)
+    }
+
+    #[test]
+    fn test_confirmed_blocks_appear_in_worklog() {
+        let (_dir, db) = test_db();
+        let end = Utc::now();
+        let block = TimeBlock::manual(end - Duration::hours(2), end, "API work".to_string());
+        db.save_time_block(&block).unwrap();
+
+        let mut unconfirmed = TimeBlock::manual(end - Duration::hours(1), end, "draft".to_string());
+        unconfirmed.confirmed = false;
+        db.save_time_block(&unconfirmed).unwrap();
+
+        let blocks = db.get_unlogged_time_blocks(end - Duration::days(1), end).unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].id, block.id);
+
+        let entry = to_entry(&db, &blocks[0], &[], &BillableCategories::default());
+        assert_eq!(entry.duration_seconds, 7200);
+        assert_eq!(entry.billable, None);
+        assert!(format_markdown(&[entry]).contains("API work"));
+    }
+
+    #[test]
+    fn test_logged_blocks_not_repeated_on_second_run() {
+        let (dir, db) = test_db();
+        let end = Utc::now();
+        let block = TimeBlock::manual(end - Duration::hours(1), end, "review".to_string());
+        db.save_time_block(&block).unwrap();
+
+        let path = dir.path().join("worklog.md");
+        let output = path.to_str();
+        let first = export_worklog(&db, end - Duration::days(1), end, "md", output).unwrap();
+        assert_eq!(first, 1);
+
+        let second = export_worklog(&db, end - Duration::days(1), end, "md", output).unwrap();
+        assert_eq!(second, 0);
+
+        // Logging locally must not affect PM sync
+        assert_eq!(db.get_confirmed_time_blocks().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_failed_write_leaves_blocks_unlogged() {
+        let (dir, db) = test_db();
+        let end = Utc::now();
+        let block = TimeBlock::manual(end - Duration::hours(1), end, "review".to_string());
+        db.save_time_block(&block).unwrap();
+
+        let missing = dir.path().join("missing").join("worklog.md");
+        let result = export_worklog(&db, end - Duration::days(1), end, "md", missing.to_str());
+        assert!(result.is_err());
+
+        let pending = db.get_unlogged_time_blocks(end - Duration::days(1), end).unwrap();
+        assert_eq!(pending.len(), 1);
+    }
+}
@@ -19,16 +19,32 @@ enum Commands {
         /// Enable database encryption
         #[arg(short, long)]
         encrypt: bool,
+        /// Recreate the database even if toki is already initialized
+        #[arg(long)]
+        force: bool,
     },
     /// Start the tracking daemon
-    Start,
+    Start {
+        /// Take over the PID file even if its process is still alive
+        #[arg(long)]
+        force: bool,
+    },
     /// (Internal) Run the daemon process
     #[command(hide = true)]
     DaemonInternalStart,
+    /// (Internal) Native messaging host started by the browser extension
+    #[command(hide = true)]
+    BrowserHost {
+        /// Manifest path and extension id passed by the browser (unused)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        browser_args: Vec<String>,
+    },
     /// Stop the tracking daemon
     Stop,
     /// Check daemon status and today's statistics
     Status,
+    /// Make the running daemon re-read settings and rules without restarting
+    Reload,
     /// Generate time tracking report
     Report {
         /// Time period: today, week, month, or custom
@@ -37,6 +53,18 @@ enum Commands {
         /// Show report grouped by outcomes (commits, issues, PRs) instead of time
         #[arg(long)]
         by_outcome: bool,
+        /// Show a category x project matrix: 'project' (projects as columns) or 'category'
+        #[arg(long, conflicts_with = "by_outcome")]
+        split_by: Option<String>,
+        /// Output format: text, json, csv, or ical (confirmed time blocks as calendar events)
+        #[arg(long, default_value = "text", conflicts_with_all = ["by_outcome", "split_by"])]
+        format: String,
+        /// Use the timezone the period's sessions were captured in instead of settings.timezone
+        #[arg(long)]
+        user_timezone_from_db: bool,
+        /// Show the time logged against one issue (e.g., 42, #42, PROJ-42) and its spans
+        #[arg(long, conflicts_with_all = ["by_outcome", "split_by", "format"])]
+        work_item: Option<String>,
     },
     /// Manage category rules
     Categories,
@@ -52,7 +80,7 @@ enum Commands {
     },
     /// Synchronize time entries to PM system
     Sync {
-        /// PM system type (plane, notion, gitlab, github, jira)
+        /// PM system type (plane, notion, gitlab, github, jira, trello)
         #[arg(default_value = "plane")]
         system: String,
         /// Dry run (don't actually sync)
@@ -61,6 +89,12 @@ enum Commands {
         /// Only sync confirmed/reviewed time blocks
         #[arg(short, long)]
         reviewed: bool,
+        /// Only sync time blocks belonging to this project
+        #[arg(long, requires = "reviewed")]
+        project: Option<String>,
+        /// Only sync blocks whose dominant category is billable
+        #[arg(long, requires = "reviewed")]
+        billable_only: bool,
     },
     /// Configuration management
     Config {
@@ -88,6 +122,12 @@ enum Commands {
         /// Confirm all suggested blocks (save for syncing)
         #[arg(long)]
         confirm_all: bool,
+        /// List activity classified with low confidence
+        #[arg(long)]
+        uncertain: bool,
+        /// Correct an uncertain app's category and learn a rule (with --uncertain)
+        #[arg(long, value_name = "APP=CATEGORY", requires = "uncertain")]
+        correct: Option<String>,
     },
     /// Teach toki to classify activities (deprecated, use auto-inference)
     #[command(hide = true)]
@@ -100,6 +140,28 @@ enum Commands {
         /// Force full resync (recompute all embeddings)
         #[arg(short, long)]
         force: bool,
+        /// Check for possible duplicate issues after syncing
+        #[arg(long)]
+        duplicates: bool,
+        /// Only sync this Notion database (id, or name of the project linked to it)
+        #[arg(long)]
+        database: Option<String>,
+        /// Show a few clusters of similar issues to sanity-check embeddings
+        #[arg(long)]
+        show_clusters: bool,
+        /// Also report when the embedding rebuild is skipped
+        #[arg(short, long)]
+        verbose: bool,
+    },
+    /// Edit reviewed time blocks
+    Block {
+        #[command(subcommand)]
+        action: commands::block::BlockAction,
+    },
+    /// Inspect synced issues
+    Issue {
+        #[command(subcommand)]
+        action: commands::issue::IssueAction,
     },
     /// Estimate issue complexity (AI-assisted)
     Estimate {
@@ -111,6 +173,10 @@ enum Commands {
         /// Issue tracking system (github, notion, plane, jira)
         #[arg(long, default_value = "github")]
         system: String,
+        /// Push the estimate: an `/estimate` note on GitLab, the complexity
+        /// field of the configured project on GitHub
+        #[arg(long, conflicts_with = "set")]
+        push: bool,
     },
     /// Project management commands
     Project {
@@ -128,6 +194,9 @@ enum Commands {
         /// Automatically link to the best match
         #[arg(short, long)]
         apply: bool,
+        /// Explain the result: candidates, signals, embeddings, and raw scores
+        #[arg(long)]
+        diagnose: bool,
     },
     /// Claude Code session management (for hooks integration)
     Session {
@@ -147,6 +216,36 @@ enum Commands {
         /// Date to generate standup for (YYYY-MM-DD format, defaults to today)
         #[arg(short, long)]
         date: Option<String>,
+        /// Also list in-progress issues under "today", even without logged time
+        #[arg(long)]
+        include_planned: bool,
+        /// Only plan issues assigned to this user (unassigned issues are always included)
+        #[arg(long, requires = "include_planned")]
+        assignee: Option<String>,
+        /// Send the report to the Slack webhook set in `slack.webhook_url` (needs --format slack)
+        #[arg(long)]
+        post: bool,
+    },
+    /// Show a day as a timeline of categories (ASCII gantt)
+    Timeline {
+        /// Date to show (YYYY-MM-DD format, defaults to today)
+        #[arg(short, long)]
+        date: Option<String>,
+        /// Chart width in columns (defaults to $COLUMNS, or 80)
+        #[arg(short, long)]
+        width: Option<usize>,
+    },
+    /// Export confirmed time blocks as a local worklog (no PM system needed)
+    Worklog {
+        /// Time period (today, week, month, all)
+        #[arg(default_value = "week")]
+        period: String,
+        /// Output format: json, csv, markdown
+        #[arg(short, long, default_value = "markdown")]
+        format: String,
+        /// Output file path (prints to stdout if omitted)
+        #[arg(short, long)]
+        output: Option<String>,
     },
     /// Suggest the next task to work on
     Next {
@@ -159,6 +258,9 @@ enum Commands {
         /// Number of suggestions to show
         #[arg(short = 'n', long, default_value = "3")]
         count: usize,
+        /// Output format: text, json, csv
+        #[arg(long, default_value = "text")]
+        format: String,
     },
     /// Analyze productivity patterns and detect anomalies
     Insights {
@@ -168,9 +270,12 @@ enum Commands {
         /// Compare with previous period
         #[arg(short, long)]
         compare: bool,
-        /// Focus on specific aspect: hours, sessions, context-switches
+        /// Focus on specific aspect: hours, sessions, context-switches, gravity, apps, unmatched, patterns
         #[arg(long)]
         focus: Option<String>,
+        /// Show how each signal contributes to gravity (with --focus gravity)
+        #[arg(long, requires = "focus")]
+        explain: bool,
     },
     /// Analyze scope creep - compare estimated vs actual time
     Scope {
@@ -190,19 +295,41 @@ enum Commands {
 
 #[derive(Subcommand, Debug)]
 enum DataAction {
-    /// Export data to JSON or CSV
+    /// Export data to JSON, CSV, NDJSON or iCalendar
     Export {
-        /// Output format: json or csv
-        format: String,
-        /// Output file path
+        /// Output format: json, csv, ndjson or ical (confirmed time blocks as .ics events)
+        #[arg(required_unless_present = "format_flag")]
+        format: Option<String>,
+        /// Output format, as a flag (same values as FORMAT)
+        #[arg(
+            long = "format",
+            id = "format_flag",
+            value_name = "FORMAT",
+            conflicts_with = "format"
+        )]
+        format_flag: Option<String>,
+        /// Output file path (`-` for stdout with ndjson)
         #[arg(short, long)]
         output: Option<String>,
+        /// Date range to export (YYYY-MM-DD:YYYY-MM-DD, default: last 365 days)
+        #[arg(long)]
+        range: Option<String>,
     },
+    /// Import a JSON or NDJSON export (skipping rows that already exist) or CSV time blocks
+    Import {
+        /// `.json` file from `toki data export json`, NDJSON file from `toki data export ndjson`,
+        /// or a `.csv` file in the worklog CSV layout
+        path: String,
+    },
+    /// Show issue embedding coverage and when embeddings were last built
+    Stats,
     /// Delete data for specified period
     Delete {
         /// Time period to delete
         period: String,
     },
+    /// Reclaim space freed by deletes and refresh query statistics (stop the daemon first)
+    Optimize,
 }
 
 #[derive(Subcommand, Debug)]
@@ -236,9 +363,48 @@ enum ConfigAction {
     },
     /// List all configuration
     List,
+    /// Write the configuration to a TOML file (API keys and tokens left out)
+    Export {
+        /// Output file path
+        path: String,
+        /// Also write API keys, tokens and webhook URLs (stored in plain text)
+        #[arg(long)]
+        include_secrets: bool,
+    },
+    /// Apply a configuration file written by `toki config export`
+    Import {
+        /// TOML file to import
+        path: String,
+    },
+}
+
+/// Enable the integration request log if configured (`integrations.debug_log`)
+fn apply_integration_debug_log(command: &Commands, data_dir: &std::path::Path) -> Result<()> {
+    // Init creates (and may encrypt) the database, so it must not be opened before
+    if matches!(
+        command,
+        Commands::Init { .. } | Commands::DaemonInternalStart | Commands::BrowserHost { .. }
+    ) {
+        return Ok(());
+    }
+    match toki_storage::Database::new(None) {
+        Ok(db) => commands::helpers::apply_integration_debug_log(&db, data_dir),
+        Err(_) => Ok(()),
+    }
+}
+
+fn privacy_action_type(action: PrivacyAction) -> commands::privacy::PrivacyActionType {
+    use commands::privacy::PrivacyActionType;
+    match action {
+        PrivacyAction::Pause => PrivacyActionType::Pause,
+        PrivacyAction::Resume => PrivacyActionType::Resume,
+        PrivacyAction::ListExcluded => PrivacyActionType::ListExcluded,
+        PrivacyAction::Exclude { app } => PrivacyActionType::Exclude { app },
+    }
 }
 
 #[tokio::main]
+#[allow(clippy::too_many_lines)]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
@@ -249,68 +415,161 @@ async fn main() -> Result<()> {
     }
 
     let data_dir = get_data_dir()?;
+    apply_integration_debug_log(&cli.command, &data_dir)?;
 
     match cli.command {
-        Commands::Init { encrypt } => commands::init::init_command(encrypt),
-        Commands::Start => commands::daemon::start_daemon(&data_dir),
+        Commands::Init { encrypt, force } => commands::init::init_command(encrypt, force),
+        Commands::Start { force } => commands::daemon::start_daemon(&data_dir, force),
         Commands::DaemonInternalStart => commands::daemon::run_daemon_process().await,
+        Commands::BrowserHost { .. } => commands::browser_host::run_browser_host(&data_dir).await,
         Commands::Stop => commands::daemon::stop_daemon(&data_dir).await,
         Commands::Status => commands::daemon::show_status(&data_dir).await,
-        Commands::Report { period, by_outcome } => {
-            commands::report::handle_report_command(&period, by_outcome)
-        }
+        Commands::Reload => commands::daemon::reload_daemon(&data_dir).await,
+        Commands::Report {
+            period,
+            by_outcome,
+            split_by,
+            format,
+            user_timezone_from_db,
+            work_item,
+        } => commands::report::handle_report_command(
+            &period,
+            by_outcome,
+            split_by.as_deref(),
+            &format,
+            user_timezone_from_db,
+            work_item.as_deref(),
+        ),
         Commands::Categories => commands::report::handle_categories_command(),
         Commands::Data { action } => match action {
-            DataAction::Export { format, output } => {
-                commands::data::handle_data_export(&format, output)
+            DataAction::Export {
+                format,
+                format_flag,
+                output,
+                range,
+            } => {
+                let format = format.or(format_flag).unwrap_or_default();
+                commands::data::handle_data_export(&format, output, range.as_deref())
             }
+            DataAction::Import { path } => commands::data::handle_data_import(&path),
+            DataAction::Stats => commands::data::handle_data_stats(),
             DataAction::Delete { period } => commands::data::handle_data_delete(&period),
+            DataAction::Optimize => commands::data::handle_data_optimize(),
         },
         Commands::Privacy { action } => {
-            use commands::privacy::PrivacyActionType;
-            let action_type = match action {
-                Some(PrivacyAction::Pause) => Some(PrivacyActionType::Pause),
-                Some(PrivacyAction::Resume) => Some(PrivacyActionType::Resume),
-                Some(PrivacyAction::ListExcluded) => Some(PrivacyActionType::ListExcluded),
-                Some(PrivacyAction::Exclude { app }) => Some(PrivacyActionType::Exclude { app }),
-                None => None,
-            };
-            commands::privacy::handle_privacy_command(action_type)
+            commands::privacy::handle_privacy_command(action.map(privacy_action_type))
         }
         Commands::Sync {
             system,
             dry_run,
             reviewed,
-        } => commands::sync::handle_sync_command(system, dry_run, reviewed).await,
+            project,
+            billable_only,
+        } => {
+            commands::sync::handle_sync_command(system, dry_run, reviewed, project, billable_only)
+                .await
+        }
         Commands::Config { action } => match action {
             ConfigAction::Get { key } => commands::config::handle_config_get(&key),
             ConfigAction::Set { key, value } => commands::config::handle_config_set(&key, &value),
             ConfigAction::List => commands::config::handle_config_list(),
+            ConfigAction::Export {
+                path,
+                include_secrets,
+            } => commands::config::handle_config_export(&path, include_secrets),
+            ConfigAction::Import { path } => commands::config::handle_config_import(&path),
         },
         Commands::Plane { action } => commands::plane::handle_plane_command(action).await,
-        Commands::Review { date, verbose, confirm_all } => {
-            commands::review::handle_review_command(date, verbose, confirm_all)
-        }
+        Commands::Review {
+            date,
+            verbose,
+            confirm_all,
+            uncertain,
+            correct,
+        } => commands::review::handle_review_command(
+            date,
+            verbose,
+            confirm_all,
+            uncertain,
+            correct.as_deref(),
+        ),
         Commands::Learn { action } => commands::learn::handle_learn_command(action),
-        Commands::IssueSync { force } => commands::issue_sync::handle_issue_sync_command(force).await,
-        Commands::Estimate { issue, set, system } => {
-            commands::estimate::handle_estimate_command(&issue, set.as_deref(), &system).await
+        Commands::IssueSync {
+            force,
+            duplicates,
+            database,
+            show_clusters,
+            verbose,
+        } => {
+            commands::issue_sync::handle_issue_sync_command(
+                force,
+                duplicates,
+                database,
+                show_clusters,
+                verbose,
+            )
+            .await
+        }
+        Commands::Block { action } => commands::block::handle_block_command(action),
+        Commands::Issue { action } => commands::issue::handle_issue_command(action),
+        Commands::Estimate {
+            issue,
+            set,
+            system,
+            push,
+        } => {
+            commands::estimate::handle_estimate_command(&issue, set.as_deref(), &system, push).await
         }
         Commands::Project { action } => commands::project::handle_project_command(action).await,
         Commands::Notion { action } => commands::notion::handle_notion_command(action).await,
-        Commands::SuggestIssue { path, max, apply } => {
-            commands::suggest::run(path, max, apply)
-        }
+        Commands::SuggestIssue {
+            path,
+            max,
+            apply,
+            diagnose,
+        } => commands::suggest::run(path, max, apply, diagnose),
         Commands::Session { action } => commands::session::handle_session_command(action),
         Commands::Summary { action } => commands::summary::handle_summary_command(action),
-        Commands::Standup { format, date } => {
-            commands::standup::handle_standup_command(&format, date.as_deref())
+        Commands::Standup {
+            format,
+            date,
+            include_planned,
+            assignee,
+            post,
+        } => {
+            commands::standup::handle_standup_command(
+                &format,
+                date.as_deref(),
+                include_planned,
+                assignee,
+                post,
+            )
+            .await
+        }
+        Commands::Timeline { date, width } => {
+            commands::timeline::handle_timeline_command(date.as_deref(), width)
         }
-        Commands::Next { time, focus, count } => {
-            commands::next::handle_next_command(time.as_deref(), focus.as_deref(), count).await
+        Commands::Worklog {
+            period,
+            format,
+            output,
+        } => commands::worklog::handle_worklog_command(&period, &format, output.as_deref()),
+        Commands::Next {
+            time,
+            focus,
+            count,
+            format,
+        } => {
+            commands::next::handle_next_command(time.as_deref(), focus.as_deref(), count, &format)
+                .await
         }
-        Commands::Insights { period, compare, focus } => {
-            commands::insights::handle_insights_command(&period, compare, focus.as_deref())
+        Commands::Insights {
+            period,
+            compare,
+            focus,
+            explain,
+        } => {
+            commands::insights::handle_insights_command(&period, compare, focus.as_deref(), explain)
         }
         Commands::Scope { issue, threshold } => {
             commands::scope::handle_scope_command(issue.as_deref(), threshold)
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use toki_storage::Database;
+
+/// Built-in friendly names for common applications, keyed by bundle id
+const DEFAULT_APP_NAMES: &[(&str, &str)] = &[
+    ("com.microsoft.VSCode", "VS Code"),
+    ("com.microsoft.VSCodeInsiders", "VS Code Insiders"),
+    ("com.todesktop.230313mzl4w4u92", "Cursor"),
+    ("dev.zed.Zed", "Zed"),
+    ("com.jetbrains.intellij", "IntelliJ IDEA"),
+    ("com.jetbrains.pycharm", "PyCharm"),
+    ("com.jetbrains.WebStorm", "WebStorm"),
+    ("com.jetbrains.goland", "GoLand"),
+    ("com.jetbrains.rustrover", "RustRover"),
+    ("com.apple.dt.Xcode", "Xcode"),
+    ("com.sublimetext.4", "Sublime Text"),
+    ("com.apple.Terminal", "Terminal"),
+    ("com.googlecode.iterm2", "iTerm2"),
+    ("dev.warp.Warp-Stable", "Warp"),
+    ("net.kovidgoyal.kitty", "kitty"),
+    ("org.alacritty", "Alacritty"),
+    ("com.mitchellh.ghostty", "Ghostty"),
+    ("com.google.Chrome", "Google Chrome"),
+    ("com.apple.Safari", "Safari"),
+    ("org.mozilla.firefox", "Firefox"),
+    ("company.thebrowser.Browser", "Arc"),
+    ("com.brave.Browser", "Brave"),
+    ("com.microsoft.edgemac", "Microsoft Edge"),
+    ("com.tinyspeck.slackmacgap", "Slack"),
+    ("com.hnc.Discord", "Discord"),
+    ("us.zoom.xos", "Zoom"),
+    ("com.microsoft.teams2", "Microsoft Teams"),
+    ("com.apple.mail", "Mail"),
+    ("com.figma.Desktop", "Figma"),
+    ("notion.id", "Notion"),
+    ("md.obsidian", "Obsidian"),
+    ("com.postmanlabs.mac", "Postman"),
+    ("com.docker.docker", "Docker Desktop"),
+    ("com.apple.finder", "Finder"),
+];
+
+/// Maps raw app bundle ids to friendly names for display
+///
+/// Storage always keeps the raw bundle id; this mapping is only applied
+/// when rendering report, status, and insights output.
+#[derive(Debug, Clone)]
+pub struct AppDisplayNames {
+    names: HashMap<String, String>,
+}
+
+impl Default for AppDisplayNames {
+    fn default() -> Self {
+        Self {
+            names: DEFAULT_APP_NAMES
+                .iter()
+                .map(|(id, name)| ((*id).to_string(), (*name).to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl AppDisplayNames {
+    /// Create a mapping from the built-in defaults with user overrides applied on top
+    #[must_use]
+    pub fn with_overrides(overrides: HashMap<String, String>) -> Self {
+        let mut mapping = Self::default();
+        mapping.names.extend(overrides);
+        mapping
+    }
+
+    /// Load the built-in defaults merged with overrides stored in the database
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the overrides cannot be read from the database
+    pub fn load(db: &Database) -> Result<Self> {
+        Ok(Self::with_overrides(db.get_app_display_names()?))
+    }
+
+    /// Get the friendly name for a bundle id, if one is mapped
+    #[must_use]
+    pub fn get(&self, bundle_id: &str) -> Option<&str> {
+        self.names.get(bundle_id).map(String::as_str)
+    }
+
+    /// Get the friendly name for a bundle id, falling back to the raw id
+    #[must_use]
+    pub fn display_name<'a>(&'a self, bundle_id: &'a str) -> &'a str {
+        self.get(bundle_id).unwrap_or(bundle_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mapped_bundle_id_shows_friendly_name() {
+        let names = AppDisplayNames::default();
+        assert_eq!(names.display_name("com.microsoft.VSCode"), "VS Code");
+    }
+
+    #[test]
+    fn test_unmapped_bundle_id_falls_back_to_raw_id() {
+        let names = AppDisplayNames::default();
+        assert_eq!(
+            names.display_name("com.example.Unknown"),
+            "com.example.Unknown"
+        );
+    }
+
+    #[test]
+    fn test_user_override_replaces_default() {
+        let mut overrides = HashMap::new();
+        overrides.insert("com.microsoft.VSCode".to_string(), "Code".to_string());
+        overrides.insert("com.example.Tool".to_string(), "Tool".to_string());
+
+        let names = AppDisplayNames::with_overrides(overrides);
+        assert_eq!(names.display_name("com.microsoft.VSCode"), "Code");
+        assert_eq!(names.display_name("com.example.Tool"), "Tool");
+        assert_eq!(names.display_name("com.apple.Safari"), "Safari");
+    }
+
+    #[test]
+    fn test_load_from_database() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(Some(temp_dir.path().join("test.db"))).unwrap();
+        db.set_app_display_name("com.example.Tool", "Tool").unwrap();
+
+        let names = AppDisplayNames::load(&db).unwrap();
+        assert_eq!(names.display_name("com.example.Tool"), "Tool");
+        assert_eq!(names.display_name("com.google.Chrome"), "Google Chrome");
+    }
+}
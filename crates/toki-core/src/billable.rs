@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use toki_storage::{ActivitySpan, Database};
+use uuid::Uuid;
+
+/// Which categories count as billable time
+///
+/// `settings.billable_categories` applies to every project unless the project
+/// has its own list. Category names match case-insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct BillableCategories {
+    default: Vec<String>,
+    projects: HashMap<Uuid, Vec<String>>,
+}
+
+/// Tracked time split into billable and non-billable seconds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BillableTotals {
+    pub billable_seconds: u32,
+    pub non_billable_seconds: u32,
+}
+
+impl BillableCategories {
+    #[must_use]
+    pub fn new(default: Vec<String>, projects: HashMap<Uuid, Vec<String>>) -> Self {
+        Self { default, projects }
+    }
+
+    /// Load the default list and per-project overrides from the database
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if settings or overrides cannot be read
+    pub fn load(db: &Database) -> Result<Self> {
+        Ok(Self::new(
+            db.get_settings()?.billable_categories,
+            db.get_project_billable_categories()?,
+        ))
+    }
+
+    /// Whether any category is billable anywhere
+    #[must_use]
+    pub fn is_configured(&self) -> bool {
+        !self.default.is_empty() || self.projects.values().any(|c| !c.is_empty())
+    }
+
+    /// Whether time in `category` is billable for the given project
+    #[must_use]
+    pub fn is_billable(&self, category: &str, project_id: Option<Uuid>) -> bool {
+        project_id
+            .and_then(|id| self.projects.get(&id))
+            .unwrap_or(&self.default)
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(category))
+    }
+
+    /// Split the tracked time of `spans` into billable and non-billable
+    #[must_use]
+    pub fn totals(&self, spans: &[ActivitySpan]) -> BillableTotals {
+        let mut totals = BillableTotals::default();
+        for span in spans {
+            if self.is_billable(&span.category, span.project_id) {
+                totals.billable_seconds += span.duration_seconds;
+            } else {
+                totals.non_billable_seconds += span.duration_seconds;
+            }
+        }
+        totals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn span(category: &str, project_id: Option<Uuid>, seconds: u32) -> ActivitySpan {
+        let mut span = ActivitySpan::new(
+            "com.microsoft.VSCode".to_string(),
+            category.to_string(),
+            Utc::now(),
+            project_id,
+            None,
+            None,
+        );
+        span.duration_seconds = seconds;
+        span
+    }
+
+    #[test]
+    fn test_billable_totals_sum_only_configured_categories() {
+        let billable = BillableCategories::new(
+            vec!["Coding".to_string(), "meeting".to_string()],
+            HashMap::new(),
+        );
+        let spans = [
+            span("Coding", None, 3600),
+            span("Meeting", None, 1800),
+            span("Communication", None, 900),
+            span("Browser", None, 600),
+        ];
+
+        let totals = billable.totals(&spans);
+        assert_eq!(totals.billable_seconds, 5400);
+        assert_eq!(totals.non_billable_seconds, 1500);
+    }
+
+    #[test]
+    fn test_project_override_replaces_default_list() {
+        let client = Uuid::new_v4();
+        let internal = Uuid::new_v4();
+        let billable = BillableCategories::new(
+            vec!["Coding".to_string()],
+            HashMap::from([
+                (internal, Vec::new()),
+                (client, vec!["Meeting".to_string()]),
+            ]),
+        );
+
+        assert!(billable.is_billable("Coding", None));
+        assert!(!billable.is_billable("Coding", Some(internal)));
+        assert!(!billable.is_billable("Coding", Some(client)));
+        assert!(billable.is_billable("Meeting", Some(client)));
+
+        let totals = billable.totals(&[
+            span("Coding", Some(Uuid::new_v4()), 600),
+            span("Coding", Some(internal), 1200),
+            span("Meeting", Some(client), 300),
+        ]);
+        assert_eq!(totals.billable_seconds, 900);
+        assert_eq!(totals.non_billable_seconds, 1200);
+    }
+}
@@ -1,6 +1,11 @@
 use anyhow::Result;
 use std::sync::Arc;
-use toki_storage::{Category, ClassificationRule, Database};
+use toki_storage::{Category, ClassificationRule, Database, PatternType};
+
+use crate::shell_history::is_terminal_app;
+
+/// Built-in category of web browsers, refined by the page's window title
+const BROWSER_CATEGORY: &str = "Browser";
 
 /// Classification result with metadata
 #[derive(Debug, Clone)]
@@ -21,6 +26,24 @@ pub enum ClassificationSource {
     Default,
 }
 
+/// Confidence in a category assigned by the AI classifier
+pub const AI_CLASSIFICATION_CONFIDENCE: f32 = 0.7;
+
+/// Spans classified below this confidence are listed by `toki review --uncertain`
+pub const UNCERTAIN_CATEGORY_CONFIDENCE: f32 = 0.5;
+
+impl ClassificationSource {
+    /// How much a category from this source can be trusted (0.0 - 1.0)
+    #[must_use]
+    pub fn confidence(&self) -> f32 {
+        match self {
+            Self::UserRule => 0.95,
+            Self::BuiltInPattern => 0.8,
+            Self::Default => 0.1,
+        }
+    }
+}
+
 /// Classifier for categorizing applications based on rules
 /// Priority: User rules > Built-in regex patterns > Default
 pub struct Classifier {
@@ -91,63 +114,62 @@ impl Classifier {
 
     /// Classify with full result metadata
     #[must_use] pub fn classify_full(&self, app_id: &str, window_title: Option<&str>) -> ClassificationResult {
-        // 1. Check user rules first (highest priority)
-        for rule in &self.user_rules {
-            if rule.matches(window_title, app_id) {
-                log::debug!(
-                    "Matched user rule '{}' -> '{}' (hits: {})",
-                    rule.pattern,
-                    rule.category,
-                    rule.hit_count
-                );
+        // 1. Check user rules first (highest priority), title rules before app rules
+        // so e.g. a "GitHub" title rule wins over the browser's bundle ID rule
+        let user_rule = self
+            .matching_user_rule(app_id, window_title, true)
+            .or_else(|| self.matching_user_rule(app_id, window_title, false));
+        if let Some(rule) = user_rule {
+            log::debug!(
+                "Matched user rule '{}' -> '{}' (hits: {})",
+                rule.pattern,
+                rule.category,
+                rule.hit_count
+            );
 
-                // Record hit asynchronously
-                if let Some(db) = &self.database {
-                    if let Err(e) = db.record_rule_hit(rule.id) {
-                        log::warn!("Failed to record rule hit: {e}");
-                    }
+            // Record hit asynchronously
+            if let Some(db) = &self.database {
+                if let Err(e) = db.record_rule_hit(rule.id) {
+                    log::warn!("Failed to record rule hit: {e}");
                 }
-
-                return ClassificationResult {
-                    category: rule.category.clone(),
-                    matched_rule_id: Some(rule.id),
-                    source: ClassificationSource::UserRule,
-                };
             }
+
+            return ClassificationResult {
+                category: rule.category.clone(),
+                matched_rule_id: Some(rule.id),
+                source: ClassificationSource::UserRule,
+            };
         }
 
-        // 2. Check built-in patterns (window title first, then bundle ID)
-        if let Some(title) = window_title {
-            for category in &self.categories {
-                if let Ok(re) = regex::Regex::new(&category.pattern) {
-                    if re.is_match(title) {
-                        log::debug!(
-                            "Classified by window title '{}' as '{}'",
-                            title,
-                            category.name
-                        );
-                        return ClassificationResult {
-                            category: category.name.clone(),
-                            matched_rule_id: None,
-                            source: ClassificationSource::BuiltInPattern,
-                        };
-                    }
-                }
+        // 2. Check built-in patterns. A dedicated app (an IDE, Slack, Zoom) is
+        // classified by its bundle ID, so a file named `email.rs` stays Coding;
+        // browsers, terminals and unknown apps host many kinds of work, so their
+        // window title decides first
+        let app_category = self.matching_category(app_id);
+        let title_decides = app_category
+            .is_none_or(|category| category.name == BROWSER_CATEGORY || is_terminal_app(app_id));
+        if title_decides {
+            if let Some(category) = window_title.and_then(|title| self.matching_category(title)) {
+                log::debug!(
+                    "Classified by window title '{}' as '{}'",
+                    window_title.unwrap_or_default(),
+                    category.name
+                );
+                return ClassificationResult {
+                    category: category.name.clone(),
+                    matched_rule_id: None,
+                    source: ClassificationSource::BuiltInPattern,
+                };
             }
         }
 
-        // Check bundle ID
-        for category in &self.categories {
-            if let Ok(re) = regex::Regex::new(&category.pattern) {
-                if re.is_match(app_id) {
-                    log::debug!("Classified '{}' as '{}'", app_id, category.name);
-                    return ClassificationResult {
-                        category: category.name.clone(),
-                        matched_rule_id: None,
-                        source: ClassificationSource::BuiltInPattern,
-                    };
-                }
-            }
+        if let Some(category) = app_category {
+            log::debug!("Classified '{}' as '{}'", app_id, category.name);
+            return ClassificationResult {
+                category: category.name.clone(),
+                matched_rule_id: None,
+                source: ClassificationSource::BuiltInPattern,
+            };
         }
 
         // 3. Default category
@@ -159,6 +181,26 @@ impl Classifier {
         }
     }
 
+    /// First built-in category whose pattern matches `text`
+    fn matching_category(&self, text: &str) -> Option<&Category> {
+        self.categories.iter().find(|category| {
+            regex::Regex::new(&category.pattern).is_ok_and(|re| re.is_match(text))
+        })
+    }
+
+    /// First user rule matching the window title (`title_rules`) or the bundle ID
+    fn matching_user_rule(
+        &self,
+        app_id: &str,
+        window_title: Option<&str>,
+        title_rules: bool,
+    ) -> Option<&ClassificationRule> {
+        self.user_rules.iter().find(|rule| {
+            (rule.pattern_type != PatternType::BundleId) == title_rules
+                && rule.matches(window_title, app_id)
+        })
+    }
+
     /// Classify an application based on bundle ID and optional window title
     /// This allows detecting CLI tools running inside terminals
     #[must_use]
@@ -194,3 +236,120 @@ impl Classifier {
         Ok(rule)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn default_classifier() -> Classifier {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        Classifier::from_database(&db).unwrap()
+    }
+
+    /// Classifier over an in-memory database with a browser app rule and a GitHub title rule
+    fn classifier_with_browser_rules() -> (Arc<Database>, Classifier) {
+        let db = Arc::new(Database::new(Some(PathBuf::from(":memory:"))).unwrap());
+        for (pattern, pattern_type, category) in [
+            ("com.brave.Browser", PatternType::BundleId, "Browser"),
+            ("GitHub", PatternType::WindowTitle, "CodeReview"),
+        ] {
+            db.save_classification_rule(&ClassificationRule::from_correction(
+                pattern.to_string(),
+                pattern_type,
+                category.to_string(),
+            ))
+            .unwrap();
+        }
+        let classifier = Classifier::from_database_arc(db.clone()).unwrap();
+        (db, classifier)
+    }
+
+    fn rule_hits(db: &Database, pattern_type: &PatternType) -> u32 {
+        db.get_classification_rules()
+            .unwrap()
+            .into_iter()
+            .find(|rule| &rule.pattern_type == pattern_type)
+            .map_or(0, |rule| rule.hit_count)
+    }
+
+    #[test]
+    fn test_app_rule_matches_without_title_rule() {
+        let (db, classifier) = classifier_with_browser_rules();
+
+        let result = classifier.classify_full("com.brave.Browser", Some("Hacker News"));
+        assert_eq!(result.category, "Browser");
+        assert_eq!(result.source, ClassificationSource::UserRule);
+        assert_eq!(rule_hits(&db, &PatternType::BundleId), 1);
+        assert_eq!(rule_hits(&db, &PatternType::WindowTitle), 0);
+    }
+
+    #[test]
+    fn test_title_rule_matches_in_any_app() {
+        let (db, classifier) = classifier_with_browser_rules();
+
+        let result = classifier.classify_full("org.mozilla.firefox", Some("Pull requests - GitHub"));
+        assert_eq!(result.category, "CodeReview");
+        assert_eq!(result.source, ClassificationSource::UserRule);
+        assert_eq!(rule_hits(&db, &PatternType::WindowTitle), 1);
+    }
+
+    #[test]
+    fn test_title_rule_wins_over_app_rule() {
+        let (db, classifier) = classifier_with_browser_rules();
+
+        let result = classifier.classify_full("com.brave.Browser", Some("Fix parser #42 - GitHub"));
+        assert_eq!(result.category, "CodeReview");
+        assert_eq!(rule_hits(&db, &PatternType::WindowTitle), 1);
+        assert_eq!(rule_hits(&db, &PatternType::BundleId), 0);
+    }
+
+    #[test]
+    fn test_calls_classify_as_meeting() {
+        let classifier = default_classifier();
+
+        assert_eq!(classifier.classify("us.zoom.xos"), "Meeting");
+        assert_eq!(classifier.classify("com.microsoft.teams2"), "Meeting");
+        assert_eq!(
+            classifier.classify_with_context("com.google.Chrome", Some("Meet - abc-defg-hij")),
+            "Meeting"
+        );
+    }
+
+    #[test]
+    fn test_chat_and_email_classify_as_async_comms() {
+        let classifier = default_classifier();
+
+        assert_eq!(
+            classifier.classify("com.tinyspeck.slackmacgap"),
+            "AsyncComms"
+        );
+        assert_eq!(classifier.classify("com.apple.mail"), "AsyncComms");
+        assert_eq!(
+            classifier.classify_with_context("com.google.Chrome", Some("Inbox (3) - Gmail")),
+            "AsyncComms"
+        );
+    }
+
+    #[test]
+    fn test_ide_title_mentioning_mail_stays_coding() {
+        let classifier = default_classifier();
+
+        assert_eq!(
+            classifier.classify_with_context(
+                "com.microsoft.VSCode",
+                Some("email.rs — Visual Studio Code")
+            ),
+            "Coding"
+        );
+        assert_eq!(
+            classifier.classify_with_context("com.jetbrains.intellij", Some("api – mailer.rs")),
+            "Coding"
+        );
+        // Terminals are still refined by title
+        assert_eq!(
+            classifier.classify_with_context("com.apple.Terminal", Some("claude ~/code/api")),
+            "AI-CLI"
+        );
+    }
+}
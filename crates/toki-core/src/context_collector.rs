@@ -5,6 +5,7 @@
 //! - Recently edited files (from IDE workspace)
 //! - Window titles over time
 //! - Browser URLs (if enabled, for PM system pages)
+//! - Terminal commands (if enabled, from the shell history)
 //!
 //! Also computes context vectors for Semantic Gravity calculation.
 
@@ -16,6 +17,8 @@ use std::path::Path;
 use toki_detector::GitDetector;
 use uuid::Uuid;
 
+use crate::native_messaging::is_url_allowed;
+
 /// Maximum number of signals to keep in memory before flushing
 const MAX_SIGNALS_IN_MEMORY: usize = 100;
 
@@ -50,6 +53,8 @@ pub enum SignalType {
     BrowserUrl,
     /// IDE workspace/project name
     WorkspaceName,
+    /// Shell command run in the focused terminal (e.g., "cargo test")
+    TerminalCommand,
 }
 
 impl SignalType {
@@ -58,12 +63,13 @@ impl SignalType {
     #[must_use]
     pub const fn weight(&self) -> f32 {
         match self {
-            Self::GitBranch => 1.0,      // Highest - explicit issue reference
-            Self::GitCommit => 0.9,      // Very high - explicit description
-            Self::BrowserUrl => 0.8,     // High - PM system pages
-            Self::WorkspaceName => 0.6,  // Medium - project context
-            Self::EditedFile => 0.4,     // Low-medium - indirect signal
-            Self::WindowTitle => 0.3,    // Low - can be noisy
+            Self::GitBranch => 1.0,       // Highest - explicit issue reference
+            Self::GitCommit => 0.9,       // Very high - explicit description
+            Self::BrowserUrl => 0.8,      // High - PM system pages
+            Self::TerminalCommand => 0.7, // High - says what kind of work it is
+            Self::WorkspaceName => 0.6,   // Medium - project context
+            Self::EditedFile => 0.4,      // Low-medium - indirect signal
+            Self::WindowTitle => 0.3,     // Low - can be noisy
         }
     }
 }
@@ -82,6 +88,8 @@ pub struct ContextCollector {
     last_git_branch: Option<String>,
     /// Last collected window title (to avoid duplicates)
     last_window_title: Option<String>,
+    /// Last collected browser URL (to avoid duplicates)
+    last_browser_url: Option<String>,
     /// Cached project context vector
     context_vector: Option<Vec<f32>>,
 }
@@ -97,6 +105,7 @@ impl ContextCollector {
             current_span_id: None,
             last_git_branch: None,
             last_window_title: None,
+            last_browser_url: None,
             context_vector: None,
         }
     }
@@ -159,13 +168,23 @@ impl ContextCollector {
     }
 
     /// Collect browser URL (only for allowed domains)
-    pub fn collect_browser_url(&mut self, url: &str, allowed_domains: &[String]) {
-        // Check if URL is from an allowed domain
-        for domain in allowed_domains {
-            if url.contains(domain) {
-                self.add_signal(SignalType::BrowserUrl, url.to_string());
-                break;
-            }
+    ///
+    /// Returns whether the URL was recorded: it must be on an allowed domain
+    /// and differ from the last one collected.
+    pub fn collect_browser_url(&mut self, url: &str, allowed_domains: &[String]) -> bool {
+        if !is_url_allowed(url, allowed_domains) || self.last_browser_url.as_deref() == Some(url) {
+            return false;
+        }
+        self.add_signal(SignalType::BrowserUrl, url.to_string());
+        self.last_browser_url = Some(url.to_string());
+        true
+    }
+
+    /// Collect a command run in the focused terminal
+    pub fn collect_terminal_command(&mut self, command: &str) {
+        let command = command.trim();
+        if !command.is_empty() {
+            self.add_signal(SignalType::TerminalCommand, command.to_string());
         }
     }
 
@@ -279,6 +298,7 @@ impl ContextCollector {
         let mut edited_files = Vec::new();
         let mut window_titles = Vec::new();
         let mut browser_urls = Vec::new();
+        let mut terminal_commands = Vec::new();
 
         for signal in signals {
             match signal.signal_type {
@@ -287,6 +307,7 @@ impl ContextCollector {
                 SignalType::EditedFile => edited_files.push(signal.value.clone()),
                 SignalType::WindowTitle => window_titles.push(signal.value.clone()),
                 SignalType::BrowserUrl => browser_urls.push(signal.value.clone()),
+                SignalType::TerminalCommand => terminal_commands.push(signal.value.clone()),
                 SignalType::WorkspaceName => {}
             }
         }
@@ -303,6 +324,7 @@ impl ContextCollector {
             edited_files,
             window_titles,
             browser_urls,
+            terminal_commands,
         }
     }
 
@@ -334,6 +356,17 @@ impl ContextCollector {
             let files: Vec<_> = summary.edited_files.iter().take(5).collect();
             parts.push(format!("Files: {}", files.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")));
         }
+
+        if !summary.terminal_commands.is_empty() {
+            let commands: Vec<&str> = summary
+                .terminal_commands
+                .iter()
+                .rev()
+                .take(5)
+                .map(String::as_str)
+                .collect();
+            parts.push(format!("Commands: {}", commands.join("; ")));
+        }
         
         parts.join("\n")
     }
@@ -353,6 +386,8 @@ pub struct SignalSummary {
     pub edited_files: Vec<String>,
     pub window_titles: Vec<String>,
     pub browser_urls: Vec<String>,
+    #[serde(default)]
+    pub terminal_commands: Vec<String>,
 }
 
 impl SignalSummary {
@@ -363,6 +398,7 @@ impl SignalSummary {
             || !self.git_commits.is_empty()
             || !self.edited_files.is_empty()
             || !self.browser_urls.is_empty()
+            || !self.terminal_commands.is_empty()
     }
 
     /// Get a text representation for AI prompt
@@ -384,6 +420,16 @@ impl SignalSummary {
         if !self.browser_urls.is_empty() {
             parts.push(format!("PM URLs: {}", self.browser_urls.join(", ")));
         }
+        if !self.terminal_commands.is_empty() {
+            let commands: Vec<&str> = self
+                .terminal_commands
+                .iter()
+                .rev()
+                .take(10)
+                .map(String::as_str)
+                .collect();
+            parts.push(format!("Terminal commands: {}", commands.join("; ")));
+        }
 
         parts.join("\n")
     }
@@ -415,6 +461,37 @@ mod tests {
         assert_eq!(collector.signal_count(), 2);
     }
 
+    #[test]
+    fn test_browser_url_respects_whitelist() {
+        let mut collector = ContextCollector::new();
+        let allowed = vec!["linear.app".to_string()];
+
+        assert!(collector.collect_browser_url("https://linear.app/acme/issue/ACM-7", &allowed));
+        assert!(!collector.collect_browser_url("https://linear.app/acme/issue/ACM-7", &allowed));
+        assert!(!collector.collect_browser_url("https://news.example.com/?linear.app", &allowed));
+
+        assert_eq!(collector.get_signal_summary().browser_urls.len(), 1);
+    }
+
+    #[test]
+    fn test_terminal_commands_in_summary_and_context() {
+        let mut collector = ContextCollector::new();
+
+        collector.collect_terminal_command("  ");
+        collector.collect_terminal_command("cargo test");
+        collector.collect_terminal_command("kubectl apply -f deploy.yaml");
+
+        let summary = collector.get_signal_summary();
+        assert!(summary.has_signals());
+        assert_eq!(summary.terminal_commands.len(), 2);
+        assert!(summary
+            .to_prompt_text()
+            .contains("Terminal commands: kubectl apply -f deploy.yaml; cargo test"));
+        assert!(collector
+            .get_context_text()
+            .contains("Commands: kubectl apply"));
+    }
+
     #[test]
     fn test_signal_summary() {
         let mut collector = ContextCollector::new();
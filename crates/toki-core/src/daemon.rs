@@ -1,18 +1,24 @@
 use crate::{
-    classifier::Classifier,
+    classifier::{Classifier, AI_CLASSIFICATION_CONFIDENCE},
     config::get_data_dir,
-    ipc::{listen, DaemonIpcHandler},
-    monitor::{create_monitor, SystemMonitor},
+    context_collector::ContextCollector,
+    display, file_category,
+    ipc::{listen, DaemonIpcHandler, ProjectQuery, ReloadReply},
+    monitor::{create_monitor, AppActivity, SystemMonitor},
+    notifier::NotificationDispatcher,
+    remote,
+    self_usage::{self, SELF_USE_CATEGORY},
     session_manager::SessionManager,
+    shell_history::{self, ShellHistory},
     ai_classifier::{AiClassifier, ContextSnapshot},
 };
 use anyhow::Result;
 use std::{sync::Arc, time::Duration};
-use toki_detector::WorkContextDetector;
-use toki_storage::{ActivitySpan, Database};
-use tokio::time::interval;
+use toki_detector::{DetectionSource, WorkContextDetector, WorkItemRef};
+use toki_storage::{ActivitySpan, Database, NotificationSettings, SelfTrackingPolicy};
+use tokio::{sync::mpsc, time::interval};
 use uuid::Uuid;
-use toki_ai::AiService;
+use toki_ai::{AiService, TimeAnalyzer};
 
 pub struct Daemon {
     database: Arc<Database>,
@@ -20,16 +26,23 @@ pub struct Daemon {
     classifier: Classifier,
     ai_classifier: Option<AiClassifier>,
     context_detector: WorkContextDetector,
+    context_collector: ContextCollector,
     session_manager: SessionManager,
     ipc_handler: Arc<DaemonIpcHandler>,
     shutdown_signal: Arc<std::sync::atomic::AtomicBool>,
+    reload_requests: mpsc::UnboundedReceiver<ReloadReply>,
+    project_queries: mpsc::UnboundedReceiver<ProjectQuery>,
     current_activity_span: Option<ActivitySpan>,
     current_session_id: Option<Uuid>,
-    current_project_id: Option<Uuid>,   // Primary: which project
-    current_work_item_id: Option<Uuid>, // Optional: specific issue
+    current_session_started_at: Option<chrono::DateTime<chrono::Utc>>,
+    current_project_id: Option<Uuid>,       // Primary: which project
+    current_work_item_id: Option<Uuid>,     // Optional: specific issue
+    last_attributed_commit: Option<String>, // Latest commit whose issue was carried back
     session_active_seconds: u32,
     session_idle_seconds: u32,
     tick_interval_seconds: u64,
+    notifications: Option<NotificationDispatcher>,
+    shell_history: Option<ShellHistory>, // Kept only while a terminal stays focused
 }
 
 impl Daemon {
@@ -43,47 +56,82 @@ impl Daemon {
     pub fn new(db: Database, tick_interval_seconds: u64) -> Result<Self> {
         let db_arc = Arc::new(db);
         let shutdown_signal = Arc::new(std::sync::atomic::AtomicBool::new(false));
-
-        // Initialize AI Classifier if enabled
-        let ai_classifier = match db_arc.get_ai_config() {
-            Ok(config) if config.enabled => {
-                match AiService::new(config) {
-                    Ok(service) => {
-                        log::info!("AI Classifier enabled with model: {}", service.model_name());
-                        Some(AiClassifier::new(Arc::new(service), 100))
-                    },
-                    Err(e) => {
-                        log::warn!("Failed to initialize AI service for classifier: {e}");
-                        None
-                    }
-                }
-            }
-            Ok(_) => {
-                log::info!("AI Classifier disabled in config");
-                None
-            },
-            Err(_) => None,
-        };
+        let (reload_sender, reload_requests) = mpsc::unbounded_channel();
+        let (query_sender, project_queries) = mpsc::unbounded_channel();
 
         Ok(Self {
             database: db_arc.clone(),
             monitor: create_monitor()?,
             classifier: Classifier::from_database_arc(db_arc.clone())?,
-            ai_classifier,
+            ai_classifier: Self::load_ai_classifier(&db_arc),
             context_detector: WorkContextDetector::new(),
+            context_collector: ContextCollector::new(),
             session_manager: SessionManager::new(db_arc.clone()),
-            ipc_handler: Arc::new(DaemonIpcHandler::new(shutdown_signal.clone())),
+            ipc_handler: Arc::new(DaemonIpcHandler::new(
+                shutdown_signal.clone(),
+                reload_sender,
+                query_sender,
+            )),
             shutdown_signal,
+            reload_requests,
+            project_queries,
             current_activity_span: None,
             current_session_id: None,
+            current_session_started_at: None,
             current_project_id: None,
             current_work_item_id: None,
+            last_attributed_commit: None,
             session_active_seconds: 0,
             session_idle_seconds: 0,
             tick_interval_seconds,
+            notifications: None,
+            shell_history: None,
         })
     }
 
+    /// Initialize the AI classifier if enabled
+    fn load_ai_classifier(db: &Database) -> Option<AiClassifier> {
+        match db.get_ai_config() {
+            Ok(config) if config.enabled => match AiService::new(config) {
+                Ok(service) => {
+                    log::info!("AI Classifier enabled with model: {}", service.model_name());
+                    Some(AiClassifier::new(Arc::new(service), 100))
+                }
+                Err(e) => {
+                    log::warn!("Failed to initialize AI service for classifier: {e}");
+                    None
+                }
+            },
+            Ok(_) => {
+                log::info!("AI Classifier disabled in config");
+                None
+            }
+            Err(_) => None,
+        }
+    }
+
+    /// Re-read settings and everything loaded once at startup
+    ///
+    /// Settings themselves are read every tick; this also rebuilds the
+    /// classifier (categories and user rules) and the AI classifier. The
+    /// current session and span are kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if settings or categories cannot be read, in which
+    /// case the previous configuration stays in effect
+    pub fn reload(&mut self) -> Result<()> {
+        let settings = self.database.get_settings()?;
+        self.classifier = Classifier::from_database_arc(self.database.clone())?;
+        self.ai_classifier = Self::load_ai_classifier(&self.database);
+        log::info!(
+            "Reloaded configuration (idle threshold {}s, {} excluded apps)",
+            settings.idle_threshold_seconds,
+            settings.excluded_apps.len()
+        );
+        Ok(())
+    }
+
     /// Run the daemon with signal handling.
     ///
     /// # Errors
@@ -112,6 +160,18 @@ impl Daemon {
                         log::error!("Daemon tick failed: {e}");
                     }
                 }
+                Some(reply) = self.reload_requests.recv() => {
+                    let outcome = self.reload().map_err(|e| format!("{e:#}"));
+                    if let Err(e) = &outcome {
+                        log::error!("Daemon reload failed: {e}");
+                    }
+                    // The CLI may have given up waiting
+                    let _ = reply.send(outcome);
+                }
+                Some((query, reply)) = self.project_queries.recv() => {
+                    let matches = self.database.search_projects(&query);
+                    let _ = reply.send(matches.map_err(|e| format!("{e:#}")));
+                }
                 _ = tokio::signal::ctrl_c() => {
                     log::info!("Received Ctrl-C, shutting down...");
                     self.shutdown_signal.store(true, std::sync::atomic::Ordering::SeqCst);
@@ -134,10 +194,14 @@ impl Daemon {
     }
 
     #[allow(clippy::cognitive_complexity)]
+    #[allow(clippy::too_many_lines)]
     async fn tick(&mut self) -> Result<()> {
         let settings = self.database.get_settings()?;
         let now = chrono::Utc::now();
         let tick_seconds = u32::try_from(self.tick_interval_seconds).unwrap_or(u32::MAX);
+        // Put back only when a terminal is focused again, so commands from a
+        // pause, an excluded app or idle time are skipped
+        let history = self.shell_history.take();
 
         // Check if tracking is paused
         if settings.pause_tracking {
@@ -146,12 +210,17 @@ impl Daemon {
             return Ok(());
         }
 
+        self.drop_discarded_session()?;
+
         // Check idle state
         let is_idle = self
             .monitor
             .is_idle(settings.idle_threshold_seconds)
             .await?;
 
+        self.dispatch_notifications(&settings.notifications, is_idle, tick_seconds)
+            .await;
+
         // Session management based on work hours and idle state
         if self.session_manager.should_start_session(now)
             && self.current_session_id.is_none()
@@ -159,14 +228,19 @@ impl Daemon {
         {
             let session_id = self.session_manager.create_session()?;
             self.current_session_id = Some(session_id);
+            self.current_session_started_at = Some(now);
             self.session_active_seconds = 0;
             self.session_idle_seconds = 0;
             log::info!("Started new work session: {session_id}");
         }
 
+        self.split_long_session(settings.max_session_seconds, now)?;
+
         if is_idle {
             self.session_idle_seconds += tick_seconds;
-            self.finalize_current_span()?;
+            // The span ended when the user walked away, not when idle was noticed
+            let idle_started_at = self.idle_started_at(now).await;
+            self.finalize_current_span_at(idle_started_at)?;
 
             if self
                 .session_manager
@@ -210,16 +284,46 @@ impl Daemon {
                 return Ok(());
             }
 
+            // A window on another display than the one the user is looking at
+            // says little about the work, so the current span carries on
+            if !display::counts_toward_category(&app, settings.require_focused_display) {
+                log::debug!("{} is not on the focused display, ignoring", app.app_id);
+                return Ok(());
+            }
+
+            // Time spent running toki itself is not work on the project
+            let self_tracking = self_usage::self_tracking_for(
+                settings.self_tracking,
+                &app.app_id,
+                window_title.as_deref(),
+            );
+            if self_tracking == SelfTrackingPolicy::Exclude {
+                log::debug!("{} is toki itself, not tracking", app.app_id);
+                self.finalize_current_span()?;
+                return Ok(());
+            }
+
             // Semantic Classification
-            let category = self
-                .classify_activity(&app.app_id, window_title.as_deref(), project_name.as_deref())
-                .await;
+            let (category, confidence) = if self_tracking == SelfTrackingPolicy::Categorize {
+                (SELF_USE_CATEGORY.to_string(), 1.0)
+            } else {
+                self.classify_activity(
+                    &app.app_id,
+                    window_title.as_deref(),
+                    project_name.as_deref(),
+                )
+                .await
+            };
 
             // Only create new span when APP changes (not when project changes within same app)
-            // This allows natural multi-window workflows without fragmenting time tracking
+            // This allows natural multi-window workflows without fragmenting time tracking.
+            // Switching into or out of toki inside the same terminal also starts a new span.
             let current_span = self.current_activity_span.as_ref();
             let app_changed = match current_span {
-                Some(span) => span.app_bundle_id != app.app_id,
+                Some(span) => {
+                    span.app_bundle_id != app.app_id
+                        || (span.category == SELF_USE_CATEGORY) != (category == SELF_USE_CATEGORY)
+                }
                 None => true,
             };
 
@@ -232,7 +336,7 @@ impl Daemon {
                 self.finalize_current_span()?;
                 self.current_project_id = project_id;
                 self.current_work_item_id = work_item_id;
-                self.start_new_span(app.app_id, category.to_string(), project_id, work_item_id)?;
+                self.start_new_span(&app, category, confidence, project_id, work_item_id)?;
             } else {
                 // App is the same - update project tracking without creating new span
                 // Track time spent per project in parallel
@@ -243,6 +347,15 @@ impl Daemon {
                 self.current_project_id = project_id;
                 self.current_work_item_id = work_item_id;
             }
+
+            if settings.capture_browser_url {
+                self.record_browser_url(&app.app_id, &settings.url_whitelist)
+                    .await?;
+            }
+
+            if settings.capture_terminal_commands && shell_history::is_terminal_app(&app.app_id) {
+                self.record_terminal_commands(history, settings.terminal_history_path.as_deref())?;
+            }
         } else {
             self.finalize_current_span()?;
         }
@@ -263,12 +376,14 @@ impl Daemon {
     }
 
     /// Classify the activity using AI or fallback classifier.
+    ///
+    /// Returns the category and how confident the classification is.
     async fn classify_activity(
         &self,
         app_id: &str,
         window_title: Option<&str>,
         project_name: Option<&str>,
-    ) -> String {
+    ) -> (String, f32) {
         if let Some(ai) = &self.ai_classifier {
             let snapshot = ContextSnapshot {
                 app_id: app_id.to_string(),
@@ -277,22 +392,19 @@ impl Daemon {
                 project_name: project_name.map(String::from),
             };
             match ai.classify(snapshot).await {
-                Ok(res) => res.category,
-                Err(e) => {
-                    log::debug!("AI classification skipped/failed: {e}");
-                    self.classifier.classify_with_context(app_id, window_title)
-                }
+                Ok(res) => return (res.category, AI_CLASSIFICATION_CONFIDENCE),
+                Err(e) => log::debug!("AI classification skipped/failed: {e}"),
             }
-        } else {
-            self.classifier.classify_with_context(app_id, window_title)
         }
+        let result = self.classifier.classify_full(app_id, window_title);
+        (result.category, result.source.confidence())
     }
 
     /// Detect project (primary) and optionally work item from context
     /// Project = the workspace/codebase being worked on
     /// Work item = optional issue ID (from git branch, commit, etc.)
     async fn detect_project_and_work_item(
-        &self,
+        &mut self,
         window_title: Option<&str>,
     ) -> Result<(Option<Uuid>, Option<Uuid>, Option<String>)> {
         let settings = self.database.get_settings()?;
@@ -326,6 +438,7 @@ impl Daemon {
 
             // Try to detect work item from git (optional)
             let work_item_id = self.detect_work_item_from_git(&path)?;
+            self.attribute_late_commit(project.id, &path, settings.commit_grace_seconds)?;
 
             Some((project.id, work_item_id, Some(project_name)))
         } else {
@@ -346,33 +459,90 @@ impl Daemon {
     ) -> Result<Option<Uuid>> {
         // Use context detector to find issue ID from git
         if let Some(work_ref) = self.context_detector.detect_from_path(workspace_path) {
-            let issue_id_str = work_ref.issue_id.full_id();
-            let external_system = work_ref.source.to_string();
-
-            let work_item = if let Some(item) = self
-                .database
-                .get_work_item(&issue_id_str, &external_system)?
-            {
-                item
-            } else {
-                let new_item = toki_storage::WorkItem::new(issue_id_str, external_system);
-                self.database.upsert_work_item(&new_item)?;
-                new_item
-            };
-            return Ok(Some(work_item.id));
+            let work_item_id = self.work_item_for(&work_ref)?;
+            return Ok(Some(work_item_id));
         }
         Ok(None)
     }
 
+    /// Back-attribute a fresh commit's issue to the project's preceding spans
+    ///
+    /// A commit usually lands after the work it describes, so its issue also
+    /// covers unattributed spans from the `grace_seconds` before it.
+    fn attribute_late_commit(
+        &mut self,
+        project_id: Uuid,
+        workspace_path: &std::path::Path,
+        grace_seconds: u32,
+    ) -> Result<()> {
+        if grace_seconds == 0 {
+            return Ok(());
+        }
+        let Some(commit) = self
+            .context_detector
+            .detect_commit_from_path(workspace_path)
+        else {
+            return Ok(());
+        };
+        if self.last_attributed_commit.as_deref() == Some(commit.hash.as_str()) {
+            return Ok(());
+        }
+        self.last_attributed_commit = Some(commit.hash);
+
+        let grace = chrono::Duration::seconds(i64::from(grace_seconds));
+        let Some(committed_at) = chrono::DateTime::from_timestamp(commit.committed_at, 0) else {
+            return Ok(());
+        };
+        // An old HEAD (e.g. at daemon start) is not a new commit
+        if chrono::Utc::now() - committed_at > grace {
+            return Ok(());
+        }
+
+        // Commit detection is reported like branch detection, so reuse its work item
+        let work_item_id = self.work_item_for(&WorkItemRef {
+            issue_id: commit.issue_id,
+            source: DetectionSource::GitBranch,
+        })?;
+        let updated = self.database.attribute_project_spans(
+            project_id,
+            work_item_id,
+            committed_at - grace,
+            committed_at,
+        )?;
+        if updated > 0 {
+            log::info!("Attributed {updated} earlier span(s) to the latest commit's issue");
+        }
+        Ok(())
+    }
+
+    /// Get or create the work item for a detected issue
+    fn work_item_for(&self, work_ref: &WorkItemRef) -> Result<Uuid> {
+        let issue_id_str = work_ref.issue_id.full_id();
+        let external_system = work_ref.source.to_string();
+
+        let work_item = if let Some(item) = self
+            .database
+            .get_work_item(&issue_id_str, &external_system)?
+        {
+            item
+        } else {
+            let new_item = toki_storage::WorkItem::new(issue_id_str, external_system);
+            self.database.upsert_work_item(&new_item)?;
+            new_item
+        };
+        Ok(work_item.id)
+    }
+
     fn start_new_span(
         &mut self,
-        app_bundle_id: String,
+        app: &AppActivity,
         category: String,
+        category_confidence: f32,
         project_id: Option<Uuid>,
         work_item_id: Option<Uuid>,
     ) -> Result<()> {
-        let span = ActivitySpan::new(
-            app_bundle_id,
+        let mut span = ActivitySpan::new(
+            app.app_id.clone(),
             category,
             chrono::Utc::now(),
             project_id,
@@ -381,21 +551,266 @@ impl Daemon {
         );
         // Note: Context (git branch, edited files, etc.) can be enriched later
         // through the CLI `toki tag` command or AI analysis
+        if remote::tag_remote_span(&mut span, app.window_title.as_deref()) {
+            log::info!("Remote-desktop session detected, flagging span as remote");
+        }
+        display::tag_span_display(&mut span, app);
 
         self.database.create_activity_span(&span)?;
+        self.database
+            .set_activity_span_category_confidence(span.id, category_confidence)?;
         self.current_activity_span = Some(span);
         Ok(())
     }
 
+    /// Record the tab the browser extension last reported for this app
+    ///
+    /// Only whitelisted URLs are kept, as a context signal and on the current
+    /// span. The span is reloaded since its context may have been enriched
+    /// while it was open.
+    async fn record_browser_url(&mut self, app_id: &str, url_whitelist: &[String]) -> Result<()> {
+        let Some(tab) = self.ipc_handler.take_browser_tab(app_id).await else {
+            return Ok(());
+        };
+        let Some(span_id) = self.current_activity_span.as_ref().map(|span| span.id) else {
+            return Ok(());
+        };
+        self.context_collector.set_project(self.current_project_id);
+        self.context_collector.set_span(Some(span_id));
+        if !self
+            .context_collector
+            .collect_browser_url(&tab.url, url_whitelist)
+        {
+            return Ok(());
+        }
+
+        let Some(span) = self.database.get_activity_span(span_id)? else {
+            return Ok(());
+        };
+        let mut context = span.context.unwrap_or_default();
+        if !context.browser_urls.contains(&tab.url) {
+            log::debug!("Browser on {}, adding it to the current span", tab.url);
+            context.browser_urls.push(tab.url);
+            self.database
+                .update_activity_span_context(span_id, &context)?;
+        }
+        Ok(())
+    }
+
+    /// Record the commands the shell appended to its history since the last tick
+    ///
+    /// `history` is the tail kept from the previous tick, `None` when the
+    /// terminal was not focused then, in which case reading starts at the
+    /// current end of the file. Commands go to the context collector and the
+    /// current span.
+    fn record_terminal_commands(
+        &mut self,
+        history: Option<ShellHistory>,
+        configured_path: Option<&str>,
+    ) -> Result<()> {
+        let Some(path) = configured_path
+            .map(std::path::PathBuf::from)
+            .or_else(shell_history::default_history_path)
+        else {
+            return Ok(());
+        };
+        let mut history = history
+            .filter(|history| history.path() == path)
+            .unwrap_or_else(|| ShellHistory::new(path));
+        let commands = history.read_new_commands();
+        self.shell_history = Some(history);
+        let commands = match commands {
+            Ok(commands) if !commands.is_empty() => commands,
+            Ok(_) => return Ok(()),
+            Err(e) => {
+                log::debug!("Failed to read shell history: {e}");
+                return Ok(());
+            }
+        };
+
+        let Some(span_id) = self.current_activity_span.as_ref().map(|span| span.id) else {
+            return Ok(());
+        };
+        self.context_collector.set_project(self.current_project_id);
+        self.context_collector.set_span(Some(span_id));
+        for command in &commands {
+            self.context_collector.collect_terminal_command(command);
+        }
+
+        let Some(span) = self.database.get_activity_span(span_id)? else {
+            return Ok(());
+        };
+        let mut context = span.context.unwrap_or_default();
+        log::debug!(
+            "{} terminal commands run, adding them to the current span",
+            commands.len()
+        );
+        context.terminal_commands.extend(commands);
+        self.database
+            .update_activity_span_context(span_id, &context)?;
+        Ok(())
+    }
+
     fn finalize_current_span(&mut self) -> Result<()> {
+        self.finalize_current_span_at(chrono::Utc::now())
+    }
+
+    /// Finalize the current span at `end_time`, but never before it started
+    fn finalize_current_span_at(&mut self, end_time: chrono::DateTime<chrono::Utc>) -> Result<()> {
         if let Some(span) = self.current_activity_span.take() {
             self.database
-                .finalize_activity_span(span.id, chrono::Utc::now())?;
+                .finalize_activity_span(span.id, end_time.max(span.start_time))?;
+            self.refine_span_category(span.id)?;
+            self.record_work_pattern(span.id)?;
+        }
+        Ok(())
+    }
+
+    /// Store the work pattern of a finished span for later analytics
+    fn record_work_pattern(&self, span_id: Uuid) -> Result<()> {
+        let Some(span) = self.database.get_activity_span(span_id)? else {
+            return Ok(());
+        };
+        if let Some(pattern) = TimeAnalyzer::detect_span_pattern(&span) {
+            self.database
+                .set_activity_span_work_pattern(span_id, pattern.as_str())?;
+        }
+        Ok(())
+    }
+
+    /// Refine a finished span's category from the files edited during it
+    ///
+    /// Reloaded from the database, since its context may have been enriched
+    /// while the span was open.
+    fn refine_span_category(&self, span_id: Uuid) -> Result<()> {
+        let Some(mut span) = self.database.get_activity_span(span_id)? else {
+            return Ok(());
+        };
+        let original = span.category.clone();
+        if file_category::refine_span_category(&mut span) {
+            log::debug!(
+                "Refined span category {original} -> {} from edited files",
+                span.category
+            );
+            self.database
+                .update_activity_span_category(span_id, &span.category)?;
+        }
+        Ok(())
+    }
+
+    /// Forget the current session if it was discarded via `toki session abort`
+    ///
+    /// The session and its spans were already closed (or deleted) by the abort,
+    /// so they are dropped without being finalized again.
+    fn drop_discarded_session(&mut self) -> Result<()> {
+        let Some(session_id) = self.current_session_id else {
+            return Ok(());
+        };
+        if !self.database.is_session_discarded(session_id)? {
+            return Ok(());
+        }
+
+        if self
+            .current_activity_span
+            .as_ref()
+            .is_some_and(|span| span.session_id == Some(session_id))
+        {
+            self.current_activity_span = None;
+        }
+        self.current_session_id = None;
+        self.current_session_started_at = None;
+        self.session_active_seconds = 0;
+        self.session_idle_seconds = 0;
+        log::info!("Session {session_id} was discarded");
+        Ok(())
+    }
+
+    /// Start a new session once the current one exceeds the maximum length
+    ///
+    /// Guards against marathon sessions, e.g. a media player keeping the
+    /// machine "active" overnight.
+    fn split_long_session(
+        &mut self,
+        max_session_seconds: u32,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        let (Some(session_id), Some(started_at)) =
+            (self.current_session_id, self.current_session_started_at)
+        else {
+            return Ok(());
+        };
+        if !SessionManager::exceeds_max_length(started_at, now, max_session_seconds) {
+            return Ok(());
         }
+
+        // Flush stats for the closing session before the counters reset
+        self.session_manager.update_session_stats(
+            session_id,
+            self.session_active_seconds,
+            self.session_idle_seconds,
+            0,
+        )?;
+
+        let (new_session_id, continuation) = self.session_manager.split_session(
+            session_id,
+            self.current_activity_span.as_ref(),
+            now,
+        )?;
+        self.current_session_id = Some(new_session_id);
+        self.current_session_started_at = Some(now);
+        self.current_activity_span = continuation;
+        self.session_active_seconds = 0;
+        self.session_idle_seconds = 0;
         Ok(())
     }
 
+    /// When the user went idle, from the monitor's idle time
+    ///
+    /// Falls back to `now` if the monitor cannot tell.
+    async fn idle_started_at(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> chrono::DateTime<chrono::Utc> {
+        match self.monitor.get_idle_seconds().await {
+            Ok(idle_seconds) => now - chrono::Duration::seconds(i64::from(idle_seconds)),
+            Err(e) => {
+                log::warn!("Failed to read idle time, ending span now: {e}");
+                now
+            }
+        }
+    }
+
+    /// Feed this tick to the notifier, rebuilding it when its settings changed
+    async fn dispatch_notifications(
+        &mut self,
+        settings: &NotificationSettings,
+        is_idle: bool,
+        tick_seconds: u32,
+    ) {
+        if self
+            .notifications
+            .as_ref()
+            .map(NotificationDispatcher::settings)
+            != Some(settings)
+        {
+            self.notifications = NotificationDispatcher::from_settings(settings);
+        }
+
+        let in_session = self.current_session_id.is_some();
+        if let Some(dispatcher) = self.notifications.as_mut() {
+            dispatcher
+                .observe(
+                    is_idle,
+                    in_session,
+                    tick_seconds,
+                    chrono::Local::now().naive_local(),
+                )
+                .await;
+        }
+    }
+
     fn finalize_current_session(&mut self) -> Result<()> {
+        self.current_session_started_at = None;
         if let Some(session_id) = self.current_session_id.take() {
             self.session_manager.finalize_session(session_id)?;
             log::info!(
@@ -418,3 +833,158 @@ impl Daemon {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use crate::native_messaging::BrowserTab;
+    use toki_storage::{ClassificationRule, PatternType};
+
+    /// Monitor that has been idle for a fixed time, with the editor in front
+    struct IdleMonitor {
+        idle_seconds: u32,
+    }
+
+    #[async_trait::async_trait]
+    impl SystemMonitor for IdleMonitor {
+        async fn start_monitoring(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_active_app(&self) -> Result<Option<AppActivity>> {
+            Ok(Some(editor_activity()))
+        }
+
+        async fn is_idle(&self, threshold_seconds: u32) -> Result<bool> {
+            Ok(self.idle_seconds >= threshold_seconds)
+        }
+
+        async fn get_idle_seconds(&self) -> Result<u32> {
+            Ok(self.idle_seconds)
+        }
+
+        async fn stop_monitoring(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn editor_activity() -> AppActivity {
+        AppActivity {
+            app_id: "com.example.editor".to_string(),
+            app_name: "Editor".to_string(),
+            window_title: None,
+            is_active: true,
+            timestamp: chrono::Utc::now(),
+            display: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reload_applies_new_rules_without_losing_session() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let mut daemon = Daemon::new(db, 10).unwrap();
+        let session_id = daemon.session_manager.create_session().unwrap();
+        daemon.current_session_id = Some(session_id);
+
+        let app = editor_activity();
+        let (category, confidence) = daemon.classify_activity(&app.app_id, None, None).await;
+        daemon
+            .start_new_span(&app, category.clone(), confidence, None, None)
+            .unwrap();
+        let span_id = daemon.current_activity_span.as_ref().unwrap().id;
+        assert_ne!(category, "Writing");
+
+        daemon
+            .database
+            .save_classification_rule(&ClassificationRule::from_correction(
+                app.app_id.clone(),
+                PatternType::BundleId,
+                "Writing".to_string(),
+            ))
+            .unwrap();
+        // Rules are cached until the daemon reloads
+        let (category, _) = daemon.classify_activity(&app.app_id, None, None).await;
+        assert_ne!(category, "Writing");
+
+        daemon.reload().unwrap();
+
+        let (category, confidence) = daemon.classify_activity(&app.app_id, None, None).await;
+        assert_eq!(category, "Writing");
+        assert!(confidence > 0.9);
+        assert_eq!(daemon.current_session_id, Some(session_id));
+        assert_eq!(
+            daemon.current_activity_span.as_ref().map(|span| span.id),
+            Some(span_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_idle_gap_is_not_counted_in_span() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let mut daemon = Daemon::new(db, 10).unwrap();
+        let settings = daemon.database.get_settings().unwrap();
+        let idle_seconds = settings.idle_threshold_seconds + 60;
+
+        // Ten minutes of work, then away for longer than the idle threshold
+        let started_at =
+            chrono::Utc::now() - chrono::Duration::seconds(600 + i64::from(idle_seconds));
+        let span = ActivitySpan::new(
+            editor_activity().app_id,
+            "Coding".to_string(),
+            started_at,
+            None,
+            None,
+            None,
+        );
+        daemon.database.create_activity_span(&span).unwrap();
+        let span_id = span.id;
+        daemon.current_activity_span = Some(span);
+
+        daemon.monitor = Box::new(IdleMonitor { idle_seconds });
+        daemon.tick().await.unwrap();
+        assert!(daemon.current_activity_span.is_none());
+        let span = daemon.database.get_activity_span(span_id).unwrap().unwrap();
+        assert!(
+            (600..=602).contains(&span.duration_seconds),
+            "span counted {}s",
+            span.duration_seconds
+        );
+
+        // Back at the keyboard: a new span starts
+        daemon.monitor = Box::new(IdleMonitor { idle_seconds: 0 });
+        daemon.tick().await.unwrap();
+        let resumed = daemon.current_activity_span.as_ref().map(|span| span.id);
+        assert!(resumed.is_some_and(|id| id != span_id));
+    }
+
+    #[tokio::test]
+    async fn test_extension_tab_recorded_for_monitor_browser_id() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let mut daemon = Daemon::new(db, 10).unwrap();
+        let browser = AppActivity {
+            app_id: "org.mozilla.firefox".to_string(),
+            app_name: "Firefox".to_string(),
+            ..editor_activity()
+        };
+        daemon
+            .start_new_span(&browser, "Browsing".to_string(), 0.5, None, None)
+            .unwrap();
+        let span_id = daemon.current_activity_span.as_ref().unwrap().id;
+
+        // The extension reports without an app id, i.e. as `firefox`
+        let tab: BrowserTab =
+            serde_json::from_str(r#"{"url": "https://github.com/acme/api/issues/42"}"#).unwrap();
+        daemon.ipc_handler.record_browser_tab(tab).await;
+        daemon
+            .record_browser_url(&browser.app_id, &["github.com".to_string()])
+            .await
+            .unwrap();
+
+        let span = daemon.database.get_activity_span(span_id).unwrap().unwrap();
+        assert_eq!(
+            span.context.unwrap().browser_urls,
+            vec!["https://github.com/acme/api/issues/42".to_string()]
+        );
+    }
+}
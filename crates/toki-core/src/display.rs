@@ -0,0 +1,188 @@
+use toki_storage::ActivitySpan;
+
+use crate::monitor::AppActivity;
+
+/// Check whether an activity sample should decide the current category
+///
+/// On multi-monitor setups the active window may sit on one display while the
+/// user reads on another. With `require_focused_display`, such samples are
+/// ignored. Samples without display information always count.
+#[must_use]
+pub fn counts_toward_category(activity: &AppActivity, require_focused_display: bool) -> bool {
+    !require_focused_display
+        || activity
+            .display
+            .is_none_or(|display| display.is_window_on_focused_display())
+}
+
+/// Record the display of the activity's window on the span, if known
+///
+/// Returns `true` if the span was tagged.
+pub fn tag_span_display(span: &mut ActivitySpan, activity: &AppActivity) -> bool {
+    let Some(display) = activity.display else {
+        return false;
+    };
+    span.set_display(display.window_display);
+    true
+}
+
+/// A rectangle in global screen coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ScreenRect {
+    fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+
+    /// Parse the `x,y,width,height` bounds the window query reports
+    #[must_use]
+    pub fn parse(bounds: &str) -> Option<Self> {
+        let mut values = bounds.split(',').map(|v| v.trim().parse::<f64>().ok());
+        let rect = Self {
+            x: values.next()??,
+            y: values.next()??,
+            width: values.next()??,
+            height: values.next()??,
+        };
+        (values.next().is_none() && rect.width > 0.0 && rect.height > 0.0).then_some(rect)
+    }
+}
+
+/// Index of the screen that owns a window
+///
+/// `screens` are display frames with a bottom-left origin, the first being the
+/// primary display; `window` has a top-left origin, as accessibility APIs
+/// report it. The window belongs to the screen holding its center. Returns
+/// `None` when the center is off every screen.
+#[must_use]
+pub fn window_display(screens: &[ScreenRect], window: ScreenRect) -> Option<u32> {
+    let primary = screens.first()?;
+    let center_x = window.x + window.width / 2.0;
+    let center_y = primary.height - (window.y + window.height / 2.0);
+    screens
+        .iter()
+        .position(|screen| screen.contains(center_x, center_y))
+        .and_then(|index| u32::try_from(index).ok())
+}
+
+/// Index of the screen holding a point given with a bottom-left origin
+#[must_use]
+pub fn point_display(screens: &[ScreenRect], x: f64, y: f64) -> Option<u32> {
+    screens
+        .iter()
+        .position(|screen| screen.contains(x, y))
+        .and_then(|index| u32::try_from(index).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::DisplayInfo;
+    use chrono::Utc;
+
+    fn activity_on(display: Option<DisplayInfo>) -> AppActivity {
+        AppActivity {
+            app_id: "com.apple.Safari".to_string(),
+            app_name: "Safari".to_string(),
+            window_title: Some("Design doc".to_string()),
+            is_active: true,
+            timestamp: Utc::now(),
+            display,
+        }
+    }
+
+    #[test]
+    fn test_window_on_secondary_display_ignored_when_required() {
+        let secondary = activity_on(Some(DisplayInfo {
+            window_display: 1,
+            focused_display: 0,
+        }));
+
+        assert!(!counts_toward_category(&secondary, true));
+        assert!(counts_toward_category(&secondary, false));
+
+        let focused = activity_on(Some(DisplayInfo {
+            window_display: 1,
+            focused_display: 1,
+        }));
+        assert!(counts_toward_category(&focused, true));
+
+        // Platforms without display information keep the old behavior
+        assert!(counts_toward_category(&activity_on(None), true));
+    }
+
+    #[test]
+    fn test_window_display_recorded_in_span_context() {
+        let activity = activity_on(Some(DisplayInfo {
+            window_display: 2,
+            focused_display: 0,
+        }));
+        let mut span = ActivitySpan::new(
+            activity.app_id.clone(),
+            "Browsing".to_string(),
+            Utc::now(),
+            None,
+            None,
+            None,
+        );
+
+        assert!(tag_span_display(&mut span, &activity));
+        assert_eq!(span.context.as_ref().and_then(|c| c.display), Some(2));
+
+        let json = serde_json::to_string(&span.context).unwrap();
+        let restored: Option<toki_storage::ActivitySpanContext> =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.and_then(|c| c.display), Some(2));
+
+        let mut untagged = ActivitySpan::new(
+            "com.apple.Safari".to_string(),
+            "Browsing".to_string(),
+            Utc::now(),
+            None,
+            None,
+            None,
+        );
+        assert!(!tag_span_display(&mut untagged, &activity_on(None)));
+        assert!(untagged.context.is_none());
+    }
+
+    #[test]
+    fn test_window_display_follows_window_center() {
+        let rect = |x, y, width, height| ScreenRect {
+            x,
+            y,
+            width,
+            height,
+        };
+        // Primary 1440x900, a 1920x1080 display to its right, one above it
+        let screens = [
+            rect(0.0, 0.0, 1440.0, 900.0),
+            rect(1440.0, 0.0, 1920.0, 1080.0),
+            rect(0.0, 900.0, 1440.0, 900.0),
+        ];
+
+        let editor = ScreenRect::parse("1600, 100, 1200, 800").unwrap();
+        assert_eq!(window_display(&screens, editor), Some(1));
+        // Top-left coordinates grow downwards, so negative y is the display above
+        let chat = ScreenRect::parse("200,-700,800,500").unwrap();
+        assert_eq!(window_display(&screens, chat), Some(2));
+        assert_eq!(point_display(&screens, 100.0, 1000.0), Some(2));
+
+        // A single display keeps reporting index 0, and off-screen windows are unknown
+        let single = &screens[..1];
+        let window = rect(100.0, 100.0, 600.0, 400.0);
+        assert_eq!(window_display(single, window), Some(0));
+        assert_eq!(window_display(single, editor), None);
+        assert_eq!(window_display(&[], editor), None);
+
+        assert_eq!(ScreenRect::parse(""), None);
+        assert_eq!(ScreenRect::parse("1,2,0,4"), None);
+        assert_eq!(ScreenRect::parse("1,2,3,4,5"), None);
+    }
+}
@@ -0,0 +1,169 @@
+use toki_storage::ActivitySpan;
+
+/// App-based categories too coarse to say what kind of work was done
+const REFINABLE_CATEGORIES: &[&str] = &["Terminal", "Coding", "AI-CLI"];
+
+/// Share of classified files that must agree before a category is refined
+const MIN_AGREEMENT: f64 = 0.7;
+
+/// Classified files needed before the edited files are trusted over the app
+const MIN_CLASSIFIED_FILES: usize = 2;
+
+const DOC_EXTENSIONS: &[&str] = &["md", "mdx", "markdown", "rst", "adoc", "txt"];
+
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "py", "ts", "tsx", "js", "jsx", "mjs", "go", "java", "kt", "swift", "c", "cc", "cpp",
+    "h", "hpp", "cs", "rb", "php", "scala", "ex", "exs", "dart", "vue", "svelte", "sh", "sql",
+];
+
+/// Category implied by a single file path, or `None` for config, lock files etc.
+fn category_for_file(path: &str) -> Option<&'static str> {
+    let path = path.replace('\\', "/").to_lowercase();
+    let (dir, name) = path.rsplit_once('/').unwrap_or(("", &path));
+    let (stem, extension) = name.rsplit_once('.')?;
+
+    let in_test_dir = dir
+        .split('/')
+        .any(|part| matches!(part, "test" | "tests" | "__tests__" | "spec"));
+    // e.g. test_api.py, session_test.go, app.test.ts, user.spec.js
+    let test_name = stem.starts_with("test_")
+        || stem.ends_with("_test")
+        || stem.ends_with("_spec")
+        || stem
+            .rsplit_once('.')
+            .is_some_and(|(_, suffix)| matches!(suffix, "test" | "spec"));
+
+    if SOURCE_EXTENSIONS.contains(&extension) {
+        Some(if in_test_dir || test_name {
+            "Testing"
+        } else {
+            "Coding"
+        })
+    } else if DOC_EXTENSIONS.contains(&extension) || dir.split('/').any(|part| part == "docs") {
+        Some("Documentation")
+    } else {
+        None
+    }
+}
+
+/// Infer the kind of work from edited files
+///
+/// Returns the category only when enough files agree on it.
+#[must_use]
+pub fn infer_category_from_files(files: &[String]) -> Option<&'static str> {
+    let categories: Vec<&str> = files.iter().filter_map(|f| category_for_file(f)).collect();
+    if categories.len() < MIN_CLASSIFIED_FILES {
+        return None;
+    }
+
+    let (best, count) = ["Testing", "Documentation", "Coding"]
+        .into_iter()
+        .map(|c| (c, categories.iter().filter(|&&x| x == c).count()))
+        .max_by_key(|(_, count)| *count)?;
+
+    #[allow(clippy::cast_precision_loss)]
+    let agreement = count as f64 / categories.len() as f64;
+    (agreement >= MIN_AGREEMENT).then_some(best)
+}
+
+/// Refine a terminal/IDE span's category from its edited files
+///
+/// The app stays untouched, so the span still shows where the work happened.
+/// Returns `true` if the category changed.
+pub fn refine_span_category(span: &mut ActivitySpan) -> bool {
+    if !REFINABLE_CATEGORIES.contains(&span.category.as_str()) {
+        return false;
+    }
+    let Some(category) = span
+        .context
+        .as_ref()
+        .and_then(|ctx| infer_category_from_files(&ctx.edited_files))
+    else {
+        return false;
+    };
+
+    if span.category == category {
+        return false;
+    }
+    span.category = category.to_string();
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn span_editing(app_id: &str, category: &str, files: &[&str]) -> ActivitySpan {
+        let mut span = ActivitySpan::new(
+            app_id.to_string(),
+            category.to_string(),
+            Utc::now(),
+            None,
+            None,
+            None,
+        );
+        for file in files {
+            span.add_edited_file((*file).to_string());
+        }
+        span
+    }
+
+    #[test]
+    fn test_terminal_span_editing_markdown_becomes_documentation() {
+        let mut span = span_editing(
+            "com.apple.Terminal",
+            "Terminal",
+            &["README.md", "docs/setup.md", "CHANGELOG.md"],
+        );
+
+        assert!(refine_span_category(&mut span));
+        assert_eq!(span.category, "Documentation");
+        assert_eq!(span.app_bundle_id, "com.apple.Terminal");
+    }
+
+    #[test]
+    fn test_test_files_refine_to_testing() {
+        let mut span = span_editing(
+            "com.googlecode.iterm2",
+            "Terminal",
+            &["src/session_test.rs", "tests/daemon.rs", "src/app.test.ts"],
+        );
+
+        assert!(refine_span_category(&mut span));
+        assert_eq!(span.category, "Testing");
+    }
+
+    #[test]
+    fn test_mixed_or_sparse_files_keep_category() {
+        let mut mixed = span_editing(
+            "com.apple.Terminal",
+            "Terminal",
+            &["README.md", "src/main.rs", "src/lib.rs", "docs/api.md"],
+        );
+        assert!(!refine_span_category(&mut mixed));
+        assert_eq!(mixed.category, "Terminal");
+
+        let mut single = span_editing("com.apple.Terminal", "Terminal", &["notes.md"]);
+        assert!(!refine_span_category(&mut single));
+
+        // Lock and config files say nothing about the kind of work
+        let mut config = span_editing(
+            "com.apple.Terminal",
+            "Terminal",
+            &["Cargo.lock", "config.toml", "README.md"],
+        );
+        assert!(!refine_span_category(&mut config));
+    }
+
+    #[test]
+    fn test_non_terminal_categories_not_refined() {
+        let mut span = span_editing(
+            "com.google.Chrome",
+            "Browser",
+            &["README.md", "docs/setup.md"],
+        );
+        assert!(!refine_span_category(&mut span));
+        assert_eq!(span.category, "Browser");
+    }
+}
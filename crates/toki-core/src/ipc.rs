@@ -1,14 +1,18 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     fs, io,
     path::{Path, PathBuf},
     sync::{atomic::Ordering, Arc},
 };
+use toki_storage::ProjectMatch;
+
+use crate::native_messaging::{browser_key, BrowserTab};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
     net::{UnixListener, UnixStream},
-    sync::Mutex,
+    sync::{mpsc, oneshot, Mutex},
 };
 
 /// IPC request from CLI to daemon
@@ -16,6 +20,13 @@ use tokio::{
 pub enum IpcRequest {
     Status,
     Shutdown,
+    Reload,
+    /// Find projects whose name matches `query`, best first
+    ResolveProject {
+        query: String,
+    },
+    /// Active browser tab, from the native messaging host
+    BrowserTab(BrowserTab),
 }
 
 /// IPC response from daemon to CLI
@@ -28,8 +39,28 @@ pub enum IpcResponse {
         session_duration: u64,
     },
     Shutdown,
+    Reloaded,
+    ReloadFailed {
+        error: String,
+    },
+    ProjectMatches {
+        matches: Vec<ProjectMatch>,
+    },
+    ResolveFailed {
+        error: String,
+    },
+    TabRecorded,
 }
 
+/// Channel on which the daemon reports the outcome of a reload
+pub type ReloadReply = oneshot::Sender<Result<(), String>>;
+
+/// Project search query, with the channel on which the daemon answers it
+pub type ProjectQuery = (String, oneshot::Sender<Result<Vec<ProjectMatch>, String>>);
+
+/// Largest request the daemon reads from a connection
+const MAX_REQUEST_BYTES: u64 = 64 * 1024;
+
 #[derive(Debug)]
 pub struct IpcClient {
     sock_path: PathBuf,
@@ -68,15 +99,26 @@ pub struct DaemonIpcHandler {
     current_issue: Arc<Mutex<Option<String>>>,
     session_start: Arc<Mutex<chrono::DateTime<chrono::Utc>>>,
     shutdown_signal: Arc<std::sync::atomic::AtomicBool>,
+    reload_requests: mpsc::UnboundedSender<ReloadReply>,
+    project_queries: mpsc::UnboundedSender<ProjectQuery>,
+    /// Most recent tab per browser family (see [`browser_key`])
+    browser_tabs: Arc<Mutex<HashMap<String, BrowserTab>>>,
 }
 
 impl DaemonIpcHandler {
-    pub fn new(shutdown_signal: Arc<std::sync::atomic::AtomicBool>) -> Self {
+    pub fn new(
+        shutdown_signal: Arc<std::sync::atomic::AtomicBool>,
+        reload_requests: mpsc::UnboundedSender<ReloadReply>,
+        project_queries: mpsc::UnboundedSender<ProjectQuery>,
+    ) -> Self {
         Self {
             current_window: Arc::new(Mutex::new(None)),
             current_issue: Arc::new(Mutex::new(None)),
             session_start: Arc::new(Mutex::new(chrono::Utc::now())),
             shutdown_signal,
+            reload_requests,
+            project_queries,
+            browser_tabs: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -95,6 +137,54 @@ impl DaemonIpcHandler {
         *lock = chrono::Utc::now();
     }
 
+    /// Keep a tab reported by the browser extension until the daemon takes it
+    pub async fn record_browser_tab(&self, tab: BrowserTab) {
+        self.browser_tabs
+            .lock()
+            .await
+            .insert(browser_key(&tab.app_id), tab);
+    }
+
+    /// Take the tab last reported for the browser `app_id` belongs to, if one
+    /// arrived since the last take
+    pub async fn take_browser_tab(&self, app_id: &str) -> Option<BrowserTab> {
+        self.browser_tabs.lock().await.remove(&browser_key(app_id))
+    }
+
+    /// Ask the daemon loop to reload and wait for it to finish
+    async fn request_reload(&self) -> IpcResponse {
+        let (reply, outcome) = oneshot::channel();
+        if self.reload_requests.send(reply).is_err() {
+            return IpcResponse::ReloadFailed {
+                error: "daemon is shutting down".to_string(),
+            };
+        }
+        match outcome.await {
+            Ok(Ok(())) => IpcResponse::Reloaded,
+            Ok(Err(error)) => IpcResponse::ReloadFailed { error },
+            Err(_) => IpcResponse::ReloadFailed {
+                error: "daemon stopped before reloading".to_string(),
+            },
+        }
+    }
+
+    /// Ask the daemon loop, which owns the database, to search projects
+    async fn resolve_project(&self, query: String) -> IpcResponse {
+        let (reply, outcome) = oneshot::channel();
+        if self.project_queries.send((query, reply)).is_err() {
+            return IpcResponse::ResolveFailed {
+                error: "daemon is shutting down".to_string(),
+            };
+        }
+        match outcome.await {
+            Ok(Ok(matches)) => IpcResponse::ProjectMatches { matches },
+            Ok(Err(error)) => IpcResponse::ResolveFailed { error },
+            Err(_) => IpcResponse::ResolveFailed {
+                error: "daemon stopped before searching projects".to_string(),
+            },
+        }
+    }
+
     /// Handles an incoming IPC request.
     ///
     /// # Errors
@@ -123,6 +213,12 @@ impl DaemonIpcHandler {
                 self.shutdown_signal.store(true, Ordering::SeqCst);
                 IpcResponse::Shutdown
             }
+            IpcRequest::Reload => self.request_reload().await,
+            IpcRequest::ResolveProject { query } => self.resolve_project(query).await,
+            IpcRequest::BrowserTab(tab) => {
+                self.record_browser_tab(tab).await;
+                IpcResponse::TabRecorded
+            }
         };
 
         let encoded = bincode::serialize(&response)?;
@@ -147,9 +243,14 @@ pub async fn listen(handler: Arc<DaemonIpcHandler>, sock_path: &Path) -> io::Res
             Ok((mut stream, _)) => {
                 let handler = handler.clone();
                 tokio::spawn(async move {
-                    let mut buf = vec![0; 1024];
-                    match stream.read(&mut buf).await {
-                        Ok(n) if n > 0 => match bincode::deserialize::<IpcRequest>(&buf[..n]) {
+                    // Clients shut down their write side once the request is sent
+                    let mut buf = Vec::new();
+                    match (&mut stream)
+                        .take(MAX_REQUEST_BYTES)
+                        .read_to_end(&mut buf)
+                        .await
+                    {
+                        Ok(n) if n > 0 => match bincode::deserialize::<IpcRequest>(&buf) {
                             Ok(request) => {
                                 if let Err(e) = handler.handle(&mut stream, request).await {
                                     log::error!("IPC handle error: {e}");
@@ -1,14 +1,28 @@
 pub mod ai_classifier;
+pub mod app_names;
+pub mod billable;
 pub mod classifier;
 pub mod config;
 pub mod context_collector;
 pub mod daemon;
 pub mod daemon_control;
+pub mod display;
+pub mod file_category;
 pub mod ipc;
+pub mod meeting_attribution;
 pub mod monitor;
+pub mod native_messaging;
+pub mod notifier;
 pub mod privacy;
+pub mod remote;
+pub mod self_usage;
 pub mod session_manager;
+pub mod shell_history;
+pub mod work_hours;
 
+pub use app_names::AppDisplayNames;
+pub use billable::{BillableCategories, BillableTotals};
 pub use context_collector::{ContextCollector, ContextSignal, SignalSummary, SignalType};
 pub use daemon::Daemon;
 pub use session_manager::BreakState;
+pub use work_hours::WorkSchedule;
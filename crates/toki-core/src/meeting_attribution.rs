@@ -0,0 +1,195 @@
+//! Attribution of tracked activity that overlaps calendar meetings
+//!
+//! `calendar.overlap_policy` decides whether time spent working during a
+//! scheduled meeting stays with the detected activity, goes to the meeting,
+//! or is shared between the two.
+
+use chrono::{DateTime, Duration, Utc};
+use toki_storage::{ActivitySpan, CalendarOverlapPolicy};
+
+/// Category given to time attributed to a meeting
+pub const MEETING_CATEGORY: &str = "Meeting";
+
+/// A scheduled meeting from a calendar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MeetingEvent {
+    pub title: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+}
+
+/// A stretch of a span's time and what it is attributed to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributedTime {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub category: String,
+    /// Title of the meeting the time went to, if any
+    pub meeting: Option<String>,
+}
+
+impl AttributedTime {
+    #[must_use]
+    pub fn duration_seconds(&self) -> i64 {
+        (self.end_time - self.start_time).num_seconds()
+    }
+}
+
+/// Divide a span's time between its own category and overlapping meetings
+///
+/// The returned stretches are ordered and together cover the whole span.
+#[must_use]
+pub fn attribute_span(
+    span: &ActivitySpan,
+    meetings: &[MeetingEvent],
+    policy: CalendarOverlapPolicy,
+) -> Vec<AttributedTime> {
+    let span_end = span
+        .end_time
+        .unwrap_or(span.start_time + Duration::seconds(i64::from(span.duration_seconds)));
+    let mut attributed = Vec::new();
+    if policy == CalendarOverlapPolicy::PreferActivity {
+        push_time(
+            &mut attributed,
+            span.start_time,
+            span_end,
+            &span.category,
+            None,
+        );
+        return attributed;
+    }
+
+    let mut meetings: Vec<&MeetingEvent> = meetings.iter().collect();
+    meetings.sort_by_key(|m| m.start_time);
+
+    let mut cursor = span.start_time;
+    for meeting in meetings {
+        // Meetings that overlap each other share time only once
+        let start = meeting.start_time.max(cursor);
+        let end = meeting.end_time.min(span_end);
+        if end <= start {
+            continue;
+        }
+
+        push_time(&mut attributed, cursor, start, &span.category, None);
+        let meeting_start = if policy == CalendarOverlapPolicy::Split {
+            let mid = start + (end - start) / 2;
+            push_time(&mut attributed, start, mid, &span.category, None);
+            mid
+        } else {
+            start
+        };
+        push_time(
+            &mut attributed,
+            meeting_start,
+            end,
+            MEETING_CATEGORY,
+            Some(&meeting.title),
+        );
+        cursor = end;
+    }
+    push_time(&mut attributed, cursor, span_end, &span.category, None);
+
+    attributed
+}
+
+/// Append a stretch, extending the previous one if it continues it
+fn push_time(
+    attributed: &mut Vec<AttributedTime>,
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    category: &str,
+    meeting: Option<&str>,
+) {
+    if end_time <= start_time {
+        return;
+    }
+    if let Some(last) = attributed.last_mut() {
+        if last.end_time == start_time
+            && last.category == category
+            && last.meeting.as_deref() == meeting
+        {
+            last.end_time = end_time;
+            return;
+        }
+    }
+    attributed.push(AttributedTime {
+        start_time,
+        end_time,
+        category: category.to_string(),
+        meeting: meeting.map(String::from),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// Coding 10:00-11:00 overlapping a 10:30-11:30 meeting
+    fn coding_during_meeting() -> (ActivitySpan, Vec<MeetingEvent>) {
+        let start = Utc.with_ymd_and_hms(2025, 3, 14, 10, 0, 0).unwrap();
+        let mut span = ActivitySpan::new(
+            "com.microsoft.VSCode".to_string(),
+            "Coding".to_string(),
+            start,
+            None,
+            None,
+            None,
+        );
+        span.end_time = Some(start + Duration::hours(1));
+        span.duration_seconds = 3600;
+
+        let meeting = MeetingEvent {
+            title: "Sprint planning".to_string(),
+            start_time: start + Duration::minutes(30),
+            end_time: start + Duration::minutes(90),
+        };
+        (span, vec![meeting])
+    }
+
+    fn summary(attributed: &[AttributedTime]) -> Vec<(&str, i64)> {
+        attributed
+            .iter()
+            .map(|t| (t.category.as_str(), t.duration_seconds()))
+            .collect()
+    }
+
+    #[test]
+    fn test_prefer_activity_keeps_detected_activity() {
+        let (span, meetings) = coding_during_meeting();
+        let attributed = attribute_span(&span, &meetings, CalendarOverlapPolicy::PreferActivity);
+
+        assert_eq!(summary(&attributed), vec![("Coding", 3600)]);
+        assert_eq!(attributed[0].meeting, None);
+    }
+
+    #[test]
+    fn test_prefer_calendar_tags_overlap_as_meeting() {
+        let (span, meetings) = coding_during_meeting();
+        let attributed = attribute_span(&span, &meetings, CalendarOverlapPolicy::PreferCalendar);
+
+        assert_eq!(
+            summary(&attributed),
+            vec![("Coding", 1800), (MEETING_CATEGORY, 1800)]
+        );
+        assert_eq!(attributed[1].meeting.as_deref(), Some("Sprint planning"));
+        assert_eq!(attributed[1].end_time, span.end_time.unwrap());
+    }
+
+    #[test]
+    fn test_split_shares_overlap_evenly() {
+        let (span, meetings) = coding_during_meeting();
+        let attributed = attribute_span(&span, &meetings, CalendarOverlapPolicy::Split);
+
+        assert_eq!(
+            summary(&attributed),
+            vec![("Coding", 2700), (MEETING_CATEGORY, 900)]
+        );
+        let total: i64 = attributed
+            .iter()
+            .map(AttributedTime::duration_seconds)
+            .sum();
+        assert_eq!(total, 3600);
+    }
+}
@@ -2,10 +2,11 @@ use anyhow::Result;
 use async_trait::async_trait;
 use chrono::Utc;
 use cocoa::base::{id, nil};
-use cocoa::foundation::NSAutoreleasePool;
+use cocoa::foundation::{NSAutoreleasePool, NSPoint, NSRect};
 use objc::{class, msg_send, sel, sel_impl};
 
-use super::{AppActivity, SystemMonitor};
+use super::{AppActivity, DisplayInfo, SystemMonitor};
+use crate::display::{self, ScreenRect};
 
 use tokio::process::Command;
 
@@ -47,6 +48,45 @@ impl MacOSMonitor {
         }
     }
 
+    /// Displays of the focused window and the pointer, as indexes into `NSScreen.screens`
+    ///
+    /// `window` is the focused window's frame from the accessibility API. Without
+    /// it, `NSScreen.mainScreen` is used, which only follows toki's own key window
+    /// and so can miss the display the user is typing on.
+    fn get_display_info(window: Option<ScreenRect>) -> Option<DisplayInfo> {
+        unsafe {
+            let _pool = NSAutoreleasePool::new(nil);
+
+            let screens: id = msg_send![class!(NSScreen), screens];
+            let count: usize = msg_send![screens, count];
+            let main_screen: id = msg_send![class!(NSScreen), mainScreen];
+            let pointer: NSPoint = msg_send![class!(NSEvent), mouseLocation];
+
+            let mut frames = Vec::with_capacity(count);
+            let mut main_display = None;
+            for index in 0..count {
+                let screen: id = msg_send![screens, objectAtIndex: index];
+                let frame: NSRect = msg_send![screen, frame];
+                if screen == main_screen {
+                    main_display = u32::try_from(index).ok();
+                }
+                frames.push(ScreenRect {
+                    x: frame.origin.x,
+                    y: frame.origin.y,
+                    width: frame.size.width,
+                    height: frame.size.height,
+                });
+            }
+
+            Some(DisplayInfo {
+                window_display: window
+                    .and_then(|window| display::window_display(&frames, window))
+                    .or(main_display)?,
+                focused_display: display::point_display(&frames, pointer.x, pointer.y)?,
+            })
+        }
+    }
+
     fn get_frontmost_app() -> Option<AppActivity> {
         unsafe {
             let _pool = NSAutoreleasePool::new(nil);
@@ -87,6 +127,7 @@ impl MacOSMonitor {
                 window_title: None,
                 is_active: true,
                 timestamp: Utc::now(),
+                display: Self::get_display_info(None),
             })
         }
     }
@@ -100,19 +141,27 @@ impl SystemMonitor for MacOSMonitor {
     }
 
     async fn get_active_app(&self) -> Result<Option<AppActivity>> {
-        // Use AppleScript to get BOTH bundle ID and window title consistently
-        // This ensures they refer to the same frontmost app
+        // Use AppleScript to get the bundle ID, focused window and its frame consistently
+        // This ensures they refer to the same frontmost app and the window with keyboard focus
         let script = r#"
             tell application "System Events"
                 set frontProc to first application process whose frontmost is true
                 set bundleId to bundle identifier of frontProc
                 set appName to name of frontProc
+                set winTitle to ""
+                set winBounds to ""
                 try
-                    set winTitle to name of first window of frontProc
+                    set focusedWin to value of attribute "AXFocusedWindow" of frontProc
+                    set winTitle to name of focusedWin
+                    set {winX, winY} to position of focusedWin
+                    set {winW, winH} to size of focusedWin
+                    set winBounds to (winX as text) & "," & (winY as text) & "," & (winW as text) & "," & (winH as text)
                 on error
-                    set winTitle to ""
+                    try
+                        set winTitle to name of first window of frontProc
+                    end try
                 end try
-                return bundleId & "|" & appName & "|" & winTitle
+                return bundleId & "|" & appName & "|" & winBounds & "|" & winTitle
             end tell
         "#;
 
@@ -125,16 +174,17 @@ impl SystemMonitor for MacOSMonitor {
         if let Ok(output) = output {
             if output.status.success() {
                 let result = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                let parts: Vec<&str> = result.splitn(3, '|').collect();
+                // The title goes last since it may itself contain '|'
+                let parts: Vec<&str> = result.splitn(4, '|').collect();
 
                 if parts.len() >= 2 {
                     let bundle_id = parts[0].to_string();
                     let app_name = parts[1].to_string();
-                    let window_title = if parts.len() > 2 && !parts[2].is_empty() {
-                        Some(parts[2].to_string())
-                    } else {
-                        None
-                    };
+                    let window = parts.get(2).copied().and_then(ScreenRect::parse);
+                    let window_title = parts
+                        .get(3)
+                        .filter(|title| !title.is_empty())
+                        .map(|title| (*title).to_string());
 
                     return Ok(Some(AppActivity {
                         app_id: bundle_id,
@@ -142,6 +192,7 @@ impl SystemMonitor for MacOSMonitor {
                         window_title,
                         is_active: true,
                         timestamp: Utc::now(),
+                        display: Self::get_display_info(window),
                     }));
                 }
             }
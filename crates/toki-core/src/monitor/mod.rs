@@ -18,6 +18,25 @@ pub struct AppActivity {
     pub window_title: Option<String>,
     pub is_active: bool,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Displays involved, on platforms that report them
+    pub display: Option<DisplayInfo>,
+}
+
+/// Which display the active window is on, and which one the user is looking at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayInfo {
+    /// Index of the display showing the active window
+    pub window_display: u32,
+    /// Index of the display holding the pointer
+    pub focused_display: u32,
+}
+
+impl DisplayInfo {
+    /// Whether the active window is on the display the user is focused on
+    #[must_use]
+    pub fn is_window_on_focused_display(&self) -> bool {
+        self.window_display == self.focused_display
+    }
 }
 
 /// System monitor trait for platform-specific implementations
@@ -37,6 +37,7 @@ impl SystemMonitor for WindowsMonitor {
             window_title: None,
             is_active: true,
             timestamp: Utc::now(),
+            display: None,
         }))
     }
 
@@ -0,0 +1,219 @@
+//! Browser native messaging
+//!
+//! Window titles rarely show which page a browser is on, so a browser
+//! extension reports the active tab to `toki browser-host`, which the browser
+//! starts as a native messaging host. The host forwards each tab to the
+//! daemon, which records whitelisted URLs as context for the current span.
+//!
+//! Messages in both directions are a 32-bit length in native byte order
+//! followed by that many bytes of UTF-8 JSON. The extension sends one message
+//! whenever the active tab changes:
+//!
+//! ```json
+//! {"url": "https://github.com/acme/api/issues/42", "title": "Fix login #42"}
+//! ```
+//!
+//! `title` is optional. `app_id` is optional too, `firefox` when left out. It
+//! only has to name the browser: tabs are matched to the focused window by
+//! browser family, so `firefox` matches the monitor's `org.mozilla.firefox`.
+//! The host answers every message with `{"ok": true}` or
+//! `{"ok": false, "error": "..."}`.
+//!
+//! For Firefox, the host manifest goes in
+//! `~/.mozilla/native-messaging-hosts/toki.json`, pointing at a script that
+//! runs `exec toki browser-host "$@"`:
+//!
+//! ```json
+//! {
+//!   "name": "toki",
+//!   "description": "toki time tracker",
+//!   "path": "/usr/local/bin/toki-browser-host",
+//!   "type": "stdio",
+//!   "allowed_extensions": ["toki@rikaidev.com"]
+//! }
+//! ```
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read, Write};
+
+/// Largest message accepted from the browser
+///
+/// A tab report is a URL and a title, far below this.
+pub const MAX_MESSAGE_BYTES: usize = 64 * 1024;
+
+/// App id of the browser when the extension does not send one
+pub const DEFAULT_BROWSER_APP_ID: &str = "firefox";
+
+/// Active tab reported by the browser extension
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BrowserTab {
+    pub url: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default = "default_app_id")]
+    pub app_id: String,
+}
+
+fn default_app_id() -> String {
+    DEFAULT_BROWSER_APP_ID.to_string()
+}
+
+/// App id fragments (lowercase) and the browser family they belong to
+const BROWSER_FAMILIES: &[(&str, &str)] = &[
+    ("firefox", "firefox"),
+    ("chromium", "chrome"),
+    ("chrome", "chrome"),
+    ("msedge", "edge"),
+    ("microsoft.edge", "edge"),
+    ("brave", "brave"),
+    ("vivaldi", "vivaldi"),
+    ("opera", "opera"),
+    ("safari", "safari"),
+];
+
+/// Key a browser app id by its family
+///
+/// The extension and the activity monitor name the same browser differently
+/// (`firefox` vs `org.mozilla.firefox`); both map to `firefox`. Unknown ids
+/// are only lowercased.
+#[must_use]
+pub fn browser_key(app_id: &str) -> String {
+    let app_id = app_id.to_lowercase();
+    BROWSER_FAMILIES
+        .iter()
+        .find(|(fragment, _)| app_id.contains(fragment))
+        .map_or(app_id, |(_, family)| (*family).to_string())
+}
+
+/// Read one length-prefixed JSON message
+///
+/// Returns `None` once the browser closes the pipe between messages.
+///
+/// # Errors
+///
+/// Returns an error if reading fails, the message is larger than
+/// [`MAX_MESSAGE_BYTES`], or it is not valid JSON for `T`
+pub fn read_message<T: for<'de> Deserialize<'de>>(reader: &mut impl Read) -> Result<Option<T>> {
+    let mut length = [0; 4];
+    match reader.read_exact(&mut length) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e).context("Failed to read message length"),
+    }
+
+    let length = usize::try_from(u32::from_ne_bytes(length))?;
+    if length > MAX_MESSAGE_BYTES {
+        bail!("Message of {length} bytes exceeds the {MAX_MESSAGE_BYTES} byte limit");
+    }
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .context("Failed to read message body")?;
+    Ok(Some(
+        serde_json::from_slice(&body).context("Failed to parse message")?,
+    ))
+}
+
+/// Write one length-prefixed JSON message and flush it
+///
+/// # Errors
+///
+/// Returns an error if serialization or writing fails
+pub fn write_message(writer: &mut impl Write, message: &impl Serialize) -> Result<()> {
+    let body = serde_json::to_vec(message)?;
+    writer.write_all(&u32::try_from(body.len())?.to_ne_bytes())?;
+    writer.write_all(&body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Check whether a URL's host is one of the allowed domains or a subdomain
+///
+/// `https://acme.atlassian.net/...` is allowed by `atlassian.net`, but
+/// `https://evil.com/?atlassian.net` is not.
+#[must_use]
+pub fn is_url_allowed(url: &str, allowed_domains: &[String]) -> bool {
+    let Some(host) = url_host(url) else {
+        return false;
+    };
+    allowed_domains.iter().any(|domain| {
+        let domain = domain.trim().trim_start_matches('.').to_lowercase();
+        !domain.is_empty()
+            && (host == domain
+                || host
+                    .strip_suffix(&domain)
+                    .is_some_and(|sub| sub.ends_with('.')))
+    })
+}
+
+/// Lowercased host of an absolute URL, without credentials or port
+fn url_host(url: &str) -> Option<String> {
+    let (_, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host_port = authority.rsplit('@').next()?;
+    let host = host_port.split(':').next()?;
+    (!host.is_empty()).then(|| host.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_message_round_trip_with_defaults() {
+        let mut buffer = Vec::new();
+        write_message(
+            &mut buffer,
+            &serde_json::json!({"url": "https://linear.app/acme"}),
+        )
+        .unwrap();
+        assert_eq!(
+            buffer[..4],
+            u32::try_from(buffer.len() - 4).unwrap().to_ne_bytes()
+        );
+
+        let mut reader = Cursor::new(buffer);
+        let tab: BrowserTab = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(tab.url, "https://linear.app/acme");
+        assert_eq!(tab.title, None);
+        assert_eq!(tab.app_id, DEFAULT_BROWSER_APP_ID);
+        assert!(read_message::<BrowserTab>(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_browser_key_matches_extension_and_monitor_ids() {
+        assert_eq!(browser_key(DEFAULT_BROWSER_APP_ID), "firefox");
+        assert_eq!(browser_key("org.mozilla.firefox"), "firefox");
+        assert_eq!(browser_key("com.google.Chrome"), "chrome");
+        assert_eq!(browser_key("chromium-browser"), "chrome");
+        assert_eq!(browser_key("com.example.Editor"), "com.example.editor");
+    }
+
+    #[test]
+    fn test_oversized_message_rejected() {
+        let length = u32::try_from(MAX_MESSAGE_BYTES + 1).unwrap();
+        let mut reader = Cursor::new(length.to_ne_bytes().to_vec());
+        assert!(read_message::<BrowserTab>(&mut reader).is_err());
+    }
+
+    #[test]
+    fn test_url_allowed_matches_host_and_subdomains_only() {
+        let allowed = vec!["atlassian.net".to_string(), "github.com".to_string()];
+        assert!(is_url_allowed(
+            "https://acme.atlassian.net/browse/PROJ-1",
+            &allowed
+        ));
+        assert!(is_url_allowed(
+            "https://GitHub.com/acme/api/issues/42",
+            &allowed
+        ));
+        assert!(is_url_allowed("http://user@github.com:443/acme", &allowed));
+
+        assert!(!is_url_allowed("https://evil.com/?q=github.com", &allowed));
+        assert!(!is_url_allowed("https://notgithub.com/acme", &allowed));
+        assert!(!is_url_allowed("github.com/acme", &allowed));
+        assert!(!is_url_allowed("https://github.com/acme", &[]));
+    }
+}
@@ -0,0 +1,461 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use serde::Serialize;
+use toki_storage::NotificationSettings;
+
+/// Hard cap on notifications across all kinds, regardless of cooldowns
+const MAX_NOTIFICATIONS_PER_HOUR: usize = 4;
+
+/// Daemon events worth interrupting the user for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationEvent {
+    /// Continuous focus without a break
+    LongFocus { minutes: u32 },
+    /// Idle inside an open session; the time is likely going untracked
+    UntrackedIdle { minutes: u32 },
+    /// Daily reminder to review and confirm time blocks
+    ReviewReminder,
+}
+
+impl NotificationEvent {
+    /// Stable identifier, used for rate limiting and in webhook payloads
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::LongFocus { .. } => "long_focus",
+            Self::UntrackedIdle { .. } => "untracked_idle",
+            Self::ReviewReminder => "review_reminder",
+        }
+    }
+
+    #[must_use]
+    pub const fn title(&self) -> &'static str {
+        match self {
+            Self::LongFocus { .. } => "Time for a break",
+            Self::UntrackedIdle { .. } => "Untracked time",
+            Self::ReviewReminder => "End-of-day review",
+        }
+    }
+
+    #[must_use]
+    pub fn message(&self) -> String {
+        match self {
+            Self::LongFocus { minutes } => format!(
+                "You've been focused for {}h {}m without a break.",
+                minutes / 60,
+                minutes % 60
+            ),
+            Self::UntrackedIdle { minutes } => {
+                format!("No activity for {minutes}m during your session. Were you in a meeting?")
+            }
+            Self::ReviewReminder => "Run 'toki review' to confirm today's time blocks.".to_string(),
+        }
+    }
+}
+
+/// A channel that delivers notifications to the user
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Deliver one notification
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the notification could not be delivered
+    async fn notify(&self, event: &NotificationEvent) -> Result<()>;
+}
+
+/// Native desktop notifications (`osascript` on macOS, `notify-send` on Linux)
+pub struct DesktopNotifier;
+
+#[async_trait]
+impl Notifier for DesktopNotifier {
+    async fn notify(&self, event: &NotificationEvent) -> Result<()> {
+        let title = event.title();
+        let message = event.message();
+
+        let mut command = if cfg!(target_os = "macos") {
+            let script = format!(
+                "display notification \"{}\" with title \"{}\"",
+                message.replace('"', "\\\""),
+                title.replace('"', "\\\"")
+            );
+            let mut cmd = tokio::process::Command::new("osascript");
+            cmd.arg("-e").arg(script);
+            cmd
+        } else if cfg!(target_os = "linux") {
+            let mut cmd = tokio::process::Command::new("notify-send");
+            cmd.arg(title).arg(message);
+            cmd
+        } else {
+            anyhow::bail!("Desktop notifications are not supported on this platform");
+        };
+
+        let status = command
+            .status()
+            .await
+            .context("Failed to run desktop notification command")?;
+        if !status.success() {
+            anyhow::bail!("Desktop notification command exited with {status}");
+        }
+        Ok(())
+    }
+}
+
+/// Posts notifications as JSON to a user-configured URL
+pub struct WebhookNotifier {
+    url: String,
+    client: reqwest::Client,
+}
+
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    event: &'a str,
+    title: &'a str,
+    message: String,
+    timestamp: String,
+}
+
+impl WebhookNotifier {
+    #[must_use]
+    pub fn new(url: String) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self { url, client }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify(&self, event: &NotificationEvent) -> Result<()> {
+        let payload = WebhookPayload {
+            event: event.kind(),
+            title: event.title(),
+            message: event.message(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&payload)
+            .send()
+            .await
+            .context("Failed to send notification webhook")?;
+        if !response.status().is_success() {
+            anyhow::bail!("Notification webhook returned {}", response.status());
+        }
+        Ok(())
+    }
+}
+
+/// Turns per-tick daemon state into notification events
+///
+/// Each event fires at most once per stretch: long focus re-arms after an idle
+/// break, untracked idle re-arms once the user is back, and the review reminder
+/// fires once per day.
+#[derive(Debug)]
+pub struct EventTriggers {
+    focus_threshold_seconds: u32,
+    idle_threshold_seconds: u32,
+    review_time: Option<NaiveTime>,
+    focus_seconds: u32,
+    idle_seconds: u32,
+    focus_fired: bool,
+    idle_fired: bool,
+    last_review: Option<NaiveDate>,
+}
+
+impl EventTriggers {
+    #[must_use]
+    pub fn from_settings(settings: &NotificationSettings) -> Self {
+        Self {
+            focus_threshold_seconds: settings.focus_hours.saturating_mul(3600),
+            idle_threshold_seconds: settings.idle_minutes.saturating_mul(60),
+            review_time: settings
+                .review_time
+                .as_deref()
+                .and_then(|t| NaiveTime::parse_from_str(t, "%H:%M").ok()),
+            focus_seconds: 0,
+            idle_seconds: 0,
+            focus_fired: false,
+            idle_fired: false,
+            last_review: None,
+        }
+    }
+
+    /// Record one daemon tick and return the events it triggers
+    ///
+    /// `now` is local wall-clock time, so the review reminder follows the user's day.
+    pub fn observe(
+        &mut self,
+        is_idle: bool,
+        in_session: bool,
+        tick_seconds: u32,
+        now: NaiveDateTime,
+    ) -> Vec<NotificationEvent> {
+        let mut events = Vec::new();
+
+        if is_idle {
+            self.focus_seconds = 0;
+            self.focus_fired = false;
+            if in_session {
+                self.idle_seconds += tick_seconds;
+            }
+            if self.idle_threshold_seconds > 0
+                && !self.idle_fired
+                && self.idle_seconds >= self.idle_threshold_seconds
+            {
+                self.idle_fired = true;
+                events.push(NotificationEvent::UntrackedIdle {
+                    minutes: self.idle_seconds / 60,
+                });
+            }
+            return events;
+        }
+
+        self.idle_seconds = 0;
+        self.idle_fired = false;
+        self.focus_seconds += tick_seconds;
+        if self.focus_threshold_seconds > 0
+            && !self.focus_fired
+            && self.focus_seconds >= self.focus_threshold_seconds
+        {
+            self.focus_fired = true;
+            events.push(NotificationEvent::LongFocus {
+                minutes: self.focus_seconds / 60,
+            });
+        }
+
+        if let Some(review_time) = self.review_time {
+            if now.time() >= review_time && self.last_review != Some(now.date()) {
+                self.last_review = Some(now.date());
+                events.push(NotificationEvent::ReviewReminder);
+            }
+        }
+
+        events
+    }
+}
+
+/// Limits how often notifications go out
+///
+/// Each event kind has its own cooldown, and all kinds together share an hourly cap.
+#[derive(Debug)]
+pub struct RateLimiter {
+    cooldown: TimeDelta,
+    max_per_hour: usize,
+    last_by_kind: HashMap<&'static str, NaiveDateTime>,
+    recent: VecDeque<NaiveDateTime>,
+}
+
+impl RateLimiter {
+    #[must_use]
+    pub fn new(cooldown: TimeDelta, max_per_hour: usize) -> Self {
+        Self {
+            cooldown,
+            max_per_hour,
+            last_by_kind: HashMap::new(),
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Check whether an event of this kind may be sent now, recording it if so
+    pub fn allow(&mut self, kind: &'static str, now: NaiveDateTime) -> bool {
+        let hour_ago = now - TimeDelta::hours(1);
+        while self.recent.front().is_some_and(|t| *t <= hour_ago) {
+            self.recent.pop_front();
+        }
+
+        if self.recent.len() >= self.max_per_hour {
+            return false;
+        }
+        if self
+            .last_by_kind
+            .get(kind)
+            .is_some_and(|last| now - *last < self.cooldown)
+        {
+            return false;
+        }
+
+        self.last_by_kind.insert(kind, now);
+        self.recent.push_back(now);
+        true
+    }
+}
+
+/// Detects daemon events and fans them out to the configured notifiers
+pub struct NotificationDispatcher {
+    settings: NotificationSettings,
+    notifiers: Vec<Box<dyn Notifier>>,
+    triggers: EventTriggers,
+    limiter: RateLimiter,
+}
+
+impl NotificationDispatcher {
+    /// Build a dispatcher from settings, or `None` if notifications are off
+    #[must_use]
+    pub fn from_settings(settings: &NotificationSettings) -> Option<Self> {
+        if !settings.enabled {
+            return None;
+        }
+
+        let mut notifiers: Vec<Box<dyn Notifier>> = Vec::new();
+        if settings.desktop {
+            notifiers.push(Box::new(DesktopNotifier));
+        }
+        if let Some(url) = settings.webhook_url.as_ref().filter(|u| !u.is_empty()) {
+            notifiers.push(Box::new(WebhookNotifier::new(url.clone())));
+        }
+        if notifiers.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            settings: settings.clone(),
+            notifiers,
+            triggers: EventTriggers::from_settings(settings),
+            limiter: RateLimiter::new(
+                TimeDelta::minutes(i64::from(settings.cooldown_minutes)),
+                MAX_NOTIFICATIONS_PER_HOUR,
+            ),
+        })
+    }
+
+    /// The settings this dispatcher was built from
+    #[must_use]
+    pub const fn settings(&self) -> &NotificationSettings {
+        &self.settings
+    }
+
+    /// Record one daemon tick and deliver any triggered, rate-allowed notifications
+    ///
+    /// Delivery failures are logged, never propagated: a broken notifier must not
+    /// stop tracking.
+    pub async fn observe(
+        &mut self,
+        is_idle: bool,
+        in_session: bool,
+        tick_seconds: u32,
+        now: NaiveDateTime,
+    ) {
+        for event in self
+            .triggers
+            .observe(is_idle, in_session, tick_seconds, now)
+        {
+            if !self.limiter.allow(event.kind(), now) {
+                log::debug!("Notification {} suppressed by rate limit", event.kind());
+                continue;
+            }
+            for notifier in &self.notifiers {
+                if let Err(e) = notifier.notify(&event).await {
+                    log::warn!("Failed to deliver {} notification: {e}", event.kind());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn triggers() -> EventTriggers {
+        EventTriggers::from_settings(&NotificationSettings {
+            enabled: true,
+            focus_hours: 1,
+            idle_minutes: 10,
+            review_time: None,
+            ..NotificationSettings::default()
+        })
+    }
+
+    #[test]
+    fn test_long_focus_fires_once_per_stretch() {
+        let mut triggers = triggers();
+        let mut fired = Vec::new();
+        for _ in 0..150 {
+            fired.extend(triggers.observe(false, true, 60, at(10, 0)));
+        }
+        assert_eq!(fired, vec![NotificationEvent::LongFocus { minutes: 60 }]);
+
+        // An idle break re-arms the trigger
+        triggers.observe(true, true, 600, at(12, 30));
+        let mut fired = Vec::new();
+        for _ in 0..60 {
+            fired.extend(triggers.observe(false, true, 60, at(13, 0)));
+        }
+        assert_eq!(fired.len(), 1);
+    }
+
+    #[test]
+    fn test_untracked_idle_only_inside_session() {
+        let mut triggers = triggers();
+        assert!(triggers.observe(true, false, 900, at(10, 0)).is_empty());
+
+        assert!(triggers.observe(true, true, 300, at(10, 5)).is_empty());
+        assert_eq!(
+            triggers.observe(true, true, 300, at(10, 10)),
+            vec![NotificationEvent::UntrackedIdle { minutes: 10 }]
+        );
+        assert!(triggers.observe(true, true, 300, at(10, 15)).is_empty());
+    }
+
+    #[test]
+    fn test_review_reminder_fires_once_per_day() {
+        let mut triggers = EventTriggers::from_settings(&NotificationSettings {
+            enabled: true,
+            focus_hours: 0,
+            idle_minutes: 0,
+            review_time: Some("17:30".to_string()),
+            ..NotificationSettings::default()
+        });
+        assert!(triggers.observe(false, true, 60, at(17, 0)).is_empty());
+        assert_eq!(
+            triggers.observe(false, true, 60, at(17, 31)),
+            vec![NotificationEvent::ReviewReminder]
+        );
+        assert!(triggers.observe(false, true, 60, at(18, 0)).is_empty());
+
+        let next_day = at(17, 45) + TimeDelta::days(1);
+        assert_eq!(
+            triggers.observe(false, true, 60, next_day),
+            vec![NotificationEvent::ReviewReminder]
+        );
+    }
+
+    #[test]
+    fn test_rate_limiter_applies_cooldown_per_kind() {
+        let mut limiter = RateLimiter::new(TimeDelta::minutes(30), 10);
+        assert!(limiter.allow("long_focus", at(10, 0)));
+        assert!(!limiter.allow("long_focus", at(10, 10)));
+        assert!(limiter.allow("untracked_idle", at(10, 10)));
+        assert!(limiter.allow("long_focus", at(10, 30)));
+    }
+
+    #[test]
+    fn test_rate_limiter_caps_notifications_per_hour() {
+        let mut limiter = RateLimiter::new(TimeDelta::zero(), 2);
+        let sent: Vec<bool> = [(10, 0), (10, 10), (10, 20), (10, 59), (11, 1)]
+            .iter()
+            .map(|(h, m)| limiter.allow("long_focus", at(*h, *m)))
+            .collect();
+        assert_eq!(sent, vec![true, true, false, false, true]);
+    }
+
+    #[test]
+    fn test_dispatcher_disabled_by_default() {
+        assert!(NotificationDispatcher::from_settings(&NotificationSettings::default()).is_none());
+    }
+}
@@ -0,0 +1,102 @@
+use toki_storage::ActivitySpan;
+
+/// Bundle ids / process names of known remote-desktop clients (lowercase)
+const REMOTE_DESKTOP_APPS: &[&str] = &[
+    "com.microsoft.rdc",
+    "mstsc",
+    "msrdc",
+    "com.realvnc.vncviewer",
+    "tigervnc",
+    "vncviewer",
+    "org.remmina.remmina",
+    "remmina",
+    "com.apple.screensharing",
+    "com.teamviewer.teamviewer",
+    "teamviewer",
+    "com.philandro.anydesk",
+    "anydesk",
+    "tv.parsec.www",
+    "parsecd",
+    "com.jumpdesktop",
+    "com.citrix.receiver",
+    "wfica32",
+    "com.vmware.horizon",
+    "vmware-view",
+    "com.nomachine.nxplayer",
+    "nxplayer",
+    "rustdesk",
+    "xfreerdp",
+];
+
+/// Window title fragments that identify a remote session hosted in another app (lowercase)
+const REMOTE_DESKTOP_TITLES: &[&str] = &["chrome remote desktop", "remote desktop connection"];
+
+/// Check whether the active app is a remote-desktop client
+///
+/// While a remote session is focused, the local monitor only sees the client,
+/// not the real work happening on the remote machine.
+#[must_use]
+pub fn is_remote_session(app_id: &str, window_title: Option<&str>) -> bool {
+    let app_id = app_id.to_lowercase();
+    if REMOTE_DESKTOP_APPS.iter().any(|app| app_id.contains(app)) {
+        return true;
+    }
+
+    window_title.is_some_and(|title| {
+        let title = title.to_lowercase();
+        REMOTE_DESKTOP_TITLES.iter().any(|t| title.contains(t))
+    })
+}
+
+/// Flag the span as remote work if its app is a remote-desktop client
+///
+/// Returns `true` if the span was flagged.
+pub fn tag_remote_span(span: &mut ActivitySpan, window_title: Option<&str>) -> bool {
+    let remote = is_remote_session(&span.app_bundle_id, window_title);
+    if remote {
+        span.mark_remote();
+    }
+    remote
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn span_for(app_id: &str) -> ActivitySpan {
+        ActivitySpan::new(
+            app_id.to_string(),
+            "Uncategorized".to_string(),
+            Utc::now(),
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_rdp_client_span_is_flagged_remote() {
+        let mut span = span_for("com.microsoft.rdc.macos");
+        assert!(tag_remote_span(&mut span, None));
+        assert!(span.is_remote());
+    }
+
+    #[test]
+    fn test_remote_session_detected_from_window_title() {
+        let mut span = span_for("com.google.Chrome");
+        assert!(tag_remote_span(
+            &mut span,
+            Some("Chrome Remote Desktop - dev-box")
+        ));
+        assert!(span.is_remote());
+    }
+
+    #[test]
+    fn test_local_app_is_not_flagged_remote() {
+        let mut span = span_for("com.microsoft.VSCode");
+        assert!(!tag_remote_span(&mut span, Some("main.rs - toki")));
+        assert!(!span.is_remote());
+        assert!(span.context.is_none());
+    }
+}
@@ -0,0 +1,101 @@
+use toki_storage::SelfTrackingPolicy;
+
+use crate::shell_history::is_terminal_app;
+
+/// Category recorded for time spent running toki itself
+pub const SELF_USE_CATEGORY: &str = "Toki-Admin";
+
+/// Shell prompt characters that can sit right before a command in a terminal title
+const PROMPT_CHARS: &[char] = &['$', '>', '#', '%', ':'];
+
+/// Check whether the foreground activity is toki itself
+///
+/// Matches a toki app/process (`toki`, `toki-mcp`, `com.rikaidev.toki`) or a
+/// terminal whose title shows a `toki <command>` invocation. A title that only
+/// mentions the repo (`daemon.rs - toki - Code`) does not count.
+#[must_use]
+pub fn is_toki_usage(app_id: &str, window_title: Option<&str>) -> bool {
+    let app_id = app_id.to_lowercase();
+    let app_name = app_id.rsplit(['.', '/', '\\']).next().unwrap_or_default();
+    if app_name == "toki" || app_name.starts_with("toki-") {
+        return true;
+    }
+
+    // Only a terminal's title shows the command running in it
+    is_terminal_app(&app_id)
+        && window_title.is_some_and(|title| {
+            let words: Vec<&str> = title
+                .split_whitespace()
+                .map(|w| w.trim_start_matches(PROMPT_CHARS))
+                .collect();
+            words.windows(2).any(|pair| {
+                pair[0].eq_ignore_ascii_case("toki")
+                    && pair[1].starts_with(|c: char| c.is_ascii_lowercase())
+            })
+        })
+}
+
+/// How this activity should be recorded under the self tracking policy
+///
+/// Anything that is not toki is always tracked normally.
+#[must_use]
+pub fn self_tracking_for(
+    policy: SelfTrackingPolicy,
+    app_id: &str,
+    window_title: Option<&str>,
+) -> SelfTrackingPolicy {
+    if is_toki_usage(app_id, window_title) {
+        policy
+    } else {
+        SelfTrackingPolicy::Track
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_toki_app_and_commands() {
+        assert!(is_toki_usage("toki", None));
+        assert!(is_toki_usage("com.rikaidev.toki", None));
+        assert!(is_toki_usage("/usr/local/bin/toki-mcp", None));
+        assert!(is_toki_usage(
+            "com.apple.Terminal",
+            Some("toki report today")
+        ));
+        assert!(is_toki_usage("kitty", Some("user@host:~$ toki review")));
+
+        assert!(!is_toki_usage("tokio-console", None));
+        assert!(!is_toki_usage(
+            "com.microsoft.VSCode",
+            Some("daemon.rs - toki - Code")
+        ));
+        assert!(!is_toki_usage("com.apple.Terminal", Some("~/src/toki")));
+        assert!(!is_toki_usage("com.apple.Terminal", None));
+        assert!(!is_toki_usage(
+            "com.google.Chrome",
+            Some("toki report docs - GitHub")
+        ));
+        assert!(!is_toki_usage(
+            "com.tinyspeck.slackmacapp",
+            Some("toki review")
+        ));
+    }
+
+    #[test]
+    fn test_policy_only_applies_to_toki_usage() {
+        let title = Some("toki report");
+        for policy in [
+            SelfTrackingPolicy::Track,
+            SelfTrackingPolicy::Categorize,
+            SelfTrackingPolicy::Exclude,
+        ] {
+            assert_eq!(self_tracking_for(policy, "kitty", title), policy);
+            assert_eq!(
+                self_tracking_for(policy, "kitty", Some("cargo test")),
+                SelfTrackingPolicy::Track
+            );
+        }
+    }
+}
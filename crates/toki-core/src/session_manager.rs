@@ -2,7 +2,7 @@ use anyhow::Result;
 use chrono::{DateTime, Timelike, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use toki_storage::Database;
+use toki_storage::{ActivitySpan, Database};
 use uuid::Uuid;
 
 /// Break state for smart idle detection
@@ -190,6 +190,60 @@ impl SessionManager {
         outside_work_hours || exceeded_idle_threshold
     }
 
+    /// Check whether a session has run past the maximum length
+    ///
+    /// A `max_secs` of 0 disables the limit.
+    #[must_use]
+    pub fn exceeds_max_length(
+        started_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+        max_secs: u32,
+    ) -> bool {
+        max_secs > 0 && (now - started_at).num_seconds() >= i64::from(max_secs)
+    }
+
+    /// Split an over-long session at `boundary`
+    ///
+    /// The session and its open span end at the boundary, and a new session starts
+    /// there with a continuation of the open span, so span coverage stays contiguous.
+    /// Returns the new session id and the continuation span, if a span was open.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database operation fails
+    pub fn split_session(
+        &self,
+        session_id: Uuid,
+        open_span: Option<&ActivitySpan>,
+        boundary: DateTime<Utc>,
+    ) -> Result<(Uuid, Option<ActivitySpan>)> {
+        if let Some(span) = open_span {
+            self.database.finalize_activity_span(span.id, boundary)?;
+        }
+        self.database.finalize_session(session_id, boundary)?;
+
+        let new_session_id = self.database.create_session(boundary)?;
+        let continuation = match open_span {
+            Some(span) => {
+                let mut next = ActivitySpan::new(
+                    span.app_bundle_id.clone(),
+                    span.category.clone(),
+                    boundary,
+                    span.project_id,
+                    span.work_item_id,
+                    Some(new_session_id),
+                );
+                next.context.clone_from(&span.context);
+                self.database.create_activity_span(&next)?;
+                Some(next)
+            }
+            None => None,
+        };
+
+        log::info!("Split session {session_id} at max length, continuing as {new_session_id}");
+        Ok((new_session_id, continuation))
+    }
+
     /// Create a new session
     ///
     /// # Errors
@@ -320,4 +374,52 @@ mod tests {
         let time = Utc.with_ymd_and_hms(2024, 1, 1, 19, 0, 0).unwrap();
         assert!(manager.should_end_session(0, time));
     }
+
+    #[test]
+    fn test_session_over_max_length_splits_with_contiguous_spans() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::new(Some(temp_dir.path().join("test.db"))).unwrap());
+        let manager = SessionManager::new(db.clone());
+
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+        let boundary = start + chrono::Duration::hours(12);
+        let just_before = boundary - chrono::Duration::seconds(1);
+        assert!(!SessionManager::exceeds_max_length(
+            start,
+            just_before,
+            43200
+        ));
+        assert!(SessionManager::exceeds_max_length(start, boundary, 43200));
+        assert!(!SessionManager::exceeds_max_length(start, boundary, 0));
+
+        let session_id = db.create_session(start).unwrap();
+        let span = ActivitySpan::new(
+            "org.videolan.vlc".to_string(),
+            "Media".to_string(),
+            start + chrono::Duration::hours(11),
+            None,
+            None,
+            Some(session_id),
+        );
+        db.create_activity_span(&span).unwrap();
+
+        let (new_session_id, continuation) = manager
+            .split_session(session_id, Some(&span), boundary)
+            .unwrap();
+        assert_ne!(new_session_id, session_id);
+
+        let old_spans = db.get_activity_spans_by_session(session_id).unwrap();
+        let new_spans = db.get_activity_spans_by_session(new_session_id).unwrap();
+        assert_eq!(old_spans.len(), 1);
+        assert_eq!(new_spans.len(), 1);
+        assert_eq!(old_spans[0].end_time, Some(boundary));
+        assert_eq!(old_spans[0].duration_seconds, 3600);
+        assert_eq!(new_spans[0].start_time, boundary);
+        assert_eq!(new_spans[0].app_bundle_id, "org.videolan.vlc");
+        assert_eq!(continuation.unwrap().id, new_spans[0].id);
+
+        let current = db.get_current_session().unwrap().unwrap();
+        assert_eq!(current.id, new_session_id);
+        assert_eq!(current.start_time, boundary);
+    }
 }
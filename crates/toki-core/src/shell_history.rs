@@ -0,0 +1,332 @@
+//! Shell history tailing
+//!
+//! Whether a terminal ran `cargo test` or `kubectl apply` says a lot about
+//! the work. When `capture_terminal_commands` is on, the daemon reads the
+//! commands appended to the shell's history file while a terminal is the
+//! focused app. Reading always starts at the end of the file, so history from
+//! before the terminal was focused, or from while tracking was paused, is
+//! never recorded. Secrets typed on the command line (tokens in environment
+//! assignments, `-p<password>`, `Authorization` headers) are redacted before a
+//! command is kept.
+//!
+//! zsh appends each command as it runs with `setopt INC_APPEND_HISTORY`; bash
+//! only writes history on exit unless `PROMPT_COMMAND='history -a'` is set.
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// App ids of terminal emulators, matched case-insensitively
+///
+/// The same apps as the default "Terminal" category.
+const TERMINAL_APPS: &[&str] = &[
+    "terminal",
+    "iterm",
+    "konsole",
+    "wezterm",
+    "alacritty",
+    "kitty",
+    "hyper",
+    "warp",
+    "ghostty",
+];
+
+/// Longest command kept; longer ones are cut
+pub const MAX_COMMAND_CHARS: usize = 200;
+
+/// Replacement for a secret found in a command
+const REDACTED: &str = "[REDACTED]";
+
+/// Variable and flag names whose values are secrets, matched case-insensitively
+const SECRET_NAMES: &[&str] = &["TOKEN", "KEY", "SECRET", "PASSWORD", "PASSWD"];
+
+/// Clients that take a password glued to `-p`, as in `mysql -uroot -phunter2`
+const PASSWORD_FLAG_CLIENTS: &[&str] = &["mysql", "mysqldump", "mysqladmin", "mariadb"];
+
+/// Authorization schemes that come before the credentials in a header value
+const AUTH_SCHEMES: &[&str] = &["basic", "bearer", "digest", "token"];
+
+/// Check whether an app is a terminal emulator
+#[must_use]
+pub fn is_terminal_app(app_id: &str) -> bool {
+    let app_id = app_id.to_lowercase();
+    TERMINAL_APPS
+        .iter()
+        .any(|terminal| app_id.contains(terminal))
+}
+
+/// History file of the user's shell: `$HISTFILE`, then zsh's, then bash's
+#[must_use]
+pub fn default_history_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("HISTFILE").filter(|path| !path.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    let home = dirs::home_dir()?;
+    [".zsh_history", ".bash_history"]
+        .iter()
+        .map(|name| home.join(name))
+        .find(|path| path.exists())
+}
+
+/// Commands appended to a shell history file since it was last read
+#[derive(Debug)]
+pub struct ShellHistory {
+    path: PathBuf,
+    /// Bytes already read, `None` until the first read
+    offset: Option<u64>,
+}
+
+impl ShellHistory {
+    #[must_use]
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, offset: None }
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Commands written since the previous read, oldest first
+    ///
+    /// The first read only notes where the file ends. A file that shrank was
+    /// rewritten by the shell, so reading restarts at its new end. A line
+    /// still being written is left for the next read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the history file cannot be read
+    pub fn read_new_commands(&mut self) -> Result<Vec<String>> {
+        let mut file = File::open(&self.path)
+            .with_context(|| format!("Failed to open {}", self.path.display()))?;
+        let length = file.metadata()?.len();
+        let offset = match self.offset {
+            Some(offset) if offset <= length => offset,
+            _ => {
+                self.offset = Some(length);
+                return Ok(Vec::new());
+            }
+        };
+
+        file.seek(SeekFrom::Start(offset))?;
+        let mut appended = Vec::new();
+        file.read_to_end(&mut appended)?;
+        let complete = appended
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |newline| newline + 1);
+        self.offset = Some(offset + u64::try_from(complete)?);
+
+        Ok(parse_history(&String::from_utf8_lossy(
+            &appended[..complete],
+        )))
+    }
+}
+
+/// Commands in a chunk of zsh or bash history
+///
+/// Handles zsh's extended `: <time>:<duration>;<command>` lines and its
+/// backslash-continued multi-line commands, and skips bash's `#<time>` lines.
+/// Runs of whitespace collapse to one space and secrets are redacted.
+fn parse_history(text: &str) -> Vec<String> {
+    let mut commands = Vec::new();
+    let mut pending = String::new();
+    for line in text.lines() {
+        let line = if pending.is_empty() {
+            strip_zsh_metadata(line)
+        } else {
+            line
+        };
+        if let Some(continued) = line.strip_suffix('\\') {
+            pending.push_str(continued);
+            pending.push(' ');
+            continue;
+        }
+        pending.push_str(line);
+
+        let command = pending.split_whitespace().collect::<Vec<_>>().join(" ");
+        let bash_timestamp = command
+            .strip_prefix('#')
+            .is_some_and(|rest| !rest.is_empty() && rest.bytes().all(|b| b.is_ascii_digit()));
+        if !command.is_empty() && !bash_timestamp {
+            let command = redact_secrets(&command);
+            commands.push(command.chars().take(MAX_COMMAND_CHARS).collect());
+        }
+        pending.clear();
+    }
+    commands
+}
+
+/// The command of a zsh extended history line, or the line unchanged
+fn strip_zsh_metadata(line: &str) -> &str {
+    line.strip_prefix(": ")
+        .and_then(|rest| rest.split_once(';'))
+        .filter(|(metadata, _)| {
+            metadata
+                .split(':')
+                .all(|field| !field.is_empty() && field.bytes().all(|b| b.is_ascii_digit()))
+        })
+        .map_or(line, |(_, command)| command)
+}
+
+/// A command with the secrets typed into it replaced
+///
+/// Covers `NAME=value` and `--name=value` where the name mentions a token,
+/// key, secret or password, the value after a bare `--password`-style flag,
+/// `-p<password>` for `mysql` clients, and `Authorization` header values.
+fn redact_secrets(command: &str) -> String {
+    let password_flag = command.split(' ').any(|word| {
+        let program = word.rsplit('/').next().unwrap_or(word);
+        PASSWORD_FLAG_CLIENTS.contains(&program)
+    });
+    let mut redacted = Vec::new();
+    let mut words = command.split(' ');
+    while let Some(word) = words.next() {
+        if let Some((name, _)) = word
+            .split_once('=')
+            .filter(|(name, _)| is_secret_name(name))
+        {
+            redacted.push(format!("{name}={REDACTED}"));
+        } else if password_flag && word.len() > 2 && word.starts_with("-p") {
+            redacted.push(format!("-p{REDACTED}"));
+        } else if word.starts_with("--") && is_secret_name(word) {
+            redacted.push(word.to_string());
+            if words.next().is_some() {
+                redacted.push(REDACTED.to_string());
+            }
+        } else if let Some((name, value)) = word.split_once(':').filter(|(name, _)| {
+            name.trim_start_matches(['"', '\''])
+                .eq_ignore_ascii_case("authorization")
+        }) {
+            let quote = word.chars().next().filter(|c| matches!(c, '"' | '\''));
+            skip_header_value(value, quote, &mut words);
+            let closing = quote.map(String::from).unwrap_or_default();
+            redacted.push(format!("{name}: {REDACTED}{closing}"));
+        } else {
+            redacted.push(word.to_string());
+        }
+    }
+    redacted.join(" ")
+}
+
+/// Whether a variable or flag name mentions a secret
+fn is_secret_name(name: &str) -> bool {
+    let name = name.trim_start_matches('-');
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && SECRET_NAMES
+            .iter()
+            .any(|secret| name.to_ascii_uppercase().contains(secret))
+}
+
+/// Consume the words of a header value that starts with `first`
+///
+/// A quoted value runs to the word ending with its quote; an unquoted one is
+/// a single word, or two when the first is an authorization scheme.
+fn skip_header_value<'a>(
+    first: &'a str,
+    quote: Option<char>,
+    words: &mut impl Iterator<Item = &'a str>,
+) {
+    let mut current = Some(first)
+        .filter(|value| !value.is_empty())
+        .or_else(|| words.next());
+    match quote {
+        Some(quote) => {
+            while let Some(value) = current {
+                if value.ends_with(quote) {
+                    break;
+                }
+                current = words.next();
+            }
+        }
+        None => {
+            if current.is_some_and(|value| {
+                AUTH_SCHEMES
+                    .iter()
+                    .any(|scheme| value.eq_ignore_ascii_case(scheme))
+            }) {
+                words.next();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_parse_zsh_and_bash_history() {
+        let history = ": 1741939200:0;cargo test -p toki-core\n\
+                       : 1741939260:3;kubectl apply \\\n\
+                       -f deploy.yaml\n\
+                       #1741939300\n\
+                       git status\n\
+                       \n";
+        assert_eq!(
+            parse_history(history),
+            vec![
+                "cargo test -p toki-core",
+                "kubectl apply -f deploy.yaml",
+                "git status",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_secrets_redacted_from_history() {
+        let history = "GITHUB_TOKEN=ghp_abc123 gh pr list\n\
+                       export AWS_SECRET_ACCESS_KEY=wJalr\n\
+                       mysql -uroot -phunter2 app\n\
+                       curl -H \"Authorization: Bearer eyJhbGci\" https://api.example.com\n\
+                       toki config set --api-key=sk-123\n\
+                       psql --password hunter2 app\n\
+                       find . -print\n";
+        assert_eq!(
+            parse_history(history),
+            vec![
+                "GITHUB_TOKEN=[REDACTED] gh pr list",
+                "export AWS_SECRET_ACCESS_KEY=[REDACTED]",
+                "mysql -uroot -p[REDACTED] app",
+                "curl -H \"Authorization: [REDACTED]\" https://api.example.com",
+                "toki config set --api-key=[REDACTED]",
+                "psql --password [REDACTED] app",
+                "find . -print",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_only_commands_appended_after_first_read_are_returned() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "ssh old-secret-host").unwrap();
+
+        let mut history = ShellHistory::new(file.path().to_path_buf());
+        assert!(history.read_new_commands().unwrap().is_empty());
+
+        write!(file, "cargo build\ncargo te").unwrap();
+        assert_eq!(history.read_new_commands().unwrap(), vec!["cargo build"]);
+
+        // The half-written line is picked up once it is complete
+        writeln!(file, "st").unwrap();
+        assert_eq!(history.read_new_commands().unwrap(), vec!["cargo test"]);
+        assert!(history.read_new_commands().unwrap().is_empty());
+
+        // A rewritten, shorter file restarts at its end
+        file.as_file().set_len(0).unwrap();
+        assert!(history.read_new_commands().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_terminal_apps() {
+        assert!(is_terminal_app("com.googlecode.iterm2"));
+        assert!(is_terminal_app("org.gnome.Terminal"));
+        assert!(is_terminal_app("kitty"));
+        assert!(!is_terminal_app("com.microsoft.VSCode"));
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::Timelike;
+use toki_storage::{ActivitySpan, Database, WorkHours};
+use uuid::Uuid;
+
+/// Expected working hours, globally and per project
+///
+/// `settings.work_hours` applies to every project unless the project has its
+/// own hours, so a night-shift client isn't reported as overtime.
+#[derive(Debug, Clone, Default)]
+pub struct WorkSchedule {
+    default: WorkHours,
+    projects: HashMap<Uuid, WorkHours>,
+}
+
+impl WorkSchedule {
+    #[must_use]
+    pub fn new(default: WorkHours, projects: HashMap<Uuid, WorkHours>) -> Self {
+        Self { default, projects }
+    }
+
+    /// Load the global hours and per-project overrides from the database
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if settings or overrides cannot be read
+    pub fn load(db: &Database) -> Result<Self> {
+        Ok(Self::new(
+            db.get_settings()?.work_hours,
+            db.get_project_work_hours()?,
+        ))
+    }
+
+    /// Working hours for the given project
+    #[must_use]
+    pub fn hours_for(&self, project_id: Option<Uuid>) -> WorkHours {
+        project_id
+            .and_then(|id| self.projects.get(&id))
+            .copied()
+            .unwrap_or(self.default)
+    }
+
+    /// Whether a span started outside the working hours of its project
+    #[must_use]
+    pub fn is_off_hours(&self, span: &ActivitySpan) -> bool {
+        !self
+            .hours_for(span.project_id)
+            .contains(span.start_time.hour())
+    }
+
+    /// Tracked seconds of `spans` that fall outside working hours
+    #[must_use]
+    pub fn off_hours_seconds(&self, spans: &[ActivitySpan]) -> u32 {
+        spans
+            .iter()
+            .filter(|span| self.is_off_hours(span))
+            .map(|span| span.duration_seconds)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn span_at(hour: u32, project_id: Option<Uuid>) -> ActivitySpan {
+        let mut span = ActivitySpan::new(
+            "com.microsoft.VSCode".to_string(),
+            "Coding".to_string(),
+            Utc.with_ymd_and_hms(2025, 3, 14, hour, 30, 0).unwrap(),
+            project_id,
+            None,
+            None,
+        );
+        span.duration_seconds = 1800;
+        span
+    }
+
+    #[test]
+    fn test_night_shift_project_not_flagged_off_hours() {
+        let night_client = Uuid::new_v4();
+        let day_client = Uuid::new_v4();
+        let schedule = WorkSchedule::new(
+            WorkHours::default(),
+            HashMap::from([
+                (night_client, "22-06".parse().unwrap()),
+                (day_client, "08-17".parse().unwrap()),
+            ]),
+        );
+
+        assert!(!schedule.is_off_hours(&span_at(23, Some(night_client))));
+        assert!(!schedule.is_off_hours(&span_at(3, Some(night_client))));
+        assert!(schedule.is_off_hours(&span_at(23, Some(day_client))));
+        assert!(schedule.is_off_hours(&span_at(12, Some(night_client))));
+
+        let spans = [
+            span_at(23, Some(night_client)),
+            span_at(23, Some(day_client)),
+            span_at(10, Some(day_client)),
+        ];
+        assert_eq!(schedule.off_hours_seconds(&spans), 1800);
+    }
+
+    #[test]
+    fn test_unconfigured_project_falls_back_to_global_hours() {
+        let schedule = WorkSchedule::new(
+            "09-18".parse().unwrap(),
+            HashMap::from([(Uuid::new_v4(), "22-06".parse().unwrap())]),
+        );
+
+        assert!(schedule.is_off_hours(&span_at(23, Some(Uuid::new_v4()))));
+        assert!(schedule.is_off_hours(&span_at(23, None)));
+        assert!(!schedule.is_off_hours(&span_at(9, None)));
+        assert!(schedule.is_off_hours(&span_at(18, None)));
+    }
+}
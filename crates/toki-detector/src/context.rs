@@ -1,7 +1,7 @@
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 
-use crate::git::GitDetector;
+use crate::git::{CommitIssue, GitDetector};
 use crate::ide::vscode;
 use crate::parser::{IssueId, IssueIdParser};
 use strum_macros::Display;
@@ -122,6 +122,13 @@ impl WorkContextDetector {
         vscode::get_last_workspace(window_title).await
     }
 
+    /// Detect the issue referenced by the latest commit of the repository containing `path`
+    #[must_use]
+    pub fn detect_commit_from_path(&self, path: &Path) -> Option<CommitIssue> {
+        let repo_path = self.git_detector.find_repo(path).ok()??;
+        self.git_detector.detect_commit_issue(&repo_path).ok()?
+    }
+
     /// Detect work item from a specific path (e.g., for git branch detection)
     #[must_use]
     pub fn detect_from_path(&self, path: &Path) -> Option<WorkItemRef> {
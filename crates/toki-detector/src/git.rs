@@ -5,6 +5,14 @@ use walkdir::WalkDir;
 
 use crate::parser::{IssueId, IssueIdParser};
 
+/// Issue referenced by a repository's latest commit
+#[derive(Debug, Clone)]
+pub struct CommitIssue {
+    pub issue_id: IssueId,
+    pub hash: String,
+    pub committed_at: i64, // Unix timestamp (seconds)
+}
+
 /// Git repository detector for extracting work item information
 pub struct GitDetector {
     parser: IssueIdParser,
@@ -69,20 +77,32 @@ impl GitDetector {
     ///
     /// Returns an error if the repository cannot be opened or commit info cannot be read
     pub fn detect_from_commit(&self, repo_path: &Path) -> Result<Option<IssueId>> {
+        Ok(self
+            .detect_commit_issue(repo_path)?
+            .map(|commit| commit.issue_id))
+    }
+
+    /// Detect the issue referenced by the most recent commit, with when it was made
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the repository cannot be opened or commit info cannot be read
+    pub fn detect_commit_issue(&self, repo_path: &Path) -> Result<Option<CommitIssue>> {
         let repo = Repository::open(repo_path)
             .with_context(|| format!("Failed to open Git repository at {}", repo_path.display()))?;
 
         let head = repo.head()?;
         let commit = head.peel_to_commit()?;
 
-        if let Some(message) = commit.message() {
-            let ids = self.parser.parse(message);
-            if !ids.is_empty() {
-                return Ok(Some(ids[0].clone()));
-            }
-        }
+        let issue_id = commit
+            .message()
+            .and_then(|message| self.parser.parse(message).into_iter().next());
 
-        Ok(None)
+        Ok(issue_id.map(|issue_id| CommitIssue {
+            issue_id,
+            hash: commit.id().to_string(),
+            committed_at: commit.time().seconds(),
+        }))
     }
 
     /// Detect issue ID from Git, trying branch first then commit
@@ -219,4 +239,33 @@ mod tests {
         assert_eq!(ids.len(), 1);
         assert_eq!(ids[0].id, "PROJ-123");
     }
+
+    #[test]
+    fn test_detect_commit_issue_reports_commit_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let signature =
+            git2::Signature::new("Dev", "dev@example.com", &git2::Time::new(1_700_000_000, 0))
+                .unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let oid = repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                "PROJ-42 handle late commits",
+                &tree,
+                &[],
+            )
+            .unwrap();
+
+        let commit = GitDetector::new()
+            .detect_commit_issue(dir.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(commit.issue_id.id, "PROJ-42");
+        assert_eq!(commit.hash, oid.to_string());
+        assert_eq!(commit.committed_at, 1_700_000_000);
+    }
 }
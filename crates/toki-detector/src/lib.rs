@@ -4,5 +4,5 @@ pub mod ide;
 pub mod parser;
 
 pub use context::{DetectionSource, WorkContextDetector, WorkItemRef};
-pub use git::GitDetector;
+pub use git::{CommitIssue, GitDetector};
 pub use parser::{IssueId, IssueIdParser};
@@ -0,0 +1,309 @@
+//! Exponential backoff shared by the integration clients
+//!
+//! [`Backoff`] yields the delays to wait between attempts; [`retry_async`] drives an
+//! async operation with it until the operation succeeds, fails with an error that
+//! is not worth retrying, or the delays run out. A `Retry-After` the API sent
+//! takes the place of the computed delay.
+
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::http::ApiError;
+
+/// Delays between retry attempts, growing exponentially up to a cap
+///
+/// Each delay is `base * factor^attempt`, capped at `max_delay`, then shortened by
+/// a random amount of up to `jitter` (a fraction) so that concurrent clients do
+/// not retry in lockstep. The iterator ends after `max_retries` delays.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    base: Duration,
+    factor: f64,
+    max_delay: Duration,
+    jitter: f64,
+    max_retries: u32,
+    attempt: u32,
+}
+
+impl Backoff {
+    /// Backoff starting at `base`, doubling up to 30 seconds, 5 retries, 20% jitter
+    #[must_use]
+    pub fn new(base: Duration) -> Self {
+        Self {
+            base,
+            factor: 2.0,
+            max_delay: Duration::from_secs(30),
+            jitter: 0.2,
+            max_retries: 5,
+            attempt: 0,
+        }
+    }
+
+    /// Multiply the delay by `factor` after each attempt (at least 1.0)
+    #[must_use]
+    pub fn factor(mut self, factor: f64) -> Self {
+        self.factor = factor.max(1.0);
+        self
+    }
+
+    /// Never wait longer than `max_delay`
+    #[must_use]
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Shorten each delay by a random fraction of up to `jitter` (0.0-1.0)
+    #[must_use]
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Stop after `max_retries` delays
+    #[must_use]
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Delay before jitter for the current attempt
+    fn nominal_delay(&self) -> Duration {
+        let exponent = i32::try_from(self.attempt).unwrap_or(i32::MAX);
+        let seconds = self.base.as_secs_f64() * self.factor.powi(exponent);
+        if seconds.is_finite() && seconds < self.max_delay.as_secs_f64() {
+            Duration::from_secs_f64(seconds)
+        } else {
+            self.max_delay
+        }
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(500))
+    }
+}
+
+impl Iterator for Backoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        if self.attempt >= self.max_retries {
+            return None;
+        }
+        let delay = self.nominal_delay();
+        self.attempt += 1;
+        Some(delay.mul_f64(1.0 - self.jitter * random_fraction()))
+    }
+}
+
+/// Random number in `[0.0, 1.0)`, good enough for spreading out retries
+fn random_fraction() -> f64 {
+    let bits = RandomState::new().build_hasher().finish() >> 11;
+    #[allow(clippy::cast_precision_loss)]
+    let fraction = bits as f64 / (1u64 << 53) as f64;
+    fraction
+}
+
+/// Run `operation` until it succeeds, retrying errors `is_retriable` accepts
+///
+/// Waits for the next `backoff` delay before each retry, or for the error's
+/// `Retry-After` (no longer than the backoff's `max_delay`) when the API sent
+/// one. The last error is returned once the delays run out, or straight away if
+/// it is not retriable.
+///
+/// # Errors
+///
+/// Returns the error of the final attempt
+pub async fn retry_async<T, F, Fut, R>(
+    mut backoff: Backoff,
+    is_retriable: R,
+    mut operation: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+    R: Fn(&anyhow::Error) -> bool,
+{
+    let max_delay = backoff.max_delay;
+    let mut attempt = 1;
+    loop {
+        let error = match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+        if !is_retriable(&error) {
+            return Err(error);
+        }
+        let Some(mut delay) = backoff.next() else {
+            return Err(error);
+        };
+        if let Some(retry_after) = ApiError::retry_after_hint(&error) {
+            delay = retry_after.min(max_delay);
+        }
+        attempt += 1;
+        log::warn!(
+            "Retrying in {} ms (attempt {attempt}) after: {error:#}",
+            delay.as_millis()
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn api_error(status: u16) -> anyhow::Error {
+        ApiError {
+            api_name: "Notion".to_string(),
+            status,
+            body: String::new(),
+            retry_after: None,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_backoff_sequence_grows_and_is_bounded() {
+        let delays: Vec<u128> = Backoff::new(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(500))
+            .jitter(0.0)
+            .max_retries(5)
+            .map(|d| d.as_millis())
+            .collect();
+
+        assert_eq!(delays, vec![100, 200, 400, 500, 500]);
+    }
+
+    #[test]
+    fn test_backoff_jitter_stays_in_range() {
+        let backoff = Backoff::new(Duration::from_secs(1))
+            .factor(1.0)
+            .jitter(0.25)
+            .max_retries(50);
+
+        for delay in backoff {
+            assert!(delay >= Duration::from_millis(750), "{delay:?} too short");
+            assert!(delay <= Duration::from_secs(1), "{delay:?} too long");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_after_max_retries() {
+        let attempts = AtomicU32::new(0);
+        let backoff = Backoff::new(Duration::from_millis(1)).max_retries(3);
+
+        let result: Result<()> = retry_async(backoff, ApiError::is_transient_error, || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(api_error(503))
+        })
+        .await;
+
+        let error = result.unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ApiError>().map(|e| e.status),
+            Some(503)
+        );
+        assert_eq!(attempts.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_retry_stops_on_permanent_error_and_recovers_from_transient() {
+        let attempts = AtomicU32::new(0);
+        let result: Result<()> = retry_async(
+            Backoff::new(Duration::from_millis(1)),
+            ApiError::is_transient_error,
+            || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(api_error(404))
+            },
+        )
+        .await;
+        assert!(ApiError::is_not_found_error(&result.unwrap_err()));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        let attempts = AtomicU32::new(0);
+        let value = retry_async(
+            Backoff::new(Duration::from_millis(1)),
+            ApiError::is_transient_error,
+            || async {
+                if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(api_error(429))
+                } else {
+                    Ok("done")
+                }
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(value, "done");
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_writes_retried_only_when_unprocessed() {
+        let attempts = AtomicU32::new(0);
+        let result: Result<()> = retry_async(
+            Backoff::new(Duration::from_millis(1)),
+            ApiError::is_unprocessed_error,
+            || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(api_error(503))
+            },
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        let attempts = AtomicU32::new(0);
+        let value = retry_async(
+            Backoff::new(Duration::from_millis(1)),
+            ApiError::is_unprocessed_error,
+            || async {
+                if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                    Err(api_error(429))
+                } else {
+                    Ok("created")
+                }
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(value, "created");
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_waits_for_retry_after_instead_of_backoff() {
+        let attempts = AtomicU32::new(0);
+        // The computed first delay is half a minute
+        let backoff = Backoff::new(Duration::from_secs(30)).jitter(0.0);
+
+        let started = std::time::Instant::now();
+        let value = retry_async(backoff, ApiError::is_transient_error, || async {
+            if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(ApiError {
+                    api_name: "Notion".to_string(),
+                    status: 429,
+                    body: String::new(),
+                    retry_after: Some(Duration::from_millis(10)),
+                }
+                .into())
+            } else {
+                Ok("done")
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(value, "done");
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
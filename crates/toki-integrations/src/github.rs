@@ -1,16 +1,18 @@
 //! GitHub Issues API client
 //!
-//! Implements the `IssueManagement` trait for GitHub repositories.
+//! Implements the `IssueManagement` trait for GitHub repositories, and sets
+//! single-select fields on Projects (v2) items through the GraphQL API.
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use reqwest::{header, Client};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
 
-use crate::http::ResponseExt;
+use crate::http::{RequestExt, ResponseExt};
 use crate::traits::{
-    CreateIssueRequest, CreatedIssue, IssueDetails, IssueManagement, IssueState,
-    UpdateIssueRequest,
+    warn_dropped_fields, CreateIssueRequest, CreatedIssue, IssueDetails, IssueField,
+    IssueFieldMapping, IssueManagement, IssueState, UpdateIssueRequest,
 };
 
 /// GitHub API client for issue management
@@ -20,12 +22,25 @@ pub struct GitHubClient {
     repo: String,
     /// API base URL (for GitHub Enterprise support)
     api_base: String,
+    /// Where `CreateIssueRequest` fields go on created issues
+    field_mapping: IssueFieldMapping,
 }
 
+/// Request fields GitHub issues can store directly
+const GITHUB_FIELDS: &[IssueField] = &[
+    IssueField::Body,
+    IssueField::Labels,
+    IssueField::Assignees,
+    IssueField::Milestone,
+];
+
 /// GitHub API issue response
 #[derive(Debug, Deserialize)]
 struct GitHubIssue {
     id: u64,
+    /// GraphQL id, used to add the issue to a project
+    #[serde(default)]
+    node_id: String,
     number: u64,
     title: String,
     body: Option<String>,
@@ -84,6 +99,78 @@ struct GitHubSearchResponse {
     items: Vec<GitHubIssue>,
 }
 
+/// Add an issue to a project; returns the existing item if it is already there
+const ADD_PROJECT_ITEM_MUTATION: &str = "mutation($project: ID!, $content: ID!) {
+  addProjectV2ItemById(input: {projectId: $project, contentId: $content}) { item { id } }
+}";
+
+/// Options of a single-select project field
+const FIELD_OPTIONS_QUERY: &str = "query($field: ID!) {
+  node(id: $field) { ... on ProjectV2SingleSelectField { options { id name } } }
+}";
+
+const UPDATE_FIELD_VALUE_MUTATION: &str =
+    "mutation($project: ID!, $item: ID!, $field: ID!, $option: String!) {
+  updateProjectV2ItemFieldValue(input: {
+    projectId: $project, itemId: $item, fieldId: $field, value: {singleSelectOptionId: $option}
+  }) { projectV2Item { id } }
+}";
+
+/// GitHub GraphQL response envelope
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AddProjectItemData {
+    #[serde(rename = "addProjectV2ItemById")]
+    added: AddedProjectItem,
+}
+
+#[derive(Debug, Deserialize)]
+struct AddedProjectItem {
+    item: GraphQlNode,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlNode {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FieldOptionsData {
+    node: Option<SingleSelectField>,
+}
+
+/// A field that is not single-select comes back without options
+#[derive(Debug, Deserialize)]
+struct SingleSelectField {
+    #[serde(default)]
+    options: Vec<SingleSelectOption>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SingleSelectOption {
+    id: String,
+    name: String,
+}
+
+/// Id of the option called `name`, ignoring case
+fn find_option_id<'a>(options: &'a [SingleSelectOption], name: &str) -> Option<&'a str> {
+    options
+        .iter()
+        .find(|option| option.name.eq_ignore_ascii_case(name))
+        .map(|option| option.id.as_str())
+}
+
 impl GitHubClient {
     /// Create a new GitHub client
     ///
@@ -130,14 +217,137 @@ impl GitHubClient {
             client,
             repo,
             api_base: api_base.trim_end_matches('/').to_string(),
+            field_mapping: IssueFieldMapping::github(),
         })
     }
 
+    /// Use a custom mapping of request fields to issue fields
+    #[must_use]
+    pub fn with_field_mapping(mut self, field_mapping: IssueFieldMapping) -> Self {
+        self.field_mapping = field_mapping;
+        self
+    }
+
     /// Get the issues API URL
     fn issues_url(&self) -> String {
         format!("{}/repos/{}/issues", self.api_base, self.repo)
     }
 
+    /// GraphQL endpoint; GitHub Enterprise serves it at `/api/graphql` next to `/api/v3`
+    fn graphql_url(&self) -> String {
+        let api = self.api_base.strip_suffix("/v3").unwrap_or(&self.api_base);
+        format!("{api}/graphql")
+    }
+
+    /// Run a GraphQL query, failing on any error GitHub reports
+    async fn graphql<T: DeserializeOwned>(&self, query: &str, variables: Value) -> Result<T> {
+        let response: GraphQlResponse<T> = self
+            .client
+            .post(self.graphql_url())
+            .json(&json!({ "query": query, "variables": variables }))
+            .send_logged("GitHub")
+            .await
+            .context("Failed to send GraphQL request")?
+            .ensure_success("GitHub")
+            .await?
+            .json()
+            .await
+            .context("Failed to parse GraphQL response")?;
+
+        if !response.errors.is_empty() {
+            let messages: Vec<_> = response.errors.into_iter().map(|e| e.message).collect();
+            anyhow::bail!("GitHub GraphQL error: {}", messages.join("; "));
+        }
+        response
+            .data
+            .ok_or_else(|| anyhow::anyhow!("GitHub GraphQL response has no data"))
+    }
+
+    /// Fetch an issue by number
+    async fn fetch_issue(&self, issue_id: &str) -> Result<GitHubIssue> {
+        let url = format!("{}/{issue_id}", self.issues_url());
+
+        self.client
+            .get(&url)
+            .send_logged("GitHub")
+            .await
+            .context("Failed to send get issue request")?
+            .ensure_success("GitHub")
+            .await?
+            .json()
+            .await
+            .context("Failed to parse issue response")
+    }
+
+    /// Set a single-select field of a project item to the given option
+    ///
+    /// # Arguments
+    /// * `project_id` - Projects (v2) node id (e.g., "`PVT_kwDO`...")
+    /// * `item_id` - Project item node id
+    /// * `field_id` - Single-select field node id
+    /// * `option_id` - Id of the option to select
+    ///
+    /// # Errors
+    /// Returns an error if the API request fails
+    pub async fn set_project_field(
+        &self,
+        project_id: &str,
+        item_id: &str,
+        field_id: &str,
+        option_id: &str,
+    ) -> Result<()> {
+        let _: Value = self
+            .graphql(
+                UPDATE_FIELD_VALUE_MUTATION,
+                json!({
+                    "project": project_id,
+                    "item": item_id,
+                    "field": field_id,
+                    "option": option_id,
+                }),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Set a single-select field for an issue to the option named `option_name`
+    ///
+    /// The issue is added to the project first if it is not on it yet.
+    ///
+    /// # Errors
+    /// Returns an error if the field has no option of that name or an API
+    /// request fails
+    pub async fn set_issue_project_option(
+        &self,
+        project_id: &str,
+        issue_number: &str,
+        field_id: &str,
+        option_name: &str,
+    ) -> Result<()> {
+        let field: FieldOptionsData = self
+            .graphql(FIELD_OPTIONS_QUERY, json!({ "field": field_id }))
+            .await?;
+        let options = field.node.map(|node| node.options).unwrap_or_default();
+        let option_id = find_option_id(&options, option_name).ok_or_else(|| {
+            let names: Vec<_> = options.iter().map(|option| option.name.as_str()).collect();
+            anyhow::anyhow!(
+                "Project field has no option named '{option_name}' (options: {})",
+                names.join(", ")
+            )
+        })?;
+
+        let issue = self.fetch_issue(issue_number).await?;
+        let added: AddProjectItemData = self
+            .graphql(
+                ADD_PROJECT_ITEM_MUTATION,
+                json!({ "project": project_id, "content": issue.node_id }),
+            )
+            .await?;
+
+        self.set_project_field(project_id, &added.added.item.id, field_id, option_id)
+            .await
+    }
+
     /// Convert GitHub issue to `IssueDetails`
     fn to_issue_details(issue: GitHubIssue) -> Result<IssueDetails> {
         let state = if issue.state == "open" {
@@ -168,6 +378,50 @@ impl GitHubClient {
         })
     }
 
+    /// Build the create payload, routing fields through the field mapping
+    fn build_create_issue(&self, request: &CreateIssueRequest) -> GitHubCreateIssue {
+        let mapping = &self.field_mapping;
+        warn_dropped_fields("GitHub", &mapping.dropped_fields(request, GITHUB_FIELDS));
+
+        let body = request
+            .body
+            .clone()
+            .filter(|_| mapping.sends(IssueField::Body));
+        // Build body with source tracking if provided
+        let body = if let (Some(source_id), Some(source_system)) =
+            (&request.source_id, &request.source_system)
+        {
+            let tracking_footer = format!("\n\n---\n_Synced from {source_system}: `{source_id}`_");
+            body.map(|b| format!("{b}{tracking_footer}"))
+                .or(Some(tracking_footer))
+        } else {
+            body
+        };
+
+        let mut labels = if mapping.sends(IssueField::Labels) {
+            request.labels.clone()
+        } else {
+            Vec::new()
+        };
+        labels.extend(mapping.routed_labels(request));
+
+        GitHubCreateIssue {
+            title: request.title.clone(),
+            body,
+            labels,
+            assignees: if mapping.sends(IssueField::Assignees) {
+                request.assignees.clone()
+            } else {
+                Vec::new()
+            },
+            milestone: request
+                .milestone
+                .as_ref()
+                .filter(|_| mapping.sends(IssueField::Milestone))
+                .and_then(|m| m.parse().ok()),
+        }
+    }
+
     /// Convert GitHub issue to `CreatedIssue`
     fn to_created_issue(issue: GitHubIssue) -> CreatedIssue {
         let state = if issue.state == "open" {
@@ -189,34 +443,13 @@ impl GitHubClient {
 #[async_trait]
 impl IssueManagement for GitHubClient {
     async fn create_issue(&self, request: &CreateIssueRequest) -> Result<CreatedIssue> {
-        // Build body with source tracking if provided
-        let body = if let (Some(source_id), Some(source_system)) =
-            (&request.source_id, &request.source_system)
-        {
-            let tracking_footer =
-                format!("\n\n---\n_Synced from {source_system}: `{source_id}`_");
-            request
-                .body
-                .as_ref()
-                .map(|b| format!("{b}{tracking_footer}"))
-                .or(Some(tracking_footer))
-        } else {
-            request.body.clone()
-        };
-
-        let github_request = GitHubCreateIssue {
-            title: request.title.clone(),
-            body,
-            labels: request.labels.clone(),
-            assignees: request.assignees.clone(),
-            milestone: request.milestone.as_ref().and_then(|m| m.parse().ok()),
-        };
+        let github_request = self.build_create_issue(request);
 
         let response = self
             .client
             .post(self.issues_url())
             .json(&github_request)
-            .send()
+            .send_logged("GitHub")
             .await
             .context("Failed to send create issue request")?
             .ensure_success("GitHub")
@@ -248,7 +481,7 @@ impl IssueManagement for GitHubClient {
         self.client
             .patch(&url)
             .json(&github_update)
-            .send()
+            .send_logged("GitHub")
             .await
             .context("Failed to send update issue request")?
             .ensure_success("GitHub")
@@ -258,23 +491,7 @@ impl IssueManagement for GitHubClient {
     }
 
     async fn get_issue(&self, issue_id: &str) -> Result<IssueDetails> {
-        let url = format!("{}/{issue_id}", self.issues_url());
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .context("Failed to send get issue request")?
-            .ensure_success("GitHub")
-            .await?;
-
-        let issue: GitHubIssue = response
-            .json()
-            .await
-            .context("Failed to parse issue response")?;
-
-        Self::to_issue_details(issue)
+        Self::to_issue_details(self.fetch_issue(issue_id).await?)
     }
 
     async fn search_issues(&self, query: &str) -> Result<Vec<IssueDetails>> {
@@ -289,7 +506,7 @@ impl IssueManagement for GitHubClient {
         let response = self
             .client
             .get(&url)
-            .send()
+            .send_logged("GitHub")
             .await
             .context("Failed to send search request")?
             .ensure_success("GitHub")
@@ -319,7 +536,7 @@ impl IssueManagement for GitHubClient {
         let response = self
             .client
             .get(&url)
-            .send()
+            .send_logged("GitHub")
             .await
             .context("Failed to send list issues request")?
             .ensure_success("GitHub")
@@ -340,7 +557,7 @@ impl IssueManagement for GitHubClient {
         let response = self
             .client
             .get(&url)
-            .send()
+            .send_logged("GitHub")
             .await
             .context("Failed to send validation request")?;
 
@@ -373,4 +590,86 @@ mod tests {
         assert_eq!(request.source_id, Some("notion-page-123".to_string()));
         assert_eq!(request.source_system, Some("notion".to_string()));
     }
+
+    #[test]
+    fn test_issue_labels_carried_into_work_item() {
+        let issue: GitHubIssue = serde_json::from_value(serde_json::json!({
+            "id": 1001,
+            "number": 42,
+            "title": "Crash on startup",
+            "body": "Steps to reproduce",
+            "state": "open",
+            "html_url": "https://github.com/owner/repo/issues/42",
+            "labels": [{"name": "bug"}, {"name": "priority: high"}],
+            "assignees": [],
+            "created_at": "2025-01-10T09:00:00Z",
+            "updated_at": "2025-01-11T09:00:00Z"
+        }))
+        .unwrap();
+
+        let details = GitHubClient::to_issue_details(issue)
+            .unwrap()
+            .into_work_item("#42".to_string(), Some("owner/repo".to_string()));
+        assert_eq!(details.status, "open");
+        assert_eq!(details.labels, ["bug", "priority: high"]);
+    }
+
+    #[test]
+    fn test_priority_dropped_without_mapping() {
+        let request = CreateIssueRequest::new("Test issue".to_string())
+            .with_label("bug".to_string())
+            .with_priority("high".to_string());
+        let client = GitHubClient::new("test-token", "owner/repo".to_string()).unwrap();
+
+        assert_eq!(
+            client.field_mapping.dropped_fields(&request, GITHUB_FIELDS),
+            vec![IssueField::Priority]
+        );
+        assert_eq!(client.build_create_issue(&request).labels, vec!["bug"]);
+
+        let client = client.with_field_mapping(IssueFieldMapping {
+            priority: Some(crate::traits::LABELS_TARGET.to_string()),
+            ..IssueFieldMapping::github()
+        });
+        assert!(client
+            .field_mapping
+            .dropped_fields(&request, GITHUB_FIELDS)
+            .is_empty());
+        assert_eq!(
+            client.build_create_issue(&request).labels,
+            vec!["bug", "priority: high"]
+        );
+    }
+
+    #[test]
+    fn test_graphql_url_for_github_and_enterprise() {
+        let client = GitHubClient::new("test-token", "owner/repo".to_string()).unwrap();
+        assert_eq!(client.graphql_url(), "https://api.github.com/graphql");
+
+        let client = GitHubClient::with_base_url(
+            "test-token",
+            "owner/repo".to_string(),
+            "https://github.example.com/api/v3/",
+        )
+        .unwrap();
+        assert_eq!(
+            client.graphql_url(),
+            "https://github.example.com/api/graphql"
+        );
+    }
+
+    #[test]
+    fn test_single_select_option_found_by_name() {
+        let field: FieldOptionsData = serde_json::from_value(serde_json::json!({
+            "node": { "options": [
+                { "id": "opt1", "name": "Trivial" },
+                { "id": "opt3", "name": "Moderate" },
+            ] }
+        }))
+        .unwrap();
+        let options = field.node.unwrap().options;
+
+        assert_eq!(find_option_id(&options, "moderate"), Some("opt3"));
+        assert_eq!(find_option_id(&options, "epic"), None);
+    }
 }
@@ -10,10 +10,11 @@ use async_trait::async_trait;
 use reqwest::{header, Client};
 use serde::{Deserialize, Serialize};
 
-use crate::http::ResponseExt;
+use crate::http::{RequestExt, ResponseExt};
 use crate::traits::{
-    CreateIssueRequest, CreatedIssue, IssueDetails, IssueManagement, IssueState,
-    ProjectManagementSystem, SyncReport, TimeEntry, UpdateIssueRequest, WorkItemDetails,
+    warn_dropped_fields, CreateIssueRequest, CreatedIssue, IssueDetails, IssueField,
+    IssueFieldMapping, IssueManagement, IssueState, ProjectManagementSystem, SyncReport, TimeEntry,
+    UpdateIssueRequest, WorkItemDetails,
 };
 
 /// GitLab API client for issue management
@@ -23,8 +24,18 @@ pub struct GitLabClient {
     project: String,
     /// API base URL
     api_base: String,
+    /// Where `CreateIssueRequest` fields go on created issues
+    field_mapping: IssueFieldMapping,
 }
 
+/// Request fields GitLab issues can store directly
+const GITLAB_FIELDS: &[IssueField] = &[
+    IssueField::Body,
+    IssueField::Labels,
+    IssueField::Milestone,
+    IssueField::DueDate,
+];
+
 /// GitLab API issue response
 #[derive(Debug, Deserialize)]
 struct GitLabIssue {
@@ -160,9 +171,17 @@ impl GitLabClient {
             client,
             project: encoded_project,
             api_base: api_base.trim_end_matches('/').to_string(),
+            field_mapping: IssueFieldMapping::gitlab(),
         })
     }
 
+    /// Use a custom mapping of request fields to issue fields
+    #[must_use]
+    pub fn with_field_mapping(mut self, field_mapping: IssueFieldMapping) -> Self {
+        self.field_mapping = field_mapping;
+        self
+    }
+
     /// Get the issues API URL
     fn issues_url(&self) -> String {
         format!("{}/projects/{}/issues", self.api_base, self.project)
@@ -198,6 +217,55 @@ impl GitLabClient {
         })
     }
 
+    /// Build the create payload, routing fields through the field mapping
+    fn build_create_issue(&self, request: &CreateIssueRequest) -> GitLabCreateIssue {
+        let mapping = &self.field_mapping;
+        warn_dropped_fields("GitLab", &mapping.dropped_fields(request, GITLAB_FIELDS));
+
+        let body = request
+            .body
+            .clone()
+            .filter(|_| mapping.sends(IssueField::Body));
+        // Build description with source tracking if provided
+        let description = if let (Some(source_id), Some(source_system)) =
+            (&request.source_id, &request.source_system)
+        {
+            let tracking_footer = format!("\n\n---\n_Synced from {source_system}: `{source_id}`_");
+            body.map(|b| format!("{b}{tracking_footer}"))
+                .or(Some(tracking_footer))
+        } else {
+            body
+        };
+
+        let mut labels = if mapping.sends(IssueField::Labels) {
+            request.labels.clone()
+        } else {
+            Vec::new()
+        };
+        labels.extend(mapping.routed_labels(request));
+
+        GitLabCreateIssue {
+            title: request.title.clone(),
+            description,
+            // Convert labels to comma-separated string
+            labels: if labels.is_empty() {
+                None
+            } else {
+                Some(labels.join(","))
+            },
+            assignee_ids: None, // Would need to resolve usernames to IDs
+            milestone_id: request
+                .milestone
+                .as_ref()
+                .filter(|_| mapping.sends(IssueField::Milestone))
+                .and_then(|m| m.parse().ok()),
+            due_date: request
+                .due_date
+                .filter(|_| mapping.sends(IssueField::DueDate))
+                .map(|d| d.format("%Y-%m-%d").to_string()),
+        }
+    }
+
     /// Convert GitLab issue to `CreatedIssue`
     fn to_created_issue(issue: GitLabIssue) -> CreatedIssue {
         let state = if issue.state == "opened" {
@@ -268,7 +336,7 @@ impl GitLabClient {
         self.client
             .post(&url)
             .json(&request)
-            .send()
+            .send_logged("GitLab")
             .await
             .context("Failed to send add spent time request")?
             .ensure_success("GitLab")
@@ -316,7 +384,7 @@ impl GitLabClient {
         let response = self
             .client
             .get(&url)
-            .send()
+            .send_logged("GitLab")
             .await
             .context("Failed to send get time stats request")?
             .ensure_success("GitLab")
@@ -348,7 +416,7 @@ impl GitLabClient {
         self.client
             .post(&url)
             .json(&request)
-            .send()
+            .send_logged("GitLab")
             .await
             .context("Failed to send set time estimate request")?
             .ensure_success("GitLab")
@@ -357,6 +425,37 @@ impl GitLabClient {
         Ok(())
     }
 
+    /// Quick-action note body that sets an issue's time estimate
+    #[must_use]
+    pub fn estimate_note_body(duration_seconds: u32) -> String {
+        format!("/estimate {}", Self::seconds_to_duration(duration_seconds))
+    }
+
+    /// Set an issue's time estimate by posting an `/estimate` note
+    ///
+    /// Unlike [`Self::set_time_estimate`], the change shows up in the issue's
+    /// activity, so teammates can see where the estimate came from.
+    ///
+    /// # Arguments
+    /// * `issue_iid` - Issue internal ID
+    /// * `duration_seconds` - Estimated duration in seconds
+    ///
+    /// # Errors
+    /// Returns an error if the API request fails
+    pub async fn push_estimate_note(&self, issue_iid: &str, duration_seconds: u32) -> Result<()> {
+        self.add_note(issue_iid, &Self::estimate_note_body(duration_seconds))
+            .await
+            .context("Failed to push time estimate note")?;
+
+        log::info!(
+            "Set estimate of GitLab issue #{} to {}",
+            issue_iid,
+            Self::seconds_to_duration(duration_seconds)
+        );
+
+        Ok(())
+    }
+
     /// Add a note (comment) to an issue
     async fn add_note(&self, issue_iid: &str, body: &str) -> Result<()> {
         let url = format!(
@@ -367,7 +466,7 @@ impl GitLabClient {
         self.client
             .post(&url)
             .json(&NoteRequest { body })
-            .send()
+            .send_logged("GitLab")
             .await
             .context("Failed to send add note request")?
             .ensure_success("GitLab")
@@ -380,42 +479,13 @@ impl GitLabClient {
 #[async_trait]
 impl IssueManagement for GitLabClient {
     async fn create_issue(&self, request: &CreateIssueRequest) -> Result<CreatedIssue> {
-        // Build description with source tracking if provided
-        let description = if let (Some(source_id), Some(source_system)) =
-            (&request.source_id, &request.source_system)
-        {
-            let tracking_footer =
-                format!("\n\n---\n_Synced from {source_system}: `{source_id}`_");
-            request
-                .body
-                .as_ref()
-                .map(|b| format!("{b}{tracking_footer}"))
-                .or(Some(tracking_footer))
-        } else {
-            request.body.clone()
-        };
-
-        // Convert labels to comma-separated string
-        let labels = if request.labels.is_empty() {
-            None
-        } else {
-            Some(request.labels.join(","))
-        };
-
-        let gitlab_request = GitLabCreateIssue {
-            title: request.title.clone(),
-            description,
-            labels,
-            assignee_ids: None, // Would need to resolve usernames to IDs
-            milestone_id: request.milestone.as_ref().and_then(|m| m.parse().ok()),
-            due_date: request.due_date.map(|d| d.format("%Y-%m-%d").to_string()),
-        };
+        let gitlab_request = self.build_create_issue(request);
 
         let response = self
             .client
             .post(self.issues_url())
             .json(&gitlab_request)
-            .send()
+            .send_logged("GitLab")
             .await
             .context("Failed to send create issue request")?
             .ensure_success("GitLab")
@@ -453,7 +523,7 @@ impl IssueManagement for GitLabClient {
         self.client
             .put(&url)
             .json(&gitlab_update)
-            .send()
+            .send_logged("GitLab")
             .await
             .context("Failed to send update issue request")?
             .ensure_success("GitLab")
@@ -468,7 +538,7 @@ impl IssueManagement for GitLabClient {
         let response = self
             .client
             .get(&url)
-            .send()
+            .send_logged("GitLab")
             .await
             .context("Failed to send get issue request")?
             .ensure_success("GitLab")
@@ -492,7 +562,7 @@ impl IssueManagement for GitLabClient {
         let response = self
             .client
             .get(&url)
-            .send()
+            .send_logged("GitLab")
             .await
             .context("Failed to send search request")?
             .ensure_success("GitLab")
@@ -518,7 +588,7 @@ impl IssueManagement for GitLabClient {
         let response = self
             .client
             .get(&url)
-            .send()
+            .send_logged("GitLab")
             .await
             .context("Failed to send list issues request")?
             .ensure_success("GitLab")
@@ -539,7 +609,7 @@ impl IssueManagement for GitLabClient {
         let response = self
             .client
             .get(&url)
-            .send()
+            .send_logged("GitLab")
             .await
             .context("Failed to send validation request")?;
 
@@ -559,18 +629,7 @@ impl IssueManagement for GitLabClient {
 impl ProjectManagementSystem for GitLabClient {
     async fn fetch_work_item(&self, work_item_id: &str) -> Result<WorkItemDetails> {
         let issue = self.get_issue(work_item_id).await?;
-
-        Ok(WorkItemDetails {
-            id: work_item_id.to_string(),
-            title: issue.title,
-            description: issue.body,
-            status: match issue.state {
-                IssueState::Open => "open".to_string(),
-                IssueState::Closed => "closed".to_string(),
-            },
-            project: Some(self.project.clone()),
-            workspace: None,
-        })
+        Ok(issue.into_work_item(work_item_id.to_string(), Some(self.project.clone())))
     }
 
     async fn add_time_entry(&self, entry: &TimeEntry) -> Result<()> {
@@ -648,4 +707,40 @@ mod tests {
         assert_eq!(GitLabClient::seconds_to_duration(3599), "59m");
         assert_eq!(GitLabClient::seconds_to_duration(3661), "1h1m");
     }
+
+    #[test]
+    fn test_estimate_note_body() {
+        assert_eq!(GitLabClient::estimate_note_body(5400), "/estimate 1h30m");
+        assert_eq!(GitLabClient::estimate_note_body(7200), "/estimate 2h");
+        assert_eq!(GitLabClient::estimate_note_body(1800), "/estimate 30m");
+
+        let note = serde_json::to_value(NoteRequest {
+            body: &GitLabClient::estimate_note_body(36000),
+        })
+        .unwrap();
+        assert_eq!(note, serde_json::json!({ "body": "/estimate 10h" }));
+    }
+
+    #[test]
+    fn test_issue_labels_carried_into_work_item() {
+        let issue: GitLabIssue = serde_json::from_value(serde_json::json!({
+            "id": 2002,
+            "iid": 7,
+            "title": "Slow dashboard",
+            "description": null,
+            "state": "closed",
+            "web_url": "https://gitlab.com/group/project/-/issues/7",
+            "labels": ["performance", "frontend"],
+            "assignees": [],
+            "created_at": "2025-01-10T09:00:00Z",
+            "updated_at": "2025-01-11T09:00:00Z"
+        }))
+        .unwrap();
+
+        let details = GitLabClient::to_issue_details(issue)
+            .unwrap()
+            .into_work_item("7".to_string(), Some("group%2Fproject".to_string()));
+        assert_eq!(details.status, "closed");
+        assert_eq!(details.labels, ["performance", "frontend"]);
+    }
 }
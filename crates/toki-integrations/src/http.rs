@@ -1,7 +1,104 @@
 //! HTTP utilities for API integrations.
 
+use std::fmt;
+use std::time::{Duration, Instant};
+
 use anyhow::Result;
 
+use crate::request_log::{active_log_level, write_entry, RequestLogEntry};
+
+/// Non-success HTTP response from an integration API
+///
+/// Returned (wrapped in `anyhow::Error`) by [`ResponseExt::ensure_success`], so callers
+/// can tell a missing resource apart from transient failures.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    pub api_name: String,
+    pub status: u16,
+    pub body: String,
+    /// How long the API asked to wait before retrying (`Retry-After`)
+    pub retry_after: Option<Duration>,
+}
+
+impl ApiError {
+    /// Whether the API reported the resource as missing (404)
+    #[must_use]
+    pub fn is_not_found(&self) -> bool {
+        self.status == 404
+    }
+
+    /// Check whether an error chain contains a 404 from an integration API
+    #[must_use]
+    pub fn is_not_found_error(err: &anyhow::Error) -> bool {
+        err.chain()
+            .filter_map(|e| e.downcast_ref::<Self>())
+            .any(Self::is_not_found)
+    }
+
+    /// Whether the API asked to slow down (429) or failed on its side (5xx)
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        self.status == 429 || self.status >= 500
+    }
+
+    /// The `Retry-After` delay of the first API error in an error chain
+    #[must_use]
+    pub fn retry_after_hint(err: &anyhow::Error) -> Option<Duration> {
+        err.chain()
+            .filter_map(|e| e.downcast_ref::<Self>())
+            .find_map(|e| e.retry_after)
+    }
+
+    /// Check whether an error chain is worth retrying
+    ///
+    /// True for transient API responses and for requests that timed out or could
+    /// not connect.
+    #[must_use]
+    pub fn is_transient_error(err: &anyhow::Error) -> bool {
+        err.chain().any(|e| {
+            e.downcast_ref::<Self>().is_some_and(Self::is_transient)
+                || e.downcast_ref::<reqwest::Error>()
+                    .is_some_and(|e| e.is_timeout() || e.is_connect())
+        })
+    }
+
+    /// Check whether a non-idempotent request (e.g. creating a page) is safe to resend
+    ///
+    /// True only when the API rate limited it (429) or no connection was made, so
+    /// the request was never processed. A timeout or 5xx may follow a write that
+    /// already happened, and resending it would duplicate the write.
+    #[must_use]
+    pub fn is_unprocessed_error(err: &anyhow::Error) -> bool {
+        err.chain().any(|e| {
+            e.downcast_ref::<Self>().is_some_and(|e| e.status == 429)
+                || e.downcast_ref::<reqwest::Error>()
+                    .is_some_and(reqwest::Error::is_connect)
+        })
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = reqwest::StatusCode::from_u16(self.status)
+            .ok()
+            .and_then(|s| s.canonical_reason());
+        match reason {
+            Some(reason) => write!(
+                f,
+                "{} API error ({} {reason}): {}",
+                self.api_name, self.status, self.body
+            ),
+            None => write!(
+                f,
+                "{} API error ({}): {}",
+                self.api_name, self.status, self.body
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
 /// Extension trait for reqwest::Response to handle common error patterns.
 #[async_trait::async_trait]
 pub trait ResponseExt {
@@ -20,10 +117,96 @@ pub trait ResponseExt {
 impl ResponseExt for reqwest::Response {
     async fn ensure_success(self, api_name: &str) -> Result<Self> {
         if !self.status().is_success() {
-            let status = self.status();
-            let error_text = self.text().await.unwrap_or_default();
-            anyhow::bail!("{api_name} API error ({status}): {error_text}");
+            let status = self.status().as_u16();
+            // Only the delay-seconds form; APIs toki talks to do not send dates
+            let retry_after = self
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+                .map(Duration::from_secs);
+            let body = self.text().await.unwrap_or_default();
+            return Err(ApiError {
+                api_name: api_name.to_string(),
+                status,
+                body,
+                retry_after,
+            }
+            .into());
         }
         Ok(self)
     }
 }
+
+/// Extension trait for `reqwest::RequestBuilder` shared by all integration clients
+#[async_trait::async_trait]
+pub trait RequestExt {
+    /// Send the request, recording it in the integration debug log when enabled
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request cannot be built or sent, or the response body
+    /// cannot be read while logging bodies.
+    async fn send_logged(self, api_name: &str) -> reqwest::Result<reqwest::Response>;
+}
+
+#[async_trait::async_trait]
+impl RequestExt for reqwest::RequestBuilder {
+    async fn send_logged(self, api_name: &str) -> reqwest::Result<reqwest::Response> {
+        let Some(include_bodies) = active_log_level() else {
+            return self.send().await;
+        };
+
+        let (client, request) = self.build_split();
+        let request = request?;
+        let method = request.method().clone();
+        let url = request.url().clone();
+        let request_headers = include_bodies.then(|| request.headers().clone());
+        let request_body = request
+            .body()
+            .and_then(reqwest::Body::as_bytes)
+            .filter(|_| include_bodies)
+            .map(<[u8]>::to_vec);
+
+        let started = Instant::now();
+        let result = client.execute(request).await;
+        let mut entry = RequestLogEntry {
+            api_name,
+            method: &method,
+            url: &url,
+            outcome: Err(String::new()),
+            elapsed: started.elapsed(),
+            request_headers: request_headers.as_ref(),
+            request_body: request_body.as_deref(),
+            response_body: None,
+        };
+
+        let response = match result {
+            Ok(response) => response,
+            Err(e) => {
+                entry.outcome = Err(e.to_string());
+                write_entry(&entry);
+                return Err(e);
+            }
+        };
+        entry.outcome = Ok(response.status().as_u16());
+        if !include_bodies {
+            write_entry(&entry);
+            return Ok(response);
+        }
+
+        // Reading the body consumes the response, so hand callers an equivalent copy
+        let status = response.status();
+        let version = response.version();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+        entry.response_body = Some(&body);
+        write_entry(&entry);
+
+        let mut copy = http::Response::new(body.clone());
+        *copy.status_mut() = status;
+        *copy.version_mut() = version;
+        *copy.headers_mut() = headers;
+        Ok(reqwest::Response::from(copy))
+    }
+}
@@ -0,0 +1,352 @@
+//! Jira Cloud REST API client
+//!
+//! Implements `ProjectManagementSystem` so tracked time can be logged as
+//! Jira worklogs. Authenticates with an account email and API token.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::{header, Client};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::http::{RequestExt, ResponseExt};
+use crate::traits::{ProjectManagementSystem, SyncReport, TimeEntry, WorkItemDetails};
+
+/// Jira Cloud API client
+pub struct JiraClient {
+    client: Client,
+    /// Site URL (e.g., "<https://example.atlassian.net>")
+    base_url: String,
+    email: String,
+    api_token: String,
+}
+
+/// Jira issue response (only the fields toki requests)
+#[derive(Debug, Deserialize)]
+struct JiraIssue {
+    key: String,
+    fields: JiraIssueFields,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssueFields {
+    summary: String,
+    /// Atlassian Document Format
+    #[serde(default)]
+    description: Option<Value>,
+    status: JiraStatus,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    project: Option<JiraProject>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraStatus {
+    name: String,
+    #[serde(rename = "statusCategory")]
+    status_category: Option<JiraStatusCategory>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraStatusCategory {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraProject {
+    name: String,
+}
+
+/// Jira add worklog request
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraWorklogRequest {
+    time_spent_seconds: u32,
+    /// Jira only accepts `yyyy-MM-dd'T'HH:mm:ss.SSSZ`
+    started: String,
+    comment: Value,
+}
+
+impl JiraWorklogRequest {
+    fn from_entry(entry: &TimeEntry) -> Self {
+        Self {
+            time_spent_seconds: entry.duration_seconds,
+            started: entry
+                .start_time
+                .format("%Y-%m-%dT%H:%M:%S%.3f%z")
+                .to_string(),
+            comment: adf_paragraph(&format!("{} - {}", entry.category, entry.description)),
+        }
+    }
+}
+
+impl JiraIssue {
+    fn into_work_item(self) -> WorkItemDetails {
+        let fields = self.fields;
+        WorkItemDetails {
+            id: self.key,
+            title: fields.summary,
+            description: fields
+                .description
+                .as_ref()
+                .map(adf_to_text)
+                .filter(|text| !text.is_empty()),
+            // The category (To Do / In Progress / Done) is the same on every workflow
+            status: fields
+                .status
+                .status_category
+                .map_or(fields.status.name, |category| category.name),
+            project: fields.project.map(|p| p.name),
+            workspace: None,
+            labels: fields.labels,
+        }
+    }
+}
+
+/// Wrap plain text in a single-paragraph ADF document
+fn adf_paragraph(text: &str) -> Value {
+    json!({
+        "type": "doc",
+        "version": 1,
+        "content": [{
+            "type": "paragraph",
+            "content": [{ "type": "text", "text": text }],
+        }],
+    })
+}
+
+/// ADF nodes that end a line of plain text
+const ADF_BLOCK_NODES: &[&str] = &[
+    "paragraph",
+    "heading",
+    "codeBlock",
+    "blockquote",
+    "listItem",
+    "tableRow",
+    "rule",
+];
+
+/// Flatten an Atlassian Document Format tree to plain text, one line per block
+#[must_use]
+pub fn adf_to_text(document: &Value) -> String {
+    fn walk(node: &Value, out: &mut String) {
+        let node_type = node.get("type").and_then(Value::as_str).unwrap_or_default();
+        match node_type {
+            "text" => out.push_str(node.get("text").and_then(Value::as_str).unwrap_or_default()),
+            "hardBreak" => out.push('\n'),
+            _ => {}
+        }
+        if let Some(children) = node.get("content").and_then(Value::as_array) {
+            for child in children {
+                walk(child, out);
+            }
+        }
+        if ADF_BLOCK_NODES.contains(&node_type) && !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+
+    let mut text = String::new();
+    walk(document, &mut text);
+    text.trim_end().to_string()
+}
+
+impl JiraClient {
+    /// Create a new Jira Cloud client
+    ///
+    /// # Arguments
+    /// * `base_url` - Site URL (e.g., "<https://example.atlassian.net>")
+    /// * `email` - Atlassian account email
+    /// * `api_token` - API token from <https://id.atlassian.com/manage-profile/security/api-tokens>
+    ///
+    /// # Errors
+    /// Returns an error if the HTTP client cannot be created
+    pub fn new(base_url: &str, email: &str, api_token: &str) -> Result<Self> {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::ACCEPT,
+            header::HeaderValue::from_static("application/json"),
+        );
+        headers.insert(
+            header::USER_AGENT,
+            header::HeaderValue::from_static("toki-time-tracker"),
+        );
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            email: email.to_string(),
+            api_token: api_token.to_string(),
+        })
+    }
+
+    /// Build a REST API v3 URL
+    fn api_url(&self, path: &str) -> String {
+        format!("{}/rest/api/3/{path}", self.base_url)
+    }
+
+    /// Get an issue by key (e.g., "PROJ-123")
+    async fn get_issue(&self, key: &str) -> Result<JiraIssue> {
+        let url = self.api_url(&format!(
+            "issue/{}?fields=summary,description,status,labels,project",
+            urlencoding::encode(key)
+        ));
+        log::debug!("GET {url}");
+
+        self.client
+            .get(&url)
+            .basic_auth(&self.email, Some(&self.api_token))
+            .send_logged("Jira")
+            .await
+            .context("Failed to send request to Jira API")?
+            .ensure_success("Jira")
+            .await?
+            .json()
+            .await
+            .context("Failed to parse Jira issue")
+    }
+
+    /// Log time on an issue as a worklog
+    ///
+    /// # Errors
+    /// Returns an error if the API request fails
+    pub async fn add_worklog(&self, entry: &TimeEntry) -> Result<()> {
+        let url = self.api_url(&format!(
+            "issue/{}/worklog",
+            urlencoding::encode(&entry.work_item_id)
+        ));
+        log::debug!("POST {url}");
+
+        self.client
+            .post(&url)
+            .basic_auth(&self.email, Some(&self.api_token))
+            .json(&JiraWorklogRequest::from_entry(entry))
+            .send_logged("Jira")
+            .await
+            .context("Failed to send request to Jira API")?
+            .ensure_success("Jira")
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ProjectManagementSystem for JiraClient {
+    async fn fetch_work_item(&self, work_item_id: &str) -> Result<WorkItemDetails> {
+        Ok(self.get_issue(work_item_id).await?.into_work_item())
+    }
+
+    async fn add_time_entry(&self, entry: &TimeEntry) -> Result<()> {
+        log::debug!(
+            "Adding worklog to Jira issue {}: {} seconds",
+            entry.work_item_id,
+            entry.duration_seconds
+        );
+        self.add_worklog(entry).await
+    }
+
+    async fn batch_sync(&self, entries: Vec<TimeEntry>) -> Result<SyncReport> {
+        let mut report = SyncReport::new(entries.len());
+
+        for entry in entries {
+            match self.add_time_entry(&entry).await {
+                Ok(()) => report.record_success(),
+                Err(e) => report.record_failure(format!("Issue {}: {e}", entry.work_item_id)),
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn validate_credentials(&self) -> Result<bool> {
+        let response = self
+            .client
+            .get(self.api_url("myself"))
+            .basic_auth(&self.email, Some(&self.api_token))
+            .send_logged("Jira")
+            .await
+            .context("Failed to send request to Jira API")?;
+        Ok(response.status().is_success())
+    }
+
+    fn system_name(&self) -> &'static str {
+        "jira"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_maps_summary_status_category_and_description() {
+        let issue: JiraIssue = serde_json::from_value(json!({
+            "key": "PROJ-7",
+            "fields": {
+                "summary": "Log time to Jira",
+                "status": { "name": "Code Review", "statusCategory": { "name": "In Progress" } },
+                "labels": ["backend"],
+                "project": { "key": "PROJ", "name": "Toki" },
+                "description": {
+                    "type": "doc",
+                    "version": 1,
+                    "content": [
+                        { "type": "heading", "content": [{ "type": "text", "text": "Goal" }] },
+                        { "type": "paragraph", "content": [
+                            { "type": "text", "text": "Create " },
+                            { "type": "text", "text": "worklogs", "marks": [{ "type": "strong" }] },
+                            { "type": "hardBreak" },
+                            { "type": "text", "text": "per block" },
+                        ] },
+                        { "type": "bulletList", "content": [
+                            { "type": "listItem", "content": [
+                                { "type": "paragraph", "content": [{ "type": "text", "text": "one" }] },
+                            ] },
+                            { "type": "listItem", "content": [
+                                { "type": "paragraph", "content": [{ "type": "text", "text": "two" }] },
+                            ] },
+                        ] },
+                    ],
+                },
+            },
+        }))
+        .unwrap();
+
+        let item = issue.into_work_item();
+        assert_eq!(item.id, "PROJ-7");
+        assert_eq!(item.title, "Log time to Jira");
+        assert_eq!(item.status, "In Progress");
+        assert_eq!(item.project.as_deref(), Some("Toki"));
+        assert_eq!(item.labels, ["backend"]);
+        assert_eq!(
+            item.description.as_deref(),
+            Some("Goal\nCreate worklogs\nper block\none\ntwo")
+        );
+    }
+
+    #[test]
+    fn test_worklog_request_uses_jira_timestamp_format() {
+        let start = chrono::DateTime::parse_from_rfc3339("2024-03-01T09:30:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let entry = TimeEntry::new(
+            "PROJ-7".to_string(),
+            start,
+            5400,
+            "Worklog sync".to_string(),
+            "Coding".to_string(),
+        );
+
+        let body = serde_json::to_value(JiraWorklogRequest::from_entry(&entry)).unwrap();
+        assert_eq!(body["timeSpentSeconds"], 5400);
+        assert_eq!(body["started"], "2024-03-01T09:30:00.000+0000");
+        assert_eq!(adf_to_text(&body["comment"]), "Coding - Worklog sync");
+    }
+}
@@ -1,30 +1,41 @@
+pub mod backoff;
 pub mod github;
 pub mod gitlab;
 mod http;
+pub mod jira;
+pub mod linear;
 pub mod notion;
 pub mod plane;
+mod rate_limit;
+mod request_log;
 pub mod traits;
+pub mod trello;
 pub mod webhook;
 
-pub use http::ResponseExt;
+pub use backoff::{retry_async, Backoff};
+pub use http::{ApiError, RequestExt, ResponseExt};
+pub use request_log::{disable_request_log, enable_request_log};
 
 pub use github::GitHubClient;
 pub use gitlab::GitLabClient;
+pub use jira::JiraClient;
+pub use linear::{LinearClient, LinearIssueCandidateData};
 pub use plane::{
     IssueCandidateData, PaginatedResponse, PlaneClient, PlaneProject, PlaneState, PlaneUser,
     PlaneWorkItem, PlaneWorklog, PlaneWorkspace, WorklogSummary,
 };
 pub use traits::{
-    CreateIssueRequest, CreatedIssue, IssueDetails, IssueManagement, IssueState,
-    IssueSyncReport, ProjectManagementSystem, SyncReport, TimeEntry, UpdateIssueRequest,
-    WorkItemDetails,
+    CreateIssueRequest, CreatedIssue, IssueDetails, IssueField, IssueFieldMapping, IssueManagement,
+    IssueState, IssueSyncReport, ProjectManagementSystem, SyncReport, TimeEntry,
+    UpdateIssueRequest, WorkItemDetails, LABELS_TARGET,
 };
+pub use trello::{TrelloCardCandidateData, TrelloClient};
 pub use webhook::{
     verify_webhook_signature, PlaneEventType, PlaneWebhookPayload, WebhookResult, WebhookWorkItem,
-    process_webhook,
+    process_webhook, process_webhook_once, post_to_slack, ReplayGuard,
 };
 pub use notion::{
     NotionClient, NotionDatabase, NotionPage, NotionBlock, NotionPropertyValue,
     NotionPropertyUpdate, NotionIssueCandidateData, NotionPaginatedResponse,
-    PropertyMapping, PropertyMappingConfig,
+    PageQuery, PagedResults, PaginationMode, PropertyMapping, PropertyMappingConfig,
 };
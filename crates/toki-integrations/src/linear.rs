@@ -0,0 +1,491 @@
+//! Linear GraphQL API client
+//!
+//! Implements `ProjectManagementSystem` for Linear and lists team issues for
+//! AI matching. Linear has no worklogs, so tracked time is posted as an issue
+//! comment, or added to a numeric issue field when one is configured.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::{header, Client};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::http::{RequestExt, ResponseExt};
+use crate::traits::{ProjectManagementSystem, SyncReport, TimeEntry, WorkItemDetails};
+
+/// Linear GraphQL endpoint
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+
+/// Issues requested per page when listing a team's issues
+const ISSUES_PAGE_SIZE: u32 = 100;
+
+/// Issue fields toki reads
+const ISSUE_FIELDS: &str = "id identifier title description url \
+    state { name } labels { nodes { name } } team { name }";
+
+/// Linear API client
+pub struct LinearClient {
+    client: Client,
+    api_url: String,
+    /// Numeric issue field that accumulates tracked hours instead of comments
+    time_field: Option<String>,
+}
+
+/// GraphQL response envelope
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+impl<T> GraphQlResponse<T> {
+    /// Surface errors Linear reports in the body of a 200 response
+    fn into_data(self) -> Result<T> {
+        if !self.errors.is_empty() {
+            let messages: Vec<&str> = self.errors.iter().map(|e| e.message.as_str()).collect();
+            anyhow::bail!("Linear API error: {}", messages.join("; "));
+        }
+        self.data
+            .ok_or_else(|| anyhow::anyhow!("Linear API returned no data"))
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct GraphQlRequest<'a> {
+    query: &'a str,
+    variables: Value,
+}
+
+/// Linear issue
+#[derive(Debug, Deserialize)]
+struct LinearIssue {
+    id: String,
+    identifier: String,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    url: String,
+    state: LinearState,
+    labels: Nodes<LinearLabel>,
+    #[serde(default)]
+    team: Option<LinearTeam>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LinearState {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LinearLabel {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LinearTeam {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IssuePage {
+    nodes: Vec<LinearIssue>,
+    page_info: PageInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueData {
+    issue: LinearIssue,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamIssuesData {
+    team: TeamIssues,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamIssues {
+    issues: IssuePage,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MutationResult {
+    success: bool,
+}
+
+/// Data for creating a local `IssueCandidate` entry from a Linear issue
+#[derive(Debug, Clone, Serialize)]
+pub struct LinearIssueCandidateData {
+    pub external_id: String, // Identifier, e.g. "ENG-123"
+    pub external_system: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub team_id: String,
+    pub issue_id: String, // Linear UUID, needed for mutations
+    pub labels: Vec<String>,
+    pub url: String,
+}
+
+impl LinearIssue {
+    fn label_names(&self) -> Vec<String> {
+        self.labels.nodes.iter().map(|l| l.name.clone()).collect()
+    }
+
+    fn to_candidate_data(&self, team_id: &str) -> LinearIssueCandidateData {
+        LinearIssueCandidateData {
+            external_id: self.identifier.clone(),
+            external_system: "linear".to_string(),
+            title: self.title.clone(),
+            description: self.description.clone(),
+            status: self.state.name.clone(),
+            team_id: team_id.to_string(),
+            issue_id: self.id.clone(),
+            labels: self.label_names(),
+            url: self.url.clone(),
+        }
+    }
+
+    fn into_work_item(self) -> WorkItemDetails {
+        let labels = self.label_names();
+        WorkItemDetails {
+            id: self.identifier,
+            title: self.title,
+            description: self.description,
+            status: self.state.name,
+            project: self.team.map(|t| t.name),
+            workspace: None,
+            labels,
+        }
+    }
+}
+
+/// Comment recording tracked time on an issue or card
+pub(crate) fn time_entry_comment(entry: &TimeEntry) -> String {
+    let hours = entry.duration_seconds / 3600;
+    let minutes = (entry.duration_seconds % 3600) / 60;
+    let duration = match (hours, minutes) {
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h {m}m"),
+    };
+    format!(
+        "Tracked {duration} ({}) on {}: {}",
+        entry.category,
+        entry.start_time.format("%Y-%m-%d"),
+        entry.description
+    )
+}
+
+/// Check that a configured field name is a plain GraphQL identifier
+fn validate_field_name(field: &str) -> Result<()> {
+    let valid = field.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        anyhow::bail!("Invalid Linear field name: {field}");
+    }
+    Ok(())
+}
+
+impl LinearClient {
+    /// Create a new Linear client
+    ///
+    /// # Arguments
+    /// * `api_key` - Personal API key (Settings > Security & access > API keys)
+    ///
+    /// # Errors
+    /// Returns an error if the HTTP client cannot be created
+    pub fn new(api_key: &str) -> Result<Self> {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            header::HeaderValue::from_str(api_key).context("Invalid API key format")?,
+        );
+        headers.insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("application/json"),
+        );
+        headers.insert(
+            header::USER_AGENT,
+            header::HeaderValue::from_static("toki-time-tracker"),
+        );
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            api_url: LINEAR_API_URL.to_string(),
+            time_field: None,
+        })
+    }
+
+    /// Add tracked hours to a numeric issue field instead of posting comments
+    ///
+    /// # Errors
+    /// Returns an error if the field name is not a valid GraphQL identifier
+    pub fn with_time_field(mut self, field: &str) -> Result<Self> {
+        validate_field_name(field)?;
+        self.time_field = Some(field.to_string());
+        Ok(self)
+    }
+
+    /// Run a GraphQL query or mutation
+    async fn graphql<T: DeserializeOwned>(&self, query: &str, variables: Value) -> Result<T> {
+        let response: GraphQlResponse<T> = self
+            .client
+            .post(&self.api_url)
+            .json(&GraphQlRequest { query, variables })
+            .send_logged("Linear")
+            .await
+            .context("Failed to send request to Linear API")?
+            .ensure_success("Linear")
+            .await?
+            .json()
+            .await
+            .context("Failed to parse Linear API response")?;
+        response.into_data()
+    }
+
+    /// Get an issue by identifier (e.g., "ENG-123") or UUID
+    async fn get_issue(&self, id: &str) -> Result<LinearIssue> {
+        let query = format!("query Issue($id: String!) {{ issue(id: $id) {{ {ISSUE_FIELDS} }} }}");
+        let data: IssueData = self.graphql(&query, json!({ "id": id })).await?;
+        Ok(data.issue)
+    }
+
+    /// List a team's open issues (not completed or canceled) for AI matching
+    ///
+    /// # Errors
+    /// Returns an error if the API request fails or Linear reports an error
+    pub async fn list_issues(&self, team_id: &str) -> Result<Vec<LinearIssueCandidateData>> {
+        let query = format!(
+            "query TeamIssues($teamId: String!, $after: String) {{
+                team(id: $teamId) {{
+                    issues(first: {ISSUES_PAGE_SIZE}, after: $after,
+                           filter: {{ state: {{ type: {{ nin: [\"completed\", \"canceled\"] }} }} }}) {{
+                        nodes {{ {ISSUE_FIELDS} }}
+                        pageInfo {{ hasNextPage endCursor }}
+                    }}
+                }}
+            }}"
+        );
+
+        let mut issues = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let data: TeamIssuesData = self
+                .graphql(&query, json!({ "teamId": team_id, "after": after }))
+                .await?;
+            let page = data.team.issues;
+            issues.extend(page.nodes.iter().map(|i| i.to_candidate_data(team_id)));
+
+            match page.page_info.end_cursor {
+                Some(cursor) if page.page_info.has_next_page => after = Some(cursor),
+                _ => break,
+            }
+        }
+
+        log::debug!(
+            "Fetched {} open issues from Linear team {team_id}",
+            issues.len()
+        );
+        Ok(issues)
+    }
+
+    /// Post a comment on an issue
+    async fn create_comment(&self, issue_id: &str, body: &str) -> Result<()> {
+        let query = "mutation Comment($input: CommentCreateInput!) {
+            commentCreate(input: $input) { success }
+        }";
+        let data: Value = self
+            .graphql(
+                query,
+                json!({ "input": { "issueId": issue_id, "body": body } }),
+            )
+            .await?;
+        Self::ensure_mutation_success(&data, "commentCreate")
+    }
+
+    /// Add tracked hours to the configured numeric field
+    async fn add_to_time_field(&self, issue_id: &str, field: &str, seconds: u32) -> Result<()> {
+        let query = format!("query IssueTime($id: String!) {{ issue(id: $id) {{ {field} }} }}");
+        let data: Value = self.graphql(&query, json!({ "id": issue_id })).await?;
+        let current = data["issue"][field].as_f64().unwrap_or(0.0);
+        let hours = ((current + f64::from(seconds) / 3600.0) * 100.0).round() / 100.0;
+
+        let mutation = "mutation UpdateIssue($id: String!, $input: IssueUpdateInput!) {
+            issueUpdate(id: $id, input: $input) { success }
+        }";
+        let data: Value = self
+            .graphql(
+                mutation,
+                json!({ "id": issue_id, "input": { field: hours } }),
+            )
+            .await?;
+        Self::ensure_mutation_success(&data, "issueUpdate")
+    }
+
+    fn ensure_mutation_success(data: &Value, mutation: &str) -> Result<()> {
+        let result: MutationResult = serde_json::from_value(data[mutation].clone())
+            .with_context(|| format!("Unexpected {mutation} response"))?;
+        if !result.success {
+            anyhow::bail!("Linear {mutation} did not succeed");
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ProjectManagementSystem for LinearClient {
+    async fn fetch_work_item(&self, work_item_id: &str) -> Result<WorkItemDetails> {
+        Ok(self.get_issue(work_item_id).await?.into_work_item())
+    }
+
+    async fn add_time_entry(&self, entry: &TimeEntry) -> Result<()> {
+        // Mutations need the issue's UUID, not its identifier
+        let issue = self
+            .get_issue(&entry.work_item_id)
+            .await
+            .context("Failed to find issue for time entry")?;
+
+        log::debug!(
+            "Adding time to Linear issue {}: {} seconds",
+            issue.identifier,
+            entry.duration_seconds
+        );
+
+        match &self.time_field {
+            Some(field) => {
+                self.add_to_time_field(&issue.id, field, entry.duration_seconds)
+                    .await
+            }
+            None => {
+                self.create_comment(&issue.id, &time_entry_comment(entry))
+                    .await
+            }
+        }
+    }
+
+    async fn batch_sync(&self, entries: Vec<TimeEntry>) -> Result<SyncReport> {
+        let mut report = SyncReport::new(entries.len());
+
+        for entry in entries {
+            match self.add_time_entry(&entry).await {
+                Ok(()) => report.record_success(),
+                Err(e) => report.record_failure(format!("Issue {}: {e}", entry.work_item_id)),
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn validate_credentials(&self) -> Result<bool> {
+        let viewer: Result<Value> = self.graphql("query { viewer { id } }", json!({})).await;
+        Ok(viewer.is_ok())
+    }
+
+    fn system_name(&self) -> &'static str {
+        "linear"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue_json() -> Value {
+        json!({
+            "id": "9cfb482a-81e3-4154-b5b9-2c805e70a02d",
+            "identifier": "ENG-123",
+            "title": "Sync worklogs",
+            "description": "Post tracked time",
+            "url": "https://linear.app/acme/issue/ENG-123",
+            "state": { "name": "In Progress" },
+            "labels": { "nodes": [{ "name": "backend" }] },
+            "team": { "name": "Engineering" },
+        })
+    }
+
+    #[test]
+    fn test_graphql_errors_surface_as_error() {
+        let response: GraphQlResponse<IssueData> = serde_json::from_value(json!({
+            "data": null,
+            "errors": [
+                { "message": "Entity not found: Issue" },
+                { "message": "Argument Validation Error" },
+            ],
+        }))
+        .unwrap();
+
+        let err = response.into_data().unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Linear API error: Entity not found: Issue; Argument Validation Error"
+        );
+    }
+
+    #[test]
+    fn test_issue_maps_to_work_item_and_candidate() {
+        let response: GraphQlResponse<IssueData> =
+            serde_json::from_value(json!({ "data": { "issue": issue_json() } })).unwrap();
+        let issue = response.into_data().unwrap().issue;
+
+        let candidate = issue.to_candidate_data("team-1");
+        assert_eq!(candidate.external_id, "ENG-123");
+        assert_eq!(candidate.external_system, "linear");
+        assert_eq!(candidate.issue_id, "9cfb482a-81e3-4154-b5b9-2c805e70a02d");
+        assert_eq!(candidate.labels, ["backend"]);
+
+        let item = issue.into_work_item();
+        assert_eq!(item.id, "ENG-123");
+        assert_eq!(item.status, "In Progress");
+        assert_eq!(item.project.as_deref(), Some("Engineering"));
+    }
+
+    #[test]
+    fn test_time_entry_comment_and_field_names() {
+        let start = chrono::DateTime::parse_from_rfc3339("2024-03-01T09:30:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let entry = TimeEntry::new(
+            "ENG-123".to_string(),
+            start,
+            5400,
+            "Worklog sync".to_string(),
+            "Coding".to_string(),
+        );
+        assert_eq!(
+            time_entry_comment(&entry),
+            "Tracked 1h 30m (Coding) on 2024-03-01: Worklog sync"
+        );
+
+        let client = LinearClient::new("lin_api_test").unwrap();
+        assert!(client.with_time_field("trackedHours").is_ok());
+        let client = LinearClient::new("lin_api_test").unwrap();
+        assert!(client.with_time_field("estimate } }").is_err());
+    }
+}
@@ -3,51 +3,152 @@
 //! This module provides the HTTP client for interacting with the Notion API.
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use tokio::sync::Mutex;
 
-use crate::http::ResponseExt;
-use super::schema::{PropertyMapping, PropertyMappingConfig, NOTION_API_VERSION, NOTION_BASE_URL, RATE_LIMIT_INTERVAL_MS, TIME_CONVENTIONS};
+use crate::backoff::{retry_async, Backoff};
+use crate::http::{ApiError, RequestExt, ResponseExt};
+use crate::rate_limit::RateLimiter;
+use crate::traits::{
+    warn_dropped_fields, CreateIssueRequest, CreatedIssue, IssueField, IssueFieldMapping,
+    IssueState,
+};
+use super::schema::{NotionApiVersion, PropertyMapping, PropertyMappingConfig, NOTION_BASE_URL, RATE_LIMIT_INTERVAL_MS, TIME_CONVENTIONS};
 use super::types::{
-    NotionBlock, NotionDatabase, NotionIssueCandidateData, NotionPage,
-    NotionPaginatedResponse, NotionPropertyUpdate, NotionPropertyValue,
-    PropertyUpdateValue, RichTextInput, SearchResponse, SelectInput, TextContent,
-    UpdatePageRequest,
+    DataSourceSearchResponse, NotionBlock, NotionDataSource, NotionDatabase,
+    NotionIssueCandidateData, NotionPage, NotionPaginatedResponse, NotionPropertyUpdate,
+    NotionPropertyValue, PropertyUpdateValue, RichTextInput, SearchResponse, SelectInput,
+    TextContent, UpdatePageRequest,
 };
 
 // ============================================================================
-// Rate Limiter
+// Retries
 // ============================================================================
 
-/// Simple rate limiter for Notion API
-pub(crate) struct RateLimiter {
-    last_request: Mutex<Instant>,
-    interval: Duration,
+/// First delay before retrying a rate-limited (429) or failed (5xx) request
+const RETRY_BASE_DELAY_MS: u64 = 1000;
+
+/// Retries per request before the error is returned
+const MAX_RETRIES: u32 = 3;
+
+/// Longest wait before a retry, also the cap on a `Retry-After` from Notion
+const MAX_RETRY_DELAY_SECS: u64 = 30;
+
+// ============================================================================
+// Pagination
+// ============================================================================
+
+/// What a paginated query does when one of its pages fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaginationMode {
+    /// Fail the whole query
+    #[default]
+    Strict,
+    /// Keep the pages fetched so far and report where the query stopped
+    Lenient,
 }
 
-impl RateLimiter {
-    pub fn new(interval_ms: u64) -> Self {
-        Self {
-            last_request: Mutex::new(
-                Instant::now()
-                    .checked_sub(Duration::from_millis(interval_ms))
-                    .unwrap_or_else(Instant::now),
-            ),
-            interval: Duration::from_millis(interval_ms),
-        }
+/// How far before the requested time an edited-since query starts
+///
+/// Notion truncates `last_edited_time` to the minute, so a page edited in the
+/// same minute as the last one seen can carry an earlier timestamp.
+pub const EDITED_SINCE_MARGIN_SECS: i64 = 120;
+
+/// Which pages of a database a paginated query fetches
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PageQuery<'a> {
+    /// Whether a failing page aborts the query or keeps the pages so far
+    pub mode: PaginationMode,
+    /// Cursor to resume an earlier, interrupted query from
+    pub start_cursor: Option<&'a str>,
+    /// Only pages edited at or after this time, less [`EDITED_SINCE_MARGIN_SECS`]
+    pub edited_since: Option<DateTime<Utc>>,
+}
+
+/// Results of a paginated query, possibly cut short in lenient mode
+#[derive(Debug)]
+pub struct PagedResults<T> {
+    pub items: Vec<T>,
+    /// Cursor of the page that failed; pass it back to resume the query
+    pub resume_cursor: Option<String>,
+    /// Why the query stopped early
+    pub error: Option<String>,
+}
+
+impl<T> PagedResults<T> {
+    /// Whether every page was fetched
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none()
     }
+}
+
+/// Follow `next_cursor` from `start_cursor` until the last page
+///
+/// In lenient mode an error after the first page ends the query with the results
+/// so far. An error on the first page always fails, since there is nothing to keep.
+pub(crate) async fn collect_pages<T, F, Fut>(
+    mode: PaginationMode,
+    start_cursor: Option<String>,
+    mut fetch: F,
+) -> Result<PagedResults<T>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<NotionPaginatedResponse<T>>>,
+{
+    let mut items = Vec::new();
+    let mut cursor = start_cursor;
+    let mut pages = 0;
+
+    loop {
+        let response = match fetch(cursor.clone()).await {
+            Ok(response) => response,
+            Err(e) if mode == PaginationMode::Lenient && pages > 0 => {
+                log::warn!(
+                    "Notion query stopped after {pages} pages ({} results): {e:#}",
+                    items.len()
+                );
+                return Ok(PagedResults {
+                    items,
+                    resume_cursor: cursor,
+                    error: Some(format!("{e:#}")),
+                });
+            }
+            Err(e) => return Err(e),
+        };
+        pages += 1;
+        items.extend(response.results);
 
-    pub async fn wait(&self) {
-        let mut last = self.last_request.lock().await;
-        let elapsed = last.elapsed();
-        if elapsed < self.interval {
-            tokio::time::sleep(self.interval - elapsed).await;
+        match response.next_cursor {
+            Some(next) if response.has_more => cursor = Some(next),
+            _ => break,
         }
-        *last = Instant::now();
     }
+
+    Ok(PagedResults {
+        items,
+        resume_cursor: None,
+        error: None,
+    })
+}
+
+/// Body of a request creating an issue page under `parent`
+///
+/// Returns the request fields the mapping could not place alongside it.
+pub(crate) fn issue_page_body(
+    parent: serde_json::Value,
+    database: &NotionDatabase,
+    request: &CreateIssueRequest,
+    mapping: &IssueFieldMapping,
+) -> Result<(serde_json::Value, Vec<IssueField>)> {
+    let (properties, dropped) = database.issue_page_properties(request, mapping)?;
+    let body = serde_json::json!({ "parent": parent, "properties": properties });
+    Ok((body, dropped))
 }
 
 // ============================================================================
@@ -59,15 +160,24 @@ pub struct NotionClient {
     api_key: String,
     client: reqwest::Client,
     rate_limiter: Arc<RateLimiter>,
-    /// Mapping from `external_id` (short ID) to full page ID
+    /// Mapping from `external_id` (short ID, workspace-prefixed when scoped) to full page ID
     page_id_cache: Arc<Mutex<HashMap<String, String>>>,
     /// Configured time property name (overrides auto-detection)
     time_property_override: Arc<Mutex<Option<String>>>,
+    /// API version sent with database, page, and search requests
+    api_version: NotionApiVersion,
+    /// Data source of each database seen so far (`None` for legacy databases)
+    data_source_ids: Arc<Mutex<HashMap<String, Option<String>>>>,
+    /// Where `CreateIssueRequest` fields go (detected from the schema when unset)
+    field_mapping: Option<IssueFieldMapping>,
 }
 
 impl NotionClient {
     /// Create a new Notion client
     ///
+    /// The API version comes from the `NOTION_API_VERSION` environment variable
+    /// when set, otherwise the legacy version is used.
+    ///
     /// # Arguments
     /// * `api_key` - Notion integration token (starts with `secret_`)
     ///
@@ -86,9 +196,26 @@ impl NotionClient {
             rate_limiter: Arc::new(RateLimiter::new(RATE_LIMIT_INTERVAL_MS)),
             page_id_cache: Arc::new(Mutex::new(HashMap::new())),
             time_property_override: Arc::new(Mutex::new(None)),
+            api_version: NotionApiVersion::from_env(),
+            data_source_ids: Arc::new(Mutex::new(HashMap::new())),
+            field_mapping: None,
         })
     }
 
+    /// Use a specific Notion API version
+    #[must_use]
+    pub fn with_api_version(mut self, api_version: NotionApiVersion) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    /// Use a fixed mapping of issue request fields to database properties
+    #[must_use]
+    pub fn with_field_mapping(mut self, field_mapping: IssueFieldMapping) -> Self {
+        self.field_mapping = Some(field_mapping);
+        self
+    }
+
     /// Create a new Notion client with a configured time property
     ///
     /// # Arguments
@@ -118,55 +245,24 @@ impl NotionClient {
 
     /// Make an authenticated GET request with rate limiting
     pub(crate) async fn get<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T> {
-        self.rate_limiter.wait().await;
-
         log::debug!("GET {url}");
-
-        let response = self
-            .client
-            .get(url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Notion-Version", NOTION_API_VERSION)
-            .header("Content-Type", "application/json")
-            .send()
-            .await
-            .context("Failed to send request to Notion API")?
-            .ensure_success("Notion")
-            .await?;
-
-        response
-            .json()
-            .await
-            .context("Failed to parse Notion API response")
+        self.send_with_retry(self.api_version, ApiError::is_transient_error, || {
+            self.client.get(url)
+        })
+        .await
     }
 
-    /// Make an authenticated POST request with rate limiting
+    /// Make an authenticated read-only POST request (query, search) with rate limiting
     pub(crate) async fn post<T: for<'de> Deserialize<'de>, B: Serialize>(
         &self,
         url: &str,
         body: &B,
     ) -> Result<T> {
-        self.rate_limiter.wait().await;
-
         log::debug!("POST {url}");
-
-        let response = self
-            .client
-            .post(url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Notion-Version", NOTION_API_VERSION)
-            .header("Content-Type", "application/json")
-            .json(body)
-            .send()
-            .await
-            .context("Failed to send request to Notion API")?
-            .ensure_success("Notion")
-            .await?;
-
-        response
-            .json()
-            .await
-            .context("Failed to parse Notion API response")
+        self.send_with_retry(self.api_version, ApiError::is_transient_error, || {
+            self.client.post(url).json(body)
+        })
+        .await
     }
 
     /// Make an authenticated PATCH request with rate limiting
@@ -175,27 +271,49 @@ impl NotionClient {
         url: &str,
         body: &B,
     ) -> Result<T> {
-        self.rate_limiter.wait().await;
-
         log::debug!("PATCH {url}");
+        self.send_with_retry(self.api_version, ApiError::is_unprocessed_error, || {
+            self.client.patch(url).json(body)
+        })
+        .await
+    }
 
-        let response = self
-            .client
-            .patch(url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Notion-Version", NOTION_API_VERSION)
-            .header("Content-Type", "application/json")
-            .json(body)
-            .send()
-            .await
-            .context("Failed to send request to Notion API")?
-            .ensure_success("Notion")
-            .await?;
-
-        response
-            .json()
-            .await
-            .context("Failed to parse Notion API response")
+    /// Send a request built by `build`, retrying failed attempts that `is_retriable` accepts
+    ///
+    /// Reads pass [`ApiError::is_transient_error`]; writes pass
+    /// [`ApiError::is_unprocessed_error`] so a timed-out write is not repeated.
+    async fn send_with_retry<T: for<'de> Deserialize<'de>>(
+        &self,
+        api_version: NotionApiVersion,
+        is_retriable: fn(&anyhow::Error) -> bool,
+        build: impl Fn() -> reqwest::RequestBuilder,
+    ) -> Result<T> {
+        let build = &build;
+        retry_async(
+            Backoff::new(Duration::from_millis(RETRY_BASE_DELAY_MS))
+                .max_delay(Duration::from_secs(MAX_RETRY_DELAY_SECS))
+                .max_retries(MAX_RETRIES),
+            is_retriable,
+            move || async move {
+                self.rate_limiter.wait().await;
+
+                let response = build()
+                    .header("Authorization", format!("Bearer {}", self.api_key))
+                    .header("Notion-Version", api_version.header())
+                    .header("Content-Type", "application/json")
+                    .send_logged("Notion")
+                    .await
+                    .context("Failed to send request to Notion API")?
+                    .ensure_success("Notion")
+                    .await?;
+
+                response
+                    .json()
+                    .await
+                    .context("Failed to parse Notion API response")
+            },
+        )
+        .await
     }
 
     // ========================================================================
@@ -204,20 +322,88 @@ impl NotionClient {
 
     /// Get a database by ID
     ///
+    /// For databases in the newer data-source shape, the schema of the
+    /// database's data source is fetched and returned under the database id.
+    ///
     /// # Errors
     ///
     /// Returns an error if the API request fails or database not found
     pub async fn get_database(&self, database_id: &str) -> Result<NotionDatabase> {
         let clean_id = Self::clean_id(database_id);
         let url = format!("{NOTION_BASE_URL}/databases/{clean_id}");
-        self.get(&url).await
+        let database: NotionDatabase = self.get(&url).await?;
+
+        let data_source_id = database.data_source_id().map(String::from);
+        self.data_source_ids
+            .lock()
+            .await
+            .insert(clean_id, data_source_id.clone());
+        let Some(data_source_id) = data_source_id else {
+            return Ok(database);
+        };
+
+        log::debug!(
+            "Notion database {} uses data source {data_source_id}",
+            database.id
+        );
+        let mut resolved = self.get_data_source(&data_source_id).await?.into_database();
+        resolved.id = database.id;
+        resolved.url = database.url;
+        if resolved.title.is_empty() {
+            resolved.title = database.title;
+        }
+        Ok(resolved)
+    }
+
+    /// Get a data source by ID (newer API versions)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or data source not found
+    pub async fn get_data_source(&self, data_source_id: &str) -> Result<NotionDataSource> {
+        let clean_id = Self::clean_id(data_source_id);
+        let url = format!("{NOTION_BASE_URL}/data_sources/{clean_id}");
+        log::debug!("GET {url}");
+        self.send_with_retry(
+            NotionApiVersion::DataSources,
+            ApiError::is_transient_error,
+            || self.client.get(&url),
+        )
+            .await
+    }
+
+    /// Data source to query for a database, or `None` for a legacy database
+    ///
+    /// Fetches the database the first time it is seen.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be fetched
+    pub async fn resolve_data_source_id(&self, database_id: &str) -> Result<Option<String>> {
+        let clean_id = Self::clean_id(database_id);
+        if let Some(known) = self.data_source_ids.lock().await.get(&clean_id) {
+            return Ok(known.clone());
+        }
+        // Fetching the database records its data source
+        self.get_database(database_id).await?;
+        Ok(self
+            .data_source_ids
+            .lock()
+            .await
+            .get(&clean_id)
+            .cloned()
+            .flatten())
     }
 
     /// Query pages in a database
     ///
+    /// Databases in the data-source shape are queried through their data source;
+    /// legacy databases are queried directly.
+    ///
     /// # Arguments
     /// * `database_id` - Database UUID
     /// * `cursor` - Optional pagination cursor
+    /// * `edited_since` - Only return pages edited at or after this time (see [`Self::query_body`])
     ///
     /// # Errors
     ///
@@ -226,16 +412,51 @@ impl NotionClient {
         &self,
         database_id: &str,
         cursor: Option<&str>,
+        edited_since: Option<DateTime<Utc>>,
     ) -> Result<NotionPaginatedResponse<NotionPage>> {
+        let body = Self::query_body(cursor, edited_since);
+
+        if let Some(data_source_id) = self.resolve_data_source_id(database_id).await? {
+            let clean_id = Self::clean_id(&data_source_id);
+            let url = format!("{NOTION_BASE_URL}/data_sources/{clean_id}/query");
+            log::debug!("POST {url}");
+            return self
+                .send_with_retry(
+                    NotionApiVersion::DataSources,
+                    ApiError::is_transient_error,
+                    || self.client.post(&url).json(&body),
+                )
+                .await;
+        }
+
         let clean_id = Self::clean_id(database_id);
         let url = format!("{NOTION_BASE_URL}/databases/{clean_id}/query");
+        self.post(&url, &body).await
+    }
 
+    /// Body of a database query
+    ///
+    /// With `edited_since`, only pages whose `last_edited_time` is on or after it,
+    /// less [`EDITED_SINCE_MARGIN_SECS`], are returned.
+    #[must_use]
+    pub fn query_body(
+        cursor: Option<&str>,
+        edited_since: Option<DateTime<Utc>>,
+    ) -> serde_json::Value {
         let mut body = serde_json::json!({});
         if let Some(c) = cursor {
             body["start_cursor"] = serde_json::Value::String(c.to_string());
         }
-
-        self.post(&url, &body).await
+        if let Some(since) = edited_since {
+            let from = since - chrono::Duration::seconds(EDITED_SINCE_MARGIN_SECS);
+            body["filter"] = serde_json::json!({
+                "timestamp": "last_edited_time",
+                "last_edited_time": {
+                    "on_or_after": from.to_rfc3339_opts(SecondsFormat::Secs, true),
+                },
+            });
+        }
+        body
     }
 
     /// Query all pages in a database (handles pagination automatically)
@@ -244,14 +465,63 @@ impl NotionClient {
     ///
     /// Returns an error if any API request fails
     pub async fn query_database_all(&self, database_id: &str) -> Result<Vec<NotionPage>> {
-        let mut all_pages = Vec::new();
+        let pages = self
+            .query_database_pages(database_id, PageQuery::default())
+            .await?;
+        Ok(pages.items)
+    }
+
+    /// Query pages in a database from the query's start cursor to the end
+    ///
+    /// In [`PaginationMode::Lenient`], a failing page ends the query with the pages
+    /// fetched so far and the cursor to resume from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the first request fails, or any request in strict mode
+    pub async fn query_database_pages(
+        &self,
+        database_id: &str,
+        query: PageQuery<'_>,
+    ) -> Result<PagedResults<NotionPage>> {
+        let start_cursor = query.start_cursor.map(String::from);
+        collect_pages(query.mode, start_cursor, |cursor| async move {
+            self.query_database(database_id, cursor.as_deref(), query.edited_since)
+                .await
+        })
+        .await
+    }
+
+    /// List databases the integration has access to (handles pagination)
+    ///
+    /// With the data-source API version, search returns data sources; each is
+    /// listed as its parent database with the data source's schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails
+    pub async fn list_databases(&self) -> Result<Vec<NotionDatabase>> {
+        if self.api_version == NotionApiVersion::DataSources {
+            return self.list_data_sources().await;
+        }
+        let url = format!("{NOTION_BASE_URL}/search");
+        let mut all_databases = Vec::new();
         let mut cursor: Option<String> = None;
 
         loop {
-            let response = self
-                .query_database(database_id, cursor.as_deref())
-                .await?;
-            all_pages.extend(response.results);
+            let mut body = serde_json::json!({
+                "filter": {
+                    "value": "database",
+                    "property": "object"
+                }
+            });
+
+            if let Some(ref c) = cursor {
+                body["start_cursor"] = serde_json::Value::String(c.clone());
+            }
+
+            let response: SearchResponse = self.post(&url, &body).await?;
+            all_databases.extend(response.results);
 
             if response.has_more {
                 cursor = response.next_cursor;
@@ -260,15 +530,11 @@ impl NotionClient {
             }
         }
 
-        Ok(all_pages)
+        Ok(all_databases)
     }
 
-    /// List databases the integration has access to (handles pagination)
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the API request fails
-    pub async fn list_databases(&self) -> Result<Vec<NotionDatabase>> {
+    /// List data sources as databases (newer API versions, handles pagination)
+    async fn list_data_sources(&self) -> Result<Vec<NotionDatabase>> {
         let url = format!("{NOTION_BASE_URL}/search");
         let mut all_databases = Vec::new();
         let mut cursor: Option<String> = None;
@@ -276,7 +542,7 @@ impl NotionClient {
         loop {
             let mut body = serde_json::json!({
                 "filter": {
-                    "value": "database",
+                    "value": "data_source",
                     "property": "object"
                 }
             });
@@ -285,8 +551,13 @@ impl NotionClient {
                 body["start_cursor"] = serde_json::Value::String(c.clone());
             }
 
-            let response: SearchResponse = self.post(&url, &body).await?;
-            all_databases.extend(response.results);
+            let response: DataSourceSearchResponse = self.post(&url, &body).await?;
+            all_databases.extend(
+                response
+                    .results
+                    .into_iter()
+                    .map(NotionDataSource::into_database),
+            );
 
             if response.has_more {
                 cursor = response.next_cursor;
@@ -313,6 +584,56 @@ impl NotionClient {
         self.get(&url).await
     }
 
+    /// Create an issue as a page in a database
+    ///
+    /// Request fields are written to the properties named by the configured
+    /// field mapping, or the mapping detected from the database schema.
+    /// Fields the database cannot hold are dropped with a warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database has no title property or an API request fails
+    pub async fn create_issue(
+        &self,
+        database_id: &str,
+        request: &CreateIssueRequest,
+    ) -> Result<CreatedIssue> {
+        let database = self.get_database(database_id).await?;
+        let mapping = self
+            .field_mapping
+            .clone()
+            .unwrap_or_else(|| database.issue_field_mapping());
+        let data_source_id = self.resolve_data_source_id(database_id).await?;
+        let (parent, version) = match data_source_id {
+            Some(data_source_id) => (
+                serde_json::json!({ "type": "data_source_id", "data_source_id": data_source_id }),
+                NotionApiVersion::DataSources,
+            ),
+            None => (
+                serde_json::json!({ "database_id": Self::clean_id(database_id) }),
+                self.api_version,
+            ),
+        };
+        let (body, dropped) = issue_page_body(parent, &database, request, &mapping)?;
+        warn_dropped_fields("Notion", &dropped);
+
+        let url = format!("{NOTION_BASE_URL}/pages");
+        log::debug!("POST {url}");
+        let page: NotionPage = self
+            .send_with_retry(version, ApiError::is_unprocessed_error, || {
+                self.client.post(&url).json(&body)
+            })
+            .await?;
+
+        Ok(CreatedIssue {
+            id: page.id,
+            number: 0, // Notion pages have no issue number
+            url: page.url.unwrap_or_default(),
+            title: request.title.clone(),
+            state: IssueState::Open,
+        })
+    }
+
     /// Get blocks (content) of a page
     ///
     /// # Arguments
@@ -447,12 +768,17 @@ impl NotionClient {
         id.replace('-', "")
     }
 
-    /// Generate external ID from database and page IDs
+    /// Generate external ID from workspace, database and page IDs
     ///
-    /// Format: `{db_prefix_4}-{page_prefix_8}`
-    /// Example: `ab12-f7e3c2a1`
+    /// Format: `{db_prefix_4}-{page_prefix_8}`, or `{workspace}/{db_prefix_4}-{page_prefix_8}`
+    /// when a workspace is given, so truncated ids from different workspaces can't collide.
+    /// Example: `ab12-f7e3c2a1`, `acme/ab12-f7e3c2a1`
     #[must_use]
-    pub fn generate_external_id(database_id: &str, page_id: &str) -> String {
+    pub fn generate_external_id(
+        workspace: Option<&str>,
+        database_id: &str,
+        page_id: &str,
+    ) -> String {
         let db_clean = Self::clean_id(database_id);
         let page_clean = Self::clean_id(page_id);
 
@@ -468,7 +794,21 @@ impl NotionClient {
             &page_clean
         };
 
-        format!("{db_prefix}-{page_prefix}")
+        match workspace.map(Self::workspace_discriminator) {
+            Some(workspace) if !workspace.is_empty() => {
+                format!("{workspace}/{db_prefix}-{page_prefix}")
+            }
+            _ => format!("{db_prefix}-{page_prefix}"),
+        }
+    }
+
+    /// Workspace name or ID reduced to lowercase letters and digits
+    fn workspace_discriminator(workspace: &str) -> String {
+        workspace
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect()
     }
 
     /// Cache a page ID mapping for later lookup
@@ -477,6 +817,16 @@ impl NotionClient {
         cache.insert(external_id.to_string(), full_page_id.to_string());
     }
 
+    /// Load stored external ID -> page ID mappings into the cache
+    ///
+    /// Lets work items be resolved right after startup instead of only once a
+    /// sync has visited their pages. Returns the number of mappings loaded.
+    pub async fn preload_page_ids(&self, page_ids: HashMap<String, String>) -> usize {
+        let count = page_ids.len();
+        self.page_id_cache.lock().await.extend(page_ids);
+        count
+    }
+
     /// Look up a full page ID from external ID
     pub async fn get_cached_page_id(&self, external_id: &str) -> Option<String> {
         let cache = self.page_id_cache.lock().await;
@@ -518,18 +868,20 @@ impl NotionClient {
     ///
     /// # Arguments
     /// * `page` - The Notion page to convert
+    /// * `workspace` - Workspace the database belongs to, if scoped (for `external_id` generation)
     /// * `database_id` - The database ID (for `external_id` generation)
     /// * `mapping` - Property mapping for extracting fields
     /// * `description_text` - Optional pre-fetched description from blocks
     #[must_use]
     pub fn page_to_issue_candidate(
         page: &NotionPage,
+        workspace: Option<&str>,
         database_id: &str,
         mapping: &PropertyMapping,
         description_text: Option<String>,
     ) -> NotionIssueCandidateData {
         // Generate external ID
-        let external_id = Self::generate_external_id(database_id, &page.id);
+        let external_id = Self::generate_external_id(workspace, database_id, &page.id);
 
         // Extract title from mapped property
         let title = mapping
@@ -556,13 +908,7 @@ impl NotionClient {
             .or(description_text);
 
         // Extract labels from multi-select if available
-        let labels = page
-            .properties
-            .values()
-            .find(|p| p.value_type == "multi_select")
-            .and_then(|p| p.multi_select.as_ref())
-            .map(|opts| opts.iter().map(|o| o.name.clone()).collect())
-            .unwrap_or_default();
+        let labels = page.labels();
 
         NotionIssueCandidateData {
             external_id,
@@ -573,6 +919,7 @@ impl NotionClient {
             database_id: database_id.to_string(),
             page_id: page.id.clone(),
             labels,
+            last_edited_time: page.last_edited_time.clone(),
         }
     }
 
@@ -586,8 +933,10 @@ impl NotionClient {
     ///
     /// # Arguments
     /// * `database_id` - The Notion database ID
+    /// * `workspace` - Workspace the database belongs to, if scoped
     /// * `config` - Optional property mapping configuration
     /// * `fetch_blocks` - Whether to fetch page blocks for descriptions (slower but more complete)
+    /// * `query` - Pagination mode, resume cursor, and edited-since filter
     ///
     /// # Errors
     ///
@@ -595,9 +944,11 @@ impl NotionClient {
     pub async fn fetch_database_as_issues(
         &self,
         database_id: &str,
+        workspace: Option<&str>,
         config: Option<&PropertyMappingConfig>,
         fetch_blocks: bool,
-    ) -> Result<Vec<NotionIssueCandidateData>> {
+        query: PageQuery<'_>,
+    ) -> Result<PagedResults<NotionIssueCandidateData>> {
         // Get database schema and detect property mapping
         let database = self.get_database(database_id).await?;
         let mapping = database.detect_property_mapping(config);
@@ -615,7 +966,13 @@ impl NotionClient {
         );
 
         // Query all pages
-        let pages = self.query_database_all(database_id).await?;
+        let PagedResults {
+            items: pages,
+            resume_cursor,
+            error,
+        } = self
+            .query_database_pages(database_id, query)
+            .await?;
         log::info!("Fetched {} pages from Notion database", pages.len());
 
         let mut candidates = Vec::with_capacity(pages.len());
@@ -647,7 +1004,13 @@ impl NotionClient {
             };
 
             // Convert to issue candidate
-            let candidate = Self::page_to_issue_candidate(page, database_id, &mapping, description_text);
+            let candidate = Self::page_to_issue_candidate(
+                page,
+                workspace,
+                database_id,
+                &mapping,
+                description_text,
+            );
 
             // Cache the page ID for later lookups
             self.cache_page_id(&candidate.external_id, &page.id).await;
@@ -655,7 +1018,11 @@ impl NotionClient {
             candidates.push(candidate);
         }
 
-        Ok(candidates)
+        Ok(PagedResults {
+            items: candidates,
+            resume_cursor,
+            error,
+        })
     }
 
     /// Add time entry using configured or auto-detected time property
@@ -11,6 +11,8 @@
 
 mod client;
 mod schema;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 mod types;
 
 use anyhow::Result;
@@ -19,18 +21,22 @@ use async_trait::async_trait;
 use crate::traits::{ProjectManagementSystem, SyncReport, TimeEntry, WorkItemDetails};
 
 // Re-export public types
-pub use client::NotionClient;
+pub use client::{
+    NotionClient, PageQuery, PagedResults, PaginationMode, EDITED_SINCE_MARGIN_SECS,
+};
 pub use schema::{
-    PropertyMapping, PropertyMappingConfig, ASSIGNEE_CONVENTIONS, DESCRIPTION_CONVENTIONS,
-    DUE_DATE_CONVENTIONS, NOTION_API_VERSION, NOTION_BASE_URL, PRIORITY_CONVENTIONS,
-    RATE_LIMIT_INTERVAL_MS, STATUS_CONVENTIONS, TIME_CONVENTIONS, TITLE_CONVENTIONS,
+    NotionApiVersion, PropertyMapping, PropertyMappingConfig, ASSIGNEE_CONVENTIONS,
+    DESCRIPTION_CONVENTIONS, DUE_DATE_CONVENTIONS, NOTION_API_VERSION, NOTION_API_VERSION_ENV,
+    NOTION_BASE_URL, NOTION_DATA_SOURCES_API_VERSION, PRIORITY_CONVENTIONS, RATE_LIMIT_INTERVAL_MS,
+    STATUS_CONVENTIONS, TIME_CONVENTIONS, TITLE_CONVENTIONS,
 };
 pub use types::{
-    NotionBlock, NotionBlockContent, NotionCodeContent, NotionDatabase, NotionDateValue,
-    NotionIssueCandidateData, NotionMultiSelectConfig, NotionPage, NotionPaginatedResponse,
-    NotionPropertySchema, NotionPropertyUpdate, NotionPropertyValue, NotionRichText,
-    NotionSelectConfig, NotionSelectOption, NotionSelectValue, NotionStatusConfig,
-    NotionStatusGroup, NotionToDoContent,
+    NotionBlock, NotionBlockContent, NotionCodeContent, NotionDataSource, NotionDataSourceParent,
+    NotionDataSourceRef, NotionDatabase, NotionDateValue, NotionIssueCandidateData,
+    NotionMultiSelectConfig, NotionPage, NotionPaginatedResponse, NotionPropertySchema,
+    NotionPropertyUpdate, NotionPropertyValue, NotionRichText, NotionSelectConfig,
+    NotionSelectOption, NotionSelectValue, NotionStatusConfig, NotionStatusGroup,
+    NotionToDoContent,
 };
 
 // ============================================================================
@@ -48,22 +54,6 @@ impl ProjectManagementSystem for NotionClient {
 
         let page = self.get_page(&page_id).await?;
 
-        // Extract title from properties (look for title type property)
-        let title = page
-            .properties
-            .values()
-            .find(|p| p.value_type == "title")
-            .and_then(NotionPropertyValue::as_plain_text)
-            .unwrap_or_else(|| "Untitled".to_string());
-
-        // Extract status from properties (look for status or select type)
-        let status = page
-            .properties
-            .values()
-            .find(|p| p.value_type == "status" || p.value_type == "select")
-            .and_then(NotionPropertyValue::as_select_name)
-            .unwrap_or_else(|| "Unknown".to_string());
-
         // Get description from page blocks
         let blocks = self.get_page_blocks_all(&page_id, 2).await.unwrap_or_default();
         let description = if blocks.is_empty() {
@@ -72,14 +62,26 @@ impl ProjectManagementSystem for NotionClient {
             Some(NotionClient::blocks_to_text(&blocks))
         };
 
-        Ok(WorkItemDetails {
-            id: work_item_id.to_string(),
-            title,
-            description,
-            status,
-            project: None,
-            workspace: None,
-        })
+        Ok(work_item_from_page(work_item_id, &page, description))
+    }
+
+    /// Fetch work items from their page properties only
+    ///
+    /// Notion has no batch page endpoint, so this skips the block walk that
+    /// `fetch_work_item` uses for descriptions to keep it to one request per item.
+    async fn fetch_work_items_bulk(&self, ids: &[String]) -> Result<Vec<WorkItemDetails>> {
+        let mut items = Vec::with_capacity(ids.len());
+        for id in ids {
+            let Some(page_id) = self.get_cached_page_id(id).await else {
+                log::warn!("Failed to fetch notion work item {id}: page ID not found in cache");
+                continue;
+            };
+            match self.get_page(&page_id).await {
+                Ok(page) => items.push(work_item_from_page(id, &page, None)),
+                Err(e) => log::warn!("Failed to fetch notion work item {id}: {e}"),
+            }
+        }
+        Ok(items)
     }
 
     async fn add_time_entry(&self, entry: &TimeEntry) -> Result<()> {
@@ -116,6 +118,39 @@ impl ProjectManagementSystem for NotionClient {
     }
 }
 
+/// Build `WorkItemDetails` from a page and its block text
+fn work_item_from_page(
+    work_item_id: &str,
+    page: &NotionPage,
+    description: Option<String>,
+) -> WorkItemDetails {
+    // Extract title from properties (look for title type property)
+    let title = page
+        .properties
+        .values()
+        .find(|p| p.value_type == "title")
+        .and_then(NotionPropertyValue::as_plain_text)
+        .unwrap_or_else(|| "Untitled".to_string());
+
+    // Extract status from properties (look for status or select type)
+    let status = page
+        .properties
+        .values()
+        .find(|p| p.value_type == "status" || p.value_type == "select")
+        .and_then(NotionPropertyValue::as_select_name)
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    WorkItemDetails {
+        id: work_item_id.to_string(),
+        title,
+        description,
+        status,
+        project: None,
+        workspace: None,
+        labels: page.labels(),
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -123,12 +158,16 @@ impl ProjectManagementSystem for NotionClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::traits::{CreateIssueRequest, IssueField, IssueFieldMapping};
     use std::collections::HashMap;
 
     #[test]
     fn test_generate_external_id() {
-        let external_id =
-            NotionClient::generate_external_id("abcd1234-5678-9abc-def0-123456789abc", "f7e3c2a1-1234-5678-9abc-def012345678");
+        let external_id = NotionClient::generate_external_id(
+            None,
+            "abcd1234-5678-9abc-def0-123456789abc",
+            "f7e3c2a1-1234-5678-9abc-def012345678",
+        );
         assert_eq!(external_id, "abcd-f7e3c2a1");
     }
 
@@ -180,6 +219,7 @@ mod tests {
             title: vec![NotionRichText::text("Test Database")],
             properties: props,
             url: None,
+            data_sources: Vec::new(),
         }
     }
 
@@ -422,6 +462,7 @@ mod tests {
             database_id: "db-123".to_string(),
             page_id: "page-456".to_string(),
             labels: vec!["bug".to_string(), "urgent".to_string()],
+            last_edited_time: None,
         };
 
         assert_eq!(candidate.external_id, "abcd-12345678");
@@ -504,11 +545,11 @@ mod tests {
     #[test]
     fn test_generate_external_id_short_ids() {
         // Test with short IDs
-        let external_id = NotionClient::generate_external_id("abc", "12345");
+        let external_id = NotionClient::generate_external_id(None, "abc", "12345");
         assert_eq!(external_id, "abc-12345");
 
         // Test with exact length
-        let external_id = NotionClient::generate_external_id("abcd", "12345678");
+        let external_id = NotionClient::generate_external_id(None, "abcd", "12345678");
         assert_eq!(external_id, "abcd-12345678");
     }
 
@@ -649,4 +690,341 @@ mod tests {
 
         assert_eq!(labels, vec!["bug".to_string(), "urgent".to_string()]);
     }
+
+    #[test]
+    fn test_work_item_labels_from_multi_select() {
+        let page: NotionPage = serde_json::from_value(serde_json::json!({
+            "id": "page-1",
+            "properties": {
+                "Name": {"id": "title", "type": "title", "title": [{"plain_text": "Write onboarding guide"}]},
+                "Status": {"id": "s", "type": "status", "status": {"id": "1", "name": "In Progress"}},
+                "Tags": {"id": "t", "type": "multi_select", "multi_select": [
+                    {"id": "1", "name": "docs", "color": "blue"},
+                    {"id": "2", "name": "Q3", "color": "gray"}
+                ]}
+            }
+        }))
+        .unwrap();
+
+        let details = work_item_from_page("abcd-f7e3c2a1", &page, None);
+        assert_eq!(details.title, "Write onboarding guide");
+        assert_eq!(details.status, "In Progress");
+        assert_eq!(details.labels, ["docs", "Q3"]);
+    }
+
+    #[test]
+    fn test_legacy_database_and_query_response() {
+        let database: NotionDatabase = serde_json::from_value(serde_json::json!({
+            "object": "database",
+            "id": "abcd1234-0000-0000-0000-000000000000",
+            "title": [{"plain_text": "Tasks"}],
+            "properties": {
+                "Name": {"id": "title", "name": "Name", "type": "title"},
+                "Hours": {"id": "h", "name": "Hours", "type": "number"}
+            }
+        }))
+        .unwrap();
+        assert_eq!(database.data_source_id(), None);
+        assert_eq!(
+            database.detect_property_mapping(None).time.as_deref(),
+            Some("Hours")
+        );
+
+        let response: NotionPaginatedResponse<NotionPage> = serde_json::from_value(serde_json::json!({
+            "object": "list",
+            "type": "page_or_database",
+            "results": [{
+                "object": "page",
+                "id": "f7e3c2a1-1234-5678-9abc-def012345678",
+                "parent": {"type": "database_id", "database_id": "abcd1234-0000-0000-0000-000000000000"},
+                "properties": {
+                    "Name": {"id": "title", "type": "title", "title": [{"plain_text": "Fix login"}]}
+                }
+            }],
+            "next_cursor": null,
+            "has_more": false
+        }))
+        .unwrap();
+        assert_eq!(response.results.len(), 1);
+        assert_eq!(
+            response.results[0].properties["Name"].as_plain_text().as_deref(),
+            Some("Fix login")
+        );
+    }
+
+    #[test]
+    fn test_data_source_database_and_query_response() {
+        // Newer API versions list data sources instead of properties
+        let database: NotionDatabase = serde_json::from_value(serde_json::json!({
+            "object": "database",
+            "id": "abcd1234-0000-0000-0000-000000000000",
+            "title": [{"plain_text": "Tasks"}],
+            "data_sources": [{"id": "ds-1111", "name": "Tasks"}]
+        }))
+        .unwrap();
+        assert_eq!(database.data_source_id(), Some("ds-1111"));
+
+        let source: NotionDataSource = serde_json::from_value(serde_json::json!({
+            "object": "data_source",
+            "id": "ds-1111",
+            "title": [{"plain_text": "Tasks"}],
+            "parent": {"type": "database_id", "database_id": "abcd1234-0000-0000-0000-000000000000"},
+            "properties": {
+                "Name": {"id": "title", "name": "Name", "type": "title"},
+                "Status": {"id": "s", "name": "Status", "type": "status"}
+            }
+        }))
+        .unwrap();
+        let resolved = source.into_database();
+        assert_eq!(resolved.id, "abcd1234-0000-0000-0000-000000000000");
+        assert_eq!(resolved.data_source_id(), None);
+        assert_eq!(
+            resolved.detect_property_mapping(None).status.as_deref(),
+            Some("Status")
+        );
+
+        let response: NotionPaginatedResponse<NotionPage> = serde_json::from_value(serde_json::json!({
+            "object": "list",
+            "type": "page_or_data_source",
+            "results": [{
+                "object": "page",
+                "id": "f7e3c2a1-1234-5678-9abc-def012345678",
+                "parent": {
+                    "type": "data_source_id",
+                    "data_source_id": "ds-1111",
+                    "database_id": "abcd1234-0000-0000-0000-000000000000"
+                },
+                "properties": {
+                    "Name": {"id": "title", "type": "title", "title": [{"plain_text": "Fix login"}]}
+                }
+            }],
+            "next_cursor": "cursor-2",
+            "has_more": true
+        }))
+        .unwrap();
+        assert_eq!(response.results[0].id, "f7e3c2a1-1234-5678-9abc-def012345678");
+        assert_eq!(response.next_cursor.as_deref(), Some("cursor-2"));
+    }
+
+    #[test]
+    fn test_issue_priority_maps_to_configured_select() {
+        let db = create_test_database(vec![
+            ("Task", "title"),
+            ("Urgency", "select"),
+            ("Owner", "people"),
+        ]);
+        let mapping = IssueFieldMapping {
+            priority: Some("Urgency".to_string()),
+            assignees: Some("Owner".to_string()),
+            ..IssueFieldMapping::default()
+        };
+        let mut request =
+            CreateIssueRequest::new("Fix login".to_string()).with_priority("High".to_string());
+        request.assignees = vec!["alice".to_string()];
+        request.milestone = Some("v1.0".to_string());
+        let parent = serde_json::json!({ "database_id": "test-db-id" });
+
+        let (body, dropped) =
+            client::issue_page_body(parent.clone(), &db, &request, &mapping).unwrap();
+
+        assert_eq!(body["parent"], parent);
+        let properties = &body["properties"];
+        assert_eq!(
+            properties["Urgency"],
+            serde_json::json!({ "select": { "name": "High" } })
+        );
+        assert_eq!(
+            properties["Task"]["title"][0]["text"]["content"],
+            "Fix login"
+        );
+        // People properties need user IDs, and milestones are unmapped
+        assert!(properties.get("Owner").is_none());
+        assert_eq!(dropped, vec![IssueField::Assignees, IssueField::Milestone]);
+
+        // Without a priority property the priority is dropped too
+        let (body, dropped) =
+            client::issue_page_body(parent, &db, &request, &IssueFieldMapping::default()).unwrap();
+        assert!(body["properties"].get("Urgency").is_none());
+        assert!(dropped.contains(&IssueField::Priority));
+    }
+
+    #[test]
+    fn test_api_version_parse() {
+        assert_eq!(
+            NotionApiVersion::parse("2022-06-28"),
+            NotionApiVersion::Legacy
+        );
+        assert_eq!(
+            NotionApiVersion::parse("2025-09-03"),
+            NotionApiVersion::DataSources
+        );
+        assert_eq!(
+            NotionApiVersion::parse(" 2026-01-15 "),
+            NotionApiVersion::DataSources
+        );
+        assert_eq!(NotionApiVersion::parse("latest"), NotionApiVersion::Legacy);
+        assert_eq!(
+            NotionApiVersion::DataSources.header(),
+            NOTION_DATA_SOURCES_API_VERSION
+        );
+    }
+
+    #[tokio::test]
+    async fn test_preloaded_page_ids_resolve_without_api_call() {
+        // Invalid key: a lookup that reached the API would fail
+        let client = NotionClient::new("invalid-key".to_string()).unwrap();
+        assert_eq!(client.get_cached_page_id("abcd-f7e3c2a1").await, None);
+
+        let loaded = client
+            .preload_page_ids(HashMap::from([(
+                "abcd-f7e3c2a1".to_string(),
+                "f7e3c2a1-1234-5678-9abc-def012345678".to_string(),
+            )]))
+            .await;
+
+        assert_eq!(loaded, 1);
+        assert_eq!(
+            client.get_cached_page_id("abcd-f7e3c2a1").await.as_deref(),
+            Some("f7e3c2a1-1234-5678-9abc-def012345678")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_colliding_short_ids_stay_distinct_across_workspaces() {
+        // Same 4-char database prefix and 8-char page prefix in two workspaces
+        let acme_page = NotionPage {
+            id: "f7e3c2a1-1111-5678-9abc-def012345678".to_string(),
+            ..create_test_page(vec![])
+        };
+        let globex_page = NotionPage {
+            id: "f7e3c2a1-2222-5678-9abc-def012345678".to_string(),
+            ..create_test_page(vec![])
+        };
+        let mapping = PropertyMapping::default();
+        let acme = NotionClient::page_to_issue_candidate(
+            &acme_page,
+            Some("Acme"),
+            "abcd1111-5678-9abc-def0-123456789abc",
+            &mapping,
+            None,
+        );
+        let globex = NotionClient::page_to_issue_candidate(
+            &globex_page,
+            Some("Globex Corp"),
+            "abcd2222-5678-9abc-def0-123456789abc",
+            &mapping,
+            None,
+        );
+
+        assert_eq!(acme.external_id, "acme/abcd-f7e3c2a1");
+        assert_eq!(globex.external_id, "globexcorp/abcd-f7e3c2a1");
+
+        let client = NotionClient::new("invalid-key".to_string()).unwrap();
+        client
+            .preload_page_ids(HashMap::from([
+                (acme.external_id.clone(), acme.page_id.clone()),
+                (globex.external_id.clone(), globex.page_id.clone()),
+            ]))
+            .await;
+        let acme_resolved = client.get_cached_page_id(&acme.external_id).await;
+        let globex_resolved = client.get_cached_page_id(&globex.external_id).await;
+        assert_eq!(acme_resolved, Some(acme_page.id));
+        assert_eq!(globex_resolved, Some(globex_page.id));
+    }
+
+    #[tokio::test]
+    async fn test_lenient_pagination_keeps_pages_before_error() {
+        let results = client::collect_pages(PaginationMode::Lenient, None, |cursor| async move {
+            test_util::mock_page(cursor, Some(3))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(results.items, vec![1, 2]);
+        assert_eq!(results.resume_cursor.as_deref(), Some("cursor-3"));
+        assert!(!results.is_complete());
+        assert!(results.error.unwrap().contains("500"));
+
+        // Resuming from the reported cursor continues where the query stopped
+        let resumed = client::collect_pages(
+            PaginationMode::Lenient,
+            Some("cursor-4".to_string()),
+            |cursor| async move { test_util::mock_page(cursor, Some(3)) },
+        )
+        .await
+        .unwrap();
+        assert_eq!(resumed.items, vec![4, 5]);
+        assert!(resumed.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_strict_pagination_fails_on_any_page() {
+        let result = client::collect_pages(PaginationMode::Strict, None, |cursor| async move {
+            test_util::mock_page(cursor, Some(3))
+        })
+        .await;
+        assert!(result.is_err());
+
+        // Nothing fetched yet: even lenient mode reports the error
+        let first_page = client::collect_pages(
+            PaginationMode::Lenient,
+            Some("cursor-3".to_string()),
+            |cursor| async move { test_util::mock_page(cursor, Some(3)) },
+        )
+        .await;
+        assert!(first_page.is_err());
+    }
+
+    #[test]
+    fn test_query_body_filters_by_last_edited_time_with_margin() {
+        let since = chrono::DateTime::parse_from_rfc3339("2025-03-14T09:30:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+
+        let body = NotionClient::query_body(Some("cursor-2"), Some(since));
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "start_cursor": "cursor-2",
+                "filter": {
+                    "timestamp": "last_edited_time",
+                    "last_edited_time": { "on_or_after": "2025-03-14T09:28:00Z" },
+                },
+            })
+        );
+        assert_eq!(EDITED_SINCE_MARGIN_SECS, 120);
+
+        // Without a timestamp every page is queried
+        assert_eq!(NotionClient::query_body(None, None), serde_json::json!({}));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_request_retried_after_retry_after() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1/users/me", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let responses = [
+                "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 11\r\nConnection: close\r\n\r\n{\"ok\":true}",
+            ];
+            for response in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = [0; 4096];
+                let _ = stream.read(&mut request).await.unwrap();
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.shutdown().await.unwrap();
+            }
+        });
+
+        let client = NotionClient::new("secret_test".to_string()).unwrap();
+        let started = std::time::Instant::now();
+        let body: serde_json::Value = client.get(&url).await.unwrap();
+
+        assert_eq!(body["ok"], true);
+        // Retry-After: 0 replaced the one second backoff delay
+        assert!(started.elapsed() < std::time::Duration::from_millis(900));
+        server.await.unwrap();
+    }
 }
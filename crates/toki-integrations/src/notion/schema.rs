@@ -12,12 +12,76 @@ use serde::{Deserialize, Serialize};
 /// Notion API version (use stable version)
 pub const NOTION_API_VERSION: &str = "2022-06-28";
 
+/// First Notion API version where databases hold data sources
+pub const NOTION_DATA_SOURCES_API_VERSION: &str = "2025-09-03";
+
+/// Environment variable that overrides the Notion API version
+pub const NOTION_API_VERSION_ENV: &str = "NOTION_API_VERSION";
+
 /// Notion API base URL
 pub const NOTION_BASE_URL: &str = "https://api.notion.com/v1";
 
 /// Rate limit: minimum interval between requests (333ms for ~3 req/sec)
 pub const RATE_LIMIT_INTERVAL_MS: u64 = 350;
 
+/// Notion API version sent with requests
+///
+/// From [`NOTION_DATA_SOURCES_API_VERSION`] on, a database is a container of
+/// data sources: its properties live on the data source, which is also what
+/// gets queried. Data source endpoints always use that version, whatever the
+/// client is configured with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NotionApiVersion {
+    /// Databases have properties and are queried directly
+    #[default]
+    Legacy,
+    /// Databases are queried through their data sources
+    DataSources,
+}
+
+impl NotionApiVersion {
+    /// Value of the `Notion-Version` header
+    #[must_use]
+    pub fn header(self) -> &'static str {
+        match self {
+            Self::Legacy => NOTION_API_VERSION,
+            Self::DataSources => NOTION_DATA_SOURCES_API_VERSION,
+        }
+    }
+
+    /// Pick the API shape for a dated version such as `2025-09-03`
+    ///
+    /// Unrecognized values fall back to the legacy version.
+    #[must_use]
+    pub fn parse(version: &str) -> Self {
+        let version = version.trim();
+        let is_date = version.len() == 10
+            && version.chars().enumerate().all(|(i, c)| {
+                if i == 4 || i == 7 {
+                    c == '-'
+                } else {
+                    c.is_ascii_digit()
+                }
+            });
+        if !is_date {
+            log::warn!("Unrecognized Notion API version '{version}', using {NOTION_API_VERSION}");
+            return Self::Legacy;
+        }
+        // ISO dates compare correctly as strings
+        if version >= NOTION_DATA_SOURCES_API_VERSION {
+            Self::DataSources
+        } else {
+            Self::Legacy
+        }
+    }
+
+    /// Version from [`NOTION_API_VERSION_ENV`], or the legacy version if unset
+    #[must_use]
+    pub fn from_env() -> Self {
+        std::env::var(NOTION_API_VERSION_ENV).map_or(Self::Legacy, |v| Self::parse(&v))
+    }
+}
+
 // ============================================================================
 // Schema Detection - Convention Lists
 // ============================================================================
@@ -0,0 +1,50 @@
+//! Mocked paginated Notion responses shared by pagination tests
+//!
+//! Enabled by the `test-util` feature so dependent crates can exercise their
+//! checkpointing against the same pages as the client's own tests.
+
+use anyhow::Result;
+
+use super::client::{collect_pages, PagedResults, PaginationMode};
+use super::types::NotionPaginatedResponse;
+
+/// Page `n` of a five-page query with one result each, requested as `cursor-n`
+///
+/// The request for `failing_page` fails like a Notion server error.
+///
+/// # Errors
+///
+/// Returns an error if `cursor` addresses `failing_page`
+///
+/// # Panics
+///
+/// Panics if `cursor` is not of the form `cursor-n`
+pub fn mock_page(
+    cursor: Option<String>,
+    failing_page: Option<u32>,
+) -> Result<NotionPaginatedResponse<u32>> {
+    let page = cursor.map_or(1, |c| c.trim_start_matches("cursor-").parse().unwrap());
+    if Some(page) == failing_page {
+        anyhow::bail!("Notion API error (500): internal server error");
+    }
+    Ok(NotionPaginatedResponse {
+        results: vec![page],
+        next_cursor: Some(format!("cursor-{}", page + 1)),
+        has_more: page < 5,
+    })
+}
+
+/// Fetch [`mock_page`]s from `start_cursor` in lenient mode
+///
+/// # Errors
+///
+/// Returns an error if the first page requested is `failing_page`
+pub async fn mock_pages(
+    start_cursor: Option<String>,
+    failing_page: Option<u32>,
+) -> Result<PagedResults<u32>> {
+    collect_pages(PaginationMode::Lenient, start_cursor, |cursor| async move {
+        mock_page(cursor, failing_page)
+    })
+    .await
+}
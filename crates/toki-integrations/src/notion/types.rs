@@ -2,7 +2,9 @@
 //!
 //! This module contains the data structures for interacting with the Notion API.
 
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
 use std::collections::HashMap;
 
 use super::schema::{PropertyMapping, PropertyMappingConfig};
@@ -10,22 +12,100 @@ use super::{
     ASSIGNEE_CONVENTIONS, DESCRIPTION_CONVENTIONS, DUE_DATE_CONVENTIONS, PRIORITY_CONVENTIONS,
     STATUS_CONVENTIONS, TIME_CONVENTIONS, TITLE_CONVENTIONS,
 };
+use crate::traits::{CreateIssueRequest, IssueField, IssueFieldMapping};
 
 // ============================================================================
 // Notion API Types
 // ============================================================================
 
 /// Notion Database schema
+///
+/// Databases returned by newer API versions carry no properties, only the
+/// data sources that hold them.
 #[derive(Debug, Clone, Deserialize)]
 pub struct NotionDatabase {
     pub id: String,
+    #[serde(default)]
     pub title: Vec<NotionRichText>,
+    #[serde(default)]
     pub properties: HashMap<String, NotionPropertySchema>,
     #[serde(default)]
     pub url: Option<String>,
+    #[serde(default)]
+    pub data_sources: Vec<NotionDataSourceRef>,
+}
+
+/// Data source listed on a database (newer API versions)
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotionDataSourceRef {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+}
+
+/// Data source with its schema (newer API versions)
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotionDataSource {
+    pub id: String,
+    #[serde(default)]
+    pub title: Vec<NotionRichText>,
+    #[serde(default)]
+    pub properties: HashMap<String, NotionPropertySchema>,
+    #[serde(default)]
+    pub parent: Option<NotionDataSourceParent>,
+}
+
+/// Database a data source belongs to
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotionDataSourceParent {
+    #[serde(default)]
+    pub database_id: Option<String>,
+}
+
+impl NotionDataSource {
+    /// View the data source as a database with its schema
+    ///
+    /// The id is the parent database's when known, so external IDs stay the
+    /// same as for legacy databases.
+    #[must_use]
+    pub fn into_database(self) -> NotionDatabase {
+        let database_id = self
+            .parent
+            .and_then(|p| p.database_id)
+            .unwrap_or_else(|| self.id.clone());
+        NotionDatabase {
+            id: database_id,
+            title: self.title,
+            properties: self.properties,
+            url: None,
+            data_sources: vec![NotionDataSourceRef {
+                id: self.id,
+                name: String::new(),
+            }],
+        }
+    }
 }
 
 impl NotionDatabase {
+    /// Data source to query instead of the database, for the newer API shape
+    ///
+    /// Returns `None` for legacy databases, which have their properties inline.
+    /// Databases with several data sources use the first one.
+    #[must_use]
+    pub fn data_source_id(&self) -> Option<&str> {
+        if !self.properties.is_empty() {
+            return None;
+        }
+        if self.data_sources.len() > 1 {
+            log::warn!(
+                "Notion database {} has {} data sources, using the first",
+                self.id,
+                self.data_sources.len()
+            );
+        }
+        self.data_sources.first().map(|source| source.id.as_str())
+    }
+
     /// Get the database title as plain text
     #[must_use]
     pub fn title_plain_text(&self) -> String {
@@ -142,6 +222,101 @@ impl NotionDatabase {
         parts.sort();
         parts.join(", ")
     }
+
+    /// Issue field mapping detected from the database schema
+    ///
+    /// Labels go to the first multi-select property; milestones have no
+    /// conventional property and are left unmapped.
+    #[must_use]
+    pub fn issue_field_mapping(&self) -> IssueFieldMapping {
+        let detected = self.detect_property_mapping(None);
+        IssueFieldMapping {
+            body: detected.description,
+            labels: self
+                .properties
+                .iter()
+                .find(|(_, schema)| schema.property_type == "multi_select")
+                .map(|(name, _)| name.clone()),
+            assignees: detected.assignee,
+            milestone: None,
+            priority: detected.priority,
+            due_date: detected.due_date,
+        }
+    }
+
+    /// Page properties for a new issue, and the request fields left out
+    ///
+    /// Each mapped field is written in the format of its property's type.
+    /// Fields with no mapping, a missing property, or a property type that
+    /// cannot hold them (such as people) are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database has no title property
+    pub fn issue_page_properties(
+        &self,
+        request: &CreateIssueRequest,
+        mapping: &IssueFieldMapping,
+    ) -> Result<(Map<String, Value>, Vec<IssueField>)> {
+        let title_property = self
+            .properties
+            .iter()
+            .find(|(_, schema)| schema.property_type == "title")
+            .map(|(name, _)| name.clone())
+            .context("Notion database has no title property")?;
+
+        let mut properties = Map::new();
+        properties.insert(
+            title_property,
+            json!({ "title": [{ "text": { "content": request.title } }] }),
+        );
+
+        let mut dropped = Vec::new();
+        for field in IssueField::ALL {
+            if !request.has_field(field) {
+                continue;
+            }
+            let value = mapping.target(field).and_then(|name| {
+                let schema = self.properties.get(name)?;
+                issue_property_value(&schema.property_type, field, request)
+                    .map(|value| (name.to_string(), value))
+            });
+            match value {
+                Some((name, value)) => {
+                    properties.insert(name, value);
+                }
+                None => dropped.push(field),
+            }
+        }
+
+        Ok((properties, dropped))
+    }
+}
+
+/// Property value holding a request field, if the property type can hold it
+fn issue_property_value(
+    property_type: &str,
+    field: IssueField,
+    request: &CreateIssueRequest,
+) -> Option<Value> {
+    let text = request.field_text(field)?;
+    match property_type {
+        "rich_text" => Some(json!({ "rich_text": [{ "text": { "content": text } }] })),
+        "select" | "status" if field.is_scalar() => {
+            Some(json!({ property_type: { "name": text } }))
+        }
+        "multi_select" => {
+            let names = match field {
+                IssueField::Labels => request.labels.clone(),
+                _ if field.is_scalar() => vec![text],
+                _ => return None,
+            };
+            let options: Vec<Value> = names.iter().map(|name| json!({ "name": name })).collect();
+            Some(json!({ "multi_select": options }))
+        }
+        "date" if field == IssueField::DueDate => Some(json!({ "date": { "start": text } })),
+        _ => None,
+    }
 }
 
 /// Notion Property Schema (defines property type in database)
@@ -213,6 +388,19 @@ pub struct NotionPage {
     pub archived: bool,
 }
 
+impl NotionPage {
+    /// Option names of the page's multi-select property (tags/labels)
+    #[must_use]
+    pub fn labels(&self) -> Vec<String> {
+        self.properties
+            .values()
+            .find(|p| p.value_type == "multi_select")
+            .and_then(|p| p.multi_select.as_ref())
+            .map(|opts| opts.iter().map(|o| o.name.clone()).collect())
+            .unwrap_or_default()
+    }
+}
+
 /// Notion Property Value (actual value in a page)
 #[derive(Debug, Clone, Deserialize)]
 pub struct NotionPropertyValue {
@@ -451,6 +639,9 @@ pub struct NotionPaginatedResponse<T> {
 /// Response for listing databases (uses same pagination structure)
 pub type SearchResponse = NotionPaginatedResponse<NotionDatabase>;
 
+/// Response for listing data sources (newer API versions)
+pub type DataSourceSearchResponse = NotionPaginatedResponse<NotionDataSource>;
+
 // ============================================================================
 // Property Update Payloads
 // ============================================================================
@@ -517,4 +708,5 @@ pub struct NotionIssueCandidateData {
     pub database_id: String,
     pub page_id: String,
     pub labels: Vec<String>,
+    pub last_edited_time: Option<String>, // RFC 3339, truncated to the minute by Notion
 }
@@ -1,9 +1,10 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::http::ResponseExt;
+use crate::http::{RequestExt, ResponseExt};
 use crate::traits::{ProjectManagementSystem, SyncReport, TimeEntry, WorkItemDetails};
 
 // ============================================================================
@@ -67,10 +68,46 @@ pub struct PlaneWorkItem {
     pub assignees: Vec<Uuid>,
     #[serde(default)]
     pub priority: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<PlaneLabelRef>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+impl PlaneWorkItem {
+    /// Names of the work item's labels
+    ///
+    /// Empty unless the work item was fetched with `expand=labels`; otherwise
+    /// Plane only returns label ids.
+    #[must_use]
+    pub fn label_names(&self) -> Vec<String> {
+        self.labels
+            .iter()
+            .filter_map(|label| match label {
+                PlaneLabelRef::Expanded(label) => Some(label.name.clone()),
+                PlaneLabelRef::Id(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// Plane.so label
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PlaneLabel {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Label reference on a work item: a bare id, or the label itself when expanded
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum PlaneLabelRef {
+    Expanded(PlaneLabel),
+    Id(Uuid),
+}
+
 /// Plane.so Project detail (embedded in work item)
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PlaneProjectDetail {
@@ -93,6 +130,25 @@ pub struct PlaneWorklog {
     pub workspace_id: Option<Uuid>,
 }
 
+/// Plane.so work item activity (audit log entry)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PlaneActivity {
+    pub id: Uuid,
+    pub created_at: String,
+    #[serde(default)]
+    pub verb: Option<String>,
+    /// Changed field (e.g., "state"); `None` for creation entries
+    #[serde(default)]
+    pub field: Option<String>,
+    #[serde(default)]
+    pub old_value: Option<String>,
+    #[serde(default)]
+    pub new_value: Option<String>,
+    /// New value as an ID (e.g., state UUID for state changes)
+    #[serde(default)]
+    pub new_identifier: Option<Uuid>,
+}
+
 /// Plane.so Worklog create/update request
 #[derive(Debug, Serialize)]
 struct PlaneWorklogRequest {
@@ -180,7 +236,7 @@ impl PlaneClient {
             .get(url)
             .header("X-Api-Key", &self.api_key)
             .header("Content-Type", "application/json")
-            .send()
+            .send_logged("Plane")
             .await
             .context("Failed to send request to Plane API")?
             .ensure_success("Plane")
@@ -206,7 +262,7 @@ impl PlaneClient {
             .header("X-Api-Key", &self.api_key)
             .header("Content-Type", "application/json")
             .json(body)
-            .send()
+            .send_logged("Plane")
             .await
             .context("Failed to send request to Plane API")?
             .ensure_success("Plane")
@@ -296,7 +352,7 @@ impl PlaneClient {
         sequence_id: &str,
     ) -> Result<PlaneWorkItem> {
         let url = self.build_url(&format!(
-            "work-items/{project_identifier}-{sequence_id}/"
+            "work-items/{project_identifier}-{sequence_id}/?expand=labels"
         ));
         self.get(&url).await
     }
@@ -330,7 +386,7 @@ impl PlaneClient {
             .header("X-Api-Key", &self.api_key)
             .header("Content-Type", "application/json")
             .query(&[("search", query)])
-            .send()
+            .send_logged("Plane")
             .await
             .context("Failed to send request to Plane API")?
             .ensure_success("Plane")
@@ -452,6 +508,84 @@ impl PlaneClient {
         }
     }
 
+    /// Convert a fetched work item to `WorkItemDetails`
+    fn to_work_item_details(&self, work_item_id: &str, work_item: PlaneWorkItem) -> WorkItemDetails {
+        let labels = work_item.label_names();
+
+        let status = work_item
+            .state_detail
+            .map_or_else(|| "Unknown".to_string(), |s| s.name);
+
+        let project_name = work_item
+            .project_detail
+            .map_or_else(|| "Unknown".to_string(), |p| p.name);
+
+        WorkItemDetails {
+            id: work_item_id.to_string(),
+            title: work_item.name,
+            description: work_item.description_stripped,
+            status,
+            project: Some(project_name),
+            workspace: Some(self.workspace_slug.clone()),
+            labels,
+        }
+    }
+
+    /// Get the activity log for a work item (state transitions, edits, etc.)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails
+    pub async fn get_issue_activity(
+        &self,
+        project_id: &Uuid,
+        work_item_id: &Uuid,
+    ) -> Result<Vec<PlaneActivity>> {
+        let url = self.build_url(&format!(
+            "projects/{project_id}/work-items/{work_item_id}/activities/"
+        ));
+        let response: PaginatedResponse<PlaneActivity> = self.get(&url).await?;
+        Ok(response.results)
+    }
+
+    /// Find when a work item moved to a completed state, from its activity log
+    ///
+    /// Only the latest state transition counts: if the item was reopened after
+    /// being completed, `None` is returned.
+    ///
+    /// # Arguments
+    /// * `activities` - Activity log from [`Self::get_issue_activity`]
+    /// * `states` - Project states, used to identify the "completed" group
+    #[must_use]
+    pub fn completed_at(
+        activities: &[PlaneActivity],
+        states: &[PlaneState],
+    ) -> Option<DateTime<Utc>> {
+        let is_completed = |activity: &PlaneActivity| {
+            states.iter().any(|state| {
+                state.group.as_deref() == Some("completed")
+                    && (activity.new_identifier == Some(state.id)
+                        || activity.new_value.as_deref() == Some(state.name.as_str()))
+            })
+        };
+
+        let mut transitions: Vec<(DateTime<Utc>, &PlaneActivity)> = activities
+            .iter()
+            .filter(|a| a.field.as_deref() == Some("state"))
+            .filter_map(|a| {
+                DateTime::parse_from_rfc3339(&a.created_at)
+                    .ok()
+                    .map(|t| (t.with_timezone(&Utc), a))
+            })
+            .collect();
+        transitions.sort_by_key(|(time, _)| *time);
+
+        transitions
+            .last()
+            .filter(|(_, activity)| is_completed(activity))
+            .map(|(time, _)| *time)
+    }
+
     // ========================================================================
     // Worklog (Time Tracking) APIs
     // ========================================================================
@@ -558,22 +692,7 @@ impl ProjectManagementSystem for PlaneClient {
             .get_work_item_by_identifier(project_identifier, sequence_id)
             .await?;
 
-        let status = work_item
-            .state_detail
-            .map_or_else(|| "Unknown".to_string(), |s| s.name);
-
-        let project_name = work_item
-            .project_detail
-            .map_or_else(|| "Unknown".to_string(), |p| p.name);
-
-        Ok(WorkItemDetails {
-            id: work_item_id.to_string(),
-            title: work_item.name,
-            description: work_item.description_stripped,
-            status,
-            project: Some(project_name),
-            workspace: Some(self.workspace_slug.clone()),
-        })
+        Ok(self.to_work_item_details(work_item_id, work_item))
     }
 
     async fn add_time_entry(&self, entry: &TimeEntry) -> Result<()> {
@@ -637,7 +756,7 @@ impl ProjectManagementSystem for PlaneClient {
             .client
             .get(&url)
             .header("X-Api-Key", &self.api_key)
-            .send()
+            .send_logged("Plane")
             .await
             .context("Failed to connect to Plane API")?;
 
@@ -713,4 +832,103 @@ mod tests {
             "https://plane.example.com/api/v1/workspaces/my-workspace/projects/"
         );
     }
+
+    #[test]
+    fn test_work_item_details_include_expanded_labels() {
+        let client =
+            PlaneClient::new("test-key".to_string(), "my-workspace".to_string(), None).unwrap();
+        let work_item: PlaneWorkItem = serde_json::from_value(serde_json::json!({
+            "id": "33333333-3333-3333-3333-333333333333",
+            "name": "Fix login redirect",
+            "sequence_id": 42,
+            "project": "44444444-4444-4444-4444-444444444444",
+            "labels": [
+                {"id": "55555555-5555-5555-5555-555555555555", "name": "bug", "color": "#ff0000"},
+                {"id": "66666666-6666-6666-6666-666666666666", "name": "auth"}
+            ],
+            "created_at": "2025-01-10T09:00:00Z",
+            "updated_at": "2025-01-10T09:00:00Z"
+        }))
+        .unwrap();
+
+        let details = client.to_work_item_details("PROJ-42", work_item);
+        assert_eq!(details.id, "PROJ-42");
+        assert_eq!(details.labels, ["bug", "auth"]);
+
+        // Without `expand=labels` only ids come back, which carry no names
+        let unexpanded: PlaneWorkItem = serde_json::from_value(serde_json::json!({
+            "id": "33333333-3333-3333-3333-333333333333",
+            "name": "Fix login redirect",
+            "sequence_id": 42,
+            "project": "44444444-4444-4444-4444-444444444444",
+            "labels": ["55555555-5555-5555-5555-555555555555"],
+            "created_at": "2025-01-10T09:00:00Z",
+            "updated_at": "2025-01-10T09:00:00Z"
+        }))
+        .unwrap();
+        assert!(unexpanded.label_names().is_empty());
+    }
+
+    fn sample_states() -> Vec<PlaneState> {
+        serde_json::from_value(serde_json::json!([
+            {"id": "11111111-1111-1111-1111-111111111111", "name": "In Progress", "group": "started"},
+            {"id": "22222222-2222-2222-2222-222222222222", "name": "Done", "group": "completed"}
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_completed_at_from_activity_log() {
+        let activities: Vec<PlaneActivity> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "aaaaaaaa-0000-0000-0000-000000000001",
+                "created_at": "2025-01-10T09:00:00.000000Z",
+                "verb": "created",
+                "field": null
+            },
+            {
+                "id": "aaaaaaaa-0000-0000-0000-000000000002",
+                "created_at": "2025-01-11T10:00:00.000000Z",
+                "verb": "updated",
+                "field": "state",
+                "old_value": "Todo",
+                "new_value": "In Progress",
+                "new_identifier": "11111111-1111-1111-1111-111111111111"
+            },
+            {
+                "id": "aaaaaaaa-0000-0000-0000-000000000003",
+                "created_at": "2025-01-12T15:30:00.000000Z",
+                "verb": "updated",
+                "field": "state",
+                "old_value": "In Progress",
+                "new_value": "Done",
+                "new_identifier": "22222222-2222-2222-2222-222222222222"
+            }
+        ]))
+        .unwrap();
+
+        let done_at = PlaneClient::completed_at(&activities, &sample_states()).unwrap();
+        assert_eq!(done_at.to_rfc3339(), "2025-01-12T15:30:00+00:00");
+    }
+
+    #[test]
+    fn test_completed_at_none_when_reopened() {
+        let activities: Vec<PlaneActivity> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "aaaaaaaa-0000-0000-0000-000000000001",
+                "created_at": "2025-01-12T15:30:00Z",
+                "field": "state",
+                "new_value": "Done"
+            },
+            {
+                "id": "aaaaaaaa-0000-0000-0000-000000000002",
+                "created_at": "2025-01-13T08:00:00Z",
+                "field": "state",
+                "new_value": "In Progress"
+            }
+        ]))
+        .unwrap();
+
+        assert!(PlaneClient::completed_at(&activities, &sample_states()).is_none());
+    }
 }
@@ -0,0 +1,34 @@
+//! Request spacing shared by the integration clients
+//!
+//! APIs that limit requests per second or per window are kept under the limit
+//! by leaving a fixed interval between requests.
+
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Simple rate limiter that spaces requests `interval` apart
+pub(crate) struct RateLimiter {
+    last_request: Mutex<Instant>,
+    interval: Duration,
+}
+
+impl RateLimiter {
+    pub fn new(interval_ms: u64) -> Self {
+        Self {
+            last_request: Mutex::new(
+                Instant::now()
+                    .checked_sub(Duration::from_millis(interval_ms))
+                    .unwrap_or_else(Instant::now),
+            ),
+            interval: Duration::from_millis(interval_ms),
+        }
+    }
+
+    pub async fn wait(&self) {
+        let mut last = self.last_request.lock().await;
+        if let Some(remaining) = self.interval.checked_sub(last.elapsed()) {
+            tokio::time::sleep(remaining).await;
+        }
+        *last = Instant::now();
+    }
+}
@@ -0,0 +1,366 @@
+//! Opt-in debug log of integration API requests
+//!
+//! Enabled process-wide by the CLI from `integrations.debug_log`. Every request sent
+//! through [`crate::RequestExt::send_logged`] is appended to the log file; with bodies
+//! enabled, headers and bodies are included after credentials are redacted.
+
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Url};
+
+/// Replacement for redacted values
+const REDACTED: &str = "[REDACTED]";
+
+/// Bodies longer than this are truncated in the log
+const MAX_LOGGED_BODY_BYTES: usize = 16 * 1024;
+
+/// Header and field name fragments that mark a credential (lowercase, no separators)
+const SENSITIVE_KEY_FRAGMENTS: &[&str] = &[
+    "authorization",
+    "token",
+    "secret",
+    "password",
+    "apikey",
+    "cookie",
+    "credential",
+];
+
+/// Prefixes of API tokens issued by the supported services
+const TOKEN_PREFIXES: &[&str] = &[
+    "secret_",
+    "ntn_",
+    "ghp_",
+    "gho_",
+    "ghs_",
+    "ghu_",
+    "github_pat_",
+    "glpat-",
+    "plane_api_",
+];
+
+/// Hosts of incoming webhooks, whose URL path is itself the credential
+const SECRET_PATH_HOSTS: &[&str] = &["hooks.slack.com", "discord.com", "discordapp.com"];
+
+static REQUEST_LOG: RwLock<Option<RequestLogConfig>> = RwLock::new(None);
+
+#[derive(Debug, Clone)]
+struct RequestLogConfig {
+    path: PathBuf,
+    include_bodies: bool,
+}
+
+/// Append integration requests to `path` for the rest of this process
+///
+/// With `include_bodies`, redacted request headers and request/response bodies are
+/// logged as well.
+pub fn enable_request_log(path: impl Into<PathBuf>, include_bodies: bool) {
+    if let Ok(mut config) = REQUEST_LOG.write() {
+        *config = Some(RequestLogConfig {
+            path: path.into(),
+            include_bodies,
+        });
+    }
+}
+
+/// Stop logging integration requests
+pub fn disable_request_log() {
+    if let Ok(mut config) = REQUEST_LOG.write() {
+        *config = None;
+    }
+}
+
+/// Whether bodies should be captured, or `None` if the log is off
+pub(crate) fn active_log_level() -> Option<bool> {
+    REQUEST_LOG
+        .read()
+        .ok()
+        .and_then(|config| config.as_ref().map(|c| c.include_bodies))
+}
+
+/// One logged request/response pair
+#[derive(Debug)]
+pub(crate) struct RequestLogEntry<'a> {
+    pub api_name: &'a str,
+    pub method: &'a Method,
+    pub url: &'a Url,
+    pub outcome: Result<u16, String>,
+    pub elapsed: Duration,
+    pub request_headers: Option<&'a HeaderMap>,
+    pub request_body: Option<&'a [u8]>,
+    pub response_body: Option<&'a [u8]>,
+}
+
+impl RequestLogEntry<'_> {
+    /// Render the entry with all credentials redacted
+    pub(crate) fn render(&self) -> String {
+        let outcome = match &self.outcome {
+            Ok(status) => status.to_string(),
+            Err(error) => format!("error: {error}"),
+        };
+        let mut out = format!(
+            "{} {} {} {} -> {outcome} ({} ms)\n",
+            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            self.api_name,
+            self.method,
+            redact_url(self.url),
+            self.elapsed.as_millis()
+        );
+
+        if let Some(headers) = self.request_headers {
+            for (name, value) in headers {
+                let value = if is_sensitive_key(name.as_str()) {
+                    REDACTED.to_string()
+                } else {
+                    redact_text(&String::from_utf8_lossy(value.as_bytes()))
+                };
+                let _ = writeln!(out, "  > {name}: {value}");
+            }
+        }
+        if let Some(body) = self.request_body {
+            let _ = writeln!(out, "  > body: {}", redact_body(body));
+        }
+        if let Some(body) = self.response_body {
+            let _ = writeln!(out, "  < body: {}", redact_body(body));
+        }
+        out
+    }
+}
+
+/// Append an entry to the configured log file, if logging is enabled
+pub(crate) fn write_entry(entry: &RequestLogEntry<'_>) {
+    let Some(path) = REQUEST_LOG
+        .read()
+        .ok()
+        .and_then(|config| config.as_ref().map(|c| c.path.clone()))
+    else {
+        return;
+    };
+
+    if let Err(e) = append_to_file(&path, &entry.render()) {
+        log::warn!("Failed to write request log {}: {e}", path.display());
+    }
+}
+
+fn append_to_file(path: &Path, text: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(text.as_bytes())
+}
+
+/// Whether a header, query parameter or JSON field name holds a credential
+fn is_sensitive_key(key: &str) -> bool {
+    let normalized = key
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    SENSITIVE_KEY_FRAGMENTS
+        .iter()
+        .any(|fragment| normalized.contains(fragment))
+}
+
+/// Redact credential query parameters (e.g. `?private_token=...`) and webhook paths
+fn redact_url(url: &Url) -> String {
+    if url
+        .host_str()
+        .is_some_and(|host| SECRET_PATH_HOSTS.contains(&host))
+    {
+        let mut redacted = url.clone();
+        redacted.set_path(REDACTED);
+        redacted.set_query(None);
+        return redacted.to_string();
+    }
+    if url.query().is_none() {
+        return url.to_string();
+    }
+
+    let mut redacted = url.clone();
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| {
+            let value = if is_sensitive_key(&key) {
+                REDACTED.to_string()
+            } else {
+                value.into_owned()
+            };
+            (key.into_owned(), value)
+        })
+        .collect();
+    redacted.query_pairs_mut().clear().extend_pairs(pairs);
+    redacted.to_string()
+}
+
+/// Redact a request or response body
+///
+/// JSON bodies have credential fields replaced; other bodies only have
+/// recognizable tokens masked.
+fn redact_body(body: &[u8]) -> String {
+    let text = match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(mut json) => {
+            redact_json(&mut json);
+            json.to_string()
+        }
+        Err(_) => redact_text(&String::from_utf8_lossy(body)),
+    };
+
+    if text.len() > MAX_LOGGED_BODY_BYTES {
+        let mut end = MAX_LOGGED_BODY_BYTES;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        format!("{}... ({} bytes total)", &text[..end], text.len())
+    } else {
+        text
+    }
+}
+
+fn redact_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if is_sensitive_key(key) && !field.is_null() {
+                    *field = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_json),
+        serde_json::Value::String(text) => *text = redact_text(text),
+        _ => {}
+    }
+}
+
+/// Mask words that look like API tokens, including `Bearer` credentials
+fn redact_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut after_bearer = false;
+    for (i, word) in text.split(' ').enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        let token_like = TOKEN_PREFIXES
+            .iter()
+            .any(|prefix| word.trim_start_matches(['"', '\'']).starts_with(prefix));
+        if (after_bearer && !word.is_empty()) || token_like {
+            out.push_str(REDACTED);
+        } else {
+            out.push_str(word);
+        }
+        after_bearer = word.eq_ignore_ascii_case("bearer");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+
+    fn entry_with<'a>(
+        url: &'a Url,
+        headers: &'a HeaderMap,
+        request_body: &'a [u8],
+        response_body: &'a [u8],
+    ) -> RequestLogEntry<'a> {
+        RequestLogEntry {
+            api_name: "Notion",
+            method: &Method::POST,
+            url,
+            outcome: Ok(200),
+            elapsed: Duration::from_millis(42),
+            request_headers: Some(headers),
+            request_body: Some(request_body),
+            response_body: Some(response_body),
+        }
+    }
+
+    #[test]
+    fn test_authorization_header_redacted() {
+        let url = Url::parse("https://api.notion.com/v1/pages").unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_static("Bearer secret_abc123"),
+        );
+        headers.insert("X-Api-Key", HeaderValue::from_static("plane_api_xyz"));
+        headers.insert("PRIVATE-TOKEN", HeaderValue::from_static("glpat-123"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        let logged = entry_with(&url, &headers, b"{}", b"{}").render();
+
+        assert!(logged.contains("Notion POST https://api.notion.com/v1/pages -> 200 (42 ms)"));
+        assert!(logged.contains("authorization: [REDACTED]"));
+        assert!(logged.contains("x-api-key: [REDACTED]"));
+        assert!(logged.contains("private-token: [REDACTED]"));
+        assert!(logged.contains("content-type: application/json"));
+        for secret in ["secret_abc123", "plane_api_xyz", "glpat-123"] {
+            assert!(!logged.contains(secret), "{secret} leaked: {logged}");
+        }
+    }
+
+    #[test]
+    fn test_webhook_url_path_redacted() {
+        let url = Url::parse("https://hooks.slack.com/services/T0001/B0002/XyZsecret").unwrap();
+        let headers = HeaderMap::new();
+
+        let logged = entry_with(&url, &headers, b"{}", b"ok").render();
+
+        assert!(logged.contains("https://hooks.slack.com/[REDACTED]"));
+        for secret in ["T0001", "B0002", "XyZsecret"] {
+            assert!(!logged.contains(secret), "{secret} leaked: {logged}");
+        }
+    }
+
+    #[test]
+    fn test_token_like_fields_redacted() {
+        let url = Url::parse("https://gitlab.com/api/v4/projects?private_token=glpat-abc&page=2")
+            .unwrap();
+        let headers = HeaderMap::new();
+        let request = br#"{"title":"Fix login","api_key":"k-123","auth":{"refresh_token":"r-456"},"note":"uses ghp_deadbeef"}"#;
+        let response = b"error: invalid token Bearer abc.def.ghi";
+
+        let logged = entry_with(&url, &headers, request, response).render();
+
+        assert!(logged.contains("private_token=%5BREDACTED%5D&page=2"));
+        assert!(logged.contains(r#""title":"Fix login""#));
+        assert!(logged.contains(r#""api_key":"[REDACTED]""#));
+        assert!(logged.contains(r#""refresh_token":"[REDACTED]""#));
+        assert!(logged.contains("uses [REDACTED]"));
+        assert!(logged.contains("invalid token Bearer [REDACTED]"));
+        for secret in ["glpat-abc", "k-123", "r-456", "ghp_deadbeef", "abc.def.ghi"] {
+            assert!(!logged.contains(secret), "{secret} leaked: {logged}");
+        }
+    }
+
+    #[test]
+    fn test_requests_level_logs_no_bodies() {
+        let url = Url::parse("https://api.github.com/repos/o/r/issues").unwrap();
+        let entry = RequestLogEntry {
+            api_name: "GitHub",
+            method: &Method::GET,
+            url: &url,
+            outcome: Err("connection refused".to_string()),
+            elapsed: Duration::from_millis(5),
+            request_headers: None,
+            request_body: None,
+            response_body: None,
+        };
+
+        let logged = entry.render();
+        assert!(logged.ends_with(
+            "GitHub GET https://api.github.com/repos/o/r/issues -> error: connection refused (5 ms)\n"
+        ));
+        assert_eq!(logged.lines().count(), 1);
+    }
+}
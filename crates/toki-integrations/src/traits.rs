@@ -31,6 +31,8 @@ pub struct WorkItemDetails {
     pub status: String,
     pub project: Option<String>,
     pub workspace: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
 }
 
 /// Generic trait for Project Management System integrations
@@ -43,6 +45,25 @@ pub trait ProjectManagementSystem: Send + Sync {
     /// Returns an error if the API request fails or the item is not found
     async fn fetch_work_item(&self, work_item_id: &str) -> Result<WorkItemDetails>;
 
+    /// Fetch several work items, returning the ones that could be fetched
+    ///
+    /// Items that fail are logged and left out. The default fetches them one
+    /// at a time; systems with a batch or query endpoint override it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if the whole batch fails
+    async fn fetch_work_items_bulk(&self, ids: &[String]) -> Result<Vec<WorkItemDetails>> {
+        let mut items = Vec::with_capacity(ids.len());
+        for id in ids {
+            match self.fetch_work_item(id).await {
+                Ok(item) => items.push(item),
+                Err(e) => log::warn!("Failed to fetch {} work item {id}: {e}", self.system_name()),
+            }
+        }
+        Ok(items)
+    }
+
     /// Add a single time entry to the PM system
     ///
     /// # Errors
@@ -267,6 +288,25 @@ impl IssueSyncReport {
     }
 }
 
+impl IssueDetails {
+    /// Convert to the work item shape shared by all PM systems
+    #[must_use]
+    pub fn into_work_item(self, id: String, project: Option<String>) -> WorkItemDetails {
+        WorkItemDetails {
+            id,
+            title: self.title,
+            description: self.body,
+            status: match self.state {
+                IssueState::Open => "open".to_string(),
+                IssueState::Closed => "closed".to_string(),
+            },
+            project,
+            workspace: None,
+            labels: self.labels,
+        }
+    }
+}
+
 impl CreateIssueRequest {
     /// Create a new issue request with just title
     #[must_use]
@@ -312,6 +352,200 @@ impl CreateIssueRequest {
         self.source_system = Some(source_system);
         self
     }
+
+    /// Set the priority
+    #[must_use]
+    pub fn with_priority(mut self, priority: String) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Check whether an optional field is set on the request
+    #[must_use]
+    pub fn has_field(&self, field: IssueField) -> bool {
+        match field {
+            IssueField::Body => self.body.is_some(),
+            IssueField::Labels => !self.labels.is_empty(),
+            IssueField::Assignees => !self.assignees.is_empty(),
+            IssueField::Milestone => self.milestone.is_some(),
+            IssueField::Priority => self.priority.is_some(),
+            IssueField::DueDate => self.due_date.is_some(),
+        }
+    }
+
+    /// Value of an optional field as plain text (lists are comma-separated)
+    #[must_use]
+    pub fn field_text(&self, field: IssueField) -> Option<String> {
+        match field {
+            IssueField::Body => self.body.clone(),
+            IssueField::Labels => Some(self.labels.join(", ")).filter(|s| !s.is_empty()),
+            IssueField::Assignees => Some(self.assignees.join(", ")).filter(|s| !s.is_empty()),
+            IssueField::Milestone => self.milestone.clone(),
+            IssueField::Priority => self.priority.clone(),
+            IssueField::DueDate => self.due_date.map(|d| d.format("%Y-%m-%d").to_string()),
+        }
+    }
+}
+
+/// Optional `CreateIssueRequest` field that a target system may not support
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueField {
+    Body,
+    Labels,
+    Assignees,
+    Milestone,
+    Priority,
+    DueDate,
+}
+
+impl IssueField {
+    /// All optional fields, in request order
+    pub const ALL: [Self; 6] = [
+        Self::Body,
+        Self::Labels,
+        Self::Assignees,
+        Self::Milestone,
+        Self::Priority,
+        Self::DueDate,
+    ];
+
+    /// Field name as used in `CreateIssueRequest`
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Body => "body",
+            Self::Labels => "labels",
+            Self::Assignees => "assignees",
+            Self::Milestone => "milestone",
+            Self::Priority => "priority",
+            Self::DueDate => "due_date",
+        }
+    }
+
+    /// Whether the field holds a single value (and can be sent as a label)
+    #[must_use]
+    pub fn is_scalar(self) -> bool {
+        matches!(self, Self::Milestone | Self::Priority | Self::DueDate)
+    }
+}
+
+/// Target that sends a single-valued field as a `field: value` label
+pub const LABELS_TARGET: &str = "labels";
+
+/// Per-system mapping of `CreateIssueRequest` fields to target fields
+///
+/// Each entry names the field (GitHub/GitLab) or database property (Notion)
+/// that receives the request field. `None` means the system has nowhere to
+/// put it, so the field is dropped with a warning. On GitHub and GitLab a
+/// single-valued field mapped to [`LABELS_TARGET`] is sent as a label.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueFieldMapping {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignees: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+}
+
+impl IssueFieldMapping {
+    /// Native GitHub issue fields (no priority or due date)
+    #[must_use]
+    pub fn github() -> Self {
+        Self {
+            body: Some("body".to_string()),
+            labels: Some("labels".to_string()),
+            assignees: Some("assignees".to_string()),
+            milestone: Some("milestone".to_string()),
+            priority: None,
+            due_date: None,
+        }
+    }
+
+    /// Native GitLab issue fields (no priority; assignees need user IDs)
+    #[must_use]
+    pub fn gitlab() -> Self {
+        Self {
+            body: Some("description".to_string()),
+            labels: Some("labels".to_string()),
+            assignees: None,
+            milestone: Some("milestone_id".to_string()),
+            priority: None,
+            due_date: Some("due_date".to_string()),
+        }
+    }
+
+    /// Target of a request field, if the system supports it
+    #[must_use]
+    pub fn target(&self, field: IssueField) -> Option<&str> {
+        match field {
+            IssueField::Body => self.body.as_deref(),
+            IssueField::Labels => self.labels.as_deref(),
+            IssueField::Assignees => self.assignees.as_deref(),
+            IssueField::Milestone => self.milestone.as_deref(),
+            IssueField::Priority => self.priority.as_deref(),
+            IssueField::DueDate => self.due_date.as_deref(),
+        }
+    }
+
+    /// Whether a field is sent in its own native field
+    #[must_use]
+    pub fn sends(&self, field: IssueField) -> bool {
+        self.target(field).is_some() && !self.routes_to_labels(field)
+    }
+
+    fn routes_to_labels(&self, field: IssueField) -> bool {
+        field.is_scalar() && self.target(field) == Some(LABELS_TARGET)
+    }
+
+    /// Labels for single-valued fields routed to [`LABELS_TARGET`]
+    #[must_use]
+    pub fn routed_labels(&self, request: &CreateIssueRequest) -> Vec<String> {
+        IssueField::ALL
+            .into_iter()
+            .filter(|&field| self.routes_to_labels(field))
+            .filter_map(|field| {
+                request
+                    .field_text(field)
+                    .map(|value| format!("{}: {value}", field.as_str()))
+            })
+            .collect()
+    }
+
+    /// Fields set on the request that this mapping has nowhere to put
+    ///
+    /// `native` lists the fields the system can store itself; anything else
+    /// survives only when routed to labels.
+    #[must_use]
+    pub fn dropped_fields(
+        &self,
+        request: &CreateIssueRequest,
+        native: &[IssueField],
+    ) -> Vec<IssueField> {
+        IssueField::ALL
+            .into_iter()
+            .filter(|&field| request.has_field(field))
+            .filter(|&field| {
+                self.target(field).is_none() || (self.sends(field) && !native.contains(&field))
+            })
+            .collect()
+    }
+}
+
+/// Log a warning for request fields a system could not store
+pub(crate) fn warn_dropped_fields(system: &str, fields: &[IssueField]) {
+    for field in fields {
+        log::warn!(
+            "{system} does not support the issue field '{}', dropping it",
+            field.as_str()
+        );
+    }
 }
 
 /// Trait for issue management systems (GitHub, GitLab, etc.)
@@ -361,3 +595,54 @@ pub trait IssueManagement: Send + Sync {
     #[must_use]
     fn project_identifier(&self) -> &str;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// System where every ID except "missing" resolves
+    struct StubSystem;
+
+    #[async_trait]
+    impl ProjectManagementSystem for StubSystem {
+        async fn fetch_work_item(&self, work_item_id: &str) -> Result<WorkItemDetails> {
+            if work_item_id == "missing" {
+                anyhow::bail!("not found");
+            }
+            Ok(WorkItemDetails {
+                id: work_item_id.to_string(),
+                title: format!("Item {work_item_id}"),
+                description: None,
+                status: "open".to_string(),
+                project: None,
+                workspace: None,
+                labels: Vec::new(),
+            })
+        }
+
+        async fn add_time_entry(&self, _entry: &TimeEntry) -> Result<()> {
+            Ok(())
+        }
+
+        async fn batch_sync(&self, entries: Vec<TimeEntry>) -> Result<SyncReport> {
+            Ok(SyncReport::new(entries.len()))
+        }
+
+        async fn validate_credentials(&self) -> Result<bool> {
+            Ok(true)
+        }
+
+        fn system_name(&self) -> &'static str {
+            "stub"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_fetch_keeps_items_that_succeed() {
+        let ids = ["A-1", "missing", "A-2"].map(String::from);
+        let items = StubSystem.fetch_work_items_bulk(&ids).await.unwrap();
+
+        let fetched: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(fetched, ["A-1", "A-2"]);
+    }
+}
@@ -0,0 +1,389 @@
+//! Trello REST API client
+//!
+//! Implements `ProjectManagementSystem` for Trello cards and lists a board's
+//! open cards for AI matching. A card's list stands in for its status. Trello
+//! has no worklogs, so tracked time is posted as a card comment.
+//!
+//! Cards are identified by their short link, the 8-character id in
+//! `https://trello.com/c/<short link>`, which the API accepts wherever it
+//! takes a card id.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::{header, Client};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::backoff::{retry_async, Backoff};
+use crate::http::{ApiError, RequestExt, ResponseExt};
+use crate::linear::time_entry_comment;
+use crate::rate_limit::RateLimiter;
+use crate::traits::{ProjectManagementSystem, SyncReport, TimeEntry, WorkItemDetails};
+
+/// Trello REST API base URL
+const TRELLO_API_URL: &str = "https://api.trello.com/1";
+
+/// Minimum time between requests
+///
+/// Trello allows 300 requests per 10 seconds per API key and 100 per token.
+/// A client uses a single key and token, so it stays under the token limit.
+pub const RATE_LIMIT_INTERVAL_MS: u64 = 100;
+
+/// First delay before retrying a rate-limited (429) or failed (5xx) request
+const RETRY_BASE_DELAY_MS: u64 = 1000;
+
+/// Retries per request before the error is returned
+const MAX_RETRIES: u32 = 3;
+
+/// Longest wait before a retry, also the cap on a `Retry-After` from Trello
+const MAX_RETRY_DELAY_SECS: u64 = 30;
+
+/// Card fields toki reads
+const CARD_FIELDS: &str = "name,desc,shortLink,shortUrl,idList,labels";
+
+/// Trello API client
+pub struct TrelloClient {
+    client: Client,
+    api_url: String,
+    rate_limiter: RateLimiter,
+}
+
+/// Trello card (only the fields toki requests)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TrelloCard {
+    id: String,
+    name: String,
+    #[serde(default)]
+    desc: String,
+    short_link: String,
+    short_url: String,
+    id_list: String,
+    #[serde(default)]
+    labels: Vec<TrelloLabel>,
+    /// Only present when the card is fetched with `list=true`
+    #[serde(default)]
+    list: Option<TrelloList>,
+    /// Only present when the card is fetched with `board=true`
+    #[serde(default)]
+    board: Option<TrelloBoard>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloLabel {
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloList {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloBoard {
+    name: String,
+}
+
+/// Data for creating a local `IssueCandidate` entry from a Trello card
+#[derive(Debug, Clone, Serialize)]
+pub struct TrelloCardCandidateData {
+    pub external_id: String, // Short link, e.g. "aB3dE5fG"
+    pub external_system: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String, // Name of the card's list
+    pub board_id: String,
+    pub card_id: String,
+    pub labels: Vec<String>,
+    pub url: String,
+}
+
+impl TrelloCard {
+    /// Labels can be color-only; those have no name to match on
+    fn label_names(&self) -> Vec<String> {
+        self.labels
+            .iter()
+            .filter(|l| !l.name.is_empty())
+            .map(|l| l.name.clone())
+            .collect()
+    }
+
+    fn description(&self) -> Option<String> {
+        Some(self.desc.trim().to_string()).filter(|desc| !desc.is_empty())
+    }
+
+    fn to_candidate_data(
+        &self,
+        board_id: &str,
+        list_names: &HashMap<String, String>,
+    ) -> TrelloCardCandidateData {
+        TrelloCardCandidateData {
+            external_id: self.short_link.clone(),
+            external_system: "trello".to_string(),
+            title: self.name.clone(),
+            description: self.description(),
+            status: list_names.get(&self.id_list).cloned().unwrap_or_default(),
+            board_id: board_id.to_string(),
+            card_id: self.id.clone(),
+            labels: self.label_names(),
+            url: self.short_url.clone(),
+        }
+    }
+
+    fn into_work_item(self) -> WorkItemDetails {
+        let labels = self.label_names();
+        let description = self.description();
+        WorkItemDetails {
+            id: self.short_link,
+            title: self.name,
+            description,
+            status: self.list.map(|l| l.name).unwrap_or_default(),
+            project: self.board.map(|b| b.name),
+            workspace: None,
+            labels,
+        }
+    }
+}
+
+impl TrelloClient {
+    /// Create a new Trello client
+    ///
+    /// # Arguments
+    /// * `api_key` - API key of a Power-Up (<https://trello.com/power-ups/admin>)
+    /// * `token` - User token authorized for that key
+    ///
+    /// # Errors
+    /// Returns an error if the credentials are not valid header text or the
+    /// HTTP client cannot be created
+    pub fn new(api_key: &str, token: &str) -> Result<Self> {
+        let mut authorization = header::HeaderValue::from_str(&format!(
+            "OAuth oauth_consumer_key=\"{api_key}\", oauth_token=\"{token}\""
+        ))
+        .context("Invalid API key or token format")?;
+        authorization.set_sensitive(true);
+
+        let mut headers = header::HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, authorization);
+        headers.insert(
+            header::ACCEPT,
+            header::HeaderValue::from_static("application/json"),
+        );
+        headers.insert(
+            header::USER_AGENT,
+            header::HeaderValue::from_static("toki-time-tracker"),
+        );
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .timeout(Duration::from_secs(30))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            api_url: TRELLO_API_URL.to_string(),
+            rate_limiter: RateLimiter::new(RATE_LIMIT_INTERVAL_MS),
+        })
+    }
+
+    /// Send a request built by `build`, retrying failed attempts that `is_retriable` accepts
+    async fn send_with_retry<T: DeserializeOwned>(
+        &self,
+        is_retriable: fn(&anyhow::Error) -> bool,
+        build: impl Fn() -> reqwest::RequestBuilder,
+    ) -> Result<T> {
+        let build = &build;
+        retry_async(
+            Backoff::new(Duration::from_millis(RETRY_BASE_DELAY_MS))
+                .max_delay(Duration::from_secs(MAX_RETRY_DELAY_SECS))
+                .max_retries(MAX_RETRIES),
+            is_retriable,
+            move || async move {
+                self.rate_limiter.wait().await;
+
+                build()
+                    .send_logged("Trello")
+                    .await
+                    .context("Failed to send request to Trello API")?
+                    .ensure_success("Trello")
+                    .await?
+                    .json()
+                    .await
+                    .context("Failed to parse Trello API response")
+            },
+        )
+        .await
+    }
+
+    /// Make a GET request to an API path
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = format!("{}/{path}", self.api_url);
+        log::debug!("GET {url}");
+        self.send_with_retry(ApiError::is_transient_error, || self.client.get(&url))
+            .await
+    }
+
+    /// Get a card by id or short link, with its list and board names
+    async fn get_card(&self, card_id: &str) -> Result<TrelloCard> {
+        self.get(&format!(
+            "cards/{}?fields={CARD_FIELDS}&list=true&board=true&board_fields=name",
+            urlencoding::encode(card_id)
+        ))
+        .await
+    }
+
+    /// Get a board's name, which also checks that the board is accessible
+    ///
+    /// # Errors
+    /// Returns an error if the API request fails or the board does not exist
+    pub async fn board_name(&self, board_id: &str) -> Result<String> {
+        let board: TrelloBoard = self
+            .get(&format!(
+                "boards/{}?fields=name",
+                urlencoding::encode(board_id)
+            ))
+            .await?;
+        Ok(board.name)
+    }
+
+    /// List a board's open (not archived) cards for AI matching
+    ///
+    /// # Errors
+    /// Returns an error if the API request fails
+    pub async fn list_cards(&self, board_id: &str) -> Result<Vec<TrelloCardCandidateData>> {
+        let board = urlencoding::encode(board_id);
+        let lists: Vec<TrelloList> = self
+            .get(&format!("boards/{board}/lists?fields=name"))
+            .await?;
+        let list_names: HashMap<String, String> =
+            lists.into_iter().map(|list| (list.id, list.name)).collect();
+
+        let cards: Vec<TrelloCard> = self
+            .get(&format!("boards/{board}/cards/open?fields={CARD_FIELDS}"))
+            .await?;
+
+        log::debug!(
+            "Fetched {} open cards from Trello board {board_id}",
+            cards.len()
+        );
+        Ok(cards
+            .iter()
+            .map(|card| card.to_candidate_data(board_id, &list_names))
+            .collect())
+    }
+
+    /// Post a comment on a card
+    ///
+    /// Resent only if Trello never processed it, so a timeout cannot post it twice.
+    async fn add_comment(&self, card_id: &str, text: &str) -> Result<()> {
+        let url = format!(
+            "{}/cards/{}/actions/comments",
+            self.api_url,
+            urlencoding::encode(card_id)
+        );
+        log::debug!("POST {url}");
+        let _: serde_json::Value = self
+            .send_with_retry(ApiError::is_unprocessed_error, || {
+                self.client.post(&url).json(&json!({ "text": text }))
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ProjectManagementSystem for TrelloClient {
+    async fn fetch_work_item(&self, work_item_id: &str) -> Result<WorkItemDetails> {
+        Ok(self.get_card(work_item_id).await?.into_work_item())
+    }
+
+    async fn add_time_entry(&self, entry: &TimeEntry) -> Result<()> {
+        log::debug!(
+            "Adding time to Trello card {}: {} seconds",
+            entry.work_item_id,
+            entry.duration_seconds
+        );
+        self.add_comment(&entry.work_item_id, &time_entry_comment(entry))
+            .await
+    }
+
+    async fn batch_sync(&self, entries: Vec<TimeEntry>) -> Result<SyncReport> {
+        let mut report = SyncReport::new(entries.len());
+
+        for entry in entries {
+            match self.add_time_entry(&entry).await {
+                Ok(()) => report.record_success(),
+                Err(e) => report.record_failure(format!("Card {}: {e}", entry.work_item_id)),
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn validate_credentials(&self) -> Result<bool> {
+        let member: Result<serde_json::Value> = self.get("members/me?fields=id").await;
+        Ok(member.is_ok())
+    }
+
+    fn system_name(&self) -> &'static str {
+        "trello"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card_json() -> serde_json::Value {
+        json!({
+            "id": "65f1c0ffee0000000000abcd",
+            "name": "Export invoices as CSV",
+            "desc": "  Include the client name\n",
+            "shortLink": "aB3dE5fG",
+            "shortUrl": "https://trello.com/c/aB3dE5fG",
+            "idList": "65f1c0ffee00000000000001",
+            "labels": [{ "name": "billing" }, { "name": "" }],
+        })
+    }
+
+    #[test]
+    fn test_card_maps_list_to_status_for_candidates() {
+        let card: TrelloCard = serde_json::from_value(card_json()).unwrap();
+        let list_names =
+            HashMap::from([("65f1c0ffee00000000000001".to_string(), "Doing".to_string())]);
+
+        let candidate = card.to_candidate_data("board-1", &list_names);
+        assert_eq!(candidate.external_id, "aB3dE5fG");
+        assert_eq!(candidate.external_system, "trello");
+        assert_eq!(candidate.status, "Doing");
+        assert_eq!(candidate.card_id, "65f1c0ffee0000000000abcd");
+        assert_eq!(
+            candidate.description.as_deref(),
+            Some("Include the client name")
+        );
+        assert_eq!(candidate.labels, ["billing"]);
+        assert_eq!(candidate.url, "https://trello.com/c/aB3dE5fG");
+    }
+
+    #[test]
+    fn test_card_with_list_and_board_maps_to_work_item() {
+        let mut value = card_json();
+        value["desc"] = json!("");
+        value["list"] = json!({ "id": "65f1c0ffee00000000000001", "name": "Review" });
+        value["board"] = json!({ "name": "Side projects" });
+        let card: TrelloCard = serde_json::from_value(value).unwrap();
+
+        let item = card.into_work_item();
+        assert_eq!(item.id, "aB3dE5fG");
+        assert_eq!(item.title, "Export invoices as CSV");
+        assert_eq!(item.description, None);
+        assert_eq!(item.status, "Review");
+        assert_eq!(item.project.as_deref(), Some("Side projects"));
+    }
+}
@@ -1,10 +1,26 @@
-//! Plane.so Webhook handling
+//! Webhook handling
 //!
-//! This module provides types and utilities for handling Plane.so webhooks.
+//! This module provides types and utilities for handling Plane.so webhooks,
+//! and for posting messages to Slack incoming webhooks.
 
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::http::{RequestExt, ResponseExt};
+
+/// How long a delivery id is remembered for replay detection
+pub const DEFAULT_REPLAY_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// Maximum allowed distance between a payload timestamp and now
+pub const DEFAULT_TIMESTAMP_TOLERANCE_SECS: i64 = 5 * 60;
+
+/// Upper bound on remembered delivery ids, oldest are evicted first
+const MAX_TRACKED_DELIVERIES: usize = 10_000;
+
 /// Plane.so webhook event types
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -148,6 +164,98 @@ fn constant_time_compare(a: &str, b: &str) -> bool {
     result == 0
 }
 
+/// Rejects replayed webhook deliveries
+///
+/// Remembers recently seen delivery ids (the `X-Plane-Delivery` header) for a
+/// TTL and, when the payload carries a timestamp, rejects deliveries outside
+/// the allowed window. Only record deliveries whose signature was verified,
+/// otherwise forged requests could poison the set.
+#[derive(Debug, Clone)]
+pub struct ReplayGuard {
+    seen: HashMap<String, DateTime<Utc>>,
+    ttl: Duration,
+    timestamp_tolerance: Option<Duration>,
+}
+
+impl Default for ReplayGuard {
+    fn default() -> Self {
+        Self::new(Duration::seconds(DEFAULT_REPLAY_TTL_SECS))
+            .with_timestamp_tolerance(Some(Duration::seconds(DEFAULT_TIMESTAMP_TOLERANCE_SECS)))
+    }
+}
+
+impl ReplayGuard {
+    /// Create a guard that remembers delivery ids for `ttl`, without timestamp checks
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            seen: HashMap::new(),
+            ttl,
+            timestamp_tolerance: None,
+        }
+    }
+
+    /// Set the allowed clock distance for payload timestamps (`None` disables the check)
+    #[must_use]
+    pub fn with_timestamp_tolerance(mut self, tolerance: Option<Duration>) -> Self {
+        self.timestamp_tolerance = tolerance;
+        self
+    }
+
+    /// Check a delivery and remember its id if it is accepted
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the delivery id was already seen within the TTL,
+    /// or the payload timestamp is unparseable or outside the allowed window
+    pub fn check(
+        &mut self,
+        delivery_id: &str,
+        timestamp: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        if let (Some(tolerance), Some(timestamp)) = (self.timestamp_tolerance, timestamp) {
+            let sent_at = DateTime::parse_from_rfc3339(timestamp)
+                .map_err(|e| anyhow::anyhow!("Invalid webhook timestamp {timestamp}: {e}"))?
+                .with_timezone(&Utc);
+            if (now - sent_at).abs() > tolerance {
+                anyhow::bail!("Webhook timestamp {timestamp} is outside the allowed window");
+            }
+        }
+
+        self.seen.retain(|_, seen_at| now - *seen_at < self.ttl);
+        if self.seen.contains_key(delivery_id) {
+            anyhow::bail!("Duplicate webhook delivery: {delivery_id}");
+        }
+
+        if self.seen.len() >= MAX_TRACKED_DELIVERIES {
+            if let Some(oldest) = self
+                .seen
+                .iter()
+                .min_by_key(|(_, seen_at)| **seen_at)
+                .map(|(id, _)| id.clone())
+            {
+                self.seen.remove(&oldest);
+            }
+        }
+        self.seen.insert(delivery_id.to_string(), now);
+        Ok(())
+    }
+}
+
+/// Process a signature-verified webhook, rejecting replayed deliveries
+#[must_use]
+pub fn process_webhook_once(
+    guard: &mut ReplayGuard,
+    delivery_id: &str,
+    payload: &PlaneWebhookPayload,
+) -> WebhookResult {
+    match guard.check(delivery_id, payload.timestamp.as_deref(), Utc::now()) {
+        Ok(()) => process_webhook(payload),
+        Err(e) => WebhookResult::failure(e.to_string()),
+    }
+}
+
 /// Process a webhook payload and extract relevant information
 ///
 /// # Errors
@@ -186,6 +294,36 @@ pub fn process_webhook(payload: &PlaneWebhookPayload) -> WebhookResult {
     }
 }
 
+/// Slack incoming-webhook message body
+#[derive(Debug, Serialize)]
+struct SlackMessage<'a> {
+    text: &'a str,
+}
+
+/// Post a message to a Slack incoming webhook
+///
+/// `text` may use Slack mrkdwn.
+///
+/// # Errors
+///
+/// Returns an error if the request cannot be sent or Slack does not accept
+/// it; Slack's response body (e.g. `invalid_token`) is part of the error.
+pub async fn post_to_slack(webhook_url: &str, text: &str) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .context("Failed to create HTTP client")?;
+    client
+        .post(webhook_url)
+        .json(&SlackMessage { text })
+        .send_logged("Slack")
+        .await
+        .context("Failed to send message to Slack webhook")?
+        .ensure_success("Slack")
+        .await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,5 +372,61 @@ mod tests {
         assert!(result.success);
         assert!(result.message.contains("Test Issue"));
     }
-}
 
+    fn deleted_payload(timestamp: Option<String>) -> PlaneWebhookPayload {
+        PlaneWebhookPayload {
+            event: PlaneEventType::IssueDeleted,
+            action: Some("DELETE".to_string()),
+            data: serde_json::json!({}),
+            workspace_id: None,
+            project_id: None,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_duplicate_delivery_is_rejected() {
+        let mut guard = ReplayGuard::default();
+        let payload = deleted_payload(None);
+
+        assert!(process_webhook_once(&mut guard, "delivery-1", &payload).success);
+
+        let replay = process_webhook_once(&mut guard, "delivery-1", &payload);
+        assert!(!replay.success);
+        assert!(replay.message.contains("Duplicate"));
+
+        assert!(process_webhook_once(&mut guard, "delivery-2", &payload).success);
+    }
+
+    #[test]
+    fn test_delivery_id_forgotten_after_ttl() {
+        let mut guard = ReplayGuard::new(Duration::minutes(10));
+        let now = Utc::now();
+        let later = now + Duration::minutes(5);
+        let expired = now + Duration::minutes(11);
+
+        assert!(guard.check("delivery-1", None, now).is_ok());
+        assert!(guard.check("delivery-1", None, later).is_err());
+        assert!(guard.check("delivery-1", None, expired).is_ok());
+    }
+
+    #[test]
+    fn test_stale_timestamp_is_rejected() {
+        let mut guard = ReplayGuard::default();
+
+        let stale = deleted_payload(Some((Utc::now() - Duration::hours(1)).to_rfc3339()));
+        assert!(!process_webhook_once(&mut guard, "delivery-1", &stale).success);
+
+        let fresh = deleted_payload(Some(Utc::now().to_rfc3339()));
+        assert!(process_webhook_once(&mut guard, "delivery-2", &fresh).success);
+    }
+
+    #[test]
+    fn test_slack_message_is_text_payload() {
+        let body = serde_json::to_value(SlackMessage {
+            text: "*Yesterday*\n- toki",
+        })
+        .unwrap();
+        assert_eq!(body, serde_json::json!({ "text": "*Yesterday*\n- toki" }));
+    }
+}
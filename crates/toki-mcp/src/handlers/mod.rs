@@ -15,17 +15,21 @@ use rmcp::{
 };
 use toki_ai::{NotionIssueSyncService, SyncOptions, SyncOutcome, SyncResult};
 use toki_integrations::IssueSyncReport;
-use toki_ai::issue_matcher::{ActivitySignals, SmartIssueMatcher};
+use toki_ai::issue_matcher::{ActivitySignals, CandidateCache, IssueMatch, SmartIssueMatcher};
 use toki_ai::standup::{StandupFormat, StandupGenerator};
 use toki_ai::work_summary::{SummaryPeriod, WorkSummaryGenerator};
 use toki_detector::git::GitDetector;
 use toki_integrations::{GitHubClient, GitLabClient, NotionClient};
-use toki_storage::{Database, IntegrationConfig};
+use toki_storage::{ActivitySpan, Database, IntegrationConfig};
 use std::path::PathBuf;
+use uuid::Uuid;
 
 #[cfg(test)]
 mod tests;
 
+/// Minutes of recent activity `match_current_issue` collects signals from
+const CURRENT_ISSUE_WINDOW_MINUTES: i64 = 30;
+
 /// Request for listing pages in a Notion database
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct ListPagesRequest {
@@ -83,6 +87,15 @@ pub struct SuggestIssueRequest {
     pub max_suggestions: Option<usize>,
 }
 
+/// Request for matching the current activity to an issue
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct MatchCurrentIssueRequest {
+    #[schemars(description = "Project ID or name (default: the project of the most recent activity)")]
+    pub project_id: Option<String>,
+    #[schemars(description = "Maximum number of matches to return (default: 5)")]
+    pub max_results: Option<usize>,
+}
+
 /// Request for generating work summary
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct GenerateSummaryRequest {
@@ -103,6 +116,48 @@ pub struct GenerateStandupRequest {
     pub date: Option<String>,
 }
 
+/// Activity signals from the context recorded on recent spans, newest branch first
+fn signals_from_spans(spans: &[ActivitySpan]) -> ActivitySignals {
+    let mut signals = ActivitySignals::default();
+    for context in spans.iter().rev().filter_map(|span| span.context.as_ref()) {
+        if signals.git_branch.is_none() {
+            signals.git_branch.clone_from(&context.git_branch);
+        }
+        for (values, found) in [
+            (&mut signals.recent_commits, &context.git_commits),
+            (&mut signals.edited_files, &context.edited_files),
+            (&mut signals.browser_urls, &context.browser_urls),
+            (&mut signals.terminal_commands, &context.terminal_commands),
+        ] {
+            for value in found {
+                if !values.contains(value) {
+                    values.push(value.clone());
+                }
+            }
+        }
+    }
+    signals
+}
+
+/// Project of the most recent span that has one
+fn active_project_id(spans: &[ActivitySpan]) -> Option<Uuid> {
+    spans.iter().rev().find_map(|span| span.project_id)
+}
+
+/// Matches as the `match_current_issue` JSON array
+fn matches_json(matches: &[IssueMatch]) -> serde_json::Value {
+    matches
+        .iter()
+        .map(|m| {
+            serde_json::json!({
+                "issue_id": m.issue_id,
+                "confidence": m.confidence,
+                "reasons": SmartIssueMatcher::format_reasons(&m.match_reasons),
+            })
+        })
+        .collect()
+}
+
 /// Format sync results into a human-readable output string.
 fn format_sync_output(report: &IssueSyncReport, results: &[SyncResult]) -> String {
     let mut output = String::from("Sync completed:\n\n");
@@ -121,9 +176,19 @@ fn format_sync_output(report: &IssueSyncReport, results: &[SyncResult]) -> Strin
             SyncOutcome::Failed { error } => {
                 let _ = writeln!(output, "[FAILED] {} - {}", result.title, error);
             }
+            SyncOutcome::Updated { issue_number, issue_url } => {
+                let _ = writeln!(
+                    output,
+                    "[UPDATED] #{} {} -> {}",
+                    issue_number, result.title, issue_url
+                );
+            }
             SyncOutcome::WouldCreate => {
                 let _ = writeln!(output, "[WOULD CREATE] {}", result.title);
             }
+            SyncOutcome::WouldUpdate { issue_number, .. } => {
+                let _ = writeln!(output, "[WOULD UPDATE] #{} {}", issue_number, result.title);
+            }
         }
     }
     let _ = write!(
@@ -131,6 +196,9 @@ fn format_sync_output(report: &IssueSyncReport, results: &[SyncResult]) -> Strin
         "\nSummary: {} created, {} skipped, {} failed",
         report.created, report.skipped, report.failed
     );
+    if report.updated > 0 {
+        let _ = write!(output, ", {} updated", report.updated);
+    }
     output
 }
 
@@ -138,19 +206,53 @@ fn format_sync_output(report: &IssueSyncReport, results: &[SyncResult]) -> Strin
 #[derive(Clone)]
 pub struct TokiService {
     db: Arc<Database>,
+    /// Ranked issue candidates shared by every matcher this server builds
+    candidate_cache: Arc<CandidateCache>,
     tool_router: ToolRouter<Self>,
 }
 
 impl TokiService {
     /// Create a new Toki service
+    ///
+    /// Issue candidates of linked projects are warmed up front unless
+    /// `candidate_warmup_threads` is 0.
     pub fn new() -> anyhow::Result<Self> {
         let db = Database::new(None).context("Failed to open database")?;
+        let settings = db.get_settings()?;
+        let candidate_cache = Arc::new(CandidateCache::new());
+        if settings.candidate_warmup_threads > 0 {
+            let threads = usize::try_from(settings.candidate_warmup_threads).unwrap_or(usize::MAX);
+            match candidate_cache.warm(&db, threads) {
+                Ok(projects) => log::info!("Warmed issue candidates for {projects} projects"),
+                Err(e) => log::warn!("Issue candidate warmup failed: {e}"),
+            }
+        }
         Ok(Self {
             db: Arc::new(db),
+            candidate_cache,
             tool_router: Self::tool_router(),
         })
     }
 
+    /// Issue matcher reading candidates from the warmed cache
+    ///
+    /// The cache is warmed again first if an issue sync ran since. With
+    /// `candidate_warmup_threads` at 0 the matcher reads the database directly.
+    fn issue_matcher(&self) -> anyhow::Result<SmartIssueMatcher> {
+        let settings = self.db.get_settings()?;
+        let matcher = SmartIssueMatcher::with_pattern(
+            self.db.clone(),
+            settings.issue_id_pattern.as_deref(),
+            settings.embeddings_enabled,
+        )?;
+        if settings.candidate_warmup_threads == 0 {
+            return Ok(matcher);
+        }
+        let threads = usize::try_from(settings.candidate_warmup_threads).unwrap_or(usize::MAX);
+        self.candidate_cache.refresh(&self.db, threads)?;
+        Ok(matcher.with_candidate_cache(self.candidate_cache.clone()))
+    }
+
     /// Get Notion client if configured
     fn get_notion_client(&self) -> anyhow::Result<NotionClient> {
         let config = self
@@ -244,6 +346,8 @@ impl TokiService {
         let db_info = client.get_database(&req.database_id).await
             .map_err(|e| Self::format_error(&e))?;
         let mapping = db_info.detect_property_mapping(None);
+        let workspace = self.db.get_notion_workspace(&req.database_id)
+            .map_err(|e| Self::format_error(&e))?;
 
         let mut result = format!("Pages in database ({} total):\n\n", pages.len());
         for page in &pages {
@@ -255,7 +359,11 @@ impl TokiService {
                 .and_then(|prop| page.properties.get(prop))
                 .and_then(toki_integrations::NotionPropertyValue::as_select_name)
                 .unwrap_or_else(|| "-".to_string());
-            let external_id = NotionClient::generate_external_id(&req.database_id, &page.id);
+            let external_id = NotionClient::generate_external_id(
+                workspace.as_deref(),
+                &req.database_id,
+                &page.id,
+            );
             let _ = writeln!(result, "- {title} [{status}] (ID: {external_id})");
         }
         Ok(CallToolResult::success(vec![Content::text(result)]))
@@ -440,6 +548,7 @@ impl TokiService {
             edited_files: files.clone(),
             browser_urls: Vec::new(),
             window_titles: Vec::new(),
+            terminal_commands: Vec::new(),
         };
 
         // Build context output
@@ -468,8 +577,7 @@ impl TokiService {
             )))?;
 
         // Create matcher and find suggestions
-        let matcher = SmartIssueMatcher::new(self.db.clone())
-            .map_err(|e| Self::format_error(&e))?;
+        let matcher = self.issue_matcher().map_err(|e| Self::format_error(&e))?;
 
         let suggestions = matcher.find_best_matches(&signals, project.id, max_suggestions)
             .map_err(|e| Self::format_error(&e))?;
@@ -501,6 +609,69 @@ impl TokiService {
         Ok(CallToolResult::success(vec![Content::text(result)]))
     }
 
+    /// Match what the user is working on right now to an issue
+    #[tool(description = "Answer 'what issue am I working on right now?'. Collects the branch, commits, edited files and browser URLs from the last 30 minutes of activity and matches them against the project's synced issues. Returns a JSON array of {issue_id, confidence, reasons}.")]
+    async fn match_current_issue(
+        &self,
+        Parameters(req): Parameters<MatchCurrentIssueRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let now = chrono::Utc::now();
+        let mut spans = self
+            .db
+            .get_activity_spans(now - chrono::Duration::minutes(CURRENT_ISSUE_WINDOW_MINUTES), now)
+            .map_err(|e| Self::format_error(&e))?;
+        if let Some(ongoing) = self.db.get_ongoing_span().map_err(|e| Self::format_error(&e))? {
+            if !spans.iter().any(|span| span.id == ongoing.id) {
+                spans.push(ongoing);
+            }
+        }
+
+        let project = match &req.project_id {
+            Some(project) => match Uuid::parse_str(project) {
+                Ok(id) => self.db.get_project(id),
+                Err(_) => self.db.get_project_by_name(project),
+            }
+            .map_err(|e| Self::format_error(&e))?,
+            None => match active_project_id(&spans) {
+                Some(id) => self.db.get_project(id).map_err(|e| Self::format_error(&e))?,
+                None => None,
+            },
+        };
+        let Some(project) = project else {
+            return Ok(CallToolResult::success(vec![Content::text(
+                "No active project: no recent activity in a tracked project. Pass project_id.",
+            )]));
+        };
+
+        let mut signals = signals_from_spans(&spans);
+        // The repository's own state is fresher than what the spans recorded
+        let git_detector = GitDetector::new();
+        if let Ok(Some(repo_path)) = git_detector.find_repo(std::path::Path::new(&project.path)) {
+            if let Ok(Some(branch)) = git_detector.get_branch_name(&repo_path) {
+                signals.git_branch = Some(branch);
+            }
+            for commit in git_detector.get_recent_commits(&repo_path, 5).unwrap_or_default() {
+                if !signals.recent_commits.contains(&commit) {
+                    signals.recent_commits.push(commit);
+                }
+            }
+            for file in git_detector.get_changed_files(&repo_path).unwrap_or_default() {
+                if !signals.edited_files.contains(&file) {
+                    signals.edited_files.push(file);
+                }
+            }
+        }
+
+        let matcher = self.issue_matcher().map_err(|e| Self::format_error(&e))?;
+        let matches = matcher
+            .find_best_matches(&signals, project.id, req.max_results.unwrap_or(5))
+            .map_err(|e| Self::format_error(&e))?;
+
+        let output = serde_json::to_string_pretty(&matches_json(&matches))
+            .map_err(|e| Self::format_error(&e.into()))?;
+        Ok(CallToolResult::success(vec![Content::text(output)]))
+    }
+
     /// Generate a work summary
     #[tool(description = "Generate a natural language summary of work activity. Summarizes Claude Code sessions, time spent, and projects worked on.")]
     async fn generate_summary(
@@ -262,3 +262,76 @@ fn test_config_key_valid_fields() {
         assert!(valid_fields.contains(&parts[1]));
     }
 }
+
+fn span_with_context(
+    project_id: Option<Uuid>,
+    branch: Option<&str>,
+    commits: &[&str],
+    urls: &[&str],
+) -> ActivitySpan {
+    let mut span = ActivitySpan::new(
+        "com.microsoft.VSCode".to_string(),
+        "Development".to_string(),
+        chrono::Utc::now(),
+        project_id,
+        None,
+        None,
+    );
+    let context = span.context_mut();
+    context.git_branch = branch.map(String::from);
+    context.git_commits = commits.iter().map(|c| (*c).to_string()).collect();
+    context.browser_urls = urls.iter().map(|u| (*u).to_string()).collect();
+    span
+}
+
+#[test]
+fn test_current_signals_use_latest_branch_and_dedupe() {
+    let api = Uuid::new_v4();
+    let spans = vec![
+        span_with_context(Some(api), Some("main"), &["Fix login"], &[]),
+        span_with_context(None, None, &[], &["https://linear.app/acme/issue/PROJ-7"]),
+        span_with_context(
+            Some(api),
+            Some("feature/PROJ-7-sync"),
+            &["Fix login", "Add sync"],
+            &[],
+        ),
+        span_with_context(None, None, &[], &[]),
+    ];
+
+    let signals = signals_from_spans(&spans);
+    assert_eq!(signals.git_branch.as_deref(), Some("feature/PROJ-7-sync"));
+    assert_eq!(signals.recent_commits, ["Fix login", "Add sync"]);
+    assert_eq!(
+        signals.browser_urls,
+        ["https://linear.app/acme/issue/PROJ-7"]
+    );
+    assert_eq!(active_project_id(&spans), Some(api));
+    assert_eq!(active_project_id(&spans[1..2]), None);
+    assert!(signals_from_spans(&[]).is_empty());
+}
+
+#[test]
+fn test_matches_json_lists_confidence_and_reasons() {
+    use toki_ai::issue_matcher::MatchReason;
+
+    let matches = vec![IssueMatch {
+        issue_id: "PROJ-7".to_string(),
+        confidence: 0.75,
+        match_reasons: vec![
+            MatchReason::BranchName,
+            MatchReason::SemanticSimilarity(0.5),
+        ],
+    }];
+
+    let json = matches_json(&matches);
+    assert_eq!(
+        json,
+        serde_json::json!([{
+            "issue_id": "PROJ-7",
+            "confidence": 0.75,
+            "reasons": "Git branch, Semantic (50%)",
+        }])
+    );
+    assert_eq!(matches_json(&[]), serde_json::json!([]));
+}
@@ -18,11 +18,13 @@ impl Database {
             .as_ref()
             .and_then(|c| serde_json::to_string(c).ok());
 
-        self.conn.execute(
-            "INSERT INTO activity_spans
-             (id, app_bundle_id, category, start_time, end_time, duration_seconds, project_id, work_item_id, session_id, context)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
-            params![
+        self.conn
+            .prepare_cached(
+                "INSERT INTO activity_spans
+                 (id, app_bundle_id, category, start_time, end_time, duration_seconds, project_id, work_item_id, session_id, context)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )?
+            .execute(params![
                 span.id.to_string(),
                 span.app_bundle_id,
                 span.category,
@@ -33,8 +35,7 @@ impl Database {
                 span.work_item_id.map(|id| id.to_string()),
                 span.session_id.map(|id| id.to_string()),
                 context_json,
-            ],
-        )?;
+            ])?;
         Ok(span.id)
     }
 
@@ -48,11 +49,12 @@ impl Database {
         span_id: uuid::Uuid,
         context: &ActivitySpanContext,
     ) -> Result<()> {
-        let context_json = serde_json::to_string(context)?;
-        self.conn.execute(
-            "UPDATE activity_spans SET context = ?1 WHERE id = ?2",
-            params![context_json, span_id.to_string()],
-        )?;
+        let mut context = context.clone();
+        context.enforce_cap(self.span_context_cap()?);
+        let context_json = serde_json::to_string(&context)?;
+        self.conn
+            .prepare_cached("UPDATE activity_spans SET context = ?1 WHERE id = ?2")?
+            .execute(params![context_json, span_id.to_string()])?;
         Ok(())
     }
 
@@ -112,13 +114,143 @@ impl Database {
             .num_seconds()
             .max(0) as u32;
 
+        self.conn
+            .prepare_cached(
+                "UPDATE activity_spans SET end_time = ?1, duration_seconds = ?2 WHERE id = ?3",
+            )?
+            .execute(params![
+                end_time.to_rfc3339(),
+                duration,
+                span_id.to_string()
+            ])?;
+
+        // Re-apply the context cap in case the limit was lowered while the span was open
+        if let Some(ctx) = &span.context {
+            self.update_activity_span_context(span_id, ctx)?;
+        }
+        Ok(())
+    }
+
+    /// Set the category of an activity span (e.g. refined from its context)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn update_activity_span_category(&self, span_id: uuid::Uuid, category: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE activity_spans SET category = ?1 WHERE id = ?2",
+            params![category, span_id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Record how confident the classifier was in a span's category
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn set_activity_span_category_confidence(
+        &self,
+        span_id: uuid::Uuid,
+        confidence: f32,
+    ) -> Result<()> {
+        self.conn.execute(
+            "UPDATE activity_spans SET category_confidence = ?1 WHERE id = ?2",
+            params![f64::from(confidence), span_id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Record the work pattern detected for an activity span
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn set_activity_span_work_pattern(&self, span_id: uuid::Uuid, pattern: &str) -> Result<()> {
         self.conn.execute(
-            "UPDATE activity_spans SET end_time = ?1, duration_seconds = ?2 WHERE id = ?3",
-            params![end_time.to_rfc3339(), duration, span_id.to_string()],
+            "UPDATE activity_spans SET work_pattern = ?1 WHERE id = ?2",
+            params![pattern, span_id.to_string()],
         )?;
         Ok(())
     }
 
+    /// Get finished spans with a recorded work pattern, with that pattern
+    ///
+    /// Spans whose pattern was never detected are not included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_work_pattern_spans(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<(ActivitySpan, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, app_bundle_id, category, start_time, end_time, duration_seconds, project_id, work_item_id, session_id, context, work_pattern
+             FROM activity_spans
+             WHERE start_time >= ?1 AND start_time <= ?2
+               AND end_time IS NOT NULL AND work_pattern IS NOT NULL
+               AND (session_id IS NULL OR session_id NOT IN (SELECT id FROM sessions WHERE discarded = 1))
+             ORDER BY start_time ASC",
+        )?;
+
+        let spans = stmt
+            .query_map(params![start.to_rfc3339(), end.to_rfc3339()], |row| {
+                Ok((Self::row_to_activity_span(row)?, row.get(10)?))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(spans)
+    }
+
+    /// Get finished spans classified with less than `max_confidence`, with their confidence
+    ///
+    /// Spans without a recorded confidence (tracked before it was recorded) are
+    /// not included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_uncertain_activity_spans(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        max_confidence: f32,
+    ) -> Result<Vec<(ActivitySpan, f32)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, app_bundle_id, category, start_time, end_time, duration_seconds, project_id, work_item_id, session_id, context, category_confidence
+             FROM activity_spans
+             WHERE start_time >= ?1 AND start_time <= ?2
+               AND end_time IS NOT NULL
+               AND category_confidence IS NOT NULL AND category_confidence < ?3
+               AND (session_id IS NULL OR session_id NOT IN (SELECT id FROM sessions WHERE discarded = 1))
+             ORDER BY start_time ASC",
+        )?;
+
+        let spans = stmt
+            .query_map(
+                params![
+                    start.to_rfc3339(),
+                    end.to_rfc3339(),
+                    f64::from(max_confidence)
+                ],
+                |row| {
+                    #[allow(clippy::cast_possible_truncation)]
+                    let confidence = row.get::<_, f64>(10)? as f32;
+                    Ok((Self::row_to_activity_span(row)?, confidence))
+                },
+            )?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(spans)
+    }
+
+    /// Max entries kept per span-context list, from settings
+    fn span_context_cap(&self) -> Result<usize> {
+        Ok(self.get_settings()?.span_context_cap as usize)
+    }
+
     /// Get an activity span by ID
     ///
     /// # Errors
@@ -132,13 +264,12 @@ impl Database {
         use rusqlite::OptionalExtension;
         let result = self
             .conn
-            .query_row(
+            .prepare_cached(
                 "SELECT id, app_bundle_id, category, start_time, end_time, duration_seconds, project_id, work_item_id, session_id, context
                  FROM activity_spans
                  WHERE id = ?1",
-                params![span_id.to_string()],
-                Self::row_to_activity_span,
-            )
+            )?
+            .query_row(params![span_id.to_string()], Self::row_to_activity_span)
             .optional()?;
 
         Ok(result)
@@ -238,6 +369,7 @@ impl Database {
             "SELECT id, app_bundle_id, category, start_time, end_time, duration_seconds, project_id, work_item_id, session_id, context
              FROM activity_spans
              WHERE start_time >= ?1 AND start_time <= ?2
+               AND (session_id IS NULL OR session_id NOT IN (SELECT id FROM sessions WHERE discarded = 1))
              ORDER BY start_time ASC",
         )?;
 
@@ -251,6 +383,66 @@ impl Database {
         Ok(spans)
     }
 
+    /// Get activity spans within a time range that have no work item attributed
+    ///
+    /// Only the primary `work_item_id` is considered; work items added to the
+    /// span context do not make a span matched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_unmatched_spans(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ActivitySpan>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, app_bundle_id, category, start_time, end_time, duration_seconds, project_id, work_item_id, session_id, context
+             FROM activity_spans
+             WHERE start_time >= ?1 AND start_time <= ?2 AND work_item_id IS NULL
+               AND (session_id IS NULL OR session_id NOT IN (SELECT id FROM sessions WHERE discarded = 1))
+             ORDER BY start_time ASC",
+        )?;
+
+        let spans = stmt
+            .query_map(
+                params![start.to_rfc3339(), end.to_rfc3339()],
+                Self::row_to_activity_span,
+            )?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(spans)
+    }
+
+    /// Attribute a project's spans without a work item that overlap a time window
+    ///
+    /// Used to carry a commit's issue back onto the work that preceded it.
+    /// Spans already attributed to a work item are left alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn attribute_project_spans(
+        &self,
+        project_id: uuid::Uuid,
+        work_item_id: uuid::Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<usize> {
+        let updated = self.conn.execute(
+            "UPDATE activity_spans SET work_item_id = ?1
+             WHERE project_id = ?2 AND work_item_id IS NULL
+               AND start_time <= ?4 AND (end_time IS NULL OR end_time >= ?3)",
+            params![
+                work_item_id.to_string(),
+                project_id.to_string(),
+                start.to_rfc3339(),
+                end.to_rfc3339()
+            ],
+        )?;
+        Ok(updated)
+    }
+
     /// Get activity spans for a specific work item
     ///
     /// # Errors
@@ -268,6 +460,7 @@ impl Database {
             "SELECT id, app_bundle_id, category, start_time, end_time, duration_seconds, project_id, work_item_id, session_id, context
              FROM activity_spans
              WHERE work_item_id = ?1
+               AND (session_id IS NULL OR session_id NOT IN (SELECT id FROM sessions WHERE discarded = 1))
              ORDER BY start_time ASC",
         )?;
 
@@ -295,6 +488,7 @@ impl Database {
             "SELECT id, app_bundle_id, category, start_time, end_time, duration_seconds, project_id, work_item_id, session_id, context
              FROM activity_spans
              WHERE project_id = ?1
+               AND (session_id IS NULL OR session_id NOT IN (SELECT id FROM sessions WHERE discarded = 1))
              ORDER BY start_time ASC",
         )?;
 
@@ -331,4 +525,17 @@ impl Database {
 
         Ok(spans)
     }
+
+    /// Delete all activity spans of a session
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn delete_activity_spans_by_session(&self, session_id: uuid::Uuid) -> Result<usize> {
+        let deleted = self.conn.execute(
+            "DELETE FROM activity_spans WHERE session_id = ?1",
+            params![session_id.to_string()],
+        )?;
+        Ok(deleted)
+    }
 }
@@ -0,0 +1,56 @@
+//! App display name database operations
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use rusqlite::params;
+
+use super::Database;
+
+impl Database {
+    /// Get all user-defined app display names, keyed by bundle id
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_app_display_names(&self) -> Result<HashMap<String, String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT bundle_id, display_name FROM app_display_names")?;
+
+        let names = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        Ok(names)
+    }
+
+    /// Set the display name for an app bundle id, replacing any existing override
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn set_app_display_name(&self, bundle_id: &str, display_name: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO app_display_names (bundle_id, display_name) VALUES (?1, ?2)
+             ON CONFLICT(bundle_id) DO UPDATE SET display_name = excluded.display_name",
+            params![bundle_id, display_name],
+        )?;
+        Ok(())
+    }
+
+    /// Remove the display name override for an app bundle id
+    ///
+    /// Returns `true` if an override was removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn remove_app_display_name(&self, bundle_id: &str) -> Result<bool> {
+        let affected = self.conn.execute(
+            "DELETE FROM app_display_names WHERE bundle_id = ?1",
+            params![bundle_id],
+        )?;
+        Ok(affected > 0)
+    }
+}
@@ -0,0 +1,289 @@
+//! Row-at-a-time export and idempotent import for backups
+//!
+//! The `for_each_*` visitors hand rows to a callback as they are read, so an
+//! export never holds the whole dataset in memory. The `import_*` methods skip
+//! rows whose id already exists, so restoring the same backup twice is harmless.
+//! Work items are not part of a backup, and projects only of a JSON export;
+//! references to ones missing from the target database are dropped on import.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+
+use super::Database;
+use crate::models::{Activity, ActivitySpan, Project, Session, TimeBlock, TimeBlockSource};
+
+impl Database {
+    /// Run `import` in a single transaction
+    ///
+    /// Nothing `import` wrote is kept if it returns an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be started or committed, or
+    /// `import` returns an error
+    pub fn import_in_transaction<T>(&self, import: impl FnOnce(&Self) -> Result<T>) -> Result<T> {
+        let tx = self.conn.unchecked_transaction()?;
+        let result = import(self)?;
+        tx.commit()?;
+        Ok(result)
+    }
+
+    /// Visit activities in a time range, oldest first
+    ///
+    /// Returns the number of rows visited.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or `visit` returns an error
+    pub fn for_each_activity(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        mut visit: impl FnMut(Activity) -> Result<()>,
+    ) -> Result<usize> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, timestamp, app_bundle_id, category, duration_seconds, is_active, work_item_id
+             FROM activities
+             WHERE timestamp BETWEEN ?1 AND ?2
+             ORDER BY timestamp ASC",
+        )?;
+        let mut rows = stmt.query(params![start.to_rfc3339(), end.to_rfc3339()])?;
+        let mut count = 0;
+        while let Some(row) = rows.next()? {
+            visit(Self::row_to_activity(row)?)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Visit activity spans starting in a time range, oldest first
+    ///
+    /// Spans of discarded sessions are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or `visit` returns an error
+    pub fn for_each_activity_span(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        mut visit: impl FnMut(ActivitySpan) -> Result<()>,
+    ) -> Result<usize> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, app_bundle_id, category, start_time, end_time, duration_seconds, project_id, work_item_id, session_id, context
+             FROM activity_spans
+             WHERE start_time >= ?1 AND start_time <= ?2
+               AND (session_id IS NULL OR session_id NOT IN (SELECT id FROM sessions WHERE discarded = 1))
+             ORDER BY start_time ASC",
+        )?;
+        let mut rows = stmt.query(params![start.to_rfc3339(), end.to_rfc3339()])?;
+        let mut count = 0;
+        while let Some(row) = rows.next()? {
+            visit(Self::row_to_activity_span(row)?)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Visit sessions starting in a time range, oldest first
+    ///
+    /// Discarded sessions are skipped; empty ones are included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or `visit` returns an error
+    pub fn for_each_session(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        mut visit: impl FnMut(Session) -> Result<()>,
+    ) -> Result<usize> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, start_time, end_time, total_active_seconds, idle_seconds, interruption_count, categories, work_item_ids
+             FROM sessions
+             WHERE start_time >= ?1 AND start_time <= ?2 AND discarded = 0
+             ORDER BY start_time ASC",
+        )?;
+        let mut rows = stmt.query(params![start.to_rfc3339(), end.to_rfc3339()])?;
+        let mut count = 0;
+        while let Some(row) = rows.next()? {
+            visit(Self::row_to_session(row)?)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Visit time blocks starting in a time range, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or `visit` returns an error
+    pub fn for_each_time_block(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        mut visit: impl FnMut(TimeBlock) -> Result<()>,
+    ) -> Result<usize> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, start_time, end_time, project_id, work_item_ids, description, tags, source, confidence, confirmed, created_at
+             FROM time_blocks
+             WHERE start_time >= ?1 AND start_time <= ?2
+             ORDER BY start_time ASC",
+        )?;
+        let mut rows = stmt.query(params![start.to_rfc3339(), end.to_rfc3339()])?;
+        let mut count = 0;
+        while let Some(row) = rows.next()? {
+            visit(Self::row_to_time_block(row)?)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Insert a project unless one with the same id or path exists
+    ///
+    /// Returns `true` if the row was inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database insert fails
+    pub fn import_project(&self, project: &Project) -> Result<bool> {
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO projects (id, name, path, description, created_at, last_active, pm_system, pm_project_id, pm_workspace)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                project.id.to_string(),
+                project.name,
+                project.path,
+                project.description,
+                project.created_at.to_rfc3339(),
+                project.last_active.to_rfc3339(),
+                project.pm_system,
+                project.pm_project_id,
+                project.pm_workspace,
+            ],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Insert an activity unless one with the same id exists
+    ///
+    /// Returns `true` if the row was inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database insert fails
+    pub fn import_activity(&self, activity: &Activity) -> Result<bool> {
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO activities (id, timestamp, app_bundle_id, category, duration_seconds, is_active, work_item_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, (SELECT id FROM work_items WHERE id = ?7))",
+            params![
+                activity.id.to_string(),
+                activity.timestamp.to_rfc3339(),
+                activity.app_bundle_id,
+                activity.category,
+                activity.duration_seconds,
+                i32::from(activity.is_active),
+                activity.work_item_id.map(|id| id.to_string()),
+            ],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Insert an activity span unless one with the same id exists
+    ///
+    /// Returns `true` if the row was inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database insert or context serialization fails
+    pub fn import_activity_span(&self, span: &ActivitySpan) -> Result<bool> {
+        let context_json = span
+            .context
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO activity_spans
+             (id, app_bundle_id, category, start_time, end_time, duration_seconds, project_id, work_item_id, session_id, context)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6,
+                     (SELECT id FROM projects WHERE id = ?7),
+                     (SELECT id FROM work_items WHERE id = ?8),
+                     (SELECT id FROM sessions WHERE id = ?9),
+                     ?10)",
+            params![
+                span.id.to_string(),
+                span.app_bundle_id,
+                span.category,
+                span.start_time.to_rfc3339(),
+                span.end_time.map(|t| t.to_rfc3339()),
+                span.duration_seconds,
+                span.project_id.map(|id| id.to_string()),
+                span.work_item_id.map(|id| id.to_string()),
+                span.session_id.map(|id| id.to_string()),
+                context_json,
+            ],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Insert a session unless one with the same id exists
+    ///
+    /// Returns `true` if the row was inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database insert or JSON serialization fails
+    pub fn import_session(&self, session: &Session) -> Result<bool> {
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO sessions (id, start_time, end_time, total_active_seconds, idle_seconds, interruption_count, categories, work_item_ids)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                session.id.to_string(),
+                session.start_time.to_rfc3339(),
+                session.end_time.map(|t| t.to_rfc3339()),
+                session.total_active_seconds,
+                session.idle_seconds,
+                session.interruption_count,
+                serde_json::to_string(&session.categories)?,
+                serde_json::to_string(&session.work_item_ids)?,
+            ],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Insert a time block unless one with the same id exists
+    ///
+    /// Imported blocks are marked as already synced and logged, so restoring a
+    /// backup never pushes the same time to a PM system or worklog twice.
+    /// Returns `true` if the row was inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database insert or JSON serialization fails
+    pub fn import_time_block(&self, block: &TimeBlock) -> Result<bool> {
+        let source_str = match block.source {
+            TimeBlockSource::Manual => "Manual",
+            TimeBlockSource::AiSuggested => "AiSuggested",
+            TimeBlockSource::AutoDetected => "AutoDetected",
+        };
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO time_blocks (id, start_time, end_time, project_id, work_item_ids, description, tags, source, confidence, confirmed, synced, logged_locally, created_at)
+             VALUES (?1, ?2, ?3, (SELECT id FROM projects WHERE id = ?4), ?5, ?6, ?7, ?8, ?9, ?10, 1, 1, ?11)",
+            params![
+                block.id.to_string(),
+                block.start_time.to_rfc3339(),
+                block.end_time.to_rfc3339(),
+                block.project_id.map(|id| id.to_string()),
+                serde_json::to_string(&block.work_item_ids)?,
+                block.description,
+                serde_json::to_string(&block.tags)?,
+                source_str,
+                block.confidence,
+                i32::from(block.confirmed),
+                block.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(inserted > 0)
+    }
+}
@@ -0,0 +1,67 @@
+//! Per-project billable category overrides
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use rusqlite::params;
+
+use super::helpers::parse_uuid;
+use super::Database;
+
+impl Database {
+    /// Get all per-project billable category overrides, keyed by project id
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_project_billable_categories(&self) -> Result<HashMap<uuid::Uuid, Vec<String>>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT project_id, categories FROM project_billable_categories")?;
+
+        let overrides = stmt
+            .query_map([], |row| {
+                let categories: String = row.get(1)?;
+                Ok((
+                    parse_uuid(&row.get::<_, String>(0)?)?,
+                    serde_json::from_str(&categories).unwrap_or_default(),
+                ))
+            })?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        Ok(overrides)
+    }
+
+    /// Set the billable categories for a project, replacing any existing override
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn set_project_billable_categories(
+        &self,
+        project_id: uuid::Uuid,
+        categories: &[String],
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO project_billable_categories (project_id, categories) VALUES (?1, ?2)
+             ON CONFLICT(project_id) DO UPDATE SET categories = excluded.categories",
+            params![project_id.to_string(), serde_json::to_string(categories)?],
+        )?;
+        Ok(())
+    }
+
+    /// Remove a project's billable category override
+    ///
+    /// Returns `true` if an override was removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn remove_project_billable_categories(&self, project_id: uuid::Uuid) -> Result<bool> {
+        let affected = self.conn.execute(
+            "DELETE FROM project_billable_categories WHERE project_id = ?1",
+            params![project_id.to_string()],
+        )?;
+        Ok(affected > 0)
+    }
+}
@@ -0,0 +1,65 @@
+//! Audit trail of time block changes
+//!
+//! Edits, merges, splits, and confirmations made while reviewing are recorded
+//! with a summary of the block before and after, so time that was synced can
+//! be traced back to what was detected.
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::params;
+
+use super::helpers::{parse_datetime, parse_uuid};
+use super::Database;
+use crate::models::{BlockHistoryEntry, TimeBlock};
+
+impl Database {
+    /// Record a change to a time block
+    pub(crate) fn record_block_history(
+        &self,
+        block_id: uuid::Uuid,
+        action: &str,
+        before: Option<&TimeBlock>,
+        after: Option<&TimeBlock>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO block_history (block_id, action, before, after, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                block_id.to_string(),
+                action,
+                before.map(TimeBlock::summary),
+                after.map(TimeBlock::summary),
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Get the recorded changes to a time block, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_block_history(&self, block_id: uuid::Uuid) -> Result<Vec<BlockHistoryEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT block_id, action, before, after, created_at
+             FROM block_history
+             WHERE block_id = ?1
+             ORDER BY id ASC",
+        )?;
+
+        let entries = stmt
+            .query_map(params![block_id.to_string()], |row| {
+                Ok(BlockHistoryEntry {
+                    block_id: parse_uuid(&row.get::<_, String>(0)?)?,
+                    action: row.get(1)?,
+                    before: row.get(2)?,
+                    after: row.get(3)?,
+                    created_at: parse_datetime(&row.get::<_, String>(4)?)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(entries)
+    }
+}
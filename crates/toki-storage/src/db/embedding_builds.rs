@@ -0,0 +1,69 @@
+//! Issue embedding build history
+//!
+//! Each completed embedding pass is recorded so a restart loop or repeated
+//! implicit sync does not reload the model and rewrite embeddings every time.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+
+use super::helpers::parse_datetime;
+use super::Database;
+
+/// Issue embedding coverage, for `toki data stats`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddingStats {
+    pub issues: usize,
+    pub embedded_issues: usize,
+    pub last_build: Option<DateTime<Utc>>,
+}
+
+impl Database {
+    /// Record a completed embedding build
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn record_embedding_build(&self, built_at: DateTime<Utc>, computed: usize) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO embedding_builds (built_at, embeddings_computed) VALUES (?1, ?2)",
+            params![built_at.to_rfc3339(), i64::try_from(computed)?],
+        )?;
+        Ok(())
+    }
+
+    /// When the last embedding build finished, if there ever was one
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_last_embedding_build(&self) -> Result<Option<DateTime<Utc>>> {
+        let built_at: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT built_at FROM embedding_builds ORDER BY built_at DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(built_at.map(|s| parse_datetime(&s)).transpose()?)
+    }
+
+    /// Count issue candidates with and without embeddings
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_embedding_stats(&self) -> Result<EmbeddingStats> {
+        let (issues, embedded_issues): (i64, i64) = self.conn.query_row(
+            "SELECT COUNT(*), COUNT(embedding) FROM issue_candidates",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(EmbeddingStats {
+            issues: usize::try_from(issues)?,
+            embedded_issues: usize::try_from(embedded_issues)?,
+            last_build: self.get_last_embedding_build()?,
+        })
+    }
+}
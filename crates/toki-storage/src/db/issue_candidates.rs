@@ -1,3 +1,6 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
 use anyhow::Result;
 use rusqlite::{params, OptionalExtension};
 
@@ -21,8 +24,8 @@ impl Database {
 
         self.conn.execute(
             "INSERT INTO issue_candidates
-             (id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
+             (id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source, done_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)
              ON CONFLICT(external_id, external_system) DO UPDATE SET
                 project_id = excluded.project_id,
                 pm_project_id = excluded.pm_project_id,
@@ -37,7 +40,8 @@ impl Database {
                 complexity = COALESCE(excluded.complexity, issue_candidates.complexity),
                 complexity_reason = COALESCE(excluded.complexity_reason, issue_candidates.complexity_reason),
                 estimated_seconds = COALESCE(excluded.estimated_seconds, issue_candidates.estimated_seconds),
-                estimate_source = COALESCE(excluded.estimate_source, issue_candidates.estimate_source)",
+                estimate_source = COALESCE(excluded.estimate_source, issue_candidates.estimate_source),
+                done_at = COALESCE(excluded.done_at, issue_candidates.done_at)",
             params![
                 candidate.id.to_string(),
                 candidate.project_id.to_string(),
@@ -56,6 +60,7 @@ impl Database {
                 candidate.complexity_reason,
                 candidate.estimated_seconds,
                 candidate.estimate_source,
+                candidate.done_at.map(|t| t.to_rfc3339()),
             ],
         )?;
         Ok(())
@@ -63,6 +68,9 @@ impl Database {
 
     /// Update issue candidate embedding
     ///
+    /// `input_hash` identifies the text the embedding was computed from, so later
+    /// syncs can skip recomputation when that text is unchanged.
+    ///
     /// # Errors
     ///
     /// Returns an error if the database operation fails
@@ -70,15 +78,33 @@ impl Database {
         &self,
         candidate_id: uuid::Uuid,
         embedding: &[f32],
+        input_hash: &str,
     ) -> Result<()> {
         let bytes: Vec<u8> = embedding.iter().flat_map(|f| f.to_le_bytes()).collect();
         self.conn.execute(
-            "UPDATE issue_candidates SET embedding = ?1 WHERE id = ?2",
-            params![bytes, candidate_id.to_string()],
+            "UPDATE issue_candidates SET embedding = ?1, embedding_hash = ?2 WHERE id = ?3",
+            params![bytes, input_hash, candidate_id.to_string()],
         )?;
         Ok(())
     }
 
+    /// Get the hash of the text an issue's stored embedding was computed from
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_issue_embedding_hash(&self, candidate_id: uuid::Uuid) -> Result<Option<String>> {
+        let hash = self
+            .conn
+            .query_row(
+                "SELECT embedding_hash FROM issue_candidates WHERE id = ?1",
+                params![candidate_id.to_string()],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .optional()?;
+        Ok(hash.flatten())
+    }
+
     /// Get all issue candidates for a project
     ///
     /// # Errors
@@ -89,7 +115,7 @@ impl Database {
         project_id: uuid::Uuid,
     ) -> Result<Vec<IssueCandidate>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source
+            "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source, done_at
              FROM issue_candidates
              WHERE project_id = ?1
              ORDER BY last_synced DESC",
@@ -112,7 +138,7 @@ impl Database {
         project_id: uuid::Uuid,
     ) -> Result<Vec<IssueCandidate>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source
+            "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source, done_at
              FROM issue_candidates
              WHERE project_id = ?1 AND status NOT IN ('done', 'cancelled', 'completed')
              ORDER BY last_synced DESC",
@@ -125,6 +151,91 @@ impl Database {
         Ok(candidates)
     }
 
+    /// Fingerprint of the stored issue candidates and project links
+    ///
+    /// Changes whenever a sync (from any process) upserts candidates, an
+    /// embedding is stored, or a project is linked or unlinked, so an
+    /// in-memory copy of the candidates can tell it is stale.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn issue_candidates_version(&self) -> Result<String> {
+        let version = self.conn.query_row(
+            "SELECT COUNT(*), COUNT(embedding), COALESCE(MAX(last_synced), ''),
+                    (SELECT COUNT(*) FROM projects
+                     WHERE pm_system IS NOT NULL AND pm_project_id IS NOT NULL)
+             FROM issue_candidates",
+            [],
+            |row| {
+                Ok(format!(
+                    "{}:{}:{}:{}",
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i64>(3)?
+                ))
+            },
+        )?;
+        Ok(version)
+    }
+
+    /// Find the `k` active issue candidates most similar to an embedding
+    ///
+    /// Cosine similarity is computed straight from each stored embedding, and
+    /// only the best `k` candidates are kept while rows are read. Candidates
+    /// without an embedding, or with one of a different length, are skipped.
+    /// Results are sorted by similarity, best first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn nearest_issue_candidates(
+        &self,
+        project_id: uuid::Uuid,
+        query_embedding: &[f32],
+        k: usize,
+    ) -> Result<Vec<(IssueCandidate, f32)>> {
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source, done_at
+             FROM issue_candidates
+             WHERE project_id = ?1 AND embedding IS NOT NULL
+               AND status NOT IN ('done', 'cancelled', 'completed')",
+        )?;
+
+        let mut nearest: BinaryHeap<Neighbor> = BinaryHeap::with_capacity(k + 1);
+        let mut rows = stmt.query([project_id.to_string()])?;
+        while let Some(row) = rows.next()? {
+            let Some(similarity) = row
+                .get_ref(11)?
+                .as_blob_or_null()?
+                .and_then(|bytes| blob_cosine_similarity(query_embedding, bytes))
+            else {
+                continue;
+            };
+            if nearest.len() == k && nearest.peek().is_some_and(|n| similarity <= n.similarity) {
+                continue;
+            }
+            nearest.push(Neighbor {
+                similarity,
+                candidate: Self::row_to_issue_candidate(row)?,
+            });
+            if nearest.len() > k {
+                nearest.pop();
+            }
+        }
+
+        Ok(nearest
+            .into_sorted_vec()
+            .into_iter()
+            .map(|n| (n.candidate, n.similarity))
+            .collect())
+    }
+
     /// Get issue candidate by external ID
     ///
     /// # Errors
@@ -138,7 +249,7 @@ impl Database {
         let result = self
             .conn
             .query_row(
-                "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source
+                "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source, done_at
                  FROM issue_candidates
                  WHERE external_id = ?1 AND external_system = ?2",
                 params![external_id, external_system],
@@ -161,7 +272,7 @@ impl Database {
         let result = self
             .conn
             .query_row(
-                "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source
+                "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source, done_at
                  FROM issue_candidates
                  WHERE external_id = ?1",
                 params![external_id],
@@ -172,6 +283,34 @@ impl Database {
         Ok(result)
     }
 
+    /// Find issue candidates whose external ID matches, ignoring case
+    ///
+    /// Also matches prefixed IDs ending in the given suffix (e.g. "42" matches "PROJ-42"),
+    /// so the same number in several systems returns several candidates. The suffix is
+    /// compared literally, so `%` and `_` are not wildcards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn find_issue_candidates_by_external_id(
+        &self,
+        external_id: &str,
+    ) -> Result<Vec<IssueCandidate>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source, done_at
+             FROM issue_candidates
+             WHERE external_id = ?1 COLLATE NOCASE
+                OR substr(external_id, -length(?1) - 1) = ('-' || ?1) COLLATE NOCASE
+             ORDER BY external_system, external_id",
+        )?;
+
+        let candidates = stmt
+            .query_map(params![external_id], Self::row_to_issue_candidate)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(candidates)
+    }
+
     /// Get an issue candidate by UUID
     ///
     /// # Errors
@@ -184,7 +323,7 @@ impl Database {
         let result = self
             .conn
             .query_row(
-                "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source
+                "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source, done_at
                  FROM issue_candidates
                  WHERE id = ?1",
                 params![id.to_string()],
@@ -226,7 +365,7 @@ impl Database {
 
     /// Helper function to parse `IssueCandidate` from database row
     pub(crate) fn row_to_issue_candidate(row: &rusqlite::Row) -> rusqlite::Result<IssueCandidate> {
-        // Column order: id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source
+        // Column order: id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source, done_at
         let labels_json: String = row.get(9)?;
         let labels: Vec<String> = serde_json::from_str(&labels_json).unwrap_or_default();
 
@@ -255,6 +394,10 @@ impl Database {
             .get::<_, Option<i64>>(15)?
             .map(|v| u32::try_from(v.max(0)).unwrap_or(u32::MAX));
         let estimate_source: Option<String> = row.get(16)?;
+        let done_at = row
+            .get::<_, Option<String>>(17)?
+            .map(|s| parse_datetime(&s))
+            .transpose()?;
 
         Ok(IssueCandidate {
             id: parse_uuid(&row.get::<_, String>(0)?)?,
@@ -274,9 +417,54 @@ impl Database {
             complexity_reason: row.get(14)?,
             estimated_seconds,
             estimate_source,
+            done_at,
         })
     }
 
+    /// Get issue candidates that transitioned to done within a time range
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_issues_done_between(
+        &self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<IssueCandidate>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source, done_at
+             FROM issue_candidates
+             WHERE done_at IS NOT NULL AND done_at >= ?1 AND done_at < ?2
+             ORDER BY done_at ASC",
+        )?;
+
+        let candidates = stmt
+            .query_map(
+                params![start.to_rfc3339(), end.to_rfc3339()],
+                Self::row_to_issue_candidate,
+            )?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(candidates)
+    }
+
+    /// Record when an issue candidate transitioned to a done state
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn update_issue_done_at(
+        &self,
+        candidate_id: uuid::Uuid,
+        done_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "UPDATE issue_candidates SET done_at = ?1 WHERE id = ?2",
+            params![done_at.to_rfc3339(), candidate_id.to_string()],
+        )?;
+        Ok(())
+    }
+
     /// Update complexity for an issue candidate
     ///
     /// # Errors
@@ -327,13 +515,13 @@ impl Database {
     pub fn get_issues_with_estimates(&self, project_id: Option<uuid::Uuid>) -> Result<Vec<IssueCandidate>> {
         let query = match project_id {
             Some(_) => {
-                "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source
+                "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source, done_at
                  FROM issue_candidates
                  WHERE estimated_seconds IS NOT NULL AND project_id = ?1
                  ORDER BY last_synced DESC"
             }
             None => {
-                "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source
+                "SELECT id, project_id, external_id, external_system, pm_project_id, source_page_id, title, description, status, labels, assignee, embedding, last_synced, complexity, complexity_reason, estimated_seconds, estimate_source, done_at
                  FROM issue_candidates
                  WHERE estimated_seconds IS NOT NULL
                  ORDER BY last_synced DESC"
@@ -353,3 +541,52 @@ impl Database {
         Ok(candidates)
     }
 }
+
+/// Candidate kept by [`Database::nearest_issue_candidates`]
+///
+/// Ordered by descending similarity, so the top of a `BinaryHeap` is the
+/// least similar candidate kept so far.
+struct Neighbor {
+    similarity: f32,
+    candidate: IssueCandidate,
+}
+
+impl PartialEq for Neighbor {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Neighbor {}
+
+impl PartialOrd for Neighbor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Neighbor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.similarity.total_cmp(&self.similarity)
+    }
+}
+
+/// Cosine similarity between an embedding and one stored as little-endian `f32` bytes
+///
+/// `None` when the lengths differ or either vector is all zeros.
+fn blob_cosine_similarity(query: &[f32], bytes: &[u8]) -> Option<f32> {
+    if bytes.len() != query.len() * 4 {
+        return None;
+    }
+    let (mut dot, mut norm_query, mut norm_stored) = (0.0f32, 0.0f32, 0.0f32);
+    for (q, chunk) in query.iter().zip(bytes.chunks_exact(4)) {
+        let stored = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        dot += q * stored;
+        norm_query += q * q;
+        norm_stored += stored * stored;
+    }
+    if norm_query == 0.0 || norm_stored == 0.0 {
+        return None;
+    }
+    Some(dot / (norm_query.sqrt() * norm_stored.sqrt()))
+}
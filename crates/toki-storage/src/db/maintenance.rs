@@ -0,0 +1,32 @@
+//! Database file maintenance
+//!
+//! `SQLite` keeps the pages freed by deletes for reuse instead of returning
+//! them to the file system, so the file never shrinks on its own.
+
+use anyhow::{Context, Result};
+
+use super::Database;
+
+impl Database {
+    /// Rebuild the database file to reclaim free pages, then refresh query
+    /// planner statistics with `PRAGMA optimize`
+    ///
+    /// `VACUUM` writes a fresh copy of the database through this connection,
+    /// so an encrypted database is rewritten with the key it was opened with;
+    /// with `SQLCipher` the copy is encrypted like the original. It needs free
+    /// disk space of up to twice the file's size and cannot run inside a
+    /// transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `VACUUM` or `PRAGMA optimize` fails
+    pub fn vacuum(&self) -> Result<()> {
+        self.conn
+            .execute_batch("VACUUM")
+            .context("Failed to vacuum database")?;
+        self.conn
+            .execute_batch("PRAGMA optimize")
+            .context("Failed to optimize database")?;
+        Ok(())
+    }
+}
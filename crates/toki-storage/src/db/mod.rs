@@ -4,15 +4,27 @@
 
 mod activity_spans;
 mod ai_config;
+mod app_display_names;
+mod backup;
+mod billable_categories;
+mod block_history;
 mod claude_sessions;
+mod embedding_builds;
 pub(crate) mod helpers;
 mod issue_candidates;
+mod maintenance;
+mod project_work_hours;
 mod projects;
 mod session_issues;
 mod session_outcomes;
+mod suggestion_rankings;
+mod sync_checkpoints;
 mod synced_issues;
 
+pub use embedding_builds::EmbeddingStats;
+pub use projects::{name_similarity, ProjectMatch, MAX_PROJECT_MATCHES};
 pub use session_issues::IssueTimeStats;
+pub use sync_checkpoints::SYNC_CHECKPOINT_MAX_AGE_SECS;
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -24,15 +36,34 @@ use crate::encryption;
 use crate::migrations;
 use crate::models::{
     Activity, Category, ClassificationRule, IntegrationConfig, PatternType, Session, Settings,
-    WorkItem,
+    TrackingTimezone, WorkItem, DEFAULT_ANALYZER_MERGE_GAP_MINUTES,
+    DEFAULT_ANALYZER_MIN_BLOCK_MINUTES, DEFAULT_AUTO_ATTACH_MIN_CONFIDENCE,
+    DEFAULT_BACKFILL_MIN_CONFIDENCE, DEFAULT_CANDIDATE_WARMUP_THREADS,
+    DEFAULT_COMMIT_GRACE_SECONDS, DEFAULT_EMBEDDING_REFRESH_HOURS, DEFAULT_ENERGY_LOOKBACK_WEEKS,
+    DEFAULT_FOCUS_MIN_SECONDS, DEFAULT_MAX_SESSION_SECONDS, DEFAULT_MIN_ACTIVITY_SECONDS,
+    DEFAULT_SPAN_CONTEXT_CAP, DEFAULT_SUGGESTION_CACHE_SIZE, DEFAULT_SUGGESTION_CACHE_TTL_SECONDS,
 };
 
+/// Largest gap after an activity that a following short activity is merged across
+pub const ACTIVITY_MERGE_MAX_GAP_SECS: i64 = 60;
+
+/// Statements kept prepared per connection by `prepare_cached`
+///
+/// Enough for the daemon's per-tick inserts, updates and settings read.
+const STATEMENT_CACHE_CAPACITY: usize = 32;
+
 /// Database connection wrapper
+///
+/// Hot paths (activity inserts, span writes, settings reads) use
+/// `prepare_cached`, so their SQL is only compiled once per connection.
 pub struct Database {
     pub(crate) conn: Connection,
 }
 
-// Implement Send and Sync for Database to allow sharing across threads
+// Implement Send and Sync for Database to allow sharing across threads.
+// The connection, including its prepared statement cache (a `RefCell`), is
+// not thread-safe: callers must not use one `Database` from two threads at
+// the same time. Every `Arc<Database>` in toki is used by one task at a time.
 unsafe impl Send for Database {}
 unsafe impl Sync for Database {}
 
@@ -63,6 +94,7 @@ impl Database {
         }
 
         let conn = Connection::open(&path).context("Failed to open database connection")?;
+        conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
 
         // Initialize encryption if key provided
         let encryption_enabled = encryption_key.is_some();
@@ -88,7 +120,8 @@ impl Database {
     }
 
     /// Get default database path
-    fn default_db_path() -> PathBuf {
+    #[must_use]
+    pub fn default_db_path() -> PathBuf {
         let mut path = dirs::data_local_dir().unwrap_or_else(|| PathBuf::from("."));
         path.push("toki");
         path.push("toki.db");
@@ -99,14 +132,25 @@ impl Database {
 
     /// Insert a new activity record
     ///
+    /// Activities shorter than `settings.min_activity_seconds` are added to the
+    /// activity right before them instead of getting a row of their own, as long
+    /// as that one ended less than [`ACTIVITY_MERGE_MAX_GAP_SECS`] earlier.
+    ///
     /// # Errors
     ///
     /// Returns an error if the database insert operation fails
     pub fn insert_activity(&self, activity: &Activity) -> Result<()> {
-        self.conn.execute(
-            "INSERT INTO activities (id, timestamp, app_bundle_id, category, duration_seconds, is_active, work_item_id)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![
+        let floor = self.get_settings()?.min_activity_seconds;
+        if activity.duration_seconds < floor && self.merge_into_previous_activity(activity)? {
+            return Ok(());
+        }
+
+        self.conn
+            .prepare_cached(
+                "INSERT INTO activities (id, timestamp, app_bundle_id, category, duration_seconds, is_active, work_item_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?
+            .execute(params![
                 activity.id.to_string(),
                 activity.timestamp.to_rfc3339(),
                 activity.app_bundle_id,
@@ -114,11 +158,43 @@ impl Database {
                 activity.duration_seconds,
                 i32::from(activity.is_active),
                 activity.work_item_id.map(|id| id.to_string()),
-            ],
-        )?;
+            ])?;
         Ok(())
     }
 
+    /// Add a short activity's duration to the activity right before it
+    ///
+    /// Returns `false` if no earlier activity ended close enough to merge into.
+    fn merge_into_previous_activity(&self, activity: &Activity) -> Result<bool> {
+        let previous: Option<(String, String, u32)> = self
+            .conn
+            .prepare_cached(
+                "SELECT id, timestamp, duration_seconds FROM activities
+                 WHERE timestamp <= ?1
+                 ORDER BY timestamp DESC LIMIT 1",
+            )?
+            .query_row(params![activity.timestamp.to_rfc3339()], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .optional()?;
+        let Some((id, timestamp, duration_seconds)) = previous else {
+            return Ok(false);
+        };
+
+        let previous_end =
+            parse_datetime(&timestamp)? + chrono::Duration::seconds(i64::from(duration_seconds));
+        if (activity.timestamp - previous_end).num_seconds() > ACTIVITY_MERGE_MAX_GAP_SECS {
+            return Ok(false);
+        }
+
+        self.conn
+            .prepare_cached(
+                "UPDATE activities SET duration_seconds = duration_seconds + ?1 WHERE id = ?2",
+            )?
+            .execute(params![activity.duration_seconds, id])?;
+        Ok(true)
+    }
+
     /// Get activities for a specific date range
     ///
     /// # Errors
@@ -141,24 +217,29 @@ impl Database {
         )?;
 
         let activities = stmt
-            .query_map(params![start.to_rfc3339(), end.to_rfc3339()], |row| {
-                Ok(Activity {
-                    id: parse_uuid(&row.get::<_, String>(0)?)?,
-                    timestamp: parse_datetime(&row.get::<_, String>(1)?)?,
-                    app_bundle_id: row.get(2)?,
-                    category: row.get(3)?,
-                    duration_seconds: row.get(4)?,
-                    is_active: row.get::<_, i32>(5)? != 0,
-                    work_item_id: row
-                        .get::<_, Option<String>>(6)?
-                        .and_then(|s| uuid::Uuid::parse_str(&s).ok()),
-                })
-            })?
+            .query_map(
+                params![start.to_rfc3339(), end.to_rfc3339()],
+                Self::row_to_activity,
+            )?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(activities)
     }
 
+    pub(crate) fn row_to_activity(row: &rusqlite::Row) -> rusqlite::Result<Activity> {
+        Ok(Activity {
+            id: parse_uuid(&row.get::<_, String>(0)?)?,
+            timestamp: parse_datetime(&row.get::<_, String>(1)?)?,
+            app_bundle_id: row.get(2)?,
+            category: row.get(3)?,
+            duration_seconds: row.get(4)?,
+            is_active: row.get::<_, i32>(5)? != 0,
+            work_item_id: row
+                .get::<_, Option<String>>(6)?
+                .and_then(|s| uuid::Uuid::parse_str(&s).ok()),
+        })
+    }
+
     /// Delete activities in a date range
     ///
     /// # Errors
@@ -271,37 +352,135 @@ impl Database {
     /// # Panics
     ///
     /// May panic if UUID parsing or JSON deserialization fails for corrupted database entries
+    #[allow(clippy::too_many_lines)] // One line per settings column
     pub fn get_settings(&self) -> Result<Settings> {
         let result: Option<Settings> = self
             .conn
-            .query_row(
+            .prepare_cached(
                 "SELECT id, pause_tracking, excluded_apps, idle_threshold_seconds,
-                        enable_work_item_tracking, capture_window_title, capture_browser_url, url_whitelist
+                        enable_work_item_tracking, capture_window_title, capture_browser_url, url_whitelist,
+                        span_context_cap, name_match_algorithm, name_match_threshold,
+                        duration_format, notifications, max_session_seconds,
+                        integration_debug_log, auto_attach_min_confidence,
+                        backfill_min_confidence, billable_categories, calendar_overlap_policy,
+                        work_hours, min_activity_seconds, timezone, focus_min_seconds,
+                        require_focused_display, sync_min_entry_seconds, embeddings_enabled,
+                        suggestion_cache_size, suggestion_cache_ttl_seconds, self_tracking,
+                        commit_grace_seconds, embedding_refresh_hours, issue_id_pattern,
+                        candidate_warmup_threads, slack_webhook_url,
+                        analyzer_min_block_minutes, analyzer_merge_gap_minutes,
+                        capture_terminal_commands, terminal_history_path, day_boundary_hour,
+                        energy_lookback_weeks
                  FROM settings LIMIT 1",
-                [],
-                |row| {
-                    let excluded_apps_json: String = row.get(2)?;
-                    let excluded_apps: Vec<String> =
-                        serde_json::from_str(&excluded_apps_json).unwrap_or_default();
-
-                    let url_whitelist_json: String = row
-                        .get::<_, Option<String>>(7)?
-                        .unwrap_or_else(|| "[]".to_string());
-                    let url_whitelist: Vec<String> =
-                        serde_json::from_str(&url_whitelist_json).unwrap_or_default();
-
-                    Ok(Settings {
-                        id: parse_uuid(&row.get::<_, String>(0)?)?,
-                        pause_tracking: row.get::<_, i32>(1)? != 0,
-                        excluded_apps,
-                        idle_threshold_seconds: row.get(3)?,
-                        enable_work_item_tracking: row.get::<_, Option<i32>>(4)?.unwrap_or(0) != 0,
-                        capture_window_title: row.get::<_, Option<i32>>(5)?.unwrap_or(0) != 0,
-                        capture_browser_url: row.get::<_, Option<i32>>(6)?.unwrap_or(0) != 0,
-                        url_whitelist,
-                    })
-                },
-            )
+            )?
+            .query_row([], |row| {
+                let excluded_apps_json: String = row.get(2)?;
+                let excluded_apps: Vec<String> =
+                    serde_json::from_str(&excluded_apps_json).unwrap_or_default();
+
+                let url_whitelist_json: String = row
+                    .get::<_, Option<String>>(7)?
+                    .unwrap_or_else(|| "[]".to_string());
+                let url_whitelist: Vec<String> =
+                    serde_json::from_str(&url_whitelist_json).unwrap_or_default();
+
+                Ok(Settings {
+                    id: parse_uuid(&row.get::<_, String>(0)?)?,
+                    pause_tracking: row.get::<_, i32>(1)? != 0,
+                    excluded_apps,
+                    idle_threshold_seconds: row.get(3)?,
+                    enable_work_item_tracking: row.get::<_, Option<i32>>(4)?.unwrap_or(0) != 0,
+                    capture_window_title: row.get::<_, Option<i32>>(5)?.unwrap_or(0) != 0,
+                    capture_browser_url: row.get::<_, Option<i32>>(6)?.unwrap_or(0) != 0,
+                    url_whitelist,
+                    span_context_cap: row
+                        .get::<_, Option<u32>>(8)?
+                        .unwrap_or(DEFAULT_SPAN_CONTEXT_CAP),
+                    name_match_algorithm: row
+                        .get::<_, Option<String>>(9)?
+                        .unwrap_or_else(|| "jaccard".to_string()),
+                    name_match_threshold: row.get::<_, Option<f32>>(10)?.unwrap_or(0.6),
+                    duration_format: row
+                        .get::<_, Option<String>>(11)?
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or_default(),
+                    notifications: row
+                        .get::<_, Option<String>>(12)?
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default(),
+                    max_session_seconds: row
+                        .get::<_, Option<u32>>(13)?
+                        .unwrap_or(DEFAULT_MAX_SESSION_SECONDS),
+                    integration_debug_log: row
+                        .get::<_, Option<String>>(14)?
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or_default(),
+                    auto_attach_min_confidence: row
+                        .get::<_, Option<f32>>(15)?
+                        .unwrap_or(DEFAULT_AUTO_ATTACH_MIN_CONFIDENCE),
+                    backfill_min_confidence: row
+                        .get::<_, Option<f32>>(16)?
+                        .unwrap_or(DEFAULT_BACKFILL_MIN_CONFIDENCE),
+                    billable_categories: row
+                        .get::<_, Option<String>>(17)?
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default(),
+                    calendar_overlap_policy: row
+                        .get::<_, Option<String>>(18)?
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or_default(),
+                    work_hours: row
+                        .get::<_, Option<String>>(19)?
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or_default(),
+                    min_activity_seconds: row
+                        .get::<_, Option<u32>>(20)?
+                        .unwrap_or(DEFAULT_MIN_ACTIVITY_SECONDS),
+                    timezone: row
+                        .get::<_, Option<String>>(21)?
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or_default(),
+                    focus_min_seconds: row
+                        .get::<_, Option<u32>>(22)?
+                        .unwrap_or(DEFAULT_FOCUS_MIN_SECONDS),
+                    require_focused_display: row.get::<_, Option<i32>>(23)?.unwrap_or(0) != 0,
+                    sync_min_entry_seconds: row.get::<_, Option<u32>>(24)?.unwrap_or(0),
+                    embeddings_enabled: row.get::<_, Option<i32>>(25)?.unwrap_or(1) != 0,
+                    suggestion_cache_size: row
+                        .get::<_, Option<u32>>(26)?
+                        .unwrap_or(DEFAULT_SUGGESTION_CACHE_SIZE),
+                    suggestion_cache_ttl_seconds: row
+                        .get::<_, Option<u32>>(27)?
+                        .unwrap_or(DEFAULT_SUGGESTION_CACHE_TTL_SECONDS),
+                    self_tracking: row
+                        .get::<_, Option<String>>(28)?
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or_default(),
+                    commit_grace_seconds: row
+                        .get::<_, Option<u32>>(29)?
+                        .unwrap_or(DEFAULT_COMMIT_GRACE_SECONDS),
+                    embedding_refresh_hours: row
+                        .get::<_, Option<u32>>(30)?
+                        .unwrap_or(DEFAULT_EMBEDDING_REFRESH_HOURS),
+                    issue_id_pattern: row.get(31)?,
+                    candidate_warmup_threads: row
+                        .get::<_, Option<u32>>(32)?
+                        .unwrap_or(DEFAULT_CANDIDATE_WARMUP_THREADS),
+                    slack_webhook_url: row.get(33)?,
+                    analyzer_min_block_minutes: row
+                        .get::<_, Option<u32>>(34)?
+                        .unwrap_or(DEFAULT_ANALYZER_MIN_BLOCK_MINUTES),
+                    analyzer_merge_gap_minutes: row
+                        .get::<_, Option<u32>>(35)?
+                        .unwrap_or(DEFAULT_ANALYZER_MERGE_GAP_MINUTES),
+                    capture_terminal_commands: row.get::<_, Option<i32>>(36)?.unwrap_or(0) != 0,
+                    terminal_history_path: row.get(37)?,
+                    day_boundary_hour: row.get::<_, Option<u32>>(38)?.unwrap_or(0),
+                    energy_lookback_weeks: row
+                        .get::<_, Option<u32>>(39)?
+                        .unwrap_or(DEFAULT_ENERGY_LOOKBACK_WEEKS),
+                })
+            })
             .optional()?;
 
         if let Some(settings) = result {
@@ -319,14 +498,33 @@ impl Database {
     /// # Errors
     ///
     /// Returns an error if the database update operation or JSON serialization fails
+    #[allow(clippy::too_many_lines)] // One line per settings column
     pub fn update_settings(&self, settings: &Settings) -> Result<()> {
         let excluded_apps_json = serde_json::to_string(&settings.excluded_apps)?;
         let url_whitelist_json = serde_json::to_string(&settings.url_whitelist)?;
+        let notifications_json = serde_json::to_string(&settings.notifications)?;
+        let billable_categories_json = serde_json::to_string(&settings.billable_categories)?;
 
         self.conn.execute(
             "INSERT INTO settings (id, pause_tracking, excluded_apps, idle_threshold_seconds,
-                                   enable_work_item_tracking, capture_window_title, capture_browser_url, url_whitelist)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                                   enable_work_item_tracking, capture_window_title, capture_browser_url, url_whitelist,
+                                   span_context_cap, name_match_algorithm, name_match_threshold,
+                                   duration_format, notifications, max_session_seconds,
+                                   integration_debug_log, auto_attach_min_confidence,
+                                   backfill_min_confidence, billable_categories,
+                                   calendar_overlap_policy, work_hours, min_activity_seconds,
+                                   timezone, focus_min_seconds, require_focused_display,
+                                   sync_min_entry_seconds, embeddings_enabled,
+                                   suggestion_cache_size, suggestion_cache_ttl_seconds,
+                                   self_tracking, commit_grace_seconds, embedding_refresh_hours,
+                                   issue_id_pattern, candidate_warmup_threads,
+                                   slack_webhook_url, analyzer_min_block_minutes,
+                                   analyzer_merge_gap_minutes, capture_terminal_commands,
+                                   terminal_history_path, day_boundary_hour,
+                                   energy_lookback_weeks)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18,
+                     ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33,
+                     ?34, ?35, ?36, ?37, ?38, ?39, ?40)
              ON CONFLICT(id) DO UPDATE SET
                 pause_tracking = ?2,
                 excluded_apps = ?3,
@@ -334,7 +532,39 @@ impl Database {
                 enable_work_item_tracking = ?5,
                 capture_window_title = ?6,
                 capture_browser_url = ?7,
-                url_whitelist = ?8",
+                url_whitelist = ?8,
+                span_context_cap = ?9,
+                name_match_algorithm = ?10,
+                name_match_threshold = ?11,
+                duration_format = ?12,
+                notifications = ?13,
+                max_session_seconds = ?14,
+                integration_debug_log = ?15,
+                auto_attach_min_confidence = ?16,
+                backfill_min_confidence = ?17,
+                billable_categories = ?18,
+                calendar_overlap_policy = ?19,
+                work_hours = ?20,
+                min_activity_seconds = ?21,
+                timezone = ?22,
+                focus_min_seconds = ?23,
+                require_focused_display = ?24,
+                sync_min_entry_seconds = ?25,
+                embeddings_enabled = ?26,
+                suggestion_cache_size = ?27,
+                suggestion_cache_ttl_seconds = ?28,
+                self_tracking = ?29,
+                commit_grace_seconds = ?30,
+                embedding_refresh_hours = ?31,
+                issue_id_pattern = ?32,
+                candidate_warmup_threads = ?33,
+                slack_webhook_url = ?34,
+                analyzer_min_block_minutes = ?35,
+                analyzer_merge_gap_minutes = ?36,
+                capture_terminal_commands = ?37,
+                terminal_history_path = ?38,
+                day_boundary_hour = ?39,
+                energy_lookback_weeks = ?40",
             params![
                 settings.id.to_string(),
                 i32::from(settings.pause_tracking),
@@ -344,6 +574,38 @@ impl Database {
                 i32::from(settings.capture_window_title),
                 i32::from(settings.capture_browser_url),
                 url_whitelist_json,
+                settings.span_context_cap,
+                settings.name_match_algorithm,
+                settings.name_match_threshold,
+                settings.duration_format.as_str(),
+                notifications_json,
+                settings.max_session_seconds,
+                settings.integration_debug_log.as_str(),
+                settings.auto_attach_min_confidence,
+                settings.backfill_min_confidence,
+                billable_categories_json,
+                settings.calendar_overlap_policy.as_str(),
+                settings.work_hours.to_string(),
+                settings.min_activity_seconds,
+                settings.timezone.to_string(),
+                settings.focus_min_seconds,
+                i32::from(settings.require_focused_display),
+                settings.sync_min_entry_seconds,
+                i32::from(settings.embeddings_enabled),
+                settings.suggestion_cache_size,
+                settings.suggestion_cache_ttl_seconds,
+                settings.self_tracking.as_str(),
+                settings.commit_grace_seconds,
+                settings.embedding_refresh_hours,
+                settings.issue_id_pattern,
+                settings.candidate_warmup_threads,
+                settings.slack_webhook_url,
+                settings.analyzer_min_block_minutes,
+                settings.analyzer_merge_gap_minutes,
+                i32::from(settings.capture_terminal_commands),
+                settings.terminal_history_path,
+                settings.day_boundary_hour,
+                settings.energy_lookback_weeks,
             ],
         )?;
         Ok(())
@@ -487,36 +749,107 @@ impl Database {
         )?;
 
         let blocks = stmt
-            .query_map([], |row| {
-                let work_item_ids_json: String = row.get(4)?;
-                let tags_json: String = row.get(6)?;
-                let source_str: String = row.get(7)?;
+            .query_map([], Self::row_to_time_block)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
 
-                Ok(crate::models::TimeBlock {
-                    id: parse_uuid(&row.get::<_, String>(0)?)?,
-                    start_time: parse_datetime(&row.get::<_, String>(1)?)?,
-                    end_time: parse_datetime(&row.get::<_, String>(2)?)?,
-                    project_id: row
-                        .get::<_, Option<String>>(3)?
-                        .and_then(|s| uuid::Uuid::parse_str(&s).ok()),
-                    work_item_ids: serde_json::from_str(&work_item_ids_json).unwrap_or_default(),
-                    description: row.get(5)?,
-                    tags: serde_json::from_str(&tags_json).unwrap_or_default(),
-                    source: match source_str.as_str() {
-                        "Manual" => crate::models::TimeBlockSource::Manual,
-                        "AiSuggested" => crate::models::TimeBlockSource::AiSuggested,
-                        _ => crate::models::TimeBlockSource::AutoDetected,
-                    },
-                    confidence: row.get(8)?,
-                    confirmed: row.get::<_, i32>(9)? != 0,
-                    created_at: parse_datetime(&row.get::<_, String>(10)?)?,
-                })
-            })?
+        Ok(blocks)
+    }
+
+    /// Get confirmed (reviewed) time blocks for a single project
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_confirmed_time_blocks_for_project(
+        &self,
+        project_id: uuid::Uuid,
+    ) -> Result<Vec<crate::models::TimeBlock>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, start_time, end_time, project_id, work_item_ids, description, tags, source, confidence, confirmed, created_at
+             FROM time_blocks
+             WHERE confirmed = 1 AND synced = 0 AND project_id = ?1
+             ORDER BY start_time ASC",
+        )?;
+
+        let blocks = stmt
+            .query_map(params![project_id.to_string()], Self::row_to_time_block)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(blocks)
+    }
+
+    /// Get confirmed time blocks in a range not yet written to the local worklog
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_unlogged_time_blocks(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<crate::models::TimeBlock>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, start_time, end_time, project_id, work_item_ids, description, tags, source, confidence, confirmed, created_at
+             FROM time_blocks
+             WHERE confirmed = 1 AND logged_locally = 0 AND start_time >= ?1 AND start_time < ?2
+             ORDER BY start_time ASC",
+        )?;
+
+        let blocks = stmt
+            .query_map(
+                params![start.to_rfc3339(), end.to_rfc3339()],
+                Self::row_to_time_block,
+            )?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
         Ok(blocks)
     }
 
+    /// Mark time blocks as written to the local worklog, all or none
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn mark_time_blocks_logged(&self, block_ids: &[uuid::Uuid]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare("UPDATE time_blocks SET logged_locally = 1 WHERE id = ?1")?;
+            for id in block_ids {
+                stmt.execute(params![id.to_string()])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub(crate) fn row_to_time_block(
+        row: &rusqlite::Row,
+    ) -> rusqlite::Result<crate::models::TimeBlock> {
+        let work_item_ids_json: String = row.get(4)?;
+        let tags_json: String = row.get(6)?;
+        let source_str: String = row.get(7)?;
+
+        Ok(crate::models::TimeBlock {
+            id: parse_uuid(&row.get::<_, String>(0)?)?,
+            start_time: parse_datetime(&row.get::<_, String>(1)?)?,
+            end_time: parse_datetime(&row.get::<_, String>(2)?)?,
+            project_id: row
+                .get::<_, Option<String>>(3)?
+                .and_then(|s| uuid::Uuid::parse_str(&s).ok()),
+            work_item_ids: serde_json::from_str(&work_item_ids_json).unwrap_or_default(),
+            description: row.get(5)?,
+            tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+            source: match source_str.as_str() {
+                "Manual" => crate::models::TimeBlockSource::Manual,
+                "AiSuggested" => crate::models::TimeBlockSource::AiSuggested,
+                _ => crate::models::TimeBlockSource::AutoDetected,
+            },
+            confidence: row.get(8)?,
+            confirmed: row.get::<_, i32>(9)? != 0,
+            created_at: parse_datetime(&row.get::<_, String>(10)?)?,
+        })
+    }
+
     /// Save a time block to the database
     ///
     /// # Errors
@@ -553,6 +886,23 @@ impl Database {
         Ok(())
     }
 
+    /// Check whether a time block has been synced to a PM system
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn is_time_block_synced(&self, block_id: uuid::Uuid) -> Result<bool> {
+        let synced: Option<i32> = self
+            .conn
+            .query_row(
+                "SELECT synced FROM time_blocks WHERE id = ?1",
+                params![block_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(synced.is_some_and(|s| s != 0))
+    }
+
     /// Mark a time block as synced
     ///
     /// # Errors
@@ -566,16 +916,186 @@ impl Database {
         Ok(())
     }
 
+    /// Get a time block by ID
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_time_block(&self, block_id: uuid::Uuid) -> Result<Option<crate::models::TimeBlock>> {
+        let block = self
+            .conn
+            .query_row(
+                "SELECT id, start_time, end_time, project_id, work_item_ids, description, tags, source, confidence, confirmed, created_at
+                 FROM time_blocks
+                 WHERE id = ?1",
+                params![block_id.to_string()],
+                Self::row_to_time_block,
+            )
+            .optional()?;
+        Ok(block)
+    }
+
+    /// Split a time block in two at `at`, attributing each part to a different issue
+    ///
+    /// The original block keeps its ID and ends at `at` attributed to `first_issue`;
+    /// a new block from `at` to the original end is attributed to `second_issue`.
+    /// Everything else (project, description, tags, confirmation) is copied, so the
+    /// two blocks are contiguous and add up to the original duration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the block does not exist, `at` is not strictly inside
+    /// the block, the block was already synced or logged, or the update fails
+    pub fn split_time_block(
+        &self,
+        block_id: uuid::Uuid,
+        at: DateTime<Utc>,
+        first_issue: uuid::Uuid,
+        second_issue: uuid::Uuid,
+    ) -> Result<(crate::models::TimeBlock, crate::models::TimeBlock)> {
+        let block = self
+            .get_time_block(block_id)?
+            .with_context(|| format!("Time block {block_id} not found"))?;
+        if at <= block.start_time || at >= block.end_time {
+            anyhow::bail!(
+                "Split time {} is outside block {} ({} - {})",
+                at.to_rfc3339(),
+                block_id,
+                block.start_time.to_rfc3339(),
+                block.end_time.to_rfc3339()
+            );
+        }
+        self.ensure_time_block_unsynced(block_id, "split")?;
+
+        let mut first = block.clone();
+        first.end_time = at;
+        first.work_item_ids = vec![first_issue];
+
+        let mut second = block.clone();
+        second.id = uuid::Uuid::new_v4();
+        second.start_time = at;
+        second.work_item_ids = vec![second_issue];
+        second.created_at = Utc::now();
+
+        let tx = self.conn.unchecked_transaction()?;
+        self.save_time_block(&first)?;
+        self.save_time_block(&second)?;
+        self.record_block_history(first.id, "split", Some(&block), Some(&first))?;
+        self.record_block_history(second.id, "split", Some(&block), Some(&second))?;
+        tx.commit()?;
+
+        Ok((first, second))
+    }
+
+    /// Replace a time block's contents after a review edit
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the block does not exist, was already synced or
+    /// logged, or the update fails
+    pub fn edit_time_block(&self, block: &crate::models::TimeBlock) -> Result<()> {
+        let before = self
+            .get_time_block(block.id)?
+            .with_context(|| format!("Time block {} not found", block.id))?;
+        self.ensure_time_block_unsynced(block.id, "edited")?;
+
+        let tx = self.conn.unchecked_transaction()?;
+        self.save_time_block(block)?;
+        self.record_block_history(block.id, "edit", Some(&before), Some(block))?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Merge `other_id` into `block_id`
+    ///
+    /// The kept block spans both, carries the issues and tags of both, and is
+    /// confirmed only if both were. The other block is deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either block does not exist, they are the same
+    /// block, either was already synced or logged, or the update fails
+    pub fn merge_time_blocks(
+        &self,
+        block_id: uuid::Uuid,
+        other_id: uuid::Uuid,
+    ) -> Result<crate::models::TimeBlock> {
+        if block_id == other_id {
+            anyhow::bail!("Cannot merge time block {block_id} with itself");
+        }
+        let block = self
+            .get_time_block(block_id)?
+            .with_context(|| format!("Time block {block_id} not found"))?;
+        let other = self
+            .get_time_block(other_id)?
+            .with_context(|| format!("Time block {other_id} not found"))?;
+        self.ensure_time_block_unsynced(block_id, "merged")?;
+        self.ensure_time_block_unsynced(other_id, "merged")?;
+
+        let mut merged = block.clone();
+        merged.start_time = block.start_time.min(other.start_time);
+        merged.end_time = block.end_time.max(other.end_time);
+        for id in &other.work_item_ids {
+            if !merged.work_item_ids.contains(id) {
+                merged.work_item_ids.push(*id);
+            }
+        }
+        for tag in &other.tags {
+            if !merged.tags.contains(tag) {
+                merged.tags.push(tag.clone());
+            }
+        }
+        if merged.description.is_empty() {
+            merged.description.clone_from(&other.description);
+        } else if !other.description.is_empty() && other.description != merged.description {
+            merged.description = format!("{}; {}", merged.description, other.description);
+        }
+        merged.confirmed = block.confirmed && other.confirmed;
+
+        let tx = self.conn.unchecked_transaction()?;
+        self.save_time_block(&merged)?;
+        self.conn.execute(
+            "DELETE FROM time_blocks WHERE id = ?1",
+            params![other_id.to_string()],
+        )?;
+        self.record_block_history(block_id, "merge", Some(&block), Some(&merged))?;
+        self.record_block_history(other_id, "merge", Some(&other), Some(&merged))?;
+        tx.commit()?;
+
+        Ok(merged)
+    }
+
+    /// Fail if a block was already synced or logged, since its time was reported
+    fn ensure_time_block_unsynced(&self, block_id: uuid::Uuid, action: &str) -> Result<()> {
+        let (synced, logged): (bool, bool) = self.conn.query_row(
+            "SELECT synced, logged_locally FROM time_blocks WHERE id = ?1",
+            params![block_id.to_string()],
+            |row| Ok((row.get::<_, i32>(0)? != 0, row.get::<_, i32>(1)? != 0)),
+        )?;
+        if synced || logged {
+            anyhow::bail!(
+                "Time block {block_id} was already synced or logged; it can no longer be {action}"
+            );
+        }
+        Ok(())
+    }
+
     /// Confirm a time block (mark as reviewed)
     ///
     /// # Errors
     ///
     /// Returns an error if the database operation fails
     pub fn confirm_time_block(&self, block_id: uuid::Uuid) -> Result<()> {
+        let before = self.get_time_block(block_id)?;
         self.conn.execute(
             "UPDATE time_blocks SET confirmed = 1 WHERE id = ?1",
             params![block_id.to_string()],
         )?;
+        if let Some(before) = before.filter(|block| !block.confirmed) {
+            let mut after = before.clone();
+            after.confirmed = true;
+            self.record_block_history(block_id, "confirm", Some(&before), Some(&after))?;
+        }
         Ok(())
     }
 
@@ -675,16 +1195,49 @@ impl Database {
     /// # Errors
     ///
     /// Returns an error if the database operation fails
+    ///
+    /// The session records the tracking timezone configured at this moment.
     pub fn create_session(&self, start_time: DateTime<Utc>) -> Result<uuid::Uuid> {
         let session_id = uuid::Uuid::new_v4();
+        let timezone = self.get_settings()?.timezone;
         self.conn.execute(
-            "INSERT INTO sessions (id, start_time, end_time, total_active_seconds, idle_seconds, interruption_count, categories, work_item_ids)
-             VALUES (?1, ?2, NULL, 0, 0, 0, '[]', '[]')",
-            params![session_id.to_string(), start_time.to_rfc3339()],
+            "INSERT INTO sessions (id, start_time, end_time, total_active_seconds, idle_seconds, interruption_count, categories, work_item_ids, timezone)
+             VALUES (?1, ?2, NULL, 0, 0, 0, '[]', '[]', ?3)",
+            params![session_id.to_string(), start_time.to_rfc3339(), timezone.to_string()],
         )?;
         Ok(session_id)
     }
 
+    /// Tracking timezones sessions in a range were captured in, most used first
+    ///
+    /// Sessions from before timezones were recorded are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_session_timezones(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<(TrackingTimezone, usize)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT timezone, COUNT(*) FROM sessions
+             WHERE start_time >= ?1 AND start_time <= ?2 AND timezone IS NOT NULL AND discarded = 0
+             GROUP BY timezone
+             ORDER BY COUNT(*) DESC, timezone ASC",
+        )?;
+        let rows = stmt
+            .query_map(params![start.to_rfc3339(), end.to_rfc3339()], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, usize>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(timezone, count)| Some((timezone.parse().ok()?, count)))
+            .collect())
+    }
+
     /// Update session statistics
     ///
     /// # Errors
@@ -732,6 +1285,44 @@ impl Database {
         Ok(())
     }
 
+    /// Discard a session so it is excluded from reports and insights
+    ///
+    /// Closes the session (and any of its ongoing spans) if still open.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn discard_session(&self, session_id: uuid::Uuid, end_time: DateTime<Utc>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE sessions SET discarded = 1, end_time = COALESCE(end_time, ?1) WHERE id = ?2",
+            params![end_time.to_rfc3339(), session_id.to_string()],
+        )?;
+
+        for span in self.get_activity_spans_by_session(session_id)? {
+            if span.is_ongoing() {
+                self.finalize_activity_span(span.id, end_time)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check whether a session has been discarded
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn is_session_discarded(&self, session_id: uuid::Uuid) -> Result<bool> {
+        let discarded: Option<i32> = self
+            .conn
+            .query_row(
+                "SELECT discarded FROM sessions WHERE id = ?1",
+                [session_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(discarded.unwrap_or(0) != 0)
+    }
+
     /// Get the current ongoing session (if any)
     ///
     /// # Errors
@@ -747,7 +1338,7 @@ impl Database {
             .query_row(
                 "SELECT id, start_time, end_time, total_active_seconds, idle_seconds, interruption_count, categories, work_item_ids
                  FROM sessions
-                 WHERE end_time IS NULL
+                 WHERE end_time IS NULL AND discarded = 0
                  ORDER BY start_time DESC
                  LIMIT 1",
                 [],
@@ -773,6 +1364,9 @@ impl Database {
 
     /// Get sessions within a time range
     ///
+    /// Sessions with no active time (opened and immediately closed by idle) are
+    /// skipped unless `include_empty` is set.
+    ///
     /// # Errors
     ///
     /// Returns an error if the database query fails
@@ -780,36 +1374,46 @@ impl Database {
     /// # Panics
     ///
     /// May panic if UUID or datetime parsing fails for corrupted database entries
-    pub fn get_sessions(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Session>> {
+    pub fn get_sessions(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        include_empty: bool,
+    ) -> Result<Vec<Session>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, start_time, end_time, total_active_seconds, idle_seconds, interruption_count, categories, work_item_ids
              FROM sessions
-             WHERE start_time >= ?1 AND start_time <= ?2
+             WHERE start_time >= ?1 AND start_time <= ?2 AND discarded = 0
+               AND (?3 OR total_active_seconds > 0)
              ORDER BY start_time ASC",
         )?;
 
         let sessions = stmt
-            .query_map(params![start.to_rfc3339(), end.to_rfc3339()], |row| {
-                Ok(Session {
-                    id: parse_uuid(&row.get::<_, String>(0)?)?,
-                    start_time: parse_datetime(&row.get::<_, String>(1)?)?,
-                    end_time: row
-                        .get::<_, Option<String>>(2)?
-                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                        .map(|dt| dt.with_timezone(&Utc)),
-                    total_active_seconds: row.get(3)?,
-                    idle_seconds: row.get(4)?,
-                    interruption_count: row.get(5)?,
-                    categories: serde_json::from_str(&row.get::<_, String>(6)?).unwrap_or_default(),
-                    work_item_ids: serde_json::from_str(&row.get::<_, String>(7)?)
-                        .unwrap_or_default(),
-                })
-            })?
+            .query_map(
+                params![start.to_rfc3339(), end.to_rfc3339(), include_empty],
+                Self::row_to_session,
+            )?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
         Ok(sessions)
     }
 
+    pub(crate) fn row_to_session(row: &rusqlite::Row) -> rusqlite::Result<Session> {
+        Ok(Session {
+            id: parse_uuid(&row.get::<_, String>(0)?)?,
+            start_time: parse_datetime(&row.get::<_, String>(1)?)?,
+            end_time: row
+                .get::<_, Option<String>>(2)?
+                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
+            total_active_seconds: row.get(3)?,
+            idle_seconds: row.get(4)?,
+            interruption_count: row.get(5)?,
+            categories: serde_json::from_str(&row.get::<_, String>(6)?).unwrap_or_default(),
+            work_item_ids: serde_json::from_str(&row.get::<_, String>(7)?).unwrap_or_default(),
+        })
+    }
+
     // ==================== Classification Rules ====================
 
     /// Save a new classification rule (user correction)
@@ -969,3 +1573,423 @@ impl Database {
         ai_config::update_ai_config_field(&self.conn, key, value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn activity_at(start: DateTime<Utc>, offset_secs: i64, duration_seconds: u32) -> Activity {
+        let mut activity = Activity::new(
+            "com.apple.Terminal".to_string(),
+            "Terminal".to_string(),
+            duration_seconds,
+        );
+        activity.timestamp = start + chrono::Duration::seconds(offset_secs);
+        activity
+    }
+
+    #[test]
+    fn test_bulk_activity_inserts_reuse_cached_statements() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let start = Utc::now() - chrono::Duration::days(30);
+
+        let started = std::time::Instant::now();
+        for i in 0..10_000 {
+            db.insert_activity(&activity_at(start, i * 60, 30)).unwrap();
+        }
+        let elapsed = started.elapsed();
+
+        let stored = db
+            .get_activities(start, start + chrono::Duration::days(10))
+            .unwrap();
+        assert_eq!(stored.len(), 10_000);
+        // Generous bound; only catches a pathological slowdown
+        assert!(elapsed < std::time::Duration::from_mins(1), "{elapsed:?}");
+    }
+
+    #[test]
+    fn test_vacuum_reclaims_deleted_rows_and_keeps_data_readable() {
+        let path = std::env::temp_dir().join(format!("toki-vacuum-{}.db", uuid::Uuid::new_v4()));
+        let db = Database::new(Some(path.clone())).unwrap();
+        let start = Utc::now() - chrono::Duration::days(30);
+        for i in 0..5_000 {
+            db.insert_activity(&activity_at(start, i * 60, 30)).unwrap();
+        }
+        let kept_from = start + chrono::Duration::minutes(4_990);
+        db.delete_activities(start, kept_from - chrono::Duration::seconds(1))
+            .unwrap();
+        let before = std::fs::metadata(&path).unwrap().len();
+
+        db.vacuum().unwrap();
+
+        let after = std::fs::metadata(&path).unwrap().len();
+        assert!(after < before, "{after} >= {before}");
+        let remaining = db
+            .get_activities(start, start + chrono::Duration::days(10))
+            .unwrap();
+        assert_eq!(remaining.len(), 10);
+        db.insert_activity(&activity_at(start, 0, 30)).unwrap();
+
+        drop(db);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_short_activities_merged_into_previous() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let mut settings = db.get_settings().unwrap();
+        settings.min_activity_seconds = 5;
+        db.update_settings(&settings).unwrap();
+
+        let start = Utc::now() - chrono::Duration::hours(1);
+        for (offset, duration) in [(0, 60), (60, 2), (62, 0), (62, 3), (65, 30), (95, 4)] {
+            db.insert_activity(&activity_at(start, offset, duration))
+                .unwrap();
+        }
+
+        let activities = db.get_activities(start, Utc::now()).unwrap();
+        let durations: Vec<u32> = activities.iter().map(|a| a.duration_seconds).collect();
+        // Newest first
+        assert_eq!(durations, [34, 65]);
+    }
+
+    fn block_between(start: DateTime<Utc>, minutes: i64) -> crate::models::TimeBlock {
+        crate::models::TimeBlock::manual(
+            start,
+            start + chrono::Duration::minutes(minutes),
+            "Feature work".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_split_time_block_produces_contiguous_blocks() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let start = Utc::now() - chrono::Duration::hours(3);
+        let block = block_between(start, 120);
+        db.save_time_block(&block).unwrap();
+
+        let (first_issue, second_issue) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        let at = start + chrono::Duration::minutes(45);
+        let (first, second) = db
+            .split_time_block(block.id, at, first_issue, second_issue)
+            .unwrap();
+
+        assert_eq!(first.id, block.id);
+        assert_eq!((first.start_time, first.end_time), (block.start_time, at));
+        assert_eq!((second.start_time, second.end_time), (at, block.end_time));
+        assert_eq!(
+            (first.end_time - first.start_time) + (second.end_time - second.start_time),
+            block.end_time - block.start_time
+        );
+        assert_eq!(first.work_item_ids, vec![first_issue]);
+        assert_eq!(second.work_item_ids, vec![second_issue]);
+        assert_eq!(second.description, block.description);
+
+        let stored = db.get_time_block(second.id).unwrap().unwrap();
+        assert_eq!(stored.start_time, at);
+        assert_eq!(db.get_time_block(block.id).unwrap().unwrap().end_time, at);
+    }
+
+    #[test]
+    fn test_split_time_block_rejects_time_outside_block() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let start = Utc::now() - chrono::Duration::hours(3);
+        let block = block_between(start, 60);
+        db.save_time_block(&block).unwrap();
+        let issue = uuid::Uuid::new_v4();
+
+        for at in [
+            start - chrono::Duration::minutes(5),
+            start,
+            block.end_time,
+            block.end_time + chrono::Duration::minutes(5),
+        ] {
+            assert!(db.split_time_block(block.id, at, issue, issue).is_err());
+        }
+
+        let unchanged = db.get_time_block(block.id).unwrap().unwrap();
+        assert_eq!(unchanged.end_time, block.end_time);
+        assert_eq!(db.get_confirmed_time_blocks().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_block_history_records_edit_then_merge() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let start = Utc::now() - chrono::Duration::hours(3);
+        let mut block = block_between(start, 60);
+        let other = block_between(start + chrono::Duration::minutes(60), 30);
+        db.save_time_block(&block).unwrap();
+        db.save_time_block(&other).unwrap();
+
+        block.description = "Login bug".to_string();
+        db.edit_time_block(&block).unwrap();
+        let merged = db.merge_time_blocks(block.id, other.id).unwrap();
+
+        let history = db.get_block_history(block.id).unwrap();
+        let actions: Vec<_> = history.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(actions, vec!["edit", "merge"]);
+        assert!(history[0].after.as_deref().unwrap().ends_with("Login bug"));
+        assert_eq!(history[1].before, history[0].after);
+        assert_eq!(history[1].after, Some(merged.summary()));
+        assert_eq!(merged.end_time, other.end_time);
+
+        assert!(db.get_time_block(other.id).unwrap().is_none());
+        let absorbed = db.get_block_history(other.id).unwrap();
+        assert_eq!(absorbed.len(), 1);
+        assert_eq!(absorbed[0].action, "merge");
+    }
+
+    #[test]
+    fn test_short_activity_kept_without_adjacent_record() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let start = Utc::now() - chrono::Duration::hours(1);
+        assert_eq!(db.get_settings().unwrap().min_activity_seconds, 1);
+
+        // Nothing before it, then a gap too long to merge across
+        db.insert_activity(&activity_at(start, 0, 0)).unwrap();
+        db.insert_activity(&activity_at(start, 600, 0)).unwrap();
+        db.insert_activity(&activity_at(start, 600, 10)).unwrap();
+
+        let activities = db.get_activities(start, Utc::now()).unwrap();
+        assert_eq!(activities.len(), 3);
+        assert_eq!(
+            activities.iter().map(|a| a.duration_seconds).sum::<u32>(),
+            10
+        );
+    }
+
+    #[test]
+    fn test_last_embedding_build_and_stats() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        assert_eq!(db.get_last_embedding_build().unwrap(), None);
+
+        let first = Utc::now() - chrono::Duration::days(2);
+        let latest = Utc::now() - chrono::Duration::hours(1);
+        db.record_embedding_build(latest, 3).unwrap();
+        db.record_embedding_build(first, 10).unwrap();
+
+        let last = db.get_last_embedding_build().unwrap().unwrap();
+        assert_eq!(last.timestamp(), latest.timestamp());
+
+        let stats = db.get_embedding_stats().unwrap();
+        assert_eq!(stats.issues, 0);
+        assert_eq!(stats.embedded_issues, 0);
+        assert_eq!(stats.last_build, Some(last));
+    }
+
+    #[test]
+    fn test_issue_time_stats_counts_distinct_days_and_bounds() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let project = db.get_or_create_project("toki", "/src/toki").unwrap();
+        let issue = crate::models::IssueCandidate::new(
+            project.id,
+            "PROJ-9".to_string(),
+            "plane".to_string(),
+            "Calibrate estimates".to_string(),
+        );
+        db.upsert_issue_candidate(&issue).unwrap();
+        let work_item = WorkItem::new("PROJ-9".to_string(), "GitBranch".to_string());
+        let other = WorkItem::new("PROJ-10".to_string(), "GitBranch".to_string());
+        db.upsert_work_item(&work_item).unwrap();
+        db.upsert_work_item(&other).unwrap();
+
+        let day = |d: u32, h: u32| Utc.with_ymd_and_hms(2025, 3, d, h, 0, 0).unwrap();
+        let span_between = |work_item_id, from: DateTime<Utc>, minutes: i64| {
+            let span = crate::models::ActivitySpan::new(
+                "com.microsoft.VSCode".to_string(),
+                "Coding".to_string(),
+                from,
+                Some(project.id),
+                Some(work_item_id),
+                None,
+            );
+            db.create_activity_span(&span).unwrap();
+            db.finalize_activity_span(span.id, from + chrono::Duration::minutes(minutes))
+                .unwrap();
+        };
+        span_between(work_item.id, day(3, 9), 60);
+        span_between(work_item.id, day(3, 14), 30);
+        span_between(work_item.id, day(5, 10), 45);
+        span_between(work_item.id, day(20, 10), 60); // outside the range
+        span_between(other.id, day(4, 10), 60); // another issue
+        span_between(issue.id, day(7, 11), 15); // attached to the candidate in review
+
+        let stats = db
+            .issue_time_stats("proj-9", day(1, 0), day(10, 0))
+            .unwrap();
+        assert_eq!(stats.issue_system, "plane");
+        assert_eq!(stats.total_seconds, (60 + 30 + 45 + 15) * 60);
+        assert_eq!(stats.days_worked, 3);
+        assert_eq!(stats.first_activity, Some(day(3, 9)));
+        assert_eq!(
+            stats.last_activity,
+            Some(day(7, 11) + chrono::Duration::minutes(15))
+        );
+
+        let empty = db
+            .issue_time_stats("PROJ-9", day(8, 0), day(10, 0))
+            .unwrap();
+        assert_eq!((empty.total_seconds, empty.days_worked), (0, 0));
+        assert_eq!(empty.first_activity, None);
+    }
+
+    #[test]
+    fn test_commit_issue_attributed_to_spans_within_grace() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let project = db.get_or_create_project("toki", "/src/toki").unwrap();
+        let other = db.get_or_create_project("site", "/src/site").unwrap();
+        let issue = WorkItem::new("PROJ-42".to_string(), "GitBranch".to_string());
+        let earlier = WorkItem::new("PROJ-7".to_string(), "GitBranch".to_string());
+        db.upsert_work_item(&issue).unwrap();
+        db.upsert_work_item(&earlier).unwrap();
+
+        let committed_at = Utc::now() - chrono::Duration::hours(1);
+        let minutes = |m: i64| committed_at + chrono::Duration::minutes(m);
+        let span_between = |project_id, work_item_id, from: i64, to: i64| {
+            let span = crate::models::ActivitySpan::new(
+                "com.microsoft.VSCode".to_string(),
+                "Coding".to_string(),
+                minutes(from),
+                Some(project_id),
+                work_item_id,
+                None,
+            );
+            db.create_activity_span(&span).unwrap();
+            db.finalize_activity_span(span.id, minutes(to)).unwrap();
+            span.id
+        };
+        let too_old = span_between(project.id, None, -150, -90);
+        let before = span_between(project.id, None, -50, -20);
+        let spanning = span_between(project.id, None, -20, 5);
+        let other_repo = span_between(other.id, None, -40, -30);
+        let attributed = span_between(project.id, Some(earlier.id), -30, -25);
+
+        let updated = db
+            .attribute_project_spans(project.id, issue.id, minutes(-60), committed_at)
+            .unwrap();
+        assert_eq!(updated, 2);
+
+        let work_item = |id| db.get_activity_span(id).unwrap().unwrap().work_item_id;
+        assert_eq!(work_item(before), Some(issue.id));
+        assert_eq!(work_item(spanning), Some(issue.id));
+        assert_eq!(work_item(too_old), None);
+        assert_eq!(work_item(other_repo), None);
+        assert_eq!(work_item(attributed), Some(earlier.id));
+    }
+
+    #[test]
+    fn test_search_projects_ranks_partial_names() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        for name in ["api-gateway", "payments", "website", "Api"] {
+            db.get_or_create_project(name, &format!("/src/{name}"))
+                .unwrap();
+        }
+
+        let exact = db.search_projects(" API ").unwrap();
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].name, "Api");
+        assert!((exact[0].score - 1.0).abs() < f32::EPSILON);
+
+        let names: Vec<_> = db
+            .search_projects("gate")
+            .unwrap()
+            .into_iter()
+            .map(|m| m.name)
+            .collect();
+        assert_eq!(names.first().map(String::as_str), Some("api-gateway"));
+        assert!(!names.contains(&"website".to_string()));
+        assert!(db.search_projects("").unwrap().is_empty());
+
+        for i in 0..12 {
+            db.get_or_create_project(&format!("service-{i}"), &format!("/src/service-{i}"))
+                .unwrap();
+        }
+        assert_eq!(
+            db.search_projects("service").unwrap().len(),
+            MAX_PROJECT_MATCHES
+        );
+    }
+
+    #[test]
+    fn test_notion_workspace_looked_up_by_database_id() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let project = db.get_or_create_project("toki", "/src/toki").unwrap();
+        db.link_project_to_pm(
+            project.id,
+            "notion",
+            "abcd1234-5678-9abc-def0-123456789abc",
+            Some("acme"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            db.get_notion_workspace("ABCD123456789ABCDEF0123456789ABC")
+                .unwrap()
+                .as_deref(),
+            Some("acme")
+        );
+        assert_eq!(db.get_notion_workspace("ffff0000").unwrap(), None);
+    }
+
+    #[test]
+    fn test_nearest_issue_candidates_keeps_top_k_best_first() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        let project = db.get_or_create_project("toki", "/src/toki").unwrap();
+        let issue = |id: &str, status: &str, embedding: Option<Vec<f32>>| {
+            let mut issue = crate::models::IssueCandidate::new(
+                project.id,
+                id.to_string(),
+                "plane".to_string(),
+                format!("Issue {id}"),
+            );
+            issue.status = status.to_string();
+            issue.embedding = embedding;
+            db.upsert_issue_candidate(&issue).unwrap();
+        };
+        issue("PROJ-1", "backlog", Some(vec![1.0, 0.0]));
+        issue("PROJ-2", "backlog", Some(vec![0.0, 1.0]));
+        issue("PROJ-3", "in_progress", Some(vec![0.8, 0.6]));
+        issue("PROJ-4", "done", Some(vec![1.0, 0.0]));
+        issue("PROJ-5", "backlog", None);
+        issue("PROJ-6", "backlog", Some(vec![1.0, 0.0, 0.0]));
+
+        let nearest = db
+            .nearest_issue_candidates(project.id, &[1.0, 0.1], 2)
+            .unwrap();
+        let ids: Vec<&str> = nearest
+            .iter()
+            .map(|(c, _)| c.external_id.as_str())
+            .collect();
+        assert_eq!(ids, ["PROJ-1", "PROJ-3"]);
+        assert!(nearest[0].1 > nearest[1].1);
+
+        let all = db
+            .nearest_issue_candidates(project.id, &[1.0, 0.1], 10)
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        assert!(db
+            .nearest_issue_candidates(project.id, &[1.0, 0.1], 0)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_stale_sync_checkpoint_is_ignored() {
+        let db = Database::new(Some(PathBuf::from(":memory:"))).unwrap();
+        db.save_sync_checkpoint("notion-db", "cursor-4").unwrap();
+        assert_eq!(
+            db.get_sync_checkpoint("notion-db").unwrap().as_deref(),
+            Some("cursor-4")
+        );
+
+        let stale = Utc::now() - chrono::Duration::seconds(SYNC_CHECKPOINT_MAX_AGE_SECS + 60);
+        db.conn
+            .execute(
+                "UPDATE sync_checkpoints SET updated_at = ?1 WHERE database_id = 'notion-db'",
+                [stale.to_rfc3339()],
+            )
+            .unwrap();
+        assert_eq!(db.get_sync_checkpoint("notion-db").unwrap(), None);
+    }
+}
@@ -0,0 +1,68 @@
+//! Per-project working hours overrides
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use rusqlite::params;
+
+use super::helpers::parse_uuid;
+use super::Database;
+use crate::models::WorkHours;
+
+impl Database {
+    /// Get all per-project working hours overrides, keyed by project id
+    ///
+    /// Overrides that no longer parse are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_project_work_hours(&self) -> Result<HashMap<uuid::Uuid, WorkHours>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT project_id, hours FROM project_work_hours")?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    parse_uuid(&row.get::<_, String>(0)?)?,
+                    row.get::<_, String>(1)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(project_id, hours)| Some((project_id, hours.parse().ok()?)))
+            .collect())
+    }
+
+    /// Set the working hours for a project, replacing any existing override
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn set_project_work_hours(&self, project_id: uuid::Uuid, hours: WorkHours) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO project_work_hours (project_id, hours) VALUES (?1, ?2)
+             ON CONFLICT(project_id) DO UPDATE SET hours = excluded.hours",
+            params![project_id.to_string(), hours.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Remove a project's working hours override
+    ///
+    /// Returns `true` if an override was removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn remove_project_work_hours(&self, project_id: uuid::Uuid) -> Result<bool> {
+        let affected = self.conn.execute(
+            "DELETE FROM project_work_hours WHERE project_id = ?1",
+            params![project_id.to_string()],
+        )?;
+        Ok(affected > 0)
+    }
+}
@@ -1,11 +1,53 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
 
 use super::helpers::{parse_datetime, parse_uuid};
 use super::Database;
 use crate::models::Project;
 
+/// Most candidates `search_projects` returns
+pub const MAX_PROJECT_MATCHES: usize = 10;
+
+/// Score added when the query appears in the project name as-is
+const SUBSTRING_BONUS: f32 = 0.5;
+
+/// Lowest score a project needs to be offered as a candidate
+const MIN_PROJECT_MATCH_SCORE: f32 = 0.3;
+
+/// A project ranked against a search query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectMatch {
+    pub id: uuid::Uuid,
+    pub name: String,
+    pub path: String,
+    pub score: f32,
+}
+
+/// Jaccard similarity of the characters of two names (0.0-1.0)
+#[must_use]
+pub fn name_similarity(a: &str, b: &str) -> f32 {
+    if a == b {
+        return 1.0;
+    }
+
+    let a_chars: std::collections::HashSet<_> = a.chars().collect();
+    let b_chars: std::collections::HashSet<_> = b.chars().collect();
+
+    let intersection = a_chars.intersection(&b_chars).count();
+    let union = a_chars.union(&b_chars).count();
+
+    if union == 0 {
+        0.0
+    } else {
+        // Names are short, so the counts convert to f32 exactly
+        #[allow(clippy::cast_precision_loss)]
+        let result = intersection as f32 / union as f32;
+        result
+    }
+}
+
 impl Database {
     /// Get or create a project by path
     ///
@@ -116,6 +158,53 @@ impl Database {
         Ok(projects)
     }
 
+    /// Rank projects by how well their name matches `query`
+    ///
+    /// Names are compared case-insensitively with [`name_similarity`], plus a
+    /// bonus when the query is part of the name. A project named exactly like
+    /// the query is returned on its own. At most [`MAX_PROJECT_MATCHES`]
+    /// candidates come back, best first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn search_projects(&self, query: &str) -> Result<Vec<ProjectMatch>> {
+        let query = query.trim().to_lowercase();
+        let to_match = |project: Project, score| ProjectMatch {
+            id: project.id,
+            name: project.name,
+            path: project.path,
+            score,
+        };
+
+        let mut projects = self.get_all_projects()?;
+        if let Some(exact) = projects.iter().position(|p| p.name.to_lowercase() == query) {
+            return Ok(vec![to_match(projects.swap_remove(exact), 1.0)]);
+        }
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut matches: Vec<ProjectMatch> = projects
+            .into_iter()
+            .filter_map(|project| {
+                let name = project.name.to_lowercase();
+                let mut score = name_similarity(&query, &name);
+                if name.contains(&query) {
+                    score += SUBSTRING_BONUS;
+                }
+                (score >= MIN_PROJECT_MATCH_SCORE).then(|| to_match(project, score))
+            })
+            .collect();
+        matches.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        matches.truncate(MAX_PROJECT_MATCHES);
+        Ok(matches)
+    }
+
     /// Get projects that have PM system linked
     ///
     /// # Errors
@@ -136,6 +225,29 @@ impl Database {
         Ok(projects)
     }
 
+    /// Get the workspace a Notion database was linked with, if any
+    ///
+    /// Database ids are compared without dashes, so both id forms Notion accepts match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_notion_workspace(&self, database_id: &str) -> Result<Option<String>> {
+        let workspace = self
+            .conn
+            .query_row(
+                "SELECT pm_workspace FROM projects
+                 WHERE pm_system = 'notion'
+                   AND LOWER(REPLACE(pm_project_id, '-', '')) = LOWER(REPLACE(?1, '-', ''))
+                 ORDER BY pm_workspace IS NULL, last_active DESC
+                 LIMIT 1",
+                params![database_id],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .optional()?;
+        Ok(workspace.flatten())
+    }
+
     /// Link a project to a PM system
     ///
     /// # Errors
@@ -1,5 +1,8 @@
+use std::collections::HashSet;
+
 use anyhow::Result;
-use rusqlite::params;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
 
 use super::helpers::{parse_datetime, parse_uuid};
 use super::Database;
@@ -228,7 +231,10 @@ impl Database {
                             CAST((julianday(cs.ended_at) - julianday(cs.started_at)) * 86400 AS INTEGER)
                         ELSE 0
                     END
-                ) as total_seconds
+                ) as total_seconds,
+                MIN(cs.started_at),
+                MAX(cs.ended_at),
+                COUNT(DISTINCT substr(cs.started_at, 1, 10))
              FROM session_issues si
              JOIN claude_sessions cs ON si.session_id = cs.id
              WHERE cs.ended_at IS NOT NULL
@@ -244,6 +250,15 @@ impl Database {
                     issue_system: row.get(1)?,
                     session_count: row.get(2)?,
                     total_seconds: u32::try_from(row.get::<_, i64>(3)?.max(0)).unwrap_or(u32::MAX),
+                    first_activity: row
+                        .get::<_, Option<String>>(4)?
+                        .map(|s| parse_datetime(&s))
+                        .transpose()?,
+                    last_activity: row
+                        .get::<_, Option<String>>(5)?
+                        .map(|s| parse_datetime(&s))
+                        .transpose()?,
+                    days_worked: row.get(6)?,
                 })
             })?
             .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -251,6 +266,81 @@ impl Database {
         Ok(stats)
     }
 
+    /// Aggregate the activity spans attributed to one issue over a date range
+    ///
+    /// Spans count when they start in `[start, end)` and are linked to the issue
+    /// with this external ID, either through a detected work item or directly to
+    /// the synced issue candidate (as review and auto-attach link them). The
+    /// issue system comes from the synced issue when there is one. Sessions are
+    /// tracking sessions, and days are counted in the configured timezone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn issue_time_stats(
+        &self,
+        external_id: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<IssueTimeStats> {
+        let timezone = self.get_settings()?.timezone;
+        let issue_system: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT external_system FROM issue_candidates WHERE external_id = ?1 COLLATE NOCASE
+                 UNION ALL
+                 SELECT external_system FROM work_items WHERE external_id = ?1 COLLATE NOCASE
+                 LIMIT 1",
+                [external_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT session_id, start_time, end_time, duration_seconds
+             FROM activity_spans
+             WHERE work_item_id IN (
+                     SELECT id FROM work_items WHERE external_id = ?1 COLLATE NOCASE
+                     UNION
+                     SELECT id FROM issue_candidates WHERE external_id = ?1 COLLATE NOCASE)
+               AND start_time >= ?2 AND start_time < ?3
+               AND (session_id IS NULL OR session_id NOT IN (SELECT id FROM sessions WHERE discarded = 1))
+             ORDER BY start_time ASC",
+        )?;
+        let mut rows = stmt.query(params![external_id, start.to_rfc3339(), end.to_rfc3339()])?;
+
+        let mut stats = IssueTimeStats {
+            issue_id: external_id.to_string(),
+            issue_system: issue_system.unwrap_or_default(),
+            session_count: 0,
+            total_seconds: 0,
+            first_activity: None,
+            last_activity: None,
+            days_worked: 0,
+        };
+        let mut sessions = HashSet::new();
+        let mut days = HashSet::new();
+        while let Some(row) = rows.next()? {
+            let session_id: Option<String> = row.get(0)?;
+            let span_start = parse_datetime(&row.get::<_, String>(1)?)?;
+            let span_end = row
+                .get::<_, Option<String>>(2)?
+                .map(|s| parse_datetime(&s))
+                .transpose()?
+                .unwrap_or(span_start);
+
+            sessions.extend(session_id);
+            days.insert(timezone.start_of_day(span_start));
+            stats.total_seconds = stats.total_seconds.saturating_add(row.get(3)?);
+            stats.first_activity = stats.first_activity.or(Some(span_start));
+            stats.last_activity = stats.last_activity.max(Some(span_end));
+        }
+        stats.session_count = u32::try_from(sessions.len()).unwrap_or(u32::MAX);
+        stats.days_worked = u32::try_from(days.len()).unwrap_or(u32::MAX);
+
+        Ok(stats)
+    }
+
     /// Helper function to parse `SessionIssue` from database row
     pub(crate) fn row_to_session_issue(row: &rusqlite::Row) -> rusqlite::Result<SessionIssue> {
         let relationship_str: String = row.get(3)?;
@@ -275,4 +365,7 @@ pub struct IssueTimeStats {
     pub issue_system: String,
     pub session_count: u32,
     pub total_seconds: u32,
+    pub first_activity: Option<DateTime<Utc>>,
+    pub last_activity: Option<DateTime<Utc>>,
+    pub days_worked: u32, // Distinct days with activity on the issue
 }
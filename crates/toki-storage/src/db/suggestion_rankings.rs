@@ -0,0 +1,79 @@
+//! Cached `toki next` rankings
+//!
+//! Scoring every open issue is slow (estimates, embedding comparisons), so the
+//! ranked list for a project is kept and reused for a while. A ranking goes
+//! stale when it outlives its TTL, when activity is recorded after it was made,
+//! or when the project's issues are synced again.
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use rusqlite::{params, OptionalExtension};
+
+use super::helpers::parse_datetime;
+use super::Database;
+use crate::models::RankedSuggestion;
+
+impl Database {
+    /// Store the ranking for a project and constraint set, replacing any earlier one
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation or JSON serialization fails
+    pub fn save_suggestion_ranking(
+        &self,
+        project_id: uuid::Uuid,
+        constraints: &str,
+        ranking: &[RankedSuggestion],
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO suggestion_rankings (project_id, constraints, ranking, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                project_id.to_string(),
+                constraints,
+                serde_json::to_string(ranking)?,
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Get a cached ranking younger than `ttl_seconds`, unless it went stale
+    ///
+    /// Returns `None` if there is no ranking, it expired, activity was recorded
+    /// after it was made, or the project's issues were synced since.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_suggestion_ranking(
+        &self,
+        project_id: uuid::Uuid,
+        constraints: &str,
+        ttl_seconds: u32,
+    ) -> Result<Option<Vec<RankedSuggestion>>> {
+        let row: Option<(String, String, bool)> = self
+            .conn
+            .query_row(
+                "SELECT r.ranking, r.created_at,
+                        EXISTS (SELECT 1 FROM activity_spans WHERE start_time > r.created_at)
+                        OR EXISTS (SELECT 1 FROM issue_candidates
+                                   WHERE project_id = r.project_id AND last_synced > r.created_at)
+                 FROM suggestion_rankings r
+                 WHERE r.project_id = ?1 AND r.constraints = ?2",
+                params![project_id.to_string(), constraints],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+
+        let Some((ranking, created_at, superseded)) = row else {
+            return Ok(None);
+        };
+        let expired =
+            parse_datetime(&created_at)? + Duration::seconds(i64::from(ttl_seconds)) <= Utc::now();
+        if superseded || expired {
+            return Ok(None);
+        }
+        Ok(serde_json::from_str(&ranking).ok())
+    }
+}
@@ -0,0 +1,105 @@
+//! Pagination checkpoints for PM syncs
+//!
+//! A paginated fetch that stops early records the cursor it stopped at, so the
+//! next sync of that database resumes there instead of starting over.
+//! Checkpoints older than [`SYNC_CHECKPOINT_MAX_AGE_SECS`] are ignored, since
+//! the cursor has likely expired and the data moved on.
+//!
+//! A sync that got through every page records a watermark instead: the latest
+//! page edit it saw, so the next sync only asks for pages edited since then.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, OptionalExtension};
+
+use super::helpers::parse_datetime;
+use super::Database;
+
+/// How long a saved cursor is resumed from
+pub const SYNC_CHECKPOINT_MAX_AGE_SECS: i64 = 60 * 60;
+
+impl Database {
+    /// Record the cursor an interrupted fetch of a database stopped at
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn save_sync_checkpoint(&self, database_id: &str, cursor: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO sync_checkpoints (database_id, cursor, updated_at)
+             VALUES (?1, ?2, ?3)",
+            params![database_id, cursor, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Cursor to resume a database's fetch from, if the last one stopped early
+    /// less than an hour ago
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_sync_checkpoint(&self, database_id: &str) -> Result<Option<String>> {
+        let checkpoint: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT cursor, updated_at FROM sync_checkpoints WHERE database_id = ?1",
+                [database_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((cursor, updated_at)) = checkpoint else {
+            return Ok(None);
+        };
+        let age = Utc::now() - parse_datetime(&updated_at)?;
+        Ok((age < Duration::seconds(SYNC_CHECKPOINT_MAX_AGE_SECS)).then_some(cursor))
+    }
+
+    /// Forget a database's checkpoint once a fetch got through every page
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn clear_sync_checkpoint(&self, database_id: &str) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM sync_checkpoints WHERE database_id = ?1",
+            [database_id],
+        )?;
+        Ok(())
+    }
+
+    /// Record the latest page edit seen by a complete sync of a database
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub fn save_sync_watermark(
+        &self,
+        database_id: &str,
+        last_edited: DateTime<Utc>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO sync_watermarks (database_id, last_edited_time, updated_at)
+             VALUES (?1, ?2, ?3)",
+            params![database_id, last_edited.to_rfc3339(), Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Latest page edit seen by the last complete sync of a database
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn get_sync_watermark(&self, database_id: &str) -> Result<Option<DateTime<Utc>>> {
+        let last_edited: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT last_edited_time FROM sync_watermarks WHERE database_id = ?1",
+                [database_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(last_edited.as_deref().map(parse_datetime).transpose()?)
+    }
+}
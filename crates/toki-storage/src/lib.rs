@@ -3,10 +3,13 @@ pub mod encryption;
 pub mod migrations;
 pub mod models;
 
-pub use db::{Database, IssueTimeStats};
+pub use db::{Database, EmbeddingStats, IssueTimeStats, ProjectMatch};
 pub use encryption::{default_key_path, generate_key, load_key_from_file, save_key_to_file};
 pub use models::{
-    Activity, ActivityContext, ActivitySpan, ActivitySpanContext, AiConfig, AiProvider, Category,
-    ClassificationRule, ClaudeSession, Complexity, DailySummary, IntegrationConfig, IssueCandidate,
-    PatternType, Project, ProjectSummary, Session, Settings, TimeBlock, TimeBlockSource, WorkItem,
+    Activity, ActivityContext, ActivitySpan, ActivitySpanContext, AiConfig, AiProvider,
+    BlockHistoryEntry, CalendarOverlapPolicy, Category, ClassificationRule, ClaudeSession,
+    Complexity, DailySummary, DurationFormat, IntegrationConfig, IntegrationDebugLog,
+    IssueCandidate, NotificationSettings, PatternType, Project, ProjectSummary, RankedSuggestion,
+    SelfTrackingPolicy, Session, Settings, TimeBlock, TimeBlockSource, TrackingTimezone, WorkHours,
+    WorkItem,
 };
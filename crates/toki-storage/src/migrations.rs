@@ -46,6 +46,9 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
         )",
         [],
     )?;
+    add_column_if_missing(conn, "sessions", "discarded", "INTEGER NOT NULL DEFAULT 0")?;
+    // Tracking timezone when the session was captured (NULL for older sessions)
+    add_column_if_missing(conn, "sessions", "timezone", "TEXT")?;
 
     // Settings table - user preferences and privacy controls
     conn.execute(
@@ -68,6 +71,38 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
         ("capture_window_title", "INTEGER DEFAULT 0"),
         ("capture_browser_url", "INTEGER DEFAULT 0"),
         ("url_whitelist", "TEXT DEFAULT '[]'"),
+        ("span_context_cap", "INTEGER DEFAULT 50"),
+        ("name_match_algorithm", "TEXT DEFAULT 'jaccard'"),
+        ("name_match_threshold", "REAL DEFAULT 0.6"),
+        ("duration_format", "TEXT DEFAULT 'hm'"),
+        ("notifications", "TEXT DEFAULT '{}'"),
+        ("max_session_seconds", "INTEGER DEFAULT 43200"),
+        ("integration_debug_log", "TEXT DEFAULT 'off'"),
+        ("auto_attach_min_confidence", "REAL DEFAULT 0.6"),
+        ("backfill_min_confidence", "REAL DEFAULT 0.85"),
+        ("billable_categories", "TEXT DEFAULT '[]'"),
+        ("calendar_overlap_policy", "TEXT DEFAULT 'prefer-activity'"),
+        ("work_hours", "TEXT DEFAULT '09-18'"),
+        ("min_activity_seconds", "INTEGER DEFAULT 1"),
+        ("timezone", "TEXT DEFAULT 'UTC'"),
+        ("focus_min_seconds", "INTEGER DEFAULT 1500"),
+        ("require_focused_display", "INTEGER DEFAULT 0"),
+        ("sync_min_entry_seconds", "INTEGER DEFAULT 0"),
+        ("embeddings_enabled", "INTEGER DEFAULT 1"),
+        ("suggestion_cache_size", "INTEGER DEFAULT 20"),
+        ("suggestion_cache_ttl_seconds", "INTEGER DEFAULT 600"),
+        ("self_tracking", "TEXT DEFAULT 'categorize'"),
+        ("commit_grace_seconds", "INTEGER DEFAULT 3600"),
+        ("embedding_refresh_hours", "INTEGER DEFAULT 24"),
+        ("issue_id_pattern", "TEXT"),
+        ("candidate_warmup_threads", "INTEGER DEFAULT 4"),
+        ("slack_webhook_url", "TEXT"),
+        ("analyzer_min_block_minutes", "INTEGER DEFAULT 5"),
+        ("analyzer_merge_gap_minutes", "INTEGER DEFAULT 10"),
+        ("capture_terminal_commands", "INTEGER DEFAULT 0"),
+        ("terminal_history_path", "TEXT"),
+        ("day_boundary_hour", "INTEGER DEFAULT 0"),
+        ("energy_lookback_weeks", "INTEGER DEFAULT 4"),
     ];
 
     for (column_name, column_type) in columns_to_add {
@@ -186,6 +221,9 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    // Track blocks already written to the local worklog (independent of PM sync)
+    add_column_if_missing(conn, "time_blocks", "logged_locally", "INTEGER NOT NULL DEFAULT 0")?;
+
     // Project time table - accumulates time per project per day
     // This supports multi-window workflows where user switches between projects frequently
     conn.execute(
@@ -325,6 +363,10 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
         conn.execute("ALTER TABLE activity_spans ADD COLUMN context TEXT", [])?;
         log::info!("Added context column to activity_spans table");
     }
+    // How sure the classifier was of the span's category (NULL when unknown)
+    add_column_if_missing(conn, "activity_spans", "category_confidence", "REAL")?;
+    // Work pattern detected when the span finished (NULL until detected)
+    add_column_if_missing(conn, "activity_spans", "work_pattern", "TEXT")?;
 
     // Add PM columns to projects if they don't exist
     let pm_system_exists: Result<i32, rusqlite::Error> = conn.query_row(
@@ -479,6 +521,12 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
         log::info!("Added scope tracking columns to issue_candidates table");
     }
 
+    // When the issue transitioned to a done state in the PM system
+    add_column_if_missing(conn, "issue_candidates", "done_at", "TEXT")?;
+
+    // Hash of the text the stored embedding was computed from
+    add_column_if_missing(conn, "issue_candidates", "embedding_hash", "TEXT")?;
+
     // Synced issues table - tracks Notion pages synced to GitHub/GitLab
     conn.execute(
         "CREATE TABLE IF NOT EXISTS synced_issues (
@@ -607,10 +655,126 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    // App display names table - user overrides for friendly application names
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS app_display_names (
+            bundle_id TEXT PRIMARY KEY,
+            display_name TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Per-project billable categories, overriding settings.billable_categories
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS project_billable_categories (
+            project_id TEXT PRIMARY KEY,
+            categories TEXT NOT NULL DEFAULT '[]'
+        )",
+        [],
+    )?;
+
+    // Per-project expected working hours, overriding settings.work_hours
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS project_work_hours (
+            project_id TEXT PRIMARY KEY,
+            hours TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Ranked `toki next` suggestions per project and constraint set, reused until stale
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS suggestion_rankings (
+            project_id TEXT NOT NULL,
+            constraints TEXT NOT NULL,
+            ranking TEXT NOT NULL DEFAULT '[]',
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (project_id, constraints)
+        )",
+        [],
+    )?;
+
+    // Completed embedding builds, so automatic rebuilds can be skipped while fresh
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS embedding_builds (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            built_at TEXT NOT NULL,
+            embeddings_computed INTEGER NOT NULL DEFAULT 0
+        )",
+        [],
+    )?;
+
+    // Audit trail of review changes to time blocks, in the order they were made
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS block_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            block_id TEXT NOT NULL,
+            action TEXT NOT NULL,
+            before TEXT,
+            after TEXT,
+            created_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_block_history_block ON block_history(block_id)",
+        [],
+    )?;
+
+    // Where an interrupted paginated fetch stopped, per PM database
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sync_checkpoints (
+            database_id TEXT PRIMARY KEY,
+            cursor TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Latest page edit seen by the last complete sync, per PM database
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sync_watermarks (
+            database_id TEXT PRIMARY KEY,
+            last_edited_time TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
     log::info!("Database schema initialized");
     Ok(())
 }
 
+/// Add a column to an existing table unless it is already present
+///
+/// `SQLite` has no `ADD COLUMN IF NOT EXISTS`, so we check `pragma_table_info` first.
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column_name: &str,
+    column_type: &str,
+) -> Result<()> {
+    let column_exists: i32 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM pragma_table_info('{table}') WHERE name='{column_name}'"),
+        [],
+        |row| row.get(0),
+    )?;
+
+    if column_exists == 0 {
+        conn.execute(
+            &format!("ALTER TABLE {table} ADD COLUMN {column_name} {column_type}"),
+            [],
+        )?;
+        log::info!("Added {column_name} column to {table} table");
+    }
+
+    Ok(())
+}
+
+/// Pattern of the former default "Communication" category
+const LEGACY_COMMUNICATION_PATTERN: &str =
+    "(?i)(slack|discord|teams|zoom|skype|telegram|whatsapp|messages|mail)";
+
 /// Insert default categories for common development tools
 ///
 /// # Errors
@@ -654,9 +818,15 @@ pub fn insert_default_categories(conn: &Connection) -> Result<()> {
             Some("Web browsers (general)"),
         ),
         (
-            "Communication",
-            "(?i)(slack|discord|teams|zoom|skype|telegram|whatsapp|messages|mail)",
-            Some("Communication tools"),
+            "Meeting",
+            // Calls: desktop apps, plus Google Meet by URL, page title, or meeting code
+            "(?i)(zoom|teams|webex|skype|facetime|gotomeeting|whereby|meet\\.google|google\\s+meet|meet\\s+[-\\x{2013}]\\s+[a-z]{3}-[a-z]{4}-[a-z]{3})",
+            Some("Video calls and meetings (synchronous)"),
+        ),
+        (
+            "AsyncComms",
+            "(?i)(slack|discord|telegram|whatsapp|messages|mail|outlook|thunderbird)",
+            Some("Chat and email (asynchronous)"),
         ),
         (
             "Documentation",
@@ -701,6 +871,13 @@ pub fn insert_default_categories(conn: &Connection) -> Result<()> {
         }
     }
 
+    // "Communication" was split into Meeting and AsyncComms; drop the old default
+    // unless the user changed its pattern
+    conn.execute(
+        "DELETE FROM categories WHERE name = 'Communication' AND pattern = ?1",
+        [LEGACY_COMMUNICATION_PATTERN],
+    )?;
+
     log::info!("Default categories inserted");
     Ok(())
 }
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -49,6 +49,67 @@ pub struct Settings {
     pub capture_window_title: bool,
     pub capture_browser_url: bool,
     pub url_whitelist: Vec<String>, // Domains like "plane.so", "github.com"
+    pub span_context_cap: u32,      // Max entries kept per span-context list
+    // Project auto-link name matching
+    pub name_match_algorithm: String, // "jaccard", "levenshtein", or "token-set"
+    pub name_match_threshold: f32,
+    pub duration_format: DurationFormat,
+    pub notifications: NotificationSettings,
+    pub max_session_seconds: u32, // Sessions longer than this are split (0 = no limit)
+    pub integration_debug_log: IntegrationDebugLog,
+    // Issue auto-attach similarity floors (live activity vs. bulk backfill/import)
+    pub auto_attach_min_confidence: f32,
+    pub backfill_min_confidence: f32,
+    pub billable_categories: Vec<String>, // Categories billed to clients (per-project overrides apply)
+    pub calendar_overlap_policy: CalendarOverlapPolicy,
+    pub work_hours: WorkHours, // Expected hours for off-hours detection (per-project overrides apply)
+    pub min_activity_seconds: u32, // Shorter activities merge into the previous record
+    pub timezone: TrackingTimezone, // Day boundaries for reports; recorded on each session
+    pub focus_min_seconds: u32, // Shorter timeline blocks count as fragmented
+    pub require_focused_display: bool, // Ignore windows on a display the user is not focused on
+    pub sync_min_entry_seconds: u32, // Per-issue time below this is not synced (0 = no floor)
+    pub embeddings_enabled: bool, // Load the local embedding model for issue matching
+    pub suggestion_cache_size: u32, // Top issues kept per project in the `next` ranking cache
+    pub suggestion_cache_ttl_seconds: u32, // How long a cached ranking is reused (0 = no cache)
+    pub self_tracking: SelfTrackingPolicy, // How time spent running toki itself is recorded
+    pub commit_grace_seconds: u32, // A commit's issue also covers spans this far before it (0 = off)
+    pub embedding_refresh_hours: u32, // Automatic embedding rebuilds wait this long after the last one
+    pub issue_id_pattern: Option<String>, // Regex for issue IDs in branches/commits/URLs (None = PROJ-123 style)
+    pub candidate_warmup_threads: u32, // Projects ranked at once by the MCP server's issue candidate cache (0 = off)
+    pub slack_webhook_url: Option<String>, // Incoming webhook `toki standup --post` sends to
+    pub analyzer_min_block_minutes: u32, // Shorter suggested blocks are dropped from `toki review`
+    pub analyzer_merge_gap_minutes: u32, // Segments closer than this join one suggested block
+    pub capture_terminal_commands: bool, // Record shell commands run while a terminal is focused
+    pub terminal_history_path: Option<String>, // Shell history to tail (None = $HISTFILE, zsh, then bash)
+    pub day_boundary_hour: u32, // Local hour a work day starts; earlier activity counts for the day before
+    pub energy_lookback_weeks: u32, // Past weeks `toki next` reads for hour-of-day energy (0 = off)
+}
+
+/// Opt-in daemon notifications (desktop and/or outgoing webhook)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+    pub desktop: bool,
+    pub webhook_url: Option<String>,
+    pub focus_hours: u32,            // Continuous focus before a break nudge (0 = off)
+    pub idle_minutes: u32,           // Idle time inside a session before a nudge (0 = off)
+    pub review_time: Option<String>, // Local "HH:MM" for the end-of-day review reminder
+    pub cooldown_minutes: u32,       // Minimum gap between two notifications of one kind
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            desktop: true,
+            webhook_url: None,
+            focus_hours: 3,
+            idle_minutes: 30,
+            review_time: Some("17:30".to_string()),
+            cooldown_minutes: 60,
+        }
+    }
 }
 
 impl Activity {
@@ -116,6 +177,55 @@ pub struct ActivitySpan {
     pub context: Option<ActivitySpanContext>,
 }
 
+/// Default number of entries kept in each span-context list
+pub const DEFAULT_SPAN_CONTEXT_CAP: u32 = 50;
+
+/// Default maximum session length before the daemon starts a new session (12 hours)
+pub const DEFAULT_MAX_SESSION_SECONDS: u32 = 12 * 3600;
+
+/// Default floor below which an activity is merged into the one before it
+///
+/// Only drops zero-length samples; spans are unaffected.
+pub const DEFAULT_MIN_ACTIVITY_SECONDS: u32 = 1;
+
+/// Default shortest block shown as focus in the timeline (one Pomodoro)
+pub const DEFAULT_FOCUS_MIN_SECONDS: u32 = 25 * 60;
+
+/// Default shortest block `toki review` suggests
+pub const DEFAULT_ANALYZER_MIN_BLOCK_MINUTES: u32 = 5;
+
+/// Default gap across which `toki review` keeps similar work in one block
+pub const DEFAULT_ANALYZER_MERGE_GAP_MINUTES: u32 = 10;
+
+/// Default number of ranked issues cached per project for `toki next`
+pub const DEFAULT_SUGGESTION_CACHE_SIZE: u32 = 20;
+
+/// Default time a cached `toki next` ranking is reused
+pub const DEFAULT_SUGGESTION_CACHE_TTL_SECONDS: u32 = 10 * 60;
+
+/// Default weeks of activity `toki next` reads to tell high- from low-energy hours
+pub const DEFAULT_ENERGY_LOOKBACK_WEEKS: u32 = 4;
+
+/// Default window before a commit whose spans inherit the commit's issue
+///
+/// Commits usually land after the work they describe.
+pub const DEFAULT_COMMIT_GRACE_SECONDS: u32 = 60 * 60;
+
+/// Default age before issue embeddings are rebuilt without `--force`
+pub const DEFAULT_EMBEDDING_REFRESH_HOURS: u32 = 24;
+
+/// Default number of projects whose issue candidates are ranked at once during warmup
+pub const DEFAULT_CANDIDATE_WARMUP_THREADS: u32 = 4;
+
+/// Default similarity needed to attach an issue to live activity
+pub const DEFAULT_AUTO_ATTACH_MIN_CONFIDENCE: f32 = 0.6;
+
+/// Default similarity needed to attach an issue during bulk operations
+///
+/// Higher than the live threshold: a bad match in a batch is repeated across
+/// many spans before anyone reviews it.
+pub const DEFAULT_BACKFILL_MIN_CONFIDENCE: f32 = 0.85;
+
 /// Rich context for activity span - enables AI analysis and flexible issue association
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ActivitySpanContext {
@@ -124,8 +234,14 @@ pub struct ActivitySpanContext {
     pub git_commits: Vec<String>, // Commit messages during this span
     pub git_branch: Option<String>, // Current branch
     pub browser_urls: Vec<String>, // Visited PM/doc URLs
+    #[serde(default)]
+    pub terminal_commands: Vec<String>, // Shell commands run during this span
     pub tags: Vec<String>,        // Manual tags from user
     pub notes: Option<String>,    // Free-form notes
+    #[serde(default)]
+    pub remote: bool, // Span was spent inside a remote-desktop session
+    #[serde(default)]
+    pub display: Option<u32>, // Display the span's window was on (multi-monitor setups)
 }
 
 impl ActivitySpan {
@@ -158,6 +274,22 @@ impl ActivitySpan {
             .get_or_insert_with(ActivitySpanContext::default)
     }
 
+    /// Whether this span was spent inside a remote-desktop session
+    #[must_use]
+    pub fn is_remote(&self) -> bool {
+        self.context.as_ref().is_some_and(|c| c.remote)
+    }
+
+    /// Flag this span as remote work
+    pub fn mark_remote(&mut self) {
+        self.context_mut().remote = true;
+    }
+
+    /// Record the display the span's window was on
+    pub fn set_display(&mut self, display: u32) {
+        self.context_mut().display = Some(display);
+    }
+
     /// Add a work item association (can add multiple via context)
     pub fn add_work_item(&mut self, work_item_id: Uuid) {
         let ctx = self.context_mut();
@@ -232,6 +364,33 @@ impl ActivitySpan {
     }
 }
 
+impl ActivitySpanContext {
+    /// Bound `edited_files`, `git_commits`, `browser_urls` and `terminal_commands`
+    /// to the `cap` most recent distinct entries
+    ///
+    /// Duplicates collapse onto their latest occurrence, so re-editing the same
+    /// file does not push other files out of the list.
+    pub fn enforce_cap(&mut self, cap: usize) {
+        keep_recent_distinct(&mut self.edited_files, cap);
+        keep_recent_distinct(&mut self.git_commits, cap);
+        keep_recent_distinct(&mut self.browser_urls, cap);
+        keep_recent_distinct(&mut self.terminal_commands, cap);
+    }
+}
+
+/// Keep the last `cap` distinct entries of `list`, preserving their order
+fn keep_recent_distinct(list: &mut Vec<String>, cap: usize) {
+    let mut seen = std::collections::HashSet::new();
+    let mut kept: Vec<String> = list
+        .drain(..)
+        .rev()
+        .filter(|entry| seen.insert(entry.clone()))
+        .take(cap)
+        .collect();
+    kept.reverse();
+    *list = kept;
+}
+
 impl Settings {
     #[must_use]
     pub fn default_settings() -> Self {
@@ -248,8 +407,67 @@ impl Settings {
                 "github.com".to_string(),
                 "jira.atlassian.com".to_string(),
             ],
+            span_context_cap: DEFAULT_SPAN_CONTEXT_CAP,
+            name_match_algorithm: "jaccard".to_string(),
+            name_match_threshold: 0.6,
+            duration_format: DurationFormat::default(),
+            notifications: NotificationSettings::default(),
+            max_session_seconds: DEFAULT_MAX_SESSION_SECONDS,
+            integration_debug_log: IntegrationDebugLog::default(),
+            auto_attach_min_confidence: DEFAULT_AUTO_ATTACH_MIN_CONFIDENCE,
+            backfill_min_confidence: DEFAULT_BACKFILL_MIN_CONFIDENCE,
+            billable_categories: Vec::new(),
+            calendar_overlap_policy: CalendarOverlapPolicy::default(),
+            work_hours: WorkHours::default(),
+            min_activity_seconds: DEFAULT_MIN_ACTIVITY_SECONDS,
+            timezone: TrackingTimezone::default(),
+            focus_min_seconds: DEFAULT_FOCUS_MIN_SECONDS,
+            require_focused_display: false,
+            sync_min_entry_seconds: 0,
+            embeddings_enabled: true,
+            suggestion_cache_size: DEFAULT_SUGGESTION_CACHE_SIZE,
+            suggestion_cache_ttl_seconds: DEFAULT_SUGGESTION_CACHE_TTL_SECONDS,
+            self_tracking: SelfTrackingPolicy::default(),
+            commit_grace_seconds: DEFAULT_COMMIT_GRACE_SECONDS,
+            embedding_refresh_hours: DEFAULT_EMBEDDING_REFRESH_HOURS,
+            issue_id_pattern: None,
+            candidate_warmup_threads: DEFAULT_CANDIDATE_WARMUP_THREADS,
+            slack_webhook_url: None,
+            analyzer_min_block_minutes: DEFAULT_ANALYZER_MIN_BLOCK_MINUTES,
+            analyzer_merge_gap_minutes: DEFAULT_ANALYZER_MERGE_GAP_MINUTES,
+            capture_terminal_commands: false,
+            terminal_history_path: None,
+            day_boundary_hour: 0,
+            energy_lookback_weeks: DEFAULT_ENERGY_LOOKBACK_WEEKS,
         }
     }
+
+    /// Similarity floor for attaching issues in bulk (backfill, import, confirm-all)
+    ///
+    /// Never looser than the live threshold, so lowering `auto_attach_min_confidence`
+    /// cannot loosen batch attribution.
+    #[must_use]
+    pub fn bulk_attach_min_confidence(&self) -> f32 {
+        self.backfill_min_confidence
+            .max(self.auto_attach_min_confidence)
+    }
+
+    /// Work day `time` belongs to, in the configured timezone and day boundary
+    ///
+    /// With `day_boundary_hour = 4`, activity at 01:00 counts for the day before.
+    #[must_use]
+    pub fn work_date(&self, time: DateTime<Utc>) -> NaiveDate {
+        self.timezone.work_date(time, self.day_boundary_hour)
+    }
+
+    /// UTC start and end of a work day in the configured timezone and day boundary
+    #[must_use]
+    pub fn work_day_range(&self, date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+        let start = self
+            .timezone
+            .start_of_work_day(date, self.day_boundary_hour);
+        (start, start + chrono::Duration::days(1))
+    }
 }
 
 impl Default for Settings {
@@ -303,6 +521,372 @@ pub struct ActivityContext {
     pub window_titles: Vec<String>,  // Recent window titles
 }
 
+// ============================================================================
+// Duration Display
+// ============================================================================
+
+/// How durations are rendered in reports, summaries and insights
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum DurationFormat {
+    /// `1h 30m`
+    #[default]
+    HoursMinutes,
+    /// `1.5h`
+    DecimalHours,
+    /// `90m`
+    Minutes,
+    /// `01:30`
+    Colon,
+}
+
+impl DurationFormat {
+    /// Setting value for this format
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::HoursMinutes => "hm",
+            Self::DecimalHours => "decimal-hours",
+            Self::Minutes => "minutes",
+            Self::Colon => "colon",
+        }
+    }
+
+    /// Render a duration in this format
+    #[must_use]
+    pub fn format(self, seconds: u32) -> String {
+        let hours = seconds / 3600;
+        let minutes = (seconds % 3600) / 60;
+        match self {
+            Self::HoursMinutes => match (hours, minutes) {
+                (0, 0) if seconds > 0 => format!("{seconds}s"),
+                (0, m) => format!("{m}m"),
+                (h, 0) => format!("{h}h"),
+                (h, m) => format!("{h}h {m}m"),
+            },
+            Self::DecimalHours => format!("{:.1}h", f64::from(seconds) / 3600.0),
+            Self::Minutes => format!("{}m", seconds / 60),
+            Self::Colon => format!("{hours:02}:{minutes:02}"),
+        }
+    }
+}
+
+impl std::fmt::Display for DurationFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for DurationFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "hm" | "hours-minutes" => Ok(Self::HoursMinutes),
+            "decimal-hours" | "decimal" => Ok(Self::DecimalHours),
+            "minutes" | "m" => Ok(Self::Minutes),
+            "colon" | "hh:mm" => Ok(Self::Colon),
+            _ => Err(format!(
+                "Unknown duration format: {s}. Use: hm, decimal-hours, minutes, colon"
+            )),
+        }
+    }
+}
+
+// ============================================================================
+// Integration Debug Log
+// ============================================================================
+
+/// How much of each integration API request is written to the debug log
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum IntegrationDebugLog {
+    /// No request log
+    #[default]
+    Off,
+    /// Method, URL, status and duration
+    Requests,
+    /// Requests plus redacted headers and bodies
+    Bodies,
+}
+
+impl IntegrationDebugLog {
+    /// Setting value for this level
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Requests => "requests",
+            Self::Bodies => "bodies",
+        }
+    }
+}
+
+impl std::fmt::Display for IntegrationDebugLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for IntegrationDebugLog {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" | "false" | "0" => Ok(Self::Off),
+            "requests" | "on" | "true" | "1" => Ok(Self::Requests),
+            "bodies" | "verbose" => Ok(Self::Bodies),
+            _ => Err(format!(
+                "Unknown debug log level: {s}. Use: off, requests, bodies"
+            )),
+        }
+    }
+}
+
+// ============================================================================
+// Calendar Overlap
+// ============================================================================
+
+/// Who gets the time when tracked activity overlaps a calendar meeting
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum CalendarOverlapPolicy {
+    /// Keep the detected activity; actual work trumps a scheduled meeting
+    #[default]
+    PreferActivity,
+    /// Attribute the overlapping time to the meeting
+    PreferCalendar,
+    /// Divide the overlapping time evenly between activity and meeting
+    Split,
+}
+
+impl CalendarOverlapPolicy {
+    /// Setting value for this policy
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PreferActivity => "prefer-activity",
+            Self::PreferCalendar => "prefer-calendar",
+            Self::Split => "split",
+        }
+    }
+}
+
+impl std::fmt::Display for CalendarOverlapPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for CalendarOverlapPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "prefer-activity" | "activity" => Ok(Self::PreferActivity),
+            "prefer-calendar" | "calendar" | "meeting" => Ok(Self::PreferCalendar),
+            "split" => Ok(Self::Split),
+            _ => Err(format!(
+                "Unknown overlap policy: {s}. Use: prefer-activity, prefer-calendar, split"
+            )),
+        }
+    }
+}
+
+// ============================================================================
+// Self Tracking
+// ============================================================================
+
+/// How time spent running toki itself (reports, review) is recorded
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SelfTrackingPolicy {
+    /// Record it like any other activity
+    Track,
+    /// Record it under its own category, apart from productive work
+    #[default]
+    Categorize,
+    /// Do not record it
+    Exclude,
+}
+
+impl SelfTrackingPolicy {
+    /// Setting value for this policy
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Track => "track",
+            Self::Categorize => "categorize",
+            Self::Exclude => "exclude",
+        }
+    }
+}
+
+impl std::fmt::Display for SelfTrackingPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for SelfTrackingPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "track" | "off" => Ok(Self::Track),
+            "categorize" | "category" => Ok(Self::Categorize),
+            "exclude" | "ignore" => Ok(Self::Exclude),
+            _ => Err(format!(
+                "Unknown self tracking policy: {s}. Use: track, categorize, exclude"
+            )),
+        }
+    }
+}
+
+// ============================================================================
+// Work Hours
+// ============================================================================
+
+/// Expected working hours as whole hours of the day (UTC, like session tracking)
+///
+/// A start after the end describes an overnight shift, e.g. `22-06`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkHours {
+    pub start_hour: u32,
+    pub end_hour: u32,
+}
+
+impl Default for WorkHours {
+    fn default() -> Self {
+        Self {
+            start_hour: 9,
+            end_hour: 18,
+        }
+    }
+}
+
+impl WorkHours {
+    /// Whether `hour` (0-23) falls inside these working hours
+    #[must_use]
+    pub fn contains(self, hour: u32) -> bool {
+        if self.start_hour <= self.end_hour {
+            hour >= self.start_hour && hour < self.end_hour
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+}
+
+impl std::fmt::Display for WorkHours {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}-{:02}", self.start_hour, self.end_hour)
+    }
+}
+
+impl std::str::FromStr for WorkHours {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || format!("Invalid work hours: {s}. Use START-END in hours, e.g. 09-18 or 22-06");
+        let (start, end) = s.trim().split_once('-').ok_or_else(invalid)?;
+        let start_hour: u32 = start.trim().parse().map_err(|_| invalid())?;
+        let end_hour: u32 = end.trim().parse().map_err(|_| invalid())?;
+        if start_hour > 23 || end_hour > 24 || start_hour == end_hour {
+            return Err(invalid());
+        }
+        Ok(Self {
+            start_hour,
+            end_hour,
+        })
+    }
+}
+
+// ============================================================================
+// Tracking Timezone
+// ============================================================================
+
+/// Fixed UTC offset used to split tracked time into days, e.g. `+08:00`
+///
+/// Timestamps are always stored in UTC; each session also records the offset
+/// configured when it was captured, so reports can detect a later change.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub struct TrackingTimezone {
+    pub offset_minutes: i32,
+}
+
+impl TrackingTimezone {
+    /// Midnight at the start of the day containing `time`, in this timezone
+    #[must_use]
+    pub fn start_of_day(self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let offset = chrono::Duration::minutes(i64::from(self.offset_minutes));
+        let local_date = (time + offset).date_naive();
+        local_date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc() - offset
+    }
+
+    /// Date of the work day containing `time`, where days start at
+    /// `boundary_hour` local time instead of midnight
+    #[must_use]
+    pub fn work_date(self, time: DateTime<Utc>, boundary_hour: u32) -> NaiveDate {
+        let shift = chrono::Duration::minutes(i64::from(self.offset_minutes))
+            - chrono::Duration::hours(i64::from(boundary_hour));
+        (time + shift).date_naive()
+    }
+
+    /// Start of the work day on `date`: `boundary_hour` local time on that date
+    #[must_use]
+    pub fn start_of_work_day(self, date: NaiveDate, boundary_hour: u32) -> DateTime<Utc> {
+        let offset = chrono::Duration::minutes(i64::from(self.offset_minutes));
+        date.and_hms_opt(boundary_hour, 0, 0)
+            .unwrap_or_default()
+            .and_utc()
+            - offset
+    }
+}
+
+impl std::fmt::Display for TrackingTimezone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.offset_minutes == 0 {
+            return write!(f, "UTC");
+        }
+        let sign = if self.offset_minutes < 0 { '-' } else { '+' };
+        let minutes = self.offset_minutes.unsigned_abs();
+        write!(f, "{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+    }
+}
+
+impl std::str::FromStr for TrackingTimezone {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || format!("Invalid timezone: {s}. Use UTC or an offset like +08:00 or -05:30");
+        let trimmed = s.trim();
+        if trimmed.eq_ignore_ascii_case("utc") || trimmed.eq_ignore_ascii_case("z") {
+            return Ok(Self::default());
+        }
+
+        let offset = trimmed
+            .strip_prefix("UTC")
+            .or_else(|| trimmed.strip_prefix("utc"))
+            .unwrap_or(trimmed);
+        let (sign, rest) = match offset.chars().next() {
+            Some('+') => (1, &offset[1..]),
+            Some('-') => (-1, &offset[1..]),
+            _ => return Err(invalid()),
+        };
+        let (hours, minutes) = rest.split_once(':').unwrap_or((rest, "0"));
+        let hours: i32 = hours.parse().map_err(|_| invalid())?;
+        let minutes: i32 = minutes.parse().map_err(|_| invalid())?;
+        if hours > 14 || !(0..60).contains(&minutes) {
+            return Err(invalid());
+        }
+        Ok(Self {
+            offset_minutes: sign * (hours * 60 + minutes),
+        })
+    }
+}
+
 // ============================================================================
 // Issue Complexity
 // ============================================================================
@@ -400,6 +984,8 @@ pub struct IssueCandidate {
     // Scope tracking - stored estimates for comparison with actual time
     pub estimated_seconds: Option<u32>,
     pub estimate_source: Option<String>, // "ai", "manual", "pm_system"
+    // When the issue moved to a done state (from PM activity history, if known)
+    pub done_at: Option<DateTime<Utc>>,
 }
 
 impl IssueCandidate {
@@ -428,23 +1014,42 @@ impl IssueCandidate {
             complexity_reason: None,
             estimated_seconds: None,
             estimate_source: None,
+            done_at: None,
         }
     }
 
     /// Generate text for embedding computation
     #[must_use]
     pub fn embedding_text(&self) -> String {
+        self.embedding_input(true)
+    }
+
+    /// Generate text for embedding computation, optionally leaving out labels
+    ///
+    /// Status, assignee, and other metadata are never part of the input, so
+    /// changing them does not affect the vector.
+    #[must_use]
+    pub fn embedding_input(&self, include_labels: bool) -> String {
         let mut parts = vec![self.external_id.clone(), self.title.clone()];
         if let Some(desc) = &self.description {
             parts.push(desc.clone());
         }
-        if !self.labels.is_empty() {
+        if include_labels && !self.labels.is_empty() {
             parts.push(self.labels.join(" "));
         }
         parts.join("\n")
     }
 }
 
+/// One issue in a cached `toki next` ranking, best first
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankedSuggestion {
+    pub issue_candidate_id: Uuid,
+    pub score: f32,
+    pub reasons: Vec<String>,
+    pub estimated_seconds: u32,
+}
+
 /// Work item - represents a task/issue from PM systems (optional metadata)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkItem {
@@ -541,6 +1146,31 @@ impl TimeBlock {
             created_at: Utc::now(),
         }
     }
+
+    /// One-line description of the block, as kept in its edit history
+    #[must_use]
+    pub fn summary(&self) -> String {
+        format!(
+            "{} - {}, {} issue(s){}: {}",
+            self.start_time.format("%Y-%m-%d %H:%M"),
+            self.end_time.format("%H:%M"),
+            self.work_item_ids.len(),
+            if self.confirmed { ", confirmed" } else { "" },
+            self.description
+        )
+    }
+}
+
+/// A recorded change to a time block (edit, merge, split, or confirm)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockHistoryEntry {
+    pub block_id: Uuid,
+    pub action: String,
+    /// Block summary before the change
+    pub before: Option<String>,
+    /// Block summary after the change
+    pub after: Option<String>,
+    pub created_at: DateTime<Utc>,
 }
 
 /// Daily summary - for display and retroactive classification
@@ -1281,3 +1911,202 @@ impl AiConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_cap_keeps_most_recent_distinct_entries() {
+        let mut ctx = ActivitySpanContext {
+            edited_files: ["a.rs", "b.rs", "a.rs", "c.rs", "d.rs", "c.rs"]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            git_commits: vec!["fix".to_string(); 5],
+            ..Default::default()
+        };
+
+        ctx.enforce_cap(3);
+
+        assert_eq!(ctx.edited_files, vec!["a.rs", "d.rs", "c.rs"]);
+        assert_eq!(ctx.git_commits, vec!["fix"]);
+    }
+
+    #[test]
+    fn test_duration_format_modes() {
+        let seconds = 5430; // 1h 30m 30s
+        assert_eq!(DurationFormat::HoursMinutes.format(seconds), "1h 30m");
+        assert_eq!(DurationFormat::DecimalHours.format(seconds), "1.5h");
+        assert_eq!(DurationFormat::Minutes.format(seconds), "90m");
+        assert_eq!(DurationFormat::Colon.format(seconds), "01:30");
+    }
+
+    #[test]
+    fn test_duration_format_round_trips_setting_value() {
+        for format in [
+            DurationFormat::HoursMinutes,
+            DurationFormat::DecimalHours,
+            DurationFormat::Minutes,
+            DurationFormat::Colon,
+        ] {
+            assert_eq!(format.as_str().parse::<DurationFormat>(), Ok(format));
+        }
+        assert!("fortnights".parse::<DurationFormat>().is_err());
+    }
+
+    #[test]
+    fn test_tracking_timezone_parses_offsets() {
+        for (input, minutes, shown) in [
+            ("UTC", 0, "UTC"),
+            ("+08:00", 480, "+08:00"),
+            ("UTC-05:30", -330, "-05:30"),
+            ("+9", 540, "+09:00"),
+        ] {
+            let tz: TrackingTimezone = input.parse().unwrap();
+            assert_eq!(tz.offset_minutes, minutes, "{input}");
+            assert_eq!(tz.to_string(), shown);
+            assert_eq!(shown.parse::<TrackingTimezone>().unwrap(), tz);
+        }
+        assert!("Asia/Taipei".parse::<TrackingTimezone>().is_err());
+        assert!("+15:00".parse::<TrackingTimezone>().is_err());
+
+        // 23:30 UTC is already the next day at +08:00
+        let tz: TrackingTimezone = "+08:00".parse().unwrap();
+        let time = DateTime::parse_from_rfc3339("2025-03-14T23:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            tz.start_of_day(time).to_rfc3339(),
+            "2025-03-14T16:00:00+00:00"
+        );
+    }
+
+    fn utc(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_work_date_switches_at_boundary_hour() {
+        let settings = Settings {
+            timezone: "+08:00".parse().unwrap(),
+            day_boundary_hour: 4,
+            ..Settings::default()
+        };
+        let march_14 = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        let march_15 = NaiveDate::from_ymd_opt(2025, 3, 15).unwrap();
+
+        // 01:00 and 03:59 local on the 15th still belong to the 14th
+        assert_eq!(settings.work_date(utc("2025-03-14T17:00:00Z")), march_14);
+        assert_eq!(settings.work_date(utc("2025-03-14T19:59:59Z")), march_14);
+        assert_eq!(settings.work_date(utc("2025-03-14T20:00:00Z")), march_15);
+
+        let (start, end) = settings.work_day_range(march_14);
+        assert_eq!(start, utc("2025-03-13T20:00:00Z"));
+        assert_eq!(end, utc("2025-03-14T20:00:00Z"));
+
+        // Without a boundary, work days are calendar days
+        let midnight = Settings {
+            day_boundary_hour: 0,
+            ..settings
+        };
+        assert_eq!(midnight.work_date(utc("2025-03-14T17:00:00Z")), march_15);
+    }
+
+    #[test]
+    fn test_work_date_across_dst_change_follows_configured_offset() {
+        // Europe/Berlin moves from +01:00 to +02:00 at 01:00 UTC on 2025-03-30.
+        // The offset is fixed, so a 04:00 boundary lands an hour apart in UTC
+        // depending on which offset is configured.
+        let winter: TrackingTimezone = "+01:00".parse().unwrap();
+        let summer: TrackingTimezone = "+02:00".parse().unwrap();
+        let march_29 = NaiveDate::from_ymd_opt(2025, 3, 29).unwrap();
+        let march_30 = NaiveDate::from_ymd_opt(2025, 3, 30).unwrap();
+
+        assert_eq!(
+            winter.start_of_work_day(march_30, 4),
+            utc("2025-03-30T03:00:00Z")
+        );
+        assert_eq!(
+            summer.start_of_work_day(march_30, 4),
+            utc("2025-03-30T02:00:00Z")
+        );
+
+        // 02:30 UTC is 03:30 winter time (before the boundary) and
+        // 04:30 summer time (after it)
+        let time = utc("2025-03-30T02:30:00Z");
+        assert_eq!(winter.work_date(time, 4), march_29);
+        assert_eq!(summer.work_date(time, 4), march_30);
+
+        // Consecutive work days never overlap or leave a gap at the switch
+        assert_eq!(
+            winter.start_of_work_day(march_29, 4) + chrono::Duration::days(1),
+            winter.start_of_work_day(march_30, 4)
+        );
+    }
+
+    #[test]
+    fn test_calendar_overlap_policy_round_trips_setting_value() {
+        for policy in [
+            CalendarOverlapPolicy::PreferActivity,
+            CalendarOverlapPolicy::PreferCalendar,
+            CalendarOverlapPolicy::Split,
+        ] {
+            assert_eq!(policy.as_str().parse::<CalendarOverlapPolicy>(), Ok(policy));
+        }
+        assert_eq!(
+            CalendarOverlapPolicy::default(),
+            CalendarOverlapPolicy::PreferActivity
+        );
+        assert!("ignore".parse::<CalendarOverlapPolicy>().is_err());
+    }
+
+    #[test]
+    fn test_self_tracking_policy_round_trips_setting_value() {
+        for policy in [
+            SelfTrackingPolicy::Track,
+            SelfTrackingPolicy::Categorize,
+            SelfTrackingPolicy::Exclude,
+        ] {
+            assert_eq!(policy.as_str().parse::<SelfTrackingPolicy>(), Ok(policy));
+        }
+        assert_eq!(
+            SelfTrackingPolicy::default(),
+            SelfTrackingPolicy::Categorize
+        );
+        assert!("hide".parse::<SelfTrackingPolicy>().is_err());
+    }
+
+    #[test]
+    fn test_bulk_attach_uses_stricter_backfill_threshold() {
+        let mut settings = Settings {
+            auto_attach_min_confidence: 0.5,
+            ..Settings::default_settings()
+        };
+        assert!((settings.bulk_attach_min_confidence() - 0.85).abs() < f32::EPSILON);
+
+        // A backfill floor below the live threshold never loosens bulk attribution
+        settings.backfill_min_confidence = 0.4;
+        settings.auto_attach_min_confidence = 0.7;
+        assert!((settings.bulk_attach_min_confidence() - 0.7).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_context_cap_bounds_serialized_size() {
+        let mut ctx = ActivitySpanContext::default();
+        for i in 0..10_000 {
+            ctx.edited_files.push(format!("src/module_{i}.rs"));
+            ctx.git_commits.push(format!("commit {i}"));
+            ctx.browser_urls
+                .push(format!("https://example.com/issues/{i}"));
+        }
+
+        ctx.enforce_cap(DEFAULT_SPAN_CONTEXT_CAP as usize);
+
+        assert_eq!(ctx.edited_files.len(), 50);
+        assert_eq!(ctx.edited_files.last().unwrap(), "src/module_9999.rs");
+        assert!(serde_json::to_string(&ctx).unwrap().len() < 10_000);
+    }
+}